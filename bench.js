@@ -16,6 +16,7 @@ import {
   normalized_damerau_levenshtein,
   jaro,
   jaro_winkler,
+  normalize,
 } from './dist/index.js';
 
 // Test data sets
@@ -167,6 +168,32 @@ function benchmarkHotLoops() {
   return allResults;
 }
 
+// Candidate sets for normalization: uncased CJK skips case folding entirely
+const NORMALIZATION_CORPORA = {
+  cjk: ['東京都渋谷区神南一丁目', '北京市海淀区中关村大街', '大阪府大阪市北区梅田', '上海市浦东新区世纪大道'],
+  latin: ['Shibuya City Hall', 'Haidian District Office', 'Umeda Sky Building', 'Century Avenue'],
+};
+
+/**
+ * Normalization benchmarks - the default preset over cased and uncased corpora
+ */
+function benchmarkNormalization() {
+  console.log('\n═══════════════════════════════════════════════════════════════');
+  console.log('NORMALIZATION BENCHMARKS');
+  console.log('═══════════════════════════════════════════════════════════════\n');
+
+  for (const [corpus, inputs] of Object.entries(NORMALIZATION_CORPORA)) {
+    const result = benchmark(`normalize(${corpus}, 'default')`, () => {
+      for (const input of inputs) {
+        normalize(input, 'default');
+      }
+    });
+    console.log(
+      `  ${corpus.padEnd(8)} ${formatThroughput(result.throughput).padStart(15)}  ${formatTime(result.avgTime).padStart(12)}`,
+    );
+  }
+}
+
 /**
  * Summary comparison across functions
  */
@@ -211,6 +238,7 @@ async function main() {
 
   benchmarkColdInit();
   const results = benchmarkHotLoops();
+  benchmarkNormalization();
   printSummary(results);
 
   console.log('\n✅ Benchmarks complete\n');
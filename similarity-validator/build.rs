@@ -0,0 +1,40 @@
+//! Records the rapidfuzz-rs version resolved in Cargo.lock so generated fixtures
+//! carry the real `source_version` instead of a hard-coded one.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    // The validator is a workspace member, so the lockfile usually lives one level up
+    let lockfile = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file());
+
+    let version = lockfile
+        .as_ref()
+        .and_then(|path| {
+            println!("cargo:rerun-if-changed={}", path.display());
+            fs::read_to_string(path).ok()
+        })
+        .and_then(|lock| locked_version(&lock, "rapidfuzz"))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=RAPIDFUZZ_VERSION={}", version);
+}
+
+/// Version of `package` from a Cargo.lock's `[[package]]` entries
+fn locked_version(lock: &str, package: &str) -> Option<String> {
+    let name_line = format!("name = \"{}\"", package);
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == name_line {
+            let version = lines.next()?.trim().strip_prefix("version = ")?;
+            return Some(version.trim_matches('"').to_string());
+        }
+    }
+    None
+}
@@ -0,0 +1,86 @@
+#!/usr/bin/env python3
+"""Python rapidfuzz reference for `similarity-validator cross-check`.
+
+Reads one JSON request per line on stdin and answers each with one JSON line:
+
+    {"id": 1, "category": "levenshtein", "inputs": {"input_a": "kitten", "input_b": "sitting"}}
+    {"id": 1, "distance": 3, "score": 0.5714285714285714}
+
+Scores are normalized to 0-1. Requires `pip install rapidfuzz`.
+
+Usage:
+    similarity-validator cross-check 'tests/fixtures/**/*.yaml' \
+        --command "python3 similarity-validator/scripts/rapidfuzz_reference.py"
+"""
+
+import json
+import sys
+
+from rapidfuzz import fuzz
+from rapidfuzz.distance import (
+    OSA,
+    DamerauLevenshtein,
+    Indel,
+    Jaro,
+    JaroWinkler,
+    LCSseq,
+    Levenshtein,
+)
+
+# Fixture/unified metric names -> rapidfuzz distance modules
+DISTANCE_MODULES = {
+    "levenshtein": Levenshtein,
+    "damerau_osa": OSA,
+    "osa": OSA,
+    "damerau_unrestricted": DamerauLevenshtein,
+    "damerau_levenshtein": DamerauLevenshtein,
+    "indel": Indel,
+    "lcs_seq": LCSseq,
+}
+
+
+def score(metric, a, b):
+    if metric == "jaro":
+        return Jaro.similarity(a, b)
+    if metric == "jaro_winkler":
+        return JaroWinkler.similarity(a, b)
+    if metric == "ratio":
+        return fuzz.ratio(a, b) / 100.0
+    if metric in DISTANCE_MODULES:
+        return DISTANCE_MODULES[metric].normalized_similarity(a, b)
+    raise ValueError(f"Unknown similarity metric: {metric}")
+
+
+def distance(metric, a, b):
+    if metric in DISTANCE_MODULES:
+        return DISTANCE_MODULES[metric].distance(a, b)
+    raise ValueError(f"Unknown distance metric: {metric}")
+
+
+def answer(request):
+    category = request["category"]
+    inputs = request["inputs"]
+    a, b = inputs.get("input_a", ""), inputs.get("input_b", "")
+
+    if category == "unified_distance":
+        return {"distance": distance(inputs.get("metric", "levenshtein"), a, b)}
+    if category == "unified_score":
+        return {"score": score(inputs.get("metric", "jaro_winkler"), a, b)}
+    if category in ("jaro_winkler", "ratio"):
+        return {"score": score(category, a, b)}
+    return {"distance": distance(category, a, b), "score": score(category, a, b)}
+
+
+def main():
+    for line in sys.stdin:
+        request = json.loads(line)
+        try:
+            response = answer(request)
+        except Exception as e:  # reported per case, the process keeps serving
+            response = {"error": str(e)}
+        response["id"] = request.get("id")
+        print(json.dumps(response), flush=True)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,61 @@
+#!/usr/bin/env node
+/**
+ * Built wasm-bindgen module runner for `similarity-validator validate --target wasm`.
+ *
+ * Reads one JSON request per line on stdin and answers each with one JSON line:
+ *
+ *     {"id": 1, "call": "levenshtein", "args": ["kitten", "sitting"]}
+ *     {"id": 1, "value": 3}
+ *
+ * A request with `class` constructs that exported class from `new` and calls the `call`
+ * method on it: {"id": 2, "class": "Comparator", "new": ["ratio", "none", null, -1],
+ * "call": "score", "args": ["a", "b"]}. Thrown errors come back as {"id": 2, "error": "..."}.
+ * Typed arrays are returned as plain arrays and undefined as null.
+ *
+ * Usage (after `npm run build:wasm`):
+ *     similarity-validator validate tests/fixtures --target wasm \
+ *         --node-runner "node similarity-validator/scripts/wasm_runner.mjs pkg/web"
+ */
+
+import { readFileSync } from 'node:fs';
+import path from 'node:path';
+import { createInterface } from 'node:readline';
+import { pathToFileURL } from 'node:url';
+
+const pkgDir = path.resolve(process.argv[2] ?? 'pkg/web');
+const wasm = await import(pathToFileURL(path.join(pkgDir, 'string_metrics_wasm.js')).href);
+const bytes = readFileSync(path.join(pkgDir, 'string_metrics_wasm_bg.wasm'));
+wasm.initSync({ module: new WebAssembly.Module(bytes) });
+
+const plain = (value) => (ArrayBuffer.isView(value) ? Array.from(value) : (value ?? null));
+
+const run = (request) => {
+  const args = request.args ?? [];
+  if (request.class !== undefined) {
+    const instance = new wasm[request.class](...(request.new ?? []));
+    try {
+      return instance[request.call](...args);
+    } finally {
+      instance.free();
+    }
+  }
+  if (typeof wasm[request.call] !== 'function') {
+    throw new Error(`No wasm export named ${request.call}`);
+  }
+  return wasm[request.call](...args);
+};
+
+for await (const line of createInterface({ input: process.stdin })) {
+  let response;
+  try {
+    const request = JSON.parse(line);
+    try {
+      response = { id: request.id, value: plain(run(request)) };
+    } catch (error) {
+      response = { id: request.id, error: error instanceof Error ? error.message : String(error) };
+    }
+  } catch (error) {
+    response = { error: `invalid request: ${error.message}` };
+  }
+  process.stdout.write(`${JSON.stringify(response)}\n`);
+}
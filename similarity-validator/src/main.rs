@@ -1,16 +1,50 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
 use chrono::Utc;
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use glob::glob;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{EventKind, RecursiveMode, Watcher};
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::process;
-use unicode_normalization::UnicodeNormalization;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use string_metrics_core::{
+    alignment_steps, generate_variants, indel_editops, lcs_seq_matching_indices,
+    normalize_with_options, opcodes, try_normalize_with_locale, try_normalize_with_options, Anchor,
+    AnchorSide, Candidate, Comparator, DidYouMean, Digraphs, EditStep, EditTag, Expansions,
+    ExtractOptions, ExtractResult, FusionMethod, LengthFilter, MatchRange, MissingPolicy,
+    NormalizeOptions, PairOptions, QueryMode, RangeUnit, RankFusion, RecencyBoost,
+    ShortStringPolicy, SplitMix64, SubstringOptions, SuggestOptions, SuggestionExplanation,
+    TokenAggregation, Tokenizer, VariantOptions, WordDiffOp, WordDiffOptions, WordToken,
+    DEFAULT_NGRAM_SIZE,
+};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Set when a validate report goes to stdout (`--output -`); the summary then moves to stderr
+static SUMMARY_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for the validate summary, honoring SUMMARY_TO_STDERR
+macro_rules! summary {
+    ($($arg:tt)*) => {
+        if SUMMARY_TO_STDERR.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+/// rapidfuzz-rs version this binary links, detected from Cargo.lock by build.rs
+const RAPIDFUZZ_VERSION: &str = env!("RAPIDFUZZ_VERSION");
+
 #[derive(Parser)]
 #[command(name = "similarity-validator")]
 #[command(version = VERSION)]
@@ -23,9 +57,111 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Validate fixtures against rapidfuzz-rs canonical implementation
+    #[command(after_help = "\
+Exit codes (the most severe applies when several do):
+  0  all cases passed (or only known baseline failures)
+  1  case failures, fixed baseline entries or `only` markers
+  2  fixture parse or schema errors (and invalid command-line arguments)
+  3  IO and glob errors: invalid pattern, no files matched, unreadable fixture,
+     baseline or report")]
     Validate {
-        /// Glob pattern for fixture files to validate
+        /// Glob pattern or directory (searched recursively) for fixture files to validate
         pattern: String,
+
+        /// Only validate cases in this category (repeatable)
+        #[arg(long = "category", value_name = "NAME")]
+        categories: Vec<String>,
+
+        /// Only validate cases carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Only validate the case at this 0-based position in its file (counted across groups,
+        /// as in the JSON report's `case_index`)
+        #[arg(long, value_name = "N")]
+        case_index: Option<usize>,
+
+        /// Only validate cases whose description is exactly TEXT
+        #[arg(long, value_name = "TEXT")]
+        description: Option<String>,
+
+        /// Stop at the first failing case or unreadable fixture file
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Number of worker threads (defaults to the number of logical cores)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Absolute tolerance for score comparisons (fixture and case `tolerance` take precedence)
+        #[arg(long, value_name = "FLOAT", value_parser = parse_tolerance)]
+        epsilon: Option<f64>,
+
+        /// Keep running and re-validate fixture files as they change
+        #[arg(long, conflicts_with = "fail_fast")]
+        watch: bool,
+
+        /// Directory to resolve `$schema` references in (default: nearest `schemas/` above each fixture)
+        #[arg(long, value_name = "DIR")]
+        schema_dir: Option<PathBuf>,
+
+        /// Don't compare suggestion `normalized_value` fields (for normalization transitions)
+        #[arg(long)]
+        ignore_normalized: bool,
+
+        /// Accepted failures from `--save-baseline`: they are reported as known and don't fail the run
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Write the run's failures to FILE for use with `--baseline`
+        #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+        save_baseline: Option<PathBuf>,
+
+        /// Run cases marked `skip: true` as well
+        #[arg(long)]
+        include_skipped: bool,
+
+        /// Report format; `html`, `csv`, `json` and `junit` write to --output, `gh-annotations`
+        /// prints GitHub Actions `::error` lines; the summary still prints
+        #[arg(long, value_enum, default_value = "text")]
+        format: ReportFormat,
+
+        /// Don't emit GitHub Actions annotations when GITHUB_ACTIONS=true is set
+        #[arg(long)]
+        no_annotations: bool,
+
+        /// List every failure in full instead of grouping repeated errors under their category
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Fail a case whose computation takes longer than this (abandoned, not stopped; not `properties`)
+        #[arg(long, value_name = "MS", default_value_t = DEFAULT_TIMEOUT_MS, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout_ms: u64,
+
+        /// Compute cases natively or through the built wasm module (`wasm` filters out
+        /// categories without a wasm export)
+        #[arg(long, value_enum, default_value = "native")]
+        target: ValidateTarget,
+
+        /// Shell command serving the wasm module for `--target wasm`, one JSON line per call
+        /// (see scripts/wasm_runner.mjs)
+        #[arg(long, value_name = "CMD", default_value = DEFAULT_NODE_RUNNER)]
+        node_runner: String,
+
+        /// Report file for `--format html|csv|json|junit` (`-` writes to stdout and the summary to stderr)
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            required_if_eq_any([
+                ("format", "html"),
+                ("format", "csv"),
+                ("format", "json"),
+                ("format", "junit")
+            ]),
+            conflicts_with = "watch"
+        )]
+        output: Option<PathBuf>,
     },
     /// Generate fixture expected values using rapidfuzz-rs
     Generate {
@@ -41,10 +177,563 @@ enum Commands {
         #[arg(long)]
         overwrite: bool,
 
-        /// Dry run - show what would be generated without writing
+        /// Dry run - show a diff of the values that would change without writing (always exits 0)
         #[arg(long)]
         dry_run: bool,
+
+        /// Only generate cases in this category (repeatable)
+        #[arg(long = "category", value_name = "NAME")]
+        categories: Vec<String>,
+
+        /// Only generate cases carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Rewrite a format 1.x fixture as format 2.0 instead of generating values
+        #[arg(long, conflicts_with_all = ["overwrite", "categories", "tags"])]
+        migrate: bool,
+
+        /// Omit debugging fields (suggestion `normalized_value`) from the output
+        #[arg(long, conflicts_with = "migrate")]
+        no_debug_fields: bool,
+
+        /// Generate cases marked `skip: true` as well
+        #[arg(long, conflicts_with = "migrate")]
+        include_skipped: bool,
+
+        /// Round generated expected values to this many decimal places (recorded in the fixture)
+        #[arg(long, value_name = "DIGITS", value_parser = clap::value_parser!(u32).range(0..=17), conflicts_with = "migrate")]
+        precision: Option<u32>,
+    },
+    /// Check fixtures for hygiene problems (read-only)
+    Lint {
+        /// Glob pattern for fixture files to lint
+        pattern: String,
+
+        /// Exit non-zero on warnings as well as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Output format (json includes the duplicate groups, for scripting cleanups)
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Warn when a quadratic-time case's inputs total more than this many characters
+        #[arg(long, value_name = "CHARS", default_value_t = 20_000)]
+        max_input_length: usize,
+    },
+    /// Compare expected values between two fixture files, directories, or globs
+    Diff {
+        /// Baseline fixtures (file, directory, or glob)
+        old: String,
+
+        /// Updated fixtures (file, directory, or glob)
+        new: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Float changes smaller than this are reported as insignificant
+        #[arg(long, value_name = "FLOAT", value_parser = parse_tolerance)]
+        epsilon: Option<f64>,
+    },
+    /// Combine fixture files into one, merging categories and removing duplicate cases
+    Merge {
+        /// Fixture files to merge, in priority order
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Warn instead of failing on conflicting expected values (first occurrence wins)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Convert a fixture between YAML and JSON
+    Convert {
+        /// Input fixture file (.yaml, .yml, or .json)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output file path; its extension selects the format unless --to is given
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Output format (overrides the output extension)
+        #[arg(long, value_enum)]
+        to: Option<FixtureFormat>,
+
+        /// Re-parse the output and verify it matches the input before writing
+        #[arg(long)]
+        check: bool,
+    },
+    /// Report fixture coverage per category and library metrics with no fixtures
+    Stats {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Fail when a metric in the library's registry has fewer than --min-cases fixture cases
+    Coverage {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Cases each registered metric needs
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        min_cases: usize,
+
+        /// Registry names exempt from the minimum, one per line (`#` starts a comment)
+        #[arg(long, value_name = "FILE")]
+        allowlist: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Compute a distance and/or score for one pair (or tab-separated pairs on stdin)
+    Check {
+        /// Metric name as accepted by the unified distance/score categories
+        #[arg(short, long, default_value = "levenshtein")]
+        metric: String,
+
+        /// First string
+        #[arg(long = "a", required_unless_present = "stdin")]
+        a: Option<String>,
+
+        /// Second string
+        #[arg(long = "b", required_unless_present = "stdin")]
+        b: Option<String>,
+
+        /// Normalization preset to apply to both strings first
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Locale for case folding (with --preset)
+        #[arg(long, requires = "preset")]
+        locale: Option<String>,
+
+        /// Read `<a>\t<b>` pairs from stdin, one per line
+        #[arg(long, conflicts_with_all = ["a", "b"])]
+        stdin: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+    /// Show the edit operations and alignment behind a distance
+    Explain {
+        /// Source string
+        #[arg(long = "a")]
+        a: String,
+
+        /// Target string
+        #[arg(long = "b")]
+        b: String,
+
+        /// Distance metric
+        #[arg(short, long, default_value = "levenshtein", value_parser = ["levenshtein", "osa"])]
+        metric: String,
+    },
+    /// Generate a reproducible random corpus with expected values from rapidfuzz-rs
+    GenerateCorpus {
+        /// PRNG seed; the same seed always produces the same cases
+        #[arg(long)]
+        seed: u64,
+
+        /// Number of cases to generate
+        #[arg(long, default_value_t = 100)]
+        count: usize,
+
+        /// Output fixture file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Categories to generate (comma-separated; default: all pairwise metrics)
+        #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+        categories: Vec<String>,
+    },
+    /// Build a fixture from a CSV/TSV of string pairs, with expected values from rapidfuzz-rs
+    Import {
+        /// CSV or TSV file (`.tsv` selects tabs unless --delimiter is given)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Pairwise category for the imported cases
+        #[arg(long)]
+        category: String,
+
+        /// Output fixture file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Zero-based column holding input_a
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        column_a: usize,
+
+        /// Zero-based column holding input_b
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        column_b: usize,
+
+        /// Zero-based column holding the case description (default: `<file> row <n>`)
+        #[arg(long, value_name = "N")]
+        description_column: Option<usize>,
+
+        /// Field delimiter (default: tab for .tsv, comma otherwise)
+        #[arg(long, value_name = "CHAR")]
+        delimiter: Option<char>,
+
+        /// Skip the first row
+        #[arg(long)]
+        header: bool,
+
+        /// Add the cases to an existing output fixture, merging into its category group
+        #[arg(long)]
+        append: bool,
+    },
+    /// Write a seeded, per-category sample of existing cases as one smoke-test fixture
+    Sample {
+        /// Glob pattern for fixture files to sample from
+        pattern: String,
+
+        /// Output fixture file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Cases to keep per category (categories with fewer keep them all)
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        per_category: usize,
+
+        /// PRNG seed; the same seed and corpus always select the same cases
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Scaffold a new fixture file with empty cases for one category
+    Template {
+        /// Category of the scaffolded cases
+        #[arg(long)]
+        category: String,
+
+        /// Output fixture file (.yaml, .yml or .json); must not exist yet
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Number of empty cases
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        cases: usize,
+    },
+    /// Check fixtures against the JSON Schema named by their `$schema` field
+    SchemaCheck {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Directory to resolve `$schema` references in (default: nearest `schemas/` above each fixture)
+        #[arg(long, value_name = "DIR")]
+        schema_dir: Option<PathBuf>,
+    },
+    /// Report cases in `checksums: true` fixtures whose expected values changed without regeneration
+    VerifyIntegrity {
+        /// Glob pattern for fixture files
+        pattern: String,
+    },
+    /// Report fixtures whose expected values or source version are out of date (read-only)
+    VerifyFresh {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Absolute tolerance for score comparisons (fixture and case `tolerance` take precedence)
+        #[arg(long, value_name = "FLOAT", value_parser = parse_tolerance)]
+        epsilon: Option<f64>,
+    },
+    /// Compare rapidfuzz-rs against an external reference implementation (e.g. Python rapidfuzz)
+    CrossCheck {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Reference command, run through the shell; speaks line-delimited JSON on stdin/stdout
+        #[arg(long, value_name = "CMD")]
+        command: String,
+
+        /// Seconds to wait for each response before killing the reference process
+        #[arg(long, value_name = "SECS", default_value_t = 10)]
+        timeout: u64,
+
+        /// Absolute tolerance for score comparisons (fixture and case `tolerance` take precedence)
+        #[arg(long, value_name = "FLOAT", value_parser = parse_tolerance)]
+        epsilon: Option<f64>,
+    },
+    /// Time each category's computations over the fixture corpus
+    Bench {
+        /// Glob pattern for fixture files
+        pattern: String,
+
+        /// Timed iterations per category
+        #[arg(short = 'n', long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+
+        /// Untimed iterations run first to warm caches
+        #[arg(long, default_value_t = 10)]
+        warmup: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Baseline report: written after the run, or read with --compare
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Compare against --baseline instead of overwriting it
+        #[arg(long, requires = "baseline")]
+        compare: bool,
+
+        /// Percent increase in median latency counted as a regression
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+/// `$schema` written into fixtures the validator creates from scratch
+const FIXTURE_SCHEMA_URL: &str =
+    "https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json";
+
+/// Score comparison tolerance used when neither the CLI, fixture, nor case sets one
+const DEFAULT_TOLERANCE: f64 = 1e-10;
+
+/// `validate --timeout-ms` default
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+
+/// Input fields of one category's cases
+struct CategoryInputs {
+    category: &'static str,
+    /// Fields every case sets; `template` scaffolds these
+    required: &'static [&'static str],
+    /// Fields a case may set to override a default
+    optional: &'static [&'static str],
+}
+
+/// Categories understood by the validator (including TypeScript-only ones it skips) and the
+/// inputs validate and generate read for each. Lint flags other fields; `template` uses it too.
+const CATEGORY_INPUTS: &[CategoryInputs] = &[
+    pair_inputs("levenshtein"),
+    pair_inputs("damerau_osa"),
+    pair_inputs("damerau_unrestricted"),
+    pair_inputs("damerau_graphemes"),
+    CategoryInputs {
+        category: "damerau_weighted",
+        required: &["input_a", "input_b", "transposition_cost"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "mfc",
+        required: &["input_a", "input_b", "k"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "affine_gap",
+        required: &["input_a", "input_b", "mismatch", "gap_open", "gap_extend"],
+        optional: &[],
+    },
+    pair_inputs("jaro_winkler"),
+    CategoryInputs {
+        category: "jaro_winkler_params",
+        required: &["input_a", "input_b", "prefix_scale", "max_prefix"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "jaro_tokens",
+        required: &["input_a", "input_b"],
+        optional: &["match_threshold", "tokenizer"],
+    },
+    CategoryInputs {
+        category: "jaro_winkler_tokens",
+        required: &["input_a", "input_b"],
+        optional: &["prefix_scale", "match_threshold", "tokenizer"],
+    },
+    pair_inputs("indel"),
+    pair_inputs("lcs_seq"),
+    pair_inputs("ratio"),
+    CategoryInputs {
+        category: "hamming",
+        required: &["input_a", "input_b"],
+        optional: &["pad"],
+    },
+    // Base64-encoded byte strings
+    CategoryInputs {
+        category: "byte_metrics",
+        required: &["bytes_a", "bytes_b", "metric"],
+        optional: &["pad"],
+    },
+    CategoryInputs {
+        category: "positional_diff",
+        required: &["input_a", "input_b"],
+        optional: &["pad"],
+    },
+    pair_inputs("prefix"),
+    pair_inputs("postfix"),
+    pair_inputs("dice"),
+    CategoryInputs {
+        category: "jaccard_ngram",
+        required: &["input_a", "input_b"],
+        optional: &["n"],
+    },
+    // Mappings of token to weight
+    CategoryInputs {
+        category: "weighted_jaccard",
+        required: &["tokens_a", "tokens_b"],
+        optional: &["preset", "locale"],
+    },
+    CategoryInputs {
+        category: "substring",
+        required: &["needle", "haystack"],
+        optional: &["range_unit", "tie_policy"],
+    },
+    CategoryInputs {
+        category: "coverage",
+        required: &["needle", "haystack"],
+        optional: &["range_unit", "tie_policy"],
+    },
+    CategoryInputs {
+        category: "excerpt",
+        required: &["input", "range", "context_chars"],
+        optional: &["ellipsis", "range_unit"],
+    },
+    CategoryInputs {
+        category: "empty_inputs",
+        required: &["input_a", "input_b"],
+        optional: &["preset"],
+    },
+    CategoryInputs {
+        category: "window_scores",
+        required: &["needle", "haystack", "metric"],
+        optional: &["window", "step"],
+    },
+    CategoryInputs {
+        category: "fuzzy_find",
+        required: &["haystack", "needle", "max_edits"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "normalization_presets",
+        required: &["input", "preset"],
+        optional: &["locale", "strip_ignorables", "keep_joiners", "fold_numbers"],
+    },
+    CategoryInputs {
+        category: "normalization_locale",
+        required: &["input", "preset", "locale"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "numeric_tokens",
+        required: &["input"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "case_transfer",
+        required: &["pattern", "target"],
+        optional: &["locale"],
+    },
+    CategoryInputs {
+        category: "normalized_key",
+        required: &["input", "preset"],
+        optional: &[
+            "locale",
+            "strip_ignorables",
+            "keep_joiners",
+            "fold_numbers",
+            "other",
+        ],
+    },
+    CategoryInputs {
+        category: "script_detection",
+        required: &["input"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "suggestions",
+        required: &["input", "candidates", "options"],
+        optional: &["locale"],
+    },
+    CategoryInputs {
+        category: "did_you_mean",
+        required: &["input", "candidates"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "unified_distance",
+        required: &["input_a", "input_b", "metric"],
+        optional: &["digraphs", "options"],
     },
+    CategoryInputs {
+        category: "unified_score",
+        required: &["input_a", "input_b", "metric"],
+        optional: &["digraphs", "options"],
+    },
+    CategoryInputs {
+        category: "anchored_similarity",
+        required: &["input_a", "input_b", "metric", "side", "length"],
+        optional: &["preset", "locale"],
+    },
+    // An omitted or null input is a missing value
+    CategoryInputs {
+        category: "score_nullable",
+        required: &["metric"],
+        optional: &["input_a", "input_b", "missing", "preset", "locale"],
+    },
+    pair_inputs("partial_ratio"),
+    pair_inputs("token_sort_ratio"),
+    pair_inputs("token_set_ratio"),
+    pair_inputs("editops"),
+    pair_inputs("opcodes"),
+    CategoryInputs {
+        category: "word_diff",
+        required: &["input_a", "input_b"],
+        optional: &["tokenizer", "metric", "threshold"],
+    },
+    CategoryInputs {
+        category: "match_profile",
+        required: &["query", "candidate"],
+        optional: &["preset", "locale"],
+    },
+    CategoryInputs {
+        category: "extract_one",
+        required: &["query", "choices", "options"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "extract",
+        required: &["query", "choices", "options"],
+        optional: &[],
+    },
+    CategoryInputs {
+        category: "properties",
+        required: &["property"],
+        optional: &["metrics", "strings", "seed", "count"],
+    },
+];
+
+/// A category whose cases compare `input_a` with `input_b`
+const fn pair_inputs(category: &'static str) -> CategoryInputs {
+    CategoryInputs {
+        category,
+        required: &["input_a", "input_b"],
+        optional: &[],
+    }
+}
+
+fn category_inputs(category: &str) -> Option<&'static CategoryInputs> {
+    CATEGORY_INPUTS
+        .iter()
+        .find(|inputs| inputs.category == category)
+}
+
+fn is_known_category(category: &str) -> bool {
+    category_inputs(category).is_some()
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -52,14 +741,29 @@ struct Fixture {
     #[serde(rename = "$schema")]
     schema: String,
     version: String,
+    /// Format 2.0: the dataset's CalVer, which format 1.x kept in `version`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dataset_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     generator: Option<GeneratorMetadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
     notes: Option<String>,
+    /// Score tolerance for every case in this file (overrides `--epsilon`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tolerance: Option<f64>,
+    /// Format 2.0: unit for substring ranges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range_unit: Option<RangeUnit>,
+    /// Format 2.0: Levenshtein operation costs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weights: Option<EditWeights>,
+    /// Opt-in: `generate` writes a per-case `checksum` that `verify-integrity` checks
+    #[serde(default, skip_serializing_if = "is_false")]
+    checksums: bool,
     test_cases: Vec<CategoryGroup>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 struct GeneratorMetadata {
     tool: String,
     tool_version: String,
@@ -68,501 +772,1622 @@ struct GeneratorMetadata {
     generated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     command: Option<String>,
+    /// Input files a merged fixture was built from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sources: Option<Vec<String>>,
+    /// PRNG seed a random corpus was generated from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    /// Decimal places generated expected values were rounded to (`generate --precision`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precision: Option<u32>,
+    /// `string_metrics_core::ARITHMETIC_MODE` the expected values were computed in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arithmetic: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct CategoryGroup {
     category: String,
+    /// Tags every case in the group inherits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
     cases: Vec<TestCase>,
 }
 
+/// A case's effective tags: the group's followed by its own, without duplicates
+fn effective_tags(group_tags: Option<&[String]>, case_tags: Option<&[String]>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in group_tags.into_iter().chain(case_tags).flatten() {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+/// Copy group tags onto a case that is moving to a group without them (sample, merge)
+fn inherit_group_tags(group_tags: Option<&[String]>, case: &mut TestCase) {
+    if group_tags.is_some_and(|tags| !tags.is_empty()) {
+        case.tags = Some(effective_tags(group_tags, case.tags.as_deref()));
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 struct TestCase {
+    /// Category-specific inputs, kept in the order they were written
     #[serde(flatten)]
-    inputs: HashMap<String, serde_yaml::Value>,
+    inputs: serde_yaml::Mapping,
     #[serde(skip_serializing_if = "Option::is_none")]
     expected_distance: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     expected_score: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     expected_range: Option<Range>,
+    /// Substring: every occurrence of the longest common substring, sorted by start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_ranges: Option<Vec<Range>>,
+    /// Inclusive bounds for implementation-defined scores; generate leaves such cases alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_score_range: Option<ScoreRange>,
     #[serde(skip_serializing_if = "Option::is_none")]
     expected: Option<serde_yaml::Value>,
+    /// Score tolerance for this case only (overrides the fixture and `--epsilon`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tolerance: Option<f64>,
+    /// Substring the computation's error must contain; the case fails if it succeeds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expect_error: Option<String>,
     description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     tags: Option<Vec<String>>,
+    /// Pending case: listed as skipped and never generated unless `--include-skipped`
+    #[serde(default, skip_serializing_if = "is_false")]
+    skip: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skip_reason: Option<String>,
+    /// Focus marker: when any loaded case has it, only those cases run and the run fails
+    #[serde(default, skip_serializing_if = "is_false")]
+    only: bool,
+    /// Written by `generate` in `checksums: true` fixtures; see `case_checksum`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 struct Range {
     start: usize,
     end: usize,
 }
 
+impl From<MatchRange> for Range {
+    fn from(range: MatchRange) -> Self {
+        Range {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct ScoreRange {
+    min: f64,
+    max: f64,
+}
+
+impl ScoreRange {
+    fn contains(&self, score: f64) -> bool {
+        self.min <= score && score <= self.max
+    }
+}
+
 #[derive(Debug, Clone)]
 struct SuggestionResult {
     value: String,
+    id: Option<String>,
     score: f64,
     matched_range: Option<Range>,
     normalized_value: String,
+    explanation: Option<SuggestionExplanation>,
 }
 
-#[derive(Debug)]
-struct ValidationResult {
-    file: String,
-    category: String,
-    description: String,
-    passed: bool,
-    expected: Option<String>,
-    actual: Option<String>,
-    error: Option<String>,
+/// Case selection from `--category` / `--tag` flags, narrowed further by
+/// `validate --case-index` / `--description`
+///
+/// Empty lists match everything. Categories and tags are each OR-ed, and a case
+/// must satisfy both lists (and the index and description, when given) to be selected.
+#[derive(Debug, Default)]
+struct CaseFilter {
+    categories: Vec<String>,
+    tags: Vec<String>,
+    case_index: Option<usize>,
+    description: Option<String>,
 }
 
-fn main() {
-    let cli = Cli::parse();
+impl CaseFilter {
+    fn new(categories: Vec<String>, tags: Vec<String>) -> Self {
+        for category in &categories {
+            if !is_known_category(category) {
+                eprintln!(
+                    "{} unknown category in --category filter: {}",
+                    "Warning:".yellow(),
+                    category
+                );
+            }
+        }
+        CaseFilter {
+            categories,
+            tags,
+            ..CaseFilter::default()
+        }
+    }
 
-    match cli.command {
-        Commands::Validate { pattern } => validate_fixtures(&pattern),
-        Commands::Generate {
-            input,
-            output,
-            overwrite,
-            dry_run,
-        } => generate_fixture(&input, output.as_deref(), overwrite, dry_run),
+    /// Narrow to a single case by its position in the file and/or its description
+    fn with_case(mut self, case_index: Option<usize>, description: Option<String>) -> Self {
+        self.case_index = case_index;
+        self.description = description;
+        self
     }
-}
 
-// ============================================================================
-// VALIDATION MODE
-// ============================================================================
+    fn is_active(&self) -> bool {
+        !self.categories.is_empty()
+            || !self.tags.is_empty()
+            || self.case_index.is_some()
+            || self.description.is_some()
+    }
 
-fn validate_fixtures(pattern: &str) {
-    println!("{} fixtures matching: {}", "Validating".cyan(), pattern);
-    println!();
+    /// `tags` are the case's effective tags (group and case)
+    fn matches(&self, category: &str, tags: &[String]) -> bool {
+        let category_matches =
+            self.categories.is_empty() || self.categories.iter().any(|c| c == category);
+        let tag_matches = self.tags.is_empty() || tags.iter().any(|t| self.tags.contains(t));
+        category_matches && tag_matches
+    }
 
-    let mut files_processed = 0;
-    let mut total_tests = 0;
-    let mut passed_tests = 0;
-    let mut failed_tests = 0;
-    let mut results: Vec<ValidationResult> = Vec::new();
+    /// `case_index` is the case's position in its file, counted across groups
+    fn matches_case(&self, case_index: usize, description: &str) -> bool {
+        self.case_index.is_none_or(|index| index == case_index)
+            && self.description.as_deref().is_none_or(|d| d == description)
+    }
 
-    for entry in glob(pattern).expect("Failed to read glob pattern") {
-        match entry {
-            Ok(path) => {
-                files_processed += 1;
-                match validate_file(&path) {
-                    Ok(file_results) => {
-                        total_tests += file_results.len();
-                        for result in file_results {
-                            if result.passed {
-                                passed_tests += 1;
-                            } else {
-                                failed_tests += 1;
-                            }
-                            results.push(result);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("{} {}: {}", "Error".red(), path.display(), e);
-                    }
-                }
-            }
-            Err(e) => eprintln!("{}: {}", "Error".red(), e),
+    fn describe(&self) -> String {
+        let mut args = Vec::new();
+        for category in &self.categories {
+            args.push(format!("--category {}", shell_quote(category)));
+        }
+        for tag in &self.tags {
+            args.push(format!("--tag {}", shell_quote(tag)));
+        }
+        if let Some(index) = self.case_index {
+            args.push(format!("--case-index {}", index));
         }
+        if let Some(description) = &self.description {
+            args.push(format!("--description={}", shell_quote(description)));
+        }
+        args.join(" ")
     }
+}
 
-    // Print summary
-    println!();
-    println!("{}", "=".repeat(80));
-    println!("{}", "SUMMARY".bold());
-    println!("{}", "=".repeat(80));
-    println!("Files processed: {}", files_processed);
-    println!("Total tests:     {}", total_tests);
-    println!("Passed:          {}", passed_tests.to_string().green());
-    println!("Failed:          {}", failed_tests.to_string().red());
-    println!();
+/// Settings for a validate run
+#[derive(Debug, Default)]
+struct ValidateOptions {
+    filter: CaseFilter,
+    fail_fast: bool,
+    /// Worker threads (None = one per logical core)
+    jobs: Option<usize>,
+    /// Run-wide score tolerance from `--epsilon`
+    epsilon: Option<f64>,
+    /// Where to look up `$schema` files (default: a `schemas/` directory above the fixture)
+    schema_dir: Option<PathBuf>,
+    /// Skip the suggestion `normalized_value` comparison
+    ignore_normalized: bool,
+    /// Accepted failures loaded from `--baseline`
+    baseline: Option<Baseline>,
+    /// Where `--save-baseline` writes the run's failures
+    save_baseline: Option<PathBuf>,
+    /// Run cases marked `skip: true`
+    include_skipped: bool,
+    /// Per-case computation limit (`--timeout-ms`)
+    timeout: Duration,
+    /// Runs cases through the built wasm module instead (`--target wasm`)
+    wasm: Option<WasmRunner>,
+    /// Report written in addition to the terminal summary
+    format: ReportFormat,
+    output: Option<PathBuf>,
+    /// Print every failure in full (`--verbose`) rather than grouped by category and error
+    verbose: bool,
+    /// Print GitHub Actions annotations (`--format gh-annotations`, or GITHUB_ACTIONS=true)
+    annotations: bool,
+}
 
-    // Print failures
-    if failed_tests > 0 {
-        println!("{}", "FAILURES:".red().bold());
-        println!();
-        for result in results.iter().filter(|r| !r.passed) {
-            println!(
-                "  {} [{}] {}",
-                "✗".red(),
-                result.category,
-                result.description
-            );
-            println!("    File: {}", result.file);
-            if let Some(expected) = &result.expected {
-                println!("    Expected: {}", expected);
-            }
-            if let Some(actual) = &result.actual {
-                println!("    Actual:   {}", actual);
-            }
-            if let Some(error) = &result.error {
-                println!("    Error: {}", error);
-            }
-            println!();
-        }
-        process::exit(1);
-    } else {
-        println!("{}", "All tests passed!".green().bold());
-        process::exit(0);
+impl ValidateOptions {
+    fn tolerance(&self) -> f64 {
+        self.epsilon.unwrap_or(DEFAULT_TOLERANCE)
     }
 }
 
-fn validate_file(path: &PathBuf) -> Result<Vec<ValidationResult>, Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string(path)?;
-    let fixture: Fixture = serde_yaml::from_str(&contents)?;
-    let filename = path.file_name().unwrap().to_string_lossy().to_string();
-
-    let mut results = Vec::new();
+/// Position of a case in the corpus; the report is ordered by this key
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct CaseKey {
+    path: String,
+    category: String,
+    case_index: usize,
+}
 
-    for group in fixture.test_cases {
-        for test in group.cases {
-            let result = validate_test_case(&filename, &group.category, &test);
-            results.push(result);
-        }
-    }
+/// A validation result with its position in the corpus, the case's tags and its run time
+#[derive(Debug)]
+struct CaseOutcome {
+    key: CaseKey,
+    tags: Vec<String>,
+    result: ValidationResult,
+    elapsed: Duration,
+}
 
-    Ok(results)
+/// Thread-safe collector shared by the parallel validation workers
+#[derive(Default)]
+struct ResultSink {
+    results: Mutex<Vec<CaseOutcome>>,
+    /// Fixture files that could not be read or parsed, as (path, error)
+    file_errors: Mutex<Vec<(String, String)>>,
+    /// Exit code of the most severe file error per path
+    file_error_codes: Mutex<BTreeMap<String, i32>>,
+    /// Cases marked `skip: true` that were not run
+    skipped: Mutex<Vec<(CaseKey, SkippedCase)>>,
+    total: AtomicUsize,
+    passed: AtomicUsize,
+    failed: AtomicUsize,
+    filtered: AtomicUsize,
+    /// Cases validated with a fixture- or case-level tolerance instead of the run's
+    tolerance_overrides: AtomicUsize,
+    /// Set once a `--fail-fast` failure has been reported
+    stop: AtomicBool,
+    schemas: SchemaCache,
 }
 
-fn validate_test_case(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    match category {
-        "levenshtein" => validate_levenshtein(file, category, test),
-        "damerau_osa" => validate_damerau_osa(file, category, test),
-        "damerau_unrestricted" => validate_damerau_unrestricted(file, category, test),
-        "jaro_winkler" => validate_jaro_winkler(file, category, test),
-        "indel" => validate_indel(file, category, test),
-        "lcs_seq" => validate_lcs_seq(file, category, test),
-        "ratio" => validate_ratio(file, category, test),
-        "substring" => validate_substring(file, category, test),
-        "normalization_presets" => validate_normalization(file, category, test),
-        "normalization_locale" => validate_normalization_locale(file, category, test),
-        "suggestions" => validate_suggestions(file, category, test),
-        "unified_distance" => validate_unified_distance(file, category, test),
-        "unified_score" => validate_unified_score(file, category, test),
-        // TypeScript-only categories - validated by TypeScript test suite
-        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" | "extract_one" | "extract" => {
-            ValidationResult {
-                file: file.to_string(),
-                category: category.to_string(),
-                description: test.description.clone(),
-                passed: true,
-                expected: Some("(TypeScript implementation)".to_string()),
-                actual: Some("(skipped - validated by TS tests)".to_string()),
-                error: None,
+impl ResultSink {
+    fn record(&self, outcome: CaseOutcome, options: &ValidateOptions) {
+        let result = &outcome.result;
+        self.total.fetch_add(1, Ordering::SeqCst);
+        if result.passed {
+            self.passed.fetch_add(1, Ordering::SeqCst);
+        } else {
+            self.failed.fetch_add(1, Ordering::SeqCst);
+            let known = options
+                .baseline
+                .as_ref()
+                .is_some_and(|baseline| baseline.contains(result));
+            if options.fail_fast && !known && !self.stop.swap(true, Ordering::SeqCst) {
+                summary!("{}", "FAILURE (--fail-fast):".red().bold());
+                summary!();
+                print_failure(result);
+                process::exit(EXIT_FAILURES);
             }
         }
-        _ => ValidationResult {
-            file: file.to_string(),
-            category: category.to_string(),
-            description: test.description.clone(),
-            passed: false,
-            expected: None,
-            actual: None,
-            error: Some(format!("Unknown category: {}", category)),
-        },
+        self.results.lock().unwrap().push(outcome);
     }
-}
-
-fn get_string_input(inputs: &HashMap<String, serde_yaml::Value>, key: &str) -> Option<String> {
-    inputs
-        .get(key)
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
 
-fn validate_levenshtein(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+    /// Record an unusable fixture; `code` is `EXIT_FIXTURE_ERRORS` or `EXIT_IO_ERRORS`
+    fn record_file_error(&self, path: String, error: String, code: i32, options: &ValidateOptions) {
+        if options.fail_fast && !self.stop.swap(true, Ordering::SeqCst) {
+            eprintln!("{}", "Stopping at first file error (--fail-fast)".red());
+            eprintln!("  {}: {}", path, error);
+            process::exit(code);
+        }
+        let mut codes = self.file_error_codes.lock().unwrap();
+        let entry = codes.entry(path.clone()).or_default();
+        *entry = (*entry).max(code);
+        self.file_errors.lock().unwrap().push((path, error));
+    }
 
-    let actual_distance =
-        rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars());
-    let actual_score =
-        rapidfuzz::distance::levenshtein::normalized_similarity(input_a.chars(), input_b.chars());
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+    /// Skipped cases in deterministic order
+    fn take_skipped(&self) -> Vec<SkippedCase> {
+        let mut skipped = std::mem::take(&mut *self.skipped.lock().unwrap());
+        skipped.sort_by(|(a, _), (b, _)| a.cmp(b));
+        skipped.into_iter().map(|(_, case)| case).collect()
+    }
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: distance_matches && score_matches,
-        expected: Some(format!(
-            "distance={:?}, score={:?}",
-            test.expected_distance, test.expected_score
-        )),
-        actual: Some(format!(
-            "distance={}, score={}",
-            actual_distance, actual_score
-        )),
-        error: None,
+    /// Consume the sink, returning results, file errors and their exit codes in deterministic order
+    #[allow(clippy::type_complexity)]
+    fn into_sorted(
+        self,
+    ) -> (
+        Vec<CaseOutcome>,
+        Vec<(String, String)>,
+        BTreeMap<String, i32>,
+    ) {
+        let mut results = self.results.into_inner().unwrap();
+        results.sort_by(|a, b| a.key.cmp(&b.key));
+        let mut file_errors = self.file_errors.into_inner().unwrap();
+        file_errors.sort();
+        (
+            results,
+            file_errors,
+            self.file_error_codes.into_inner().unwrap(),
+        )
     }
 }
 
-fn validate_damerau_osa(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+/// A pending case left out of the run
+#[derive(Debug)]
+struct SkippedCase {
+    file: String,
+    category: String,
+    description: String,
+    reason: Option<String>,
+}
 
-    let actual_distance = rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars());
-    let actual_score =
-        rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars());
+#[derive(Debug)]
+struct ValidationResult {
+    file: String,
+    category: String,
+    description: String,
+    passed: bool,
+    expected: Option<String>,
+    actual: Option<String>,
+    error: Option<String>,
+    /// Command line that re-runs just this case; set for failures by `validate_file`
+    repro: Option<String>,
+}
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+fn main() {
+    let cli = Cli::parse();
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: distance_matches && score_matches,
-        expected: Some(format!(
-            "distance={:?}, score={:?}",
-            test.expected_distance, test.expected_score
-        )),
-        actual: Some(format!(
-            "distance={}, score={}",
-            actual_distance, actual_score
-        )),
-        error: None,
+    match cli.command {
+        Commands::Validate {
+            pattern,
+            categories,
+            tags,
+            case_index,
+            description,
+            fail_fast,
+            jobs,
+            epsilon,
+            watch,
+            schema_dir,
+            ignore_normalized,
+            baseline,
+            save_baseline,
+            include_skipped,
+            timeout_ms,
+            target,
+            node_runner,
+            format,
+            no_annotations,
+            verbose,
+            output,
+        } => {
+            let baseline = baseline.map(|path| {
+                Baseline::load(&path).unwrap_or_else(|e| {
+                    eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                    process::exit(EXIT_IO_ERRORS);
+                })
+            });
+            if output.as_deref() == Some(Path::new("-")) {
+                SUMMARY_TO_STDERR.store(true, Ordering::Relaxed);
+            }
+            let options = ValidateOptions {
+                filter: CaseFilter::new(categories, tags).with_case(case_index, description),
+                fail_fast,
+                jobs,
+                epsilon,
+                schema_dir,
+                ignore_normalized,
+                baseline,
+                save_baseline,
+                include_skipped,
+                timeout: Duration::from_millis(timeout_ms),
+                wasm: (target == ValidateTarget::Wasm)
+                    .then(|| WasmRunner::new(node_runner, Duration::from_millis(timeout_ms))),
+                format,
+                output,
+                verbose,
+                annotations: format == ReportFormat::GhAnnotations
+                    || (!no_annotations
+                        && std::env::var("GITHUB_ACTIONS").as_deref() == Ok("true")),
+            };
+            if watch {
+                watch_fixtures(&pattern, &options)
+            } else {
+                validate_fixtures(&pattern, &options)
+            }
+        }
+        Commands::Generate {
+            input,
+            output,
+            overwrite,
+            dry_run,
+            categories,
+            tags,
+            migrate,
+            no_debug_fields,
+            include_skipped,
+            precision,
+        } => generate_fixture(
+            &input,
+            output.as_deref(),
+            &GenerateOptions {
+                overwrite,
+                dry_run,
+                migrate,
+                no_debug_fields,
+                include_skipped,
+                precision,
+                filter: CaseFilter::new(categories, tags),
+            },
+        ),
+        Commands::Lint {
+            pattern,
+            strict,
+            format,
+            max_input_length,
+        } => lint_fixtures(&pattern, strict, format, max_input_length),
+        Commands::Diff {
+            old,
+            new,
+            format,
+            epsilon,
+        } => diff_fixtures(&old, &new, format, epsilon),
+        Commands::Merge {
+            inputs,
+            output,
+            force,
+        } => merge_fixtures(&inputs, &output, force),
+        Commands::Convert {
+            input,
+            output,
+            to,
+            check,
+        } => convert_fixture(&input, &output, to, check),
+        Commands::Stats { pattern, format } => stats_fixtures(&pattern, format),
+        Commands::Coverage {
+            pattern,
+            min_cases,
+            allowlist,
+            format,
+        } => coverage_gate(&pattern, min_cases, allowlist.as_deref(), format),
+        Commands::Check {
+            metric,
+            a,
+            b,
+            preset,
+            locale,
+            stdin,
+            format,
+        } => check_pairs(
+            a,
+            b,
+            stdin,
+            &CheckOptions {
+                metric,
+                preset,
+                locale,
+            },
+            format,
+        ),
+        Commands::Explain { a, b, metric } => explain_pair(&a, &b, &metric),
+        Commands::GenerateCorpus {
+            seed,
+            count,
+            output,
+            categories,
+        } => generate_corpus(seed, count, &output, &categories),
+        Commands::Import {
+            input,
+            category,
+            output,
+            column_a,
+            column_b,
+            description_column,
+            delimiter,
+            header,
+            append,
+        } => import_pairs(
+            &input,
+            &output,
+            &ImportOptions {
+                category,
+                column_a,
+                column_b,
+                description_column,
+                delimiter,
+                header,
+                append,
+            },
+        ),
+        Commands::Sample {
+            pattern,
+            output,
+            per_category,
+            seed,
+        } => sample_fixtures(&pattern, &output, per_category, seed),
+        Commands::Template {
+            category,
+            output,
+            cases,
+        } => template_fixture(&category, &output, cases),
+        Commands::SchemaCheck {
+            pattern,
+            schema_dir,
+        } => schema_check(&pattern, schema_dir.as_deref()),
+        Commands::VerifyIntegrity { pattern } => verify_integrity(&pattern),
+        Commands::VerifyFresh { pattern, epsilon } => verify_fresh(&pattern, epsilon),
+        Commands::CrossCheck {
+            pattern,
+            command,
+            timeout,
+            epsilon,
+        } => cross_check(&pattern, &command, Duration::from_secs(timeout), epsilon),
+        Commands::Bench {
+            pattern,
+            iterations,
+            warmup,
+            format,
+            baseline,
+            compare,
+            threshold,
+        } => bench_fixtures(
+            &pattern,
+            &BenchOptions {
+                iterations: iterations as usize,
+                warmup,
+                format,
+                baseline,
+                compare,
+                threshold,
+            },
+        ),
     }
 }
 
-fn validate_damerau_unrestricted(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+// ============================================================================
+// VALIDATION MODE
+// ============================================================================
 
-    let actual_distance =
-        rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars());
-    let actual_score = rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
-        input_a.chars(),
-        input_b.chars(),
-    );
+/// `validate` exit code for case failures (see the subcommand's `--help`)
+const EXIT_FAILURES: i32 = 1;
+/// `validate` exit code for fixtures that don't parse or violate the schema
+const EXIT_FIXTURE_ERRORS: i32 = 2;
+/// `validate` exit code for IO and glob problems, including a pattern matching nothing
+const EXIT_IO_ERRORS: i32 = 3;
+
+fn validate_fixtures(pattern: &str, options: &ValidateOptions) {
+    summary!("{} fixtures matching: {}", "Validating".cyan(), pattern);
+    if let Some(wasm) = &options.wasm {
+        summary!("Target: wasm module via {}", wasm.command);
+    }
+    let files = expand_input(pattern);
+    let failing = run_validation(&files.paths, files.errors, files.ignored, options);
+    process::exit(exit_code(&failing));
+}
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+/// Most severe exit code among failing paths, 0 when nothing failed
+fn exit_code(failing: &BTreeMap<String, i32>) -> i32 {
+    failing.values().copied().max().unwrap_or(0)
+}
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: distance_matches && score_matches,
-        expected: Some(format!(
-            "distance={:?}, score={:?}",
-            test.expected_distance, test.expected_score
-        )),
-        actual: Some(format!(
-            "distance={}, score={}",
-            actual_distance, actual_score
-        )),
-        error: None,
-    }
+/// Ignore file read from the root of a directory input
+const IGNORE_FILE: &str = ".validatorignore";
+
+/// Fixture files named by a glob pattern or directory
+struct FixtureFiles {
+    /// Sorted fixture paths
+    paths: Vec<PathBuf>,
+    /// (path, error) for entries that could not be read
+    errors: Vec<(String, String)>,
+    /// Fixture files excluded by `.validatorignore` (directory inputs only)
+    ignored: Option<usize>,
 }
 
-fn validate_jaro_winkler(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+/// Which changed files belong to a validate input; used by watch mode
+enum InputMatcher {
+    Glob(glob::Pattern),
+    Directory { root: PathBuf, ignore: Gitignore },
+}
 
-    let actual_score =
-        rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars());
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+impl InputMatcher {
+    fn new(pattern: &str) -> Result<Self, String> {
+        let root = Path::new(pattern);
+        if root.is_dir() {
+            Ok(InputMatcher::Directory {
+                root: root.to_path_buf(),
+                ignore: load_ignore_file(root)?,
+            })
+        } else {
+            glob::Pattern::new(pattern)
+                .map(InputMatcher::Glob)
+                .map_err(|e| format!("invalid pattern {}: {}", pattern, e))
+        }
+    }
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: score_matches,
-        expected: Some(format!("score={:?}", test.expected_score)),
-        actual: Some(format!("score={}", actual_score)),
-        error: None,
+    fn matches(&self, path: &Path) -> bool {
+        match self {
+            InputMatcher::Glob(pattern) => pattern.matches_path(path),
+            InputMatcher::Directory { root, ignore } => {
+                path.starts_with(root)
+                    && has_fixture_extension(path)
+                    && !ignore.matched_path_or_any_parents(path, false).is_ignore()
+            }
+        }
     }
 }
 
-fn validate_indel(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
-
-    let actual_distance = rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars());
-    let actual_score =
-        rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars());
+/// Paths for a glob pattern, plus (path, error) for entries that could not be read
+fn expand_pattern(pattern: &str) -> (Vec<PathBuf>, Vec<(String, String)>) {
+    let files = expand_input(pattern);
+    (files.paths, files.errors)
+}
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+/// Expand a glob pattern, or walk a directory for `.yaml`/`.yml`/`.json` files honoring its
+/// `.validatorignore`. An invalid pattern or one matching no files is reported as an error
+/// against the pattern.
+fn expand_input(pattern: &str) -> FixtureFiles {
+    let mut files = if Path::new(pattern).is_dir() {
+        walk_directory(Path::new(pattern))
+    } else {
+        expand_glob(pattern)
+    };
+    if files.paths.is_empty() && files.errors.is_empty() {
+        eprintln!("{}: no files matched {}", "Error".red(), pattern);
+        files
+            .errors
+            .push((pattern.to_string(), "no files matched".to_string()));
+    }
+    files.paths.sort();
+    files
+}
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: distance_matches && score_matches,
-        expected: Some(format!(
-            "distance={:?}, score={:?}",
-            test.expected_distance, test.expected_score
-        )),
-        actual: Some(format!(
-            "distance={}, score={}",
-            actual_distance, actual_score
-        )),
-        error: None,
+fn expand_glob(pattern: &str) -> FixtureFiles {
+    let mut files = FixtureFiles {
+        paths: Vec::new(),
+        errors: Vec::new(),
+        ignored: None,
+    };
+    let entries = match glob(pattern) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("{}: invalid pattern {}: {}", "Error".red(), pattern, e);
+            files
+                .errors
+                .push((pattern.to_string(), format!("invalid pattern: {}", e)));
+            return files;
+        }
+    };
+    for entry in entries {
+        match entry {
+            Ok(path) => files.paths.push(path),
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                files
+                    .errors
+                    .push((e.path().display().to_string(), e.error().to_string()));
+            }
+        }
     }
+    files
 }
 
-fn validate_lcs_seq(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+fn has_fixture_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "yaml" | "yml" | "json"))
+}
 
-    let actual_distance = rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars());
-    let actual_score =
-        rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars());
+/// Gitignore-style patterns from `root/.validatorignore`; empty when the file is absent
+fn load_ignore_file(root: &Path) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new(root);
+    let path = root.join(IGNORE_FILE);
+    if path.is_file() {
+        if let Some(e) = builder.add(&path) {
+            return Err(format!("{}: {}", path.display(), e));
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+/// Recursively collect fixture files under `root`, following symlinks. Each directory is
+/// entered once by canonical path, so symlink cycles end the descent instead of looping.
+fn walk_directory(root: &Path) -> FixtureFiles {
+    let mut files = FixtureFiles {
+        paths: Vec::new(),
+        errors: Vec::new(),
+        ignored: Some(0),
+    };
+    let ignore = match load_ignore_file(root) {
+        Ok(ignore) => ignore,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            files
+                .errors
+                .push((root.join(IGNORE_FILE).display().to_string(), e));
+            return files;
+        }
+    };
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: distance_matches && score_matches,
-        expected: Some(format!(
-            "distance={:?}, score={:?}",
-            test.expected_distance, test.expected_score
-        )),
-        actual: Some(format!(
-            "distance={}, score={}",
-            actual_distance, actual_score
-        )),
-        error: None,
+    let mut visited = HashSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    let mut ignored = 0;
+    while let Some(dir) = pending.pop() {
+        let read = fs::canonicalize(&dir).and_then(|canonical| {
+            // Already entered through another path (a symlink cycle or alias)
+            if !visited.insert(canonical) {
+                return Ok(None);
+            }
+            fs::read_dir(&dir).map(Some)
+        });
+        let entries = match read {
+            Ok(Some(entries)) => entries,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error".red(), dir.display(), e);
+                files
+                    .errors
+                    .push((dir.display().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    eprintln!("{} {}: {}", "Error".red(), dir.display(), e);
+                    files
+                        .errors
+                        .push((dir.display().to_string(), e.to_string()));
+                    continue;
+                }
+            };
+            // `is_dir` follows symlinks; a dangling link is neither and is passed over
+            if path.is_dir() {
+                pending.push(path);
+            } else if has_fixture_extension(&path) {
+                if ignore.matched_path_or_any_parents(&path, false).is_ignore() {
+                    ignored += 1;
+                } else {
+                    files.paths.push(path);
+                }
+            }
+        }
     }
+    files.ignored = Some(ignored);
+    files
 }
 
-fn validate_ratio(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+/// Validate the given files and print the summary; returns the paths with failures or file
+/// errors, each with its exit code
+fn run_validation(
+    paths: &[PathBuf],
+    glob_errors: Vec<(String, String)>,
+    ignored: Option<usize>,
+    options: &ValidateOptions,
+) -> BTreeMap<String, i32> {
+    let filter = &options.filter;
+    if filter.is_active() {
+        summary!("Filters: {}", filter.describe());
+    }
+    summary!();
 
-    let actual_score = rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars());
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+    let sink = ResultSink::default();
+    for (path, error) in glob_errors {
+        sink.record_file_error(path, error, EXIT_IO_ERRORS, options);
+    }
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: score_matches,
-        expected: Some(format!("score={:?}", test.expected_score)),
-        actual: Some(format!("score={}", actual_score)),
-        error: None,
+    // `only` anywhere in the loaded set narrows the run to the marked cases
+    let focused_paths: BTreeSet<String> = paths
+        .par_iter()
+        .filter(|path| load_fixture(path).is_ok_and(|fixture| has_focused_cases(&fixture)))
+        .map(|path| path.display().to_string())
+        .collect();
+    let focused = !focused_paths.is_empty();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.jobs.unwrap_or(0))
+        .build()
+        .expect("Failed to build thread pool");
+    pool.install(|| {
+        paths
+            .par_iter()
+            .for_each(|path| validate_file(path, options, focused, &sink));
+    });
+
+    let files_processed = paths.len();
+    let total_tests = sink.total.load(Ordering::SeqCst);
+    let passed_tests = sink.passed.load(Ordering::SeqCst);
+    let failed_tests = sink.failed.load(Ordering::SeqCst);
+    let filtered_tests = sink.filtered.load(Ordering::SeqCst);
+    let tolerance_overrides = sink.tolerance_overrides.load(Ordering::SeqCst);
+    let skipped = sink.take_skipped();
+    let (results, file_errors, file_error_codes) = sink.into_sorted();
+    let outcome = BaselineOutcome::classify(&results, options.baseline.as_ref());
+
+    // Fixture paths with a new failure, a fixed baseline entry, a file error or `only` markers
+    let mut failing: BTreeMap<String, i32> = outcome
+        .new
+        .iter()
+        .chain(&outcome.fixed)
+        .map(|outcome| outcome.key.path.clone())
+        .chain(focused_paths.iter().cloned())
+        .map(|path| (path, EXIT_FAILURES))
+        .collect();
+    failing.extend(file_error_codes);
+
+    // Print summary
+    summary!();
+    summary!("{}", "=".repeat(80));
+    summary!("{}", "SUMMARY".bold());
+    summary!("{}", "=".repeat(80));
+    match ignored {
+        Some(ignored) => summary!(
+            "Files processed: {} ({} discovered, {} ignored by {})",
+            files_processed,
+            files_processed + ignored,
+            ignored.to_string().yellow(),
+            IGNORE_FILE
+        ),
+        None => summary!("Files processed: {}", files_processed),
     }
-}
+    summary!("File errors:     {}", file_errors.len().to_string().red());
+    summary!("Total tests:     {}", total_tests);
+    summary!("Passed:          {}", passed_tests.to_string().green());
+    summary!("Failed:          {}", failed_tests.to_string().red());
+    if filter.is_active() || focused {
+        summary!("Filtered out:    {}", filtered_tests.to_string().yellow());
+    }
+    if !skipped.is_empty() {
+        summary!("Skipped:         {}", skipped.len().to_string().yellow());
+    }
+    if tolerance_overrides > 0 {
+        summary!(
+            "Tolerance:       {:e} ({} cases overridden by fixture/case tolerance)",
+            options.tolerance(),
+            tolerance_overrides
+        );
+    } else {
+        summary!("Tolerance:       {:e}", options.tolerance());
+    }
+    if options.baseline.is_some() {
+        summary!(
+            "Known failures:  {}",
+            outcome.known.len().to_string().yellow()
+        );
+        summary!("New failures:    {}", outcome.new.len().to_string().red());
+        summary!(
+            "Fixed:           {}",
+            outcome.fixed.len().to_string().green()
+        );
+    }
+    summary!();
 
-fn validate_unified_distance(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
-    let metric =
-        get_string_input(&test.inputs, "metric").unwrap_or_else(|| "levenshtein".to_string());
+    let report = RunReport {
+        results: &results,
+        skipped: &skipped,
+        file_errors: &file_errors,
+    };
+    let tallies = report.tallies();
+    if !tallies.is_empty() {
+        print_category_table(&tallies);
+    }
 
-    let actual_distance = match metric.as_str() {
-        "levenshtein" => {
-            rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars())
+    if !file_errors.is_empty() {
+        summary!("{}", "FILE ERRORS:".red().bold());
+        summary!();
+        for (file, error) in &file_errors {
+            summary!("  {} {}", "✗".red(), file);
+            summary!("    Error: {}", error);
+            summary!();
         }
-        "damerau_levenshtein" => {
-            rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars())
+    }
+
+    // Print failures
+    if options.baseline.is_some() {
+        outcome.print();
+    } else if failed_tests > 0 {
+        summary!("{}", "FAILURES:".red().bold());
+        summary!();
+        if options.verbose {
+            for outcome in results.iter().filter(|outcome| !outcome.result.passed) {
+                print_failure(&outcome.result);
+            }
+        } else {
+            print_grouped_failures(&results);
         }
-        "osa" => rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars()),
-        "indel" => rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars()),
-        "lcs_seq" => rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars()),
-        _ => {
-            return ValidationResult {
-                file: file.to_string(),
-                category: category.to_string(),
-                description: test.description.clone(),
-                passed: false,
-                expected: None,
-                actual: None,
-                error: Some(format!("Unknown distance metric: {}", metric)),
+    }
+
+    if options.annotations {
+        print_annotations(&outcome, &file_errors, &focused_paths);
+    }
+
+    if !results.is_empty() {
+        summary!("{}", "SLOWEST CASES:".bold());
+        summary!();
+        for outcome in slowest_cases(&results) {
+            summary!(
+                "  {:>10.3} ms  [{}] {} ({})",
+                outcome.elapsed.as_secs_f64() * 1000.0,
+                outcome.result.category,
+                outcome.result.description,
+                outcome.result.file
+            );
+        }
+        summary!();
+    }
+
+    if !skipped.is_empty() {
+        summary!("{}", "SKIPPED (skip: true):".yellow().bold());
+        summary!();
+        for case in &skipped {
+            let reason = case
+                .reason
+                .as_ref()
+                .map(|reason| format!(": {}", reason))
+                .unwrap_or_default();
+            summary!(
+                "  {} [{}] {} ({}){}",
+                "-".yellow(),
+                case.category,
+                case.description,
+                case.file,
+                reason
+            );
+        }
+        summary!();
+    }
+
+    if focused {
+        summary!(
+            "{} `only` markers found; ran only the marked cases and failing the run so they aren't committed:",
+            "Warning:".yellow().bold()
+        );
+        for path in &focused_paths {
+            summary!("  {}", path);
+        }
+        summary!();
+    }
+
+    if let Some(path) = &options.output {
+        if let Err(e) = report.write(options.format, path) {
+            eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+            process::exit(EXIT_IO_ERRORS);
+        }
+        if path != Path::new("-") {
+            summary!("{} {}", "✅ Report written:".green(), path.display());
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        let baseline = Baseline::from_results(&results);
+        if let Err(e) = baseline.save(path) {
+            eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+            process::exit(EXIT_IO_ERRORS);
+        }
+        summary!(
+            "{} {} ({} failures)",
+            "✅ Baseline written:".green(),
+            path.display(),
+            baseline.failures.len()
+        );
+    }
+
+    if failing.is_empty() {
+        if outcome.known.is_empty() {
+            summary!("{}", "All tests passed!".green().bold());
+        } else {
+            summary!(
+                "{}",
+                format!("No new failures ({} known)", outcome.known.len())
+                    .green()
+                    .bold()
+            );
+        }
+    }
+    failing
+}
+
+/// Cases listed under SLOWEST CASES in the validate summary (and `slowest` in JSON reports)
+const SLOWEST_CASES: usize = 10;
+
+/// The `SLOWEST_CASES` longest-running cases, slowest first; ties keep corpus order
+fn slowest_cases(results: &[CaseOutcome]) -> Vec<&CaseOutcome> {
+    let mut slowest: Vec<&CaseOutcome> = results.iter().collect();
+    slowest.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then_with(|| a.key.cmp(&b.key)));
+    slowest.truncate(SLOWEST_CASES);
+    slowest
+}
+
+fn print_category_table(tallies: &BTreeMap<&str, CategoryTally>) {
+    let width = tallies
+        .keys()
+        .map(|category| category.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Category".len());
+    summary!("{}", "CATEGORIES:".bold());
+    summary!();
+    summary!(
+        "  {:<width$}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>10}",
+        "Category",
+        "Cases",
+        "Passed",
+        "Failed",
+        "Skipped",
+        "Pass %",
+        "Time (ms)"
+    );
+    for (category, tally) in tallies {
+        let failed = format!("{:>7}", tally.failed);
+        summary!(
+            "  {:<width$}  {:>7}  {:>7}  {}  {:>7}  {:>6.1}%  {:>10.3}",
+            category,
+            tally.cases,
+            tally.passed,
+            if tally.failed > 0 {
+                failed.red()
+            } else {
+                failed.normal()
+            },
+            tally.skipped,
+            tally.pass_rate(),
+            tally.elapsed.as_secs_f64() * 1000.0
+        );
+    }
+    summary!();
+}
+
+/// Failures under one header per category. Within a category, failures sharing an error
+/// message print once, followed by how many more there are; `--verbose` lists them all.
+/// Failures without a message (plain value mismatches) are always listed.
+fn print_grouped_failures(results: &[CaseOutcome]) {
+    let mut categories: BTreeMap<&str, Vec<&ValidationResult>> = BTreeMap::new();
+    for outcome in results.iter().filter(|outcome| !outcome.result.passed) {
+        categories
+            .entry(outcome.result.category.as_str())
+            .or_default()
+            .push(&outcome.result);
+    }
+    for (category, failures) in &categories {
+        summary!(
+            "{} {}",
+            category.bold(),
+            format!("({} failed)", failures.len()).red()
+        );
+        summary!();
+        // Messages in order of first appearance, each with the failures that share it
+        let mut groups: Vec<Vec<&ValidationResult>> = Vec::new();
+        let mut group_of: HashMap<&str, usize> = HashMap::new();
+        for &result in failures {
+            let Some(error) = result.error.as_deref() else {
+                groups.push(vec![result]);
+                continue;
+            };
+            let index = *group_of.entry(error).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(result);
+        }
+        for group in &groups {
+            print_failure_details(group[0]);
+            if group.len() > 1 {
+                summary!(
+                    "    {}",
+                    format!(
+                        "... and {} more with this error (--verbose lists them)",
+                        group.len() - 1
+                    )
+                    .yellow()
+                );
             }
+            summary!();
         }
-    };
+    }
+}
 
-    let distance_matches = test
-        .expected_distance
-        .map_or(true, |exp| exp == actual_distance);
+fn print_failure(result: &ValidationResult) {
+    print_failure_details(result);
+    summary!();
+}
 
-    ValidationResult {
+fn print_failure_details(result: &ValidationResult) {
+    summary!(
+        "  {} [{}] {}",
+        "✗".red(),
+        result.category,
+        result.description
+    );
+    summary!("    File: {}", result.file);
+    if let Some(expected) = &result.expected {
+        summary!("    Expected: {}", expected);
+    }
+    if let Some(actual) = &result.actual {
+        summary!("    Actual:   {}", actual);
+    }
+    if let Some(error) = &result.error {
+        summary!("    Error: {}", error);
+    }
+    if let Some(repro) = &result.repro {
+        summary!("    Repro: {}", repro);
+    }
+}
+
+/// Run one case on its own thread, giving up after `options.timeout`.
+/// A timed-out computation can't be cancelled; its thread is left to finish or die with the process.
+/// Property cases run many computations by design and are bounded by their `count` instead.
+fn validate_with_timeout(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+    options: &ValidateOptions,
+) -> (ValidationResult, Duration) {
+    let start = Instant::now();
+    // The runner bounds each call by the timeout itself
+    if let Some(wasm) = &options.wasm {
+        return (
+            validate_wasm_case(file, category, test, tol, wasm),
+            start.elapsed(),
+        );
+    }
+    if category == "properties" {
+        let result = validate_test_case(file, category, test, tol, options.ignore_normalized);
+        return (result, start.elapsed());
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let (worker_file, worker_category, worker_test) =
+        (file.to_string(), category.to_string(), test.clone());
+    let ignore_normalized = options.ignore_normalized;
+    std::thread::spawn(move || {
+        let result = validate_test_case(
+            &worker_file,
+            &worker_category,
+            &worker_test,
+            tol,
+            ignore_normalized,
+        );
+        let _ = tx.send(result);
+    });
+
+    let error = match rx.recv_timeout(options.timeout) {
+        Ok(result) => return (result, start.elapsed()),
+        Err(mpsc::RecvTimeoutError::Timeout) => format!(
+            "timeout: no result after {} ms ({})",
+            options.timeout.as_millis(),
+            input_lengths(test)
+        ),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            format!("computation panicked ({})", input_lengths(test))
+        }
+    };
+    let result = ValidationResult {
         file: file.to_string(),
         category: category.to_string(),
         description: test.description.clone(),
-        passed: distance_matches,
-        expected: Some(format!("distance={:?}", test.expected_distance)),
-        actual: Some(format!("distance={}", actual_distance)),
-        error: None,
-    }
+        passed: false,
+        expected: None,
+        actual: None,
+        error: Some(error),
+        repro: None,
+    };
+    (result, start.elapsed())
 }
 
-fn validate_unified_score(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
-    let metric =
-        get_string_input(&test.inputs, "metric").unwrap_or_else(|| "jaro_winkler".to_string());
+/// Character counts of a case's string inputs, e.g. "input_a: 3 chars, input_b: 5 chars"
+fn input_lengths(test: &TestCase) -> String {
+    let lengths: Vec<String> = LENGTH_INPUTS
+        .iter()
+        .filter_map(|key| {
+            get_string_input(&test.inputs, key)
+                .map(|value| format!("{}: {} chars", key, value.chars().count()))
+        })
+        .collect();
+    if lengths.is_empty() {
+        "no string inputs".to_string()
+    } else {
+        lengths.join(", ")
+    }
+}
 
-    let actual_score = match metric.as_str() {
-        "levenshtein" => rapidfuzz::distance::levenshtein::normalized_similarity(
-            input_a.chars(),
-            input_b.chars(),
-        ),
-        "damerau_levenshtein" => rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
-            input_a.chars(),
-            input_b.chars(),
-        ),
-        "osa" => rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars()),
-        "jaro" => rapidfuzz::distance::jaro::similarity(input_a.chars(), input_b.chars()),
-        "jaro_winkler" => {
-            rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars())
+/// Validate every selected case in a fixture file, recording into the shared sink.
+/// With `focused`, only cases marked `only: true` are selected.
+fn validate_file(path: &Path, options: &ValidateOptions, focused: bool, sink: &ResultSink) {
+    match schema_issues(path, options.schema_dir.as_deref(), &sink.schemas) {
+        Ok(issues) => {
+            let mut errors = Vec::new();
+            for issue in issues {
+                if issue.is_error() {
+                    errors.push(issue.to_string());
+                } else {
+                    eprintln!("{} {}: {}", "Warning".yellow(), path.display(), issue);
+                }
+            }
+            if !errors.is_empty() {
+                sink.record_file_error(
+                    path.display().to_string(),
+                    format!("schema violations:\n      {}", errors.join("\n      ")),
+                    EXIT_FIXTURE_ERRORS,
+                    options,
+                );
+            }
         }
-        "indel" => {
-            rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars())
+        Err(SchemaError::Unresolved(message)) => {
+            eprintln!("{} {}: {}", "Warning".yellow(), path.display(), message);
         }
-        "lcs_seq" => {
-            rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars())
+        Err(SchemaError::Fatal(message)) => {
+            sink.record_file_error(
+                path.display().to_string(),
+                message,
+                EXIT_FIXTURE_ERRORS,
+                options,
+            );
+            return;
         }
-        "ratio" => rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars()) / 100.0, // ratio returns 0-100, normalize to 0-1
-        // TypeScript-only metrics - these can't be validated in Rust
-        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" => {
-            return ValidationResult {
-                file: file.to_string(),
+        Err(SchemaError::Unreadable(message)) => {
+            sink.record_file_error(path.display().to_string(), message, EXIT_IO_ERRORS, options);
+            return;
+        }
+    }
+
+    let fixture = match load_fixture(path) {
+        Ok(fixture) => fixture,
+        Err(e) => {
+            eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+            let code = if e.is::<std::io::Error>() {
+                EXIT_IO_ERRORS
+            } else {
+                EXIT_FIXTURE_ERRORS
+            };
+            sink.record_file_error(path.display().to_string(), e.to_string(), code, options);
+            return;
+        }
+    };
+    let filename = path.file_name().unwrap().to_string_lossy().to_string();
+    let path_key = path.display().to_string();
+    // Precedence: case > file > CLI > default
+    let file_tolerance = fixture.tolerance.unwrap_or_else(|| options.tolerance());
+
+    let cases: Vec<(usize, &str, Vec<String>, &TestCase)> = fixture
+        .test_cases
+        .iter()
+        .flat_map(|group| {
+            group.cases.iter().map(move |test| {
+                let tags = effective_tags(group.tags.as_deref(), test.tags.as_deref());
+                (group.category.as_str(), tags, test)
+            })
+        })
+        .enumerate()
+        .map(|(case_index, (category, tags, test))| (case_index, category, tags, test))
+        .collect();
+
+    cases
+        .par_iter()
+        .for_each(|&(case_index, category, ref tags, test)| {
+            if sink.stopped() {
+                return;
+            }
+            if !options.filter.matches(category, tags)
+                || !options.filter.matches_case(case_index, &test.description)
+                || (focused && !test.only)
+                || (options.wasm.is_some() && !wasm_supports(category, test))
+            {
+                sink.filtered.fetch_add(1, Ordering::SeqCst);
+                return;
+            }
+            let key = CaseKey {
+                path: path_key.clone(),
                 category: category.to_string(),
-                description: test.description.clone(),
-                passed: true,
-                expected: Some("(TypeScript implementation)".to_string()),
-                actual: Some("(skipped - validated by TS tests)".to_string()),
-                error: None,
+                case_index,
+            };
+            if test.skip && !options.include_skipped {
+                let skipped = SkippedCase {
+                    file: filename.clone(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    reason: test.skip_reason.clone(),
+                };
+                sink.skipped.lock().unwrap().push((key, skipped));
+                return;
+            }
+            if fixture.tolerance.is_some() || test.tolerance.is_some() {
+                sink.tolerance_overrides.fetch_add(1, Ordering::SeqCst);
+            }
+            let tol = test.tolerance.unwrap_or(file_tolerance);
+            let (mut result, elapsed) =
+                validate_with_timeout(&filename, category, test, tol, options);
+            if !result.passed {
+                result.repro = Some(repro_command(
+                    &path_key, category, case_index, test, options,
+                ));
             }
+            let outcome = CaseOutcome {
+                key,
+                tags: tags.clone(),
+                result,
+                elapsed,
+            };
+            sink.record(outcome, options);
+        });
+}
+
+/// A `validate` command line that re-runs one failed case with the run's result-affecting
+/// flags. The fixture path goes last, after `--`, so a leading `-` can't read as a flag.
+fn repro_command(
+    path: &str,
+    category: &str,
+    case_index: usize,
+    test: &TestCase,
+    options: &ValidateOptions,
+) -> String {
+    let program = std::env::args()
+        .next()
+        .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string());
+    let filter = CaseFilter {
+        categories: vec![category.to_string()],
+        case_index: Some(case_index),
+        description: Some(test.description.clone()),
+        ..CaseFilter::default()
+    };
+    let mut args = vec![
+        shell_quote(&program),
+        "validate".to_string(),
+        filter.describe(),
+    ];
+    if let Some(epsilon) = options.epsilon {
+        args.push(format!("--epsilon {}", epsilon));
+    }
+    if let Some(dir) = &options.schema_dir {
+        args.push(format!(
+            "--schema-dir {}",
+            shell_quote(&dir.display().to_string())
+        ));
+    }
+    if options.ignore_normalized {
+        args.push("--ignore-normalized".to_string());
+    }
+    if test.skip {
+        args.push("--include-skipped".to_string());
+    }
+    if options.timeout != Duration::from_millis(DEFAULT_TIMEOUT_MS) {
+        args.push(format!("--timeout-ms {}", options.timeout.as_millis()));
+    }
+    if let Some(wasm) = &options.wasm {
+        args.push("--target wasm".to_string());
+        if wasm.command != DEFAULT_NODE_RUNNER {
+            args.push(format!("--node-runner {}", shell_quote(&wasm.command)));
+        }
+    }
+    args.push(format!("-- {}", shell_quote(path)));
+    args.join(" ")
+}
+
+/// Quote `s` as one POSIX shell word: bare when every character is plainly safe, otherwise
+/// single-quoted. Nothing is special inside single quotes (unicode, `"`, `$`, `!` included)
+/// except the quote itself, which closes the string, is escaped, and reopens it: `'\''`.
+fn shell_quote(s: &str) -> String {
+    let bare = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if bare {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
+
+/// Whether any case in the fixture carries an `only` focus marker
+fn has_focused_cases(fixture: &Fixture) -> bool {
+    fixture
+        .test_cases
+        .iter()
+        .flat_map(|group| &group.cases)
+        .any(|case| case.only)
+}
+
+fn load_fixture(path: &Path) -> Result<Fixture, Box<dyn std::error::Error>> {
+    let format = FixtureFormat::from_path(path)?;
+    let contents = fs::read_to_string(path)?;
+    parse_fixture(format, &contents)
+}
+
+fn validate_test_case(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+    ignore_normalized: bool,
+) -> ValidationResult {
+    if let Some(expected_error) = &test.expect_error {
+        return validate_expected_error(file, category, test, expected_error);
+    }
+
+    match category {
+        "levenshtein" => validate_levenshtein(file, category, test, tol),
+        "damerau_osa" => validate_damerau_osa(file, category, test, tol),
+        "damerau_unrestricted" => validate_damerau_unrestricted(file, category, test, tol),
+        "damerau_graphemes" => validate_damerau_graphemes(file, category, test, tol),
+        "damerau_weighted" => validate_damerau_weighted(file, category, test, tol),
+        "mfc" => validate_mfc(file, category, test, tol),
+        "affine_gap" => validate_affine_gap(file, category, test, tol),
+        "jaro_winkler" => validate_jaro_winkler(file, category, test, tol),
+        "jaro_winkler_params" => validate_jaro_winkler_params(file, category, test, tol),
+        "jaro_tokens" | "jaro_winkler_tokens" => validate_jaro_tokens(file, category, test, tol),
+        "indel" => validate_indel(file, category, test, tol),
+        "lcs_seq" => validate_lcs_seq(file, category, test, tol),
+        "ratio" => validate_ratio(file, category, test, tol),
+        "hamming" => validate_hamming(file, category, test, tol),
+        "byte_metrics" => validate_byte_metrics(file, category, test, tol),
+        "positional_diff" => validate_positional_diff(file, category, test),
+        "prefix" => validate_prefix(file, category, test, tol),
+        "postfix" => validate_postfix(file, category, test, tol),
+        "dice" => validate_dice(file, category, test, tol),
+        "jaccard_ngram" => validate_jaccard_ngram(file, category, test, tol),
+        "weighted_jaccard" => validate_weighted_jaccard(file, category, test, tol),
+        "substring" => validate_substring(file, category, test, tol),
+        "coverage" => validate_coverage(file, category, test),
+        "excerpt" => validate_excerpt(file, category, test),
+        "empty_inputs" => validate_empty_inputs(file, category, test, tol),
+        "window_scores" => validate_window_scores(file, category, test, tol),
+        "fuzzy_find" => validate_fuzzy_find(file, category, test),
+        "normalization_presets" => validate_normalization(file, category, test),
+        "normalization_locale" => validate_normalization_locale(file, category, test),
+        "numeric_tokens" => validate_numeric_tokens(file, category, test),
+        "case_transfer" => validate_case_transfer(file, category, test),
+        "normalized_key" => validate_normalized_key(file, category, test),
+        "script_detection" => validate_script_detection(file, category, test),
+        "suggestions" => validate_suggestions(file, category, test, tol, ignore_normalized),
+        "did_you_mean" => validate_did_you_mean(file, category, test, tol),
+        "unified_distance" => validate_unified_distance(file, category, test),
+        "unified_score" => validate_unified_score(file, category, test, tol),
+        "anchored_similarity" => validate_anchored_similarity(file, category, test, tol),
+        "score_nullable" => validate_score_nullable(file, category, test, tol),
+        "extract_one" | "extract" => validate_extract(file, category, test, tol),
+        "editops" | "opcodes" => validate_edit_operations(file, category, test),
+        "word_diff" => validate_word_diff(file, category, test),
+        "match_profile" => validate_match_profile(file, category, test),
+        "properties" => validate_properties(file, category, test, tol),
+        // TypeScript-only categories - validated by TypeScript test suite
+        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: true,
+            expected: Some("(TypeScript implementation)".to_string()),
+            actual: Some("(skipped - validated by TS tests)".to_string()),
+            error: None,
+            repro: None,
+        },
+        _ => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: None,
+            actual: None,
+            error: Some(format!("Unknown category: {}", category)),
+            repro: None,
+        },
+    }
+}
+
+/// Compare an expected score with a computed one within an absolute tolerance
+fn scores_match(expected: f64, actual: f64, tol: f64) -> bool {
+    (expected - actual).abs() < tol
+}
+
+/// Check a computed score against the case's `expected_score` and `expected_score_range`
+fn score_expectation_met(test: &TestCase, actual: f64, tol: f64) -> bool {
+    test.expected_score
+        .is_none_or(|exp| scores_match(exp, actual, tol))
+        && test
+            .expected_score_range
+            .as_ref()
+            .is_none_or(|range| range.contains(actual))
+}
+
+/// Human-readable score expectation for failure output
+fn expected_score_label(test: &TestCase) -> String {
+    match (test.expected_score, &test.expected_score_range) {
+        (Some(score), Some(range)) => format!("{} within [{}, {}]", score, range.min, range.max),
+        (None, Some(range)) => format!("[{}, {}]", range.min, range.max),
+        (score, None) => format!("{:?}", score),
+    }
+}
+
+/// Parse `--epsilon`, rejecting negative or non-finite values
+fn parse_tolerance(value: &str) -> Result<f64, String> {
+    let tol: f64 = value.parse().map_err(|e| format!("{}", e))?;
+    if tol.is_finite() && tol >= 0.0 {
+        Ok(tol)
+    } else {
+        Err(format!(
+            "tolerance must be a finite, non-negative number (got {})",
+            value
+        ))
+    }
+}
+
+/// Run a negative case: pass only if the computation fails with a matching error
+fn validate_expected_error(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    expected_error: &str,
+) -> ValidationResult {
+    let outcome = match category {
+        "normalization_presets" => {
+            let input = get_string_input(&test.inputs, "input").unwrap_or_default();
+            let preset = get_string_input(&test.inputs, "preset").unwrap_or_default();
+            try_normalize_with_options(&input, &preset, &normalize_options(&test.inputs, None))
+                .map(|n| format!("\"{}\"", n))
+        }
+        "unified_score" => {
+            let metric = get_string_input(&test.inputs, "metric")
+                .unwrap_or_else(|| "jaro_winkler".to_string());
+            case_unified_score(&test.inputs, &metric).map(|s| format!("score={}", s))
+        }
+        "unified_distance" => {
+            let metric = get_string_input(&test.inputs, "metric")
+                .unwrap_or_else(|| "levenshtein".to_string());
+            case_unified_distance(&test.inputs, &metric).map(|d| format!("distance={}", d))
+        }
+        "anchored_similarity" => {
+            case_anchored_similarity(&test.inputs).map(|s| format!("score={}", s))
+        }
+        "score_nullable" => score_nullable_fields(&test.inputs)
+            .map(|fields| serde_json::to_string(&fields).unwrap_or_default()),
+        "hamming" => {
+            let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+            let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+            string_metrics_core::hamming_distance(&input_a, &input_b, hamming_pad(&test.inputs))
+                .map(|d| format!("distance={}", d))
+        }
+        "byte_metrics" => {
+            byte_metric_values(&test.inputs).map(|(d, s)| format!("distance={}, score={}", d, s))
+        }
+        "positional_diff" => positional_diff_fields(&test.inputs).map(|fields| {
+            format!(
+                "distance={}",
+                fields.get("distance").and_then(|d| d.as_u64()).unwrap_or(0)
+            )
+        }),
+        "affine_gap" => affine_gap_values(&test.inputs).map(|(cost, _)| format!("cost={}", cost)),
+        "excerpt" => excerpt_fields(&test.inputs).map(|fields| {
+            format!(
+                "text={:?}",
+                fields
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+            )
+        }),
+        "fuzzy_find" => fuzzy_matches(&test.inputs).map(|found| format!("{} matches", found.len())),
+        "window_scores" => {
+            case_window_scores(&test.inputs).map(|scores| format!("{} windows", scores.len()))
+        }
+        "empty_inputs" => {
+            empty_inputs_fields(&test.inputs).map(|fields| format!("{} fields", fields.len()))
+        }
+        "word_diff" => word_diffs(&test.inputs).map(|ops| format!("{} ops", ops.len())),
+        "match_profile" => {
+            match_profile_classes(&test.inputs).map(|classes| format!("{} chars", classes.len()))
+        }
+        "jaccard_ngram" => {
+            let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+            let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+            string_metrics_core::jaccard_ngram(&input_a, &input_b, ngram_size(&test.inputs))
+                .map(|s| format!("score={}", s))
         }
+        "weighted_jaccard" => case_weighted_jaccard(&test.inputs).map(|s| format!("score={}", s)),
+        "extract_one" | "extract" => run_extraction(category, &ExtractionQuery::from_case(test))
+            .map(|results| format!("{} results", results.len())),
         _ => {
             return ValidationResult {
                 file: file.to_string(),
@@ -571,978 +2396,10129 @@ fn validate_unified_score(file: &str, category: &str, test: &TestCase) -> Valida
                 passed: false,
                 expected: None,
                 actual: None,
-                error: Some(format!("Unknown score metric: {}", metric)),
+                error: Some(format!(
+                    "expect_error is not supported for category: {}",
+                    category
+                )),
+                repro: None,
             }
         }
     };
 
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+    let (passed, actual, error) = match outcome {
+        Ok(value) => (
+            false,
+            value,
+            Some("Computation succeeded but an error was expected".to_string()),
+        ),
+        Err(e) if e.contains(expected_error) => (true, format!("error={:?}", e), None),
+        Err(e) => (
+            false,
+            format!("error={:?}", e),
+            Some("Error mismatch".to_string()),
+        ),
+    };
 
     ValidationResult {
         file: file.to_string(),
         category: category.to_string(),
         description: test.description.clone(),
-        passed: score_matches,
-        expected: Some(format!("score={:?}", test.expected_score)),
-        actual: Some(format!("score={}", actual_score)),
-        error: None,
+        passed,
+        expected: Some(format!("error containing {:?}", expected_error)),
+        actual: Some(actual),
+        error,
+        repro: None,
     }
 }
 
-fn validate_substring(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let needle = get_string_input(&test.inputs, "needle").unwrap_or_default();
-    let haystack = get_string_input(&test.inputs, "haystack").unwrap_or_default();
+fn get_string_input(inputs: &serde_yaml::Mapping, key: &str) -> Option<String> {
+    inputs
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
 
-    let (actual_score, actual_range) = compute_substring_similarity(&needle, &haystack);
+fn validate_levenshtein(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
 
-    let score_matches = test
-        .expected_score
-        .map_or(true, |exp| (exp - actual_score).abs() < 1e-10);
+    let actual_distance =
+        rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::levenshtein::normalized_similarity(input_a.chars(), input_b.chars());
 
-    let range_matches = match (&test.expected_range, &actual_range) {
-        (Some(exp), Some(act)) => exp.start == act.start && exp.end == act.end,
-        (None, None) => true,
-        _ => false,
-    };
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
 
     ValidationResult {
         file: file.to_string(),
         category: category.to_string(),
         description: test.description.clone(),
-        passed: score_matches && range_matches,
+        passed: distance_matches && score_matches,
         expected: Some(format!(
-            "score={:?}, range={:?}",
-            test.expected_score, test.expected_range
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
         )),
-        actual: Some(format!("score={}, range={:?}", actual_score, actual_range)),
         error: None,
+        repro: None,
     }
 }
 
-fn validate_normalization(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
-    let preset = get_string_input(&test.inputs, "preset").unwrap_or_default();
+fn validate_damerau_osa(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
 
-    let actual_normalized = normalize(&input, &preset);
+    let actual_distance = rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars());
 
-    let expected_normalized = test
-        .expected
-        .as_ref()
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
 
-    let matches = actual_normalized == expected_normalized;
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_damerau_unrestricted(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_distance =
+        rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars());
+    let actual_score = rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
+        input_a.chars(),
+        input_b.chars(),
+    );
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
 
     ValidationResult {
         file: file.to_string(),
         category: category.to_string(),
         description: test.description.clone(),
-        passed: matches,
-        expected: Some(format!("\"{}\"", expected_normalized)),
-        actual: Some(format!("\"{}\"", actual_normalized)),
-        error: if matches {
-            None
-        } else {
-            Some("Normalization mismatch".to_string())
-        },
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
     }
 }
 
-fn validate_normalization_locale(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
-    let preset = get_string_input(&test.inputs, "preset").unwrap_or_default();
-    let locale = test
-        .inputs
-        .get("locale")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+fn validate_damerau_graphemes(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
 
-    let actual_normalized = normalize_with_locale(&input, &preset, locale.as_deref());
+    let actual_distance = string_metrics_core::damerau_levenshtein_graphemes(&input_a, &input_b);
+    let actual_score = string_metrics_core::damerau_levenshtein_graphemes_normalized_similarity(
+        &input_a, &input_b,
+    );
 
-    let expected_normalized = test
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Total cost and normalized score for `damerau_weighted`; the core clamps the cost
+fn damerau_weighted_values(inputs: &serde_yaml::Mapping) -> (f64, f64) {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let cost = inputs
+        .get("transposition_cost")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0);
+    (
+        string_metrics_core::damerau_levenshtein_weighted(&input_a, &input_b, cost),
+        string_metrics_core::damerau_levenshtein_weighted_normalized_similarity(
+            &input_a, &input_b, cost,
+        ),
+    )
+}
+
+/// `expected` is the total cost (an f64, so not `expected_distance`)
+fn validate_damerau_weighted(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let (actual_cost, actual_score) = damerau_weighted_values(&test.inputs);
+    let expected_cost = test.expected.as_ref().and_then(|v| v.as_f64());
+
+    let cost_matches = expected_cost.is_none_or(|exp| scores_match(exp, actual_cost, tol));
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: cost_matches && score_matches,
+        expected: Some(format!(
+            "cost={:?}, score={}",
+            expected_cost,
+            expected_score_label(test)
+        )),
+        actual: Some(format!("cost={}, score={}", actual_cost, actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Both signatures and the similarity for an `mfc` case
+fn mfc_values(inputs: &serde_yaml::Mapping) -> (Vec<String>, f64) {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let k = inputs.get("k").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    (
+        vec![
+            string_metrics_core::mfc_signature(&input_a, k),
+            string_metrics_core::mfc_signature(&input_b, k),
+        ],
+        string_metrics_core::mfc_similarity(&input_a, &input_b, k),
+    )
+}
+
+/// `expected` is the pair of signatures; scoring those signatures must reproduce the score
+fn validate_mfc(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let (signatures, actual_score) = mfc_values(&test.inputs);
+    let expected_signatures: Option<Vec<String>> = test
         .expected
         .as_ref()
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
+        .and_then(|v| serde_yaml::from_value(v.clone()).ok());
 
-    let matches = actual_normalized == expected_normalized;
+    let signatures_match = expected_signatures
+        .as_ref()
+        .is_none_or(|expected| *expected == signatures);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+    let stored_score =
+        string_metrics_core::mfc_signature_similarity(&signatures[0], &signatures[1]);
+    let round_trips = stored_score
+        .as_ref()
+        .is_ok_and(|&stored| scores_match(stored, actual_score, tol));
 
     ValidationResult {
         file: file.to_string(),
         category: category.to_string(),
         description: test.description.clone(),
-        passed: matches,
-        expected: Some(format!("\"{}\"", expected_normalized)),
-        actual: Some(format!("\"{}\"", actual_normalized)),
-        error: if matches {
-            None
-        } else {
-            Some(format!("Normalization mismatch (locale={:?})", locale))
-        },
+        passed: signatures_match && score_matches && round_trips,
+        expected: Some(format!(
+            "signatures={:?}, score={}",
+            expected_signatures,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "signatures={:?}, score={}",
+            signatures, actual_score
+        )),
+        error: (!round_trips).then(|| {
+            format!(
+                "Scoring the signatures gives {:?}, not the direct score",
+                stored_score
+            )
+        }),
+        repro: None,
     }
 }
 
-fn validate_suggestions(file: &str, category: &str, test: &TestCase) -> ValidationResult {
-    // Extract inputs
-    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
-    let candidates = test
-        .inputs
-        .get("candidates")
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_default();
+/// Total cost and normalized score for `affine_gap`, or the penalty validation error
+fn affine_gap_values(inputs: &serde_yaml::Mapping) -> Result<(f64, f64), String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let penalty = |key: &str| inputs.get(key).and_then(|v| v.as_f64()).unwrap_or(1.0);
+    let (mismatch, gap_open, gap_extend) = (
+        penalty("mismatch"),
+        penalty("gap_open"),
+        penalty("gap_extend"),
+    );
+    Ok((
+        string_metrics_core::affine_gap_distance(
+            &input_a, &input_b, mismatch, gap_open, gap_extend,
+        )?,
+        string_metrics_core::affine_gap_normalized_similarity(
+            &input_a, &input_b, mismatch, gap_open, gap_extend,
+        )?,
+    ))
+}
 
-    // Extract options
-    let options = test
-        .inputs
-        .get("options")
-        .and_then(|v| v.as_mapping())
-        .cloned()
-        .unwrap_or_default();
+/// `expected` is the total cost (an f64, so not `expected_distance`), as for `damerau_weighted`
+fn validate_affine_gap(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let (actual_cost, actual_score) = match affine_gap_values(&test.inputs) {
+        Ok(values) => values,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: Some(format!("error={:?}", e)),
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let expected_cost = test.expected.as_ref().and_then(|v| v.as_f64());
+
+    let cost_matches = expected_cost.is_none_or(|exp| scores_match(exp, actual_cost, tol));
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: cost_matches && score_matches,
+        expected: Some(format!(
+            "cost={:?}, score={}",
+            expected_cost,
+            expected_score_label(test)
+        )),
+        actual: Some(format!("cost={}, score={}", actual_cost, actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_jaro_winkler(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_score =
+        rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars());
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_jaro_winkler_params(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let actual_score = jaro_winkler_params_score(&test.inputs);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Score from the library's own `jaro_winkler_with_params` export (it does not delegate to
+/// rapidfuzz's prefix logic). Missing parameters take the TypeScript wrapper's defaults.
+fn jaro_winkler_params_score(inputs: &serde_yaml::Mapping) -> f64 {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let prefix_scale = inputs
+        .get("prefix_scale")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.1);
+    let max_prefix = inputs
+        .get("max_prefix")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(4) as usize;
+    string_metrics_core::jaro_winkler_with_params(&input_a, &input_b, prefix_scale, max_prefix)
+}
+
+fn validate_jaro_tokens(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let actual_score = match jaro_tokens_score(category, &test.inputs) {
+        Ok(score) => score,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: Some(format!("score={}", expected_score_label(test))),
+                actual: Some(format!("error={:?}", e)),
+                error: Some(e),
+                repro: None,
+            };
+        }
+    };
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Token-level Jaro (`jaro_tokens`) or Jaro-Winkler (`jaro_winkler_tokens`). A missing
+/// `match_threshold` is 0.9, `prefix_scale` 0.1 and `tokenizer` whitespace, as in the
+/// TypeScript wrapper; an unknown tokenizer is an error.
+fn jaro_tokens_score(category: &str, inputs: &serde_yaml::Mapping) -> Result<f64, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let threshold = inputs
+        .get("match_threshold")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(string_metrics_core::DEFAULT_TOKEN_MATCH_THRESHOLD);
+    let tokenizer = match get_string_input(inputs, "tokenizer") {
+        Some(name) => Tokenizer::parse(&name)?,
+        None => Tokenizer::Whitespace,
+    };
+    if category == "jaro_tokens" {
+        return Ok(string_metrics_core::jaro_tokens(
+            &input_a, &input_b, threshold, tokenizer,
+        ));
+    }
+    let prefix_scale = inputs
+        .get("prefix_scale")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.1);
+    Ok(string_metrics_core::jaro_winkler_tokens(
+        &input_a,
+        &input_b,
+        prefix_scale,
+        threshold,
+        tokenizer,
+    ))
+}
+
+fn validate_indel(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_distance = rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars());
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_lcs_seq(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_distance = rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars());
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_ratio(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_score = rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars());
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Normalization options of a case: `locale`, `strip_ignorables` (missing follows the
+/// preset), `keep_joiners` and `fold_numbers`
+fn normalize_options<'a>(
+    inputs: &serde_yaml::Mapping,
+    locale: Option<&'a str>,
+) -> NormalizeOptions<'a> {
+    NormalizeOptions {
+        locale,
+        expansions: None,
+        strip_ignorables: inputs.get("strip_ignorables").and_then(|v| v.as_bool()),
+        keep_joiners: inputs
+            .get("keep_joiners")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        fold_numbers: inputs
+            .get("fold_numbers")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    }
+}
+
+/// `pad` lets unequal lengths through; without it they are an error
+fn hamming_pad(inputs: &serde_yaml::Mapping) -> bool {
+    inputs.get("pad").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Library output for a `positional_diff` case: the `distance` and the ascending char
+/// positions behind it, or the unequal-length error when `pad` is not set
+fn positional_diff_fields(inputs: &serde_yaml::Mapping) -> Result<serde_yaml::Mapping, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let diff = string_metrics_core::positional_diff(&input_a, &input_b, hamming_pad(inputs))?;
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("distance".into(), (diff.distance as u64).into());
+    map.insert(
+        "differing_positions".into(),
+        serde_yaml::Value::Sequence(
+            diff.differing_positions
+                .into_iter()
+                .map(|position| (position as u64).into())
+                .collect(),
+        ),
+    );
+    Ok(map)
+}
+
+fn validate_positional_diff(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    match positional_diff_fields(&test.inputs) {
+        Ok(fields) => validate_fields(
+            file,
+            category,
+            test,
+            fields,
+            "distance and/or differing_positions",
+            "Positional diff mismatch",
+        ),
+        Err(e) => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: None,
+            actual: Some(format!("error={:?}", e)),
+            error: Some(e),
+            repro: None,
+        },
+    }
+}
+
+/// N-gram size for `jaccard_ngram`; a missing `n` means bigrams
+fn ngram_size(inputs: &serde_yaml::Mapping) -> usize {
+    inputs
+        .get("n")
+        .and_then(|v| v.as_u64())
+        .map_or(DEFAULT_NGRAM_SIZE, |n| n as usize)
+}
+
+fn validate_hamming(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+    let pad = hamming_pad(&test.inputs);
+
+    let computed = string_metrics_core::hamming_distance(&input_a, &input_b, pad).and_then(|d| {
+        string_metrics_core::hamming_normalized_similarity(&input_a, &input_b, pad).map(|s| (d, s))
+    });
+    let (actual_distance, actual_score) = match computed {
+        Ok(values) => values,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Library output for a `byte_metrics` case: the distance and normalized similarity of
+/// `metric` ("levenshtein" or "hamming", honoring `pad`) over the base64-decoded inputs
+fn byte_metric_values(inputs: &serde_yaml::Mapping) -> Result<(usize, f64), String> {
+    let decode = |key: &str| {
+        BASE64_STANDARD
+            .decode(get_string_input(inputs, key).unwrap_or_default())
+            .map_err(|e| format!("{} is not valid base64: {}", key, e))
+    };
+    let (a, b) = (decode("bytes_a")?, decode("bytes_b")?);
+    let metric = get_string_input(inputs, "metric").unwrap_or_default();
+    match metric.as_str() {
+        "levenshtein" => Ok((
+            string_metrics_core::levenshtein_bytes(&a, &b),
+            string_metrics_core::normalized_levenshtein_bytes(&a, &b),
+        )),
+        "hamming" => {
+            let pad = hamming_pad(inputs);
+            Ok((
+                string_metrics_core::hamming_bytes(&a, &b, pad)?,
+                string_metrics_core::normalized_hamming_bytes(&a, &b, pad)?,
+            ))
+        }
+        _ => Err(format!("Unknown byte metric: {}", metric)),
+    }
+}
+
+fn validate_byte_metrics(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let (actual_distance, actual_score) = match byte_metric_values(&test.inputs) {
+        Ok(values) => values,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_prefix(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_distance = rapidfuzz::distance::prefix::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::prefix::normalized_similarity(input_a.chars(), input_b.chars());
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_postfix(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_distance = rapidfuzz::distance::postfix::distance(input_a.chars(), input_b.chars());
+    let actual_score =
+        rapidfuzz::distance::postfix::normalized_similarity(input_a.chars(), input_b.chars());
+
+    let distance_matches = test
+        .expected_distance
+        .is_none_or(|exp| exp == actual_distance);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches && score_matches,
+        expected: Some(format!(
+            "distance={:?}, score={}",
+            test.expected_distance,
+            expected_score_label(test)
+        )),
+        actual: Some(format!(
+            "distance={}, score={}",
+            actual_distance, actual_score
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_dice(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_score = string_metrics_core::dice(&input_a, &input_b);
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_jaccard_ngram(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+
+    let actual_score =
+        match string_metrics_core::jaccard_ngram(&input_a, &input_b, ngram_size(&test.inputs)) {
+            Ok(score) => score,
+            Err(e) => {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: None,
+                    actual: None,
+                    error: Some(e),
+                    repro: None,
+                }
+            }
+        };
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// `weighted_jaccard` of a case's `tokens_a` and `tokens_b` (mappings of token to weight)
+/// under its optional `preset` (default "none") and `locale`
+fn case_weighted_jaccard(inputs: &serde_yaml::Mapping) -> Result<f64, String> {
+    let token_weights = |key: &str| -> Result<Vec<(String, f64)>, String> {
+        let Some(weights) = inputs.get(key) else {
+            return Ok(Vec::new());
+        };
+        let weights = weights
+            .as_mapping()
+            .ok_or_else(|| format!("{} must be a mapping of token to weight", key))?;
+        weights
+            .iter()
+            .map(|(token, weight)| {
+                let token = token
+                    .as_str()
+                    .ok_or_else(|| format!("{} tokens must be strings (got {:?})", key, token))?;
+                let weight = weight
+                    .as_f64()
+                    .ok_or_else(|| format!("{} weight of {:?} must be a number", key, token))?;
+                Ok((token.to_string(), weight))
+            })
+            .collect()
+    };
+    let (a, b) = (token_weights("tokens_a")?, token_weights("tokens_b")?);
+    let a: Vec<(&str, f64)> = a.iter().map(|(t, w)| (t.as_str(), *w)).collect();
+    let b: Vec<(&str, f64)> = b.iter().map(|(t, w)| (t.as_str(), *w)).collect();
+    let preset = get_string_input(inputs, "preset").unwrap_or_else(|| "none".to_string());
+    let locale = get_string_input(inputs, "locale");
+    string_metrics_core::weighted_jaccard(&a, &b, &preset, locale.as_deref())
+}
+
+fn validate_weighted_jaccard(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let actual_score = match case_weighted_jaccard(&test.inputs) {
+        Ok(score) => score,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Units a unified case's optional `digraphs` lists; none when missing or empty
+fn case_digraphs(inputs: &serde_yaml::Mapping) -> Result<Option<Digraphs>, String> {
+    let Some(units) = inputs.get("digraphs").and_then(|v| v.as_sequence()) else {
+        return Ok(None);
+    };
+    let units: Vec<&str> = units.iter().filter_map(|v| v.as_str()).collect();
+    let digraphs = Digraphs::new(&units)?;
+    Ok((!digraphs.is_empty()).then_some(digraphs))
+}
+
+/// Keys a unified case's `options` mapping may set: the `PairOptions` of the core `Comparator`
+const UNIFIED_OPTIONS: &[&str] = &["preset", "locale", "score_cutoff"];
+
+/// `PairOptions` from a unified case's `options` mapping, None without one. An unknown key or a
+/// value of the wrong type is an error, so a typo can't quietly test the defaults instead.
+fn unified_options(inputs: &serde_yaml::Mapping) -> Result<Option<PairOptions>, String> {
+    let Some(options) = inputs.get("options") else {
+        return Ok(None);
+    };
+    let options = options
+        .as_mapping()
+        .ok_or_else(|| "Unified options must be a mapping".to_string())?;
+    let mut pair_options = PairOptions::default();
+    for (key, value) in options {
+        let key = key.as_str().unwrap_or_default();
+        let text = || {
+            value
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("Unified option {} must be a string", key))
+        };
+        match key {
+            "preset" => {
+                let preset = text()?;
+                // The comparator passes unknown presets through; fixtures shouldn't
+                try_normalize_with_locale("", &preset, None)?;
+                pair_options.preset = preset;
+            }
+            "locale" => pair_options.locale = Some(text()?),
+            "score_cutoff" => {
+                pair_options.score_cutoff =
+                    Some(value.as_f64().ok_or_else(|| {
+                        "Unified option score_cutoff must be a number".to_string()
+                    })?)
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown unified option: {} (expected one of {})",
+                    key,
+                    UNIFIED_OPTIONS.join(", ")
+                ))
+            }
+        }
+    }
+    Ok(Some(pair_options))
+}
+
+/// A unified case's `options` as a core `Comparator`, None without them; `digraphs` have no
+/// comparator, so a case can't set both
+fn case_comparator(
+    inputs: &serde_yaml::Mapping,
+    metric: &str,
+) -> Result<Option<Comparator>, String> {
+    let Some(options) = unified_options(inputs)? else {
+        return Ok(None);
+    };
+    if inputs.contains_key("digraphs") {
+        return Err("Unified options can't be combined with digraphs".to_string());
+    }
+    Comparator::new(metric, options).map(Some)
+}
+
+/// `unified_distance` of a case's `input_a` and `input_b`, counting its `digraphs` as one symbol
+/// or under its `options`
+fn case_unified_distance(inputs: &serde_yaml::Mapping, metric: &str) -> Result<usize, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    if let Some(mut comparator) = case_comparator(inputs, metric)? {
+        return comparator.distance(&input_a, &input_b);
+    }
+    match case_digraphs(inputs)? {
+        Some(digraphs) => string_metrics_core::unified_distance_with_digraphs(
+            &input_a, &input_b, metric, &digraphs,
+        ),
+        None => string_metrics_core::unified_distance(&input_a, &input_b, metric),
+    }
+}
+
+/// `unified_score` of a case's `input_a` and `input_b`, counting its `digraphs` as one symbol
+/// or under its `options`
+fn case_unified_score(inputs: &serde_yaml::Mapping, metric: &str) -> Result<f64, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    if let Some(mut comparator) = case_comparator(inputs, metric)? {
+        return comparator.score(&input_a, &input_b);
+    }
+    match case_digraphs(inputs)? {
+        Some(digraphs) => {
+            string_metrics_core::unified_score_with_digraphs(&input_a, &input_b, metric, &digraphs)
+        }
+        None => string_metrics_core::unified_score(&input_a, &input_b, metric),
+    }
+}
+
+/// A unified case's expected distance: `expected`, as the unified fixtures write it, or
+/// `expected_distance`
+fn unified_expected_distance(test: &TestCase) -> Option<usize> {
+    test.expected_distance.or_else(|| {
+        test.expected
+            .as_ref()
+            .and_then(|v| v.as_u64())
+            .map(|d| d as usize)
+    })
+}
+
+/// A unified case's expected score: `expected`, as the unified fixtures write it, or
+/// `expected_score`
+fn unified_expected_score(test: &TestCase) -> Option<f64> {
+    test.expected_score
+        .or_else(|| test.expected.as_ref().and_then(|v| v.as_f64()))
+}
+
+fn validate_unified_distance(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let metric =
+        get_string_input(&test.inputs, "metric").unwrap_or_else(|| "levenshtein".to_string());
+
+    let actual_distance = match case_unified_distance(&test.inputs, &metric) {
+        Ok(distance) => distance,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let expected_distance = unified_expected_distance(test);
+    let distance_matches = expected_distance.is_none_or(|exp| exp == actual_distance);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: distance_matches,
+        expected: Some(format!("distance={:?}", expected_distance)),
+        actual: Some(format!("distance={}", actual_distance)),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_unified_score(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let metric =
+        get_string_input(&test.inputs, "metric").unwrap_or_else(|| "jaro_winkler".to_string());
+
+    // TypeScript-only metrics - these can't be validated in Rust
+    if matches!(
+        metric.as_str(),
+        "partial_ratio" | "token_sort_ratio" | "token_set_ratio"
+    ) {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: true,
+            expected: Some("(TypeScript implementation)".to_string()),
+            actual: Some("(skipped - validated by TS tests)".to_string()),
+            error: None,
+            repro: None,
+        };
+    }
+
+    let actual_score = match case_unified_score(&test.inputs, &metric) {
+        Ok(score) => score,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let expected_score = unified_expected_score(test);
+    let score_matches = expected_score.is_none_or(|exp| scores_match(exp, actual_score, tol))
+        && score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={:?}", expected_score)),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// `anchored_similarity` of a case's `input_a` and `input_b`; `preset` defaults to "none"
+fn case_anchored_similarity(inputs: &serde_yaml::Mapping) -> Result<f64, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let metric = get_string_input(inputs, "metric").unwrap_or_default();
+    let side = AnchorSide::parse(&get_string_input(inputs, "side").unwrap_or_default())?;
+    let length = inputs.get("length").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let preset = get_string_input(inputs, "preset").unwrap_or_else(|| "none".to_string());
+    let locale = get_string_input(inputs, "locale");
+    string_metrics_core::anchored_similarity(
+        &input_a,
+        &input_b,
+        Anchor { side, length },
+        &metric,
+        &preset,
+        locale.as_deref(),
+    )
+}
+
+fn validate_anchored_similarity(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let actual_score = match case_anchored_similarity(&test.inputs) {
+        Ok(score) => score,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches,
+        expected: Some(format!("score={}", expected_score_label(test))),
+        actual: Some(format!("score={}", actual_score)),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Library output for a `score_nullable` case: the `score` (null when the `missing` policy,
+/// default "ignore", leaves it out) and the presence `status`; an omitted or null
+/// `input_a`/`input_b` is missing and `preset` defaults to "none"
+fn score_nullable_fields(inputs: &serde_yaml::Mapping) -> Result<serde_yaml::Mapping, String> {
+    let input_a = get_string_input(inputs, "input_a");
+    let input_b = get_string_input(inputs, "input_b");
+    let metric = get_string_input(inputs, "metric").unwrap_or_default();
+    let missing = MissingPolicy::parse(
+        &get_string_input(inputs, "missing").unwrap_or_else(|| "ignore".to_string()),
+    )?;
+    let options = PairOptions {
+        preset: get_string_input(inputs, "preset").unwrap_or_else(|| "none".to_string()),
+        locale: get_string_input(inputs, "locale"),
+        score_cutoff: None,
+    };
+    let result = string_metrics_core::score_nullable(
+        input_a.as_deref(),
+        input_b.as_deref(),
+        &metric,
+        &options,
+        missing,
+    )?;
+
+    let mut map = serde_yaml::Mapping::new();
+    map.insert(
+        "score".into(),
+        result.score.map_or(serde_yaml::Value::Null, Into::into),
+    );
+    map.insert("status".into(), result.status.as_str().into());
+    Ok(map)
+}
+
+fn validate_score_nullable(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let actual = match score_nullable_fields(&test.inputs) {
+        Ok(fields) => serde_yaml::Value::Mapping(fields),
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let compact = |value: &serde_yaml::Value| serde_json::to_string(value).unwrap_or_default();
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: test
+            .expected
+            .as_ref()
+            .is_some_and(|expected| yaml_values_match(expected, &actual, tol)),
+        expected: test.expected.as_ref().map(compact),
+        actual: Some(compact(&actual)),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_substring(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let needle = get_string_input(&test.inputs, "needle").unwrap_or_default();
+    let haystack = get_string_input(&test.inputs, "haystack").unwrap_or_default();
+    let options = match substring_options(&test.inputs) {
+        Ok(options) => options,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let (actual_score, actual_range) =
+        string_metrics_core::substring_similarity(&needle, &haystack, options);
+
+    let score_matches = score_expectation_met(test, actual_score, tol);
+
+    let range_matches = match (&test.expected_range, &actual_range) {
+        (Some(exp), Some(act)) => exp.start == act.start && exp.end == act.end,
+        (None, None) => true,
+        _ => false,
+    };
+    // Every tied occurrence, checked only when the case lists them
+    let all_ranges: Option<Vec<Range>> = test.expected_ranges.as_ref().map(|_| {
+        string_metrics_core::substring_all_ranges(&needle, &haystack, options.range_unit)
+            .1
+            .into_iter()
+            .map(Range::from)
+            .collect()
+    });
+    let all_ranges_match = all_ranges == test.expected_ranges;
+    let show_all = |ranges: &Option<Vec<Range>>| {
+        ranges
+            .as_ref()
+            .map_or(String::new(), |ranges| format!(", ranges={:?}", ranges))
+    };
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: score_matches && range_matches && all_ranges_match,
+        expected: Some(format!(
+            "score={}, range={:?}{}",
+            expected_score_label(test),
+            test.expected_range,
+            show_all(&test.expected_ranges)
+        )),
+        actual: Some(format!(
+            "score={}, range={:?}{}",
+            actual_score,
+            actual_range,
+            show_all(&all_ranges)
+        )),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Library output for a `coverage` case: contiguous `score` and `range`, the gap-tolerant
+/// `subsequence_score` and `subsequence_ranges`, and the `substring_score` the same pair gets
+/// from `substring_similarity`, so fixtures can pin where the two disagree
+fn coverage_fields(inputs: &serde_yaml::Mapping) -> Result<serde_yaml::Mapping, String> {
+    let needle = get_string_input(inputs, "needle").unwrap_or_default();
+    let haystack = get_string_input(inputs, "haystack").unwrap_or_default();
+    let options = substring_options(inputs)?;
+    let range_value =
+        |range: MatchRange| serde_yaml::to_value(Range::from(range)).expect("ranges serialize");
+
+    let (score, range) = string_metrics_core::coverage_score(&needle, &haystack, options);
+    let (subsequence_score, subsequence_ranges) =
+        string_metrics_core::subsequence_coverage_score(&needle, &haystack, options.range_unit);
+    let (substring_score, _) =
+        string_metrics_core::substring_similarity(&needle, &haystack, options);
+
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("score".into(), score.into());
+    map.insert(
+        "range".into(),
+        range.map_or(serde_yaml::Value::Null, range_value),
+    );
+    map.insert("subsequence_score".into(), subsequence_score.into());
+    map.insert(
+        "subsequence_ranges".into(),
+        serde_yaml::Value::Sequence(subsequence_ranges.into_iter().map(range_value).collect()),
+    );
+    map.insert("substring_score".into(), substring_score.into());
+    Ok(map)
+}
+
+fn validate_coverage(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = match coverage_fields(&test.inputs) {
+        Ok(actual) => actual,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    validate_fields(
+        file,
+        category,
+        test,
+        actual,
+        "score, range, subsequence_score, subsequence_ranges and/or substring_score",
+        "Coverage mismatch",
+    )
+}
+
+/// Library output for an `excerpt` case: the excerpt `text` and the match's `range` in it
+fn excerpt_fields(inputs: &serde_yaml::Mapping) -> Result<serde_yaml::Mapping, String> {
+    let input = get_string_input(inputs, "input").unwrap_or_default();
+    let range: Range = inputs
+        .get("range")
+        .map(|v| serde_yaml::from_value(v.clone()).map_err(|e| format!("range: {}", e)))
+        .transpose()?
+        .ok_or("range is required")?;
+    let context_chars = inputs
+        .get("context_chars")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    let ellipsis = get_string_input(inputs, "ellipsis").unwrap_or_else(|| "…".to_string());
+    let range = MatchRange {
+        start: range.start,
+        end: range.end,
+    };
+    let unit = substring_options(inputs)?.range_unit;
+
+    let excerpt =
+        string_metrics_core::excerpt_around_range(&input, range, context_chars, &ellipsis, unit)?;
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("text".into(), excerpt.text.into());
+    map.insert(
+        "range".into(),
+        serde_yaml::to_value(Range::from(excerpt.range)).expect("ranges serialize"),
+    );
+    Ok(map)
+}
+
+fn validate_excerpt(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    match excerpt_fields(&test.inputs) {
+        Ok(fields) => validate_fields(
+            file,
+            category,
+            test,
+            fields,
+            "text and/or range",
+            "Excerpt mismatch",
+        ),
+        Err(e) => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: None,
+            actual: Some(format!("error={:?}", e)),
+            error: Some(e),
+            repro: None,
+        },
+    }
+}
+
+/// Library output for an `empty_inputs` case, after the optional `preset` normalizes both
+/// inputs: every registered metric with a WASM export under `scores` (keyed by the export),
+/// every metric `unified_score` and
+/// `unified_distance` dispatch under `unified_scores` and `unified_distances`, and the
+/// `substring_score` from `substring_similarity`
+fn empty_inputs_fields(inputs: &serde_yaml::Mapping) -> Result<serde_yaml::Mapping, String> {
+    let preset = get_string_input(inputs, "preset").unwrap_or_else(|| "none".to_string());
+    let normalized = |key: &str| {
+        let input = get_string_input(inputs, key).unwrap_or_default();
+        string_metrics_core::try_normalize_with_locale(&input, &preset, None)
+    };
+    let (a, b) = (normalized("input_a")?, normalized("input_b")?);
+
+    let scores: serde_yaml::Mapping = string_metrics_core::list_metrics()
+        .iter()
+        .filter_map(|info| Some((info.export?.into(), info.score(&a, &b).into())))
+        .collect();
+    let unified_scores = unified_metric_names(false)?
+        .into_iter()
+        .map(|metric| {
+            Ok((
+                metric.into(),
+                string_metrics_core::unified_score(&a, &b, metric)?.into(),
+            ))
+        })
+        .collect::<Result<serde_yaml::Mapping, String>>()?;
+    let unified_distances = unified_metric_names(true)?
+        .into_iter()
+        .map(|metric| {
+            let distance = string_metrics_core::unified_distance(&a, &b, metric)? as u64;
+            Ok((metric.into(), distance.into()))
+        })
+        .collect::<Result<serde_yaml::Mapping, String>>()?;
+    let (substring_score, _) =
+        string_metrics_core::substring_similarity(&a, &b, SubstringOptions::default());
+
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("scores".into(), scores.into());
+    map.insert("unified_scores".into(), unified_scores.into());
+    map.insert("unified_distances".into(), unified_distances.into());
+    map.insert("substring_score".into(), substring_score.into());
+    Ok(map)
+}
+
+/// The first path (`scores.jaro`) where two values differ, with the expected and actual
+/// value there; numbers compare within `tol`, and a key missing on either side differs
+fn first_difference(
+    path: &str,
+    expected: Option<&serde_yaml::Value>,
+    actual: Option<&serde_yaml::Value>,
+    tol: f64,
+) -> Option<(String, Option<serde_yaml::Value>, Option<serde_yaml::Value>)> {
+    let differs = || Some((path.to_string(), expected.cloned(), actual.cloned()));
+    match (expected, actual) {
+        (Some(serde_yaml::Value::Mapping(exp)), Some(serde_yaml::Value::Mapping(act))) => {
+            let keys = exp
+                .keys()
+                .chain(act.keys().filter(|key| !exp.contains_key(*key)));
+            keys.into_iter().find_map(|key| {
+                let key_name = key.as_str().unwrap_or_default();
+                let nested = if path.is_empty() {
+                    key_name.to_string()
+                } else {
+                    format!("{}.{}", path, key_name)
+                };
+                first_difference(&nested, exp.get(key), act.get(key), tol)
+            })
+        }
+        (Some(exp), Some(act)) => match (exp.as_f64(), act.as_f64()) {
+            (Some(e), Some(a)) if scores_match(e, a, tol) => None,
+            (Some(_), Some(_)) => differs(),
+            _ if exp == act => None,
+            _ => differs(),
+        },
+        _ => differs(),
+    }
+}
+
+/// Every field must be listed, so a metric added to the library without its empty-input
+/// behavior pinned fails the case
+fn validate_empty_inputs(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    validate_whole_mapping(
+        file,
+        category,
+        test,
+        empty_inputs_fields(&test.inputs),
+        tol,
+        "Empty-input behavior mismatch",
+    )
+}
+
+/// `actual` against the whole `expected` mapping: a field missing on either side fails,
+/// and numbers compare within `tol`
+fn validate_whole_mapping(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    actual: Result<serde_yaml::Mapping, String>,
+    tol: f64,
+    mismatch: &str,
+) -> ValidationResult {
+    let actual = match actual {
+        Ok(actual) => serde_yaml::Value::Mapping(actual),
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let compact = |value: Option<&serde_yaml::Value>| {
+        value.map_or("(none)".to_string(), |v| {
+            serde_json::to_string(v).unwrap_or_default()
+        })
+    };
+
+    match first_difference("", test.expected.as_ref(), Some(&actual), tol) {
+        Some((path, expected, actual)) => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!("{} = {}", path, compact(expected.as_ref()))),
+            actual: Some(format!("{} = {}", path, compact(actual.as_ref()))),
+            error: Some(mismatch.to_string()),
+            repro: None,
+        },
+        None => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: true,
+            expected: Some(compact(test.expected.as_ref())),
+            actual: Some(compact(Some(&actual))),
+            error: None,
+            repro: None,
+        },
+    }
+}
+
+/// Library output for a `window_scores` case; `window` and `step` fall back to the
+/// library defaults when absent
+fn case_window_scores(inputs: &serde_yaml::Mapping) -> Result<Vec<f64>, String> {
+    let needle = get_string_input(inputs, "needle").unwrap_or_default();
+    let haystack = get_string_input(inputs, "haystack").unwrap_or_default();
+    let metric = get_string_input(inputs, "metric").unwrap_or_default();
+    let size = |key: &str| inputs.get(key).and_then(|v| v.as_u64()).map(|n| n as usize);
+    string_metrics_core::window_scores(&needle, &haystack, size("window"), size("step"), &metric)
+}
+
+/// One score per window, in order; each within `tol`
+fn validate_window_scores(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    let actual = match case_window_scores(&test.inputs) {
+        Ok(scores) => scores,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: Some(format!("error={:?}", e)),
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let expected: Vec<Option<f64>> = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .map(|scores| scores.iter().map(|score| score.as_f64()).collect())
+        .unwrap_or_default();
+    let describe = |score: Option<f64>| score.map_or("(none)".to_string(), |s| s.to_string());
+
+    if let Some(i) = (0..actual.len().max(expected.len())).find(|&i| {
+        match (expected.get(i).copied().flatten(), actual.get(i)) {
+            (Some(expected), Some(&actual)) => !scores_match(expected, actual, tol),
+            _ => true,
+        }
+    }) {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!(
+                "{} windows, window[{}] = {}",
+                expected.len(),
+                i,
+                describe(expected.get(i).copied().flatten())
+            )),
+            actual: Some(format!(
+                "{} windows, window[{}] = {}",
+                actual.len(),
+                i,
+                describe(actual.get(i).copied())
+            )),
+            error: Some("Window scores mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} windows", expected.len())),
+        actual: Some(format!("{} windows", actual.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_normalization(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
+    let preset = get_string_input(&test.inputs, "preset").unwrap_or_default();
+    let locale = get_string_input(&test.inputs, "locale");
+
+    let options = normalize_options(&test.inputs, locale.as_deref());
+    let actual_normalized = match try_normalize_with_options(&input, &preset, &options) {
+        Ok(normalized) => normalized,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let expected_normalized = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let matches = actual_normalized == expected_normalized;
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: matches,
+        expected: Some(format!("\"{}\"", expected_normalized)),
+        actual: Some(format!("\"{}\"", actual_normalized)),
+        error: if matches {
+            None
+        } else {
+            Some("Normalization mismatch".to_string())
+        },
+        repro: None,
+    }
+}
+
+fn validate_normalization_locale(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
+    let preset = get_string_input(&test.inputs, "preset").unwrap_or_default();
+    let locale = test
+        .inputs
+        .get("locale")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let actual_normalized = normalize_with_options(
+        &input,
+        &preset,
+        &NormalizeOptions::with_locale(locale.as_deref()),
+    );
+
+    let expected_normalized = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let matches = actual_normalized == expected_normalized;
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: matches,
+        expected: Some(format!("\"{}\"", expected_normalized)),
+        actual: Some(format!("\"{}\"", actual_normalized)),
+        error: if matches {
+            None
+        } else {
+            Some(format!("Normalization mismatch (locale={:?})", locale))
+        },
+        repro: None,
+    }
+}
+
+fn validate_numeric_tokens(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let input = get_string_input(&test.inputs, "input").unwrap_or_default();
+    let actual = string_metrics_core::fold_numeric_tokens(&input);
+    let expected = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let matches = actual == expected;
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: matches,
+        expected: Some(format!("\"{}\"", expected)),
+        actual: Some(format!("\"{}\"", actual)),
+        error: if matches {
+            None
+        } else {
+            Some("Numeric token mismatch".to_string())
+        },
+        repro: None,
+    }
+}
+
+fn case_transferred(inputs: &serde_yaml::Mapping) -> String {
+    let pattern = get_string_input(inputs, "pattern").unwrap_or_default();
+    let target = get_string_input(inputs, "target").unwrap_or_default();
+    let locale = get_string_input(inputs, "locale");
+    string_metrics_core::transfer_case(&pattern, &target, locale.as_deref())
+}
+
+fn validate_case_transfer(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = case_transferred(&test.inputs);
+    let expected = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let matches = actual == expected;
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: matches,
+        expected: Some(format!("\"{}\"", expected)),
+        actual: Some(format!("\"{}\"", actual)),
+        error: if matches {
+            None
+        } else {
+            Some("Case transfer mismatch".to_string())
+        },
+        repro: None,
+    }
+}
+
+/// An `expansions` options mapping of key → replacement, in fixture order; None when it is
+/// not a string mapping or the library rejects it
+fn expansions_from_yaml(value: &serde_yaml::Value) -> Option<Expansions> {
+    let pairs: Option<Vec<(&str, &str)>> = value
+        .as_mapping()?
+        .iter()
+        .map(|(key, replacement)| Some((key.as_str()?, replacement.as_str()?)))
+        .collect();
+    Expansions::new(&pairs?).ok()
+}
+
+/// `min_candidate_len`, `max_candidate_len` and `max_length_ratio` from a case's options
+fn length_filter_from_yaml(options: Option<&serde_yaml::Value>) -> LengthFilter {
+    let option = |key: &str| options.and_then(|options| options.get(key));
+    LengthFilter {
+        min_candidate_len: option("min_candidate_len")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize),
+        max_candidate_len: option("max_candidate_len")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize),
+        max_length_ratio: option("max_length_ratio").and_then(|v| v.as_f64()),
+    }
+}
+
+/// The `rank_fusion` mapping of `suggestions` or `extract` options
+/// (`{ metrics, method, k? }`); an invalid one is ignored like other unparsable options
+fn rank_fusion_from_yaml(options: Option<&serde_yaml::Value>) -> Option<RankFusion> {
+    let fusion = options?.get("rank_fusion")?;
+    let metrics: Vec<String> = serde_yaml::from_value(fusion.get("metrics")?.clone()).ok()?;
+    let method = FusionMethod::parse(fusion.get("method")?.as_str()?).ok()?;
+    RankFusion::new(metrics, method, fusion.get("k").and_then(|v| v.as_f64())).ok()
+}
+
+/// The `recency_boost` mapping of `suggestions` options
+/// (`{ half_life_ms, max_boost, now_ms, allow_boost_past_cutoff? }`); `now_ms` is
+/// required so fixtures don't depend on the clock, and an invalid one is ignored
+fn recency_boost_from_yaml(options: Option<&serde_yaml::Value>) -> Option<RecencyBoost> {
+    let boost = options?.get("recency_boost")?;
+    let number = |key: &str| boost.get(key).and_then(|v| v.as_f64());
+    RecencyBoost::new(
+        number("half_life_ms")?,
+        number("max_boost")?,
+        number("now_ms")?,
+        boost
+            .get("allow_boost_past_cutoff")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    )
+    .ok()
+}
+
+/// Suggestion engine inputs read from a `suggestions` case
+struct SuggestionQuery {
+    input: String,
+    candidates: Vec<Candidate>,
+    options: SuggestOptions,
+}
+
+/// A `candidates` entry: a plain string, or a `{ value, id?, locale?, last_selected_ms? }`
+/// mapping
+fn candidate_from_yaml(value: &serde_yaml::Value) -> Option<Candidate> {
+    if let Some(s) = value.as_str() {
+        return Some(s.into());
+    }
+    let field = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    Some(Candidate {
+        value: field("value")?,
+        id: field("id"),
+        locale: field("locale"),
+        last_selected_ms: value.get("last_selected_ms").and_then(|v| v.as_f64()),
+    })
+}
+
+impl SuggestionQuery {
+    fn from_case(test: &TestCase) -> Self {
+        let candidates = test
+            .inputs
+            .get("candidates")
+            .and_then(|v| v.as_sequence())
+            .map(|seq| seq.iter().filter_map(candidate_from_yaml).collect())
+            .unwrap_or_default();
+        let option = |key: &str| {
+            test.inputs
+                .get("options")
+                .and_then(|options| options.get(key))
+        };
+        let defaults = SuggestOptions::default();
+
+        SuggestionQuery {
+            input: get_string_input(&test.inputs, "input").unwrap_or_default(),
+            candidates,
+            options: SuggestOptions {
+                min_score: option("min_score")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(defaults.min_score),
+                max_suggestions: option("max_suggestions")
+                    .and_then(|v| v.as_u64())
+                    .map_or(defaults.max_suggestions, |n| n as usize),
+                metric: option("metric")
+                    .and_then(|v| v.as_str())
+                    .map_or(defaults.metric, |s| s.to_string()),
+                normalize_preset: option("normalize_preset")
+                    .and_then(|v| v.as_str())
+                    .map_or(defaults.normalize_preset, |s| s.to_string()),
+                prefer_prefix: option("prefer_prefix")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(defaults.prefer_prefix),
+                prefix_bonus_weight: string_metrics_core::clamp_prefix_bonus_weight(
+                    option("prefix_bonus_weight").and_then(|v| v.as_f64()),
+                ),
+                // Case-level locale applies to the query and to candidates without their own
+                locale: get_string_input(&test.inputs, "locale"),
+                explain: option("explain")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(defaults.explain),
+                query_mode: option("query_mode")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| QueryMode::parse(s).ok())
+                    .unwrap_or(defaults.query_mode),
+                token_aggregation: option("token_aggregation")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| TokenAggregation::parse(s).ok())
+                    .unwrap_or(defaults.token_aggregation),
+                expansions: option("expansions")
+                    .and_then(expansions_from_yaml)
+                    .unwrap_or(defaults.expansions),
+                short_string_policy: option("short_string_policy")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| ShortStringPolicy::parse(s).ok())
+                    .unwrap_or(defaults.short_string_policy),
+                short_string_length: option("short_string_length")
+                    .and_then(|v| v.as_u64())
+                    .map_or(defaults.short_string_length, |n| n as usize),
+                segment_input: option("segment_input")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(defaults.segment_input),
+                length_filter: length_filter_from_yaml(test.inputs.get("options")),
+                rank_fusion: rank_fusion_from_yaml(test.inputs.get("options")),
+                recency_boost: recency_boost_from_yaml(test.inputs.get("options")),
+            },
+        }
+    }
+}
+
+/// Score, filter and rank candidates with the library's `suggest`
+fn rank_suggestions(query: &SuggestionQuery) -> Vec<SuggestionResult> {
+    string_metrics_core::suggest_candidates(&query.input, &query.candidates, &query.options)
+        .into_iter()
+        .map(|s| SuggestionResult {
+            value: s.value,
+            id: s.id,
+            score: s.score,
+            matched_range: s.matched_range.map(Range::from),
+            normalized_value: s.normalized_value,
+            explanation: s.explanation,
+        })
+        .collect()
+}
+
+/// Library output for a `did_you_mean` case: its `kind`, plus the `value` for an exact
+/// match or a correction and the `score` for a correction
+fn did_you_mean_fields(inputs: &serde_yaml::Mapping) -> serde_yaml::Mapping {
+    let input = get_string_input(inputs, "input").unwrap_or_default();
+    let candidates: Vec<&str> = inputs
+        .get("candidates")
+        .and_then(|v| v.as_sequence())
+        .map(|seq| seq.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    let outcome = string_metrics_core::did_you_mean(&input, &candidates);
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("kind".into(), outcome.kind().into());
+    match outcome {
+        DidYouMean::Exact { value } => {
+            map.insert("value".into(), value.into());
+        }
+        DidYouMean::Corrected { value, score } => {
+            map.insert("value".into(), value.into());
+            map.insert("score".into(), score.into());
+        }
+        DidYouMean::None => {}
+    }
+    map
+}
+
+/// Every field must match; a `none` case lists neither value nor score
+fn validate_did_you_mean(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+) -> ValidationResult {
+    validate_whole_mapping(
+        file,
+        category,
+        test,
+        Ok(did_you_mean_fields(&test.inputs)),
+        tol,
+        "Did-you-mean mismatch",
+    )
+}
+
+fn validate_suggestions(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+    ignore_normalized: bool,
+) -> ValidationResult {
+    let results = rank_suggestions(&SuggestionQuery::from_case(test));
+
+    // Validate against expected
+    let empty_vec = vec![];
+    let expected_suggestions = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .unwrap_or(&empty_vec);
+
+    // Check length matches
+    if results.len() != expected_suggestions.len() {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!("{} suggestions", expected_suggestions.len())),
+            actual: Some(format!("{} suggestions", results.len())),
+            error: Some("Suggestion count mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    // Check each suggestion
+    for (i, (actual, expected)) in results.iter().zip(expected_suggestions.iter()).enumerate() {
+        let expected_map = expected.as_mapping();
+        if expected_map.is_none() {
+            continue;
+        }
+        let expected_map = expected_map.unwrap();
+
+        // Check value
+        let expected_value = expected_map
+            .get("value")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if actual.value != expected_value {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: Some(format!("suggestion[{}].value = {}", i, expected_value)),
+                actual: Some(format!("suggestion[{}].value = {}", i, actual.value)),
+                error: Some("Value mismatch".to_string()),
+                repro: None,
+            };
+        }
+
+        // Check id if present (candidates given as mappings echo theirs back)
+        if let Some(expected_id) = expected_map.get("id").and_then(|v| v.as_str()) {
+            if actual.id.as_deref() != Some(expected_id) {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: Some(format!("suggestion[{}].id = {:?}", i, expected_id)),
+                    actual: Some(format!("suggestion[{}].id = {:?}", i, actual.id)),
+                    error: Some("Id mismatch".to_string()),
+                    repro: None,
+                };
+            }
+        }
+
+        // Check score
+        let expected_score = expected_map
+            .get("score")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        if !scores_match(expected_score, actual.score, tol) {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: Some(format!("suggestion[{}].score = {}", i, expected_score)),
+                actual: Some(format!("suggestion[{}].score = {}", i, actual.score)),
+                error: Some("Score mismatch".to_string()),
+                repro: None,
+            };
+        }
+
+        // Check normalized_value if present (exact match)
+        if let Some(expected_normalized) = expected_map
+            .get("normalized_value")
+            .and_then(|v| v.as_str())
+            .filter(|_| !ignore_normalized)
+        {
+            if actual.normalized_value != expected_normalized {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: Some(format!(
+                        "suggestion[{}].normalized_value = {:?}",
+                        i, expected_normalized
+                    )),
+                    actual: Some(format!(
+                        "suggestion[{}].normalized_value = {:?}",
+                        i, actual.normalized_value
+                    )),
+                    error: Some("Normalized value mismatch".to_string()),
+                    repro: None,
+                };
+            }
+        }
+
+        // Check matched_range if present
+        if let Some(expected_range) = expected_map
+            .get("matched_range")
+            .and_then(|v| v.as_mapping())
+        {
+            let exp_start = expected_range
+                .get("start")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0) as usize;
+            let exp_end = expected_range
+                .get("end")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0) as usize;
+
+            match &actual.matched_range {
+                Some(range) => {
+                    if range.start != exp_start || range.end != exp_end {
+                        return ValidationResult {
+                            file: file.to_string(),
+                            category: category.to_string(),
+                            description: test.description.clone(),
+                            passed: false,
+                            expected: Some(format!(
+                                "suggestion[{}].matched_range = [{}, {})",
+                                i, exp_start, exp_end
+                            )),
+                            actual: Some(format!(
+                                "suggestion[{}].matched_range = [{}, {})",
+                                i, range.start, range.end
+                            )),
+                            error: Some("Range mismatch".to_string()),
+                            repro: None,
+                        };
+                    }
+                }
+                None => {
+                    return ValidationResult {
+                        file: file.to_string(),
+                        category: category.to_string(),
+                        description: test.description.clone(),
+                        passed: false,
+                        expected: Some(format!(
+                            "suggestion[{}].matched_range = [{}, {})",
+                            i, exp_start, exp_end
+                        )),
+                        actual: Some(format!("suggestion[{}].matched_range = None", i)),
+                        error: Some("Expected matched_range but got None".to_string()),
+                        repro: None,
+                    };
+                }
+            }
+        }
+
+        // Check the explanation fields the case lists, if any
+        if let Some(expected_explanation) =
+            expected_map.get("explanation").and_then(|v| v.as_mapping())
+        {
+            if let Err((expected, actual, error)) =
+                check_explanation(expected_explanation, actual.explanation.as_ref(), tol)
+            {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: Some(format!("suggestion[{}].explanation.{}", i, expected)),
+                    actual: Some(format!("suggestion[{}].explanation.{}", i, actual)),
+                    error: Some(error),
+                    repro: None,
+                };
+            }
+        }
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} suggestions", expected_suggestions.len())),
+        actual: Some(format!("{} suggestions", results.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Compare the explanation fields a case lists (any subset) with the computed explanation,
+/// returning (expected, actual, error) for the first mismatch
+fn check_explanation(
+    expected: &serde_yaml::Mapping,
+    actual: Option<&SuggestionExplanation>,
+    tol: f64,
+) -> Result<(), (String, String, String)> {
+    let Some(actual) = actual else {
+        return Err((
+            "present".to_string(),
+            "missing".to_string(),
+            "Expected an explanation; set `explain: true` in options".to_string(),
+        ));
+    };
+    for (key, value) in expected {
+        let key = key.as_str().unwrap_or_default();
+        let mismatch = |actual: String| {
+            Err((
+                format!("{} = {}", key, yaml_scalar(value)),
+                format!("{} = {}", key, actual),
+                "Explanation mismatch".to_string(),
+            ))
+        };
+        match key {
+            "raw_score" | "bonus_amount" => {
+                let actual_value = if key == "raw_score" {
+                    actual.raw_score
+                } else {
+                    actual.bonus_amount
+                };
+                if !value
+                    .as_f64()
+                    .is_some_and(|exp| scores_match(exp, actual_value, tol))
+                {
+                    return mismatch(actual_value.to_string());
+                }
+            }
+            "prefix_bonus_applied" => {
+                if value.as_bool() != Some(actual.prefix_bonus_applied) {
+                    return mismatch(actual.prefix_bonus_applied.to_string());
+                }
+            }
+            "metric" | "normalized_input" | "normalized_candidate" | "prefilter" => {
+                let actual_value = match key {
+                    "metric" => actual.metric.as_str(),
+                    "normalized_input" => actual.normalized_input.as_str(),
+                    "normalized_candidate" => actual.normalized_candidate.as_str(),
+                    _ => actual.prefilter.as_str(),
+                };
+                if value.as_str() != Some(actual_value) {
+                    return mismatch(format!("{:?}", actual_value));
+                }
+            }
+            _ => {
+                return Err((
+                    key.to_string(),
+                    "(no such field)".to_string(),
+                    format!("Unknown explanation field: {}", key),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An expected scalar as written in the fixture (strings quoted)
+fn yaml_scalar(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => format!("{:?}", s),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+/// Extraction inputs read from an `extract` or `extract_one` case
+struct ExtractionQuery {
+    query: String,
+    choices: Vec<String>,
+    options: ExtractOptions,
+}
+
+impl ExtractionQuery {
+    fn from_case(test: &TestCase) -> Self {
+        let choices = test
+            .inputs
+            .get("choices")
+            .and_then(|v| v.as_sequence())
+            .map(|seq| {
+                seq.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        let option = |key: &str| {
+            test.inputs
+                .get("options")
+                .and_then(|options| options.get(key))
+        };
+        let defaults = ExtractOptions::default();
+
+        ExtractionQuery {
+            query: get_string_input(&test.inputs, "query").unwrap_or_default(),
+            choices,
+            options: ExtractOptions {
+                scorer: option("scorer")
+                    .and_then(|v| v.as_str())
+                    .map_or(defaults.scorer, |s| s.to_string()),
+                processor: option("processor")
+                    .and_then(|v| v.as_str())
+                    .map_or(defaults.processor, |s| s.to_string()),
+                score_cutoff: option("score_cutoff")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(defaults.score_cutoff),
+                limit: option("limit")
+                    .and_then(|v| v.as_u64())
+                    .map(|n| n as usize)
+                    .or(defaults.limit),
+                explain: option("explain")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(defaults.explain),
+                query_mode: option("query_mode")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| QueryMode::parse(s).ok())
+                    .unwrap_or(defaults.query_mode),
+                token_aggregation: option("token_aggregation")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| TokenAggregation::parse(s).ok())
+                    .unwrap_or(defaults.token_aggregation),
+                expansions: option("expansions")
+                    .and_then(expansions_from_yaml)
+                    .unwrap_or(defaults.expansions),
+                short_string_policy: option("short_string_policy")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| ShortStringPolicy::parse(s).ok())
+                    .unwrap_or(defaults.short_string_policy),
+                short_string_length: option("short_string_length")
+                    .and_then(|v| v.as_u64())
+                    .map_or(defaults.short_string_length, |n| n as usize),
+                length_filter: length_filter_from_yaml(test.inputs.get("options")),
+                rank_fusion: rank_fusion_from_yaml(test.inputs.get("options")),
+            },
+        }
+    }
+}
+
+/// Run `extract` or `extract_one`; a single best match comes back as a one-element list
+fn run_extraction(category: &str, query: &ExtractionQuery) -> Result<Vec<ExtractResult>, String> {
+    if category == "extract_one" {
+        string_metrics_core::extract_one(&query.query, &query.choices, &query.options)
+            .map(|best| best.into_iter().collect())
+    } else {
+        string_metrics_core::extract(&query.query, &query.choices, &query.options)
+    }
+}
+
+fn validate_extract(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let results = match run_extraction(category, &ExtractionQuery::from_case(test)) {
+        Ok(results) => results,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: None,
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+
+    let empty_vec = vec![];
+    let expected_results = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .unwrap_or(&empty_vec);
+
+    if results.len() != expected_results.len() {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!("{} results", expected_results.len())),
+            actual: Some(format!("{} results", results.len())),
+            error: Some("Result count mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    for (i, (actual, expected)) in results.iter().zip(expected_results.iter()).enumerate() {
+        let Some(expected_map) = expected.as_mapping() else {
+            continue;
+        };
+
+        let expected_choice = expected_map
+            .get("choice")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let expected_index = expected_map.get("index").and_then(|v| v.as_u64());
+        if actual.choice != expected_choice || expected_index != Some(actual.index as u64) {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: Some(format!(
+                    "result[{}] = {:?} at index {}",
+                    i,
+                    expected_choice,
+                    expected_index.map_or("?".to_string(), |n| n.to_string())
+                )),
+                actual: Some(format!(
+                    "result[{}] = {:?} at index {}",
+                    i, actual.choice, actual.index
+                )),
+                error: Some("Choice mismatch".to_string()),
+                repro: None,
+            };
+        }
+
+        // Score is optional in hand-written cases; generate always fills it
+        if let Some(expected_score) = expected_map.get("score").and_then(|v| v.as_f64()) {
+            if !scores_match(expected_score, actual.score, tol) {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: Some(format!("result[{}].score = {}", i, expected_score)),
+                    actual: Some(format!("result[{}].score = {}", i, actual.score)),
+                    error: Some("Score mismatch".to_string()),
+                    repro: None,
+                };
+            }
+        }
+
+        if let Some(expected_explanation) =
+            expected_map.get("explanation").and_then(|v| v.as_mapping())
+        {
+            if let Err((expected, actual, error)) =
+                check_explanation(expected_explanation, actual.explanation.as_ref(), tol)
+            {
+                return ValidationResult {
+                    file: file.to_string(),
+                    category: category.to_string(),
+                    description: test.description.clone(),
+                    passed: false,
+                    expected: Some(format!("result[{}].explanation.{}", i, expected)),
+                    actual: Some(format!("result[{}].explanation.{}", i, actual)),
+                    error: Some(error),
+                    repro: None,
+                };
+            }
+        }
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} results", expected_results.len())),
+        actual: Some(format!("{} results", results.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Library output for an `editops` or `opcodes` case, one mapping per operation
+fn edit_operations(category: &str, test: &TestCase) -> Vec<serde_yaml::Value> {
+    let input_a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+    let entry = |fields: Vec<(&str, serde_yaml::Value)>| {
+        serde_yaml::Value::Mapping(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    };
+    let position = |n: usize| serde_yaml::Value::from(n as u64);
+
+    if category == "editops" {
+        string_metrics_core::levenshtein_editops(&input_a, &input_b)
+            .into_iter()
+            .map(|op| {
+                entry(vec![
+                    ("tag", op.tag.name().into()),
+                    ("src_pos", position(op.src_pos)),
+                    ("dest_pos", position(op.dest_pos)),
+                ])
+            })
+            .collect()
+    } else {
+        string_metrics_core::levenshtein_opcodes(&input_a, &input_b)
+            .into_iter()
+            .map(|op| {
+                entry(vec![
+                    ("tag", op.tag.name().into()),
+                    ("src_start", position(op.src_start)),
+                    ("src_end", position(op.src_end)),
+                    ("dest_start", position(op.dest_start)),
+                    ("dest_end", position(op.dest_end)),
+                ])
+            })
+            .collect()
+    }
+}
+
+/// Library output for a `script_detection` case: `dominant`, `mixed`, the `breakdown`
+/// of characters per script, most frequent first, and whether it has `bidi_controls`
+fn script_detection(inputs: &serde_yaml::Mapping) -> serde_yaml::Mapping {
+    let input = get_string_input(inputs, "input").unwrap_or_default();
+    let breakdown: serde_yaml::Mapping = string_metrics_core::script_breakdown(&input)
+        .into_iter()
+        .map(|(script, count)| (script.into(), (count as u64).into()))
+        .collect();
+    let mut map = serde_yaml::Mapping::new();
+    map.insert(
+        "dominant".into(),
+        string_metrics_core::dominant_script(&input).into(),
+    );
+    map.insert(
+        "mixed".into(),
+        string_metrics_core::is_mixed_script(&input).into(),
+    );
+    map.insert("breakdown".into(), serde_yaml::Value::Mapping(breakdown));
+    map.insert(
+        "bidi_controls".into(),
+        string_metrics_core::contains_bidi_controls(&input).into(),
+    );
+    map
+}
+
+fn validate_script_detection(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = script_detection(&test.inputs);
+    validate_fields(
+        file,
+        category,
+        test,
+        actual,
+        "dominant, mixed, breakdown and/or bidi_controls",
+        "Script detection mismatch",
+    )
+}
+
+/// Library output for a `normalized_key` case: the `key`, its `hash` as 16 hex digits
+/// (YAML and JSON readers lose precision on large integers), and whether `other`
+/// `equals` the input after normalization
+fn normalized_key_fields(inputs: &serde_yaml::Mapping) -> serde_yaml::Mapping {
+    let input = get_string_input(inputs, "input").unwrap_or_default();
+    let preset = get_string_input(inputs, "preset").unwrap_or_default();
+    let locale = get_string_input(inputs, "locale");
+    let options = normalize_options(inputs, locale.as_deref());
+    let mut map = serde_yaml::Mapping::new();
+    map.insert(
+        "key".into(),
+        string_metrics_core::normalized_key(&input, &preset, &options).into(),
+    );
+    let hash = string_metrics_core::normalized_hash(&input, &preset, &options);
+    map.insert("hash".into(), format!("{:016x}", hash).into());
+    if let Some(other) = get_string_input(inputs, "other") {
+        let equals = string_metrics_core::equals_normalized(&input, &other, &preset, &options);
+        map.insert("equals".into(), equals.into());
+    }
+    map
+}
+
+fn validate_normalized_key(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = normalized_key_fields(&test.inputs);
+    validate_fields(
+        file,
+        category,
+        test,
+        actual,
+        "key, hash and/or equals",
+        "Normalized key mismatch",
+    )
+}
+
+/// Compare the fields an `expected` mapping lists against the library's output mapping;
+/// unlisted fields are not checked and mapping order is not significant
+fn validate_fields(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    actual: serde_yaml::Mapping,
+    fields: &str,
+    mismatch: &str,
+) -> ValidationResult {
+    let Some(expected) = test.expected.as_ref().and_then(|v| v.as_mapping()) else {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: None,
+            actual: None,
+            error: Some(format!("Expected a mapping of {}", fields)),
+            repro: None,
+        };
+    };
+    let compact = |value: Option<&serde_yaml::Value>| {
+        value.map_or("(none)".to_string(), |v| {
+            serde_json::to_string(v).unwrap_or_default()
+        })
+    };
+
+    if let Some((field, value)) = expected
+        .iter()
+        .find(|(field, value)| actual.get(field) != Some(value))
+    {
+        let field = field.as_str().unwrap_or_default();
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!("{} = {}", field, compact(Some(value)))),
+            actual: Some(format!("{} = {}", field, compact(actual.get(field)))),
+            error: Some(mismatch.to_string()),
+            repro: None,
+        };
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(compact(test.expected.as_ref())),
+        actual: Some(compact(Some(&serde_yaml::Value::Mapping(actual)))),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Exact structural comparison: tie-breaking between equally short alignments is part of the contract
+/// Library output for a `fuzzy_find` case, one `{start, end, edits}` mapping per match,
+/// or the error for a `max_edits` that is not below the needle length
+fn fuzzy_matches(inputs: &serde_yaml::Mapping) -> Result<Vec<serde_yaml::Value>, String> {
+    let haystack = get_string_input(inputs, "haystack").unwrap_or_default();
+    let needle = get_string_input(inputs, "needle").unwrap_or_default();
+    let max_edits = inputs
+        .get("max_edits")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    let position = |n: usize| serde_yaml::Value::from(n as u64);
+    Ok(
+        string_metrics_core::fuzzy_find_all(&haystack, &needle, max_edits)?
+            .into_iter()
+            .map(|found| {
+                let mut map = serde_yaml::Mapping::new();
+                map.insert("start".into(), position(found.start));
+                map.insert("end".into(), position(found.end));
+                map.insert("edits".into(), position(found.edits));
+                serde_yaml::Value::Mapping(map)
+            })
+            .collect(),
+    )
+}
+
+fn validate_fuzzy_find(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = match fuzzy_matches(&test.inputs) {
+        Ok(found) => found,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: Some(format!("error={:?}", e)),
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let empty_vec = vec![];
+    let expected = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .unwrap_or(&empty_vec);
+    let compact = |value: &serde_yaml::Value| serde_json::to_string(value).unwrap_or_default();
+
+    if let Some(i) =
+        (0..actual.len().max(expected.len())).find(|&i| actual.get(i) != expected.get(i))
+    {
+        let describe =
+            |found: Option<&serde_yaml::Value>| found.map_or("(none)".to_string(), compact);
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!(
+                "{} matches, match[{}] = {}",
+                expected.len(),
+                i,
+                describe(expected.get(i))
+            )),
+            actual: Some(format!(
+                "{} matches, match[{}] = {}",
+                actual.len(),
+                i,
+                describe(actual.get(i))
+            )),
+            error: Some("Match mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} matches", expected.len())),
+        actual: Some(format!("{} matches", actual.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+/// Options for a `word_diff` case; missing fields take the library defaults
+fn word_diff_options(inputs: &serde_yaml::Mapping) -> Result<WordDiffOptions, String> {
+    let defaults = WordDiffOptions::default();
+    Ok(WordDiffOptions {
+        tokenizer: match get_string_input(inputs, "tokenizer") {
+            Some(name) => Tokenizer::parse(&name)?,
+            None => defaults.tokenizer,
+        },
+        metric: get_string_input(inputs, "metric").unwrap_or(defaults.metric),
+        threshold: inputs
+            .get("threshold")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(defaults.threshold),
+    })
+}
+
+/// Library output for a `word_diff` case: per run, its `op`, the `a_tokens` and `b_tokens`
+/// it covers (each token with its trailing whitespace), and a replacement's `score`
+fn word_diffs(inputs: &serde_yaml::Mapping) -> Result<Vec<serde_yaml::Value>, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let options = word_diff_options(inputs)?;
+    let a_tokens = string_metrics_core::word_tokens(&input_a, options.tokenizer);
+    let b_tokens = string_metrics_core::word_tokens(&input_b, options.tokenizer);
+    let texts = |tokens: &[WordToken]| {
+        serde_yaml::Value::Sequence(
+            tokens
+                .iter()
+                .map(|t| format!("{}{}", t.text, t.whitespace).into())
+                .collect(),
+        )
+    };
+    Ok(
+        string_metrics_core::word_diff(&input_a, &input_b, &options)?
+            .into_iter()
+            .map(|op| {
+                let mut map = serde_yaml::Mapping::new();
+                map.insert("op".into(), op.tag.name().into());
+                map.insert("a_tokens".into(), texts(&a_tokens[op.a_start..op.a_end]));
+                map.insert("b_tokens".into(), texts(&b_tokens[op.b_start..op.b_end]));
+                if let Some(score) = op.score {
+                    map.insert("score".into(), score.into());
+                }
+                serde_yaml::Value::Mapping(map)
+            })
+            .collect(),
+    )
+}
+
+fn validate_word_diff(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    match word_diffs(&test.inputs) {
+        Ok(ops) => compare_operations(file, category, test, &ops),
+        Err(e) => ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: None,
+            actual: Some(format!("error={:?}", e)),
+            error: Some(e),
+            repro: None,
+        },
+    }
+}
+
+/// Library output for a `match_profile` case: one class name per candidate char
+fn match_profile_classes(inputs: &serde_yaml::Mapping) -> Result<Vec<serde_yaml::Value>, String> {
+    let query = get_string_input(inputs, "query").unwrap_or_default();
+    let candidate = get_string_input(inputs, "candidate").unwrap_or_default();
+    let preset = get_string_input(inputs, "preset").unwrap_or_else(|| "none".to_string());
+    let locale = get_string_input(inputs, "locale");
+    Ok(
+        string_metrics_core::match_profile(&query, &candidate, &preset, locale.as_deref())?
+            .into_iter()
+            .map(|class| class.name().into())
+            .collect(),
+    )
+}
+
+/// Exact comparison: the class of every candidate char is part of the contract
+fn validate_match_profile(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    let actual = match match_profile_classes(&test.inputs) {
+        Ok(classes) => classes,
+        Err(e) => {
+            return ValidationResult {
+                file: file.to_string(),
+                category: category.to_string(),
+                description: test.description.clone(),
+                passed: false,
+                expected: None,
+                actual: Some(format!("error={:?}", e)),
+                error: Some(e),
+                repro: None,
+            }
+        }
+    };
+    let empty_vec = vec![];
+    let expected = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .unwrap_or(&empty_vec);
+    let describe = |class: Option<&serde_yaml::Value>| {
+        class.map_or("(none)".to_string(), |v| {
+            v.as_str().unwrap_or_default().to_string()
+        })
+    };
+
+    if let Some(i) =
+        (0..actual.len().max(expected.len())).find(|&i| actual.get(i) != expected.get(i))
+    {
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!(
+                "{} chars, char[{}] = {}",
+                expected.len(),
+                i,
+                describe(expected.get(i))
+            )),
+            actual: Some(format!(
+                "{} chars, char[{}] = {}",
+                actual.len(),
+                i,
+                describe(actual.get(i))
+            )),
+            error: Some("Match profile mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} chars", expected.len())),
+        actual: Some(format!("{} chars", actual.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+fn validate_edit_operations(file: &str, category: &str, test: &TestCase) -> ValidationResult {
+    compare_operations(file, category, test, &edit_operations(category, test))
+}
+
+/// Compare computed operations with the case's `expected` sequence, reporting the first mismatch
+fn compare_operations(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    actual: &[serde_yaml::Value],
+) -> ValidationResult {
+    let empty_vec = vec![];
+    let expected = test
+        .expected
+        .as_ref()
+        .and_then(|v| v.as_sequence())
+        .unwrap_or(&empty_vec);
+    let compact = |value: &serde_yaml::Value| serde_json::to_string(value).unwrap_or_default();
+
+    if let Some(i) =
+        (0..actual.len().max(expected.len())).find(|&i| actual.get(i) != expected.get(i))
+    {
+        let describe = |op: Option<&serde_yaml::Value>| op.map_or("(none)".to_string(), compact);
+        return ValidationResult {
+            file: file.to_string(),
+            category: category.to_string(),
+            description: test.description.clone(),
+            passed: false,
+            expected: Some(format!(
+                "{} ops, op[{}] = {}",
+                expected.len(),
+                i,
+                describe(expected.get(i))
+            )),
+            actual: Some(format!(
+                "{} ops, op[{}] = {}",
+                actual.len(),
+                i,
+                describe(actual.get(i))
+            )),
+            error: Some("Operation mismatch".to_string()),
+            repro: None,
+        };
+    }
+
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed: true,
+        expected: Some(format!("{} ops", expected.len())),
+        actual: Some(format!("{} ops", actual.len())),
+        error: None,
+        repro: None,
+    }
+}
+
+// ============================================================================
+// WATCH MODE
+// ============================================================================
+
+/// Quiet period before a burst of file events triggers a re-run
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+fn watch_fixtures(pattern: &str, options: &ValidateOptions) {
+    let matcher = InputMatcher::new(pattern).unwrap_or_else(|e| {
+        eprintln!("{}: {}", "Error".red(), e);
+        process::exit(EXIT_IO_ERRORS);
+    });
+    let root = watch_root(pattern);
+    let cwd = std::env::current_dir().expect("Failed to read current directory");
+
+    println!("{} fixtures matching: {}", "Validating".cyan(), pattern);
+    let files = expand_input(pattern);
+    let mut failing = run_validation(&files.paths, files.errors, files.ignored, options);
+
+    // Ctrl-C exits with the status of the most recent run
+    let status = Arc::new(AtomicI32::new(exit_code(&failing)));
+    let handler_status = Arc::clone(&status);
+    ctrlc::set_handler(move || {
+        println!();
+        process::exit(handler_status.load(Ordering::SeqCst));
+    })
+    .expect("Failed to install Ctrl-C handler");
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).unwrap_or_else(|e| {
+        eprintln!("{}: failed to start file watcher: {}", "Error".red(), e);
+        process::exit(1);
+    });
+    if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+        eprintln!("{} {}: {}", "Error".red(), root.display(), e);
+        process::exit(1);
+    }
+    print_watch_status(&root, &failing);
+
+    while let Ok(first) = rx.recv() {
+        // Debounce: keep collecting until the watcher has been quiet for a while
+        let mut changed: BTreeSet<PathBuf> = BTreeSet::new();
+        let mut next = Some(first);
+        while let Some(event) = next {
+            match event {
+                // Reading fixtures during validation raises access events; ignore them
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    for path in event.paths {
+                        let path = path.strip_prefix(&cwd).unwrap_or(&path).to_path_buf();
+                        if matcher.matches(&path) {
+                            changed.insert(path);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{}: {}", "Watch error".yellow(), e),
+            }
+            next = rx.recv_timeout(WATCH_DEBOUNCE).ok();
+        }
+        if changed.is_empty() {
+            continue;
+        }
+
+        // Renamed-away and deleted files no longer count towards the status
+        let (existing, removed): (Vec<PathBuf>, Vec<PathBuf>) =
+            changed.into_iter().partition(|path| path.is_file());
+        for path in existing.iter().chain(&removed) {
+            failing.remove(&path.display().to_string());
+        }
+        // A pattern that matched nothing at startup now matches a file
+        if !existing.is_empty() {
+            failing.remove(pattern);
+        }
+
+        print!("\x1B[2J\x1B[H");
+        println!(
+            "{} {} changed file(s) matching: {}",
+            "Re-validating".cyan(),
+            existing.len(),
+            pattern
+        );
+        for path in &removed {
+            println!("  {} {}", "removed".yellow(), path.display());
+        }
+        if !existing.is_empty() {
+            failing.extend(run_validation(&existing, Vec::new(), None, options));
+        }
+
+        status.store(exit_code(&failing), Ordering::SeqCst);
+        print_watch_status(&root, &failing);
+    }
+}
+
+/// Deepest directory of the pattern without glob metacharacters
+fn watch_root(pattern: &str) -> PathBuf {
+    let literal: PathBuf = Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect();
+    // A literal file path is watched through its directory so editors that
+    // replace the file on save are still seen
+    let root = if literal.is_file() {
+        literal.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        literal
+    };
+    if root.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        root
+    }
+}
+
+fn print_watch_status(root: &Path, failing: &BTreeMap<String, i32>) {
+    println!();
+    if failing.is_empty() {
+        println!("{}", "All watched fixtures passing.".green());
+    } else {
+        println!(
+            "{} {}",
+            "Failing fixtures:".red(),
+            failing.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
+    println!(
+        "Watching {} for changes (Ctrl-C to exit)...",
+        root.display()
+    );
+}
+
+// ============================================================================
+// BASELINE
+// ============================================================================
+//
+// A baseline is the failure set of an earlier run, saved as JSON. Cases are
+// matched by (file name, category, description); failures in the baseline are
+// "known" and don't fail the run, while new failures and baseline entries that
+// now pass ("fixed") do, so the baseline is kept in step with the corpus.
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Baseline {
+    tool_version: String,
+    generated_at: String,
+    failures: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BaselineEntry {
+    file: String,
+    category: String,
+    description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    actual: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Baseline {
+    fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    fn from_results(results: &[CaseOutcome]) -> Self {
+        Baseline {
+            tool_version: VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            failures: results
+                .iter()
+                .map(|outcome| &outcome.result)
+                .filter(|result| !result.passed)
+                .map(|result| BaselineEntry {
+                    file: result.file.clone(),
+                    category: result.category.clone(),
+                    description: result.description.clone(),
+                    expected: result.expected.clone(),
+                    actual: result.actual.clone(),
+                    error: result.error.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn contains(&self, result: &ValidationResult) -> bool {
+        self.failures.iter().any(|entry| {
+            entry.file == result.file
+                && entry.category == result.category
+                && entry.description == result.description
+        })
+    }
+}
+
+/// Results of one run split against the baseline (everything failing is new without one)
+struct BaselineOutcome<'a> {
+    known: Vec<&'a CaseOutcome>,
+    new: Vec<&'a CaseOutcome>,
+    fixed: Vec<&'a CaseOutcome>,
+}
+
+impl<'a> BaselineOutcome<'a> {
+    fn classify(results: &'a [CaseOutcome], baseline: Option<&Baseline>) -> Self {
+        let mut outcome = BaselineOutcome {
+            known: Vec::new(),
+            new: Vec::new(),
+            fixed: Vec::new(),
+        };
+        for entry in results {
+            let in_baseline = baseline.is_some_and(|baseline| baseline.contains(&entry.result));
+            match (entry.result.passed, in_baseline) {
+                (false, true) => outcome.known.push(entry),
+                (false, false) => outcome.new.push(entry),
+                (true, true) => outcome.fixed.push(entry),
+                (true, false) => {}
+            }
+        }
+        outcome
+    }
+
+    fn print(&self) {
+        if !self.new.is_empty() {
+            summary!("{}", "NEW FAILURES:".red().bold());
+            summary!();
+            for CaseOutcome { result, .. } in &self.new {
+                print_failure(result);
+            }
+        }
+        if !self.fixed.is_empty() {
+            summary!(
+                "{}",
+                "FIXED (now passing; remove from baseline):".green().bold()
+            );
+            summary!();
+            for CaseOutcome { result, .. } in &self.fixed {
+                summary!(
+                    "  {} [{}] {}",
+                    "✓".green(),
+                    result.category,
+                    result.description
+                );
+                summary!("    File: {}", result.file);
+            }
+            summary!();
+        }
+        if !self.known.is_empty() {
+            summary!("{}", "KNOWN FAILURES (baseline):".yellow().bold());
+            summary!();
+            for CaseOutcome { result, .. } in &self.known {
+                summary!(
+                    "  {} [{}] {} ({})",
+                    "~".yellow(),
+                    result.category,
+                    result.description,
+                    result.file
+                );
+            }
+            summary!();
+        }
+    }
+}
+
+// ============================================================================
+// REPORTS
+// ============================================================================
+//
+// The HTML report is one self-contained file (inline CSS, a few lines of
+// vanilla JS for the expanders) meant for reviewers outside the terminal.
+// Every fixture-derived string goes through `html_escape`. The CSV export is
+// one row per case for spreadsheets; its columns are append-only. JSON and
+// JUnit are for CI dashboards and carry the same per-category aggregates as
+// the terminal table, so nothing downstream has to recompute them.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum ReportFormat {
+    #[default]
+    Text,
+    Html,
+    Csv,
+    Json,
+    Junit,
+    /// `::error` workflow commands on stdout, one per failure
+    GhAnnotations,
+}
+
+/// CSV header; keep the order stable and only append new columns
+const CSV_COLUMNS: &[&str] = &[
+    "file",
+    "category",
+    "description",
+    "passed",
+    "expected",
+    "actual",
+    "error",
+    "tags",
+];
+
+/// Everything a report file is rendered from
+struct RunReport<'a> {
+    results: &'a [CaseOutcome],
+    skipped: &'a [SkippedCase],
+    file_errors: &'a [(String, String)],
+}
+
+/// Per-category counts for the report table
+#[derive(Debug, Default)]
+struct CategoryTally {
+    cases: usize,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    /// Summed run time of the category's cases
+    elapsed: Duration,
+}
+
+impl CategoryTally {
+    fn pass_rate(&self) -> f64 {
+        if self.cases == 0 {
+            0.0
+        } else {
+            self.passed as f64 / self.cases as f64 * 100.0
+        }
+    }
+
+    fn summary(&self) -> CategorySummary {
+        CategorySummary {
+            cases: self.cases,
+            passed: self.passed,
+            failed: self.failed,
+            skipped: self.skipped,
+            pass_rate: self.pass_rate(),
+            time_ms: self.elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// A `CategoryTally` as it appears in JSON reports
+#[derive(Debug, Serialize)]
+struct CategorySummary {
+    cases: usize,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    /// Percent of the cases that ran
+    pass_rate: f64,
+    time_ms: f64,
+}
+
+/// `--format json`: totals, per-category aggregates, the slowest cases and every case
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    validator_version: &'a str,
+    rapidfuzz_version: &'a str,
+    timestamp: String,
+    totals: CategorySummary,
+    categories: BTreeMap<&'a str, CategorySummary>,
+    slowest: Vec<JsonCase<'a>>,
+    cases: Vec<JsonCase<'a>>,
+    skipped: Vec<JsonSkipped<'a>>,
+    file_errors: Vec<JsonFileError<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonCase<'a> {
+    file: &'a str,
+    category: &'a str,
+    case_index: usize,
+    description: &'a str,
+    passed: bool,
+    expected: Option<&'a str>,
+    actual: Option<&'a str>,
+    error: Option<&'a str>,
+    repro: Option<&'a str>,
+    tags: &'a [String],
+    time_ms: f64,
+}
+
+impl<'a> JsonCase<'a> {
+    fn new(outcome: &'a CaseOutcome) -> Self {
+        let result = &outcome.result;
+        JsonCase {
+            file: &result.file,
+            category: &result.category,
+            case_index: outcome.key.case_index,
+            description: &result.description,
+            passed: result.passed,
+            expected: result.expected.as_deref(),
+            actual: result.actual.as_deref(),
+            error: result.error.as_deref(),
+            repro: result.repro.as_deref(),
+            tags: &outcome.tags,
+            time_ms: outcome.elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonSkipped<'a> {
+    file: &'a str,
+    category: &'a str,
+    description: &'a str,
+    reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonFileError<'a> {
+    file: &'a str,
+    error: &'a str,
+}
+
+// GitHub Actions reads `::error file=...,title=...::message` lines from a
+// step's stdout and pins them to the file in the PR view. A command must be a
+// single line: `%`, CR and LF are percent-encoded in the message, and `:` and
+// `,` as well in property values. Expected/actual strings can be whole
+// paragraphs, so they are cut to ANNOTATION_VALUE_CHARS first.
+
+/// Longest expected/actual value quoted in an annotation, in characters
+const ANNOTATION_VALUE_CHARS: usize = 200;
+
+/// Annotations for new failures, fixed baseline entries, file errors and `only` markers
+fn print_annotations(
+    outcome: &BaselineOutcome,
+    file_errors: &[(String, String)],
+    focused_paths: &BTreeSet<String>,
+) {
+    for CaseOutcome { key, result, .. } in &outcome.new {
+        print_annotation(&key.path, &result.category, &annotation_message(result));
+    }
+    for CaseOutcome { key, result, .. } in &outcome.fixed {
+        let message = format!("{} — now passing; remove from baseline", result.description);
+        print_annotation(&key.path, &result.category, &message);
+    }
+    for (file, error) in file_errors {
+        print_annotation(file, "file error", error);
+    }
+    for path in focused_paths {
+        print_annotation(
+            path,
+            "only",
+            "`only` markers found; remove them before committing",
+        );
+    }
+}
+
+fn print_annotation(file: &str, title: &str, message: &str) {
+    println!(
+        "::error file={},title={}::{}",
+        escape_annotation_property(&annotation_path(file)),
+        escape_annotation_property(title),
+        escape_annotation_data(message)
+    );
+}
+
+/// `description — expected X, got Y` plus the error, if any
+fn annotation_message(result: &ValidationResult) -> String {
+    let mut message = result.description.clone();
+    match (&result.expected, &result.actual) {
+        (Some(expected), Some(actual)) => message.push_str(&format!(
+            " — expected {}, got {}",
+            truncate_annotation_value(expected),
+            truncate_annotation_value(actual)
+        )),
+        (Some(expected), None) => message.push_str(&format!(
+            " — expected {}",
+            truncate_annotation_value(expected)
+        )),
+        (None, Some(actual)) => {
+            message.push_str(&format!(" — got {}", truncate_annotation_value(actual)))
+        }
+        (None, None) => {}
+    }
+    if let Some(error) = &result.error {
+        message.push_str(&format!(" — {}", truncate_annotation_value(error)));
+    }
+    message
+}
+
+fn truncate_annotation_value(value: &str) -> String {
+    let total = value.chars().count();
+    if total <= ANNOTATION_VALUE_CHARS {
+        return value.to_string();
+    }
+    let kept: String = value.chars().take(ANNOTATION_VALUE_CHARS).collect();
+    format!("{}… ({} more chars)", kept, total - ANNOTATION_VALUE_CHARS)
+}
+
+/// Fixture path relative to the working directory (the checkout root in CI)
+fn annotation_path(file: &str) -> String {
+    let path = Path::new(file);
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf());
+    relative
+        .to_string_lossy()
+        .trim_start_matches("./")
+        .replace('\\', "/")
+}
+
+fn escape_annotation_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_annotation_property(value: &str) -> String {
+    escape_annotation_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Above this many DP cells an inline diff marks the whole strings as changed
+const MAX_INLINE_DIFF_CELLS: usize = 1_000_000;
+
+const REPORT_STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+h1{margin-bottom:.25rem}.meta{color:#666;margin-top:0}\
+.counts span{display:inline-block;margin-right:1.5rem;font-size:1.1rem}\
+.pass{color:#1a7f37}.fail{color:#cf222e}.skip{color:#9a6700}\
+table{border-collapse:collapse;margin:1rem 0;width:100%}\
+th,td{border:1px solid #d0d7de;padding:.35rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}td.num{text-align:right}\
+tr.failure{cursor:pointer}tr.failure:hover{background:#f6f8fa}\
+tr.failure td:first-child::before{content:'\\25B8 ';color:#666}\
+tr.failure.open td:first-child::before{content:'\\25BE '}\
+.value{font-family:ui-monospace,monospace;white-space:pre-wrap;word-break:break-all}\
+del{background:#ffebe9;color:#82071e;text-decoration:none}\
+ins{background:#dafbe1;color:#116329;text-decoration:none}";
+
+const REPORT_SCRIPT: &str = "\
+document.querySelectorAll('tr.failure').forEach(function(row){\
+row.addEventListener('click',function(){\
+var detail=row.nextElementSibling;detail.hidden=!detail.hidden;\
+row.classList.toggle('open',!detail.hidden);});});";
+
+impl RunReport<'_> {
+    /// Write the report to `path` (`-` for stdout); text has no report file
+    fn write(&self, format: ReportFormat, path: &Path) -> std::io::Result<()> {
+        let contents = match format {
+            ReportFormat::Text | ReportFormat::GhAnnotations => return Ok(()),
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Junit => self.to_junit(),
+        };
+        if path == Path::new("-") {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(contents.as_bytes())?;
+            stdout.flush()
+        } else {
+            fs::write(path, contents)
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = csv_row(CSV_COLUMNS.iter().copied());
+        for CaseOutcome { tags, result, .. } in self.results {
+            let tags = tags.join(";");
+            csv.push_str(&csv_row([
+                result.file.as_str(),
+                result.category.as_str(),
+                result.description.as_str(),
+                if result.passed { "true" } else { "false" },
+                result.expected.as_deref().unwrap_or(""),
+                result.actual.as_deref().unwrap_or(""),
+                result.error.as_deref().unwrap_or(""),
+                tags.as_str(),
+            ]));
+        }
+        csv
+    }
+
+    fn tallies(&self) -> BTreeMap<&str, CategoryTally> {
+        let mut tallies: BTreeMap<&str, CategoryTally> = BTreeMap::new();
+        for CaseOutcome {
+            result, elapsed, ..
+        } in self.results
+        {
+            let tally = tallies.entry(result.category.as_str()).or_default();
+            tally.cases += 1;
+            tally.elapsed += *elapsed;
+            if result.passed {
+                tally.passed += 1;
+            } else {
+                tally.failed += 1;
+            }
+        }
+        for case in self.skipped {
+            tallies.entry(case.category.as_str()).or_default().skipped += 1;
+        }
+        tallies
+    }
+
+    /// All categories added together
+    fn totals(tallies: &BTreeMap<&str, CategoryTally>) -> CategoryTally {
+        let mut totals = CategoryTally::default();
+        for tally in tallies.values() {
+            totals.cases += tally.cases;
+            totals.passed += tally.passed;
+            totals.failed += tally.failed;
+            totals.skipped += tally.skipped;
+            totals.elapsed += tally.elapsed;
+        }
+        totals
+    }
+
+    fn to_json(&self) -> String {
+        let tallies = self.tallies();
+        let report = JsonReport {
+            validator_version: VERSION,
+            rapidfuzz_version: RAPIDFUZZ_VERSION,
+            timestamp: Utc::now().to_rfc3339(),
+            totals: Self::totals(&tallies).summary(),
+            categories: tallies
+                .iter()
+                .map(|(category, tally)| (*category, tally.summary()))
+                .collect(),
+            slowest: slowest_cases(self.results)
+                .into_iter()
+                .map(JsonCase::new)
+                .collect(),
+            cases: self.results.iter().map(JsonCase::new).collect(),
+            skipped: self
+                .skipped
+                .iter()
+                .map(|case| JsonSkipped {
+                    file: &case.file,
+                    category: &case.category,
+                    description: &case.description,
+                    reason: case.reason.as_deref(),
+                })
+                .collect(),
+            file_errors: self
+                .file_errors
+                .iter()
+                .map(|(file, error)| JsonFileError { file, error })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&report).expect("Failed to serialize report") + "\n"
+    }
+
+    /// One `<testsuite>` per category (its counts and time are that category's aggregates, the
+    /// pass rate a property), plus a `file errors` suite when fixtures could not be loaded
+    fn to_junit(&self) -> String {
+        let tallies = self.tallies();
+        let totals = Self::totals(&tallies);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"similarity-validator\" tests=\"{}\" failures=\"{}\" \
+             errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+            totals.cases + totals.skipped,
+            totals.failed,
+            self.file_errors.len(),
+            totals.skipped,
+            totals.elapsed.as_secs_f64()
+        ));
+        for (category, tally) in &tallies {
+            let name = html_escape(category);
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" \
+                 skipped=\"{}\" time=\"{:.6}\">\n",
+                name,
+                tally.cases + tally.skipped,
+                tally.failed,
+                tally.skipped,
+                tally.elapsed.as_secs_f64()
+            ));
+            xml.push_str(&format!(
+                "    <properties>\n      <property name=\"pass_rate\" value=\"{:.1}\"/>\n    \
+                 </properties>\n",
+                tally.pass_rate()
+            ));
+            let outcomes = self
+                .results
+                .iter()
+                .filter(|outcome| outcome.result.category == *category);
+            for CaseOutcome {
+                result, elapsed, ..
+            } in outcomes
+            {
+                let open = format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\" time=\"{:.6}\"",
+                    name,
+                    html_escape(&result.description),
+                    html_escape(&result.file),
+                    elapsed.as_secs_f64()
+                );
+                if result.passed {
+                    xml.push_str(&open);
+                    xml.push_str("/>\n");
+                    continue;
+                }
+                let mut detail = Vec::new();
+                if let Some(expected) = &result.expected {
+                    detail.push(format!("Expected: {}", expected));
+                }
+                if let Some(actual) = &result.actual {
+                    detail.push(format!("Actual:   {}", actual));
+                }
+                if let Some(repro) = &result.repro {
+                    detail.push(format!("Repro:    {}", repro));
+                }
+                xml.push_str(&format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                    open,
+                    html_escape(result.error.as_deref().unwrap_or("Value mismatch")),
+                    html_escape(&detail.join("\n"))
+                ));
+            }
+            for case in self
+                .skipped
+                .iter()
+                .filter(|case| case.category == *category)
+            {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\">\n      \
+                     <skipped message=\"{}\"/>\n    </testcase>\n",
+                    name,
+                    html_escape(&case.description),
+                    html_escape(&case.file),
+                    html_escape(case.reason.as_deref().unwrap_or(""))
+                ));
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        if !self.file_errors.is_empty() {
+            xml.push_str(&format!(
+                "  <testsuite name=\"file errors\" tests=\"{0}\" failures=\"0\" errors=\"{0}\" \
+                 skipped=\"0\" time=\"0\">\n",
+                self.file_errors.len()
+            ));
+            for (file, error) in self.file_errors {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"file errors\" name=\"{0}\" file=\"{0}\">\n      \
+                     <error message=\"{1}\"/>\n    </testcase>\n",
+                    html_escape(file),
+                    html_escape(error)
+                ));
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    fn to_html(&self) -> String {
+        let tallies = self.tallies();
+        let passed = self
+            .results
+            .iter()
+            .filter(|outcome| outcome.result.passed)
+            .count();
+        let failed = self.results.len() - passed;
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>Similarity fixture validation report</title>\n");
+        html.push_str(&format!(
+            "<style>{}</style>\n</head>\n<body>\n",
+            REPORT_STYLE
+        ));
+        html.push_str("<h1>Similarity fixture validation report</h1>\n");
+        html.push_str(&format!(
+            "<p class=\"meta\">similarity-validator {} &middot; rapidfuzz-rs {} &middot; {}</p>\n",
+            html_escape(VERSION),
+            html_escape(RAPIDFUZZ_VERSION),
+            html_escape(&Utc::now().to_rfc3339())
+        ));
+        html.push_str(&format!(
+            "<p class=\"counts\"><span>Total: {}</span><span class=\"pass\">Passed: {}</span>\
+             <span class=\"fail\">Failed: {}</span><span class=\"skip\">Skipped: {}</span>\
+             <span class=\"fail\">File errors: {}</span></p>\n",
+            self.results.len(),
+            passed,
+            failed,
+            self.skipped.len(),
+            self.file_errors.len()
+        ));
+
+        html.push_str(
+            "<h2>Categories</h2>\n<table>\n<tr><th>Category</th><th>Cases</th>\
+                       <th>Passed</th><th>Failed</th><th>Skipped</th><th>Pass rate</th></tr>\n",
+        );
+        for (category, tally) in &tallies {
+            let name = if tally.failed > 0 {
+                format!("<a href=\"#category-{0}\">{0}</a>", html_escape(category))
+            } else {
+                html_escape(category)
+            };
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num pass\">{}</td>\
+                 <td class=\"num fail\">{}</td><td class=\"num skip\">{}</td>\
+                 <td class=\"num\">{:.1}%</td></tr>\n",
+                name,
+                tally.cases,
+                tally.passed,
+                tally.failed,
+                tally.skipped,
+                tally.pass_rate()
+            ));
+        }
+        html.push_str("</table>\n");
+
+        if !self.file_errors.is_empty() {
+            html.push_str("<h2 class=\"fail\">File errors</h2>\n<table>\n");
+            html.push_str("<tr><th>File</th><th>Error</th></tr>\n");
+            for (file, error) in self.file_errors {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td class=\"value\">{}</td></tr>\n",
+                    html_escape(file),
+                    html_escape(error)
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        for (category, tally) in tallies.iter().filter(|(_, tally)| tally.failed > 0) {
+            html.push_str(&format!(
+                "<h2 id=\"category-{0}\">{0} <span class=\"fail\">({1} failed)</span></h2>\n",
+                html_escape(category),
+                tally.failed
+            ));
+            html.push_str("<table>\n<tr><th>Description</th><th>File</th><th>Error</th></tr>\n");
+            let failures = self
+                .results
+                .iter()
+                .map(|outcome| &outcome.result)
+                .filter(|result| !result.passed && result.category == *category);
+            for result in failures {
+                html.push_str(&format!(
+                    "<tr class=\"failure\"><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(&result.description),
+                    html_escape(&result.file),
+                    html_escape(result.error.as_deref().unwrap_or(""))
+                ));
+                let (expected, actual) = inline_diff(
+                    result.expected.as_deref().unwrap_or(""),
+                    result.actual.as_deref().unwrap_or(""),
+                );
+                html.push_str(&format!(
+                    "<tr hidden><td colspan=\"3\">Expected: <span class=\"value\">{}</span><br>\
+                     Actual: <span class=\"value\">{}</span></td></tr>\n",
+                    expected, actual
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        if !self.skipped.is_empty() {
+            html.push_str("<h2 class=\"skip\">Skipped</h2>\n<table>\n");
+            html.push_str(
+                "<tr><th>Category</th><th>Description</th><th>File</th><th>Reason</th></tr>\n",
+            );
+            for case in self.skipped {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(&case.category),
+                    html_escape(&case.description),
+                    html_escape(&case.file),
+                    html_escape(case.reason.as_deref().unwrap_or(""))
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str(&format!(
+            "<script>{}</script>\n</body>\n</html>\n",
+            REPORT_SCRIPT
+        ));
+        html
+    }
+}
+
+/// One RFC 4180 record: fields with commas, quotes or line breaks are quoted, quotes doubled
+fn csv_row<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    fields.join(",") + "\r\n"
+}
+
+/// Escapes text and attribute values for the HTML report; the JUnit XML uses it too
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escaped expected/actual markup with character-level changes wrapped in `<del>`/`<ins>`
+fn inline_diff(expected: &str, actual: &str) -> (String, String) {
+    let a: Vec<char> = expected.chars().collect();
+    let b: Vec<char> = actual.chars().collect();
+    let span = |chars: &[char], tag: Option<&str>| {
+        let text = html_escape(&chars.iter().collect::<String>());
+        match tag {
+            Some(tag) if !text.is_empty() => format!("<{0}>{1}</{0}>", tag, text),
+            _ => text,
+        }
+    };
+    if (a.len() + 1) * (b.len() + 1) > MAX_INLINE_DIFF_CELLS {
+        return (span(&a, Some("del")), span(&b, Some("ins")));
+    }
+
+    let (mut old, mut new) = (String::new(), String::new());
+    for op in opcodes(&alignment_steps(&a, &b, false)) {
+        let (src, dest) = (&a[op.src_start..op.src_end], &b[op.dest_start..op.dest_end]);
+        let changed = op.tag != EditTag::Equal;
+        old.push_str(&span(src, changed.then_some("del")));
+        new.push_str(&span(dest, changed.then_some("ins")));
+    }
+    (old, new)
+}
+
+// ============================================================================
+// GENERATION MODE
+// ============================================================================
+
+/// Settings for a generate run
+#[derive(Debug, Default)]
+struct GenerateOptions {
+    overwrite: bool,
+    dry_run: bool,
+    migrate: bool,
+    /// Leave suggestion `normalized_value` out of the output
+    no_debug_fields: bool,
+    /// Generate cases marked `skip: true`
+    include_skipped: bool,
+    /// Decimal places for generated expected values (None = shortest round-trip form)
+    precision: Option<u32>,
+    filter: CaseFilter,
+}
+
+fn generate_fixture(input_path: &Path, output_path: Option<&Path>, options: &GenerateOptions) {
+    let output_path = output_path.unwrap_or(input_path);
+    let filter = &options.filter;
+
+    println!("{} {}", "Generating fixture:".cyan(), input_path.display());
+
+    // Read fixture
+    let mut fixture = load_fixture(input_path).unwrap_or_else(|e| {
+        eprintln!("{} {}: {}", "Error".red(), input_path.display(), e);
+        process::exit(1);
+    });
+
+    if options.migrate {
+        migrate_fixture(fixture, input_path, output_path, options.dry_run);
+        return;
+    }
+
+    // Both sides of the dry-run diff go through the serializer, so only values differ
+    let before = serde_yaml::to_string(&fixture).expect("Failed to serialize YAML");
+
+    // Generate expected values for each test case
+    let mut total_generated = 0;
+    let mut skipped = 0;
+    let mut pending = 0;
+    let mut filtered = 0;
+    let focused = has_focused_cases(&fixture);
+
+    for group in &mut fixture.test_cases {
+        for case in &mut group.cases {
+            let tags = effective_tags(group.tags.as_deref(), case.tags.as_deref());
+            if !filter.matches(&group.category, &tags) || (focused && !case.only) {
+                filtered += 1;
+                continue;
+            }
+            if case.skip && !options.include_skipped {
+                pending += 1;
+                continue;
+            }
+            let original = case.clone();
+            let generated = generate_test_case(&group.category, case, options.overwrite);
+            if generated {
+                if let Some(digits) = options.precision {
+                    round_expectations(case, digits);
+                }
+                // Unchanged values keep their loaded form (key order, number formatting)
+                if expectation_values(&original) == expectation_values(case) {
+                    *case = original;
+                }
+                case.checksum = None;
+                total_generated += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+    }
+
+    if options.no_debug_fields {
+        strip_debug_fields(&mut fixture);
+    }
+    if fixture.checksums {
+        sign_cases(&mut fixture);
+    }
+    let after = serde_yaml::to_string(&fixture).expect("Failed to serialize YAML");
+
+    // Update generator metadata
+    let mut generator = GeneratorMetadata {
+        tool: "similarity-validator".to_string(),
+        tool_version: VERSION.to_string(),
+        source_library: "rapidfuzz-rs".to_string(),
+        source_version: RAPIDFUZZ_VERSION.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        command: Some(generate_command(input_path, options)),
+        sources: None,
+        seed: None,
+        precision: options.precision,
+        arithmetic: Some(string_metrics_core::ARITHMETIC_MODE.to_string()),
+    };
+    // A regenerate that changes nothing keeps the old timestamp, so the file is byte-identical
+    if let Some(previous) = fixture.generator.take().filter(|_| before == after) {
+        let unchanged = GeneratorMetadata {
+            generated_at: previous.generated_at.clone(),
+            ..generator.clone()
+        };
+        if unchanged == previous {
+            generator = previous;
+        }
+    }
+    fixture.generator = Some(generator);
+
+    // Update notes
+    fixture.notes = Some(format!(
+        "GENERATED FILE - DO NOT EDIT EXPECTED VALUES BY HAND\n\n\
+         This fixture was generated using similarity-validator with rapidfuzz-rs as the\n\
+         canonical source. Values are authoritative and ready for cross-language validation.\n\n\
+         Reference implementations:\n\
+         - Canonical: rapidfuzz-rs 3.x (Rust, used for generation)\n\
+         - Cross-check: rapidfuzz 3.x (Python, pyfulmen; see `similarity-validator cross-check`)\n\
+         - Under test: string-metrics-wasm (TypeScript/WASM)\n\n\
+         To regenerate:\n\
+           similarity-validator generate --input {} --overwrite",
+        input_path.display()
+    ));
+
+    println!(
+        "Generated: {} | Skipped: {} | Pending (skip: true): {} | Filtered out: {}",
+        total_generated.to_string().green(),
+        skipped.to_string().yellow(),
+        pending.to_string().yellow(),
+        filtered.to_string().yellow()
+    );
+
+    if options.dry_run {
+        println!("{}", "\n[DRY RUN - No files written]".yellow());
+        println!();
+        let diff = unified_diff(
+            &format!("{} (current)", output_path.display()),
+            &format!("{} (generated)", output_path.display()),
+            &before,
+            &after,
+        );
+        if diff.is_empty() {
+            println!("No expected values would change.");
+        }
+        for line in diff.lines() {
+            match line.chars().next() {
+                Some('+') if !line.starts_with("+++") => println!("{}", line.green()),
+                Some('-') if !line.starts_with("---") => println!("{}", line.red()),
+                Some('@') => println!("{}", line.cyan()),
+                _ => println!("{}", line),
+            }
+        }
+        // Exploratory: a dry run exits 0 even with `only` markers
+        return;
+    }
+
+    // Write output
+    let yaml = serde_yaml::to_string(&fixture).expect("Failed to serialize YAML");
+    fs::write(output_path, yaml).expect("Failed to write output file");
+    println!("{} {}", "✅ Written:".green(), output_path.display());
+
+    if focused {
+        println!(
+            "{} `only` markers found; generated only the marked cases",
+            "Warning:".yellow().bold()
+        );
+        process::exit(1);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineChange {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Lines of context around each change in a unified diff
+const DIFF_CONTEXT: usize = 3;
+
+/// Myers shortest edit script; each step carries the old/new line positions before it
+fn diff_lines(a: &[&str], b: &[&str]) -> Vec<(LineChange, usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m;
+    let index = |k: isize| (k + offset) as usize;
+    let mut v = vec![0isize; 2 * (n + m) as usize + 2];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=offset {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[index(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            steps.push((LineChange::Same, x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                steps.push((LineChange::Added, x as usize, prev_y as usize));
+            } else {
+                steps.push((LineChange::Removed, prev_x as usize, y as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    steps.reverse();
+    steps
+}
+
+/// Unified diff of two texts (empty when they are equal)
+fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let steps = diff_lines(&a, &b);
+    let changes: Vec<usize> = (0..steps.len())
+        .filter(|&i| steps[i].0 != LineChange::Same)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Merge changes whose context windows touch into hunks of step indices
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changes {
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(steps.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_label, new_label);
+    for (start, end) in hunks {
+        let hunk = &steps[start..end];
+        let old_count = hunk.iter().filter(|s| s.0 != LineChange::Added).count();
+        let new_count = hunk.iter().filter(|s| s.0 != LineChange::Removed).count();
+        let (_, old_pos, new_pos) = hunk[0];
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_pos + usize::from(old_count > 0),
+            old_count,
+            new_pos + usize::from(new_count > 0),
+            new_count
+        ));
+        for &(change, old_pos, new_pos) in hunk {
+            let line = match change {
+                LineChange::Same => format!(" {}", a[old_pos]),
+                LineChange::Removed => format!("-{}", a[old_pos]),
+                LineChange::Added => format!("+{}", b[new_pos]),
+            };
+            diff.push_str(&line);
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Round the float expected values of a freshly generated case to `digits` decimal places
+fn round_expectations(case: &mut TestCase, digits: u32) {
+    let scale = 10f64.powi(digits as i32);
+    let round = |x: f64| (x * scale).round() / scale;
+    fn round_floats(value: &mut serde_yaml::Value, round: &dyn Fn(f64) -> f64) {
+        match value {
+            serde_yaml::Value::Number(n) if n.is_f64() => {
+                if let Some(x) = n.as_f64() {
+                    *value = serde_yaml::Value::from(round(x));
+                }
+            }
+            serde_yaml::Value::Sequence(items) => {
+                items.iter_mut().for_each(|item| round_floats(item, round))
+            }
+            serde_yaml::Value::Mapping(map) => {
+                map.values_mut().for_each(|item| round_floats(item, round))
+            }
+            _ => {}
+        }
+    }
+
+    case.expected_score = case.expected_score.map(round);
+    if let Some(expected) = case.expected.as_mut() {
+        round_floats(expected, &round);
+    }
+    for (key, value) in case.inputs.iter_mut() {
+        if is_expectation_key(key) {
+            round_floats(value, &round);
+        }
+    }
+}
+
+/// Remove debugging fields (suggestion `normalized_value`) from every case in the fixture
+fn strip_debug_fields(fixture: &mut Fixture) {
+    let suggestions = fixture
+        .test_cases
+        .iter_mut()
+        .filter(|group| group.category == "suggestions")
+        .flat_map(|group| group.cases.iter_mut())
+        .filter_map(|case| case.expected.as_mut())
+        .filter_map(|expected| expected.as_sequence_mut());
+    for suggestion in suggestions.flatten() {
+        if let Some(map) = suggestion.as_mapping_mut() {
+            map.remove("normalized_value");
+        }
+    }
+}
+
+/// Reconstruct the generate invocation recorded in GeneratorMetadata
+fn generate_command(input_path: &Path, options: &GenerateOptions) -> String {
+    let filter = &options.filter;
+    let mut command = format!(
+        "similarity-validator generate --input {}",
+        input_path.display()
+    );
+    if options.overwrite {
+        command.push_str(" --overwrite");
+    }
+    if options.no_debug_fields {
+        command.push_str(" --no-debug-fields");
+    }
+    if options.include_skipped {
+        command.push_str(" --include-skipped");
+    }
+    if let Some(digits) = options.precision {
+        command.push_str(&format!(" --precision {}", digits));
+    }
+    if filter.is_active() {
+        command.push(' ');
+        command.push_str(&filter.describe());
+    }
+    command
+}
+
+fn generate_test_case(category: &str, case: &mut TestCase, overwrite: bool) -> bool {
+    // Range-based expectations are hand-written; exact values would defeat their purpose
+    if case.expected_score_range.is_some() {
+        return false;
+    }
+    // Negative cases have no expected values to generate
+    if case.expect_error.is_some() {
+        return false;
+    }
+
+    match category {
+        "levenshtein" => generate_levenshtein(case, overwrite),
+        "damerau_osa" => generate_damerau_osa(case, overwrite),
+        "damerau_unrestricted" => generate_damerau_unrestricted(case, overwrite),
+        "damerau_graphemes" => generate_damerau_graphemes(case, overwrite),
+        "damerau_weighted" => generate_damerau_weighted(case, overwrite),
+        "mfc" => generate_mfc(case, overwrite),
+        "affine_gap" => generate_affine_gap(case, overwrite),
+        "jaro_winkler" => generate_jaro_winkler(case, overwrite),
+        "jaro_winkler_params" => generate_jaro_winkler_params(case, overwrite),
+        "jaro_tokens" | "jaro_winkler_tokens" => generate_jaro_tokens(category, case, overwrite),
+        "indel" => generate_indel(case, overwrite),
+        "lcs_seq" => generate_lcs_seq(case, overwrite),
+        "ratio" => generate_ratio(case, overwrite),
+        "hamming" => generate_hamming(case, overwrite),
+        "byte_metrics" => generate_byte_metrics(case, overwrite),
+        "positional_diff" => generate_positional_diff(case, overwrite),
+        "prefix" => generate_prefix(case, overwrite),
+        "postfix" => generate_postfix(case, overwrite),
+        "dice" => generate_dice(case, overwrite),
+        "jaccard_ngram" => generate_jaccard_ngram(case, overwrite),
+        "weighted_jaccard" => generate_weighted_jaccard(case, overwrite),
+        "substring" => generate_substring(case, overwrite),
+        "coverage" => generate_coverage(case, overwrite),
+        "excerpt" => generate_excerpt(case, overwrite),
+        "empty_inputs" => generate_empty_inputs(case, overwrite),
+        "fuzzy_find" => generate_fuzzy_find(case, overwrite),
+        "normalization_presets" => generate_normalization(case, overwrite),
+        "normalization_locale" => generate_normalization_locale(case, overwrite),
+        "numeric_tokens" => generate_numeric_tokens(case, overwrite),
+        "case_transfer" => generate_case_transfer(case, overwrite),
+        "normalized_key" => generate_normalized_key(case, overwrite),
+        "script_detection" => generate_script_detection(case, overwrite),
+        "suggestions" => generate_suggestions(case, overwrite),
+        "did_you_mean" => generate_did_you_mean(case, overwrite),
+        "unified_distance" => generate_unified_distance(case, overwrite),
+        "unified_score" => generate_unified_score(case, overwrite),
+        "anchored_similarity" => generate_anchored_similarity(case, overwrite),
+        "score_nullable" => generate_score_nullable(case, overwrite),
+        "extract_one" | "extract" => generate_extract(category, case, overwrite),
+        "editops" | "opcodes" => generate_edit_operations(category, case, overwrite),
+        "word_diff" => generate_word_diff(case, overwrite),
+        "match_profile" => generate_match_profile(case, overwrite),
+        "window_scores" => generate_window_scores(case, overwrite),
+        // Invariants have no expected values to fill in
+        "properties" => false,
+        // TypeScript-only categories - skip generation
+        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" => false,
+        _ => {
+            eprintln!("⚠️  Unknown category: {}", category);
+            false
+        }
+    }
+}
+
+fn generate_levenshtein(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars());
+    let score =
+        rapidfuzz::distance::levenshtein::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_damerau_osa(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars());
+    let score = rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_damerau_unrestricted(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance =
+        rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars());
+    let score = rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
+        input_a.chars(),
+        input_b.chars(),
+    );
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_damerau_graphemes(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    case.expected_distance = Some(string_metrics_core::damerau_levenshtein_graphemes(
+        &input_a, &input_b,
+    ));
+    case.expected_score = Some(
+        string_metrics_core::damerau_levenshtein_graphemes_normalized_similarity(
+            &input_a, &input_b,
+        ),
+    );
+    true
+}
+
+fn generate_damerau_weighted(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let (cost, score) = damerau_weighted_values(&case.inputs);
+    case.expected = Some(serde_yaml::Value::from(cost));
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_mfc(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let (signatures, score) = mfc_values(&case.inputs);
+    case.expected = Some(serde_yaml::Value::from(signatures));
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_affine_gap(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    // Invalid penalties are negative cases; leave them for expect_error
+    let Ok((cost, score)) = affine_gap_values(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::from(cost));
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_jaro_winkler(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let score = rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars());
+
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_jaro_winkler_params(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    case.expected_score = Some(jaro_winkler_params_score(&case.inputs));
+    true
+}
+
+fn generate_jaro_tokens(category: &str, case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    let Ok(score) = jaro_tokens_score(category, &case.inputs) else {
+        return false;
+    };
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_indel(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars());
+    let score = rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_lcs_seq(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars());
+    let score =
+        rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_ratio(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let score = rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars());
+
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_hamming(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+    let pad = hamming_pad(&case.inputs);
+
+    let computed = string_metrics_core::hamming_distance(&input_a, &input_b, pad).and_then(|d| {
+        string_metrics_core::hamming_normalized_similarity(&input_a, &input_b, pad).map(|s| (d, s))
+    });
+    let (distance, score) = match computed {
+        Ok(values) => values,
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            return false;
+        }
+    };
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_byte_metrics(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    // Bad base64, an unknown metric or unequal unpadded lengths are negative cases;
+    // leave them for expect_error
+    let Ok((distance, score)) = byte_metric_values(&case.inputs) else {
+        return false;
+    };
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_positional_diff(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // Unequal lengths without `pad` are a negative case; leave it for expect_error
+    let Ok(fields) = positional_diff_fields(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Mapping(fields));
+    true
+}
+
+fn generate_prefix(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::prefix::distance(input_a.chars(), input_b.chars());
+    let score =
+        rapidfuzz::distance::prefix::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_postfix(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    let distance = rapidfuzz::distance::postfix::distance(input_a.chars(), input_b.chars());
+    let score =
+        rapidfuzz::distance::postfix::normalized_similarity(input_a.chars(), input_b.chars());
+
+    case.expected_distance = Some(distance);
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_dice(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    case.expected_score = Some(string_metrics_core::dice(&input_a, &input_b));
+    true
+}
+
+fn generate_jaccard_ngram(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+
+    match string_metrics_core::jaccard_ngram(&input_a, &input_b, ngram_size(&case.inputs)) {
+        Ok(score) => {
+            case.expected_score = Some(score);
+            true
+        }
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            false
+        }
+    }
+}
+
+fn generate_weighted_jaccard(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    // Invalid weights or presets are negative cases; leave them for expect_error
+    let Ok(score) = case_weighted_jaccard(&case.inputs) else {
+        return false;
+    };
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_unified_distance(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && unified_expected_distance(case).is_some() {
+        return false;
+    }
+
+    let metric =
+        get_string_input(&case.inputs, "metric").unwrap_or_else(|| "levenshtein".to_string());
+
+    match case_unified_distance(&case.inputs, &metric) {
+        Ok(distance) => {
+            // Written as `expected`, which the TypeScript suite reads too
+            case.expected_distance = None;
+            case.expected = Some((distance as u64).into());
+            true
+        }
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            false
+        }
+    }
+}
+
+fn generate_unified_score(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && unified_expected_score(case).is_some() {
+        return false;
+    }
+
+    let metric =
+        get_string_input(&case.inputs, "metric").unwrap_or_else(|| "jaro_winkler".to_string());
+
+    // TypeScript-only - skip generation
+    if matches!(
+        metric.as_str(),
+        "partial_ratio" | "token_sort_ratio" | "token_set_ratio"
+    ) {
+        return false;
+    }
+
+    match case_unified_score(&case.inputs, &metric) {
+        Ok(score) => {
+            case.expected_score = None;
+            case.expected = Some(score.into());
+            true
+        }
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            false
+        }
+    }
+}
+
+fn generate_anchored_similarity(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() {
+        return false;
+    }
+
+    // An unknown side, metric or preset is a negative case; leave it for expect_error
+    let Ok(score) = case_anchored_similarity(&case.inputs) else {
+        return false;
+    };
+    case.expected_score = Some(score);
+    true
+}
+
+fn generate_score_nullable(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // An unknown metric, preset or policy is a negative case; leave it for expect_error
+    let Ok(fields) = score_nullable_fields(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Mapping(fields));
+    true
+}
+
+fn generate_substring(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected_score.is_some() && case.expected_range.is_some() {
+        return false;
+    }
+
+    let needle = get_string_input(&case.inputs, "needle").unwrap_or_default();
+    let haystack = get_string_input(&case.inputs, "haystack").unwrap_or_default();
+    let options = match substring_options(&case.inputs) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("⚠️  Skipping substring case {:?}: {}", case.description, e);
+            return false;
+        }
+    };
+
+    let (score, range) = string_metrics_core::substring_similarity(&needle, &haystack, options);
+    let (_, all_ranges) =
+        string_metrics_core::substring_all_ranges(&needle, &haystack, options.range_unit);
+
+    case.expected_score = Some(score);
+    case.expected_range = range.map(Range::from);
+    // Recorded where a single range would hide another occurrence, or where the case asks
+    if all_ranges.len() > 1 || case.expected_ranges.is_some() {
+        case.expected_ranges = Some(all_ranges.into_iter().map(Range::from).collect());
+    }
+    true
+}
+
+fn generate_coverage(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    match coverage_fields(&case.inputs) {
+        Ok(fields) => {
+            case.expected = Some(serde_yaml::Value::Mapping(fields));
+            true
+        }
+        Err(e) => {
+            eprintln!("⚠️  Skipping coverage case {:?}: {}", case.description, e);
+            false
+        }
+    }
+}
+
+fn generate_excerpt(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // A range the input can't hold is a negative case; leave it for expect_error
+    let Ok(fields) = excerpt_fields(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Mapping(fields));
+    true
+}
+
+fn generate_empty_inputs(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    match empty_inputs_fields(&case.inputs) {
+        Ok(fields) => {
+            case.expected = Some(serde_yaml::Value::Mapping(fields));
+            true
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠️  Skipping empty_inputs case {:?}: {}",
+                case.description, e
+            );
+            false
+        }
+    }
+}
+
+fn generate_normalization(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
+    let preset = get_string_input(&case.inputs, "preset").unwrap_or_default();
+    let locale = get_string_input(&case.inputs, "locale");
+    let options = normalize_options(&case.inputs, locale.as_deref());
+
+    let normalized = normalize_with_options(&input, &preset, &options);
+
+    case.expected = Some(serde_yaml::Value::String(normalized));
+    true
+}
+
+fn generate_normalization_locale(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
+    let preset = get_string_input(&case.inputs, "preset").unwrap_or_default();
+    let locale = case
+        .inputs
+        .get("locale")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let normalized = normalize_with_options(
+        &input,
+        &preset,
+        &NormalizeOptions::with_locale(locale.as_deref()),
+    );
+
+    case.expected = Some(serde_yaml::Value::String(normalized));
+    true
+}
+
+fn generate_numeric_tokens(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
+    let folded = string_metrics_core::fold_numeric_tokens(&input);
+
+    case.expected = Some(serde_yaml::Value::String(folded));
+    true
+}
+
+fn generate_case_transfer(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    case.expected = Some(serde_yaml::Value::String(case_transferred(&case.inputs)));
+    true
+}
+
+fn generate_did_you_mean(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    case.expected = Some(serde_yaml::Value::Mapping(did_you_mean_fields(
+        &case.inputs,
+    )));
+    true
+}
+
+fn generate_suggestions(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    let results = rank_suggestions(&SuggestionQuery::from_case(case));
+
+    // Convert to YAML format
+    let suggestions: Vec<serde_yaml::Value> = results
+        .iter()
+        .map(|r| {
+            let mut map = serde_yaml::Mapping::new();
+            map.insert(
+                serde_yaml::Value::String("value".to_string()),
+                serde_yaml::Value::String(r.value.clone()),
+            );
+            if let Some(ref id) = r.id {
+                map.insert(
+                    serde_yaml::Value::String("id".to_string()),
+                    serde_yaml::Value::String(id.clone()),
+                );
+            }
+            map.insert(
+                serde_yaml::Value::String("score".to_string()),
+                serde_yaml::to_value(r.score).expect("Failed to serialize score"),
+            );
+            // Include normalized_value for debugging
+            map.insert(
+                serde_yaml::Value::String("normalized_value".to_string()),
+                serde_yaml::Value::String(r.normalized_value.clone()),
+            );
+            if let Some(ref range) = r.matched_range {
+                let mut range_map = serde_yaml::Mapping::new();
+                range_map.insert(
+                    serde_yaml::Value::String("start".to_string()),
+                    serde_yaml::Value::Number(serde_yaml::Number::from(range.start)),
+                );
+                range_map.insert(
+                    serde_yaml::Value::String("end".to_string()),
+                    serde_yaml::Value::Number(serde_yaml::Number::from(range.end)),
+                );
+                map.insert(
+                    serde_yaml::Value::String("matched_range".to_string()),
+                    serde_yaml::Value::Mapping(range_map),
+                );
+            }
+            if let Some(ref explanation) = r.explanation {
+                map.insert(
+                    serde_yaml::Value::String("explanation".to_string()),
+                    explanation_value(explanation),
+                );
+            }
+            serde_yaml::Value::Mapping(map)
+        })
+        .collect();
+
+    case.expected = Some(serde_yaml::Value::Sequence(suggestions));
+    true
+}
+
+/// Every explanation field, in the documented order
+fn explanation_value(explanation: &SuggestionExplanation) -> serde_yaml::Value {
+    let mut map = serde_yaml::Mapping::new();
+    let mut insert = |key: &str, value: serde_yaml::Value| {
+        map.insert(serde_yaml::Value::String(key.to_string()), value);
+    };
+    insert("raw_score", serde_yaml::Value::from(explanation.raw_score));
+    insert(
+        "metric",
+        serde_yaml::Value::from(explanation.metric.as_str()),
+    );
+    insert(
+        "prefix_bonus_applied",
+        serde_yaml::Value::from(explanation.prefix_bonus_applied),
+    );
+    insert(
+        "bonus_amount",
+        serde_yaml::Value::from(explanation.bonus_amount),
+    );
+    insert(
+        "normalized_input",
+        serde_yaml::Value::from(explanation.normalized_input.as_str()),
+    );
+    insert(
+        "normalized_candidate",
+        serde_yaml::Value::from(explanation.normalized_candidate.as_str()),
+    );
+    insert(
+        "prefilter",
+        serde_yaml::Value::from(explanation.prefilter.as_str()),
+    );
+    serde_yaml::Value::Mapping(map)
+}
+
+fn generate_normalized_key(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    case.expected = Some(serde_yaml::Value::Mapping(normalized_key_fields(
+        &case.inputs,
+    )));
+    true
+}
+
+fn generate_script_detection(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    case.expected = Some(serde_yaml::Value::Mapping(script_detection(&case.inputs)));
+    true
+}
+
+fn generate_edit_operations(category: &str, case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    case.expected = Some(serde_yaml::Value::Sequence(edit_operations(category, case)));
+    true
+}
+
+fn generate_word_diff(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // An unknown tokenizer or metric is a negative case; leave it for expect_error
+    let Ok(ops) = word_diffs(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Sequence(ops));
+    true
+}
+
+fn generate_match_profile(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // An unknown preset is a negative case; leave it for expect_error
+    let Ok(classes) = match_profile_classes(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Sequence(classes));
+    true
+}
+
+fn generate_window_scores(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // A zero window or step or an unknown metric is a negative case; leave it for expect_error
+    let Ok(scores) = case_window_scores(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Sequence(
+        scores.into_iter().map(serde_yaml::Value::from).collect(),
+    ));
+    true
+}
+
+fn generate_fuzzy_find(case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    // An invalid max_edits is a negative case; leave it for expect_error
+    let Ok(found) = fuzzy_matches(&case.inputs) else {
+        return false;
+    };
+    case.expected = Some(serde_yaml::Value::Sequence(found));
+    true
+}
+
+fn generate_extract(category: &str, case: &mut TestCase, overwrite: bool) -> bool {
+    if !overwrite && case.expected.is_some() {
+        return false;
+    }
+
+    let results = match run_extraction(category, &ExtractionQuery::from_case(case)) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            return false;
+        }
+    };
+
+    let results: Vec<serde_yaml::Value> = results
+        .iter()
+        .map(|r| {
+            let mut map = serde_yaml::Mapping::new();
+            map.insert(
+                serde_yaml::Value::String("choice".to_string()),
+                serde_yaml::Value::String(r.choice.clone()),
+            );
+            map.insert(
+                serde_yaml::Value::String("score".to_string()),
+                serde_yaml::to_value(r.score).expect("Failed to serialize score"),
+            );
+            map.insert(
+                serde_yaml::Value::String("index".to_string()),
+                serde_yaml::Value::Number(r.index.into()),
+            );
+            if let Some(ref explanation) = r.explanation {
+                map.insert(
+                    serde_yaml::Value::String("explanation".to_string()),
+                    explanation_value(explanation),
+                );
+            }
+            serde_yaml::Value::Mapping(map)
+        })
+        .collect();
+
+    case.expected = Some(serde_yaml::Value::Sequence(results));
+    true
+}
+
+// ============================================================================
+// FORMAT VERSIONS
+// ============================================================================
+// Format 1.x keeps the dataset's CalVer (e.g. 2025.10.3) in `version`; every
+// fixture written before format 2.0 is 1.x. Format 2.0 sets `version: "2.0"`,
+// moves the CalVer to `dataset_version`, and makes file-level settings explicit.
+
+/// Format versions this build understands, for error messages
+const SUPPORTED_FORMAT_VERSIONS: &str = "1.x (CalVer), 2.0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatVersion {
+    V1,
+    V2,
+}
+
+impl FormatVersion {
+    fn detect(version: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = version.split('.').collect();
+        let numeric: Option<Vec<u32>> = parts.iter().map(|p| p.parse().ok()).collect();
+        let Some(numeric) = numeric else {
+            return Err(format!(
+                "unrecognized fixture version {:?} (supported: {})",
+                version, SUPPORTED_FORMAT_VERSIONS
+            ));
+        };
+        match numeric.as_slice() {
+            // CalVer dataset versions predate explicit format versions
+            [year, _, _] if *year >= 2000 => Ok(FormatVersion::V1),
+            [1, ..] => Ok(FormatVersion::V1),
+            [2, ..] => Ok(FormatVersion::V2),
+            [major, ..] if *major > 2 => Err(format!(
+                "this tool is too old: fixture format {} needs a newer similarity-validator \
+                 ({} reads {})",
+                version, VERSION, SUPPORTED_FORMAT_VERSIONS
+            )),
+            _ => Err(format!(
+                "unrecognized fixture version {:?} (supported: {})",
+                version, SUPPORTED_FORMAT_VERSIONS
+            )),
+        }
+    }
+}
+
+/// Levenshtein operation costs; only unit weights are validated today
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct EditWeights {
+    insertion: usize,
+    deletion: usize,
+    substitution: usize,
+}
+
+impl Default for EditWeights {
+    fn default() -> Self {
+        EditWeights {
+            insertion: 1,
+            deletion: 1,
+            substitution: 1,
+        }
+    }
+}
+
+/// Format 2.0 on disk: file-level settings are required rather than implied
+#[derive(Debug, Deserialize)]
+struct FixtureV2 {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    dataset_version: Option<String>,
+    generator: Option<GeneratorMetadata>,
+    notes: Option<String>,
+    tolerance: f64,
+    range_unit: RangeUnit,
+    weights: EditWeights,
+    #[serde(default)]
+    checksums: bool,
+    test_cases: Vec<CategoryGroup>,
+}
+
+impl From<FixtureV2> for Fixture {
+    fn from(v2: FixtureV2) -> Self {
+        Fixture {
+            schema: v2.schema,
+            version: v2.version,
+            generator: v2.generator,
+            notes: v2.notes,
+            tolerance: Some(v2.tolerance),
+            dataset_version: v2.dataset_version,
+            range_unit: Some(v2.range_unit),
+            weights: Some(v2.weights),
+            checksums: v2.checksums,
+            test_cases: v2.test_cases,
+        }
+    }
+}
+
+/// Parse a fixture of any supported format version into the in-memory model
+fn parse_fixture(
+    format: FixtureFormat,
+    contents: &str,
+) -> Result<Fixture, Box<dyn std::error::Error>> {
+    let raw: serde_json::Value = format.parse(contents)?;
+    let version = raw
+        .get("version")
+        .and_then(|v| v.as_str())
+        .ok_or("missing top-level `version` string")?;
+    let fixture: Fixture = match FormatVersion::detect(version)? {
+        FormatVersion::V1 => serde_json::from_value(raw)?,
+        FormatVersion::V2 => serde_json::from_value::<FixtureV2>(raw)?.into(),
+    };
+    if fixture
+        .weights
+        .as_ref()
+        .is_some_and(|w| *w != EditWeights::default())
+    {
+        return Err("custom `weights` are not supported by this validator yet".into());
+    }
+    if fixture
+        .range_unit
+        .is_some_and(|unit| unit != RangeUnit::Char)
+    {
+        return Err(
+            "fixture-level `range_unit` must be \"char\"; set `range_unit` on substring cases instead"
+                .into(),
+        );
+    }
+    Ok(fixture)
+}
+
+/// Format 1.x → 2.0: keep every case, move the CalVer, and spell out defaults
+fn migrate_v1_to_v2(mut fixture: Fixture) -> Fixture {
+    fixture.dataset_version = Some(std::mem::replace(&mut fixture.version, "2.0".to_string()));
+    fixture.tolerance = Some(fixture.tolerance.unwrap_or(DEFAULT_TOLERANCE));
+    fixture.range_unit = Some(RangeUnit::default());
+    fixture.weights = Some(EditWeights::default());
+    fixture
+}
+
+fn migrate_fixture(fixture: Fixture, input_path: &Path, output_path: &Path, dry_run: bool) {
+    let format = FormatVersion::detect(&fixture.version).expect("version checked on load");
+    if format == FormatVersion::V2 {
+        println!(
+            "{} is already format 2.0; nothing to migrate",
+            input_path.display()
+        );
+        return;
+    }
+
+    let cases: usize = fixture.test_cases.iter().map(|g| g.cases.len()).sum();
+    let migrated = migrate_v1_to_v2(fixture);
+    let yaml = serde_yaml::to_string(&migrated).expect("Failed to serialize YAML");
+    println!(
+        "Migrated {} cases from format 1.x (dataset version {}) to 2.0",
+        cases,
+        migrated.dataset_version.as_deref().unwrap_or_default()
+    );
+
+    if dry_run {
+        println!("{}", "\n[DRY RUN - No files written]".yellow());
+        println!("{}", yaml);
+    } else {
+        fs::write(output_path, yaml).expect("Failed to write output file");
+        println!("{} {}", "✅ Written:".green(), output_path.display());
+    }
+}
+
+// ============================================================================
+// CORPUS GENERATION MODE
+// ============================================================================
+// Random pairs are drawn from the core's self-contained SplitMix64 stream so a
+// seed reproduces the same corpus regardless of dependency versions; mutated
+// inputs come from `generate_variants`, so they sit at an exact edit distance.
+
+/// Pairwise categories `generate-corpus` can fill (all take input_a/input_b)
+const CORPUS_CATEGORIES: &[&str] = &[
+    "levenshtein",
+    "damerau_osa",
+    "damerau_unrestricted",
+    "jaro_winkler",
+    "indel",
+    "lcs_seq",
+    "ratio",
+];
+
+/// String generators, cycled through by case index
+const STRING_GENERATORS: &[&str] = &[
+    "ascii_words",
+    "mixed_script",
+    "combining_marks",
+    "emoji_zwj",
+    "very_long",
+    "near_empty",
+];
+
+/// Corpus draws on top of the core SplitMix64 stream
+trait CorpusRng {
+    fn chance(&mut self, percent: usize) -> bool;
+    fn char_in(&mut self, from: u32, to: u32) -> char;
+}
+
+impl CorpusRng for SplitMix64 {
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(0..100) < percent
+    }
+
+    fn char_in(&mut self, from: u32, to: u32) -> char {
+        char::from_u32(from + self.below(0..(to - from + 1) as usize) as u32).unwrap_or('?')
+    }
+}
+
+fn random_string(generator: &str, rng: &mut SplitMix64) -> String {
+    match generator {
+        "ascii_words" => (0..rng.below(1..4))
+            .map(|_| {
+                (0..rng.below(2..9))
+                    .map(|_| rng.char_in('a' as u32, 'z' as u32))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        "mixed_script" => {
+            // Latin, Greek, Cyrillic, Arabic, CJK, Hangul
+            let blocks = [
+                (0x61, 0x7A),
+                (0x3B1, 0x3C9),
+                (0x430, 0x44F),
+                (0x627, 0x64A),
+                (0x4E00, 0x4FFF),
+                (0xAC00, 0xAD00),
+            ];
+            (0..rng.below(3..13))
+                .map(|_| {
+                    let (from, to) = blocks[rng.below(0..blocks.len())];
+                    rng.char_in(from, to)
+                })
+                .collect()
+        }
+        "combining_marks" => {
+            let mut s = String::new();
+            for _ in 0..rng.below(2..8) {
+                s.push(rng.char_in('a' as u32, 'z' as u32));
+                for _ in 0..rng.below(0..4) {
+                    s.push(rng.char_in(0x300, 0x36F));
+                }
+            }
+            s
+        }
+        "emoji_zwj" => {
+            let mut s = String::new();
+            for _ in 0..rng.below(1..5) {
+                match rng.below(0..4) {
+                    0 => s.push(rng.char_in(0x1F600, 0x1F64F)),
+                    1 => {
+                        // Family: man ZWJ woman ZWJ girl
+                        s.push_str("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+                    }
+                    2 => {
+                        s.push(rng.char_in(0x1F44A, 0x1F450));
+                        s.push(rng.char_in(0x1F3FB, 0x1F3FF));
+                    }
+                    _ => {
+                        // Regional indicator pair (flag)
+                        s.push(rng.char_in(0x1F1E6, 0x1F1FF));
+                        s.push(rng.char_in(0x1F1E6, 0x1F1FF));
+                    }
+                }
+                if rng.chance(30) {
+                    s.push(' ');
+                }
+            }
+            s
+        }
+        "very_long" => (0..rng.below(256..1025))
+            .map(|_| {
+                if rng.chance(15) {
+                    ' '
+                } else {
+                    rng.char_in('a' as u32, 'z' as u32)
+                }
+            })
+            .collect(),
+        _ => match rng.below(0..4) {
+            0 => String::new(),
+            1 => " ".to_string(),
+            _ => rng.char_in('a' as u32, 'c' as u32).to_string(),
+        },
+    }
+}
+
+/// A variant one to three Levenshtein edits away, reusing characters already in the string
+fn mutate(input: &str, rng: &mut SplitMix64) -> String {
+    let options = VariantOptions {
+        seed: rng.next_u64(),
+        extra_chars: if input.is_empty() { "x" } else { "" }.to_string(),
+        ..VariantOptions::default()
+    };
+    generate_variants(input, rng.below(1..4), 1, &options)
+        .ok()
+        .and_then(|variants| variants.into_iter().next())
+        .unwrap_or_else(|| input.to_string())
+}
+
+fn generate_corpus(seed: u64, count: usize, output: &Path, categories: &[String]) {
+    let categories: Vec<&str> = if categories.is_empty() {
+        CORPUS_CATEGORIES.to_vec()
+    } else {
+        categories.iter().map(String::as_str).collect()
+    };
+    if let Some(unknown) = categories.iter().find(|c| !CORPUS_CATEGORIES.contains(c)) {
+        eprintln!(
+            "{}: category {:?} is not supported by generate-corpus (supported: {})",
+            "Error".red(),
+            unknown,
+            CORPUS_CATEGORIES.join(", ")
+        );
+        process::exit(1);
+    }
+
+    let mut rng = SplitMix64(seed);
+    let mut groups: Vec<CategoryGroup> = categories
+        .iter()
+        .map(|category| CategoryGroup {
+            category: category.to_string(),
+            tags: None,
+            cases: Vec::new(),
+        })
+        .collect();
+
+    for index in 0..count {
+        let generator = STRING_GENERATORS[index % STRING_GENERATORS.len()];
+        let group = &mut groups[rng.below(0..categories.len())];
+        let input_a = random_string(generator, &mut rng);
+        let input_b = if rng.chance(70) {
+            mutate(&input_a, &mut rng)
+        } else {
+            random_string(generator, &mut rng)
+        };
+
+        let mut case = TestCase {
+            inputs: serde_yaml::Mapping::from_iter([
+                ("input_a".into(), serde_yaml::Value::String(input_a)),
+                ("input_b".into(), serde_yaml::Value::String(input_b)),
+            ]),
+            expected_distance: None,
+            expected_score: None,
+            expected_range: None,
+            expected_ranges: None,
+            expected_score_range: None,
+            expected: None,
+            expect_error: None,
+            tolerance: None,
+            description: format!("{} seed={} index={}", generator, seed, index),
+            tags: Some(vec!["generated".to_string(), generator.to_string()]),
+            skip: false,
+            skip_reason: None,
+            only: false,
+            checksum: None,
+        };
+        generate_test_case(&group.category, &mut case, true);
+        group.cases.push(case);
+    }
+    groups.retain(|group| !group.cases.is_empty());
+
+    let fixture = Fixture {
+        schema: FIXTURE_SCHEMA_URL.to_string(),
+        version: Utc::now().format("%Y.%-m.%-d").to_string(),
+        dataset_version: None,
+        generator: Some(GeneratorMetadata {
+            tool: "similarity-validator".to_string(),
+            tool_version: VERSION.to_string(),
+            source_library: "rapidfuzz-rs".to_string(),
+            source_version: RAPIDFUZZ_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            command: Some(format!(
+                "similarity-validator generate-corpus --seed {} --count {} --output {} --categories {}",
+                seed,
+                count,
+                output.display(),
+                categories.join(",")
+            )),
+            sources: None,
+            seed: Some(seed),
+            precision: None,
+            arithmetic: Some(string_metrics_core::ARITHMETIC_MODE.to_string()),
+        }),
+        notes: Some(format!(
+            "GENERATED FILE - random corpus, reproducible from seed {}\n\
+             Each description names the string generator, seed, and case index.",
+            seed
+        )),
+        tolerance: None,
+        range_unit: None,
+        weights: None,
+        checksums: false,
+        test_cases: groups,
+    };
+
+    let format = FixtureFormat::from_path(output).unwrap_or_else(|e| {
+        eprintln!("{}: {}", "Error".red(), e);
+        process::exit(1);
+    });
+    let serialized = format
+        .serialize(&fixture)
+        .expect("Failed to serialize corpus");
+    fs::write(output, serialized).unwrap_or_else(|e| {
+        eprintln!("{} {}: {}", "Error".red(), output.display(), e);
+        process::exit(1);
+    });
+    println!(
+        "{} {} cases (seed {}) to {}",
+        "✅ Generated".green(),
+        count,
+        seed,
+        output.display()
+    );
+}
+
+// ============================================================================
+// IMPORT MODE
+// ============================================================================
+// Spreadsheet exports become fixtures, one pairwise case per row. The reader
+// follows RFC 4180: quoted fields may contain delimiters, newlines and `""`.
+
+struct ImportOptions {
+    category: String,
+    column_a: usize,
+    column_b: usize,
+    description_column: Option<usize>,
+    delimiter: Option<char>,
+    header: bool,
+    append: bool,
+}
+
+fn import_pairs(input: &Path, output: &Path, options: &ImportOptions) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}: {}", "Error".red(), message);
+        process::exit(1);
+    };
+
+    if !CORPUS_CATEGORIES.contains(&options.category.as_str()) {
+        fail(format!(
+            "category {:?} is not supported by import (supported: {})",
+            options.category,
+            CORPUS_CATEGORIES.join(", ")
+        ));
+    }
+    let format = FixtureFormat::from_path(output).unwrap_or_else(|e| fail(e));
+    if output.exists() && !options.append {
+        fail(format!(
+            "{} already exists; pass --append to add cases to it",
+            output.display()
+        ));
+    }
+
+    let text =
+        fs::read_to_string(input).unwrap_or_else(|e| fail(format!("{}: {}", input.display(), e)));
+    let delimiter =
+        options
+            .delimiter
+            .unwrap_or_else(|| match input.extension().and_then(|e| e.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("tsv") => '\t',
+                _ => ',',
+            });
+    let rows = parse_delimited(&text, delimiter)
+        .unwrap_or_else(|e| fail(format!("{}: {}", input.display(), e)));
+
+    let file_name = input
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut cases = Vec::new();
+    for (index, row) in rows.iter().enumerate().skip(usize::from(options.header)) {
+        let column = |n: usize, flag: &str| -> &str {
+            row.get(n).map(String::as_str).unwrap_or_else(|| {
+                fail(format!(
+                    "{} row {} has {} fields; {} {} needs at least {}",
+                    input.display(),
+                    index + 1,
+                    row.len(),
+                    flag,
+                    n,
+                    n + 1
+                ))
+            })
+        };
+        let input_a = column(options.column_a, "--column-a");
+        let input_b = column(options.column_b, "--column-b");
+        let description = match options.description_column {
+            Some(n) if !column(n, "--description-column").is_empty() => {
+                column(n, "--description-column").to_string()
+            }
+            _ => format!("{} row {}", file_name, index + 1),
+        };
+
+        let mut case = TestCase {
+            inputs: serde_yaml::Mapping::from_iter([
+                ("input_a".into(), input_a.into()),
+                ("input_b".into(), input_b.into()),
+            ]),
+            expected_distance: None,
+            expected_score: None,
+            expected_range: None,
+            expected_ranges: None,
+            expected_score_range: None,
+            expected: None,
+            expect_error: None,
+            tolerance: None,
+            description,
+            tags: None,
+            skip: false,
+            skip_reason: None,
+            only: false,
+            checksum: None,
+        };
+        generate_test_case(&options.category, &mut case, true);
+        cases.push(case);
+    }
+
+    let mut fixture = if options.append && output.exists() {
+        load_fixture(output).unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)))
+    } else {
+        Fixture {
+            schema: FIXTURE_SCHEMA_URL.to_string(),
+            version: Utc::now().format("%Y.%-m.%-d").to_string(),
+            dataset_version: None,
+            generator: None,
+            notes: Some(
+                "GENERATED FILE - imported string pairs with expected values from rapidfuzz-rs"
+                    .to_string(),
+            ),
+            tolerance: None,
+            range_unit: None,
+            weights: None,
+            checksums: false,
+            test_cases: Vec::new(),
+        }
+    };
+
+    let group = match fixture
+        .test_cases
+        .iter()
+        .position(|g| g.category == options.category)
+    {
+        Some(index) => &mut fixture.test_cases[index],
+        None => {
+            fixture.test_cases.push(CategoryGroup {
+                category: options.category.clone(),
+                tags: None,
+                cases: Vec::new(),
+            });
+            fixture.test_cases.last_mut().expect("group just pushed")
+        }
+    };
+    let mut seen: HashSet<String> = group.cases.iter().map(|c| inputs_key(&c.inputs)).collect();
+    let mut imported = 0;
+    let mut duplicates = 0;
+    for case in cases {
+        if seen.insert(inputs_key(&case.inputs)) {
+            group.cases.push(case);
+            imported += 1;
+        } else {
+            duplicates += 1;
+        }
+    }
+
+    let mut sources = fixture
+        .generator
+        .take()
+        .and_then(|g| g.sources)
+        .unwrap_or_default();
+    let source = input.display().to_string();
+    if !sources.contains(&source) {
+        sources.push(source);
+    }
+    fixture.generator = Some(GeneratorMetadata {
+        tool: "similarity-validator".to_string(),
+        tool_version: VERSION.to_string(),
+        source_library: "rapidfuzz-rs".to_string(),
+        source_version: RAPIDFUZZ_VERSION.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        command: Some(import_command(input, output, options)),
+        sources: Some(sources),
+        seed: None,
+        precision: None,
+        arithmetic: Some(string_metrics_core::ARITHMETIC_MODE.to_string()),
+    });
+
+    let serialized = format
+        .serialize(&fixture)
+        .unwrap_or_else(|e| fail(format!("cannot serialize: {}", e)));
+    fs::write(output, serialized).unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)));
+    println!(
+        "Imported: {} | Duplicates skipped: {}",
+        imported.to_string().green(),
+        duplicates.to_string().yellow()
+    );
+    println!("{} {}", "✅ Written:".green(), output.display());
+}
+
+/// The `import` invocation recorded in generator metadata
+fn import_command(input: &Path, output: &Path, options: &ImportOptions) -> String {
+    let mut command = format!(
+        "similarity-validator import --input {} --category {} --output {} --column-a {} --column-b {}",
+        input.display(),
+        options.category,
+        output.display(),
+        options.column_a,
+        options.column_b
+    );
+    if let Some(n) = options.description_column {
+        command.push_str(&format!(" --description-column {}", n));
+    }
+    if let Some(delimiter) = options.delimiter {
+        command.push_str(&format!(" --delimiter {:?}", delimiter.to_string()));
+    }
+    if options.header {
+        command.push_str(" --header");
+    }
+    if options.append {
+        command.push_str(" --append");
+    }
+    command
+}
+
+/// Split CSV/TSV text into rows of fields (RFC 4180 quoting; blank lines are skipped)
+fn parse_delimited(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    // Line a still-open quoted field started on, for the error message
+    let mut quoted_from: Option<usize> = None;
+    let mut after_quote = false;
+
+    while let Some(c) = chars.next() {
+        if quoted_from.is_some() {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => {
+                    quoted_from = None;
+                    after_quote = true;
+                }
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() && !after_quote => quoted_from = Some(line),
+            c if c == delimiter => {
+                row.push(std::mem::take(&mut field));
+                after_quote = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() || after_quote {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+                after_quote = false;
+                line += 1;
+            }
+            _ if after_quote => {
+                return Err(format!(
+                    "line {}: unexpected {:?} after a closing quote",
+                    line, c
+                ));
+            }
+            _ => field.push(c),
+        }
+    }
+    if let Some(start) = quoted_from {
+        return Err(format!("line {}: unterminated quoted field", start));
+    }
+    if !row.is_empty() || !field.is_empty() || after_quote {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+// ============================================================================
+// SAMPLE MODE
+// ============================================================================
+// A sample is a pure subset: cases are copied verbatim (expected values, tags,
+// per-case tolerance) and nothing is regenerated, so it passes exactly when
+// the selected originals do. Each category draws from its own SplitMix64
+// stream, so adding a category elsewhere doesn't reshuffle the others.
+
+fn sample_fixtures(pattern: &str, output: &Path, per_category: usize, seed: u64) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}: {}", "Error".red(), message);
+        process::exit(1);
+    };
+
+    let (paths, glob_errors) = expand_pattern(pattern);
+    if let Some((path, error)) = glob_errors.first() {
+        fail(format!("{}: {}", path, error));
+    }
+    let format = FixtureFormat::from_path(output).unwrap_or_else(|e| fail(e));
+
+    let mut schema = None;
+    let mut versions = Vec::new();
+    // category -> (source file, case), in corpus order
+    let mut pools: Vec<(String, Vec<(String, TestCase)>)> = Vec::new();
+    for path in paths.iter().filter(|path| path.as_path() != output) {
+        let fixture =
+            load_fixture(path).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)));
+        schema.get_or_insert(fixture.schema);
+        versions.push(fixture.version);
+        for group in fixture.test_cases {
+            let index = match pools.iter().position(|(c, _)| *c == group.category) {
+                Some(index) => index,
+                None => {
+                    pools.push((group.category.clone(), Vec::new()));
+                    pools.len() - 1
+                }
+            };
+            for mut case in group.cases.into_iter().filter(|case| !case.skip) {
+                // The sample has no file-level tolerance; keep the source's on the case
+                if case.tolerance.is_none() {
+                    case.tolerance = fixture.tolerance;
+                }
+                inherit_group_tags(group.tags.as_deref(), &mut case);
+                pools[index].1.push((path.display().to_string(), case));
+            }
+        }
+    }
+    let Some(schema) = schema else {
+        fail(format!("no fixture files match {}", pattern));
+    };
+
+    let mut sources: Vec<String> = Vec::new();
+    let mut total = 0;
+    let mut groups = Vec::new();
+    for (category, mut pool) in pools {
+        total += pool.len();
+        let mut rng = SplitMix64(seed ^ fnv1a(category.as_bytes()));
+        // Partial Fisher-Yates, then restore corpus order among the chosen
+        let keep = per_category.min(pool.len());
+        let mut order: Vec<usize> = (0..pool.len()).collect();
+        for i in 0..keep {
+            let j = rng.below(i..pool.len());
+            order.swap(i, j);
+        }
+        let mut chosen: Vec<usize> = order[..keep].to_vec();
+        chosen.sort_unstable();
+
+        let mut cases = Vec::new();
+        for index in chosen.into_iter().rev() {
+            let (source, case) = pool.swap_remove(index);
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+            cases.push(case);
+        }
+        cases.reverse();
+        if !cases.is_empty() {
+            groups.push(CategoryGroup {
+                category,
+                tags: None,
+                cases,
+            });
+        }
+    }
+    sources.sort();
+
+    let sampled: usize = groups.iter().map(|g| g.cases.len()).sum();
+    let fixture = Fixture {
+        schema,
+        version: latest_version(versions.iter().map(String::as_str)),
+        dataset_version: None,
+        generator: Some(GeneratorMetadata {
+            tool: "similarity-validator".to_string(),
+            tool_version: VERSION.to_string(),
+            source_library: "rapidfuzz-rs".to_string(),
+            source_version: RAPIDFUZZ_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            command: Some(format!(
+                "similarity-validator sample '{}' --output {} --per-category {} --seed {}",
+                pattern,
+                output.display(),
+                per_category,
+                seed
+            )),
+            sources: Some(sources),
+            seed: Some(seed),
+            precision: None,
+            arithmetic: None,
+        }),
+        notes: Some(format!(
+            "SAMPLED FILE - up to {} cases per category (seed {}), copied verbatim from the sources",
+            per_category, seed
+        )),
+        tolerance: None,
+        range_unit: None,
+        weights: None,
+        checksums: false,
+        test_cases: groups,
+    };
+
+    let serialized = format
+        .serialize(&fixture)
+        .unwrap_or_else(|e| fail(format!("cannot serialize: {}", e)));
+    fs::write(output, serialized).unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)));
+    println!(
+        "Sampled: {} of {} cases | Categories: {} | Seed: {}",
+        sampled.to_string().green(),
+        total,
+        fixture.test_cases.len(),
+        seed
+    );
+    println!("{} {}", "✅ Written:".green(), output.display());
+}
+
+/// FNV-1a, for deriving stable per-category PRNG streams
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+// ============================================================================
+// TEMPLATE MODE
+// ============================================================================
+// A template is a well-formed fixture without expected values: fill in the
+// inputs and descriptions, then `generate` computes the expectations, after
+// which the file also satisfies the schema.
+
+fn template_fixture(category: &str, output: &Path, cases: usize) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}: {}", "Error".red(), message);
+        process::exit(1);
+    };
+
+    let inputs = category_inputs(category).unwrap_or_else(|| {
+        let known: Vec<&str> = CATEGORY_INPUTS.iter().map(|c| c.category).collect();
+        fail(format!(
+            "unknown category {} (known: {})",
+            category,
+            known.join(", ")
+        ))
+    });
+    if cases == 0 {
+        fail("--cases must be at least 1".to_string());
+    }
+    let format = FixtureFormat::from_path(output).unwrap_or_else(|e| fail(e));
+    if output.exists() {
+        fail(format!("{} already exists", output.display()));
+    }
+
+    let case = TestCase {
+        inputs: inputs
+            .required
+            .iter()
+            .map(|field| ((*field).into(), input_placeholder(category, field)))
+            .collect(),
+        expected_distance: None,
+        expected_score: None,
+        expected_range: None,
+        expected_ranges: None,
+        expected_score_range: None,
+        expected: None,
+        expect_error: None,
+        tolerance: None,
+        description: "TODO".to_string(),
+        tags: None,
+        skip: false,
+        skip_reason: None,
+        only: false,
+        checksum: None,
+    };
+    let command = format!(
+        "similarity-validator template --category {} --output {} --cases {}",
+        category,
+        output.display(),
+        cases
+    );
+    let fixture = Fixture {
+        schema: FIXTURE_SCHEMA_URL.to_string(),
+        version: Utc::now().format("%Y.%-m.%-d").to_string(),
+        dataset_version: None,
+        generator: Some(GeneratorMetadata {
+            tool: "similarity-validator".to_string(),
+            tool_version: VERSION.to_string(),
+            source_library: "rapidfuzz-rs".to_string(),
+            source_version: RAPIDFUZZ_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            command: Some(command),
+            sources: None,
+            seed: None,
+            precision: None,
+            arithmetic: None,
+        }),
+        notes: Some(format!(
+            "TODO: describe what these {} cases cover.\n\nTo fill in expected values:\nsimilarity-validator generate --input {}",
+            category,
+            output.display()
+        )),
+        tolerance: None,
+        range_unit: None,
+        weights: None,
+        checksums: false,
+        test_cases: vec![CategoryGroup {
+            category: category.to_string(),
+            tags: None,
+            cases: vec![case; cases],
+        }],
+    };
+
+    let serialized = format
+        .serialize(&fixture)
+        .unwrap_or_else(|e| fail(format!("cannot serialize: {}", e)));
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).unwrap_or_else(|e| fail(format!("{}: {}", parent.display(), e)));
+    }
+    fs::write(output, serialized).unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)));
+    println!(
+        "{} {} ({} {} cases)",
+        "✅ Written:".green(),
+        output.display(),
+        cases,
+        category
+    );
+    println!(
+        "Fill in the inputs and descriptions, then run: similarity-validator generate --input {}",
+        output.display()
+    );
+}
+
+/// Starting value for a scaffolded input field: the library default where there is one
+fn input_placeholder(category: &str, field: &str) -> serde_yaml::Value {
+    use serde_yaml::Value;
+    match field {
+        "candidates" | "choices" | "strings" | "metrics" => Value::Sequence(Vec::new()),
+        "options" if category.starts_with("extract") => {
+            let defaults = ExtractOptions::default();
+            Value::Mapping(serde_yaml::Mapping::from_iter([
+                ("scorer".into(), defaults.scorer.into()),
+                ("processor".into(), defaults.processor.into()),
+                ("score_cutoff".into(), defaults.score_cutoff.into()),
+            ]))
+        }
+        "options" => {
+            let defaults = SuggestOptions::default();
+            Value::Mapping(serde_yaml::Mapping::from_iter([
+                ("min_score".into(), defaults.min_score.into()),
+                (
+                    "max_suggestions".into(),
+                    (defaults.max_suggestions as u64).into(),
+                ),
+                ("metric".into(), defaults.metric.into()),
+                ("normalize_preset".into(), defaults.normalize_preset.into()),
+            ]))
+        }
+        "prefix_scale" => 0.1.into(),
+        "max_prefix" => 4u64.into(),
+        "metric" => "levenshtein".into(),
+        "preset" => "default".into(),
+        "locale" => "tr".into(),
+        "property" => "symmetry".into(),
+        _ => "".into(),
+    }
+}
+
+// ============================================================================
+// LINT MODE
+// ============================================================================
+
+/// Token ratios the TypeScript wrapper scores itself; the core registry doesn't know them
+const TS_ONLY_METRICS: &[&str] = &["partial_ratio", "token_sort_ratio", "token_set_ratio"];
+
+/// Every name and alias the unified registry resolves, keeping only metrics with a distance
+/// form when `distances` is set. A registry that failed to build accepts nothing.
+fn registered_spellings(distances: bool) -> Vec<&'static str> {
+    string_metrics_core::registered_metrics()
+        .unwrap_or_default()
+        .iter()
+        .filter(|def| def.unified && (!distances || def.distance_fn().is_some()))
+        .flat_map(|def| def.names())
+        .collect()
+}
+
+/// Metric names accepted by each metric-selectable category (fixture spelling)
+fn known_metrics(category: &str) -> Vec<&'static str> {
+    match category {
+        "unified_distance" => registered_spellings(true),
+        "unified_score" => [registered_spellings(false), TS_ONLY_METRICS.to_vec()].concat(),
+        "suggestions" => [
+            registered_spellings(false),
+            vec!["substring"],
+            TS_ONLY_METRICS.to_vec(),
+        ]
+        .concat(),
+        "extract_one" | "extract" => registered_spellings(false),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize)]
+struct LintIssue {
+    severity: Severity,
+    /// Where the problem is, e.g. `basic.yaml [levenshtein] #3 "Classic example"`
+    location: String,
+    message: String,
+}
+
+/// A case tagged with where it came from, for cross-file checks
+struct LintCase<'a> {
+    location: String,
+    file: &'a str,
+    index: usize,
+    category: &'a str,
+    /// Effective tags (group and case)
+    tags: Vec<String>,
+    test: &'a TestCase,
+}
+
+/// Cases sharing a category and canonical inputs
+#[derive(Debug, Serialize)]
+struct DuplicateGroup {
+    category: String,
+    inputs: serde_yaml::Value,
+    /// Expectation fields on which members disagree; non-empty is a lint error
+    conflicts: Vec<String>,
+    cases: Vec<DuplicateMember>,
+}
+
+#[derive(Debug, Serialize)]
+struct DuplicateMember {
+    file: String,
+    index: usize,
+    description: String,
+    expected: BTreeMap<String, serde_yaml::Value>,
+}
+
+#[derive(Serialize)]
+struct LintReport<'a> {
+    files: usize,
+    cases: usize,
+    issues: &'a [LintIssue],
+    duplicates: &'a [DuplicateGroup],
+}
+
+fn lint_fixtures(pattern: &str, strict: bool, format: OutputFormat, max_input_length: usize) {
+    if format == OutputFormat::Text {
+        println!("{} fixtures matching: {}", "Linting".cyan(), pattern);
+        println!();
+    }
+
+    let mut issues: Vec<LintIssue> = Vec::new();
+    let mut fixtures: Vec<(String, Fixture)> = Vec::new();
+
+    for entry in glob(pattern).expect("Failed to read glob pattern") {
+        match entry {
+            Ok(path) => match load_fixture(&path) {
+                Ok(fixture) => fixtures.push((path.display().to_string(), fixture)),
+                Err(e) => issues.push(LintIssue {
+                    severity: Severity::Error,
+                    location: path.display().to_string(),
+                    message: format!("cannot load fixture: {}", e),
+                }),
+            },
+            Err(e) => issues.push(LintIssue {
+                severity: Severity::Error,
+                location: e.path().display().to_string(),
+                message: e.error().to_string(),
+            }),
+        }
+    }
+    fixtures.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let cases: Vec<LintCase> = fixtures
+        .iter()
+        .flat_map(|(name, fixture)| {
+            fixture.test_cases.iter().flat_map(move |group| {
+                group
+                    .cases
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, test)| LintCase {
+                        location: format!(
+                            "{} [{}] #{} {:?}",
+                            name, group.category, index, test.description
+                        ),
+                        file: name.as_str(),
+                        index,
+                        category: group.category.as_str(),
+                        tags: effective_tags(group.tags.as_deref(), test.tags.as_deref()),
+                        test,
+                    })
+            })
+        })
+        .collect();
+
+    for case in &cases {
+        lint_case(case, max_input_length, &mut issues);
+    }
+    let duplicates = lint_duplicates(&cases, &mut issues);
+    lint_rare_tags(&cases, &mut issues);
+
+    if format == OutputFormat::Json {
+        issues.sort_by_key(|issue| issue.severity);
+        let report = LintReport {
+            files: fixtures.len(),
+            cases: cases.len(),
+            issues: &issues,
+            duplicates: &duplicates,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize lint report")
+        );
+        let failed = issues
+            .iter()
+            .any(|i| i.severity == Severity::Error || strict);
+        process::exit(i32::from(failed));
+    }
+
+    let errors: Vec<&LintIssue> = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .collect();
+    let warnings: Vec<&LintIssue> = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Warning)
+        .collect();
+
+    if !errors.is_empty() {
+        println!("{}", format!("ERRORS ({}):", errors.len()).red().bold());
+        println!();
+        for issue in &errors {
+            println!("  {} {}", "✗".red(), issue.location);
+            println!("    {}", issue.message);
+        }
+        println!();
+    }
+    if !warnings.is_empty() {
+        println!(
+            "{}",
+            format!("WARNINGS ({}):", warnings.len()).yellow().bold()
+        );
+        println!();
+        for issue in &warnings {
+            println!("  {} {}", "!".yellow(), issue.location);
+            println!("    {}", issue.message);
+        }
+        println!();
+    }
+
+    println!("{}", "=".repeat(80));
+    println!("{}", "SUMMARY".bold());
+    println!("{}", "=".repeat(80));
+    println!("Files linted:    {}", fixtures.len());
+    println!("Cases checked:   {}", cases.len());
+    println!("Errors:          {}", errors.len().to_string().red());
+    println!("Warnings:        {}", warnings.len().to_string().yellow());
+    println!();
+
+    if !errors.is_empty() || (strict && !warnings.is_empty()) {
+        process::exit(1);
+    }
+    println!("{}", "No lint errors.".green().bold());
+}
+
+/// Categories whose computation is quadratic in the input lengths
+const QUADRATIC_CATEGORIES: &[&str] = &[
+    "levenshtein",
+    "damerau_osa",
+    "damerau_unrestricted",
+    "damerau_graphemes",
+    "damerau_weighted",
+    "affine_gap",
+    "indel",
+    "lcs_seq",
+    "ratio",
+    "substring",
+    "coverage",
+    "window_scores",
+    "fuzzy_find",
+    "unified_distance",
+    "unified_score",
+    "anchored_similarity",
+    "score_nullable",
+    "byte_metrics",
+];
+
+/// Checks that only need a single case
+fn lint_case(case: &LintCase, max_input_length: usize, issues: &mut Vec<LintIssue>) {
+    let mut report = |severity, message: String| {
+        issues.push(LintIssue {
+            severity,
+            location: case.location.clone(),
+            message,
+        })
+    };
+    let test = case.test;
+
+    match category_inputs(case.category) {
+        None => report(
+            Severity::Error,
+            format!("unknown category: {}", case.category),
+        ),
+        Some(inputs) => {
+            for field in inputs.required {
+                if !test.inputs.contains_key(*field) {
+                    report(Severity::Error, format!("missing input: {}", field));
+                }
+            }
+            for key in test.inputs.keys() {
+                let Some(key) = key.as_str() else { continue };
+                if !key.starts_with("expected_")
+                    && !inputs.required.contains(&key)
+                    && !inputs.optional.contains(&key)
+                {
+                    report(
+                        Severity::Warning,
+                        format!("input {} is not read for category {}", key, case.category),
+                    );
+                }
+            }
+        }
+    }
+    if test.description.trim().is_empty() {
+        report(Severity::Error, "empty description".to_string());
+    }
+    if !has_expectation(test) {
+        report(Severity::Error, "no expected value".to_string());
+    }
+    if QUADRATIC_CATEGORIES.contains(&case.category) {
+        let combined: usize = LENGTH_INPUTS
+            .iter()
+            .filter_map(|key| get_string_input(&test.inputs, key))
+            .map(|value| value.chars().count())
+            .sum();
+        if combined > max_input_length {
+            report(
+                Severity::Warning,
+                format!(
+                    "inputs total {} chars (over {}) under quadratic-time {}; validation may hit --timeout-ms",
+                    combined, max_input_length, case.category
+                ),
+            );
+        }
+    }
+
+    // Negative cases use bad metric names on purpose
+    if test.expect_error.is_none() {
+        let metric = match case.category {
+            "unified_distance" | "unified_score" => get_string_input(&test.inputs, "metric"),
+            "suggestions" => test
+                .inputs
+                .get("options")
+                .and_then(|options| options.get("metric"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            "extract_one" | "extract" => test
+                .inputs
+                .get("options")
+                .and_then(|options| options.get("scorer"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            _ => None,
+        };
+        if let Some(metric) = metric {
+            if !known_metrics(case.category).contains(&metric.as_str()) {
+                report(Severity::Error, format!("unknown metric: {}", metric));
+            }
+        }
+    }
+
+    if let Some(range) = &test.expected_range {
+        if range.end < range.start {
+            report(
+                Severity::Error,
+                format!(
+                    "expected_range end < start ({} < {})",
+                    range.end, range.start
+                ),
+            );
+        }
+    }
+    for (i, range) in test.expected_ranges.iter().flatten().enumerate() {
+        if range.end < range.start {
+            report(
+                Severity::Error,
+                format!(
+                    "expected_ranges[{}] end < start ({} < {})",
+                    i, range.end, range.start
+                ),
+            );
+        }
+    }
+    if let Some(suggestions) = test.expected.as_ref().and_then(|v| v.as_sequence()) {
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            let Some(range) = suggestion.get("matched_range") else {
+                continue;
+            };
+            let start = range.get("start").and_then(|v| v.as_u64()).unwrap_or(0);
+            let end = range.get("end").and_then(|v| v.as_u64()).unwrap_or(0);
+            if end < start {
+                report(
+                    Severity::Error,
+                    format!(
+                        "suggestion[{}].matched_range end < start ({} < {})",
+                        i, end, start
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Whether a case asserts anything (typed fields or category-specific `expected_*` inputs)
+fn has_expectation(test: &TestCase) -> bool {
+    test.expected_distance.is_some()
+        || test.expected_score.is_some()
+        || test.expected_score_range.is_some()
+        || test.expected.is_some()
+        || test.expect_error.is_some()
+        || test.inputs.contains_key("property")
+        || test.inputs.keys().any(is_expectation_key)
+}
+
+/// Duplicate (category, inputs) groups and duplicate descriptions, within and across files.
+/// A group whose members disagree on an expected value is an error.
+fn lint_duplicates(cases: &[LintCase], issues: &mut Vec<LintIssue>) -> Vec<DuplicateGroup> {
+    let mut by_inputs: BTreeMap<(&str, String), Vec<&LintCase>> = BTreeMap::new();
+    let mut by_description: HashMap<(&str, &str), &LintCase> = HashMap::new();
+
+    for case in cases {
+        by_inputs
+            .entry((case.category, inputs_key(&case.test.inputs)))
+            .or_default()
+            .push(case);
+
+        let description = case.test.description.trim();
+        if description.is_empty() {
+            continue;
+        }
+        if let Some(first) = by_description.get(&(case.category, description)) {
+            issues.push(LintIssue {
+                severity: Severity::Warning,
+                location: case.location.clone(),
+                message: format!("duplicate description of {}", first.location),
+            });
+        } else {
+            by_description.insert((case.category, description), case);
+        }
+    }
+
+    let mut groups = Vec::new();
+    for ((category, _), members) in by_inputs.into_iter().filter(|(_, m)| m.len() > 1) {
+        let expected: Vec<_> = members
+            .iter()
+            .map(|case| expectation_values(case.test))
+            .collect();
+        let mut conflicts: BTreeSet<String> = BTreeSet::new();
+        for (i, a) in expected.iter().enumerate() {
+            for b in &expected[i + 1..] {
+                for (field, value) in a {
+                    if b.get(field)
+                        .is_some_and(|other| !yaml_values_match(value, other, DEFAULT_TOLERANCE))
+                    {
+                        conflicts.insert(field.clone());
+                    }
+                }
+            }
+        }
+
+        let others: Vec<&str> = members[1..]
+            .iter()
+            .map(|case| case.location.as_str())
+            .collect();
+        let (severity, message) = if conflicts.is_empty() {
+            (Severity::Warning, "duplicate inputs".to_string())
+        } else {
+            (
+                Severity::Error,
+                format!(
+                    "duplicate inputs with conflicting {}",
+                    conflicts.iter().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            )
+        };
+        issues.push(LintIssue {
+            severity,
+            location: members[0].location.clone(),
+            message: format!("{}; also at:\n      {}", message, others.join("\n      ")),
+        });
+
+        groups.push(DuplicateGroup {
+            category: category.to_string(),
+            inputs: canonical_inputs(&members[0].test.inputs),
+            conflicts: conflicts.into_iter().collect(),
+            cases: members
+                .iter()
+                .zip(expected)
+                .map(|(case, expected)| DuplicateMember {
+                    file: case.file.to_string(),
+                    index: case.index,
+                    description: case.test.description.clone(),
+                    expected,
+                })
+                .collect(),
+        });
+    }
+    groups
+}
+
+/// Tags used exactly once across the corpus are likely typos
+fn lint_rare_tags(cases: &[LintCase], issues: &mut Vec<LintIssue>) {
+    let mut counts: HashMap<&str, Vec<&LintCase>> = HashMap::new();
+    for case in cases {
+        for tag in &case.tags {
+            counts.entry(tag.as_str()).or_default().push(case);
+        }
+    }
+    let mut rare: Vec<(&str, &LintCase)> = counts
+        .into_iter()
+        .filter(|(_, uses)| uses.len() == 1)
+        .map(|(tag, uses)| (tag, uses[0]))
+        .collect();
+    rare.sort_by(|a, b| a.0.cmp(b.0));
+    for (tag, case) in rare {
+        issues.push(LintIssue {
+            severity: Severity::Warning,
+            location: case.location.clone(),
+            message: format!("tag {:?} is used only once", tag),
+        });
+    }
+}
+
+/// Canonical form of a case's inputs (sorted keys, `expected_*` excluded) for matching cases
+fn inputs_key(inputs: &serde_yaml::Mapping) -> String {
+    serde_yaml::to_string(&canonical_inputs(inputs)).unwrap_or_default()
+}
+
+/// A case's inputs without `expected_*` fields, with mapping keys sorted at every depth
+fn canonical_inputs(inputs: &serde_yaml::Mapping) -> serde_yaml::Value {
+    let inputs: serde_yaml::Mapping = inputs
+        .iter()
+        .filter(|(k, _)| !is_expectation_key(k))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    canonical_value(&serde_yaml::Value::Mapping(inputs))
+}
+
+/// Category-specific expectations live in the inputs as `expected_*` keys
+fn is_expectation_key(key: &serde_yaml::Value) -> bool {
+    key.as_str().is_some_and(|k| k.starts_with("expected_"))
+}
+
+fn canonical_value(value: &serde_yaml::Value) -> serde_yaml::Value {
+    use serde_yaml::Value;
+    match value {
+        Value::Mapping(map) => {
+            let mut entries: Vec<(Value, Value)> = map
+                .iter()
+                .map(|(k, v)| (canonical_value(k), canonical_value(v)))
+                .collect();
+            entries.sort_by_cached_key(|(k, _)| serde_yaml::to_string(k).unwrap_or_default());
+            Value::Mapping(entries.into_iter().collect())
+        }
+        Value::Sequence(items) => Value::Sequence(items.iter().map(canonical_value).collect()),
+        other => other.clone(),
+    }
+}
+
+// ============================================================================
+// DIFF MODE
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Cases are matched across corpora by (category, description, inputs)
+type CaseIdentity = (String, String, String);
+
+/// One case's expected values, keyed by field name
+struct CaseExpectations {
+    file: String,
+    values: BTreeMap<String, serde_yaml::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct CaseRef {
+    file: String,
+    category: String,
+    description: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ValueChange {
+    #[serde(flatten)]
+    case: CaseRef,
+    field: String,
+    old: Option<serde_yaml::Value>,
+    new: Option<serde_yaml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct FixtureDiff {
+    added: Vec<CaseRef>,
+    removed: Vec<CaseRef>,
+    changed: Vec<ValueChange>,
+    /// Numeric changes smaller than the tolerance
+    insignificant: Vec<ValueChange>,
+}
+
+impl FixtureDiff {
+    fn has_differences(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+fn diff_fixtures(old: &str, new: &str, format: OutputFormat, epsilon: Option<f64>) {
+    let tol = epsilon.unwrap_or(DEFAULT_TOLERANCE);
+    let load = |spec: &str| match load_corpus(spec) {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            process::exit(2);
+        }
+    };
+    let old_cases = load(old);
+    let new_cases = load(new);
+    let diff = compute_diff(&old_cases, &new_cases, tol);
+
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&diff).expect("Failed to serialize diff")
+        ),
+        OutputFormat::Text => print_diff(old, new, &diff, tol),
+    }
+
+    process::exit(if diff.has_differences() { 1 } else { 0 });
+}
+
+/// Load a fixture file, directory (searched recursively for *.yaml), or glob
+fn load_corpus(spec: &str) -> Result<BTreeMap<CaseIdentity, CaseExpectations>, String> {
+    let path = Path::new(spec);
+    let pattern = if path.is_dir() {
+        format!("{}/**/*.yaml", spec.trim_end_matches('/'))
+    } else {
+        spec.to_string()
+    };
+
+    let mut cases = BTreeMap::new();
+    let mut files = 0;
+    for entry in glob(&pattern).map_err(|e| format!("invalid pattern {}: {}", pattern, e))? {
+        let path = entry.map_err(|e| e.to_string())?;
+        let fixture = load_fixture(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        files += 1;
+        for group in &fixture.test_cases {
+            for test in &group.cases {
+                let identity = (
+                    group.category.clone(),
+                    test.description.clone(),
+                    inputs_key(&test.inputs),
+                );
+                let expectations = CaseExpectations {
+                    file: path.display().to_string(),
+                    values: expectation_values(test),
+                };
+                cases.insert(identity, expectations);
+            }
+        }
+    }
+    if files == 0 {
+        return Err(format!("no fixture files match {}", spec));
+    }
+    Ok(cases)
+}
+
+/// Every expected value a case asserts, including category-specific `expected_*` inputs
+fn expectation_values(test: &TestCase) -> BTreeMap<String, serde_yaml::Value> {
+    let mut values: BTreeMap<String, serde_yaml::Value> = test
+        .inputs
+        .iter()
+        .filter(|(k, _)| is_expectation_key(k))
+        .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.clone())))
+        .collect();
+    let typed = [
+        (
+            "expected_distance",
+            serde_yaml::to_value(test.expected_distance),
+        ),
+        ("expected_score", serde_yaml::to_value(test.expected_score)),
+        ("expected_range", serde_yaml::to_value(&test.expected_range)),
+        (
+            "expected_ranges",
+            serde_yaml::to_value(&test.expected_ranges),
+        ),
+        (
+            "expected_score_range",
+            serde_yaml::to_value(&test.expected_score_range),
+        ),
+        ("expected", serde_yaml::to_value(&test.expected)),
+        ("expect_error", serde_yaml::to_value(&test.expect_error)),
+    ];
+    for (field, value) in typed {
+        match value {
+            Ok(serde_yaml::Value::Null) | Err(_) => {}
+            Ok(value) => {
+                values.insert(field.to_string(), value);
+            }
+        }
+    }
+    values
+}
+
+fn compute_diff(
+    old: &BTreeMap<CaseIdentity, CaseExpectations>,
+    new: &BTreeMap<CaseIdentity, CaseExpectations>,
+    tol: f64,
+) -> FixtureDiff {
+    let case_ref = |identity: &CaseIdentity, file: &str| CaseRef {
+        file: file.to_string(),
+        category: identity.0.clone(),
+        description: identity.1.clone(),
+    };
+    let mut diff = FixtureDiff::default();
+
+    for (identity, old_case) in old {
+        let Some(new_case) = new.get(identity) else {
+            diff.removed.push(case_ref(identity, &old_case.file));
+            continue;
+        };
+        let fields: std::collections::BTreeSet<&String> = old_case
+            .values
+            .keys()
+            .chain(new_case.values.keys())
+            .collect();
+        for field in fields {
+            let old_value = old_case.values.get(field);
+            let new_value = new_case.values.get(field);
+            if old_value == new_value {
+                continue;
+            }
+            let delta = match (
+                old_value.and_then(|v| v.as_f64()),
+                new_value.and_then(|v| v.as_f64()),
+            ) {
+                (Some(a), Some(b)) => Some(b - a),
+                _ => None,
+            };
+            let change = ValueChange {
+                case: case_ref(identity, &new_case.file),
+                field: field.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+                delta,
+            };
+            // Integer fields (distances) are exact; only float drift can be insignificant
+            let is_float =
+                old_value.is_some_and(|v| v.is_f64()) || new_value.is_some_and(|v| v.is_f64());
+            match delta {
+                Some(d) if is_float && d.abs() < tol => diff.insignificant.push(change),
+                _ => diff.changed.push(change),
+            }
+        }
+    }
+    for (identity, new_case) in new {
+        if !old.contains_key(identity) {
+            diff.added.push(case_ref(identity, &new_case.file));
+        }
+    }
+    diff
+}
+
+fn print_diff(old: &str, new: &str, diff: &FixtureDiff, tol: f64) {
+    println!("{} {} → {}", "Comparing".cyan(), old, new);
+    println!();
+
+    let print_cases = |title: String, marker: colored::ColoredString, cases: &[CaseRef]| {
+        if cases.is_empty() {
+            return;
+        }
+        println!("{}", title.bold());
+        for case in cases {
+            println!(
+                "  {} [{}] {} ({})",
+                marker, case.category, case.description, case.file
+            );
+        }
+        println!();
+    };
+    let print_changes = |title: String, changes: &[ValueChange]| {
+        if changes.is_empty() {
+            return;
+        }
+        println!("{}", title.bold());
+        for change in changes {
+            println!(
+                "  {} [{}] {} ({})",
+                "~".yellow(),
+                change.case.category,
+                change.case.description,
+                change.case.file
+            );
+            let show = |value: &Option<serde_yaml::Value>| match value {
+                Some(v) => serde_json::to_string(v).unwrap_or_default(),
+                None => "(absent)".to_string(),
+            };
+            let delta = change
+                .delta
+                .map(|d| {
+                    if d.fract() == 0.0 {
+                        format!(" (Δ {:+})", d)
+                    } else {
+                        format!(" (Δ {:+e})", d)
+                    }
+                })
+                .unwrap_or_default();
+            println!(
+                "      {}: {} → {}{}",
+                change.field,
+                show(&change.old),
+                show(&change.new),
+                delta
+            );
+        }
+        println!();
+    };
+
+    print_cases(
+        format!("ADDED ({}):", diff.added.len()),
+        "+".green(),
+        &diff.added,
+    );
+    print_cases(
+        format!("REMOVED ({}):", diff.removed.len()),
+        "-".red(),
+        &diff.removed,
+    );
+    print_changes(format!("CHANGED ({}):", diff.changed.len()), &diff.changed);
+    print_changes(
+        format!(
+            "INSIGNIFICANT ({}, below tolerance {:e}):",
+            diff.insignificant.len(),
+            tol
+        ),
+        &diff.insignificant,
+    );
+
+    println!("{}", "=".repeat(80));
+    println!("{}", "SUMMARY".bold());
+    println!("{}", "=".repeat(80));
+    println!("Added:           {}", diff.added.len().to_string().green());
+    println!("Removed:         {}", diff.removed.len().to_string().red());
+    println!(
+        "Changed:         {}",
+        diff.changed.len().to_string().yellow()
+    );
+    println!("Insignificant:   {}", diff.insignificant.len());
+    println!();
+    if !diff.has_differences() {
+        println!("{}", "No differences.".green().bold());
+    }
+}
+
+// ============================================================================
+// MERGE MODE
+// ============================================================================
+
+fn merge_fixtures(inputs: &[PathBuf], output: &Path, force: bool) {
+    println!("{} {} fixture files", "Merging".cyan(), inputs.len());
+
+    let mut fixtures = Vec::new();
+    for path in inputs {
+        match load_fixture(path) {
+            Ok(fixture) => fixtures.push(fixture),
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // All inputs are expected to share the first one's $schema
+    let schema = fixtures[0].schema.clone();
+    let version = latest_version(fixtures.iter().map(|f| f.version.as_str()));
+
+    let mut groups: Vec<CategoryGroup> = Vec::new();
+    // (category, inputs) -> (group index, case index) of the first case seen
+    let mut seen: HashMap<(String, String), Vec<(usize, usize)>> = HashMap::new();
+    let mut duplicates = 0;
+    let mut conflicts: Vec<String> = Vec::new();
+
+    for (path, fixture) in inputs.iter().zip(fixtures) {
+        let file_tolerance = fixture.tolerance;
+        for group in fixture.test_cases {
+            let group_index = match groups.iter().position(|g| g.category == group.category) {
+                Some(index) => index,
+                None => {
+                    groups.push(CategoryGroup {
+                        category: group.category.clone(),
+                        tags: None,
+                        cases: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+
+            'cases: for mut case in group.cases {
+                // The merged file has no file-level tolerance; keep each source's on its cases
+                if case.tolerance.is_none() {
+                    case.tolerance = file_tolerance;
+                }
+                inherit_group_tags(group.tags.as_deref(), &mut case);
+
+                let key = (group.category.clone(), inputs_key(&case.inputs));
+                let matches = seen.entry(key).or_default();
+                for &(g, c) in matches.iter() {
+                    let existing = &groups[g].cases[c];
+                    if expectation_values(existing) != expectation_values(&case) {
+                        conflicts.push(format!(
+                            "[{}] {:?} in {} conflicts with {:?}",
+                            group.category,
+                            case.description,
+                            path.display(),
+                            existing.description
+                        ));
+                        continue 'cases;
+                    }
+                    if existing.description == case.description {
+                        duplicates += 1;
+                        continue 'cases;
+                    }
+                }
+                matches.push((group_index, groups[group_index].cases.len()));
+                groups[group_index].cases.push(case);
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        let label = if force { "WARNING" } else { "ERROR" };
+        println!();
+        println!(
+            "{}",
+            format!(
+                "{}: {} cases share inputs but have conflicting expected values:",
+                label,
+                conflicts.len()
+            )
+            .red()
+            .bold()
+        );
+        for conflict in &conflicts {
+            println!("  {} {}", "✗".red(), conflict);
+        }
+        if !force {
+            eprintln!();
+            eprintln!("Nothing written. Re-run with --force to keep the first occurrence of each.");
+            process::exit(1);
+        }
+        println!("Keeping the first occurrence of each (--force)");
+    }
+
+    let sources: Vec<String> = inputs.iter().map(|p| p.display().to_string()).collect();
+    let merged = Fixture {
+        schema,
+        version,
+        generator: Some(GeneratorMetadata {
+            tool: "similarity-validator".to_string(),
+            tool_version: VERSION.to_string(),
+            source_library: "rapidfuzz-rs".to_string(),
+            source_version: RAPIDFUZZ_VERSION.to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            command: Some(format!(
+                "similarity-validator merge --output {} {}{}",
+                output.display(),
+                sources.join(" "),
+                if force { " --force" } else { "" }
+            )),
+            sources: Some(sources.clone()),
+            seed: None,
+            precision: None,
+            arithmetic: None,
+        }),
+        notes: Some(format!("Merged from:\n- {}", sources.join("\n- "))),
+        tolerance: None,
+        dataset_version: None,
+        range_unit: None,
+        weights: None,
+        checksums: false,
+        test_cases: groups,
+    };
+
+    let yaml = serde_yaml::to_string(&merged).expect("Failed to serialize YAML");
+    fs::write(output, yaml).expect("Failed to write output file");
+
+    let total: usize = merged.test_cases.iter().map(|g| g.cases.len()).sum();
+    println!(
+        "Cases: {} | Categories: {} | Duplicates removed: {} | Conflicts: {}",
+        total.to_string().green(),
+        merged.test_cases.len(),
+        duplicates.to_string().yellow(),
+        conflicts.len().to_string().red()
+    );
+    println!("{} {}", "✅ Written:".green(), output.display());
+}
+
+/// Newest of a set of CalVer versions
+fn latest_version<'a>(versions: impl Iterator<Item = &'a str>) -> String {
+    versions
+        .max_by_key(|version| {
+            version
+                .split('.')
+                .map(|part| part.parse::<u32>().unwrap_or(0))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+        .to_string()
+}
+
+// ============================================================================
+// CONVERT MODE
+// ============================================================================
+
+/// On-disk fixture encodings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum FixtureFormat {
+    Yaml,
+    Json,
+}
+
+impl FixtureFormat {
+    fn from_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "yaml" | "yml" => Ok(FixtureFormat::Yaml),
+            "json" => Ok(FixtureFormat::Json),
+            _ => Err(format!(
+                "unsupported fixture format {:?} for {} (supported: .yaml, .yml, .json)",
+                extension,
+                path.display()
+            )),
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(self, contents: &str) -> Result<T, Box<dyn std::error::Error>> {
+        Ok(match self {
+            FixtureFormat::Yaml => serde_yaml::from_str(contents)?,
+            FixtureFormat::Json => serde_json::from_str(contents)?,
+        })
+    }
+
+    fn serialize(self, fixture: &Fixture) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(match self {
+            FixtureFormat::Yaml => serde_yaml::to_string(fixture)?,
+            FixtureFormat::Json => serde_json::to_string_pretty(fixture)? + "\n",
+        })
+    }
+}
+
+fn convert_fixture(input: &Path, output: &Path, to: Option<FixtureFormat>, check: bool) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}: {}", "Error".red(), message);
+        process::exit(1);
+    };
+
+    let fixture =
+        load_fixture(input).unwrap_or_else(|e| fail(format!("{}: {}", input.display(), e)));
+    let format = match to {
+        Some(format) => format,
+        None => FixtureFormat::from_path(output).unwrap_or_else(|e| fail(e)),
+    };
+    let serialized = format
+        .serialize(&fixture)
+        .unwrap_or_else(|e| fail(format!("cannot serialize: {}", e)));
+
+    if check {
+        let reparsed = parse_fixture(format, &serialized)
+            .unwrap_or_else(|e| fail(format!("converted output does not parse: {}", e)));
+        if !fixtures_equal(&fixture, &reparsed) {
+            fail(format!(
+                "converted output of {} is not semantically equal to the input",
+                input.display()
+            ));
+        }
+    }
+
+    fs::write(output, serialized).expect("Failed to write output file");
+    println!(
+        "{} {} → {}{}",
+        "✅ Converted:".green(),
+        input.display(),
+        output.display(),
+        if check { " (checked)" } else { "" }
+    );
+}
+
+/// Semantic equality: same data regardless of encoding or key order
+fn fixtures_equal(a: &Fixture, b: &Fixture) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// ============================================================================
+// STATS MODE
+// ============================================================================
+
+/// Metrics the library exposes, in fixture (snake_case) spelling
+const LIBRARY_METRICS: &[&str] = &[
+    "levenshtein",
+    "damerau_osa",
+    "damerau_unrestricted",
+    "damerau_graphemes",
+    "damerau_weighted",
+    "affine_gap",
+    "mfc",
+    "jaro",
+    "jaro_winkler",
+    "jaro_winkler_params",
+    "jaro_tokens",
+    "jaro_winkler_tokens",
+    "indel",
+    "lcs_seq",
+    "ratio",
+    "partial_ratio",
+    "token_sort_ratio",
+    "token_set_ratio",
+    "substring",
+    "coverage",
+    "fuzzy_find",
+];
+
+/// Map metric aliases onto their LIBRARY_METRICS name
+fn canonical_metric(metric: &str) -> &str {
+    match metric {
+        "osa" => "damerau_osa",
+        "damerau_levenshtein" => "damerau_unrestricted",
+        other => other,
+    }
+}
+
+/// The metric a unified, suggestion or extraction case selects by name
+fn case_metric(category: &str, test: &TestCase) -> Option<String> {
+    let option = |key: &str| {
+        test.inputs
+            .get("options")
+            .and_then(|options| options.get(key))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    match category {
+        // Negative cases reference bad metric names on purpose
+        _ if test.expect_error.is_some() => None,
+        "unified_distance"
+        | "unified_score"
+        | "window_scores"
+        | "anchored_similarity"
+        | "score_nullable" => get_string_input(&test.inputs, "metric"),
+        "suggestions" => option("metric"),
+        "extract_one" | "extract" => option("scorer"),
+        _ => None,
+    }
+}
+
+/// Input keys whose string lengths are bucketed
+const LENGTH_INPUTS: &[&str] = &["input_a", "input_b", "input", "needle", "haystack", "query"];
+
+fn length_bucket(len: usize) -> &'static str {
+    match len {
+        0 => "0",
+        1..=4 => "1-4",
+        5..=16 => "5-16",
+        17..=64 => "17-64",
+        _ => "65+",
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CategoryStats {
+    cases: usize,
+    with_expected: usize,
+    pending: usize,
+    tags: BTreeMap<String, usize>,
+    input_lengths: BTreeMap<&'static str, usize>,
+    metrics: std::collections::BTreeSet<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CorpusStats {
+    files: usize,
+    cases: usize,
+    categories: BTreeMap<String, CategoryStats>,
+    /// Library metrics with no fixture coverage at all
+    uncovered_metrics: Vec<&'static str>,
+}
+
+fn stats_fixtures(pattern: &str, format: OutputFormat) {
+    let mut stats = CorpusStats::default();
+
+    for entry in glob(pattern).expect("Failed to read glob pattern") {
+        let path = match entry {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                continue;
+            }
+        };
+        let fixture = match load_fixture(&path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                continue;
+            }
+        };
+        stats.files += 1;
+
+        for group in &fixture.test_cases {
+            let category = stats.categories.entry(group.category.clone()).or_default();
+            for test in &group.cases {
+                stats.cases += 1;
+                category.cases += 1;
+                if has_expectation(test) {
+                    category.with_expected += 1;
+                } else {
+                    category.pending += 1;
+                }
+                for tag in effective_tags(group.tags.as_deref(), test.tags.as_deref()) {
+                    *category.tags.entry(tag).or_default() += 1;
+                }
+                for key in LENGTH_INPUTS {
+                    if let Some(value) = get_string_input(&test.inputs, key) {
+                        *category
+                            .input_lengths
+                            .entry(length_bucket(value.chars().count()))
+                            .or_default() += 1;
+                    }
+                }
+                if let Some(metric) = case_metric(&group.category, test) {
+                    category.metrics.insert(metric);
+                }
+            }
+        }
+    }
+
+    let covered: std::collections::HashSet<&str> = stats
+        .categories
+        .iter()
+        .flat_map(|(name, category)| {
+            std::iter::once(name.as_str()).chain(category.metrics.iter().map(|m| m.as_str()))
+        })
+        .map(canonical_metric)
+        .collect();
+    stats.uncovered_metrics = LIBRARY_METRICS
+        .iter()
+        .copied()
+        .filter(|metric| !covered.contains(metric))
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&stats).expect("Failed to serialize stats")
+        ),
+        OutputFormat::Text => print_stats(pattern, &stats),
+    }
+}
+
+// ============================================================================
+// COVERAGE GATE
+// ============================================================================
+
+/// Fixture categories whose cases count toward a registered metric besides its own names.
+/// Export-only similarities are fixtured under the category of the function they wrap.
+const REGISTRY_FIXTURE_NAMES: &[(&str, &[&str])] = &[
+    (
+        "normalized_damerau_levenshtein_graphemes",
+        &["damerau_graphemes"],
+    ),
+    (
+        "normalized_damerau_levenshtein_weighted",
+        &["damerau_weighted"],
+    ),
+    ("mfc_similarity", &["mfc"]),
+    ("normalized_affine_gap_similarity", &["affine_gap"]),
+    ("jaro_winkler_with_params", &["jaro_winkler_params"]),
+    ("coverage_score", &["coverage"]),
+    ("subsequence_coverage_score", &["coverage"]),
+    ("normalized_levenshtein_bytes", &["byte_metrics"]),
+    ("normalized_hamming_bytes", &["byte_metrics"]),
+];
+
+/// Fixture names (categories, or metric names in canonical spelling) whose cases count toward
+/// a registered metric: its name and aliases, plus any `REGISTRY_FIXTURE_NAMES` entry. A
+/// metric registered without fixtures under any of them fails the gate.
+fn registry_fixture_names(def: &string_metrics_core::MetricDef) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = def.names().map(canonical_metric).collect();
+    if let Some((_, extra)) = REGISTRY_FIXTURE_NAMES
+        .iter()
+        .find(|(name, _)| *name == def.name)
+    {
+        names.extend_from_slice(extra);
+    }
+    names.dedup();
+    names
+}
+
+#[derive(Debug, Serialize)]
+struct MetricCoverage {
+    metric: &'static str,
+    fixture_names: Vec<&'static str>,
+    cases: usize,
+    allowlisted: bool,
+}
+
+/// Registry names listed in an allowlist file; blank lines and `#` comments are ignored
+fn read_allowlist(path: &Path) -> Result<BTreeSet<String>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read allowlist {}: {}", path.display(), e))?;
+    let names: BTreeSet<String> = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    // A stale or misspelled name would silently exempt nothing
+    let registered = string_metrics_core::list_metrics();
+    let unknown: Vec<&str> = names
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !registered.iter().any(|info| info.name == *name))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Allowlist {} names metrics the registry does not have: {}",
+            path.display(),
+            unknown.join(", ")
+        ));
+    }
+    Ok(names)
+}
+
+/// Count the cases with expected values under each fixture name, then require `min_cases` for
+/// every registered metric that is not allowlisted. Property cases don't count: they check
+/// invariants, not values.
+fn coverage_gate(pattern: &str, min_cases: usize, allowlist: Option<&Path>, format: OutputFormat) {
+    let allowed = match allowlist.map(read_allowlist).transpose() {
+        Ok(allowed) => allowed.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e);
+            process::exit(1);
+        }
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut files = 0;
+    for entry in glob(pattern).expect("Failed to read glob pattern") {
+        let path = match entry {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                process::exit(1);
+            }
+        };
+        let fixture = match load_fixture(&path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                process::exit(1);
+            }
+        };
+        files += 1;
+        for group in &fixture.test_cases {
+            for test in &group.cases {
+                if !has_expectation(test) || test.inputs.contains_key("property") {
+                    continue;
+                }
+                let mut names = BTreeSet::from([canonical_metric(&group.category).to_string()]);
+                names.extend(
+                    case_metric(&group.category, test).map(|m| canonical_metric(&m).to_string()),
+                );
+                for name in names {
+                    *counts.entry(name).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let registered = match string_metrics_core::registered_metrics() {
+        Ok(registered) => registered,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e);
+            process::exit(1);
+        }
+    };
+    let report: Vec<MetricCoverage> = registered
+        .iter()
+        .map(|def| {
+            let fixture_names = registry_fixture_names(def);
+            MetricCoverage {
+                metric: def.name,
+                cases: fixture_names
+                    .iter()
+                    .map(|name| counts.get(*name).copied().unwrap_or(0))
+                    .sum(),
+                fixture_names,
+                allowlisted: allowed.contains(def.name),
+            }
+        })
+        .collect();
+    let below: Vec<&MetricCoverage> = report
+        .iter()
+        .filter(|metric| metric.cases < min_cases && !metric.allowlisted)
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize coverage")
+        ),
+        OutputFormat::Text => {
+            println!(
+                "{} {} ({} files, minimum {} cases per metric)",
+                "Fixture coverage for".cyan(),
+                pattern,
+                files,
+                min_cases
+            );
+            println!();
+            for metric in &report {
+                let status = if metric.allowlisted {
+                    "allowlisted".yellow()
+                } else if metric.cases < min_cases {
+                    "✗".red()
+                } else {
+                    "✓".green()
+                };
+                println!(
+                    "  {:<42} {:>5}  {}  ({})",
+                    metric.metric,
+                    metric.cases,
+                    status,
+                    metric.fixture_names.join(", ")
+                );
+            }
+            println!();
+            if !below.is_empty() {
+                println!("{}", "BELOW MINIMUM:".red().bold());
+                for metric in &below {
+                    println!(
+                        "  {} {}: {} of {} cases (fixture names: {})",
+                        "✗".red(),
+                        metric.metric,
+                        metric.cases,
+                        min_cases,
+                        metric.fixture_names.join(", ")
+                    );
+                }
+                println!();
+            }
+        }
+    }
+
+    if !below.is_empty() {
+        process::exit(1);
+    }
+    if format == OutputFormat::Text {
+        println!(
+            "{}",
+            "Every registered metric meets the minimum.".green().bold()
+        );
+    }
+}
+
+fn print_stats(pattern: &str, stats: &CorpusStats) {
+    println!("{} fixtures matching: {}", "Statistics for".cyan(), pattern);
+    println!();
+
+    for (name, category) in &stats.categories {
+        println!("{}", name.bold());
+        println!(
+            "  Cases:         {} ({} with expected values, {} pending)",
+            category.cases, category.with_expected, category.pending
+        );
+        if !category.input_lengths.is_empty() {
+            // Print buckets in length order rather than lexically
+            let buckets: Vec<String> = ["0", "1-4", "5-16", "17-64", "65+"]
+                .iter()
+                .filter_map(|bucket| {
+                    category
+                        .input_lengths
+                        .get(bucket)
+                        .map(|count| format!("{}: {}", bucket, count))
+                })
+                .collect();
+            println!("  Input lengths: {}", buckets.join(", "));
+        }
+        if !category.metrics.is_empty() {
+            let metrics: Vec<&str> = category.metrics.iter().map(|m| m.as_str()).collect();
+            println!("  Metrics:       {}", metrics.join(", "));
+        }
+        if !category.tags.is_empty() {
+            let mut tags: Vec<(&String, &usize)> = category.tags.iter().collect();
+            tags.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            let tags: Vec<String> = tags
+                .iter()
+                .map(|(tag, count)| format!("{} ({})", tag, count))
+                .collect();
+            println!("  Tags:          {}", tags.join(", "));
+        }
+        println!();
+    }
+
+    println!("{}", "=".repeat(80));
+    println!("{}", "SUMMARY".bold());
+    println!("{}", "=".repeat(80));
+    println!("Files:           {}", stats.files);
+    println!("Categories:      {}", stats.categories.len());
+    println!("Cases:           {}", stats.cases);
+    if stats.uncovered_metrics.is_empty() {
+        println!("{}", "Every library metric has fixture coverage.".green());
+    } else {
+        println!(
+            "Uncovered:       {}",
+            stats.uncovered_metrics.join(", ").red()
+        );
+    }
+}
+
+// ============================================================================
+// CHECK MODE
+// ============================================================================
+
+/// Inputs for a one-off computation
+struct CheckOptions {
+    metric: String,
+    preset: Option<String>,
+    locale: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    a: String,
+    b: String,
+    metric: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalized_a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalized_b: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+}
+
+fn check_pairs(
+    a: Option<String>,
+    b: Option<String>,
+    stdin: bool,
+    options: &CheckOptions,
+    format: OutputFormat,
+) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}: {}", "Error".red(), message);
+        process::exit(1);
+    };
+
+    let pairs: Vec<(String, String)> = if stdin {
+        std::io::stdin()
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .map(|(i, line)| match line.split_once('\t') {
+                Some((a, b)) => (a.to_string(), b.to_string()),
+                None => fail(format!("stdin line {}: expected <a>\\t<b>", i + 1)),
+            })
+            .collect()
+    } else {
+        vec![(a.unwrap_or_default(), b.unwrap_or_default())]
+    };
+
+    let results: Vec<CheckResult> = pairs
+        .into_iter()
+        .map(|(a, b)| check_pair(a, b, options).unwrap_or_else(|e| fail(e)))
+        .collect();
+
+    match format {
+        OutputFormat::Json => {
+            let json = if stdin {
+                serde_json::to_string_pretty(&results)
+            } else {
+                serde_json::to_string_pretty(&results[0])
+            };
+            println!("{}", json.expect("Failed to serialize results"));
+        }
+        OutputFormat::Text if stdin => {
+            // One tab-separated row per input line, for spreadsheets and `cut`
+            for result in &results {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    result.a,
+                    result.b,
+                    result.distance.map(|d| d.to_string()).unwrap_or_default(),
+                    result.score.map(|s| s.to_string()).unwrap_or_default()
+                );
+            }
+        }
+        OutputFormat::Text => {
+            let result = &results[0];
+            println!("metric:   {}", result.metric);
+            println!("a:        {:?}", result.a);
+            println!("b:        {:?}", result.b);
+            if let (Some(a), Some(b)) = (&result.normalized_a, &result.normalized_b) {
+                println!("normalized a: {:?}", a);
+                println!("normalized b: {:?}", b);
+            }
+            if let Some(distance) = result.distance {
+                println!("distance: {}", distance);
+            }
+            if let Some(score) = result.score {
+                println!("score:    {}", score);
+            }
+        }
+    }
+}
+
+/// Compute through the same unified dispatchers validation uses
+fn check_pair(a: String, b: String, options: &CheckOptions) -> Result<CheckResult, String> {
+    let (normalized_a, normalized_b) = match &options.preset {
+        Some(preset) => (
+            Some(try_normalize_with_locale(
+                &a,
+                preset,
+                options.locale.as_deref(),
+            )?),
+            Some(try_normalize_with_locale(
+                &b,
+                preset,
+                options.locale.as_deref(),
+            )?),
+        ),
+        None => (None, None),
+    };
+    let input_a = normalized_a.as_deref().unwrap_or(&a);
+    let input_b = normalized_b.as_deref().unwrap_or(&b);
+
+    if matches!(
+        options.metric.as_str(),
+        "partial_ratio" | "token_sort_ratio" | "token_set_ratio"
+    ) {
+        return Err(format!(
+            "{} is TypeScript-only and has no rapidfuzz-rs equivalent",
+            options.metric
+        ));
+    }
+    let distance = string_metrics_core::unified_distance(input_a, input_b, &options.metric);
+    let score = string_metrics_core::unified_score(input_a, input_b, &options.metric);
+    if let (Err(distance_error), Err(score_error)) = (&distance, &score) {
+        return Err(format!("{}; {}", distance_error, score_error));
+    }
+    let (distance, score) = (distance.ok(), score.ok());
+
+    Ok(CheckResult {
+        a,
+        b,
+        metric: options.metric.clone(),
+        normalized_a,
+        normalized_b,
+        distance,
+        score,
+    })
+}
+
+// ============================================================================
+// EXPLAIN MODE
+// ============================================================================
+// Edit operations come from the core's Wagner-Fischer backtrace; the distance
+// they imply is cross-checked against rapidfuzz-rs before printing.
+
+/// Marker shown under an alignment column
+fn edit_marker(tag: EditTag) -> char {
+    match tag {
+        EditTag::Equal => ' ',
+        EditTag::Replace => '*',
+        EditTag::Insert => '+',
+        EditTag::Delete => '-',
+        EditTag::Transpose => '~',
+    }
+}
+
+/// Terminal column width: 0 for combining marks, 2 for wide East Asian and emoji
+fn char_width(c: char) -> usize {
+    if unicode_normalization::char::is_combining_mark(c) {
+        return 0;
+    }
+    match c as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Three lines (a, markers, b) with gaps shown as '-'
+fn render_alignment(a: &[char], b: &[char], steps: &[EditStep]) -> (String, String, String) {
+    let (mut top, mut middle, mut bottom) = (String::new(), String::new(), String::new());
+    let mut column = |x: Option<&[char]>, y: Option<&[char]>, marker: char| {
+        let text = |chars: Option<&[char]>| chars.map(|c| c.iter().collect::<String>());
+        let width_of = |chars: Option<&[char]>| {
+            chars.map_or(0, |c| c.iter().map(|&ch| char_width(ch)).sum::<usize>())
+        };
+        let width = width_of(x).max(width_of(y)).max(1);
+        let pad = |chars: Option<&[char]>| match text(chars) {
+            Some(s) => format!("{}{}", s, " ".repeat(width - width_of(chars))),
+            None => "-".repeat(width),
+        };
+        top.push_str(&pad(x));
+        bottom.push_str(&pad(y));
+        middle.push_str(&marker.to_string().repeat(width));
+    };
+
+    for step in steps {
+        let (src_len, dest_len) = match step.tag {
+            EditTag::Transpose => (2, 2),
+            EditTag::Insert => (0, 1),
+            EditTag::Delete => (1, 0),
+            _ => (1, 1),
+        };
+        let x = (src_len > 0).then(|| &a[step.src_pos..step.src_pos + src_len]);
+        let y = (dest_len > 0).then(|| &b[step.dest_pos..step.dest_pos + dest_len]);
+        column(x, y, edit_marker(step.tag));
+    }
+    (top, middle, bottom)
+}
+
+fn explain_pair(a: &str, b: &str, metric: &str) {
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    let transpositions = metric == "osa";
+    let steps = alignment_steps(&a_chars, &b_chars, transpositions);
+    let edits: Vec<&EditStep> = steps.iter().filter(|s| s.tag != EditTag::Equal).collect();
+
+    let canonical = if transpositions {
+        rapidfuzz::distance::osa::distance(a.chars(), b.chars())
+    } else {
+        rapidfuzz::distance::levenshtein::distance(a.chars(), b.chars())
+    };
+    if edits.len() != canonical {
+        eprintln!(
+            "{}: reconstructed {} edits but rapidfuzz-rs reports distance {}",
+            "Error".red(),
+            edits.len(),
+            canonical
+        );
+        process::exit(1);
+    }
+
+    println!("{} {}: {:?} → {:?}", "Explaining".cyan(), metric, a, b);
+    println!("Distance: {}", canonical);
+    println!();
+
+    println!("{}", "Edit operations:".bold());
+    if edits.is_empty() {
+        println!("  (none - strings are identical)");
+    }
+    for step in &edits {
+        let a_at = |len: usize| {
+            a_chars[step.src_pos..step.src_pos + len]
+                .iter()
+                .collect::<String>()
+        };
+        let b_at = |len: usize| {
+            b_chars[step.dest_pos..step.dest_pos + len]
+                .iter()
+                .collect::<String>()
+        };
+        let detail = match step.tag {
+            EditTag::Replace => format!(
+                "a[{}] {:?} → b[{}] {:?}",
+                step.src_pos,
+                a_at(1),
+                step.dest_pos,
+                b_at(1)
+            ),
+            EditTag::Insert => format!("b[{}] {:?} at a[{}]", step.dest_pos, b_at(1), step.src_pos),
+            EditTag::Delete => format!(
+                "a[{}] {:?} (before b[{}])",
+                step.src_pos,
+                a_at(1),
+                step.dest_pos
+            ),
+            EditTag::Transpose => format!(
+                "a[{}..{}] {:?} → b[{}..{}] {:?}",
+                step.src_pos,
+                step.src_pos + 2,
+                a_at(2),
+                step.dest_pos,
+                step.dest_pos + 2,
+                b_at(2)
+            ),
+            EditTag::Equal => unreachable!(),
+        };
+        println!("  {:<9} {}", step.tag.name(), detail);
+    }
+    println!();
+
+    println!("{}", "Opcodes:".bold());
+    for op in opcodes(&steps) {
+        let a_span: String = a_chars[op.src_start..op.src_end].iter().collect();
+        let b_span: String = b_chars[op.dest_start..op.dest_end].iter().collect();
+        println!(
+            "  {:<9} a[{}:{}] {:?} → b[{}:{}] {:?}",
+            op.tag.name(),
+            op.src_start,
+            op.src_end,
+            a_span,
+            op.dest_start,
+            op.dest_end,
+            b_span
+        );
+    }
+    println!();
+
+    let (top, middle, bottom) = render_alignment(&a_chars, &b_chars, &steps);
+    println!("{}", "Alignment:".bold());
+    println!("  a: {}", top);
+    println!("     {}", middle.trim_end());
+    println!("  b: {}", bottom);
+}
+
+// ============================================================================
+// BENCH MODE
+// ============================================================================
+// Measures the native rapidfuzz-rs path (not WASM); good enough to catch
+// algorithm-level regressions without a criterion setup.
+
+/// Categories with no native computation to time
+const TS_ONLY_CATEGORIES: &[&str] = &["partial_ratio", "token_sort_ratio", "token_set_ratio"];
+
+struct BenchOptions {
+    iterations: usize,
+    warmup: usize,
+    format: OutputFormat,
+    baseline: Option<PathBuf>,
+    compare: bool,
+    threshold: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CategoryTiming {
+    category: String,
+    cases: usize,
+    /// Per-case latency, median over iterations
+    median_ns: f64,
+    /// Per-case latency, 95th percentile over iterations
+    p95_ns: f64,
+    /// Cases per second at the median latency
+    throughput: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchReport {
+    iterations: usize,
+    warmup: usize,
+    categories: Vec<CategoryTiming>,
+}
+
+fn bench_fixtures(pattern: &str, options: &BenchOptions) {
+    let mut corpus: BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+    for entry in glob(pattern).expect("Failed to read glob pattern") {
+        let path = match entry {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                continue;
+            }
+        };
+        let fixture = match load_fixture(&path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                continue;
+            }
+        };
+        for group in fixture.test_cases {
+            if TS_ONLY_CATEGORIES.contains(&group.category.as_str()) {
+                continue;
+            }
+            corpus
+                .entry(group.category)
+                .or_default()
+                .extend(group.cases.into_iter().filter(|t| t.expect_error.is_none()));
+        }
+    }
+    corpus.retain(|_, cases| !cases.is_empty());
+
+    let report = BenchReport {
+        iterations: options.iterations,
+        warmup: options.warmup,
+        categories: corpus
+            .iter()
+            .map(|(category, cases)| time_category(category, cases, options))
+            .collect(),
+    };
+
+    let regressions = if options.compare {
+        let path = options
+            .baseline
+            .as_deref()
+            .expect("--compare requires --baseline");
+        let baseline: BenchReport = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+                process::exit(1);
+            });
+        find_regressions(&baseline, &report, options.threshold)
+    } else {
+        Vec::new()
+    };
+
+    match options.format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize bench report")
+        ),
+        OutputFormat::Text => print_bench(pattern, &report),
+    }
+
+    if let (Some(path), false) = (&options.baseline, options.compare) {
+        let json = serde_json::to_string_pretty(&report).expect("Failed to serialize bench report");
+        fs::write(path, json + "\n").unwrap_or_else(|e| {
+            eprintln!("{} {}: {}", "Error".red(), path.display(), e);
+            process::exit(1);
+        });
+        eprintln!("Baseline written to {}", path.display());
+    }
 
-    let min_score = options
-        .get(&serde_yaml::Value::String("min_score".to_string()))
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.6);
+    if !regressions.is_empty() {
+        eprintln!();
+        eprintln!(
+            "{} (median latency up more than {}%):",
+            "REGRESSIONS".red().bold(),
+            options.threshold
+        );
+        for (category, before, after) in &regressions {
+            eprintln!(
+                "  {}: {:.0} ns -> {:.0} ns ({:+.1}%)",
+                category,
+                before,
+                after,
+                (after / before - 1.0) * 100.0
+            );
+        }
+        process::exit(1);
+    }
+}
 
-    let max_suggestions = options
-        .get(&serde_yaml::Value::String("max_suggestions".to_string()))
-        .and_then(|v| v.as_u64())
-        .unwrap_or(3) as usize;
+/// Run every case of a category once per iteration; each iteration yields one per-case latency
+fn time_category(category: &str, cases: &[TestCase], options: &BenchOptions) -> CategoryTiming {
+    let run = || {
+        for test in cases {
+            std::hint::black_box(validate_test_case(
+                "bench",
+                category,
+                std::hint::black_box(test),
+                DEFAULT_TOLERANCE,
+                false,
+            ));
+        }
+    };
 
-    let metric = options
-        .get(&serde_yaml::Value::String("metric".to_string()))
-        .and_then(|v| v.as_str())
-        .unwrap_or("levenshtein");
+    for _ in 0..options.warmup {
+        run();
+    }
 
-    let normalize_preset = options
-        .get(&serde_yaml::Value::String("normalize_preset".to_string()))
-        .and_then(|v| v.as_str())
-        .unwrap_or("default");
+    let mut samples: Vec<f64> = (0..options.iterations)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed().as_nanos() as f64 / cases.len() as f64
+        })
+        .collect();
+    samples.sort_by(|a, b| a.total_cmp(b));
 
-    let prefer_prefix = options
-        .get(&serde_yaml::Value::String("prefer_prefix".to_string()))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+    let median_ns = percentile(&samples, 0.5);
+    CategoryTiming {
+        category: category.to_string(),
+        cases: cases.len(),
+        median_ns,
+        p95_ns: percentile(&samples, 0.95),
+        throughput: if median_ns > 0.0 {
+            1e9 / median_ns
+        } else {
+            0.0
+        },
+    }
+}
 
-    // Normalize input
-    let normalized_input = normalize(&input, normalize_preset);
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
 
-    // Compute scores for each candidate with original index for stable sorting
-    let mut results: Vec<(usize, SuggestionResult)> = candidates
+/// Categories whose median latency grew by more than `threshold` percent
+fn find_regressions(
+    baseline: &BenchReport,
+    current: &BenchReport,
+    threshold: f64,
+) -> Vec<(String, f64, f64)> {
+    current
+        .categories
         .iter()
-        .enumerate()
-        .map(|(idx, candidate)| {
-            let normalized_candidate = normalize(candidate, normalize_preset);
-            let (mut score, matched_range) =
-                compute_score_for_metric(&normalized_input, &normalized_candidate, metric);
+        .filter_map(|timing| {
+            let before = baseline
+                .categories
+                .iter()
+                .find(|b| b.category == timing.category)?
+                .median_ns;
+            (timing.median_ns > before * (1.0 + threshold / 100.0))
+                .then(|| (timing.category.clone(), before, timing.median_ns))
+        })
+        .collect()
+}
 
-            // Apply prefix bonus if enabled
-            // Formula: finalScore = min(1.0, score + (1 - score) * 0.1)
-            if prefer_prefix && normalized_candidate.starts_with(&normalized_input) {
-                let bonus_weight = 0.1;
-                score = (score + (1.0 - score) * bonus_weight).min(1.0);
-            }
+fn print_bench(pattern: &str, report: &BenchReport) {
+    println!("{} fixtures matching: {}", "Benchmarking".cyan(), pattern);
+    println!(
+        "{} iterations after {} warmup (native path, per-case latency)",
+        report.iterations, report.warmup
+    );
+    println!();
+    println!(
+        "{:<24} {:>6} {:>12} {:>12} {:>14}",
+        "CATEGORY", "CASES", "MEDIAN", "P95", "CASES/SEC"
+    );
+    for timing in &report.categories {
+        println!(
+            "{:<24} {:>6} {:>9.0} ns {:>9.0} ns {:>14.0}",
+            timing.category, timing.cases, timing.median_ns, timing.p95_ns, timing.throughput
+        );
+    }
+}
 
-            (
-                idx,
-                SuggestionResult {
-                    value: candidate.clone(),
-                    score,
-                    matched_range,
-                    normalized_value: normalized_candidate.clone(),
-                },
-            )
-        })
-        .filter(|(_, r)| r.score >= min_score)
-        .collect();
+// ============================================================================
+// SCHEMA VALIDATION
+// ============================================================================
 
-    // Sort by score (descending), preserving original order for ties
-    results.sort_by(|(idx_a, a), (idx_b, b)| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| idx_a.cmp(idx_b))
-    });
+/// Compiled schemas keyed by resolved path, shared by the validation workers
+#[derive(Default)]
+struct SchemaCache {
+    validators: Mutex<HashMap<PathBuf, Result<Arc<jsonschema::Validator>, String>>>,
+}
 
-    // Truncate to max_suggestions
-    results.truncate(max_suggestions);
+impl SchemaCache {
+    fn get(&self, path: &Path) -> Result<Arc<jsonschema::Validator>, String> {
+        let mut validators = self.validators.lock().unwrap();
+        validators
+            .entry(path.to_path_buf())
+            .or_insert_with(|| compile_schema(path).map(Arc::new))
+            .clone()
+    }
+}
 
-    // Extract just the results, discarding indices
-    let results: Vec<SuggestionResult> = results.into_iter().map(|(_, r)| r).collect();
+enum SchemaError {
+    /// `$schema` is missing or names a file that isn't available locally
+    Unresolved(String),
+    /// Unparseable fixture or malformed schema file
+    Fatal(String),
+    /// Fixture file that could not be read
+    Unreadable(String),
+}
 
-    // Validate against expected
-    let empty_vec = vec![];
-    let expected_suggestions = test
-        .expected
-        .as_ref()
-        .and_then(|v| v.as_sequence())
-        .unwrap_or(&empty_vec);
+/// One schema violation, located by a JSON pointer into the fixture
+struct SchemaIssue {
+    pointer: String,
+    message: String,
+    /// Extra properties are usually typos (`expected_socre`), reported as warnings
+    unknown_field: bool,
+}
 
-    // Check length matches
-    if results.len() != expected_suggestions.len() {
-        return ValidationResult {
-            file: file.to_string(),
-            category: category.to_string(),
-            description: test.description.clone(),
-            passed: false,
-            expected: Some(format!("{} suggestions", expected_suggestions.len())),
-            actual: Some(format!("{} suggestions", results.len())),
-            error: Some("Suggestion count mismatch".to_string()),
+impl SchemaIssue {
+    fn is_error(&self) -> bool {
+        !self.unknown_field
+    }
+}
+
+impl std::fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
         };
+        write!(f, "{}: {}", pointer, self.message)
     }
+}
 
-    // Check each suggestion
-    for (i, (actual, expected)) in results.iter().zip(expected_suggestions.iter()).enumerate() {
-        let expected_map = expected.as_mapping();
-        if expected_map.is_none() {
-            continue;
-        }
-        let expected_map = expected_map.unwrap();
+fn compile_schema(path: &Path) -> Result<jsonschema::Validator, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read schema {}: {}", path.display(), e))?;
+    let schema: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("malformed schema {}: {}", path.display(), e))?;
+    jsonschema::validator_for(&schema)
+        .map_err(|e| format!("malformed schema {}: {}", path.display(), e))
+}
 
-        // Check value
-        let expected_value = expected_map
-            .get(&serde_yaml::Value::String("value".to_string()))
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        if actual.value != expected_value {
-            return ValidationResult {
-                file: file.to_string(),
-                category: category.to_string(),
-                description: test.description.clone(),
-                passed: false,
-                expected: Some(format!("suggestion[{}].value = {}", i, expected_value)),
-                actual: Some(format!("suggestion[{}].value = {}", i, actual.value)),
-                error: Some("Value mismatch".to_string()),
-            };
+/// Locate the file a fixture's `$schema` refers to.
+///
+/// Relative paths resolve against the fixture's directory (then `schema_dir`).
+/// URLs are looked up by their path, then their file name, in `schema_dir`, or
+/// by file name in the nearest `schemas/` directory above the fixture.
+fn resolve_schema(
+    fixture: &Path,
+    schema_ref: &str,
+    schema_dir: Option<&Path>,
+) -> Result<PathBuf, SchemaError> {
+    let fixture_dir = fixture.parent().unwrap_or(Path::new(""));
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if let Some((_, rest)) = schema_ref.split_once("://") {
+        let url_path = rest.split_once('/').map(|(_, p)| p).unwrap_or("");
+        let file_name = url_path.rsplit('/').next().unwrap_or("");
+        if file_name.is_empty() {
+            return Err(SchemaError::Unresolved(format!(
+                "$schema {:?} does not name a schema file",
+                schema_ref
+            )));
         }
+        match schema_dir {
+            Some(dir) => {
+                candidates.push(dir.join(url_path));
+                candidates.push(dir.join(file_name));
+            }
+            None => candidates.extend(
+                fixture_dir
+                    .ancestors()
+                    .map(|dir| dir.join("schemas").join(file_name)),
+            ),
+        }
+    } else {
+        candidates.push(fixture_dir.join(schema_ref));
+        if let Some(dir) = schema_dir {
+            candidates.push(dir.join(schema_ref));
+        }
+    }
 
-        // Check score
-        let expected_score = expected_map
-            .get(&serde_yaml::Value::String("score".to_string()))
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        if (actual.score - expected_score).abs() >= 1e-10 {
-            return ValidationResult {
-                file: file.to_string(),
-                category: category.to_string(),
-                description: test.description.clone(),
-                passed: false,
-                expected: Some(format!("suggestion[{}].score = {}", i, expected_score)),
-                actual: Some(format!("suggestion[{}].score = {}", i, actual.score)),
-                error: Some("Score mismatch".to_string()),
+    candidates
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| {
+            SchemaError::Unresolved(format!(
+                "schema {:?} not found locally{}; schema not checked",
+                schema_ref,
+                schema_dir
+                    .map(|dir| format!(" in {}", dir.display()))
+                    .unwrap_or_default()
+            ))
+        })
+}
+
+/// Validate one fixture file against the schema its `$schema` field names
+fn schema_issues(
+    path: &Path,
+    schema_dir: Option<&Path>,
+    cache: &SchemaCache,
+) -> Result<Vec<SchemaIssue>, SchemaError> {
+    let instance: serde_json::Value = FixtureFormat::from_path(path)
+        .map_err(SchemaError::Fatal)
+        .and_then(|format| {
+            let contents =
+                fs::read_to_string(path).map_err(|e| SchemaError::Unreadable(e.to_string()))?;
+            format
+                .parse(&contents)
+                .map_err(|e| SchemaError::Fatal(e.to_string()))
+        })?;
+
+    let schema_ref = instance
+        .get("$schema")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            SchemaError::Unresolved("no $schema field; schema not checked".to_string())
+        })?;
+    let schema_path = resolve_schema(path, schema_ref, schema_dir)?;
+    let validator = cache.get(&schema_path).map_err(SchemaError::Fatal)?;
+
+    Ok(validator
+        .iter_errors(&instance)
+        .map(|error| {
+            use jsonschema::error::ValidationErrorKind;
+            let message = match error.kind {
+                // The default message dumps the whole case; point at the keyword instead
+                ValidationErrorKind::AnyOf => format!(
+                    "matches none of the allowed field combinations (see schema {})",
+                    error.schema_path
+                ),
+                _ => error.to_string(),
             };
-        }
+            SchemaIssue {
+                pointer: error.instance_path.to_string(),
+                unknown_field: matches!(
+                    error.kind,
+                    ValidationErrorKind::AdditionalProperties { .. }
+                ),
+                message,
+            }
+        })
+        .collect())
+}
 
-        // Check matched_range if present
-        if let Some(expected_range) = expected_map
-            .get(&serde_yaml::Value::String("matched_range".to_string()))
-            .and_then(|v| v.as_mapping())
-        {
-            let exp_start = expected_range
-                .get(&serde_yaml::Value::String("start".to_string()))
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as usize;
-            let exp_end = expected_range
-                .get(&serde_yaml::Value::String("end".to_string()))
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as usize;
+fn schema_check(pattern: &str, schema_dir: Option<&Path>) {
+    println!(
+        "{} fixtures matching: {}",
+        "Schema-checking".cyan(),
+        pattern
+    );
+    println!();
 
-            match &actual.matched_range {
-                Some(range) => {
-                    if range.start != exp_start || range.end != exp_end {
-                        return ValidationResult {
-                            file: file.to_string(),
-                            category: category.to_string(),
-                            description: test.description.clone(),
-                            passed: false,
-                            expected: Some(format!(
-                                "suggestion[{}].matched_range = [{}, {})",
-                                i, exp_start, exp_end
-                            )),
-                            actual: Some(format!(
-                                "suggestion[{}].matched_range = [{}, {})",
-                                i, range.start, range.end
-                            )),
-                            error: Some("Range mismatch".to_string()),
-                        };
-                    }
+    let cache = SchemaCache::default();
+    let (paths, glob_errors) = expand_pattern(pattern);
+    let mut failed_files = glob_errors.len();
+    let mut warnings = 0;
+
+    for path in &paths {
+        match schema_issues(path, schema_dir, &cache) {
+            Ok(issues) if issues.is_empty() => {
+                println!("  {} {}", "✓".green(), path.display());
+            }
+            Ok(issues) => {
+                let errors = issues.iter().filter(|issue| issue.is_error()).count();
+                warnings += issues.len() - errors;
+                if errors > 0 {
+                    failed_files += 1;
+                    println!("  {} {}", "✗".red(), path.display());
+                } else {
+                    println!("  {} {}", "!".yellow(), path.display());
                 }
-                None => {
-                    return ValidationResult {
-                        file: file.to_string(),
-                        category: category.to_string(),
-                        description: test.description.clone(),
-                        passed: false,
-                        expected: Some(format!(
-                            "suggestion[{}].matched_range = [{}, {})",
-                            i, exp_start, exp_end
-                        )),
-                        actual: Some(format!("suggestion[{}].matched_range = None", i)),
-                        error: Some("Expected matched_range but got None".to_string()),
+                for issue in &issues {
+                    let label = if issue.is_error() {
+                        "error".red()
+                    } else {
+                        "warning".yellow()
                     };
+                    println!("      {} {}", label, issue);
                 }
             }
+            // Without a schema there is nothing to check against
+            Err(SchemaError::Unresolved(message))
+            | Err(SchemaError::Fatal(message))
+            | Err(SchemaError::Unreadable(message)) => {
+                failed_files += 1;
+                println!("  {} {}", "✗".red(), path.display());
+                println!("      {} {}", "error".red(), message);
+            }
         }
     }
 
-    ValidationResult {
-        file: file.to_string(),
-        category: category.to_string(),
-        description: test.description.clone(),
-        passed: true,
-        expected: Some(format!("{} suggestions", expected_suggestions.len())),
-        actual: Some(format!("{} suggestions", results.len())),
-        error: None,
+    println!();
+    println!("Files checked:   {}", paths.len());
+    println!("Files failing:   {}", failed_files);
+    println!("Warnings:        {}", warnings);
+
+    if failed_files > 0 {
+        process::exit(1);
     }
 }
 
 // ============================================================================
-// GENERATION MODE
+// INTEGRITY CHECKSUMS
 // ============================================================================
+// A fixture with `checksums: true` has `generate` sign each case: a hash of the
+// category, canonical inputs, expected values and the generator's tool version.
+// `verify-integrity` recomputes them, so an expected value edited by hand shows
+// up as a mismatch. Hashing canonical forms (sorted keys, parsed numbers) keeps
+// checksums stable when the YAML is only reformatted. Opt-in per file so forks
+// with their own fixtures aren't forced into it.
+
+/// Stable checksum of what `generate` vouches for in a case
+fn case_checksum(category: &str, test: &TestCase, tool_version: &str) -> String {
+    let expected: serde_yaml::Mapping = expectation_values(test)
+        .into_iter()
+        .map(|(field, value)| (serde_yaml::Value::String(field), value))
+        .collect();
+    let signed = serde_json::json!({
+        "category": category,
+        "inputs": canonical_inputs(&test.inputs),
+        "expected": canonical_value(&serde_yaml::Value::Mapping(expected)),
+        "tool_version": tool_version,
+    });
+    let canonical = serde_json::to_string(&signed).unwrap_or_default();
+    format!("{:016x}", fnv1a(canonical.as_bytes()))
+}
 
-fn generate_fixture(
-    input_path: &PathBuf,
-    output_path: Option<&std::path::Path>,
-    overwrite: bool,
-    dry_run: bool,
-) {
-    let output_path = output_path.unwrap_or(input_path.as_path());
-
-    println!("{} {}", "Generating fixture:".cyan(), input_path.display());
-
-    // Read fixture
-    let contents = fs::read_to_string(input_path).expect("Failed to read input file");
-    let mut fixture: Fixture = serde_yaml::from_str(&contents).expect("Failed to parse YAML");
-
-    // Generate expected values for each test case
-    let mut total_generated = 0;
-    let mut skipped = 0;
-
+/// Sign a `checksums: true` fixture for `VERSION` before it's written. Cases whose values
+/// generate just computed had their checksum cleared and new cases have none; both are
+/// signed. An existing checksum is carried over only if it still matches under the
+/// previous tool version, so a hand edit stays flagged until the case is regenerated.
+fn sign_cases(fixture: &mut Fixture) {
+    let previous = fixture
+        .generator
+        .as_ref()
+        .map(|generator| generator.tool_version.clone());
     for group in &mut fixture.test_cases {
         for case in &mut group.cases {
-            let generated = generate_test_case(&group.category, case, overwrite);
-            if generated {
-                total_generated += 1;
-            } else {
-                skipped += 1;
+            let intact = match (&case.checksum, &previous) {
+                (None, _) => true,
+                (Some(checksum), Some(version)) => {
+                    *checksum == case_checksum(&group.category, case, version)
+                }
+                (Some(_), None) => false,
+            };
+            if intact {
+                case.checksum = Some(case_checksum(&group.category, case, VERSION));
             }
         }
     }
+}
 
-    // Update generator metadata
-    fixture.generator = Some(GeneratorMetadata {
-        tool: "similarity-validator".to_string(),
-        tool_version: VERSION.to_string(),
-        source_library: "rapidfuzz-rs".to_string(),
-        source_version: "3.0".to_string(),
-        generated_at: Utc::now().to_rfc3339(),
-        command: Some(format!(
-            "similarity-validator generate --input {} {}",
-            input_path.display(),
-            if overwrite { "--overwrite" } else { "" }
-        )),
-    });
-
-    // Update notes
-    fixture.notes = Some(format!(
-        "GENERATED FILE - DO NOT EDIT EXPECTED VALUES BY HAND\n\n\
-         This fixture was generated using similarity-validator with rapidfuzz-rs as the\n\
-         canonical source. Values are authoritative and ready for cross-language validation.\n\n\
-         Reference implementations:\n\
-         - Canonical: rapidfuzz-rs 3.x (Rust, used for generation)\n\
-         - Cross-check: rapidfuzz 3.x (Python, pyfulmen)\n\
-         - Under test: string-metrics-wasm (TypeScript/WASM)\n\n\
-         To regenerate:\n\
-           similarity-validator generate --input {} --overwrite",
-        input_path.display()
-    ));
+/// A signed case whose checksum is missing or no longer matches
+struct IntegrityIssue {
+    file: String,
+    category: String,
+    description: String,
+    /// `None` when the case has no checksum
+    recorded: Option<String>,
+}
 
+fn verify_integrity(pattern: &str) {
     println!(
-        "Generated: {} | Skipped: {}",
-        total_generated.to_string().green(),
-        skipped.to_string().yellow()
+        "{} fixtures matching: {}",
+        "Verifying checksums of".cyan(),
+        pattern
     );
+    println!();
 
-    if dry_run {
-        println!("{}", "\n[DRY RUN - No files written]".yellow());
-        println!("\nGenerated YAML preview:");
-        println!("{}", "=".repeat(80));
-        let yaml = serde_yaml::to_string(&fixture).expect("Failed to serialize YAML");
-        println!("{}", yaml);
-    } else {
-        // Write output
-        let yaml = serde_yaml::to_string(&fixture).expect("Failed to serialize YAML");
-        fs::write(output_path, yaml).expect("Failed to write output file");
-        println!("{} {}", "✅ Written:".green(), output_path.display());
-    }
-}
-
-fn generate_test_case(category: &str, case: &mut TestCase, overwrite: bool) -> bool {
-    match category {
-        "levenshtein" => generate_levenshtein(case, overwrite),
-        "damerau_osa" => generate_damerau_osa(case, overwrite),
-        "damerau_unrestricted" => generate_damerau_unrestricted(case, overwrite),
-        "jaro_winkler" => generate_jaro_winkler(case, overwrite),
-        "indel" => generate_indel(case, overwrite),
-        "lcs_seq" => generate_lcs_seq(case, overwrite),
-        "ratio" => generate_ratio(case, overwrite),
-        "substring" => generate_substring(case, overwrite),
-        "normalization_presets" => generate_normalization(case, overwrite),
-        "normalization_locale" => generate_normalization_locale(case, overwrite),
-        "suggestions" => generate_suggestions(case, overwrite),
-        "unified_distance" => generate_unified_distance(case, overwrite),
-        "unified_score" => generate_unified_score(case, overwrite),
-        // TypeScript-only categories - skip generation
-        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" | "extract_one" | "extract" => {
-            false
+    let (paths, glob_errors) = expand_pattern(pattern);
+    let mut file_errors = glob_errors;
+    let mut issues: Vec<IntegrityIssue> = Vec::new();
+    let mut signed_files = 0;
+    let mut checked = 0;
+
+    for path in &paths {
+        let fixture = match load_fixture(path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                file_errors.push((path.display().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        if !fixture.checksums {
+            continue;
         }
-        _ => {
-            eprintln!("⚠️  Unknown category: {}", category);
-            false
+        let file = path.display().to_string();
+        let Some(generator) = &fixture.generator else {
+            file_errors.push((
+                file,
+                "checksums: true but no generator metadata; run generate".to_string(),
+            ));
+            continue;
+        };
+        signed_files += 1;
+        for group in &fixture.test_cases {
+            for test in &group.cases {
+                checked += 1;
+                let intact = test.checksum.as_ref().is_some_and(|checksum| {
+                    *checksum == case_checksum(&group.category, test, &generator.tool_version)
+                });
+                if !intact {
+                    issues.push(IntegrityIssue {
+                        file: file.clone(),
+                        category: group.category.clone(),
+                        description: test.description.clone(),
+                        recorded: test.checksum.clone(),
+                    });
+                }
+            }
         }
     }
-}
 
-fn generate_levenshtein(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
-        return false;
+    for (file, error) in &file_errors {
+        println!("  {} {}: {}", "✗".red(), file, error);
     }
-
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-
-    let distance = rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars());
-    let score =
-        rapidfuzz::distance::levenshtein::normalized_similarity(input_a.chars(), input_b.chars());
-
-    case.expected_distance = Some(distance);
-    case.expected_score = Some(score);
-    true
-}
-
-fn generate_damerau_osa(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
-        return false;
+    if !issues.is_empty() {
+        println!("{}", "CHANGED WITHOUT REGENERATION:".red().bold());
+        for issue in &issues {
+            println!(
+                "  {} [{}] {} ({})",
+                "✗".red(),
+                issue.category,
+                issue.description,
+                issue.file
+            );
+            match &issue.recorded {
+                Some(checksum) => println!(
+                    "      checksum {} no longer matches its inputs and expected values",
+                    checksum
+                ),
+                None => println!("      no checksum"),
+            }
+        }
+        println!();
+        println!("Regenerate the affected files (generate --overwrite) instead of editing values by hand.");
     }
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-
-    let distance = rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars());
-    let score = rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars());
+    println!();
+    println!("Files checked:   {}", paths.len());
+    println!("Files signed:    {}", signed_files);
+    println!("Cases checked:   {}", checked);
+    println!("Cases changed:   {}", issues.len());
 
-    case.expected_distance = Some(distance);
-    case.expected_score = Some(score);
-    true
+    if !issues.is_empty() || !file_errors.is_empty() {
+        process::exit(1);
+    }
 }
 
-fn generate_damerau_unrestricted(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
-        return false;
-    }
+// ============================================================================
+// VERIFY-FRESH MODE
+// ============================================================================
+// Read-only twin of `generate --overwrite`: recompute every value the
+// generator would write today and report anything that would change.
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+/// A recorded expected value that differs from what generation produces now
+struct StaleValue {
+    file: String,
+    category: String,
+    description: String,
+    field: String,
+    recorded: Option<serde_yaml::Value>,
+    fresh: Option<serde_yaml::Value>,
+}
 
-    let distance =
-        rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars());
-    let score = rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
-        input_a.chars(),
-        input_b.chars(),
+fn verify_fresh(pattern: &str, epsilon: Option<f64>) {
+    println!(
+        "{} fixtures matching: {} (rapidfuzz-rs {})",
+        "Verifying freshness of".cyan(),
+        pattern,
+        RAPIDFUZZ_VERSION
     );
+    println!();
 
-    case.expected_distance = Some(distance);
-    case.expected_score = Some(score);
-    true
-}
+    let (paths, glob_errors) = expand_pattern(pattern);
+    let mut file_errors = glob_errors;
+    let mut stale_values: Vec<StaleValue> = Vec::new();
+    let mut stale_versions: Vec<(String, String)> = Vec::new();
+    let mut checked = 0;
+
+    for path in &paths {
+        let fixture = match load_fixture(path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                file_errors.push((path.display().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        let file = path.display().to_string();
 
-fn generate_jaro_winkler(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_score.is_some() {
-        return false;
+        // Hand-written fixtures have no generator block and nothing to compare
+        if let Some(generator) = &fixture.generator {
+            if generator.source_version != RAPIDFUZZ_VERSION {
+                stale_versions.push((file.clone(), generator.source_version.clone()));
+            }
+        }
+
+        let file_tolerance = fixture.tolerance.or(epsilon).unwrap_or(DEFAULT_TOLERANCE);
+        for group in &fixture.test_cases {
+            for test in &group.cases {
+                let mut fresh = test.clone();
+                if test.skip || !generate_test_case(&group.category, &mut fresh, true) {
+                    continue;
+                }
+                checked += 1;
+                let tol = test.tolerance.unwrap_or(file_tolerance);
+                let fresh_values = expectation_values(&fresh);
+                // Only values the fixture records are compared; fields it never
+                // carried (e.g. pending cases) are not staleness
+                for (field, recorded) in expectation_values(test) {
+                    let current = fresh_values.get(&field);
+                    let matches = current.is_some_and(|v| yaml_values_match(&recorded, v, tol));
+                    if !matches {
+                        stale_values.push(StaleValue {
+                            file: file.clone(),
+                            category: group.category.clone(),
+                            description: test.description.clone(),
+                            field,
+                            recorded: Some(recorded),
+                            fresh: current.cloned(),
+                        });
+                    }
+                }
+            }
+        }
     }
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+    let show = |value: &Option<serde_yaml::Value>| match value {
+        Some(v) => serde_json::to_string(v).unwrap_or_default(),
+        None => "(absent)".to_string(),
+    };
+    for (file, error) in &file_errors {
+        println!("  {} {}: {}", "✗".red(), file, error);
+    }
+    if !stale_versions.is_empty() {
+        println!("{}", "STALE SOURCE VERSION:".yellow().bold());
+        for (file, version) in &stale_versions {
+            println!(
+                "  {} {} (generated with {}, linked {})",
+                "~".yellow(),
+                file,
+                version,
+                RAPIDFUZZ_VERSION
+            );
+        }
+        println!();
+    }
+    if !stale_values.is_empty() {
+        println!("{}", "STALE VALUES:".red().bold());
+        for stale in &stale_values {
+            println!(
+                "  {} [{}] {} ({})",
+                "✗".red(),
+                stale.category,
+                stale.description,
+                stale.file
+            );
+            println!(
+                "      {}: recorded {} → fresh {}",
+                stale.field,
+                show(&stale.recorded),
+                show(&stale.fresh)
+            );
+        }
+        println!();
+    }
 
-    let score = rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars());
+    println!("Files checked:   {}", paths.len());
+    println!("Cases checked:   {}", checked);
+    println!("Stale values:    {}", stale_values.len());
+    println!("Stale versions:  {}", stale_versions.len());
 
-    case.expected_score = Some(score);
-    true
+    if !stale_values.is_empty() || !stale_versions.is_empty() || !file_errors.is_empty() {
+        process::exit(1);
+    }
+    println!("{}", "All fixtures are fresh.".green().bold());
 }
 
-fn generate_indel(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
-        return false;
+/// Structural equality where numbers may differ by `tol` (nested suggestion scores included)
+fn yaml_values_match(a: &serde_yaml::Value, b: &serde_yaml::Value, tol: f64) -> bool {
+    use serde_yaml::Value;
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) if x.is_f64() || y.is_f64() => {
+            match (x.as_f64(), y.as_f64()) {
+                (Some(x), Some(y)) => scores_match(x, y, tol),
+                _ => false,
+            }
+        }
+        (Value::Sequence(xs), Value::Sequence(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| yaml_values_match(x, y, tol))
+        }
+        (Value::Mapping(xs), Value::Mapping(ys)) => {
+            xs.len() == ys.len()
+                && xs
+                    .iter()
+                    .all(|(k, x)| ys.get(k).is_some_and(|y| yaml_values_match(x, y, tol)))
+        }
+        _ => a == b,
     }
+}
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
+// ============================================================================
+// CROSS-CHECK MODE
+// ============================================================================
+// Three-way comparison of fixture, rapidfuzz-rs and an external reference.
+// The reference runs as one long-lived child: each case is one JSON line on
+// its stdin, answered by one JSON line on its stdout:
+//
+//   -> {"id": 3, "category": "levenshtein", "inputs": {"input_a": "kitten", "input_b": "sitting"}}
+//   <- {"id": 3, "distance": 3, "score": 0.5714285714285714}
+//
+// Scores are normalized to 0-1 (Python's fuzz.ratio must be divided by 100).
+// A response may carry "error" instead; "id" is optional but checked if present.
+
+/// Consecutive timeouts or exits before the run is abandoned
+const MAX_REFERENCE_FAILURES: usize = 3;
+
+/// Distance and/or normalized score for one case, from any of the three sources
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+struct ReferenceValues {
+    distance: Option<usize>,
+    score: Option<f64>,
+}
 
-    let distance = rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars());
-    let score = rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars());
+impl ReferenceValues {
+    fn agrees_with(&self, other: &ReferenceValues, tol: f64) -> bool {
+        let distance_agrees = match (self.distance, other.distance) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        let score_agrees = match (self.score, other.score) {
+            (Some(a), Some(b)) => scores_match(a, b, tol),
+            _ => true,
+        };
+        distance_agrees && score_agrees
+    }
 
-    case.expected_distance = Some(distance);
-    case.expected_score = Some(score);
-    true
+    fn is_empty(&self) -> bool {
+        self.distance.is_none() && self.score.is_none()
+    }
 }
 
-fn generate_lcs_seq(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() && case.expected_score.is_some() {
-        return false;
+impl std::fmt::Display for ReferenceValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.distance, self.score) {
+            (Some(d), Some(s)) => write!(f, "distance={}, score={}", d, s),
+            (Some(d), None) => write!(f, "distance={}", d),
+            (None, Some(s)) => write!(f, "score={}", s),
+            (None, None) => write!(f, "-"),
+        }
     }
+}
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-
-    let distance = rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars());
-    let score =
-        rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars());
+#[derive(Debug, Deserialize)]
+struct ReferenceResponse {
+    id: Option<u64>,
+    #[serde(flatten)]
+    values: ReferenceValues,
+    error: Option<String>,
+}
 
-    case.expected_distance = Some(distance);
-    case.expected_score = Some(score);
-    true
+/// Why a case got no usable answer from the reference command
+enum ReferenceError {
+    Timeout,
+    Exited,
+    Protocol(String),
 }
 
-fn generate_ratio(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_score.is_some() {
-        return false;
+impl std::fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReferenceError::Timeout => write!(f, "timed out (process killed)"),
+            ReferenceError::Exited => write!(f, "exited"),
+            ReferenceError::Protocol(message) => write!(f, "{}", message),
+        }
     }
+}
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-
-    let score = rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars());
-
-    case.expected_score = Some(score);
-    true
+struct ReferenceProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: mpsc::Receiver<std::io::Result<String>>,
 }
 
-fn generate_unified_distance(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_distance.is_some() {
-        return false;
+impl ReferenceProcess {
+    fn spawn(command: &str) -> std::io::Result<Self> {
+        let mut child = if cfg!(windows) {
+            Command::new("cmd")
+                .args(["/C", command])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()?
+        } else {
+            Command::new("sh")
+                .args(["-c", command])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()?
+        };
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        // A reader thread lets the main thread wait on responses with a timeout
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(ReferenceProcess {
+            child,
+            stdin,
+            lines,
+        })
     }
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-    let metric =
-        get_string_input(&case.inputs, "metric").unwrap_or_else(|| "levenshtein".to_string());
-
-    let distance = match metric.as_str() {
-        "levenshtein" => {
-            rapidfuzz::distance::levenshtein::distance(input_a.chars(), input_b.chars())
-        }
-        "damerau_levenshtein" => {
-            rapidfuzz::distance::damerau_levenshtein::distance(input_a.chars(), input_b.chars())
+    fn query(&mut self, request: &str, timeout: Duration) -> Result<String, ReferenceError> {
+        if writeln!(self.stdin, "{}", request)
+            .and_then(|_| self.stdin.flush())
+            .is_err()
+        {
+            return Err(ReferenceError::Exited);
         }
-        "osa" => rapidfuzz::distance::osa::distance(input_a.chars(), input_b.chars()),
-        "indel" => rapidfuzz::distance::indel::distance(input_a.chars(), input_b.chars()),
-        "lcs_seq" => rapidfuzz::distance::lcs_seq::distance(input_a.chars(), input_b.chars()),
-        _ => {
-            eprintln!("⚠️  Unknown distance metric: {}", metric);
-            return false;
+        match self.lines.recv_timeout(timeout) {
+            Ok(Ok(line)) => Ok(line),
+            Ok(Err(e)) => Err(ReferenceError::Protocol(format!(
+                "unreadable response: {}",
+                e
+            ))),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(ReferenceError::Timeout),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(ReferenceError::Exited),
         }
-    };
-
-    case.expected_distance = Some(distance);
-    true
-}
-
-fn generate_unified_score(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_score.is_some() {
-        return false;
     }
 
-    let input_a = get_string_input(&case.inputs, "input_a").unwrap_or_default();
-    let input_b = get_string_input(&case.inputs, "input_b").unwrap_or_default();
-    let metric =
-        get_string_input(&case.inputs, "metric").unwrap_or_else(|| "jaro_winkler".to_string());
+    fn shutdown(mut self) {
+        // Closing stdin is the reference's cue to exit; kill covers stragglers
+        drop(self.stdin);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
 
-    let score = match metric.as_str() {
-        "levenshtein" => rapidfuzz::distance::levenshtein::normalized_similarity(
-            input_a.chars(),
-            input_b.chars(),
-        ),
-        "damerau_levenshtein" => rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
-            input_a.chars(),
-            input_b.chars(),
-        ),
-        "osa" => rapidfuzz::distance::osa::normalized_similarity(input_a.chars(), input_b.chars()),
-        "jaro" => rapidfuzz::distance::jaro::similarity(input_a.chars(), input_b.chars()),
-        "jaro_winkler" => {
-            rapidfuzz::distance::jaro_winkler::similarity(input_a.chars(), input_b.chars())
-        }
-        "indel" => {
-            rapidfuzz::distance::indel::normalized_similarity(input_a.chars(), input_b.chars())
-        }
-        "lcs_seq" => {
-            rapidfuzz::distance::lcs_seq::normalized_similarity(input_a.chars(), input_b.chars())
-        }
-        "ratio" => rapidfuzz::fuzz::ratio(input_a.chars(), input_b.chars()) / 100.0,
-        "partial_ratio" | "token_sort_ratio" | "token_set_ratio" => {
-            // TypeScript-only - skip generation
-            return false;
-        }
-        _ => {
-            eprintln!("⚠️  Unknown score metric: {}", metric);
-            return false;
-        }
+/// rapidfuzz-rs values for categories the reference can compute (pairwise metrics)
+fn rust_reference_values(
+    category: &str,
+    test: &TestCase,
+) -> Option<Result<ReferenceValues, String>> {
+    let a = get_string_input(&test.inputs, "input_a").unwrap_or_default();
+    let b = get_string_input(&test.inputs, "input_b").unwrap_or_default();
+    let metric = get_string_input(&test.inputs, "metric");
+    let both = |metric: &str| {
+        Ok(ReferenceValues {
+            distance: string_metrics_core::unified_distance(&a, &b, metric).ok(),
+            score: string_metrics_core::unified_score(&a, &b, metric).ok(),
+        })
     };
 
-    case.expected_score = Some(score);
-    true
+    Some(match category {
+        "levenshtein" | "indel" | "lcs_seq" => both(category),
+        "damerau_osa" => both("osa"),
+        "damerau_unrestricted" => both("damerau_levenshtein"),
+        "jaro_winkler" | "ratio" => both(category),
+        "unified_distance" => string_metrics_core::unified_distance(
+            &a,
+            &b,
+            metric.as_deref().unwrap_or("levenshtein"),
+        )
+        .map(|distance| ReferenceValues {
+            distance: Some(distance),
+            score: None,
+        }),
+        "unified_score" => {
+            let metric = metric.unwrap_or_else(|| "jaro_winkler".to_string());
+            if matches!(
+                metric.as_str(),
+                "partial_ratio" | "token_sort_ratio" | "token_set_ratio"
+            ) {
+                return None;
+            }
+            string_metrics_core::unified_score(&a, &b, &metric).map(|score| ReferenceValues {
+                distance: None,
+                score: Some(score),
+            })
+        }
+        _ => return None,
+    })
 }
 
-fn generate_substring(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected_score.is_some() && case.expected_range.is_some() {
-        return false;
+/// Values the fixture records, in the same distance/0-1 score terms
+fn fixture_reference_values(category: &str, test: &TestCase) -> ReferenceValues {
+    let expected = test.expected.as_ref();
+    ReferenceValues {
+        distance: test.expected_distance.or_else(|| {
+            (category == "unified_distance")
+                .then(|| expected.and_then(|v| v.as_u64()).map(|d| d as usize))
+                .flatten()
+        }),
+        score: test.expected_score.or_else(|| match category {
+            "unified_score" => expected.and_then(|v| v.as_f64()),
+            "ratio" => test
+                .inputs
+                .get("expected_ratio")
+                .and_then(|v| v.as_f64())
+                .map(|ratio| ratio / 100.0),
+            _ => None,
+        }),
     }
+}
 
-    let needle = get_string_input(&case.inputs, "needle").unwrap_or_default();
-    let haystack = get_string_input(&case.inputs, "haystack").unwrap_or_default();
+struct CrossCheckRow {
+    file: String,
+    category: String,
+    description: String,
+    fixture: ReferenceValues,
+    rust: ReferenceValues,
+    external: Result<ReferenceValues, String>,
+}
 
-    let (score, range) = compute_substring_similarity(&needle, &haystack);
+fn cross_check(pattern: &str, command: &str, timeout: Duration, epsilon: Option<f64>) {
+    println!(
+        "{} {} against: {}",
+        "Cross-checking".cyan(),
+        pattern,
+        command
+    );
+    println!();
 
-    case.expected_score = Some(score);
-    case.expected_range = range;
-    true
-}
+    let spawn = || {
+        ReferenceProcess::spawn(command).unwrap_or_else(|e| {
+            eprintln!("{}: cannot start {:?}: {}", "Error".red(), command, e);
+            process::exit(1);
+        })
+    };
+    let (paths, glob_errors) = expand_pattern(pattern);
+    let mut file_errors = glob_errors;
+    let mut reference: Option<ReferenceProcess> = None;
+    let mut disagreements: Vec<CrossCheckRow> = Vec::new();
+    let (mut compared, mut skipped, mut next_id) = (0, 0, 0u64);
+    let mut consecutive_failures = 0;
+
+    for path in &paths {
+        let fixture = match load_fixture(path) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                file_errors.push((path.display().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        let file_tolerance = fixture.tolerance.or(epsilon).unwrap_or(DEFAULT_TOLERANCE);
 
-fn generate_normalization(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected.is_some() {
-        return false;
+        for group in &fixture.test_cases {
+            for test in &group.cases {
+                if test.expect_error.is_some() {
+                    skipped += 1;
+                    continue;
+                }
+                let Some(rust) = rust_reference_values(&group.category, test) else {
+                    skipped += 1;
+                    continue;
+                };
+                compared += 1;
+                next_id += 1;
+                let tol = test.tolerance.unwrap_or(file_tolerance);
+
+                let rust = rust.unwrap_or_default();
+                let fixture_values = fixture_reference_values(&group.category, test);
+                let request = serde_json::json!({
+                    "id": next_id,
+                    "category": group.category,
+                    "inputs": serde_json::to_value(&test.inputs).unwrap_or_default(),
+                })
+                .to_string();
+
+                let process = reference.get_or_insert_with(spawn);
+                let response = process.query(&request, timeout);
+                consecutive_failures = if response.is_ok() {
+                    0
+                } else {
+                    consecutive_failures + 1
+                };
+                let external = match response {
+                    Ok(line) => match serde_json::from_str::<ReferenceResponse>(&line) {
+                        Ok(response) if response.id.is_some_and(|id| id != next_id) => {
+                            Err(format!(
+                                "response id {} does not match request id {}",
+                                response.id.unwrap_or_default(),
+                                next_id
+                            ))
+                        }
+                        Ok(ReferenceResponse {
+                            error: Some(error), ..
+                        }) => Err(error),
+                        Ok(response) if response.values.is_empty() => {
+                            Err("response has neither distance nor score".to_string())
+                        }
+                        Ok(response) => Ok(response.values),
+                        Err(e) => Err(format!("invalid response {:?}: {}", line, e)),
+                    },
+                    Err(e) => {
+                        // Hung or dead references are replaced for the next case,
+                        // unless replacements keep failing the same way
+                        if let Some(process) = reference.take() {
+                            process.shutdown();
+                        }
+                        if consecutive_failures >= MAX_REFERENCE_FAILURES {
+                            eprintln!(
+                                "{}: reference command {} for {} cases in a row; giving up",
+                                "Error".red(),
+                                e,
+                                consecutive_failures
+                            );
+                            process::exit(1);
+                        }
+                        Err(e.to_string())
+                    }
+                };
+
+                let agrees = match &external {
+                    Ok(external) => {
+                        external.agrees_with(&rust, tol) && fixture_values.agrees_with(&rust, tol)
+                    }
+                    Err(_) => false,
+                };
+                if !agrees {
+                    disagreements.push(CrossCheckRow {
+                        file: path.display().to_string(),
+                        category: group.category.clone(),
+                        description: test.description.clone(),
+                        fixture: fixture_values,
+                        rust,
+                        external,
+                    });
+                }
+            }
+        }
+    }
+    if let Some(process) = reference {
+        process.shutdown();
     }
 
-    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
-    let preset = get_string_input(&case.inputs, "preset").unwrap_or_default();
+    for row in &disagreements {
+        println!(
+            "{} {} [{}] {}",
+            "✗".red(),
+            row.file,
+            row.category,
+            row.description
+        );
+        println!("    fixture:  {}", row.fixture);
+        println!("    rust:     {}", row.rust);
+        match &row.external {
+            Ok(values) => println!("    external: {}", values),
+            Err(e) => println!("    external: {} {}", "error:".red(), e),
+        }
+    }
+    for (file, error) in &file_errors {
+        println!("{} {}: {}", "✗".red(), file, error);
+    }
 
-    let normalized = normalize(&input, &preset);
+    println!();
+    println!("{}", "=".repeat(80));
+    println!("{}", "SUMMARY".bold());
+    println!("{}", "=".repeat(80));
+    println!("Files processed: {}", paths.len());
+    println!("File errors:     {}", file_errors.len());
+    println!("Cases compared:  {}", compared);
+    println!(
+        "Disagreements:   {}",
+        if disagreements.is_empty() {
+            "0".green()
+        } else {
+            disagreements.len().to_string().red()
+        }
+    );
+    println!(
+        "Skipped:         {} (negative cases or no external equivalent)",
+        skipped
+    );
+    println!();
 
-    case.expected = Some(serde_yaml::Value::String(normalized));
-    true
+    if disagreements.is_empty() && file_errors.is_empty() {
+        println!(
+            "{}",
+            "Fixture, rapidfuzz-rs and reference agree.".green().bold()
+        );
+    } else {
+        process::exit(1);
+    }
+}
+
+// ============================================================================
+// WASM PARITY
+// ============================================================================
+//
+// `validate --target wasm` computes cases through the built wasm-bindgen
+// module instead of the native crates, to catch bugs that only show up across
+// the bindings: Option and string marshalling, u32 results, JsError messages.
+// The exports need their generated JS glue, so the module runs under node
+// (scripts/wasm_runner.mjs), driven over the same line-delimited JSON pipe as
+// cross-check. Each supported category calls the exports the TypeScript
+// wrapper builds on; categories computed in the wrapper itself (suggestions,
+// the token ratios) have nothing to call and are filtered out of the run.
+
+const DEFAULT_NODE_RUNNER: &str = "node similarity-validator/scripts/wasm_runner.mjs pkg/web";
+
+/// Where `validate` computes cases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum ValidateTarget {
+    /// The native string-metrics-core and rapidfuzz-rs crates
+    #[default]
+    Native,
+    /// The built wasm module, through `--node-runner`
+    Wasm,
 }
 
-fn generate_normalization_locale(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected.is_some() {
-        return false;
+/// Pairwise categories as (category, distance export, 0-1 score export)
+const WASM_PAIR_EXPORTS: &[(&str, Option<&str>, &str)] = &[
+    ("levenshtein", Some("levenshtein"), "normalized_levenshtein"),
+    (
+        "damerau_osa",
+        Some("osa_distance"),
+        "normalized_osa_similarity",
+    ),
+    (
+        "damerau_unrestricted",
+        Some("damerau_levenshtein"),
+        "normalized_damerau_levenshtein",
+    ),
+    (
+        "indel",
+        Some("indel_distance"),
+        "indel_normalized_similarity",
+    ),
+    ("jaro_winkler", None, "jaro_winkler"),
+];
+
+/// Whether `--target wasm` can compute a case. The normalization exports fall back to the
+/// input on an unknown preset rather than throwing, so expected-error cases are left out.
+fn wasm_supports(category: &str, test: &TestCase) -> bool {
+    match category {
+        "unified_score" => !matches!(
+            get_string_input(&test.inputs, "metric").as_deref(),
+            Some("partial_ratio" | "token_sort_ratio" | "token_set_ratio")
+        ),
+        "unified_distance" | "hamming" => true,
+        "normalization_presets" | "normalization_locale" => test.expect_error.is_none(),
+        _ => WASM_PAIR_EXPORTS.iter().any(|(name, ..)| *name == category),
     }
+}
 
-    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
-    let preset = get_string_input(&case.inputs, "preset").unwrap_or_default();
-    let locale = case
-        .inputs
-        .get("locale")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+/// The node process serving the wasm module: started on first use, restarted after a failure.
+/// Calls from the validation workers take turns.
+struct WasmRunner {
+    command: String,
+    timeout: Duration,
+    /// The running process, if any, and the last request id
+    process: Mutex<(Option<ReferenceProcess>, u64)>,
+}
 
-    let normalized = normalize_with_locale(&input, &preset, locale.as_deref());
+impl std::fmt::Debug for WasmRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmRunner")
+            .field("command", &self.command)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
 
-    case.expected = Some(serde_yaml::Value::String(normalized));
-    true
+#[derive(Debug, Deserialize)]
+struct WasmResponse {
+    id: Option<u64>,
+    #[serde(default)]
+    value: serde_json::Value,
+    error: Option<String>,
 }
 
-fn generate_suggestions(case: &mut TestCase, overwrite: bool) -> bool {
-    if !overwrite && case.expected.is_some() {
-        return false;
+impl WasmRunner {
+    fn new(command: String, timeout: Duration) -> Self {
+        WasmRunner {
+            command,
+            timeout,
+            process: Mutex::new((None, 0)),
+        }
     }
 
-    // Extract inputs
-    let input = get_string_input(&case.inputs, "input").unwrap_or_default();
-    let candidates = case
-        .inputs
-        .get("candidates")
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_default();
+    /// Call the export `name` with `args`; a thrown error comes back as Err with its message
+    fn call(&self, name: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.request(serde_json::json!({ "call": name, "args": args }))
+    }
 
-    // Extract options
-    let options = case
-        .inputs
-        .get("options")
-        .and_then(|v| v.as_mapping())
-        .cloned()
-        .unwrap_or_default();
+    /// Construct the exported `class` from `new` and call its `method` with `args`
+    fn call_method(
+        &self,
+        class: &str,
+        new: serde_json::Value,
+        method: &str,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.request(
+            serde_json::json!({ "class": class, "new": new, "call": method, "args": args }),
+        )
+    }
 
-    let min_score = options
-        .get(&serde_yaml::Value::String("min_score".to_string()))
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.6);
+    fn request(&self, mut request: serde_json::Value) -> Result<serde_json::Value, String> {
+        let mut state = self.process.lock().unwrap_or_else(|e| e.into_inner());
+        let (process, last_id) = &mut *state;
+        *last_id += 1;
+        let id = *last_id;
+        request["id"] = id.into();
+
+        if process.is_none() {
+            let spawned = ReferenceProcess::spawn(&self.command)
+                .map_err(|e| format!("cannot start wasm runner ({}): {}", self.command, e))?;
+            *process = Some(spawned);
+        }
+        let running = process.as_mut().expect("started above");
+        let line = match running.query(&request.to_string(), self.timeout) {
+            Ok(line) => line,
+            Err(e) => {
+                if let Some(failed) = process.take() {
+                    failed.shutdown();
+                }
+                return Err(format!("wasm runner {} ({})", e, self.command));
+            }
+        };
+        let response: WasmResponse = serde_json::from_str(&line)
+            .map_err(|e| format!("invalid wasm runner response {:?}: {}", line, e))?;
+        if response.id.is_some_and(|got| got != id) {
+            return Err(format!(
+                "wasm runner answered request {:?}, expected {}",
+                response.id, id
+            ));
+        }
+        match response.error {
+            Some(error) => Err(error),
+            None => Ok(response.value),
+        }
+    }
+}
 
-    let max_suggestions = options
-        .get(&serde_yaml::Value::String("max_suggestions".to_string()))
-        .and_then(|v| v.as_u64())
-        .unwrap_or(3) as usize;
+/// A case's values as computed by the wasm exports
+enum WasmOutput {
+    Pair { distance: Option<usize>, score: f64 },
+    Distance(usize),
+    Score(f64),
+    Text(String),
+}
 
-    let metric = options
-        .get(&serde_yaml::Value::String("metric".to_string()))
-        .and_then(|v| v.as_str())
-        .unwrap_or("levenshtein");
+impl std::fmt::Display for WasmOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmOutput::Pair {
+                distance: Some(distance),
+                score,
+            } => write!(f, "distance={}, score={}", distance, score),
+            WasmOutput::Pair {
+                distance: None,
+                score,
+            }
+            | WasmOutput::Score(score) => write!(f, "score={}", score),
+            WasmOutput::Distance(distance) => write!(f, "distance={}", distance),
+            WasmOutput::Text(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
 
-    let normalize_preset = options
-        .get(&serde_yaml::Value::String("normalize_preset".to_string()))
-        .and_then(|v| v.as_str())
-        .unwrap_or("default");
+fn wasm_distance(value: serde_json::Value) -> Result<usize, String> {
+    value
+        .as_u64()
+        .map(|d| d as usize)
+        .ok_or_else(|| format!("wasm module returned {} for a distance", value))
+}
 
-    let prefer_prefix = options
-        .get(&serde_yaml::Value::String("prefer_prefix".to_string()))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+fn wasm_score(value: serde_json::Value) -> Result<f64, String> {
+    value
+        .as_f64()
+        .ok_or_else(|| format!("wasm module returned {} for a score", value))
+}
 
-    // Normalize input
-    let normalized_input = normalize(&input, normalize_preset);
+fn wasm_text(value: serde_json::Value) -> Result<String, String> {
+    match value {
+        serde_json::Value::String(text) => Ok(text),
+        other => Err(format!("wasm module returned {} for a string", other)),
+    }
+}
 
-    // Compute scores for each candidate with original index for stable sorting
-    let mut results: Vec<(usize, SuggestionResult)> = candidates
-        .iter()
-        .enumerate()
-        .map(|(idx, candidate)| {
-            let normalized_candidate = normalize(candidate, normalize_preset);
-            let (mut score, matched_range) =
-                compute_score_for_metric(&normalized_input, &normalized_candidate, metric);
+/// A unified case through the wasm exports: `distance` or `score` of a `Comparator` built
+/// from the case's `options` (no preset without them), or the digraph exports for `digraphs`
+fn wasm_unified(
+    wasm: &WasmRunner,
+    inputs: &serde_yaml::Mapping,
+    method: &str,
+    default_metric: &str,
+) -> Result<serde_json::Value, String> {
+    let input_a = get_string_input(inputs, "input_a").unwrap_or_default();
+    let input_b = get_string_input(inputs, "input_b").unwrap_or_default();
+    let metric = get_string_input(inputs, "metric").unwrap_or_else(|| default_metric.to_string());
+    let digraphs: Vec<&str> = inputs
+        .get("digraphs")
+        .and_then(|v| v.as_sequence())
+        .map(|units| units.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let options = unified_options(inputs)?;
+    if options.is_some() && inputs.contains_key("digraphs") {
+        return Err("Unified options can't be combined with digraphs".to_string());
+    }
+    if options.is_none() && !digraphs.is_empty() {
+        return wasm.call(
+            &format!("{}_with_digraphs", method),
+            serde_json::json!([input_a, input_b, metric, digraphs]),
+        );
+    }
+    let options = options.unwrap_or_default();
+    wasm.call_method(
+        "Comparator",
+        serde_json::json!([
+            metric,
+            options.preset,
+            options.locale,
+            options.score_cutoff.unwrap_or(-1.0)
+        ]),
+        method,
+        serde_json::json!([input_a, input_b]),
+    )
+}
 
-            // Apply prefix bonus if enabled
-            // Formula: finalScore = min(1.0, score + (1 - score) * 0.1)
-            if prefer_prefix && normalized_candidate.starts_with(&normalized_input) {
-                let bonus_weight = 0.1;
-                score = (score + (1.0 - score) * bonus_weight).min(1.0);
-            }
+/// Compute a case through the wasm exports its category maps to
+fn wasm_output(category: &str, test: &TestCase, wasm: &WasmRunner) -> Result<WasmOutput, String> {
+    let inputs = &test.inputs;
+    match category {
+        "unified_distance" => wasm_unified(wasm, inputs, "distance", "levenshtein")
+            .and_then(wasm_distance)
+            .map(WasmOutput::Distance),
+        "unified_score" => wasm_unified(wasm, inputs, "score", "jaro_winkler")
+            .and_then(wasm_score)
+            .map(WasmOutput::Score),
+        "normalization_presets" => {
+            let locale = get_string_input(inputs, "locale");
+            let options = normalize_options(inputs, locale.as_deref());
+            let args = serde_json::json!([
+                get_string_input(inputs, "input").unwrap_or_default(),
+                get_string_input(inputs, "preset").unwrap_or_default(),
+                options.locale,
+                options.strip_ignorables,
+                options.keep_joiners,
+                options.fold_numbers,
+                []
+            ]);
+            wasm.call("normalize_with_options", args)
+                .and_then(wasm_text)
+                .map(WasmOutput::Text)
+        }
+        "normalization_locale" => {
+            let args = serde_json::json!([
+                get_string_input(inputs, "input").unwrap_or_default(),
+                get_string_input(inputs, "preset").unwrap_or_default(),
+                get_string_input(inputs, "locale")
+            ]);
+            wasm.call("normalize_with_locale", args)
+                .and_then(wasm_text)
+                .map(WasmOutput::Text)
+        }
+        "hamming" => {
+            let args = serde_json::json!([
+                get_string_input(inputs, "input_a").unwrap_or_default(),
+                get_string_input(inputs, "input_b").unwrap_or_default(),
+                hamming_pad(inputs)
+            ]);
+            let distance = wasm
+                .call("hamming_with_padding", args.clone())
+                .and_then(wasm_distance)?;
+            let score = wasm
+                .call("normalized_hamming_similarity_with_padding", args)
+                .and_then(wasm_score)?;
+            Ok(WasmOutput::Pair {
+                distance: Some(distance),
+                score,
+            })
+        }
+        _ => {
+            let (_, distance, score) = WASM_PAIR_EXPORTS
+                .iter()
+                .find(|(name, ..)| *name == category)
+                .ok_or_else(|| format!("No wasm export for category: {}", category))?;
+            let pair = serde_json::json!([
+                get_string_input(inputs, "input_a").unwrap_or_default(),
+                get_string_input(inputs, "input_b").unwrap_or_default()
+            ]);
+            let distance = distance
+                .map(|name| wasm.call(name, pair.clone()).and_then(wasm_distance))
+                .transpose()?;
+            let score = wasm.call(score, pair).and_then(wasm_score)?;
+            Ok(WasmOutput::Pair { distance, score })
+        }
+    }
+}
 
+/// Whether a wasm output meets the case's expectations, with the expectation for failure output
+fn wasm_expectation(test: &TestCase, output: &WasmOutput, tol: f64) -> (bool, String) {
+    match output {
+        WasmOutput::Pair { distance, score } => {
+            let distance_matches = distance
+                .is_none_or(|actual| test.expected_distance.is_none_or(|exp| exp == actual));
+            let expected = match distance {
+                Some(_) => format!(
+                    "distance={:?}, score={}",
+                    test.expected_distance,
+                    expected_score_label(test)
+                ),
+                None => format!("score={}", expected_score_label(test)),
+            };
             (
-                idx,
-                SuggestionResult {
-                    value: candidate.clone(),
-                    score,
-                    matched_range,
-                    normalized_value: normalized_candidate.clone(),
-                },
+                distance_matches && score_expectation_met(test, *score, tol),
+                expected,
             )
-        })
-        .filter(|(_, r)| r.score >= min_score)
-        .collect();
+        }
+        WasmOutput::Distance(actual) => {
+            let expected = unified_expected_distance(test);
+            (
+                expected.is_none_or(|exp| exp == *actual),
+                format!("distance={:?}", expected),
+            )
+        }
+        WasmOutput::Score(actual) => {
+            let expected = unified_expected_score(test);
+            (
+                expected.is_none_or(|exp| scores_match(exp, *actual, tol))
+                    && score_expectation_met(test, *actual, tol),
+                format!("score={:?}", expected),
+            )
+        }
+        WasmOutput::Text(actual) => {
+            let expected = test
+                .expected
+                .as_ref()
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            (actual == expected, format!("\"{}\"", expected))
+        }
+    }
+}
 
-    // Sort by score (descending), preserving original order for ties
-    results.sort_by(|(idx_a, a), (idx_b, b)| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| idx_a.cmp(idx_b))
-    });
+/// `validate_test_case` for `--target wasm`: the same expectations and tolerance, checked
+/// against the wasm module's output
+fn validate_wasm_case(
+    file: &str,
+    category: &str,
+    test: &TestCase,
+    tol: f64,
+    wasm: &WasmRunner,
+) -> ValidationResult {
+    let outcome = wasm_output(category, test, wasm);
+    let (passed, expected, actual, error) = match (&test.expect_error, outcome) {
+        (Some(expected_error), Ok(output)) => (
+            false,
+            Some(format!("error containing {:?}", expected_error)),
+            Some(output.to_string()),
+            Some("Computation succeeded but an error was expected".to_string()),
+        ),
+        (Some(expected_error), Err(e)) => {
+            let matched = e.contains(expected_error.as_str());
+            (
+                matched,
+                Some(format!("error containing {:?}", expected_error)),
+                Some(format!("error={:?}", e)),
+                (!matched).then(|| "Error mismatch".to_string()),
+            )
+        }
+        (None, Ok(output)) => {
+            let (passed, expected) = wasm_expectation(test, &output, tol);
+            let error = (!passed && matches!(output, WasmOutput::Text(_)))
+                .then(|| "Normalization mismatch".to_string());
+            (passed, Some(expected), Some(output.to_string()), error)
+        }
+        (None, Err(e)) => (false, None, None, Some(e)),
+    };
 
-    // Truncate to max_suggestions
-    results.truncate(max_suggestions);
+    ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed,
+        expected,
+        actual,
+        error,
+        repro: None,
+    }
+}
 
-    // Extract just the results, discarding indices
-    let results: Vec<SuggestionResult> = results.into_iter().map(|(_, r)| r).collect();
+// ============================================================================
+// PROPERTY-BASED INVARIANTS
+// ============================================================================
+// A `properties` case names an invariant and the inputs to check it over:
+// either an explicit `strings` list (every ordered pair or triple) or a
+// `seed` + `count` drawing tuples from the generate-corpus generators.
+
+/// Metrics with a distance (checked by identity, symmetry and triangle_inequality)
+const PROPERTY_DISTANCE_METRICS: &[&str] = &[
+    "levenshtein",
+    "damerau_levenshtein",
+    "osa",
+    "indel",
+    "lcs_seq",
+];
+
+/// Metrics with a normalized score
+const PROPERTY_SCORE_METRICS: &[&str] = &[
+    "levenshtein",
+    "damerau_levenshtein",
+    "osa",
+    "jaro",
+    "jaro_winkler",
+    "indel",
+    "lcs_seq",
+    "ratio",
+];
+
+/// Registered library similarities not already covered under a unified metric name;
+/// `symmetry` and `unit_interval` check these too, at their own scale
+fn registered_metric(name: &str) -> Option<string_metrics_core::MetricInfo> {
+    string_metrics_core::list_metrics()
+        .into_iter()
+        .find(|info| info.name == name && !PROPERTY_SCORE_METRICS.contains(&info.name))
+}
 
-    // Convert to YAML format
-    let suggestions: Vec<serde_yaml::Value> = results
+/// Unified score metrics plus the registered similarities that pass `keep`
+fn with_registered(keep: fn(&string_metrics_core::MetricInfo) -> bool) -> Vec<&'static str> {
+    let registered = string_metrics_core::list_metrics()
+        .into_iter()
+        .filter(|info| keep(info) && registered_metric(info.name).is_some())
+        .map(|info| info.name);
+    PROPERTY_SCORE_METRICS
         .iter()
-        .map(|r| {
-            let mut map = serde_yaml::Mapping::new();
-            map.insert(
-                serde_yaml::Value::String("value".to_string()),
-                serde_yaml::Value::String(r.value.clone()),
-            );
-            map.insert(
-                serde_yaml::Value::String("score".to_string()),
-                serde_yaml::to_value(r.score).expect("Failed to serialize score"),
-            );
-            // Include normalized_value for debugging
-            map.insert(
-                serde_yaml::Value::String("normalized_value".to_string()),
-                serde_yaml::Value::String(r.normalized_value.clone()),
-            );
-            if let Some(ref range) = r.matched_range {
-                let mut range_map = serde_yaml::Mapping::new();
-                range_map.insert(
-                    serde_yaml::Value::String("start".to_string()),
-                    serde_yaml::Value::Number(serde_yaml::Number::from(range.start)),
-                );
-                range_map.insert(
-                    serde_yaml::Value::String("end".to_string()),
-                    serde_yaml::Value::Number(serde_yaml::Number::from(range.end)),
-                );
-                map.insert(
-                    serde_yaml::Value::String("matched_range".to_string()),
-                    serde_yaml::Value::Mapping(range_map),
-                );
-            }
-            serde_yaml::Value::Mapping(map)
-        })
-        .collect();
+        .copied()
+        .chain(registered)
+        .collect()
+}
 
-    case.expected = Some(serde_yaml::Value::Sequence(suggestions));
-    true
+/// True metrics (distance satisfies the triangle inequality). OSA is not one:
+/// osa("ca", "ac") + osa("ac", "abc") = 2 < osa("ca", "abc") = 3.
+const TRIANGLE_METRICS: &[&str] = &["levenshtein", "damerau_levenshtein", "indel"];
+
+/// Everything the unified registry dispatches, by canonical name, keeping only metrics with a
+/// distance form when `distances` is set; `comparator_parity` checks a Comparator for each
+fn unified_metric_names(distances: bool) -> Result<Vec<&'static str>, String> {
+    Ok(string_metrics_core::registered_metrics()?
+        .into_iter()
+        .filter(|def| def.unified && (!distances || def.distance_fn().is_some()))
+        .map(|def| def.name)
+        .collect())
 }
 
-// ============================================================================
-// SUBSTRING SIMILARITY (Longest Common Substring)
-// ============================================================================
+/// Presets `comparator_parity` builds a Comparator with: one borrows, one normalizes
+const COMPARATOR_PRESETS: &[&str] = &["none", "default"];
+
+/// Random chunkings `stream_chunking` feeds each haystack in
+const STREAM_CHUNKINGS: usize = 4;
+
+/// Locales `case_fold_fast_path` folds under: the Unicode default and the Turkic pairs
+const CASE_FOLD_LOCALES: [Option<&str>; 2] = [None, Some("tr")];
+
+/// The core's case folding written out char by char with no fast path, as the oracle for
+/// `case_fold_fast_path`
+fn reference_case_fold(s: &str, locale: Option<&str>) -> String {
+    let turkic = matches!(locale, Some("tr") | Some("az"));
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'İ' if turkic => folded.push('i'),
+            'I' if turkic => folded.push('ı'),
+            'İ' => folded.push_str("i\u{0307}"),
+            'ß' => folded.push_str("ss"),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
 
-/// Compute substring similarity using Longest Common Substring algorithm
-/// Returns (score, matched_range_in_haystack)
-/// Score formula: (2 * lcs_length) / (needle_length + haystack_length)
-fn compute_substring_similarity(needle: &str, haystack: &str) -> (f64, Option<Range>) {
-    let needle_chars: Vec<char> = needle.chars().collect();
-    let haystack_chars: Vec<char> = haystack.chars().collect();
-    let m = needle_chars.len();
-    let n = haystack_chars.len();
-
-    if m == 0 || n == 0 {
-        return (0.0, None);
-    }
-
-    // DP table for longest common substring
-    let mut dp = vec![vec![0usize; n + 1]; m + 1];
-    let mut max_len = 0;
-    let mut end_in_haystack = 0;
-
-    for i in 1..=m {
-        for j in 1..=n {
-            if needle_chars[i - 1] == haystack_chars[j - 1] {
-                dp[i][j] = dp[i - 1][j - 1] + 1;
-                if dp[i][j] > max_len {
-                    max_len = dp[i][j];
-                    end_in_haystack = j;
-                }
-            }
+/// Property violations reported per case before the rest are only counted
+const MAX_REPORTED_VIOLATIONS: usize = 3;
+
+/// Input tuples for a property case: explicit strings or seeded random ones
+fn property_inputs(test: &TestCase, arity: usize) -> Result<Vec<Vec<String>>, String> {
+    if let Some(strings) = test.inputs.get("strings") {
+        let strings: Vec<String> = serde_yaml::from_value(strings.clone())
+            .map_err(|e| format!("strings must be a list of strings: {}", e))?;
+        let mut tuples: Vec<Vec<String>> = vec![Vec::new()];
+        for _ in 0..arity {
+            tuples = tuples
+                .into_iter()
+                .flat_map(|tuple| {
+                    strings.iter().map(move |s| {
+                        let mut next = tuple.clone();
+                        next.push(s.clone());
+                        next
+                    })
+                })
+                .collect();
         }
+        return Ok(tuples);
     }
 
-    let score = if max_len == 0 {
-        0.0
-    } else {
-        (2.0 * max_len as f64) / (m + n) as f64
+    let seed = test.inputs.get("seed").and_then(|v| v.as_u64());
+    let count = test.inputs.get("count").and_then(|v| v.as_u64());
+    let (Some(seed), Some(count)) = (seed, count) else {
+        return Err("properties case needs `strings` or both `seed` and `count`".to_string());
     };
 
-    let range = if max_len > 0 {
-        Some(Range {
-            start: end_in_haystack - max_len,
-            end: end_in_haystack,
+    // Each element is usually a few edits from the previous one, so the
+    // tuples exercise near matches rather than only unrelated strings
+    let mut rng = SplitMix64(seed);
+    Ok((0..count as usize)
+        .map(|index| {
+            let generator = STRING_GENERATORS[index % STRING_GENERATORS.len()];
+            let mut tuple = vec![random_string(generator, &mut rng)];
+            while tuple.len() < arity {
+                let previous = tuple.last().unwrap();
+                let next = if rng.chance(70) {
+                    mutate(previous, &mut rng)
+                } else {
+                    random_string(generator, &mut rng)
+                };
+                tuple.push(next);
+            }
+            tuple
         })
-    } else {
-        None
+        .collect())
+}
+
+/// Metrics named by the case, checked against those the property applies to
+fn property_metrics(
+    test: &TestCase,
+    property: &str,
+    applicable: &[&'static str],
+) -> Result<Vec<&'static str>, String> {
+    let Some(requested) = test.inputs.get("metrics") else {
+        return Ok(applicable.to_vec());
     };
+    let requested: Vec<String> = serde_yaml::from_value(requested.clone())
+        .map_err(|e| format!("metrics must be a list of names: {}", e))?;
+    requested
+        .iter()
+        .map(|metric| {
+            applicable
+                .iter()
+                .find(|known| **known == metric.as_str())
+                .copied()
+                .ok_or_else(|| {
+                    if property == "triangle_inequality" && metric == "osa" {
+                        "osa does not satisfy the triangle inequality \
+                         (osa(ca, ac) + osa(ac, abc) < osa(ca, abc))"
+                            .to_string()
+                    } else {
+                        format!(
+                            "{} does not apply to metric {} (supported: {})",
+                            property,
+                            metric,
+                            applicable.join(", ")
+                        )
+                    }
+                })
+        })
+        .collect()
+}
+
+/// Shorten long generated inputs for failure messages
+fn abbreviate(s: &str) -> String {
+    const LIMIT: usize = 24;
+    if s.chars().count() <= LIMIT {
+        format!("{:?}", s)
+    } else {
+        format!(
+            "{:?}… ({} chars)",
+            s.chars().take(LIMIT).collect::<String>(),
+            s.chars().count()
+        )
+    }
+}
 
-    (score, range)
+fn property_distance(metric: &str, a: &str, b: &str) -> usize {
+    string_metrics_core::unified_distance(a, b, metric)
+        .expect("metric validated by property_metrics")
 }
 
-// ============================================================================
-// METRIC COMPUTATION UTILITIES
-// ============================================================================
+fn property_score(metric: &str, a: &str, b: &str) -> f64 {
+    if let Some(info) = registered_metric(metric) {
+        return info.score(a, b);
+    }
+    string_metrics_core::unified_score(a, b, metric).expect("metric validated by property_metrics")
+}
 
-/// Compute score for a given metric
-/// Returns (score, optional_range) where range is only populated for substring metric
-fn compute_score_for_metric(input: &str, candidate: &str, metric: &str) -> (f64, Option<Range>) {
-    match metric {
-        "levenshtein" => {
-            let score = rapidfuzz::distance::levenshtein::normalized_similarity(
-                input.chars(),
-                candidate.chars(),
-            );
-            (score, None)
+/// Check one invariant over every input tuple, returning (checks run, violations)
+fn check_property(
+    property: &str,
+    test: &TestCase,
+    tol: f64,
+) -> Result<(usize, Vec<String>), String> {
+    let mut checks = 0;
+    let mut violations = Vec::new();
+    let mut check = |holds: bool, violation: &dyn Fn() -> String| {
+        checks += 1;
+        if !holds {
+            violations.push(violation());
         }
-        "damerau_osa" => {
-            let score =
-                rapidfuzz::distance::osa::normalized_similarity(input.chars(), candidate.chars());
-            (score, None)
+    };
+
+    match property {
+        "symmetry" => {
+            let applicable = with_registered(|info| info.symmetric);
+            let metrics = property_metrics(test, property, &applicable)?;
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                for &metric in &metrics {
+                    if PROPERTY_DISTANCE_METRICS.contains(&metric) {
+                        let (ab, ba) = (
+                            property_distance(metric, a, b),
+                            property_distance(metric, b, a),
+                        );
+                        check(ab == ba, &|| {
+                            format!(
+                                "{} distance({}, {}) = {} but distance({}, {}) = {}",
+                                metric,
+                                abbreviate(a),
+                                abbreviate(b),
+                                ab,
+                                abbreviate(b),
+                                abbreviate(a),
+                                ba
+                            )
+                        });
+                    }
+                    let (ab, ba) = (property_score(metric, a, b), property_score(metric, b, a));
+                    check(scores_match(ab, ba, tol), &|| {
+                        format!(
+                            "{} score({}, {}) = {} but score({}, {}) = {}",
+                            metric,
+                            abbreviate(a),
+                            abbreviate(b),
+                            ab,
+                            abbreviate(b),
+                            abbreviate(a),
+                            ba
+                        )
+                    });
+                }
+            }
         }
-        "damerau_unrestricted" => {
-            let score = rapidfuzz::distance::damerau_levenshtein::normalized_similarity(
-                input.chars(),
-                candidate.chars(),
-            );
-            (score, None)
+        "identity" => {
+            let metrics = property_metrics(test, property, PROPERTY_SCORE_METRICS)?;
+            for single in property_inputs(test, 1)? {
+                let a = &single[0];
+                for &metric in &metrics {
+                    if PROPERTY_DISTANCE_METRICS.contains(&metric) {
+                        let d = property_distance(metric, a, a);
+                        check(d == 0, &|| {
+                            format!("{} distance({}, itself) = {}", metric, abbreviate(a), d)
+                        });
+                    }
+                    let s = property_score(metric, a, a);
+                    check(scores_match(s, 1.0, tol), &|| {
+                        format!("{} score({}, itself) = {}", metric, abbreviate(a), s)
+                    });
+                }
+            }
+        }
+        "triangle_inequality" => {
+            let metrics = property_metrics(test, property, TRIANGLE_METRICS)?;
+            for triple in property_inputs(test, 3)? {
+                let (a, b, c) = (&triple[0], &triple[1], &triple[2]);
+                for &metric in &metrics {
+                    let (ab, bc, ac) = (
+                        property_distance(metric, a, b),
+                        property_distance(metric, b, c),
+                        property_distance(metric, a, c),
+                    );
+                    check(ac <= ab + bc, &|| {
+                        format!(
+                            "{}: d(a, c) = {} > d(a, b) + d(b, c) = {} + {} for a={}, b={}, c={}",
+                            metric,
+                            ac,
+                            ab,
+                            bc,
+                            abbreviate(a),
+                            abbreviate(b),
+                            abbreviate(c)
+                        )
+                    });
+                }
+            }
+        }
+        "unit_interval" => {
+            let applicable = with_registered(|_| true);
+            let metrics = property_metrics(test, property, &applicable)?;
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                for &metric in &metrics {
+                    let max = registered_metric(metric).map_or(1.0, |info| info.scale.max());
+                    let s = property_score(metric, a, b);
+                    check((-tol..=max + tol).contains(&s), &|| {
+                        format!(
+                            "{} score({}, {}) = {} is outside [0, {}]",
+                            metric,
+                            abbreviate(a),
+                            abbreviate(b),
+                            s,
+                            max
+                        )
+                    });
+                }
+            }
+        }
+        "damerau_ordering" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "damerau_ordering always compares osa and damerau_levenshtein; \
+                            remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                let osa = property_distance("osa", a, b);
+                let damerau = property_distance("damerau_levenshtein", a, b);
+                let len_diff = a.chars().count().abs_diff(b.chars().count());
+                check(osa >= damerau && damerau >= len_diff, &|| {
+                    format!(
+                        "expected osa >= damerau >= |len diff| but got {} >= {} >= {} for {}, {}",
+                        osa,
+                        damerau,
+                        len_diff,
+                        abbreviate(a),
+                        abbreviate(b)
+                    )
+                });
+            }
+        }
+        "lcs_alignment" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "lcs_alignment always checks the LCS matching indices and indel editops; \
+                            remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                let violation = lcs_alignment_violation(a, b);
+                check(violation.is_none(), &|| {
+                    format!(
+                        "{} for {}, {}",
+                        violation.clone().unwrap_or_default(),
+                        abbreviate(a),
+                        abbreviate(b)
+                    )
+                });
+            }
+        }
+        "damerau_unit_cost" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "damerau_unit_cost always compares weighted and integer damerau_levenshtein; \
+                            remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                let weighted = string_metrics_core::damerau_levenshtein_weighted(a, b, 1.0);
+                let distance = property_distance("damerau_levenshtein", a, b);
+                check(weighted == distance as f64, &|| {
+                    format!(
+                        "weighted cost at 1.0 = {} but damerau_levenshtein distance = {} for {}, {}",
+                        weighted,
+                        distance,
+                        abbreviate(a),
+                        abbreviate(b)
+                    )
+                });
+                let weighted_score =
+                    string_metrics_core::damerau_levenshtein_weighted_normalized_similarity(
+                        a, b, 1.0,
+                    );
+                let score = property_score("damerau_levenshtein", a, b);
+                check(scores_match(weighted_score, score, tol), &|| {
+                    format!(
+                        "weighted score at 1.0 = {} but damerau_levenshtein score = {} for {}, {}",
+                        weighted_score,
+                        score,
+                        abbreviate(a),
+                        abbreviate(b)
+                    )
+                });
+            }
+        }
+        "mfc_bounds_indel" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "mfc_bounds_indel always compares mfc_similarity (k = 0) with indel; \
+                            remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                let mfc = string_metrics_core::mfc_similarity(a, b, 0);
+                let indel = property_score("indel", a, b);
+                check(mfc >= indel - tol, &|| {
+                    format!(
+                        "mfc_similarity (k = 0) = {} is below the indel score {} for {}, {}",
+                        mfc,
+                        indel,
+                        abbreviate(a),
+                        abbreviate(b)
+                    )
+                });
+            }
+        }
+        "stream_chunking" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "stream_chunking always compares StreamMatcher with fuzzy_find_all; \
+                            remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                // b is the needle, embedded between copies of a so most haystacks match
+                let (a, needle) = (&pair[0], &pair[1]);
+                let needle_len = needle.chars().count();
+                if needle_len == 0 {
+                    continue;
+                }
+                let max_edits = needle_len / 3;
+                let haystack: Vec<char> = format!("{}{}{}", a, needle, a).chars().collect();
+                let whole = haystack.iter().collect::<String>();
+                let expected = string_metrics_core::fuzzy_find_all(&whole, needle, max_edits)
+                    .expect("max_edits is below the needle length");
+                let mut rng = SplitMix64(fnv1a(whole.as_bytes()));
+                for _ in 0..STREAM_CHUNKINGS {
+                    let mut matcher = string_metrics_core::StreamMatcher::new(needle, max_edits)
+                        .expect("max_edits is below the needle length");
+                    let mut streamed = Vec::new();
+                    let mut chunks = Vec::new();
+                    let mut start = 0;
+                    while start < haystack.len() {
+                        let end = (start + rng.below(0..needle_len + 2)).min(haystack.len());
+                        chunks.push(end - start);
+                        streamed
+                            .extend(matcher.feed(&haystack[start..end].iter().collect::<String>()));
+                        start = end;
+                    }
+                    streamed.extend(matcher.finish());
+                    check(streamed == expected, &|| {
+                        format!(
+                            "chunks of {:?} chars gave {:?} but fuzzy_find_all gave {:?} \
+                             for needle {} (max_edits {}) in {}",
+                            chunks,
+                            streamed,
+                            expected,
+                            abbreviate(needle),
+                            max_edits,
+                            abbreviate(&whole)
+                        )
+                    });
+                }
+            }
+        }
+        "comparator_parity" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "comparator_parity always checks every unified metric; remove `metrics`"
+                        .to_string(),
+                );
+            }
+            let pairs = property_inputs(test, 2)?;
+            for metric in unified_metric_names(false)? {
+                for preset in COMPARATOR_PRESETS {
+                    // One comparator per metric and preset, so its buffers carry over between pairs
+                    let options = string_metrics_core::PairOptions {
+                        preset: preset.to_string(),
+                        locale: None,
+                        score_cutoff: None,
+                    };
+                    let mut comparator = string_metrics_core::Comparator::new(metric, options)
+                        .expect("registered metrics are unified metrics");
+                    for pair in &pairs {
+                        let a = string_metrics_core::normalize_with_locale(&pair[0], preset, None);
+                        let b = string_metrics_core::normalize_with_locale(&pair[1], preset, None);
+                        let expected = string_metrics_core::unified_score(&a, &b, metric)
+                            .expect("registered metrics are unified metrics");
+                        let reused = comparator.score(&pair[0], &pair[1])?;
+                        check(reused.to_bits() == expected.to_bits(), &|| {
+                            format!(
+                                "Comparator({}, {}) scored {} but unified_score gave {} \
+                                 for {}, {}",
+                                metric,
+                                preset,
+                                reused,
+                                expected,
+                                abbreviate(&pair[0]),
+                                abbreviate(&pair[1])
+                            )
+                        });
+                        if let Ok(expected) = string_metrics_core::unified_distance(&a, &b, metric)
+                        {
+                            let reused = comparator.distance(&pair[0], &pair[1])?;
+                            check(reused == expected, &|| {
+                                format!(
+                                    "Comparator({}, {}) measured {} but unified_distance gave \
+                                     {} for {}, {}",
+                                    metric,
+                                    preset,
+                                    reused,
+                                    expected,
+                                    abbreviate(&pair[0]),
+                                    abbreviate(&pair[1])
+                                )
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        "word_diff_lossless" => {
+            if test.inputs.contains_key("metrics") {
+                return Err(
+                    "word_diff_lossless always diffs with the default metric; remove `metrics`"
+                        .to_string(),
+                );
+            }
+            for pair in property_inputs(test, 2)? {
+                let (a, b) = (&pair[0], &pair[1]);
+                for tokenizer in [Tokenizer::Whitespace, Tokenizer::Words] {
+                    let options = WordDiffOptions {
+                        tokenizer,
+                        ..WordDiffOptions::default()
+                    };
+                    let ops = string_metrics_core::word_diff(a, b, &options)?;
+                    // Each side's runs, minus the other side's insertions or deletions, rebuild it
+                    let rebuilt = |s: &str, side: fn(&WordDiffOp) -> (usize, usize), skip| {
+                        let tokens = string_metrics_core::word_tokens(s, tokenizer);
+                        ops.iter()
+                            .filter(|op| op.tag != skip)
+                            .flat_map(|op| {
+                                let (start, end) = side(op);
+                                &tokens[start..end]
+                            })
+                            .map(|t| format!("{}{}", t.text, t.whitespace))
+                            .collect::<String>()
+                    };
+                    let a_side = rebuilt(a, |op| (op.a_start, op.a_end), EditTag::Insert);
+                    let b_side = rebuilt(b, |op| (op.b_start, op.b_end), EditTag::Delete);
+                    check(a_side == *a && b_side == *b, &|| {
+                        format!(
+                            "{:?} word diff of {}, {} rebuilt {}, {}",
+                            tokenizer,
+                            abbreviate(a),
+                            abbreviate(b),
+                            abbreviate(&a_side),
+                            abbreviate(&b_side)
+                        )
+                    });
+                }
+            }
         }
-        "jaro_winkler" => {
-            let score =
-                rapidfuzz::distance::jaro_winkler::similarity(input.chars(), candidate.chars());
-            (score, None)
+        "case_fold_fast_path" => {
+            if test.inputs.contains_key("metrics") {
+                return Err("case_fold_fast_path checks no metrics; remove `metrics`".to_string());
+            }
+            // The generators emit lowercase text, so each input is also checked uppercased
+            let inputs = property_inputs(test, 1)?;
+            for input in inputs
+                .iter()
+                .flat_map(|single| [single[0].clone(), single[0].to_uppercase()])
+            {
+                let s = input.as_str();
+                let needs_fold = string_metrics_core::needs_case_fold(s);
+                for locale in CASE_FOLD_LOCALES {
+                    let folded = string_metrics_core::case_fold_with_locale(s, locale);
+                    let expected = reference_case_fold(s, locale);
+                    check(folded == expected, &|| {
+                        format!(
+                            "case_fold({}, {:?}) = {} but folding each char gives {}",
+                            abbreviate(s),
+                            locale,
+                            abbreviate(&folded),
+                            abbreviate(&expected)
+                        )
+                    });
+                    check(needs_fold == (expected != s), &|| {
+                        format!(
+                            "needs_case_fold({}) = {} but folding under {:?} gives {}",
+                            abbreviate(s),
+                            needs_fold,
+                            locale,
+                            abbreviate(&expected)
+                        )
+                    });
+                }
+            }
         }
-        "substring" => {
-            let (score, range) = compute_substring_similarity(input, candidate);
-            (score, range)
+        _ => {
+            return Err(format!(
+                "Unknown property: {} (supported: symmetry, identity, \
+                 triangle_inequality, unit_interval, damerau_ordering, lcs_alignment, \
+                 damerau_unit_cost, mfc_bounds_indel, stream_chunking, comparator_parity, \
+                 word_diff_lossless, case_fold_fast_path)",
+                property
+            ))
         }
-        _ => (0.0, None), // Unknown metric
     }
+    Ok((checks, violations))
 }
 
-// ============================================================================
-// NORMALIZATION UTILITIES
-// ============================================================================
-// These functions match the canonical implementation in src/lib.rs
-
-/// Case folding with optional locale support
-fn case_fold_with_locale(s: &str, locale: Option<&str>) -> String {
-    match locale {
-        // Turkish and Azerbaijani: special handling for dotted/dotless I
-        Some("tr") | Some("az") => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i'],      // İ (with dot) → i (lowercase with dot)
-                'I' => vec!['ı'],      // I (no dot) → ı (lowercase dotless)
-                'ß' => vec!['s', 's'], // German sharp S
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+/// Why the core's LCS alignment of a and b is wrong, if it is: the pairs must match equal chars,
+/// increase in both strings and number rapidfuzz's LCS similarity; the indel editops must number
+/// its indel distance; and UTF-16 indices must be the same positions re-counted
+fn lcs_alignment_violation(a: &str, b: &str) -> Option<String> {
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    let pairs = lcs_seq_matching_indices(a, b, RangeUnit::Char);
+
+    let lcs = rapidfuzz::distance::lcs_seq::similarity(a.chars(), b.chars());
+    if pairs.len() != lcs {
+        return Some(format!(
+            "{} matching indices but lcs_seq similarity {}",
+            pairs.len(),
+            lcs
+        ));
+    }
+    if let Some(&(i, j)) = pairs.iter().find(|&&(i, j)| a_chars[i] != b_chars[j]) {
+        return Some(format!("a[{}] != b[{}] in the matching indices", i, j));
+    }
+    if let Some(w) = pairs
+        .windows(2)
+        .find(|w| w[1].0 <= w[0].0 || w[1].1 <= w[0].1)
+    {
+        return Some(format!(
+            "matching indices {:?} then {:?} don't increase",
+            w[0], w[1]
+        ));
+    }
 
-        // Lithuanian: add combining dot above for i/j/į when followed by accents
-        Some("lt") => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i', '\u{0307}'], // Preserve combining dot behavior
-                'ß' => vec!['s', 's'],
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+    let editops = indel_editops(a, b, RangeUnit::Char);
+    let indel = rapidfuzz::distance::indel::distance(a.chars(), b.chars());
+    if editops.len() != indel {
+        return Some(format!(
+            "{} indel editops but indel distance {}",
+            editops.len(),
+            indel
+        ));
+    }
 
-        // Default (no locale or unknown locale): Unicode casefold
-        _ => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i', '\u{0307}'], // İ → i + combining dot (Unicode default)
-                'ß' => vec!['s', 's'],
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+    let utf16 = |chars: &[char], index: usize| -> usize {
+        chars[..index].iter().map(|c| c.len_utf16()).sum()
+    };
+    let expected: Vec<(usize, usize)> = pairs
+        .iter()
+        .map(|&(i, j)| (utf16(&a_chars, i), utf16(&b_chars, j)))
+        .collect();
+    if lcs_seq_matching_indices(a, b, RangeUnit::Utf16) != expected {
+        return Some("UTF-16 matching indices don't match the char positions".to_string());
     }
+    None
 }
 
-fn normalize(input: &str, preset: &str) -> String {
-    normalize_with_locale(input, preset, None)
-}
+fn validate_properties(file: &str, category: &str, test: &TestCase, tol: f64) -> ValidationResult {
+    let property = get_string_input(&test.inputs, "property").unwrap_or_default();
+    let result = |passed, expected, actual, error| ValidationResult {
+        file: file.to_string(),
+        category: category.to_string(),
+        description: test.description.clone(),
+        passed,
+        expected,
+        actual,
+        error,
+        repro: None,
+    };
 
-fn normalize_with_locale(input: &str, preset: &str, locale: Option<&str>) -> String {
-    match preset {
-        "none" => input.to_string(),
-        "minimal" => input.trim().nfc().collect::<String>(),
-        "default" => case_fold_with_locale(input, locale)
-            .trim()
-            .nfc()
-            .collect::<String>(),
-        "aggressive" => {
-            let folded = case_fold_with_locale(input, locale);
-            let nfkd: String = folded.nfkd().collect();
-            let without_diac: String = nfkd
-                .chars()
-                .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
-                .collect();
-            let alphanum: String = without_diac
-                .chars()
-                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-                .collect();
-            alphanum.trim().to_string()
+    match check_property(&property, test, tol) {
+        Err(e) => result(false, None, None, Some(e)),
+        Ok((checks, violations)) if violations.is_empty() => result(
+            true,
+            Some(format!("{} holds", property)),
+            Some(format!("{} checks passed", checks)),
+            None,
+        ),
+        Ok((checks, violations)) => {
+            let mut actual = violations
+                .iter()
+                .take(MAX_REPORTED_VIOLATIONS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("; ");
+            if violations.len() > MAX_REPORTED_VIOLATIONS {
+                actual.push_str(&format!(
+                    "; … {} more",
+                    violations.len() - MAX_REPORTED_VIOLATIONS
+                ));
+            }
+            result(
+                false,
+                Some(format!("{} holds", property)),
+                Some(format!(
+                    "{} of {} checks violated: {}",
+                    violations.len(),
+                    checks,
+                    actual
+                )),
+                None,
+            )
         }
-        _ => input.to_string(),
     }
 }
+
+// ============================================================================
+// SUBSTRING OPTIONS
+// ============================================================================
+
+/// Per-case substring options; absent fields keep the historical behavior
+/// (char ranges, first maximum found scanning the needle left to right)
+fn substring_options(inputs: &serde_yaml::Mapping) -> Result<SubstringOptions, String> {
+    fn field<T: DeserializeOwned>(
+        inputs: &serde_yaml::Mapping,
+        key: &str,
+    ) -> Result<Option<T>, String> {
+        inputs
+            .get(key)
+            .map(|v| serde_yaml::from_value(v.clone()).map_err(|e| format!("{}: {}", key, e)))
+            .transpose()
+    }
+    Ok(SubstringOptions {
+        range_unit: field(inputs, "range_unit")?.unwrap_or_default(),
+        tie_policy: field(inputs, "tie_policy")?,
+    })
+}
@@ -0,0 +1,1710 @@
+//! End-to-end checks of the validator's command-line behavior (exit codes, file handling).
+//! Metric values themselves are covered by the fixture corpus, not here.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const PASSING_FIXTURE: &str = r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        expected_score: 0.5714285714285714
+        description: Classic Levenshtein example
+"#;
+
+const FAILING_FIXTURE: &str = r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 99
+        description: Wrong distance (first)
+      - input_a: abc
+        input_b: abd
+        expected_distance: 99
+        description: Wrong distance (second)
+"#;
+
+/// Score is 1e-8 away from the computed 0.5714285714285714
+const NEAR_MISS_FIXTURE: &str = r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_score: 0.5714285814285714
+        description: Near miss
+"#;
+
+const LINT_FIXTURE: &str = r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: substring
+    cases:
+      - needle: hello
+        haystack: hello world
+        expected_score: 0.625
+        expected_range:
+          start: 5
+          end: 0
+        description: Backwards range
+"#;
+
+const MALFORMED_FIXTURE: &str = "test_cases: [\n  - category: levenshtein\n    cases: {\n";
+
+/// Scratch directory unique to one test, removed and recreated on each run
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "similarity-validator-cli-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+fn write(dir: &Path, name: &str, contents: &str) {
+    fs::write(dir.join(name), contents).expect("write fixture");
+}
+
+fn validator(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_similarity-validator"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .env_remove("GITHUB_ACTIONS")
+        .output()
+        .expect("run similarity-validator")
+}
+
+fn glob_for(dir: &Path) -> String {
+    format!("{}/*.yaml", dir.display())
+}
+
+#[test]
+fn passing_fixtures_exit_zero() {
+    let dir = scratch_dir("passing");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn malformed_file_in_passing_glob_fails_run() {
+    let dir = scratch_dir("malformed");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    write(&dir, "b.yaml", MALFORMED_FIXTURE);
+    write(&dir, "c.yaml", PASSING_FIXTURE);
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout.contains("File errors:     1"), "{}", stdout);
+    // The other files are still validated
+    assert!(stdout.contains("Passed:          2"), "{}", stdout);
+}
+
+#[test]
+fn fail_fast_stops_on_malformed_file() {
+    let dir = scratch_dir("malformed-fail-fast");
+    write(&dir, "a.yaml", MALFORMED_FIXTURE);
+    write(&dir, "b.yaml", PASSING_FIXTURE);
+
+    let output = validator(&["validate", &glob_for(&dir), "--fail-fast"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!stdout.contains("SUMMARY"), "{}", stdout);
+}
+
+#[test]
+fn fail_fast_reports_only_first_failure() {
+    let dir = scratch_dir("fail-fast");
+    write(&dir, "a.yaml", FAILING_FIXTURE);
+
+    let output = validator(&["validate", &glob_for(&dir), "--fail-fast"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Wrong distance (first)"), "{}", stdout);
+    assert!(!stdout.contains("Wrong distance (second)"), "{}", stdout);
+}
+
+#[test]
+fn exit_codes_distinguish_failures_fixture_errors_and_io_problems() {
+    let code = |pattern: &str| validator(&["validate", pattern]).status.code();
+
+    let dir = scratch_dir("exit-codes-failures");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    write(&dir, "b.yaml", FAILING_FIXTURE);
+    assert_eq!(code(&glob_for(&dir)), Some(1));
+
+    // Parse errors outrank case failures
+    let dir = scratch_dir("exit-codes-parse");
+    write(&dir, "a.yaml", FAILING_FIXTURE);
+    write(&dir, "b.yaml", MALFORMED_FIXTURE);
+    assert_eq!(code(&glob_for(&dir)), Some(2));
+
+    let dir = scratch_dir("exit-codes-schema");
+    write(
+        &dir,
+        "a.yaml",
+        &PASSING_FIXTURE.replace("expected_distance: 3", "expected_distance: three"),
+    );
+    assert_eq!(code(&glob_for(&dir)), Some(2));
+
+    // A directory matching the glob can't be read as a fixture; IO outranks parse errors
+    let dir = scratch_dir("exit-codes-io");
+    write(&dir, "a.yaml", MALFORMED_FIXTURE);
+    fs::create_dir_all(dir.join("b.yaml")).unwrap();
+    assert_eq!(code(&glob_for(&dir)), Some(3));
+
+    let dir = scratch_dir("exit-codes-empty");
+    let output = validator(&["validate", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stdout.contains("no files matched"), "{}", stdout);
+
+    assert_eq!(code("fixtures/[.yaml"), Some(3));
+
+    let help = validator(&["validate", "--help"]);
+    let help = String::from_utf8_lossy(&help.stdout);
+    assert!(help.contains("Exit codes"), "{}", help);
+}
+
+#[cfg(unix)]
+#[test]
+fn directory_input_walks_recursively_and_honors_ignore_file() {
+    let dir = scratch_dir("directory-walk");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    fs::create_dir_all(dir.join("nested/drafts")).unwrap();
+    write(&dir.join("nested"), "b.yml", PASSING_FIXTURE);
+    write(&dir.join("nested/drafts"), "c.yaml", FAILING_FIXTURE);
+    write(&dir.join("nested"), "notes.txt", "not a fixture");
+    // A symlink back to the root must not hang the walk or count files twice
+    std::os::unix::fs::symlink("..", dir.join("nested/loop")).unwrap();
+    let dir_arg = dir.display().to_string();
+
+    let output = validator(&["validate", &dir_arg]);
+    assert_eq!(output.status.code(), Some(1));
+
+    write(&dir, ".validatorignore", "# work in progress\ndrafts/\n");
+    let output = validator(&["validate", &dir_arg]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("Files processed: 2 (3 discovered, 1 ignored by .validatorignore)"),
+        "{}",
+        stdout
+    );
+
+    // Explicit globs still work
+    let output = validator(&["validate", &format!("{}/nested/*.yml", dir_arg)]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn tolerance_precedence_case_over_file_over_cli() {
+    let dir = scratch_dir("tolerance");
+    write(&dir, "a.yaml", NEAR_MISS_FIXTURE);
+    let pattern = glob_for(&dir);
+
+    // Default tolerance (1e-10) is too strict
+    assert_eq!(validator(&["validate", &pattern]).status.code(), Some(1));
+    // Run-wide epsilon loosens it
+    let output = validator(&["validate", &pattern, "--epsilon", "1e-6"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Tolerance:       1e-6"));
+
+    // File tolerance overrides the CLI
+    let strict_file = NEAR_MISS_FIXTURE.replace("test_cases:", "tolerance: 1e-12\ntest_cases:");
+    write(&dir, "a.yaml", &strict_file);
+    let output = validator(&["validate", &pattern, "--epsilon", "1e-6"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // Case tolerance overrides the file
+    let loose_case = strict_file.replace(
+        "description: Near miss",
+        "tolerance: 1e-6\n        description: Near miss",
+    );
+    write(&dir, "a.yaml", &loose_case);
+    let output = validator(&["validate", &pattern]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn group_tags_merge_into_case_tags_for_filters_and_reports() {
+    let dir = scratch_dir("group-tags");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    tags: [slow]
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        expected_score: 0.5714285714285714
+        tags: [unicode, slow]
+        description: Tagged on both levels
+      - input_a: abc
+        input_b: abc
+        expected_distance: 0
+        expected_score: 1.0
+        description: Group tag only
+  - category: levenshtein
+    cases:
+      - input_a: abc
+        input_b: abd
+        expected_distance: 1
+        expected_score: 0.6666666666666667
+        description: Untagged
+"#,
+    );
+    let schema_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../schemas");
+    let output = validator(&["schema-check", &glob_for(&dir), "--schema-dir", schema_dir]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--tag",
+        "slow",
+        "--format",
+        "csv",
+        "--output",
+        "-",
+    ]);
+    let csv = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(csv.contains("Tagged on both levels,true,"), "{}", csv);
+    assert!(csv.contains(",slow;unicode\r\n"), "{}", csv);
+    assert!(csv.contains("Group tag only,true,"), "{}", csv);
+    assert!(!csv.contains("Untagged"), "{}", csv);
+
+    let output = validator(&["stats", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("slow (2), unicode (1)"), "{}", stdout);
+}
+
+#[test]
+fn lint_reports_errors_and_strict_fails_on_warnings() {
+    let dir = scratch_dir("lint");
+    write(&dir, "a.yaml", LINT_FIXTURE);
+
+    let output = validator(&["lint", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("expected_range end < start"), "{}", stdout);
+
+    // Two identical passing files: only duplicate warnings
+    let dir = scratch_dir("lint-strict");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    write(&dir, "b.yaml", PASSING_FIXTURE);
+    assert_eq!(validator(&["lint", &glob_for(&dir)]).status.code(), Some(0));
+
+    let output = validator(&["lint", &glob_for(&dir), "--strict"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("duplicate inputs"), "{}", stdout);
+}
+
+#[test]
+fn generate_dry_run_prints_a_diff_of_changed_values_only() {
+    let dir = scratch_dir("generate-dry-run");
+    write(&dir, "a.yaml", FAILING_FIXTURE);
+    let path = dir.join("a.yaml");
+
+    let output = validator(&[
+        "generate",
+        "--input",
+        path.to_str().unwrap(),
+        "--overwrite",
+        "--dry-run",
+        "--category",
+        "levenshtein",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let changed: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with(['+', '-']))
+        .filter(|line| !line.starts_with("+++") && !line.starts_with("---"))
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            "-    expected_distance: 99",
+            "+    expected_distance: 3",
+            "+    expected_score: 0.5714285714285714",
+            "-    expected_distance: 99",
+            "+    expected_distance: 1",
+            "+    expected_score: 0.6666666666666667",
+        ],
+        "{}",
+        stdout
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), FAILING_FIXTURE);
+}
+
+#[test]
+fn idempotent_regenerate_is_byte_identical() {
+    let dir = scratch_dir("regenerate-idempotent");
+    let source =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/v2.0.0/suggestions.yaml");
+    fs::copy(&source, dir.join("a.yaml")).expect("copy fixture");
+    let path = dir.join("a.yaml");
+    let path = path.to_str().unwrap();
+
+    let args = [
+        "generate",
+        "--input",
+        path,
+        "--overwrite",
+        "--precision",
+        "12",
+    ];
+    assert_eq!(validator(&args).status.code(), Some(0));
+    let first = fs::read(path).unwrap();
+    assert_eq!(validator(&args).status.code(), Some(0));
+    assert_eq!(
+        fs::read(path).unwrap(),
+        first,
+        "second regenerate changed the file"
+    );
+
+    let text = String::from_utf8(first).unwrap();
+    assert!(text.contains("precision: 12"), "{}", text);
+    assert!(text.contains("score: 0.888888888889"), "{}", text);
+}
+
+#[test]
+fn lint_groups_duplicates_across_files_and_flags_conflicts() {
+    let dir = scratch_dir("lint-duplicates");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    // Same inputs in a different key order, with a stale distance
+    write(
+        &dir,
+        "b.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_b: sitting
+        input_a: kitten
+        expected_distance: 4
+        description: Kitten again
+"#,
+    );
+
+    let output = validator(&["lint", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("duplicate inputs with conflicting expected_distance"),
+        "{}",
+        stdout
+    );
+
+    let output = validator(&["lint", &glob_for(&dir), "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("lint JSON");
+    let groups = report["duplicates"].as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(
+        groups[0]["conflicts"],
+        serde_json::json!(["expected_distance"])
+    );
+    assert_eq!(groups[0]["cases"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn diff_exits_one_on_changes_and_separates_insignificant_drift() {
+    let old = scratch_dir("diff-old");
+    let new = scratch_dir("diff-new");
+    write(&old, "a.yaml", PASSING_FIXTURE);
+    write(&new, "a.yaml", PASSING_FIXTURE);
+
+    let old_path = old.display().to_string();
+    let new_path = new.display().to_string();
+    assert_eq!(
+        validator(&["diff", &old_path, &new_path]).status.code(),
+        Some(0)
+    );
+
+    // Last-ulp drift only
+    let drifted = PASSING_FIXTURE.replace("0.5714285714285714", "0.5714285714285715");
+    write(&new, "a.yaml", &drifted);
+    let output = validator(&["diff", &old_path, &new_path, "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(report["insignificant"].as_array().unwrap().len(), 1);
+
+    let changed = drifted.replace("expected_distance: 3", "expected_distance: 4");
+    write(&new, "a.yaml", &changed);
+    let output = validator(&["diff", &old_path, &new_path, "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(report["changed"][0]["field"], "expected_distance");
+    assert_eq!(report["changed"][0]["delta"], 1.0);
+}
+
+#[test]
+fn merge_deduplicates_and_refuses_conflicts_without_force() {
+    let dir = scratch_dir("merge");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    write(&dir, "b.yaml", PASSING_FIXTURE);
+    let a = dir.join("a.yaml").display().to_string();
+    let b = dir.join("b.yaml").display().to_string();
+    let merged = dir.join("merged.yaml");
+    let merged_path = merged.display().to_string();
+
+    let output = validator(&["merge", "--output", &merged_path, &a, &b]);
+    assert_eq!(output.status.code(), Some(0));
+    let contents = fs::read_to_string(&merged).expect("merged output");
+    assert_eq!(contents.matches("input_a: kitten").count(), 1);
+    assert_eq!(
+        validator(&["validate", &merged_path]).status.code(),
+        Some(0)
+    );
+
+    fs::remove_file(&merged).unwrap();
+    write(
+        &dir,
+        "b.yaml",
+        &PASSING_FIXTURE.replace("expected_distance: 3", "expected_distance: 4"),
+    );
+    let output = validator(&["merge", "--output", &merged_path, &a, &b]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!merged.exists());
+
+    let output = validator(&["merge", "--output", &merged_path, &a, &b, "--force"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        validator(&["validate", &merged_path]).status.code(),
+        Some(0)
+    );
+}
+
+#[test]
+fn convert_round_trips_yaml_through_json() {
+    let dir = scratch_dir("convert");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    let yaml = dir.join("a.yaml").display().to_string();
+    let json = dir.join("a.json").display().to_string();
+
+    let output = validator(&["convert", "-i", &yaml, "-o", &json, "--check"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(validator(&["validate", &json]).status.code(), Some(0));
+
+    let output = validator(&["convert", "-i", &yaml, "-o", "a.toml"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr.contains("supported: .yaml, .yml, .json"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn explain_lists_operations_for_supported_metrics() {
+    let output = validator(&["explain", "--a", "abcd", "--b", "abdc", "--metric", "osa"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("transpose"), "{}", stdout);
+
+    let output = validator(&["explain", "--a", "a", "--b", "b", "--metric", "jaro"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn verify_fresh_flags_outdated_source_version() {
+    let dir = scratch_dir("verify-fresh");
+    write(&dir, "fresh.yaml", PASSING_FIXTURE);
+    let output = validator(&["verify-fresh", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let outdated = PASSING_FIXTURE.replacen(
+        "test_cases:",
+        "generator:\n  tool: similarity-validator\n  tool_version: 0.1.0\n  source_library: rapidfuzz-rs\n  source_version: 0.0.1\n  generated_at: 2025-01-01T00:00:00+00:00\ntest_cases:",
+        1,
+    );
+    write(&dir, "fresh.yaml", &outdated);
+    let before = fs::read_to_string(dir.join("fresh.yaml")).unwrap();
+    let output = validator(&["verify-fresh", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("STALE SOURCE VERSION"), "{}", stdout);
+    assert_eq!(fs::read_to_string(dir.join("fresh.yaml")).unwrap(), before);
+}
+
+#[test]
+fn schema_check_reports_pointer_paths() {
+    let dir = scratch_dir("schema-check");
+    let schema_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../schemas");
+    write(&dir, "ok.yaml", PASSING_FIXTURE);
+    let output = validator(&["schema-check", &glob_for(&dir), "--schema-dir", schema_dir]);
+    assert_eq!(output.status.code(), Some(0));
+
+    write(
+        &dir,
+        "typo.yaml",
+        &PASSING_FIXTURE.replace("expected_score:", "expected_socre:"),
+    );
+    let output = validator(&["schema-check", &glob_for(&dir), "--schema-dir", schema_dir]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("/test_cases/0/cases/0: Additional properties are not allowed"),
+        "{}",
+        stdout
+    );
+
+    let output = validator(&["validate", &glob_for(&dir), "--schema-dir", schema_dir]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let bad_schema_dir = dir.join("schemas");
+    fs::create_dir_all(&bad_schema_dir).unwrap();
+    write(&bad_schema_dir, "similarity.schema.json", "{ not json");
+    let bad_schema_dir = bad_schema_dir.display().to_string();
+    let output = validator(&[
+        "validate",
+        &dir.join("ok.yaml").display().to_string(),
+        "--schema-dir",
+        &bad_schema_dir,
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout.contains("malformed schema"), "{}", stdout);
+}
+
+#[test]
+fn migrated_corpus_still_validates() {
+    let dir = scratch_dir("migrate");
+    let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    fs::create_dir_all(dir.join("schemas")).unwrap();
+    fs::copy(
+        repo.join("schemas/similarity.schema.json"),
+        dir.join("schemas/similarity.schema.json"),
+    )
+    .unwrap();
+
+    let pattern = repo.join("tests/fixtures/**/*.yaml").display().to_string();
+    for (i, entry) in glob::glob(&pattern).unwrap().enumerate() {
+        let input = entry.unwrap().display().to_string();
+        let output = dir.join(format!("{}.yaml", i)).display().to_string();
+        let result = validator(&[
+            "generate",
+            "--input",
+            &input,
+            "--output",
+            &output,
+            "--migrate",
+        ]);
+        assert_eq!(result.status.code(), Some(0), "{}", input);
+    }
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    write(
+        &dir,
+        "future.yaml",
+        &PASSING_FIXTURE.replace("version: 2025.10.3", "version: '3.0'"),
+    );
+    let output = validator(&["validate", &dir.join("future.yaml").display().to_string()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout.contains("this tool is too old"), "{}", stdout);
+}
+
+#[test]
+fn generate_corpus_is_reproducible_and_validates() {
+    let first = scratch_dir("corpus-first");
+    let second = scratch_dir("corpus-second");
+    let generate = |dir: &Path, seed: &str| {
+        let output = dir.join("corpus.yaml").display().to_string();
+        let status = validator(&[
+            "generate-corpus",
+            "--seed",
+            seed,
+            "--count",
+            "60",
+            "--output",
+            &output,
+            "--categories",
+            "levenshtein,jaro_winkler,ratio",
+        ])
+        .status;
+        assert_eq!(status.code(), Some(0));
+    };
+
+    generate(&first, "42");
+    generate(&second, "42");
+    let contents = fs::read_to_string(first.join("corpus.yaml")).unwrap();
+    assert!(contents.contains("seed: 42"));
+    assert!(contents.contains("emoji_zwj seed=42 index=3"));
+    assert_eq!(
+        validator(&["validate", &glob_for(&first)]).status.code(),
+        Some(0)
+    );
+
+    let first_path = first.display().to_string();
+    let second_path = second.display().to_string();
+    assert_eq!(
+        validator(&["diff", &first_path, &second_path])
+            .status
+            .code(),
+        Some(0)
+    );
+    generate(&second, "43");
+    assert_eq!(
+        validator(&["diff", &first_path, &second_path])
+            .status
+            .code(),
+        Some(1)
+    );
+
+    let output = validator(&[
+        "generate-corpus",
+        "--seed",
+        "1",
+        "--output",
+        &first.join("bad.yaml").display().to_string(),
+        "--categories",
+        "substring",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn import_reads_quoted_csv_and_appends_by_category() {
+    let dir = scratch_dir("import");
+    write(
+        &dir,
+        "pairs.csv",
+        "query,candidate,note\r\nkitten,sitting,Classic\r\n\"a, b\",\"a\nb\",\"say \"\"hi\"\"\"\r\n",
+    );
+    write(&dir, "more.tsv", "kitten\tsitting\nabc\tabd\n");
+    let output = dir.join("imported.yaml").display().to_string();
+    let pairs = dir.join("pairs.csv").display().to_string();
+    let more = dir.join("more.tsv").display().to_string();
+
+    let import = |input: &str, extra: &[&str]| {
+        let mut args = vec![
+            "import",
+            "--input",
+            input,
+            "--category",
+            "levenshtein",
+            "--output",
+            &output,
+        ];
+        args.extend_from_slice(extra);
+        validator(&args)
+    };
+
+    let first = import(&pairs, &["--header", "--description-column", "2"]);
+    assert_eq!(first.status.code(), Some(0));
+    let contents = fs::read_to_string(&output).unwrap();
+    assert!(contents.contains("input_a: a, b"), "{}", contents);
+    assert!(contents.contains("description: say \"hi\""), "{}", contents);
+    assert!(contents.contains("expected_distance: 3"), "{}", contents);
+
+    // Without --append an existing output is left alone
+    assert_eq!(import(&more, &[]).status.code(), Some(1));
+
+    let appended = import(&more, &["--append"]);
+    assert_eq!(appended.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&appended.stdout);
+    assert!(
+        stdout.contains("Imported: 1 | Duplicates skipped: 1"),
+        "{}",
+        stdout
+    );
+    let contents = fs::read_to_string(&output).unwrap();
+    assert_eq!(contents.matches("- category: levenshtein").count(), 1);
+    assert!(
+        contents.contains("description: more.tsv row 2"),
+        "{}",
+        contents
+    );
+    assert_eq!(
+        validator(&["validate", &glob_for(&dir)]).status.code(),
+        Some(0)
+    );
+}
+
+#[test]
+fn sample_is_a_seeded_subset_without_skipped_cases() {
+    let source = scratch_dir("sample-source");
+    let out = scratch_dir("sample-out");
+    let mut cases = String::new();
+    for i in 0..20 {
+        cases.push_str(&format!(
+            "      - input_a: word{i}\n        input_b: word{i}\n        expected_distance: 0\n        tags: [t{i}]\n        description: Identical {i}\n"
+        ));
+    }
+    cases.push_str(
+        "      - input_a: abc\n        input_b: abd\n        expected_distance: 99\n        skip: true\n        description: Pending\n",
+    );
+    write(
+        &source,
+        "many.yaml",
+        &format!(
+            "$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json\nversion: 2025.10.3\ntest_cases:\n  - category: levenshtein\n    cases:\n{}",
+            cases
+        ),
+    );
+    write(&source, "failing.yaml", FAILING_FIXTURE);
+
+    let sample = |name: &str, per_category: &str, seed: &str| {
+        let output = out.join(name).display().to_string();
+        let status = validator(&[
+            "sample",
+            &glob_for(&source),
+            "--output",
+            &output,
+            "--per-category",
+            per_category,
+            "--seed",
+            seed,
+        ])
+        .status;
+        assert_eq!(status.code(), Some(0));
+        fs::read_to_string(&output).unwrap()
+    };
+
+    let first = sample("a.yaml", "5", "7");
+    let again = sample("b.yaml", "5", "7");
+    let other = sample("c.yaml", "5", "8");
+    let cases_of = |text: &str| -> Vec<String> {
+        text.lines()
+            .filter(|line| line.contains("description:"))
+            .map(str::to_string)
+            .collect()
+    };
+    assert_eq!(cases_of(&first).len(), 5);
+    assert_eq!(cases_of(&first), cases_of(&again));
+    assert_ne!(cases_of(&first), cases_of(&other));
+    assert!(first.contains("seed: 7"), "{}", first);
+    assert!(first.contains("many.yaml"), "{}", first);
+
+    // Every case fits: the sample fails exactly like the originals, minus the skipped one
+    let all = sample("all.yaml", "100", "7");
+    assert!(!all.contains("Pending"), "{}", all);
+    assert!(all.contains("tags:\n    - t19"), "{}", all);
+    let output = validator(&["validate", &out.join("all.yaml").display().to_string()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Failed:          2"), "{}", stdout);
+    assert!(stdout.contains("Passed:          20"), "{}", stdout);
+}
+
+#[test]
+fn template_scaffolds_category_inputs_for_generate_to_fill() {
+    let dir = scratch_dir("template");
+    let schema_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../schemas");
+    let path = dir.join("substring.yaml").display().to_string();
+    let args = [
+        "template",
+        "--category",
+        "substring",
+        "--output",
+        &path,
+        "--cases",
+        "3",
+    ];
+    assert_eq!(validator(&args).status.code(), Some(0));
+    let scaffold = fs::read_to_string(&path).unwrap();
+    assert_eq!(scaffold.matches("needle: ''").count(), 3, "{}", scaffold);
+    assert_eq!(scaffold.matches("description: TODO").count(), 3);
+    assert!(scaffold.contains("command: similarity-validator template"));
+    // Existing files are never overwritten
+    assert_eq!(validator(&args).status.code(), Some(1));
+
+    // Without expected values the skeleton doesn't pass yet; generate fills them in
+    let lint = validator(&["lint", &path]);
+    assert!(String::from_utf8_lossy(&lint.stdout).contains("no expected value"));
+    let filled = scaffold
+        .replacen("needle: ''", "needle: world", 1)
+        .replacen("haystack: ''", "haystack: hello world", 1);
+    fs::write(&path, filled).unwrap();
+    assert_eq!(
+        validator(&["generate", "--input", &path]).status.code(),
+        Some(0)
+    );
+    let output = validator(&["validate", &path, "--schema-dir", schema_dir]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = validator(&[
+        "template",
+        "--category",
+        "no_such_metric",
+        "--output",
+        &dir.join("x.yaml").display().to_string(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn slow_cases_time_out_and_are_flagged_by_lint() {
+    let dir = scratch_dir("timeout");
+    write(
+        &dir,
+        "slow.yaml",
+        &format!(
+            r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: damerau_unrestricted
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        description: Short pair
+      - input_a: {}
+        input_b: {}
+        expected_distance: 1
+        description: Pathological pair
+"#,
+            "ab".repeat(10_000),
+            "ba".repeat(10_000)
+        ),
+    );
+
+    let output = validator(&["validate", &glob_for(&dir), "--timeout-ms", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "Error: timeout: no result after 1 ms (input_a: 20000 chars, input_b: 20000 chars)"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Passed:          1"), "{}", stdout);
+    assert!(stdout.contains("SLOWEST CASES:"), "{}", stdout);
+
+    let lint = validator(&["lint", &glob_for(&dir), "--strict"]);
+    assert_eq!(lint.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&lint.stdout);
+    assert!(
+        stdout.contains("inputs total 40000 chars (over 20000)"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn baseline_separates_known_new_and_fixed_failures() {
+    let dir = scratch_dir("baseline");
+    write(&dir, "a.yaml", FAILING_FIXTURE);
+    let baseline = dir.join("baseline.json");
+    let baseline = baseline.to_str().unwrap();
+
+    let output = validator(&["validate", &glob_for(&dir), "--save-baseline", baseline]);
+    assert_eq!(output.status.code(), Some(1));
+    let saved = fs::read_to_string(baseline).unwrap();
+    assert!(saved.contains("Wrong distance (second)"), "{}", saved);
+
+    // Both failures are known
+    let output = validator(&["validate", &glob_for(&dir), "--baseline", baseline]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Known failures:  2"), "{}", stdout);
+
+    // One baseline entry is fixed, one new failure appears
+    let changed = FAILING_FIXTURE
+        .replace(
+            "expected_distance: 99\n        description: Wrong distance (first)",
+            "expected_distance: 3\n        description: Wrong distance (first)",
+        )
+        .replace("Wrong distance (second)", "Wrong distance (renamed)");
+    write(&dir, "a.yaml", &changed);
+    let output = validator(&["validate", &glob_for(&dir), "--baseline", baseline]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("Known failures:  0"), "{}", stdout);
+    assert!(stdout.contains("New failures:    1"), "{}", stdout);
+    assert!(stdout.contains("Fixed:           1"), "{}", stdout);
+}
+
+#[test]
+fn html_report_escapes_fixture_strings_and_diffs_failures() {
+    let dir = scratch_dir("html-report");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: normalization_presets
+    cases:
+      - input: "<b>Cafe</b> & x"
+        preset: minimal
+        expected: "<b>cafe</b> & x"
+        description: <script>alert("x")</script>
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        description: Passing
+"#,
+    );
+    let report = dir.join("report.html");
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "html",
+        "--output",
+        report.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let html = fs::read_to_string(&report).unwrap();
+    assert!(!html.contains("<script>alert"), "{}", html);
+    assert!(
+        html.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"),
+        "{}",
+        html
+    );
+    // Only the changed character is marked
+    assert!(
+        html.contains("&lt;b&gt;<del>c</del>afe&lt;/b&gt; &amp; x"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("&lt;b&gt;<ins>C</ins>afe&lt;/b&gt; &amp; x"),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains("<a href=\"#category-normalization_presets\">"),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn summary_groups_failures_by_category_and_reports_carry_aggregates() {
+    let dir = scratch_dir("grouped-summary");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: normalization_presets
+    cases:
+      - input: A
+        preset: none
+        expected: a
+        description: Unchanged (first)
+      - input: B
+        preset: none
+        expected: b
+        description: Unchanged (second)
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        description: Passing
+      - input_a: a
+        input_b: b
+        expected_distance: 1
+        description: Pending
+        skip: true
+"#,
+    );
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("CATEGORIES:"), "{}", stdout);
+    assert!(
+        stdout.contains("normalization_presets (2 failed)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("] Unchanged (first)\n"), "{}", stdout);
+    assert!(!stdout.contains("] Unchanged (second)\n"), "{}", stdout);
+    assert!(stdout.contains("and 1 more with this error"), "{}", stdout);
+
+    let output = validator(&["validate", &glob_for(&dir), "--verbose"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("] Unchanged (second)\n"), "{}", stdout);
+    assert!(!stdout.contains("more with this error"), "{}", stdout);
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "json",
+        "--output",
+        "-",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("report JSON");
+    let levenshtein = &report["categories"]["levenshtein"];
+    assert_eq!(levenshtein["cases"], 1);
+    assert_eq!(levenshtein["skipped"], 1);
+    assert_eq!(levenshtein["pass_rate"], 100.0);
+    assert_eq!(report["categories"]["normalization_presets"]["failed"], 2);
+    assert_eq!(report["totals"]["cases"], 3);
+    assert_eq!(report["slowest"].as_array().unwrap().len(), 3);
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "junit",
+        "--output",
+        "-",
+    ]);
+    let junit = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        junit.contains(
+            "<testsuite name=\"normalization_presets\" tests=\"2\" failures=\"2\" errors=\"0\" skipped=\"0\""
+        ),
+        "{}",
+        junit
+    );
+    assert!(
+        junit.contains("<property name=\"pass_rate\" value=\"100.0\"/>"),
+        "{}",
+        junit
+    );
+    assert!(junit.contains("<skipped message=\"\"/>"), "{}", junit);
+}
+
+#[test]
+fn csv_export_quotes_fields_and_keeps_stdout_clean() {
+    let dir = scratch_dir("csv-export");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: normalization_presets
+    cases:
+      - input: "a, b"
+        preset: none
+        expected: "a, \"b\"\nc"
+        description: Comma, quote and newline
+        tags: [csv, escaping]
+"#,
+    );
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "csv",
+        "--output",
+        "-",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("SUMMARY"), "{}", stderr);
+    assert_eq!(
+        stdout,
+        "file,category,description,passed,expected,actual,error,tags\r\n\
+         a.yaml,normalization_presets,\"Comma, quote and newline\",false,\
+         \"\"\"a, \"\"b\"\"\nc\"\"\",\"\"\"a, b\"\"\",Normalization mismatch,csv;escaping\r\n"
+    );
+}
+
+#[test]
+fn skip_and_only_markers_narrow_the_run() {
+    let dir = scratch_dir("skip-only");
+    let fixture = r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        description: Passing
+      - input_a: abc
+        input_b: abd
+        expected_distance: 99
+        skip: true
+        skip_reason: waiting on weighted costs
+        description: Pending
+"#;
+    write(&dir, "a.yaml", fixture);
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Skipped:         1"), "{}", stdout);
+    assert!(stdout.contains("waiting on weighted costs"), "{}", stdout);
+
+    let output = validator(&["validate", &glob_for(&dir), "--include-skipped"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // Generate leaves the pending case alone
+    let path = dir.join("a.yaml");
+    let output = validator(&["generate", "--input", path.to_str().unwrap(), "--overwrite"]);
+    assert_eq!(output.status.code(), Some(0));
+    let generated = fs::read_to_string(&path).unwrap();
+    assert!(generated.contains("expected_distance: 99"), "{}", generated);
+
+    // `only` runs just the marked case, but never passes
+    write(
+        &dir,
+        "a.yaml",
+        &fixture.replace(
+            "description: Passing",
+            "description: Passing\n        only: true",
+        ),
+    );
+    let output = validator(&["validate", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("Total tests:     1"), "{}", stdout);
+    assert!(stdout.contains("Passed:          1"), "{}", stdout);
+    assert!(stdout.contains("`only` markers found"), "{}", stdout);
+}
+
+#[test]
+fn suggestions_compare_normalized_value_unless_ignored() {
+    let dir = scratch_dir("normalized-value");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: suggestions
+    cases:
+      - options:
+          metric: levenshtein
+          normalize_preset: default
+        input: docscrib
+        candidates: [Docscribe]
+        description: Normalized candidate
+"#,
+    );
+    let fixture = dir.join("a.yaml");
+    let fixture = fixture.to_str().unwrap();
+    let output = validator(&["generate", "--input", fixture]);
+    assert_eq!(output.status.code(), Some(0));
+    let generated = fs::read_to_string(dir.join("a.yaml")).unwrap();
+    assert!(
+        generated.contains("normalized_value: docscribe"),
+        "{}",
+        generated
+    );
+
+    // Only normalized_value is wrong: value and score still match
+    write(
+        &dir,
+        "a.yaml",
+        &generated.replace("normalized_value: docscribe", "normalized_value: Docscribe"),
+    );
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Normalized value mismatch"), "{}", stdout);
+
+    let output = validator(&["validate", &glob_for(&dir), "--ignore-normalized"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = validator(&["generate", "--input", fixture, "--no-debug-fields"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stripped = fs::read_to_string(dir.join("a.yaml")).unwrap();
+    assert!(!stripped.contains("normalized_value"), "{}", stripped);
+    assert!(stripped.contains("--no-debug-fields"), "{}", stripped);
+}
+
+#[test]
+fn extract_results_are_generated_and_compared_in_rank_order() {
+    let dir = scratch_dir("extract");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: extract
+    cases:
+      - query: hello
+        choices: [hella, hello, world]
+        options:
+          limit: 2
+        description: Ranked with a limit
+  - category: extract_one
+    cases:
+      - query: hello
+        choices: [world]
+        options:
+          score_cutoff: 90
+        description: Nothing reaches the cutoff
+"#,
+    );
+    let fixture = dir.join("a.yaml");
+    let fixture = fixture.to_str().unwrap();
+    let output = validator(&["generate", "--input", fixture]);
+    assert_eq!(output.status.code(), Some(0));
+    let generated = fs::read_to_string(dir.join("a.yaml")).unwrap();
+    assert!(generated.contains("choice: hello"), "{}", generated);
+    assert!(generated.contains("expected: []"), "{}", generated);
+
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(0));
+
+    // Swap the ranked order: both choices are still present but at the wrong ranks
+    write(
+        &dir,
+        "a.yaml",
+        &generated
+            .replacen("choice: hello", "choice: PLACEHOLDER", 1)
+            .replacen("choice: hella", "choice: hello", 1)
+            .replacen("choice: PLACEHOLDER", "choice: hella", 1),
+    );
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Choice mismatch"), "{}", stdout);
+}
+
+#[test]
+fn properties_check_invariants_and_reject_non_metrics() {
+    let dir = scratch_dir("properties");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: properties
+    cases:
+      - property: triangle_inequality
+        strings: [ca, ac, abc]
+        description: True metrics
+      - property: symmetry
+        seed: 7
+        count: 5
+        metrics: [levenshtein, ratio]
+        description: Random pairs
+"#,
+    );
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let osa = fs::read_to_string(dir.join("a.yaml")).unwrap().replace(
+        "strings: [ca, ac, abc]",
+        "strings: [ca, ac, abc]\n        metrics: [osa]",
+    );
+    write(&dir, "a.yaml", &osa);
+    let output = validator(&["validate", &glob_for(&dir)]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("osa does not satisfy the triangle inequality"));
+}
+
+#[cfg(unix)]
+#[test]
+fn cross_check_compares_against_a_long_lived_reference() {
+    let dir = scratch_dir("cross-check");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    let reference = |response: &str| format!("while read -r line; do echo '{}'; done", response);
+
+    let agreeing = reference(r#"{"distance": 3, "score": 0.5714285714285714}"#);
+    let output = validator(&["cross-check", &glob_for(&dir), "--command", &agreeing]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let disagreeing = reference(r#"{"distance": 4, "score": 0.5714285714285714}"#);
+    let output = validator(&["cross-check", &glob_for(&dir), "--command", &disagreeing]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("external: distance=4"));
+
+    let output = validator(&[
+        "cross-check",
+        &glob_for(&dir),
+        "--command",
+        "sleep 30",
+        "--timeout",
+        "1",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("timed out"));
+}
+
+#[cfg(unix)]
+#[test]
+fn validate_target_wasm_checks_the_runner_output() {
+    let dir = scratch_dir("validate-wasm");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+    let runner = |distance: u32| {
+        format!(
+            r#"while read -r line; do case "$line" in *normalized_levenshtein*) echo '{{"value": 0.5714285714285714}}';; *) echo '{{"value": {}}}';; esac; done"#,
+            distance
+        )
+    };
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--target",
+        "wasm",
+        "--node-runner",
+        &runner(3),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--target",
+        "wasm",
+        "--node-runner",
+        &runner(4),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("distance=4"));
+    assert!(stdout.contains("--target wasm"));
+}
+
+#[test]
+fn coverage_fails_for_registered_metrics_below_the_minimum() {
+    let dir = scratch_dir("coverage");
+    write(&dir, "a.yaml", PASSING_FIXTURE);
+
+    // One levenshtein case: every other registered metric has none
+    let output = validator(&["coverage", &glob_for(&dir), "--min-cases", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("✗ jaro_winkler: 0 of 1 cases"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("✗ levenshtein"), "{}", stdout);
+    // Unified metrics outside the exports are gated too
+    assert!(stdout.contains("✗ hamming: 0 of 1 cases"), "{}", stdout);
+
+    let output = validator(&["coverage", &glob_for(&dir), "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json report");
+    let levenshtein = report
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|metric| metric["metric"] == "levenshtein")
+        .expect("registered");
+    assert_eq!(levenshtein["cases"], 1);
+
+    // Exempting everything else passes (comments allowed); an unknown name is an error
+    let others: String = report
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|metric| metric["metric"].as_str().unwrap())
+        .filter(|name| *name != "levenshtein")
+        .map(|name| format!("{}  # pending\n", name))
+        .collect();
+    write(&dir, "allow.txt", &others);
+    let allowlist = dir.join("allow.txt");
+    let allowlist = allowlist.to_str().unwrap();
+    let args = [
+        "coverage",
+        &glob_for(&dir),
+        "--min-cases",
+        "1",
+        "--allowlist",
+        allowlist,
+    ];
+    assert_eq!(validator(&args).status.code(), Some(0));
+
+    write(&dir, "allow.txt", "levenstein\n");
+    let output = validator(&args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("levenstein"), "{}", stderr);
+}
+
+#[test]
+fn gh_annotations_point_at_the_fixture_file_on_one_line() {
+    let dir = scratch_dir("gh-annotations");
+    let long = "x".repeat(500);
+    write(
+        &dir,
+        "a.yaml",
+        &format!(
+            r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: normalization_presets
+    cases:
+      - input: "{long}"
+        preset: minimal
+        expected: "{long}y"
+        description: "Long value, with 100% newline\nin the description"
+"#
+        ),
+    );
+    let output = validator(&["validate", &glob_for(&dir), "--format", "gh-annotations"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    let annotations: Vec<&str> = stdout
+        .lines()
+        .filter(|l| l.starts_with("::error"))
+        .collect();
+    assert_eq!(annotations.len(), 1, "{}", stdout);
+    let annotation = annotations[0];
+    assert!(
+        annotation.starts_with(&format!(
+            "::error file={}/a.yaml,title=normalization_presets::Long value, with 100%25 newline%0Ain",
+            dir.display().to_string().replace(':', "%3A").replace(',', "%2C")
+        )),
+        "{}",
+        annotation
+    );
+    assert!(
+        annotation.contains("… (303 more chars), got"),
+        "{}",
+        annotation
+    );
+    // The normal summary still prints
+    assert!(stdout.contains("FAILURES:"), "{}", stdout);
+
+    // Detected from the environment unless opted out
+    let run = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_similarity-validator"))
+            .args(["validate", &glob_for(&dir)])
+            .args(extra)
+            .env("NO_COLOR", "1")
+            .env("GITHUB_ACTIONS", "true")
+            .output()
+            .expect("run similarity-validator")
+    };
+    assert!(String::from_utf8_lossy(&run(&[]).stdout).contains("::error file="));
+    assert!(!String::from_utf8_lossy(&run(&["--no-annotations"]).stdout).contains("::error"));
+}
+
+#[test]
+fn verify_integrity_flags_hand_edits_but_not_reformatting() {
+    let dir = scratch_dir("integrity");
+    let signed = PASSING_FIXTURE.replace("test_cases:", "checksums: true\ntest_cases:");
+    write(&dir, "a.yaml", &signed);
+    write(&dir, "unsigned.yaml", FAILING_FIXTURE);
+    let path = dir.join("a.yaml");
+    let path = path.to_str().unwrap();
+
+    // Unsigned cases are reported until generate writes their checksums
+    assert_eq!(
+        validator(&["verify-integrity", &glob_for(&dir)])
+            .status
+            .code(),
+        Some(1)
+    );
+    assert!(validator(&["generate", "--input", path]).status.success());
+    let generated = fs::read_to_string(path).unwrap();
+    assert!(generated.contains("checksum: "), "{}", generated);
+    let output = validator(&["verify-integrity", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Files signed:    1"), "{}", stdout);
+
+    // Reformatting keeps the checksum valid
+    let json = dir.join("a.json");
+    let json = json.to_str().unwrap();
+    assert!(validator(&["convert", "--input", path, "--output", json])
+        .status
+        .success());
+    assert_eq!(
+        validator(&["verify-integrity", json]).status.code(),
+        Some(0)
+    );
+
+    // A hand edit is flagged, and a plain regenerate doesn't bless it
+    write(
+        &dir,
+        "a.yaml",
+        &generated.replace("expected_distance: 3", "expected_distance: 4"),
+    );
+    let output = validator(&["verify-integrity", &glob_for(&dir)]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("Classic Levenshtein example"), "{}", stdout);
+    assert!(validator(&["generate", "--input", path]).status.success());
+    assert_eq!(
+        validator(&["verify-integrity", &glob_for(&dir)])
+            .status
+            .code(),
+        Some(1)
+    );
+    assert!(validator(&["generate", "--input", path, "--overwrite"])
+        .status
+        .success());
+    assert_eq!(
+        validator(&["verify-integrity", &glob_for(&dir)])
+            .status
+            .code(),
+        Some(0)
+    );
+}
+
+#[test]
+fn failures_carry_a_repro_command_that_reruns_only_that_case() {
+    let dir = scratch_dir("repro");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: levenshtein
+    cases:
+      - input_a: kitten
+        input_b: sitting
+        expected_distance: 3
+        description: Passes
+      - input_a: café
+        input_b: cafe
+        expected_distance: 99
+        description: "It's \"café\" — $HOME `pwd` ✓"
+      - input_a: abc
+        input_b: abd
+        expected_distance: 99
+        description: "-starts with a dash"
+"#,
+    );
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "json",
+        "--output",
+        "-",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("report JSON");
+    let cases = report["cases"].as_array().unwrap();
+    assert!(cases[0]["repro"].is_null());
+    let repros: Vec<&str> = cases[1..]
+        .iter()
+        .map(|case| case["repro"].as_str().expect("repro on failure"))
+        .collect();
+    assert!(
+        repros[0].contains(r#"--case-index 1 --description='It'\''s "café" — $HOME `pwd` ✓'"#),
+        "{}",
+        repros[0]
+    );
+
+    // Each command runs through a real shell and selects exactly its own case
+    for (repro, description) in repros.iter().zip(["It's \"café\"", "-starts with a dash"]) {
+        let output = Command::new("sh")
+            .args(["-c", repro])
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("run repro");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(output.status.code(), Some(1), "{}\n{}", repro, stdout);
+        assert!(stdout.contains("Total tests:     1\n"), "{}", stdout);
+        assert!(stdout.contains("Filtered out:    2\n"), "{}", stdout);
+        assert!(stdout.contains(description), "{}", stdout);
+    }
+
+    let output = validator(&[
+        "validate",
+        &glob_for(&dir),
+        "--format",
+        "junit",
+        "--output",
+        "-",
+    ]);
+    let junit = String::from_utf8_lossy(&output.stdout);
+    assert!(junit.contains("Repro:    "), "{}", junit);
+}
+
+#[test]
+fn canonical_arithmetic_regenerates_stored_reference_values() {
+    let dir = scratch_dir("canonical-arithmetic");
+    write(
+        &dir,
+        "a.yaml",
+        r#"$schema: https://schemas.fulmenhq.dev/library/foundry/v2.0.0/similarity.schema.json
+version: 2025.10.3
+test_cases:
+  - category: jaro_winkler_params
+    cases:
+      - input_a: transformation
+        input_b: transformative
+        prefix_scale: 0.1
+        max_prefix: 8
+        description: Winkler adjustment
+  - category: jaro_winkler_tokens
+    cases:
+      - input_a: John Smith Jr
+        input_b: John Smyth
+        prefix_scale: 0.25
+        description: Token Winkler adjustment
+  - category: substring
+    cases:
+      - needle: hello
+        haystack: say hello world
+        description: Substring score
+  - category: suggestions
+    cases:
+      - input: tr
+        candidates: [transformation]
+        options:
+          metric: jaro_winkler
+          min_score: 0.0
+          prefer_prefix: true
+          prefix_bonus_weight: 0.1
+        description: Prefix bonus
+"#,
+    );
+    let path = dir.join("a.yaml");
+    let path = path.to_str().unwrap();
+    assert!(validator(&["generate", "--input", path, "--overwrite"])
+        .status
+        .success());
+
+    // Computed once in canonical mode; native arithmetic gives 0.980952380952381 and
+    // 0.7942857142857143 for the first and last, so any drift shows up as a different digit
+    let text = fs::read_to_string(path).unwrap();
+    for line in [
+        "arithmetic: canonical",
+        "expected_score: 0.9809523809523809",
+        "expected_score: 0.7083333333333333",
+        "expected_score: 0.5",
+        "score: 0.7942857142857144",
+    ] {
+        assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+    }
+}
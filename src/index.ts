@@ -3,6 +3,111 @@ import wasm from './wasm.js';
 export type NormalizationPreset = 'none' | 'minimal' | 'default' | 'aggressive';
 export type NormalizationLocale = 'tr' | 'az' | 'lt';
 
+// ============================================================================
+// Module Defaults - Options used when a call leaves them out
+// ============================================================================
+
+export interface DefaultOptions {
+  /** Normalization preset for the unified, pair, suggest, extract and match_profile APIs */
+  preset?: NormalizationPreset;
+  /** Locale for the preset's case folding */
+  locale?: NormalizationLocale;
+  /** Unit of indices and ranges (alignment, substring, coverage and excerpt APIs) */
+  rangeUnit?: 'char' | 'utf16';
+  range_unit?: 'char' | 'utf16';
+  /**
+   * Decimal places returned scores are rounded to (integer 0-15). Cutoffs and ranking still
+   * use the unrounded scores.
+   */
+  scorePrecision?: number;
+  score_precision?: number;
+  /** Longest input accepted, in UTF-16 code units (string length); longer inputs throw */
+  maxInputLength?: number;
+  max_input_length?: number;
+}
+
+// The defaults live in the WASM module, and each property asks it afresh, so defaults set
+// through the raw `wasm` exports apply here too
+const moduleDefaults = {
+  get preset(): NormalizationPreset | undefined {
+    return wasm.default_preset() as NormalizationPreset | undefined;
+  },
+  get locale(): NormalizationLocale | undefined {
+    return wasm.default_locale() as NormalizationLocale | undefined;
+  },
+  get rangeUnit(): 'char' | 'utf16' | undefined {
+    return wasm.default_range_unit() as 'char' | 'utf16' | undefined;
+  },
+  get scorePrecision(): number | undefined {
+    return wasm.default_score_precision();
+  },
+  get maxInputLength(): number | undefined {
+    return wasm.default_max_input_length();
+  },
+};
+
+/**
+ * Set the options every call falls back to when it leaves them out; options passed to a call
+ * always win. Replaces all previous defaults, so spread `get_default_options()` to change one.
+ * Throws on an unknown key or an invalid value, leaving the previous defaults in place.
+ *
+ * The defaults live in the WASM module, so its pair and batch exports and `Comparator` read
+ * them too when called directly.
+ */
+export function set_default_options(options: DefaultOptions): void {
+  wasm.set_default_options(options);
+}
+
+/** A copy of the current defaults, with camelCase keys and unset ones omitted */
+export function get_default_options(): DefaultOptions {
+  return wasm.get_default_options() as DefaultOptions;
+}
+
+/** Clear every default, restoring each API's own */
+export function reset_default_options(): void {
+  wasm.reset_default_options();
+}
+
+// Throws when an input is longer than the maxInputLength default
+const checkInputLengths = (inputs: readonly string[]): void => {
+  const max = moduleDefaults.maxInputLength;
+  if (max === undefined) {
+    return;
+  }
+  for (const input of inputs) {
+    if (input.length > max) {
+      throw new Error(`Input exceeds maxInputLength (${input.length} > ${max})`);
+    }
+  }
+};
+
+// wasm-bindgen hands each unpaired surrogate to Rust as U+FFFD, so by default '\uD800' and
+// '\uFFFD' compare equal. With strictUnicode the JS strings are checked first and throw instead.
+const checkWellFormed = (inputs: readonly string[], strictUnicode: boolean | undefined): void => {
+  if (!strictUnicode) {
+    return;
+  }
+  for (const input of inputs) {
+    wasm.check_well_formed(input);
+  }
+};
+
+// A returned score rounded to the scorePrecision default
+const roundScore = (value: number): number => {
+  const precision = moduleDefaults.scorePrecision;
+  if (precision === undefined) {
+    return value;
+  }
+  const factor = 10 ** precision;
+  return Math.round(value * factor) / factor;
+};
+
+const roundScores = (scores: Float64Array): Float64Array =>
+  moduleDefaults.scorePrecision === undefined ? scores : scores.map(roundScore);
+
+const usesUtf16 = (rangeUnit: 'char' | 'utf16' | undefined): boolean =>
+  (rangeUnit ?? moduleDefaults.rangeUnit) === 'utf16';
+
 export function levenshtein(a: string, b: string): number {
   return wasm.levenshtein(a, b);
 }
@@ -11,6 +116,56 @@ export function normalized_levenshtein(a: string, b: string): number {
   return wasm.normalized_levenshtein(a, b);
 }
 
+/** Bytes, or a string taken as its UTF-8 encoding */
+export type BytesInput = Uint8Array | string;
+
+const utf8Encoder = new TextEncoder();
+
+const toBytes = (input: BytesInput): Uint8Array =>
+  typeof input === 'string' ? utf8Encoder.encode(input) : input;
+
+/**
+ * Levenshtein distance over bytes, for base64 tokens, hex digests and `Uint8Array` output of
+ * crypto APIs. Every byte is one symbol, so for non-ASCII text this is NOT `levenshtein`:
+ * `'é'` against `'e'` is two edits here and one there.
+ */
+export function levenshtein_bytes(a: BytesInput, b: BytesInput): number {
+  return wasm.levenshtein_bytes(toBytes(a), toBytes(b));
+}
+
+/** `1 - levenshtein_bytes / max(byte length)`; two empty inputs score 1 */
+export function normalized_levenshtein_bytes(a: BytesInput, b: BytesInput): number {
+  return wasm.normalized_levenshtein_bytes(toBytes(a), toBytes(b));
+}
+
+/**
+ * Positions (code points) that differ, for fixed-length identifiers such as SKUs or hex
+ * hashes. Unequal lengths throw unless `pad` is set, in which case each extra character of
+ * the longer string counts once.
+ */
+export function hamming(a: string, b: string, pad = false): number {
+  return wasm.hamming_with_padding(a, b, pad);
+}
+
+/** `1 - hamming / max(length)`; two empty strings score 1 */
+export function normalized_hamming_similarity(a: string, b: string, pad = false): number {
+  return wasm.normalized_hamming_similarity_with_padding(a, b, pad);
+}
+
+/**
+ * Byte positions that differ. Unequal lengths throw unless `pad` is set, in which case each
+ * extra byte of the longer input counts once. Not equivalent to a char-based Hamming distance
+ * for non-ASCII text.
+ */
+export function hamming_bytes(a: BytesInput, b: BytesInput, pad = false): number {
+  return wasm.hamming_bytes(toBytes(a), toBytes(b), pad);
+}
+
+/** `1 - hamming_bytes / max(byte length)`; two empty inputs score 1 */
+export function normalized_hamming_bytes(a: BytesInput, b: BytesInput, pad = false): number {
+  return wasm.normalized_hamming_bytes(toBytes(a), toBytes(b), pad);
+}
+
 export function osa_distance(a: string, b: string): number {
   return wasm.osa_distance(a, b);
 }
@@ -27,6 +182,93 @@ export function normalized_damerau_levenshtein(a: string, b: string): number {
   return wasm.normalized_damerau_levenshtein(a, b);
 }
 
+/**
+ * Damerau-Levenshtein distance over extended grapheme clusters, so a ZWJ emoji
+ * sequence, a flag or a letter with combining marks counts as one unit
+ */
+export function damerau_levenshtein_graphemes(a: string, b: string): number {
+  return wasm.damerau_levenshtein_graphemes(a, b);
+}
+
+export function normalized_damerau_levenshtein_graphemes(a: string, b: string): number {
+  return wasm.normalized_damerau_levenshtein_graphemes(a, b);
+}
+
+/**
+ * Damerau-Levenshtein total cost with transpositions charged `transpositionCost`
+ *
+ * The cost is clamped to (0, 2]. At 1.0 the result equals `damerau_levenshtein` exactly.
+ */
+export function damerau_levenshtein_weighted(
+  a: string,
+  b: string,
+  transpositionCost: number,
+): number {
+  return wasm.damerau_levenshtein_weighted(a, b, transpositionCost);
+}
+
+export function normalized_damerau_levenshtein_weighted(
+  a: string,
+  b: string,
+  transpositionCost: number,
+): number {
+  return wasm.normalized_damerau_levenshtein_weighted(a, b, transpositionCost);
+}
+
+/**
+ * Most-frequent-k-characters similarity (0-1): the summed counts of the characters both strings'
+ * k most frequent characters share, over the total of both. Very cheap, for a first pass over a
+ * large corpus. With `k = 0` every character counts, and the score is never below
+ * `indel_normalized_similarity`, so filtering on it never drops a pair whose indel similarity
+ * (or `ratio() / 100`) reaches the same cutoff.
+ */
+export function mfc_similarity(a: string, b: string, k: number): number {
+  return wasm.mfc_similarity(a, b, k);
+}
+
+/**
+ * Storable signature for `mfc_signature_similarity`: each kept character followed by its count,
+ * most frequent first (ties by first appearance), joined by ':' — `mfc_signature('research', 2)`
+ * is `'r2:e2'`
+ */
+export function mfc_signature(input: string, k: number): string {
+  return wasm.mfc_signature(input, k);
+}
+
+/** `mfc_similarity` from two precomputed signatures; throws on a malformed signature */
+export function mfc_signature_similarity(a: string, b: string): number {
+  return wasm.mfc_signature_similarity(a, b);
+}
+
+/**
+ * Affine-gap edit cost (Gotoh): `mismatch` per substitution, and a gap of k inserted or deleted
+ * characters costs `gapOpen + (k - 1) * gapExtend`, so one long gap costs less than many short
+ * ones. Penalties must be positive with `gapExtend <= gapOpen`, otherwise this throws.
+ */
+export function affine_gap_distance(
+  a: string,
+  b: string,
+  mismatch: number,
+  gapOpen: number,
+  gapExtend: number,
+): number {
+  return wasm.affine_gap_distance(a, b, mismatch, gapOpen, gapExtend);
+}
+
+/**
+ * `1 - cost / worst case` for `affine_gap_distance`, where the worst case deletes all of `a` and
+ * inserts all of `b` as two gaps
+ */
+export function normalized_affine_gap_similarity(
+  a: string,
+  b: string,
+  mismatch: number,
+  gapOpen: number,
+  gapExtend: number,
+): number {
+  return wasm.normalized_affine_gap_similarity(a, b, mismatch, gapOpen, gapExtend);
+}
+
 export function jaro(a: string, b: string): number {
   return wasm.jaro(a, b);
 }
@@ -45,12 +287,150 @@ export function jaro_winkler_custom(
   return wasm.jaro_winkler_with_params(a, b, prefixScale, maxPrefix);
 }
 
+/**
+ * Jaro similarity over tokens instead of characters
+ *
+ * Two levels: each token is one symbol in the outer Jaro, and two tokens count as
+ * equal when their character Jaro-Winkler exceeds `match_threshold` (default 0.9,
+ * clamped to 0-1; 1.0 requires exact tokens). The matching window is Jaro's but at
+ * least one token wide, so "Garcia Maria" vs "Maria Garcia" still matches both tokens.
+ * `tokenizer` splits as in `word_diff` (default: 'whitespace').
+ */
+export function jaro_tokens(
+  a: string,
+  b: string,
+  options: { match_threshold?: number; tokenizer?: WordDiffOptions['tokenizer'] } = {},
+): number {
+  return wasm.jaro_tokens(a, b, options.match_threshold ?? 0.9, options.tokenizer ?? 'whitespace');
+}
+
+/**
+ * Token-level Jaro-Winkler: `jaro_tokens` plus a boost of `prefix_scale` (default 0.1,
+ * clamped to 0-0.25) per leading matching token, up to four tokens
+ */
+export function jaro_winkler_tokens(
+  a: string,
+  b: string,
+  options: {
+    prefix_scale?: number;
+    match_threshold?: number;
+    tokenizer?: WordDiffOptions['tokenizer'];
+  } = {},
+): number {
+  const prefixScale = options.prefix_scale ?? 0.1;
+  const matchThreshold = options.match_threshold ?? 0.9;
+  const tokenizer = options.tokenizer ?? 'whitespace';
+  return wasm.jaro_winkler_tokens(a, b, prefixScale, matchThreshold, tokenizer);
+}
+
+/** Token → weight, as an object, a Map or `[token, weight]` pairs */
+export type TokenWeights =
+  | Readonly<Record<string, number>>
+  | ReadonlyMap<string, number>
+  | ReadonlyArray<readonly [string, number]>;
+
+export interface WeightedJaccardOptions {
+  /** Normalize tokens before comparing them; default 'none' compares them exactly */
+  preset?: NormalizationPreset;
+  /** Locale for the preset's case folding */
+  locale?: string;
+}
+
+const tokenWeightEntries = (weights: TokenWeights): ReadonlyArray<readonly [string, number]> => {
+  if (Array.isArray(weights)) {
+    return weights;
+  }
+  return weights instanceof Map ? Array.from(weights) : Object.entries(weights);
+};
+
+/**
+ * Weighted Jaccard of two token → weight maps, e.g. tags with caller-supplied importance:
+ * the sum of the smaller weight of each token over the sum of the larger, across both maps.
+ * A token missing from one side weighs 0 there. Tokens are compared exactly, so normalize
+ * them first or pass `options.preset`; a token that appears twice (or after normalization)
+ * keeps its larger weight. Weights must be finite and non-negative. Returns 1 when every
+ * weight is 0, including when both maps are empty.
+ */
+export function weighted_jaccard(
+  a: TokenWeights,
+  b: TokenWeights,
+  options: WeightedJaccardOptions = {},
+): number {
+  const [entriesA, entriesB] = [tokenWeightEntries(a), tokenWeightEntries(b)];
+  return wasm.weighted_jaccard(
+    entriesA.map(([token]) => token),
+    Float64Array.from(entriesA, ([, weight]) => weight),
+    entriesB.map(([token]) => token),
+    Float64Array.from(entriesB, ([, weight]) => weight),
+    options.preset ?? 'none',
+    options.locale,
+  );
+}
+
+export interface NormalizeOptions {
+  /**
+   * Remove bidi controls (e.g. a right-to-left override) and other default-ignorable code
+   * points such as zero-width spaces and soft hyphens. Default: on for the 'default' and
+   * 'aggressive' presets, off for 'minimal'; 'none' never changes the input.
+   */
+  stripIgnorables?: boolean;
+  /** Keep ZWNJ and ZWJ while stripping (Persian and Indic spelling, emoji sequences) */
+  keepJoiners?: boolean;
+  /** Apply `fold_numeric_tokens()` first, so '1,000.50' and '1 000,50' compare equal */
+  foldNumbers?: boolean;
+  /** Abbreviations to rewrite after the preset (see `Expansions`); 'none' ignores them */
+  expansions?: Expansions;
+}
+
+/**
+ * Abbreviation dictionary, e.g. `{ St: 'Street', Intl: 'International' }`. Keys match whole
+ * words case-insensitively ('St.' matches 'St' and keeps the period) and may span several
+ * words; replacements are normalized with the same preset. One left-to-right pass: the key
+ * covering the most words wins, and replacements are never expanded again. Keys equal after
+ * case folding throw.
+ */
+export type Expansions = Record<string, string>;
+
+// Expansions as WASM takes them: [key, replacement, key, replacement, ...]
+const flattenExpansions = (expansions: Expansions | undefined): string[] =>
+  Object.entries(expansions ?? {}).flat();
+
+/**
+ * Rewrite numbers with grouping separators or a decimal comma as plain digits with a '.'
+ * decimal point, leaving other text unchanged: '1,000.50', '1.000,50' and '1 000,50' all
+ * become '1000.50'. Separators are only grouping when the digit groups fit three-digit grouping
+ * of one kind, so '1,000' becomes '1000'; otherwise a lone ',' or '.' is the decimal separator
+ * ('3,14' becomes '3.14'). A plain space only groups a number with a decimal part, so '555 123'
+ * stays two numbers. Tokens with no reading (dates like '12.03.2024', versions like '1.2.3')
+ * stay as written.
+ */
+export function fold_numeric_tokens(input: string): string {
+  return wasm.fold_numeric_tokens(input);
+}
+
+/**
+ * `target` in the casing of `pattern`, for presenting a correction the way the user typed it:
+ * `transfer_case('LEVENSHTIEN', 'levenshtein')` is `'LEVENSHTEIN'`. An all-lower, all-upper or
+ * title-case pattern has that style applied to the whole target, whatever the lengths; any other
+ * pattern is copied char by char along the Levenshtein alignment, with unmatched target chars
+ * taking the case of the pattern char before them. Uppercasing 'ß' gives 'SS'. With locale 'tr'
+ * or 'az', i and İ (and ı and I) are case pairs.
+ */
+export function transfer_case(
+  pattern: string,
+  target: string,
+  locale?: NormalizationLocale,
+): string {
+  return wasm.transfer_case(pattern, target, locale ?? moduleDefaults.locale);
+}
+
 /**
  * Normalize a string using the specified preset and optional locale
  *
  * @param input - The string to normalize
  * @param preset - Normalization preset (none, minimal, default, aggressive)
  * @param locale - Optional locale for locale-specific case folding (tr, az, lt)
+ * @param options - Ignorable stripping, number folding and expansions (see `NormalizeOptions`)
  * @returns Normalized string
  *
  * Locale-specific behavior:
@@ -62,13 +442,247 @@ export function normalize(
   input: string,
   preset: NormalizationPreset = 'none',
   locale?: NormalizationLocale,
+  options: NormalizeOptions = {},
 ): string {
+  const expansions = flattenExpansions(options.expansions);
+  if (
+    options.stripIgnorables !== undefined ||
+    options.keepJoiners ||
+    options.foldNumbers ||
+    expansions.length > 0
+  ) {
+    return wasm.normalize_with_options(
+      input,
+      preset,
+      locale,
+      options.stripIgnorables,
+      options.keepJoiners ?? false,
+      options.foldNumbers ?? false,
+      expansions,
+    );
+  }
   if (locale !== undefined) {
     return wasm.normalize_with_locale(input, preset, locale);
   }
   return wasm.normalize(input, preset);
 }
 
+/**
+ * Whether case folding would change `input` under any locale. False for text with no cased chars
+ * (Chinese, Japanese, Thai, digits) and for already-folded text; the `default` preset skips its
+ * per-char fold for such input, and callers can use it to skip re-normalizing.
+ */
+export function needs_case_fold(input: string): boolean {
+  return wasm.needs_case_fold(input);
+}
+
+/**
+ * Whether `a` and `b` normalize to the same string. Cheaper than comparing two `normalize()`
+ * results: nothing is built or copied back to JS.
+ */
+export function equals_normalized(
+  a: string,
+  b: string,
+  preset: NormalizationPreset = 'none',
+  locale?: NormalizationLocale,
+  options: NormalizeOptions = {},
+): boolean {
+  return wasm.equals_normalized(
+    a, b,
+    preset,
+    locale,
+    options.stripIgnorables,
+    options.keepJoiners ?? false,
+    options.foldNumbers ?? false,
+    flattenExpansions(options.expansions),
+  );
+}
+
+/**
+ * Stable key for bucketing strings by normalized form (the same string `normalize()` returns).
+ * Keys only change when `NORMALIZED_HASH_VERSION` does.
+ */
+export function normalized_key(
+  input: string,
+  preset: NormalizationPreset = 'none',
+  locale?: NormalizationLocale,
+  options: NormalizeOptions = {},
+): string {
+  return wasm.normalized_key(
+    input,
+    preset,
+    locale,
+    options.stripIgnorables,
+    options.keepJoiners ?? false,
+    options.foldNumbers ?? false,
+    flattenExpansions(options.expansions),
+  );
+}
+
+/**
+ * 64-bit FNV-1a hash of `normalized_key()`. Identical on every platform and release that
+ * reports the same `NORMALIZED_HASH_VERSION`; store the version next to cached hashes.
+ */
+export function normalized_hash(
+  input: string,
+  preset: NormalizationPreset = 'none',
+  locale?: NormalizationLocale,
+  options: NormalizeOptions = {},
+): bigint {
+  return wasm.normalized_hash(
+    input,
+    preset,
+    locale,
+    options.stripIgnorables,
+    options.keepJoiners ?? false,
+    options.foldNumbers ?? false,
+    flattenExpansions(options.expansions),
+  );
+}
+
+/** Bumped whenever `normalized_key()` or `normalized_hash()` output changes for any input */
+export const NORMALIZED_HASH_VERSION: number = wasm.normalized_hash_version();
+
+/**
+ * Fingerprint of `normalize()` output for a preset and locale, derived from
+ * `NORMALIZED_HASH_VERSION`. Store it with caches of normalized candidates or indexes built from
+ * them, and rebuild them when the current build returns a different value.
+ */
+export function normalization_fingerprint(
+  preset: NormalizationPreset,
+  locale?: NormalizationLocale,
+): string {
+  return wasm.normalization_fingerprint(preset, locale);
+}
+
+/** Thrown by `CandidateSet.fromBytes` for bytes built with another normalization fingerprint */
+export class FingerprintMismatchError extends Error {
+  readonly code = 'FINGERPRINT_MISMATCH';
+
+  constructor(
+    /** The fingerprint the bytes were built with */
+    readonly stored: string,
+    /** What this build gives their preset and locale */
+    readonly current: string,
+  ) {
+    super(
+      `FINGERPRINT_MISMATCH: built with normalization fingerprint ${stored}, this build has ${current}`,
+    );
+    this.name = 'FingerprintMismatchError';
+  }
+}
+
+const FINGERPRINT_MISMATCH_MESSAGE =
+  /^FINGERPRINT_MISMATCH: built with normalization fingerprint (\S+), this build has (\S+)/;
+
+/**
+ * Candidates with their `normalize()` keys for one preset and locale, built once and cached as
+ * bytes. `toBytes` embeds the `normalization_fingerprint`, and `fromBytes` throws a
+ * `FingerprintMismatchError` when the current build gives a different one, so a cache never
+ * outlives a normalization change. Call `free` once done to release the WASM memory.
+ */
+export class CandidateSet {
+  private inner: InstanceType<typeof wasm.CandidateSet>;
+
+  constructor(
+    candidates: readonly string[],
+    preset: NormalizationPreset,
+    locale?: NormalizationLocale,
+  ) {
+    this.inner = new wasm.CandidateSet([...candidates], preset, locale);
+  }
+
+  /** A set from `toBytes` output; throws on malformed bytes or a fingerprint mismatch */
+  static fromBytes(bytes: Uint8Array): CandidateSet {
+    let inner: InstanceType<typeof wasm.CandidateSet>;
+    try {
+      inner = wasm.CandidateSet.from_bytes(bytes);
+    } catch (error) {
+      const match =
+        error instanceof Error ? FINGERPRINT_MISMATCH_MESSAGE.exec(error.message) : null;
+      if (match) {
+        throw new FingerprintMismatchError(match[1], match[2]);
+      }
+      throw error;
+    }
+    const set = Object.create(CandidateSet.prototype) as CandidateSet;
+    set.inner = inner;
+    return set;
+  }
+
+  toBytes(): Uint8Array {
+    return this.inner.to_bytes();
+  }
+
+  get preset(): NormalizationPreset {
+    return this.inner.preset() as NormalizationPreset;
+  }
+
+  get locale(): NormalizationLocale | undefined {
+    return this.inner.locale() as NormalizationLocale | undefined;
+  }
+
+  get candidates(): string[] {
+    return this.inner.candidates();
+  }
+
+  get keys(): string[] {
+    return this.inner.keys();
+  }
+
+  get fingerprint(): string {
+    return this.inner.fingerprint();
+  }
+
+  /** Indices of the candidates whose key equals the normalized query, in order */
+  lookup(query: string): number[] {
+    return Array.from(this.inner.lookup(query));
+  }
+
+  free(): void {
+    this.inner.free();
+  }
+}
+
+/**
+ * ISO 15924 code of the most frequent script in `input` (e.g. 'Latn', 'Cyrl', 'Thai'),
+ * ignoring Common (digits, punctuation, emoji) and Inherited (combining marks).
+ * Returns 'Zyyy' when there is no other script. Script tables cover the major scripts;
+ * characters outside them count as 'Zzzz' (Unknown).
+ */
+export function dominant_script(input: string): string {
+  return wasm.dominant_script(input);
+}
+
+/**
+ * Whether `input` contains bidi control characters (LRM, RLM, ALM, embeddings, overrides or
+ * isolates). A right-to-left override can disguise 'invoice\u202Efdp.exe' as 'invoiceexe.pdf'.
+ */
+export function contains_bidi_controls(input: string): boolean {
+  return wasm.contains_bidi_controls(input);
+}
+
+/** Whether more than one script appears, ignoring Common and Inherited (Han plus kana counts) */
+export function is_mixed_script(input: string): boolean {
+  return wasm.is_mixed_script(input);
+}
+
+let scriptCodes: string[] | undefined;
+
+/** Characters per script code in `input`, including Common ('Zyyy') and Inherited ('Zinh') */
+export function script_breakdown(input: string): Record<string, number> {
+  scriptCodes ??= wasm.script_codes();
+  const codes = scriptCodes;
+  const counts = wasm.script_counts(input);
+  const breakdown: Record<string, number> = {};
+  counts.forEach((count, i) => {
+    if (count > 0) {
+      breakdown[codes[i]] = count;
+    }
+  });
+  return breakdown;
+}
+
 // ============================================================================
 // RapidFuzz Fuzz Module - Ratio-based similarity (0-100 scale)
 // ============================================================================
@@ -125,6 +739,280 @@ export function lcs_seq_normalized_similarity(a: string, b: string): number {
   return wasm.lcs_seq_normalized_similarity(a, b);
 }
 
+// ============================================================================
+// RapidFuzz Distance Module - Prefix and Postfix metrics
+// ============================================================================
+
+/**
+ * Prefix distance - characters of the longer string outside the common prefix
+ */
+export function prefix_distance(a: string, b: string): number {
+  return wasm.prefix_distance(a, b);
+}
+
+/**
+ * Normalized prefix similarity (0.0-1.0 scale): common prefix length over the longer length
+ */
+export function prefix_normalized_similarity(a: string, b: string): number {
+  return wasm.prefix_normalized_similarity(a, b);
+}
+
+/**
+ * Postfix distance - characters of the longer string outside the common suffix
+ */
+export function postfix_distance(a: string, b: string): number {
+  return wasm.postfix_distance(a, b);
+}
+
+/**
+ * Normalized postfix similarity (0.0-1.0 scale): common suffix length over the longer length
+ */
+export function postfix_normalized_similarity(a: string, b: string): number {
+  return wasm.postfix_normalized_similarity(a, b);
+}
+
+// ============================================================================
+// N-gram set metrics
+// ============================================================================
+
+/**
+ * Sørensen-Dice similarity over character bigrams (0.0-1.0); repeated bigrams count once,
+ * and strings too short for a bigram only match themselves
+ */
+export function dice(a: string, b: string): number {
+  return wasm.dice(a, b);
+}
+
+/**
+ * Jaccard similarity over character n-grams (0.0-1.0), with `n` defaulting to 2; throws
+ * when `n` is below 1
+ */
+export function jaccard_ngram(a: string, b: string, options: { n?: number } = {}): number {
+  return wasm.jaccard_ngram(a, b, options.n ?? 2);
+}
+
+export interface AlignmentOptions {
+  /** Unit of the returned indices: code points (default) or UTF-16 code units (string indices) */
+  rangeUnit?: 'char' | 'utf16';
+}
+
+export interface IndelEditop {
+  tag: 'delete' | 'insert';
+  srcPos: number;
+  destPos: number;
+}
+
+// Tag codes of the flattened edit operations WASM returns
+const EDIT_TAGS = ['equal', 'replace', 'insert', 'delete'] as const;
+
+export interface LevenshteinEditop {
+  tag: 'replace' | 'insert' | 'delete';
+  srcPos: number;
+  destPos: number;
+}
+
+/** A run of one edit tag as half-open code point ranges of `a` (src) and `b` (dest) */
+export interface LevenshteinOpcode {
+  tag: 'equal' | 'replace' | 'insert' | 'delete';
+  srcStart: number;
+  srcEnd: number;
+  destStart: number;
+  destEnd: number;
+}
+
+/**
+ * Index pairs [i, j] (a[i] === b[j]) of one longest common subsequence, for highlighting
+ * which characters matched. The alignment is leftmost-greedy in `b`, and the number of
+ * pairs always equals lcs_seq_similarity(a, b).
+ */
+export function lcs_seq_matching_indices(
+  a: string,
+  b: string,
+  options: AlignmentOptions = {},
+): Array<[number, number]> {
+  const flat = wasm.lcs_seq_matching_indices(a, b, usesUtf16(options.rangeUnit));
+  const pairs: Array<[number, number]> = [];
+  for (let k = 0; k < flat.length; k += 2) {
+    pairs.push([flat[k], flat[k + 1]]);
+  }
+  return pairs;
+}
+
+/**
+ * Indel edit operations (deletes and inserts) turning `a` into `b`, derived from
+ * lcs_seq_matching_indices; the count always equals indel_distance(a, b)
+ */
+export function indel_editops(a: string, b: string, options: AlignmentOptions = {}): IndelEditop[] {
+  const flat = wasm.indel_editops(a, b, usesUtf16(options.rangeUnit));
+  const ops: IndelEditop[] = [];
+  for (let k = 0; k < flat.length; k += 3) {
+    ops.push({
+      tag: flat[k] === 1 ? 'insert' : 'delete',
+      srcPos: flat[k + 1],
+      destPos: flat[k + 2],
+    });
+  }
+  return ops;
+}
+
+/**
+ * Levenshtein edit operations turning `a` into `b`, at code point positions; the count
+ * always equals levenshtein(a, b). Where several alignments are optimal, the backtrace
+ * prefers equal, then replace, delete and insert from the end.
+ */
+export function levenshtein_editops(a: string, b: string): LevenshteinEditop[] {
+  const flat = wasm.levenshtein_editops(a, b);
+  const ops: LevenshteinEditop[] = [];
+  for (let k = 0; k < flat.length; k += 3) {
+    ops.push({
+      tag: EDIT_TAGS[flat[k]] as LevenshteinEditop['tag'],
+      srcPos: flat[k + 1],
+      destPos: flat[k + 2],
+    });
+  }
+  return ops;
+}
+
+/** `levenshtein_editops` grouped into runs, including the equal ones between them */
+export function levenshtein_opcodes(a: string, b: string): LevenshteinOpcode[] {
+  const flat = wasm.levenshtein_opcodes(a, b);
+  const ops: LevenshteinOpcode[] = [];
+  for (let k = 0; k < flat.length; k += 5) {
+    ops.push({
+      tag: EDIT_TAGS[flat[k]],
+      srcStart: flat[k + 1],
+      srcEnd: flat[k + 2],
+      destStart: flat[k + 3],
+      destEnd: flat[k + 4],
+    });
+  }
+  return ops;
+}
+
+export interface PositionalDiffOptions {
+  /**
+   * Pad the shorter string at its end instead of throwing on unequal lengths; the padding
+   * matches no character, so every padded position is reported as differing (default: false)
+   */
+  pad?: boolean;
+}
+
+export interface PositionalDiff {
+  /** Hamming distance: the number of differing positions */
+  distance: number;
+  /** Ascending code point positions where the strings differ */
+  differing_positions: Uint32Array;
+}
+
+/**
+ * Hamming distance with the positions behind it, for highlighting which characters of two
+ * fixed-width codes disagree. Throws when the lengths differ unless `pad` is set.
+ */
+export function positional_diff(
+  a: string,
+  b: string,
+  options: PositionalDiffOptions = {},
+): PositionalDiff {
+  const differing_positions = wasm.positional_diff(a, b, options.pad ?? false);
+  return { distance: differing_positions.length, differing_positions };
+}
+
+export type MatchClass = 'match' | 'fold_match' | 'substituted' | 'inserted';
+
+export interface MatchProfileOptions {
+  /** Normalization preset applied to both inputs first (default: 'none') */
+  preset?: NormalizationPreset;
+  locale?: NormalizationLocale;
+}
+
+const MATCH_CLASSES = ['match', 'fold_match', 'substituted', 'inserted'] as const;
+
+/**
+ * How each code point of `candidate` matched `query`, for shading a heatmap: 'match' (equal as
+ * typed), 'fold_match' (equal only after the preset, or removed by it), 'substituted' or
+ * 'inserted'. Both inputs are normalized a char and its combining marks at a time, aligned by
+ * Levenshtein distance, and each candidate char takes the class of the normalized chars it
+ * produced. The result has one entry per code point of `candidate`.
+ */
+export function match_profile(
+  query: string,
+  candidate: string,
+  options: MatchProfileOptions = {},
+): MatchClass[] {
+  checkInputLengths([query, candidate]);
+  const preset = options.preset ?? moduleDefaults.preset ?? 'none';
+  const locale = options.locale ?? moduleDefaults.locale;
+  const codes = wasm.match_profile(query, candidate, preset, locale);
+  return Array.from(codes, (code) => MATCH_CLASSES[code]);
+}
+
+export interface WordDiffOptions {
+  /**
+   * 'whitespace' (default) splits on runs of whitespace; 'words' splits at Unicode word
+   * boundaries, so punctuation marks are tokens of their own
+   */
+  tokenizer?: 'whitespace' | 'words';
+  /** Metric scoring two unequal tokens (default: 'levenshtein'; token metrics are not supported) */
+  metric?: SimilarityMetric;
+  /** Score unequal tokens need to align as a `replace` (default: 0.6; above 1 disables) */
+  threshold?: number;
+}
+
+/** A token and the whitespace after it; leading whitespace is a token with empty text */
+export interface WordToken {
+  text: string;
+  whitespace: string;
+}
+
+export interface WordDiffOp {
+  op: 'equal' | 'insert' | 'delete' | 'replace';
+  a_tokens: WordToken[];
+  b_tokens: WordToken[];
+  /** Similarity of the two tokens of a replacement */
+  score?: number;
+}
+
+const toWordTokens = (flat: string[]): WordToken[] => {
+  const tokens: WordToken[] = [];
+  for (let k = 0; k < flat.length; k += 2) {
+    tokens.push({ text: flat[k], whitespace: flat[k + 1] });
+  }
+  return tokens;
+};
+
+/**
+ * Word-level diff for change tracking. Tokens align by their longest common subsequence, where
+ * two unequal tokens scoring at least `threshold` also align and come back as one `replace` with
+ * their score instead of a delete and an insert. Whitespace stays attached to the token before
+ * it, so joining `text + whitespace` over the b side of every op but `delete` gives `b` exactly
+ * (and over the a side of every op but `insert`, `a`).
+ */
+export function word_diff(a: string, b: string, options: WordDiffOptions = {}): WordDiffOp[] {
+  checkInputLengths([a, b]);
+  const tokenizer = options.tokenizer ?? 'whitespace';
+  const normalizedMetric = normalizeSimilarityMetric(options.metric ?? 'levenshtein');
+  const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+  if (coreMetric === undefined) {
+    throw new Error(`Unsupported word diff metric: ${normalizedMetric}`);
+  }
+  const aTokens = toWordTokens(wasm.word_tokens(a, tokenizer));
+  const bTokens = toWordTokens(wasm.word_tokens(b, tokenizer));
+  const flat = wasm.word_diff(a, b, tokenizer, coreMetric, options.threshold ?? 0.6);
+  const ops: WordDiffOp[] = [];
+  for (let k = 0; k < flat.length; k += 6) {
+    const op: WordDiffOp = {
+      op: EDIT_TAGS[flat[k]],
+      a_tokens: aTokens.slice(flat[k + 1], flat[k + 2]),
+      b_tokens: bTokens.slice(flat[k + 3], flat[k + 4]),
+    };
+    if (!Number.isNaN(flat[k + 5])) {
+      op.score = roundScore(flat[k + 5]);
+    }
+    ops.push(op);
+  }
+  return ops;
+}
+
 // ============================================================================
 // Token-based Fuzzy Matching (TypeScript implementations)
 // ============================================================================
@@ -209,47 +1097,296 @@ export function tokenSetRatio(a: string, b: string): number {
   const setA = new Set(tokensA);
   const setB = new Set(tokensB);
 
-  // Find intersection (common tokens)
-  const intersection = new Set([...setA].filter((token) => setB.has(token)));
+  // Find intersection (common tokens)
+  const intersection = new Set([...setA].filter((token) => setB.has(token)));
+
+  // Find differences (unique to each set)
+  const diffA = [...setA].filter((token) => !intersection.has(token));
+  const diffB = [...setB].filter((token) => !intersection.has(token));
+
+  const intersectionStr = [...intersection].sort().join(' ');
+  const diffAStr = diffA.sort().join(' ');
+  const diffBStr = diffB.sort().join(' ');
+
+  // Build strings for comparison
+  const sortedIntersection = intersectionStr;
+  const strA = sortedIntersection + (diffAStr ? ` ${diffAStr}` : '');
+  const strB = sortedIntersection + (diffBStr ? ` ${diffBStr}` : '');
+
+  // Compare: intersection vs intersection, full vs full, and intersection vs each full
+  const scores = [ratio(strA, strB)];
+
+  // Only include intersection comparisons if there's a non-empty intersection
+  if (sortedIntersection.length > 0) {
+    scores.push(ratio(sortedIntersection, sortedIntersection));
+    scores.push(ratio(sortedIntersection, strA));
+    scores.push(ratio(sortedIntersection, strB));
+  }
+
+  return Math.max(...scores);
+}
+
+// ============================================================================
+// Process Module - Finding best matches from a list (TypeScript implementations)
+// ============================================================================
+
+export type ScorerFunction = (a: string, b: string) => number;
+
+/**
+ * How `suggest` and `extract` score a query against a candidate:
+ * - `'whole'`: the whole query against the whole candidate
+ * - `'tokens'`: each whitespace token of the query against its best-matching candidate
+ *   token, combined with a `TokenAggregation`
+ */
+export type QueryMode = 'whole' | 'tokens';
+
+/** How `'tokens'` mode combines per-token scores; `'weighted'` weights by token length */
+export type TokenAggregation = 'mean' | 'min' | 'weighted';
+
+/** Share of the score scale below which a query token counts as missing from a candidate */
+export const MISSING_TOKEN_THRESHOLD = 0.5;
+
+/**
+ * How `suggest` and `extract` treat short pairs, where Jaro-Winkler in particular scores
+ * one-character noise highly (`'a'` against `'ab'` is 0.85). A pair is short when its shorter
+ * normalized side has fewer than `shortStringLength` code points; equal pairs are never affected.
+ * - `'none'`: scores stand as the metric gives them
+ * - `'penalize'`: scores scale by the shorter side's length over `shortStringLength`
+ * - `'require_prefix'`: only candidates that start with the query are eligible
+ */
+export type ShortStringPolicy = 'none' | 'penalize' | 'require_prefix';
+
+/** Code points below which a pair counts as short when `shortStringLength` is not set */
+export const DEFAULT_SHORT_STRING_LENGTH = 3;
+
+// The score of a normalized pair under the policy, or undefined when the pair is ineligible
+// (mirrors ShortStringPolicy::apply in string-metrics-core)
+const applyShortStringPolicy = (
+  policy: ShortStringPolicy,
+  length: number,
+  query: string,
+  candidate: string,
+  score: number,
+): number | undefined => {
+  const shorter = Math.min(Array.from(query).length, Array.from(candidate).length);
+  if (policy === 'none' || shorter >= length || query === candidate) {
+    return score;
+  }
+  if (policy === 'penalize') {
+    return (score * shorter) / length;
+  }
+  return candidate.startsWith(query) ? score : undefined;
+};
+
+/** Candidates each prefilter outcome covered; calls add to it, so one object can total several */
+export interface PrefilterCounts {
+  scored: number;
+  skipped_length: number;
+}
+
+/**
+ * Length limits checked before scoring, on normalized (for `extract`, processed) code point
+ * lengths. Every limit is off by default.
+ */
+export interface LengthFilterOptions {
+  minCandidateLen?: number;
+  min_candidate_len?: number;
+  maxCandidateLen?: number;
+  max_candidate_len?: number;
+  /**
+   * Largest longer/shorter ratio of the query and candidate lengths; an empty side against a
+   * non-empty one exceeds every ratio
+   */
+  maxLengthRatio?: number;
+  max_length_ratio?: number;
+  /** Incremented in place with the candidates scored and skipped */
+  prefilterCounts?: PrefilterCounts;
+  prefilter_counts?: PrefilterCounts;
+}
 
-  // Find differences (unique to each set)
-  const diffA = [...setA].filter((token) => !intersection.has(token));
-  const diffB = [...setB].filter((token) => !intersection.has(token));
+type LengthFilter = {
+  minCandidateLen?: number;
+  maxCandidateLen?: number;
+  maxLengthRatio?: number;
+  counts?: PrefilterCounts;
+};
 
-  const intersectionStr = [...intersection].sort().join(' ');
-  const diffAStr = diffA.sort().join(' ');
-  const diffBStr = diffB.sort().join(' ');
+const resolveLengthFilter = (options: LengthFilterOptions): LengthFilter => ({
+  minCandidateLen: options.minCandidateLen ?? options.min_candidate_len,
+  maxCandidateLen: options.maxCandidateLen ?? options.max_candidate_len,
+  maxLengthRatio: options.maxLengthRatio ?? options.max_length_ratio,
+  counts: options.prefilterCounts ?? options.prefilter_counts,
+});
+
+// Whether a candidate is scored, tallying the outcome (mirrors LengthFilter::admits in
+// string-metrics-core)
+const admitsLength = (filter: LengthFilter, queryLength: number, candidate: string): boolean => {
+  const candidateLength = Array.from(candidate).length;
+  const shorter = Math.min(queryLength, candidateLength);
+  const longer = Math.max(queryLength, candidateLength);
+  const admitted =
+    (filter.minCandidateLen === undefined || candidateLength >= filter.minCandidateLen) &&
+    (filter.maxCandidateLen === undefined || candidateLength <= filter.maxCandidateLen) &&
+    (filter.maxLengthRatio === undefined ||
+      shorter === longer ||
+      (shorter > 0 && longer / shorter <= filter.maxLengthRatio));
+  if (filter.counts) {
+    if (admitted) {
+      filter.counts.scored += 1;
+    } else {
+      filter.counts.skipped_length += 1;
+    }
+  }
+  return admitted;
+};
 
-  // Build strings for comparison
-  const sortedIntersection = intersectionStr;
-  const strA = sortedIntersection + (diffAStr ? ` ${diffAStr}` : '');
-  const strB = sortedIntersection + (diffBStr ? ` ${diffBStr}` : '');
+/**
+ * How `rankFusion` merges the rankings of its metrics:
+ * - `'interleave'`: rank 1 of every metric in order, then rank 2, and so on, skipping
+ *   candidates already taken, so one found by several metrics lands at its best position
+ * - `'rrf'`: reciprocal rank fusion, ordered by the sum of `1 / (k + rank)` over the metrics
+ *   that rank the candidate (ranks from 1); ties go to the better best rank, then input order
+ */
+export type FusionMethod = 'interleave' | 'rrf';
 
-  // Compare: intersection vs intersection, full vs full, and intersection vs each full
-  const scores = [ratio(strA, strB)];
+/** Reciprocal rank fusion constant when `k` is not set */
+export const DEFAULT_RRF_K = 60;
 
-  // Only include intersection comparisons if there's a non-empty intersection
-  if (sortedIntersection.length > 0) {
-    scores.push(ratio(sortedIntersection, sortedIntersection));
-    scores.push(ratio(sortedIntersection, strA));
-    scores.push(ratio(sortedIntersection, strB));
+/**
+ * Rank with each of `metrics` instead of the single metric or scorer and merge the rankings
+ * rather than blending scores. Each metric applies the score cutoff on its own, and every
+ * result keeps its best score across the metrics (the first metric's on ties).
+ */
+export interface RankFusion<M> {
+  metrics: M[];
+  method: FusionMethod;
+  /** RRF constant; larger values flatten the gap between top ranks */
+  k?: number;
+}
+
+// The RRF constant of a validated fusion (mirrors RankFusion::new in string-metrics-core)
+const fusionK = <M>(fusion: RankFusion<M>): number => {
+  if (fusion.metrics.length === 0) {
+    throw new Error('rank_fusion needs at least one metric');
+  }
+  if (fusion.method !== 'interleave' && fusion.method !== 'rrf') {
+    throw new Error(`Unknown rank fusion method: ${fusion.method as string}`);
   }
+  const k = fusion.k ?? DEFAULT_RRF_K;
+  if (!Number.isFinite(k) || k < 0) {
+    throw new Error(`rank_fusion k must be a non-negative number, got ${k}`);
+  }
+  return k;
+};
 
-  return Math.max(...scores);
-}
+// Merge best-first rankings of candidate indices, each index once (mirrors fuse_rankings in
+// string-metrics-core)
+const fuseRankings = (rankings: number[][], method: FusionMethod, k: number): number[] => {
+  if (method === 'interleave') {
+    const depth = Math.max(0, ...rankings.map((ranking) => ranking.length));
+    const seen = new Set<number>();
+    const fused: number[] = [];
+    for (let rank = 0; rank < depth; rank++) {
+      for (const ranking of rankings) {
+        const id = ranking[rank];
+        if (id !== undefined && !seen.has(id)) {
+          seen.add(id);
+          fused.push(id);
+        }
+      }
+    }
+    return fused;
+  }
+  const fused = new Map<number, { score: number; bestRank: number }>();
+  for (const ranking of rankings) {
+    ranking.forEach((id, rank) => {
+      const entry = fused.get(id) ?? { score: 0, bestRank: rank };
+      entry.score += 1 / (k + (rank + 1));
+      entry.bestRank = Math.min(entry.bestRank, rank);
+      fused.set(id, entry);
+    });
+  }
+  return Array.from(fused.entries())
+    .sort(([idA, a], [idB, b]) => b.score - a.score || a.bestRank - b.bestRank || idA - idB)
+    .map(([id]) => id);
+};
 
-// ============================================================================
-// Process Module - Finding best matches from a list (TypeScript implementations)
-// ============================================================================
+// Token mode (mirrors token_query_score in string-metrics-core): a missing token scores 0
+// and the aggregate is scaled by the fraction of tokens found, so a candidate lacking a word
+// ranks below one that only matches every word weakly. A query without tokens is scored whole.
+const tokenQueryScore = (
+  query: string,
+  candidate: string,
+  aggregation: TokenAggregation,
+  scale: number,
+  score: (a: string, b: string) => number,
+): number => {
+  const queryTokens = tokenize(query);
+  if (queryTokens.length === 0) {
+    return score(query, candidate);
+  }
+  const candidateTokens = tokenize(candidate);
+  const threshold = MISSING_TOKEN_THRESHOLD * scale;
+
+  let found = 0;
+  const tokenScores = queryTokens.map((token) => {
+    let best = 0;
+    for (const candidateToken of candidateTokens) {
+      best = Math.max(best, score(token, candidateToken));
+    }
+    if (best >= threshold) {
+      found++;
+      return best;
+    }
+    return 0;
+  });
 
-export type ScorerFunction = (a: string, b: string) => number;
+  let aggregate: number;
+  switch (aggregation) {
+    case 'min':
+      aggregate = Math.min(...tokenScores);
+      break;
+    case 'weighted': {
+      let weighted = 0;
+      let total = 0;
+      queryTokens.forEach((token, i) => {
+        const weight = Array.from(token).length;
+        weighted += tokenScores[i] * weight;
+        total += weight;
+      });
+      aggregate = weighted / total;
+      break;
+    }
+    default:
+      aggregate = tokenScores.reduce((sum, tokenScore) => sum + tokenScore, 0) / queryTokens.length;
+  }
+  return (aggregate * found) / queryTokens.length;
+};
 
-export interface ExtractOptions {
+export interface ExtractOptions extends LengthFilterOptions {
   scorer?: ScorerFunction;
   processor?: (str: string) => string;
+  /**
+   * Applied by the default processor after the preset; a custom processor can pass them to
+   * `normalize()` itself
+   */
+  expansions?: Expansions;
   scoreCutoff?: number;
   score_cutoff?: number;
   limit?: number;
+  /** Attach an `explanation` to each returned result */
+  explain?: boolean;
+  queryMode?: QueryMode;
+  query_mode?: QueryMode;
+  tokenAggregation?: TokenAggregation;
+  token_aggregation?: TokenAggregation;
+  shortStringPolicy?: ShortStringPolicy;
+  short_string_policy?: ShortStringPolicy;
+  shortStringLength?: number;
+  short_string_length?: number;
+  /** Rank with each of these scorers instead of `scorer` and merge the rankings */
+  rankFusion?: RankFusion<ScorerFunction>;
+  rank_fusion?: RankFusion<ScorerFunction>;
 }
 
 type NormalizedExtractOptions = {
@@ -257,29 +1394,91 @@ type NormalizedExtractOptions = {
   processor: (str: string) => string;
   scoreCutoff: number;
   limit?: number;
+  explain: boolean;
+  queryMode: QueryMode;
+  tokenAggregation: TokenAggregation;
+  shortStringPolicy: ShortStringPolicy;
+  shortStringLength: number;
+  lengthFilter: LengthFilter;
+  rankFusion?: RankFusion<ScorerFunction> & { k: number };
 };
 
 const defaultProcessor = (value: string): string => value;
 
+// Without a processor, choices are normalized with the preset default when one is set
+const defaultPresetProcessor = (expansions?: Expansions): ((str: string) => string) => {
+  const { preset, locale } = moduleDefaults;
+  return preset === undefined || preset === 'none'
+    ? defaultProcessor
+    : (value: string): string => normalize(value, preset, locale, { expansions });
+};
+
 const normalizeExtractOptions = (options: ExtractOptions = {}): NormalizedExtractOptions => {
   const scorer = options.scorer ?? ratio;
-  const processor = options.processor ?? defaultProcessor;
+  const processor = options.processor ?? defaultPresetProcessor(options.expansions);
   const scoreCutoff = options.scoreCutoff ?? options.score_cutoff ?? 0;
+  const rankFusion = options.rankFusion ?? options.rank_fusion;
 
   return {
     scorer,
     processor,
     scoreCutoff,
     limit: options.limit,
+    explain: options.explain ?? false,
+    queryMode: options.queryMode ?? options.query_mode ?? 'whole',
+    tokenAggregation: options.tokenAggregation ?? options.token_aggregation ?? 'mean',
+    shortStringPolicy: options.shortStringPolicy ?? options.short_string_policy ?? 'none',
+    shortStringLength:
+      options.shortStringLength ?? options.short_string_length ?? DEFAULT_SHORT_STRING_LENGTH,
+    lengthFilter: resolveLengthFilter(options),
+    rankFusion: rankFusion && { ...rankFusion, k: fusionK(rankFusion) },
   };
 };
 
+// Scorer output for one processed choice after the short-string policy, or undefined when
+// the policy rules the choice out; token mode scores on the scorer's 0-100 scale
+const scoreChoice = (
+  processedQuery: string,
+  processedChoice: string,
+  options: NormalizedExtractOptions,
+  scorer: ScorerFunction = options.scorer,
+): number | undefined => {
+  const score =
+    options.queryMode === 'tokens'
+      ? tokenQueryScore(processedQuery, processedChoice, options.tokenAggregation, 100, scorer)
+      : scorer(processedQuery, processedChoice);
+  return applyShortStringPolicy(
+    options.shortStringPolicy,
+    options.shortStringLength,
+    processedQuery,
+    processedChoice,
+    score,
+  );
+};
+
 export interface ExtractResult {
   choice: string;
   score: number;
   index: number;
+  explanation?: SuggestionExplanation;
 }
 
+// Extraction has no prefix bonus; raw_score is the scorer's own output
+const explainExtraction = (
+  scorer: ScorerFunction,
+  processedQuery: string,
+  processedChoice: string,
+  score: number,
+): SuggestionExplanation => ({
+  raw_score: score,
+  metric: scorer.name,
+  prefix_bonus_applied: false,
+  bonus_amount: 0,
+  normalized_input: processedQuery,
+  normalized_candidate: processedChoice,
+  prefilter: 'scored',
+});
+
 /**
  * Find the best match from a list of choices
  * Returns the best matching choice with its score and index
@@ -289,23 +1488,34 @@ export function extractOne(
   choices: string[],
   options: ExtractOptions = {},
 ): ExtractResult | null {
-  const { scorer, processor, scoreCutoff } = normalizeExtractOptions(options);
+  const settings = normalizeExtractOptions(options);
+  if (settings.rankFusion) {
+    return rankChoices(query, choices, { ...settings, limit: 1 }).results[0] ?? null;
+  }
+  const { scorer, processor, scoreCutoff, explain } = settings;
+  checkInputLengths([query, ...choices]);
 
   if (choices.length === 0) {
     return null;
   }
 
   const processedQuery = processor(query);
+  const queryLength = Array.from(processedQuery).length;
   let bestMatch: ExtractResult | null = null;
+  let bestProcessed = '';
   let bestScore = -Infinity;
 
   for (let i = 0; i < choices.length; i++) {
     const choice = choices[i];
     const processedChoice = processor(choice);
-    const score = scorer(processedQuery, processedChoice);
+    if (!admitsLength(settings.lengthFilter, queryLength, processedChoice)) {
+      continue;
+    }
+    const score = scoreChoice(processedQuery, processedChoice, settings);
 
-    if (score >= scoreCutoff && score > bestScore) {
+    if (score !== undefined && score >= scoreCutoff && score > bestScore) {
       bestScore = score;
+      bestProcessed = processedChoice;
       bestMatch = {
         choice,
         score,
@@ -314,47 +1524,161 @@ export function extractOne(
     }
   }
 
+  if (bestMatch && explain) {
+    bestMatch.explanation = explainExtraction(scorer, processedQuery, bestProcessed, bestScore);
+  }
+  if (bestMatch) {
+    bestMatch.score = roundScore(bestMatch.score);
+  }
+
   return bestMatch;
 }
 
 /**
  * Find the top N best matches from a list of choices
- * Returns an array of matches sorted by score (best first)
+ * Returns an array of matches sorted by score (best first), ties in input order
  */
 export function extract(
   query: string,
   choices: string[],
   options: ExtractOptions = {},
 ): ExtractResult[] {
-  const { scorer, processor, scoreCutoff, limit } = normalizeExtractOptions(options);
+  return rankChoices(query, choices, normalizeExtractOptions(options)).results;
+}
 
-  if (choices.length === 0) {
-    return [];
+export interface BudgetedExtractOptions extends ExtractOptions {
+  /** Wall-clock budget in milliseconds; omitted means no budget */
+  timeBudgetMs?: number;
+  time_budget_ms?: number;
+}
+
+export interface BudgetedExtractResult {
+  results: ExtractResult[];
+  /** False when the budget ran out before every choice was scored */
+  complete: boolean;
+  /** Choices scored, always a prefix of `choices` in input order */
+  processed: number;
+  total: number;
+}
+
+// Choices scored between clock reads, so the clock costs little next to the scoring itself
+const BUDGET_CHECK_INTERVAL = 256;
+
+const budgetExpired = (budgetMs: number): (() => boolean) => {
+  const now =
+    typeof performance !== 'undefined' && typeof performance.now === 'function'
+      ? () => performance.now()
+      : () => Date.now();
+  const started = now();
+  return () => now() - started >= budgetMs;
+};
+
+/**
+ * `extract` that stops once `timeBudgetMs` has elapsed and returns what it found so far
+ *
+ * Choices are scored in input order and the clock is read every 256 choices, so a partial
+ * result ranks exactly the first `processed` choices. Where the cut-off lands depends on
+ * timing and is not deterministic; a `complete: true` result is identical to `extract`.
+ * At least 256 choices (or all of them, if fewer) are always scored.
+ */
+export function extractWithBudget(
+  query: string,
+  choices: string[],
+  options: BudgetedExtractOptions = {},
+): BudgetedExtractResult {
+  const budget = options.timeBudgetMs ?? options.time_budget_ms;
+  if (budget !== undefined && !(budget >= 0)) {
+    throw new Error(`timeBudgetMs must be a non-negative number, got ${budget}`);
   }
+  return rankChoices(query, choices, normalizeExtractOptions(options), budget);
+}
 
-  const processedQuery = processor(query);
-  const results: ExtractResult[] = [];
+type RankedChoice = ExtractResult & { processedChoice: string; scorer: ScorerFunction };
 
-  for (let i = 0; i < choices.length; i++) {
-    const choice = choices[i];
-    const processedChoice = processor(choice);
-    const score = scorer(processedQuery, processedChoice);
+// Best score first, ties in input order: a total order, so results match across engines
+const byScore = (a: RankedChoice, b: RankedChoice): number =>
+  b.score - a.score || a.index - b.index;
 
-    if (score >= scoreCutoff) {
-      results.push({
-        choice,
-        score,
-        index: i,
-      });
+const rankChoices = (
+  query: string,
+  choices: string[],
+  settings: NormalizedExtractOptions,
+  budgetMs?: number,
+): BudgetedExtractResult => {
+  const { processor, scoreCutoff, limit, explain, rankFusion } = settings;
+  checkInputLengths([query, ...choices]);
+
+  const total = choices.length;
+  if (total === 0) {
+    return { results: [], complete: true, processed: 0, total };
+  }
+
+  const expired = budgetMs === undefined ? undefined : budgetExpired(budgetMs);
+  const processedQuery = processor(query);
+  const queryLength = Array.from(processedQuery).length;
+  const scorers = rankFusion ? rankFusion.metrics : [settings.scorer];
+  // One list per scorer, in input order
+  const perScorer: RankedChoice[][] = scorers.map(() => []);
+  let processed = 0;
+
+  for (; processed < total; processed++) {
+    if (
+      expired !== undefined &&
+      processed > 0 &&
+      processed % BUDGET_CHECK_INTERVAL === 0 &&
+      expired()
+    ) {
+      break;
+    }
+    const choice = choices[processed];
+    const processedChoice = processor(choice);
+    if (!admitsLength(settings.lengthFilter, queryLength, processedChoice)) {
+      continue;
     }
+    scorers.forEach((scorer, s) => {
+      const score = scoreChoice(processedQuery, processedChoice, settings, scorer);
+      if (score !== undefined && score >= scoreCutoff) {
+        perScorer[s].push({ choice, score, index: processed, processedChoice, scorer });
+      }
+    });
   }
 
   // Sort by score descending
-  results.sort((a, b) => b.score - a.score);
+  perScorer.forEach((results) => results.sort(byScore));
+  let ranked = perScorer[0];
+  if (rankFusion) {
+    // Each choice at its best score across the scorers, the first scorer's on ties
+    const best = new Map<number, RankedChoice>();
+    for (const result of perScorer.flat()) {
+      const current = best.get(result.index);
+      if (current === undefined || result.score > current.score) {
+        best.set(result.index, result);
+      }
+    }
+    const rankings = perScorer.map((results) => results.map((result) => result.index));
+    ranked = fuseRankings(rankings, rankFusion.method, rankFusion.k).flatMap((index) => {
+      const result = best.get(index);
+      return result === undefined ? [] : [result];
+    });
+  }
 
-  // Apply limit if specified
-  return limit !== undefined ? results.slice(0, limit) : results;
-}
+  // Apply limit if specified, then explain only what is returned
+  const limited = limit !== undefined ? ranked.slice(0, limit) : ranked;
+  return {
+    results: limited.map(({ processedChoice, scorer, ...result }) =>
+      explain
+        ? {
+            ...result,
+            score: roundScore(result.score),
+            explanation: explainExtraction(scorer, processedQuery, processedChoice, result.score),
+          }
+        : { ...result, score: roundScore(result.score) },
+    ),
+    complete: processed === total,
+    processed,
+    total,
+  };
+};
 
 // ============================================================================
 // Unified API - Metric-selectable distance and scoring
@@ -456,6 +1780,40 @@ const normalizeSimilarityMetric = (
   }
 };
 
+export interface UnifiedOptions {
+  /** Normalization preset applied to both inputs first (default: 'none') */
+  preset?: NormalizationPreset;
+  locale?: NormalizationLocale;
+  /**
+   * Multi-char units counted as one symbol, such as Dutch 'ij' or Hungarian 'sz'. They are
+   * matched case-sensitively after normalization, longest first at each position. Token metrics
+   * do not support them.
+   */
+  digraphs?: string[];
+  /**
+   * Throw on inputs containing unpaired surrogates instead of comparing each as U+FFFD
+   * (default: false)
+   */
+  strictUnicode?: boolean;
+  strict_unicode?: boolean;
+}
+
+// Both inputs normalized with the call's preset and locale, falling back to the defaults
+const normalizeUnifiedInputs = (
+  a: string,
+  b: string,
+  options: UnifiedOptions,
+): [string, string] => {
+  checkInputLengths([a, b]);
+  checkWellFormed([a, b], options.strictUnicode ?? options.strict_unicode);
+  const preset = options.preset ?? moduleDefaults.preset ?? 'none';
+  if (preset === 'none') {
+    return [a, b];
+  }
+  const locale = options.locale ?? moduleDefaults.locale;
+  return [normalize(a, preset, locale), normalize(b, preset, locale)];
+};
+
 /**
  * Calculate edit distance between two strings using the specified metric
  * Returns the raw distance (number of edits required)
@@ -463,22 +1821,33 @@ const normalizeSimilarityMetric = (
  * @param a First string
  * @param b Second string
  * @param metric Distance metric to use (default: 'levenshtein')
+ * @param options Normalization preset and locale (see `set_default_options` for defaults), digraphs
  * @returns Edit distance (raw number)
  */
-export function distance(a: string, b: string, metric: DistanceMetric = 'levenshtein'): number {
+export function distance(
+  a: string,
+  b: string,
+  metric: DistanceMetric = 'levenshtein',
+  options: UnifiedOptions = {},
+): number {
   const normalizedMetric = normalizeDistanceMetric(metric);
+  const [left, right] = normalizeUnifiedInputs(a, b, options);
+  if (options.digraphs?.length) {
+    const coreMetric = CORE_METRIC_NAMES[normalizedMetric] as string;
+    return wasm.distance_with_digraphs(left, right, coreMetric, options.digraphs);
+  }
 
   switch (normalizedMetric) {
     case 'levenshtein':
-      return levenshtein(a, b);
+      return levenshtein(left, right);
     case 'damerauLevenshtein':
-      return damerau_levenshtein(a, b);
+      return damerau_levenshtein(left, right);
     case 'osa':
-      return osa_distance(a, b);
+      return osa_distance(left, right);
     case 'indel':
-      return indel_distance(a, b);
+      return indel_distance(left, right);
     case 'lcsSeq':
-      return lcs_seq_distance(a, b);
+      return lcs_seq_distance(left, right);
     default:
       throw new Error(`Unknown distance metric: ${normalizedMetric as string}`);
   }
@@ -491,11 +1860,68 @@ export function distance(a: string, b: string, metric: DistanceMetric = 'levensh
  * @param a First string
  * @param b Second string
  * @param metric Similarity metric to use (default: 'jaroWinkler')
+ * @param options Normalization preset and locale (see `set_default_options` for defaults), digraphs
  * @returns Similarity score (0.0-1.0)
  */
-export function score(a: string, b: string, metric: SimilarityMetric = 'jaroWinkler'): number {
+export function score(
+  a: string,
+  b: string,
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: UnifiedOptions = {},
+): number {
+  const normalizedMetric = normalizeSimilarityMetric(metric);
+  const [left, right] = normalizeUnifiedInputs(a, b, options);
+  if (options.digraphs?.length) {
+    const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+    if (coreMetric === undefined) {
+      throw new Error(`Metric does not support digraphs: ${normalizedMetric}`);
+    }
+    return roundScore(wasm.score_with_digraphs(left, right, coreMetric, options.digraphs));
+  }
+  return roundScore(scoreWithMetric(left, right, normalizedMetric));
+}
+
+export interface Anchor {
+  /** Which end of the strings must match: 'start' for prefixes, 'end' for suffixes */
+  side: 'start' | 'end';
+  /** Code points that must match exactly; longer than a string means all of it */
+  length: number;
+}
+
+/** Preset, locale and strictUnicode as for `score`; digraphs do not apply */
+export type AnchoredOptions = Omit<UnifiedOptions, 'digraphs'>;
+
+/**
+ * Similarity that only counts when both strings agree exactly on the first (or last)
+ * `anchor.length` code points after normalization, e.g. a SKU's product line. Returns 0 when
+ * they differ. Otherwise the anchored code points count as matched and `metric` scores the
+ * rest, weighted by the share of code points outside the anchor, so identical strings score 1.
+ * An anchor longer than either string needs the shorter one to be a prefix (or suffix) of the
+ * other. Token metrics do not support anchors.
+ */
+export function anchored_similarity(
+  a: string,
+  b: string,
+  anchor: Anchor,
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: AnchoredOptions = {},
+): number {
   const normalizedMetric = normalizeSimilarityMetric(metric);
+  const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+  if (coreMetric === undefined) {
+    throw new Error(`Metric does not support anchors: ${normalizedMetric}`);
+  }
+  checkInputLengths([a, b]);
+  checkWellFormed([a, b], options.strictUnicode ?? options.strict_unicode);
+  const preset = options.preset ?? moduleDefaults.preset ?? 'none';
+  const locale = options.locale ?? moduleDefaults.locale;
+  return roundScore(
+    wasm.anchored_similarity(a, b, anchor.side, anchor.length, coreMetric, preset, locale),
+  );
+}
 
+// `score` without normalization, the length check or rounding, for callers that apply them
+const scoreWithMetric = (a: string, b: string, normalizedMetric: SimilarityMetricCamel): number => {
   switch (normalizedMetric) {
     case 'levenshtein':
       return normalized_levenshtein(a, b);
@@ -522,6 +1948,478 @@ export function score(a: string, b: string, metric: SimilarityMetric = 'jaroWink
     default:
       throw new Error(`Unknown similarity metric: ${normalizedMetric as string}`);
   }
+};
+
+export interface PairOptions {
+  /** Normalization preset applied to both sides of every pair (default: 'none') */
+  preset?: NormalizationPreset;
+  locale?: NormalizationLocale;
+  /**
+   * score_pairs: scores below this become 0. distance_pairs: distances above it become
+   * cutoff + 1.
+   */
+  scoreCutoff?: number;
+  score_cutoff?: number;
+  /**
+   * Throw on inputs containing unpaired surrogates instead of comparing each as U+FFFD
+   * (default: false)
+   */
+  strictUnicode?: boolean;
+  strict_unicode?: boolean;
+  /** Stop with a `CancelledError` once this token is cancelled (checked every 256 rows) */
+  cancel?: CancellationToken;
+}
+
+/**
+ * A cancel flag for `score_pairs`, `distance_pairs` and their `_async` variants, and for `cdist`,
+ * `similar_pairs` and `dedupe`. Cancelling is permanent, and cancelling twice does nothing more.
+ * A synchronous WASM call runs to completion before the thread's event loop gets a turn, so a
+ * `cancel()` from the same thread (a click handler, a timer) only stops the next call; the
+ * `_async` variants yield between chunks of `PAIR_CHUNK_ROWS` rows and see it at the next chunk.
+ * Calls on a thread sharing the module's memory see it within 256 rows. Call `free` once done to
+ * release the WASM memory.
+ */
+export class CancellationToken {
+  /** @internal */
+  readonly inner: InstanceType<typeof wasm.CancellationToken>;
+
+  constructor() {
+    this.inner = new wasm.CancellationToken();
+  }
+
+  cancel(): void {
+    this.inner.cancel();
+  }
+
+  get cancelled(): boolean {
+    return this.inner.cancelled;
+  }
+
+  free(): void {
+    this.inner.free();
+  }
+}
+
+/** Thrown by a batch call whose `CancellationToken` was cancelled */
+export class CancelledError extends Error {
+  readonly code = 'CANCELLED';
+
+  constructor(
+    /** Rows finished before the call stopped */
+    readonly completed: number,
+    readonly total: number,
+  ) {
+    super(`CANCELLED: cancelled after ${completed} of ${total} rows`);
+    this.name = 'CancelledError';
+  }
+}
+
+// Rows between token checks, as in string-metrics-core
+const CANCEL_CHECK_INTERVAL = 256;
+const CANCELLED_MESSAGE = /^CANCELLED: cancelled after (\d+) of (\d+) rows/;
+
+// Rethrow a core "CANCELLED: cancelled after <completed> of <total> rows" error structured
+const cancellable = <T>(call: () => T): T => {
+  try {
+    return call();
+  } catch (error) {
+    const match = error instanceof Error ? CANCELLED_MESSAGE.exec(error.message) : null;
+    if (match) {
+      throw new CancelledError(Number(match[1]), Number(match[2]));
+    }
+    throw error;
+  }
+};
+
+// Metric names as string-metrics-core spells them
+const CORE_METRIC_NAMES: Partial<Record<SimilarityMetricCamel, string>> = {
+  levenshtein: 'levenshtein',
+  damerauLevenshtein: 'damerau_levenshtein',
+  osa: 'osa',
+  jaro: 'jaro',
+  jaroWinkler: 'jaro_winkler',
+  indel: 'indel',
+  lcsSeq: 'lcs_seq',
+  ratio: 'ratio',
+};
+
+const checkPairLengths = (a: string[], b: string[]): void => {
+  if (a.length !== b.length) {
+    throw new Error(`Pair lists must have equal lengths (got ${a.length} and ${b.length})`);
+  }
+};
+
+/**
+ * Score each row of two parallel lists: `score(a[i], b[i], metric)`, in one WASM call.
+ * Unlike a cross product, `a` and `b` must have equal lengths.
+ */
+export function score_pairs(
+  a: string[],
+  b: string[],
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: PairOptions = {},
+): Float64Array {
+  const normalizedMetric = normalizeSimilarityMetric(metric);
+  const scoreCutoff = options.scoreCutoff ?? options.score_cutoff;
+  checkInputLengths(a);
+  checkInputLengths(b);
+  checkWellFormed(a, options.strictUnicode ?? options.strict_unicode);
+  checkWellFormed(b, options.strictUnicode ?? options.strict_unicode);
+  const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+  const { preset, locale, cancel } = options;
+  if (coreMetric !== undefined) {
+    // The WASM side applies the preset, locale and scorePrecision defaults
+    const cutoff = scoreCutoff ?? -1;
+    return cancel
+      ? cancellable(() =>
+          wasm.score_pairs_cancellable(a, b, coreMetric, preset, locale, cutoff, cancel.inner),
+        )
+      : wasm.score_pairs(a, b, coreMetric, preset, locale, cutoff);
+  }
+
+  // Token metrics are TypeScript-only, so score those rows here, checking the token as often
+  checkPairLengths(a, b);
+  const rowPreset = preset ?? moduleDefaults.preset ?? 'none';
+  const rowLocale = locale ?? moduleDefaults.locale;
+  return Float64Array.from(a, (left, i) => {
+    if (i % CANCEL_CHECK_INTERVAL === 0 && cancel?.cancelled) {
+      throw new CancelledError(i, a.length);
+    }
+    const value = scoreWithMetric(
+      normalize(left, rowPreset, rowLocale),
+      normalize(b[i], rowPreset, rowLocale),
+      normalizedMetric,
+    );
+    return roundScore(scoreCutoff !== undefined && value < scoreCutoff ? 0 : value);
+  });
+}
+
+/**
+ * Edit distance for each row of two parallel lists: `distance(a[i], b[i], metric)`, in one
+ * WASM call. `a` and `b` must have equal lengths.
+ */
+export function distance_pairs(
+  a: string[],
+  b: string[],
+  metric: DistanceMetric = 'levenshtein',
+  options: PairOptions = {},
+): Uint32Array {
+  const coreMetric = CORE_METRIC_NAMES[normalizeDistanceMetric(metric)] as string;
+  const scoreCutoff = options.scoreCutoff ?? options.score_cutoff;
+  checkInputLengths(a);
+  checkInputLengths(b);
+  checkWellFormed(a, options.strictUnicode ?? options.strict_unicode);
+  checkWellFormed(b, options.strictUnicode ?? options.strict_unicode);
+  const { preset, locale, cancel } = options;
+  const cutoff = scoreCutoff ?? -1;
+  return cancel
+    ? cancellable(() =>
+        wasm.distance_pairs_cancellable(a, b, coreMetric, preset, locale, cutoff, cancel.inner),
+      )
+    : wasm.distance_pairs(a, b, coreMetric, preset, locale, cutoff);
+}
+
+// The core name of a metric for the batch calls that only run in WASM
+const wasmBatchMetric = (metric: SimilarityMetric, call: string): string => {
+  const coreMetric = CORE_METRIC_NAMES[normalizeSimilarityMetric(metric)];
+  if (coreMetric === undefined) {
+    throw new Error(`${call} does not support metric: ${metric}`);
+  }
+  return coreMetric;
+};
+
+/**
+ * Score every query against every choice in one WASM call: row `i` holds the scores of
+ * `queries[i]` against each choice. Takes the `score_pairs` options; `cancel` counts each score
+ * as a row. The token metrics (`partialRatio`, `tokenSortRatio`, ...) throw.
+ */
+export function cdist(
+  queries: string[],
+  choices: string[],
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: PairOptions = {},
+): Float64Array[] {
+  const coreMetric = wasmBatchMetric(metric, 'cdist');
+  const cutoff = options.scoreCutoff ?? options.score_cutoff ?? -1;
+  checkInputLengths(queries);
+  checkInputLengths(choices);
+  checkWellFormed(queries, options.strictUnicode ?? options.strict_unicode);
+  checkWellFormed(choices, options.strictUnicode ?? options.strict_unicode);
+  const { preset, locale, cancel } = options;
+  const scores = cancel
+    ? cancellable(() =>
+        wasm.cdist_cancellable(queries, choices, coreMetric, preset, locale, cutoff, cancel.inner),
+      )
+    : wasm.cdist(queries, choices, coreMetric, preset, locale, cutoff);
+  const width = choices.length;
+  return Array.from(queries, (_, i) => scores.subarray(i * width, (i + 1) * width));
+}
+
+export interface SimilarPairsOptions extends Omit<PairOptions, 'scoreCutoff' | 'score_cutoff'> {
+  /** Lowest score that makes two items a pair (default: 0.9) */
+  minScore?: number;
+  min_score?: number;
+}
+
+/** Two items of one list that `similar_pairs` found alike */
+export interface SimilarPair {
+  /** Index of the earlier item */
+  left: number;
+  right: number;
+  score: number;
+}
+
+/**
+ * Every pair of items scoring at least `minScore`, ordered by `left` and then `right`, in one
+ * WASM call. Compares each pair once, so it costs n * (n - 1) / 2 scores; `cancel` counts each
+ * as a row. The token metrics throw.
+ */
+export function similar_pairs(
+  items: string[],
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: SimilarPairsOptions = {},
+): SimilarPair[] {
+  const coreMetric = wasmBatchMetric(metric, 'similar_pairs');
+  const minScore = options.minScore ?? options.min_score ?? 0.9;
+  checkInputLengths(items);
+  checkWellFormed(items, options.strictUnicode ?? options.strict_unicode);
+  const { preset, locale, cancel } = options;
+  const flat = cancel
+    ? cancellable(() =>
+        wasm.similar_pairs_cancellable(items, coreMetric, preset, locale, minScore, cancel.inner),
+      )
+    : wasm.similar_pairs(items, coreMetric, preset, locale, minScore);
+  const pairs: SimilarPair[] = [];
+  for (let i = 0; i < flat.length; i += 3) {
+    pairs.push({ left: flat[i], right: flat[i + 1], score: flat[i + 2] });
+  }
+  return pairs;
+}
+
+/**
+ * Group near-duplicates: entry `i` is the index of the first item in `items[i]`'s group, where a
+ * group is every item linked by a chain of `similar_pairs` (so `a` ~ `b` ~ `c` is one group even
+ * when `a` and `c` score below `minScore`). Takes the `similar_pairs` options.
+ */
+export function dedupe(
+  items: string[],
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: SimilarPairsOptions = {},
+): Uint32Array {
+  const coreMetric = wasmBatchMetric(metric, 'dedupe');
+  const minScore = options.minScore ?? options.min_score ?? 0.9;
+  checkInputLengths(items);
+  checkWellFormed(items, options.strictUnicode ?? options.strict_unicode);
+  const { preset, locale, cancel } = options;
+  return cancel
+    ? cancellable(() =>
+        wasm.dedupe_cancellable(items, coreMetric, preset, locale, minScore, cancel.inner),
+      )
+    : wasm.dedupe(items, coreMetric, preset, locale, minScore);
+}
+
+/** Rows the `_async` batch calls hand to one WASM call before yielding to the event loop */
+export const PAIR_CHUNK_ROWS = 4096;
+
+// A macrotask, so timers and input handlers (and any `cancel()` they make) run first
+const nextTask = (): Promise<void> => new Promise((resolve) => setTimeout(resolve, 0));
+
+// `run` over successive PAIR_CHUNK_ROWS-row slices of `total` rows (one empty slice when there
+// are none, so the arguments are still validated), yielding between slices. A CancelledError
+// counts the rows completed across the whole run.
+const inChunks = async <T extends Float64Array | Uint32Array>(
+  total: number,
+  output: T,
+  run: (start: number, end: number) => T,
+): Promise<T> => {
+  for (let start = 0; start === 0 || start < total; start += PAIR_CHUNK_ROWS) {
+    if (start > 0) {
+      await nextTask();
+    }
+    try {
+      output.set(run(start, Math.min(start + PAIR_CHUNK_ROWS, total)), start);
+    } catch (error) {
+      if (error instanceof CancelledError) {
+        throw new CancelledError(start + error.completed, total);
+      }
+      throw error;
+    }
+  }
+  return output;
+};
+
+/**
+ * `score_pairs` in chunks of `PAIR_CHUNK_ROWS` rows, yielding to the event loop between them so
+ * a `cancel()` from the same thread stops the run at the next chunk.
+ */
+export async function score_pairs_async(
+  a: string[],
+  b: string[],
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: PairOptions = {},
+): Promise<Float64Array> {
+  checkPairLengths(a, b);
+  return inChunks(a.length, new Float64Array(a.length), (start, end) =>
+    score_pairs(a.slice(start, end), b.slice(start, end), metric, options),
+  );
+}
+
+/** `distance_pairs` in chunks, yielding between them as `score_pairs_async` does */
+export async function distance_pairs_async(
+  a: string[],
+  b: string[],
+  metric: DistanceMetric = 'levenshtein',
+  options: PairOptions = {},
+): Promise<Uint32Array> {
+  checkPairLengths(a, b);
+  return inChunks(a.length, new Uint32Array(a.length), (start, end) =>
+    distance_pairs(a.slice(start, end), b.slice(start, end), metric, options),
+  );
+}
+
+/** What `score_nullable` scores when a side is missing */
+export type MissingPolicy = 'ignore' | 'penalize' | 'treat_as_empty';
+
+/** Which sides of a `score_nullable` comparison had a value */
+export type PresenceStatus = 'both_present' | 'one_missing' | 'both_missing';
+
+export interface NullableScore {
+  /**
+   * null only when a side is missing and the policy is 'ignore', so a weighted combination can
+   * hand the field's weight to the others; a present pair always has a score
+   */
+  score: number | null;
+  status: PresenceStatus;
+}
+
+export interface NullableOptions extends PairOptions {
+  /**
+   * 'ignore' (default): score null. 'penalize': 0. 'treat_as_empty': the metric's score with
+   * each missing side as '' (so two missing values score 1).
+   */
+  missing?: MissingPolicy;
+}
+
+const PRESENCE_STATUSES: readonly PresenceStatus[] = [
+  'both_present',
+  'one_missing',
+  'both_missing',
+];
+
+/**
+ * `score(a, b, metric)` for fields that may be missing. null and undefined are missing; '' is a
+ * present empty value. The status always reports which sides were present, so a missing value
+ * is never confused with a genuine 0 similarity; `options.missing` decides the score. Unknown
+ * metrics and policies throw even when both sides are missing.
+ */
+export function score_nullable(
+  a: string | null | undefined,
+  b: string | null | undefined,
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: NullableOptions = {},
+): NullableScore {
+  const normalizedMetric = normalizeSimilarityMetric(metric);
+  const preset = options.preset ?? moduleDefaults.preset ?? 'none';
+  const locale = options.locale ?? moduleDefaults.locale;
+  const scoreCutoff = options.scoreCutoff ?? options.score_cutoff;
+  const missing = options.missing ?? 'ignore';
+  const present = [a, b].filter((value): value is string => value != null);
+  checkInputLengths(present);
+  checkWellFormed(present, options.strictUnicode ?? options.strict_unicode);
+  const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+  if (coreMetric !== undefined) {
+    const [value, status] = wasm.score_nullable(
+      a ?? undefined,
+      b ?? undefined,
+      coreMetric,
+      options.preset,
+      options.locale,
+      missing,
+      scoreCutoff ?? -1,
+    );
+    return {
+      score: Number.isNaN(value) ? null : value,
+      status: PRESENCE_STATUSES[status],
+    };
+  }
+
+  // Token metrics are TypeScript-only, so apply the policy here
+  if (!['ignore', 'penalize', 'treat_as_empty'].includes(missing)) {
+    throw new Error(`Unknown missing-value policy: ${missing as string}`);
+  }
+  const left = normalize(a ?? '', preset, locale);
+  const right = normalize(b ?? '', preset, locale);
+  const status = PRESENCE_STATUSES[2 - present.length];
+  if (status !== 'both_present' && missing !== 'treat_as_empty') {
+    return { score: missing === 'penalize' ? 0 : null, status };
+  }
+  const value = scoreWithMetric(left, right, normalizedMetric);
+  return {
+    score: roundScore(scoreCutoff !== undefined && value < scoreCutoff ? 0 : value),
+    status,
+  };
+}
+
+/**
+ * `score` and `distance` for one metric and `PairOptions`, for comparing many pairs one at a
+ * time (e.g. in a loop over candidates). The WASM side keeps its buffers between calls instead
+ * of allocating per pair; results equal the free functions on `normalize(value, preset)`, with
+ * the cutoff applied as in `score_pairs`. Token metrics run in TypeScript. The preset and locale
+ * defaults are read when it is constructed. Like any WASM object it belongs to one thread: call
+ * `free` once done to release the WASM memory.
+ */
+export class Comparator {
+  private readonly inner: InstanceType<typeof wasm.Comparator> | undefined;
+  private readonly metric: SimilarityMetricCamel;
+  private readonly preset: NormalizationPreset;
+  private readonly locale: NormalizationLocale | undefined;
+  private readonly scoreCutoff: number | undefined;
+  private readonly strictUnicode: boolean | undefined;
+
+  constructor(metric: SimilarityMetric = 'jaroWinkler', options: PairOptions = {}) {
+    this.metric = normalizeSimilarityMetric(metric);
+    this.preset = options.preset ?? moduleDefaults.preset ?? 'none';
+    this.locale = options.locale ?? moduleDefaults.locale;
+    this.scoreCutoff = options.scoreCutoff ?? options.score_cutoff;
+    this.strictUnicode = options.strictUnicode ?? options.strict_unicode;
+    const coreMetric = CORE_METRIC_NAMES[this.metric];
+    if (coreMetric !== undefined) {
+      this.inner = new wasm.Comparator(
+        coreMetric,
+        options.preset,
+        options.locale,
+        this.scoreCutoff ?? -1,
+      );
+    }
+  }
+
+  score(a: string, b: string): number {
+    checkInputLengths([a, b]);
+    checkWellFormed([a, b], this.strictUnicode);
+    if (this.inner !== undefined) {
+      return this.inner.score(a, b);
+    }
+    const value = scoreWithMetric(
+      normalize(a, this.preset, this.locale),
+      normalize(b, this.preset, this.locale),
+      this.metric,
+    );
+    return roundScore(this.scoreCutoff !== undefined && value < this.scoreCutoff ? 0 : value);
+  }
+
+  /** Throws for metrics without an edit distance (jaro, jaroWinkler, ratio, token metrics) */
+  distance(a: string, b: string): number {
+    if (this.inner === undefined) {
+      throw new Error(`Unknown distance metric: ${this.metric}`);
+    }
+    checkInputLengths([a, b]);
+    checkWellFormed([a, b], this.strictUnicode);
+    return this.inner.distance(a, b);
+  }
+
+  free(): void {
+    this.inner?.free();
+  }
 }
 
 export interface SubstringResult {
@@ -530,9 +2428,23 @@ export interface SubstringResult {
   candidateRange: [number, number];
 }
 
+export interface SubstringOptions {
+  /** Unit of the returned ranges: code points (default) or UTF-16 code units (string indices) */
+  rangeUnit?: 'char' | 'utf16';
+  /** Occurrence reported when several longest matches tie; default is the first found scanning the query */
+  tiePolicy?: 'first' | 'last';
+}
+
 const toCodePoints = (value: string): string[] => Array.from(value);
 
-export function substringSimilarity(query: string, candidate: string): SubstringResult {
+const toUtf16Offset = (chars: string[], index: number): number =>
+  chars.slice(0, index).reduce((offset, char) => offset + char.length, 0);
+
+export function substringSimilarity(
+  query: string,
+  candidate: string,
+  options: SubstringOptions = {},
+): SubstringResult {
   const aChars = toCodePoints(query);
   const bChars = toCodePoints(candidate);
   const m = aChars.length;
@@ -548,7 +2460,11 @@ export function substringSimilarity(query: string, candidate: string): Substring
     for (let j = 1; j <= n; j++) {
       if (aChars[i - 1] === bChars[j - 1]) {
         dp[i][j] = dp[i - 1][j - 1] + 1;
-        if (dp[i][j] > maxLen) {
+        const tieWins =
+          dp[i][j] === maxLen &&
+          ((options.tiePolicy === 'first' && j < endB) ||
+            (options.tiePolicy === 'last' && j > endB));
+        if (dp[i][j] > maxLen || tieWins) {
           maxLen = dp[i][j];
           endA = i;
           endB = j;
@@ -559,10 +2475,18 @@ export function substringSimilarity(query: string, candidate: string): Substring
     }
   }
 
-  const score = maxLen === 0 ? 0 : (2 * maxLen) / (m + n);
+  // Two empty strings are identical
+  const score = m + n === 0 ? 1 : (2 * maxLen) / (m + n);
   const startA = endA - maxLen;
   const startB = endB - maxLen;
 
+  if (usesUtf16(options.rangeUnit)) {
+    return {
+      score,
+      queryRange: [toUtf16Offset(aChars, startA), toUtf16Offset(aChars, endA)],
+      candidateRange: [toUtf16Offset(bChars, startB), toUtf16Offset(bChars, endB)],
+    };
+  }
   return {
     score,
     queryRange: [startA, endA],
@@ -570,6 +2494,259 @@ export function substringSimilarity(query: string, candidate: string): Substring
   };
 }
 
+export interface SubstringRangesResult {
+  score: number;
+  /** Every haystack range holding a longest common substring, sorted by start */
+  ranges: Array<[number, number]>;
+}
+
+/**
+ * `substringSimilarity`'s score with every occurrence of the longest common substring in
+ * `haystack` rather than one: `substring_all_ranges('ab', 'abxab')` returns both `[0, 2]` and
+ * `[3, 5]`. The `'first'` and `'last'` tie policies pick the first and last of these ranges.
+ */
+export function substring_all_ranges(
+  needle: string,
+  haystack: string,
+  options: AlignmentOptions = {},
+): SubstringRangesResult {
+  const utf16 = usesUtf16(options.rangeUnit);
+  const flat = wasm.substring_all_ranges(needle, haystack, utf16);
+  const ranges: Array<[number, number]> = [];
+  for (let k = 0; k < flat.length; k += 2) {
+    ranges.push([flat[k], flat[k + 1]]);
+  }
+  if (ranges.length === 0) {
+    return { score: needle === '' && haystack === '' ? 1 : 0, ranges };
+  }
+  const [start, end] = ranges[0];
+  const matchLength = utf16 ? toCodePoints(haystack.slice(start, end)).length : end - start;
+  const total = toCodePoints(needle).length + toCodePoints(haystack).length;
+  return { score: (2 * matchLength) / total, ranges };
+}
+
+export interface CoverageOptions {
+  /** Count matches in order with gaps between them (longest common subsequence) */
+  gaps?: boolean;
+  /** Unit of the returned ranges: code points (default) or UTF-16 code units (string indices) */
+  rangeUnit?: 'char' | 'utf16';
+  /** Contiguous occurrence reported when several longest matches tie, as for substringSimilarity */
+  tiePolicy?: 'first' | 'last';
+}
+
+export interface CoverageResult {
+  score: number;
+  /** Matched runs in the haystack, half-open; at most one without `gaps` */
+  ranges: Array<[number, number]>;
+}
+
+/** Share of the needle found as one contiguous run in the haystack (0.0-1.0) */
+export function coverage_score(needle: string, haystack: string): number {
+  return wasm.coverage_score(needle, haystack);
+}
+
+/** Share of the needle found in order in the haystack, gaps allowed (0.0-1.0) */
+export function subsequence_coverage_score(needle: string, haystack: string): number {
+  return wasm.subsequence_coverage_score(needle, haystack);
+}
+
+/**
+ * How much of `needle` appears in `haystack`, ignoring the haystack's length: the longest
+ * common substring (or with `gaps`, subsequence) over the needle's length. Unlike
+ * `substringSimilarity`, a short query found verbatim in a long title scores 1. Gap-tolerant
+ * ranges follow lcs_seq_matching_indices, so matches sit as early in the haystack as possible.
+ */
+export function coverage(
+  needle: string,
+  haystack: string,
+  options: CoverageOptions = {},
+): CoverageResult {
+  const gaps = options.gaps ?? false;
+  const score = gaps
+    ? wasm.subsequence_coverage_score(needle, haystack)
+    : wasm.coverage_score(needle, haystack);
+  const utf16 = usesUtf16(options.rangeUnit);
+  const flat = wasm.coverage_ranges(needle, haystack, gaps, utf16, options.tiePolicy);
+  const ranges: Array<[number, number]> = [];
+  for (let k = 0; k < flat.length; k += 2) {
+    ranges.push([flat[k], flat[k + 1]]);
+  }
+  return { score, ranges };
+}
+
+export interface Excerpt {
+  text: string;
+  /** The match's range within `text`, in the unit of the input range */
+  range: [number, number];
+}
+
+/**
+ * The text around `range` of `s` for display, with up to `contextChars` user-perceived
+ * characters (grapheme clusters) on each side and `ellipsis` at each end where text was cut.
+ * Cuts never split a surrogate pair, an emoji sequence or a letter from its accents; a cluster
+ * the range only partly covers is kept whole. `range` is read in `rangeUnit`, so ranges from
+ * `coverage` or `substring_all_ranges` can be passed straight through. Throws on a reversed or
+ * out-of-bounds range, or a UTF-16 range inside a surrogate pair.
+ */
+export function excerpt_around_range(
+  s: string,
+  range: [number, number],
+  contextChars: number,
+  ellipsis = '…',
+  options: AlignmentOptions = {},
+): Excerpt {
+  const [text, start, end] = wasm.excerpt_around_range(
+    s,
+    range[0],
+    range[1],
+    contextChars,
+    ellipsis,
+    usesUtf16(options.rangeUnit),
+  );
+  return { text, range: [Number(start), Number(end)] };
+}
+
+export interface WindowScoresOptions {
+  /** Window length in code points (default: the needle's length) */
+  window?: number;
+  /** Code points between window starts (default: max(1, floor(window / 4))) */
+  step?: number;
+}
+
+/**
+ * Similarity profile of `needle` along `haystack`: `score(needle, slice, metric)` for each
+ * `window`-code-point slice of the haystack, one every `step` code points, so score `i` is for
+ * the slice starting at `i * step`. A haystack no longer than the window is one window. Keep
+ * the window near the needle's length for edit metrics, which charge for every extra
+ * character; a wider window tolerates paraphrase at the cost of lower peaks. Inputs are not
+ * normalized. Throws on a zero window (an empty needle without `window`) or step.
+ */
+export function window_scores(
+  needle: string,
+  haystack: string,
+  metric: SimilarityMetric = 'jaroWinkler',
+  options: WindowScoresOptions = {},
+): Float64Array {
+  const normalizedMetric = normalizeSimilarityMetric(metric);
+  checkInputLengths([needle, haystack]);
+  const coreMetric = CORE_METRIC_NAMES[normalizedMetric];
+  if (coreMetric !== undefined) {
+    return roundScores(
+      wasm.window_scores(needle, haystack, options.window, options.step, coreMetric),
+    );
+  }
+
+  // Token metrics are TypeScript-only, so score those windows here
+  const window = options.window ?? toCodePoints(needle).length;
+  if (window < 1) {
+    throw new Error('Window must be at least 1 char');
+  }
+  const step = options.step ?? Math.max(1, Math.floor(window / 4));
+  if (step < 1) {
+    throw new Error('Step must be at least 1 char');
+  }
+  const chars = toCodePoints(haystack);
+  const scores: number[] = [];
+  for (let start = 0; start === 0 || start + window <= chars.length; start += step) {
+    const slice = chars.slice(start, start + window).join('');
+    scores.push(roundScore(scoreWithMetric(needle, slice, normalizedMetric)));
+  }
+  return Float64Array.from(scores);
+}
+
+export interface FuzzyMatch {
+  /** Start of the match in code points (inclusive) */
+  start: number;
+  /** End of the match in code points (exclusive) */
+  end: number;
+  /** Edits turning the match into the needle */
+  edits: number;
+}
+
+const toFuzzyMatches = (flat: Uint32Array): FuzzyMatch[] => {
+  const matches: FuzzyMatch[] = [];
+  for (let k = 0; k < flat.length; k += 3) {
+    matches.push({ start: flat[k], end: flat[k + 1], edits: flat[k + 2] });
+  }
+  return matches;
+};
+
+/**
+ * Every non-overlapping fuzzy occurrence of `needle` in `haystack` needing at most `maxEdits`
+ * edits, left to right. Each match is the shortest of the best alignments ending there, and
+ * overlapping candidates keep the one with the fewest edits (the earliest on ties).
+ * Throws unless `maxEdits` is less than the needle's length.
+ */
+export function fuzzy_find_all(haystack: string, needle: string, maxEdits: number): FuzzyMatch[] {
+  return toFuzzyMatches(wasm.fuzzy_find_all(haystack, needle, maxEdits));
+}
+
+/**
+ * `fuzzy_find_all` over text that arrives in chunks, e.g. a network stream. `feed` returns the
+ * matches each chunk completes, with offsets counted from the start of the stream, and
+ * `finish` returns the last one and resets the matcher for another stream. Matches that
+ * straddle chunk boundaries are found, none is reported twice, and the results always equal
+ * `fuzzy_find_all` on the concatenated text. Chunks must not split a surrogate pair.
+ * Call `free` once done to release the WASM memory.
+ */
+export class StreamMatcher {
+  private readonly inner: InstanceType<typeof wasm.StreamMatcher>;
+
+  constructor(needle: string, maxEdits: number) {
+    this.inner = new wasm.StreamMatcher(needle, maxEdits);
+  }
+
+  feed(chunk: string): FuzzyMatch[] {
+    return toFuzzyMatches(this.inner.feed(chunk));
+  }
+
+  finish(): FuzzyMatch[] {
+    return toFuzzyMatches(this.inner.finish());
+  }
+
+  free(): void {
+    this.inner.free();
+  }
+}
+
+export type VariantEdit = 'insert' | 'delete' | 'substitute' | 'transpose';
+
+export interface VariantOptions {
+  /** Seed for the random stream (a non-negative safe integer); default 0 */
+  seed?: number;
+  /** Edits to draw from; default all four. A transposition counts as two edits. */
+  edits?: readonly VariantEdit[];
+  /** Chars to insert and substitute besides those of the input */
+  extraChars?: string;
+}
+
+/**
+ * Up to `count` distinct strings exactly `distance` Levenshtein edits from `s`, e.g. to
+ * probe a score threshold with near misses. Every variant is checked with the real metric.
+ * The same seed gives the same variants on every platform. Inserted and substituted chars
+ * come from `s` plus `options.extraChars`. Fewer than `count` come back when few strings lie
+ * at that distance, e.g. deleting from a one-char string.
+ */
+export function generate_variants(
+  s: string,
+  distance: number,
+  count: number,
+  options: VariantOptions = {},
+): string[] {
+  const seed = options.seed ?? 0;
+  if (!Number.isSafeInteger(seed) || seed < 0) {
+    throw new Error(`generate_variants seed must be a non-negative safe integer, got ${seed}`);
+  }
+  return wasm.generate_variants(
+    s,
+    distance,
+    count,
+    BigInt(seed),
+    [...(options.edits ?? [])],
+    options.extraChars ?? '',
+  );
+}
+
 type SuggestMetricCamel =
   | 'levenshtein'
   | 'damerauOsa'
@@ -634,7 +2811,91 @@ const normalizeSuggestMetric = (metric: SuggestMetric = 'jaroWinkler'): SuggestM
   }
 };
 
-export interface SuggestionOptions {
+/** Extra cost of each character no vocabulary word covers (see `segment_words`) */
+export const DEFAULT_UNKNOWN_CHAR_PENALTY = 10;
+
+export interface SegmentOptions {
+  /** Relative word weights, one per word; every word weighs 1 when omitted */
+  frequencies?: number[];
+  unknownCharPenalty?: number;
+  unknown_char_penalty?: number;
+}
+
+export interface Segmentation {
+  /** Vocabulary words and unknown runs, in input order */
+  words: string[];
+  /** Natural log probability of the split (0 for an input without words) */
+  score: number;
+}
+
+/**
+ * Split run-together words (`'newyorkcity'`) into the most probable sequence of `vocabulary`
+ * words. Matching is exact, so normalize the input and the vocabulary alike first. Whitespace
+ * in the input always splits; substrings no word covers are kept as they are, each character
+ * costing `unknownCharPenalty` more than the rarest word.
+ */
+export function segment_words(
+  input: string,
+  vocabulary: string[],
+  options: SegmentOptions = {},
+): Segmentation {
+  checkInputLengths([input]);
+  const [score, ...words] = wasm.segment_words(
+    input,
+    vocabulary,
+    new Float64Array(options.frequencies ?? []),
+    options.unknownCharPenalty ?? options.unknown_char_penalty ?? DEFAULT_UNKNOWN_CHAR_PENALTY,
+  );
+  return { words, score: Number(score) };
+}
+
+/**
+ * Ranks recently selected candidates higher: a candidate last selected at `t` (its
+ * `lastSelectedMs`, or its latest `SuggestSession.recordSelection`) gains
+ * `maxBoost * 2^(-(nowMs - t) / halfLifeMs)` after the prefix bonus, capped at 1.0.
+ * Selections after `nowMs` count as selected now.
+ */
+export interface RecencyBoost {
+  /** Positive; the boost halves every `halfLifeMs` */
+  halfLifeMs: number;
+  /** Boost for a candidate selected at `nowMs`, between 0 and 1 */
+  maxBoost: number;
+  /** Defaults to `Date.now()`; pass it for results that don't depend on the clock */
+  nowMs?: number;
+  /** Compare the boosted score with `minScore` instead of the unboosted one */
+  allowBoostPastCutoff?: boolean;
+}
+
+type ResolvedRecencyBoost = Required<RecencyBoost>;
+
+// A validated boost with `nowMs` filled in (mirrors RecencyBoost::new in string-metrics-core)
+const resolveRecencyBoost = (boost: RecencyBoost): ResolvedRecencyBoost => {
+  const { halfLifeMs, maxBoost, nowMs = Date.now(), allowBoostPastCutoff = false } = boost;
+  if (!Number.isFinite(halfLifeMs) || halfLifeMs <= 0) {
+    throw new Error(`recency_boost half_life_ms must be a positive number, got ${halfLifeMs}`);
+  }
+  if (!(maxBoost >= 0 && maxBoost <= 1)) {
+    throw new Error(`recency_boost max_boost must be between 0 and 1, got ${maxBoost}`);
+  }
+  if (!Number.isFinite(nowMs)) {
+    throw new Error(`recency_boost now_ms must be finite, got ${nowMs}`);
+  }
+  return { halfLifeMs, maxBoost, nowMs, allowBoostPastCutoff };
+};
+
+// What a candidate last selected at `lastSelectedMs` gains; never selected gains 0
+const recencyBoostAmount = (
+  boost: ResolvedRecencyBoost,
+  lastSelectedMs: number | undefined,
+): number => {
+  if (lastSelectedMs === undefined || !Number.isFinite(lastSelectedMs)) {
+    return 0;
+  }
+  const age = Math.max(0, boost.nowMs - lastSelectedMs);
+  return boost.maxBoost * 2 ** (-age / boost.halfLifeMs);
+};
+
+export interface SuggestionOptions extends LengthFilterOptions {
   metric?: SuggestMetric;
   preset?: NormalizationPreset;
   normalizePreset?: NormalizationPreset;
@@ -645,10 +2906,37 @@ export interface SuggestionOptions {
   max_suggestions?: number;
   preferPrefix?: boolean;
   prefer_prefix?: boolean;
+  prefixBonusWeight?: number;
+  prefix_bonus_weight?: number;
   jaroPrefixScale?: number;
   jaro_prefix_scale?: number;
   jaroMaxPrefix?: number;
   jaro_max_prefix?: number;
+  locale?: NormalizationLocale;
+  /** Attach an `explanation` to each returned suggestion */
+  explain?: boolean;
+  queryMode?: QueryMode;
+  query_mode?: QueryMode;
+  tokenAggregation?: TokenAggregation;
+  token_aggregation?: TokenAggregation;
+  /** Applied to the query and every candidate after the preset */
+  expansions?: Expansions;
+  shortStringPolicy?: ShortStringPolicy;
+  short_string_policy?: ShortStringPolicy;
+  shortStringLength?: number;
+  short_string_length?: number;
+  /**
+   * Also score the query split into words of the candidates (`'newyorkcity'` as
+   * `'new york city'`, see `segment_words`), keeping whichever scores each candidate higher
+   */
+  segmentInput?: boolean;
+  segment_input?: boolean;
+  /** Rank with each of these metrics instead of `metric` and merge the rankings */
+  rankFusion?: RankFusion<SuggestMetric>;
+  rank_fusion?: RankFusion<SuggestMetric>;
+  /** Rank recently selected candidates higher (see `RecencyBoost`) */
+  recencyBoost?: RecencyBoost;
+  recency_boost?: RecencyBoost;
 }
 
 type NormalizedSuggestionOptions = {
@@ -658,10 +2946,30 @@ type NormalizedSuggestionOptions = {
   minScore: number;
   maxSuggestions: number;
   preferPrefix: boolean;
+  prefixBonusWeight: number;
   jaroPrefixScale: number;
   jaroMaxPrefix: number;
+  locale?: NormalizationLocale;
+  explain: boolean;
+  queryMode: QueryMode;
+  tokenAggregation: TokenAggregation;
+  expansions?: Expansions;
+  shortStringPolicy: ShortStringPolicy;
+  shortStringLength: number;
+  segmentInput: boolean;
+  lengthFilter: LengthFilter;
+  rankFusion?: { metrics: SuggestMetricCamel[]; method: FusionMethod; k: number };
+  recencyBoost?: ResolvedRecencyBoost;
 };
 
+const DEFAULT_PREFIX_BONUS_WEIGHT = 0.1;
+
+// Clamp to [0, 1]; a missing or non-finite weight uses the default
+const clampPrefixBonusWeight = (weight: number | undefined): number =>
+  weight !== undefined && Number.isFinite(weight)
+    ? Math.min(1, Math.max(0, weight))
+    : DEFAULT_PREFIX_BONUS_WEIGHT;
+
 const normalizeSuggestionOptions = (
   options: SuggestionOptions = {},
 ): NormalizedSuggestionOptions => {
@@ -671,8 +2979,13 @@ const normalizeSuggestionOptions = (
   const minScore = options.minScore ?? options.min_score ?? 0.6;
   const maxSuggestions = options.maxSuggestions ?? options.max_suggestions ?? 5;
   const preferPrefix = options.preferPrefix ?? options.prefer_prefix ?? false;
+  const prefixBonusWeight = clampPrefixBonusWeight(
+    options.prefixBonusWeight ?? options.prefix_bonus_weight,
+  );
   const jaroPrefixScale = options.jaroPrefixScale ?? options.jaro_prefix_scale ?? 0.1;
   const jaroMaxPrefix = options.jaroMaxPrefix ?? options.jaro_max_prefix ?? 4;
+  const rankFusion = options.rankFusion ?? options.rank_fusion;
+  const recencyBoost = options.recencyBoost ?? options.recency_boost;
 
   return {
     metric,
@@ -681,19 +2994,72 @@ const normalizeSuggestionOptions = (
     minScore,
     maxSuggestions,
     preferPrefix,
+    prefixBonusWeight,
     jaroPrefixScale,
     jaroMaxPrefix,
+    locale: options.locale,
+    explain: options.explain ?? false,
+    queryMode: options.queryMode ?? options.query_mode ?? 'whole',
+    tokenAggregation: options.tokenAggregation ?? options.token_aggregation ?? 'mean',
+    expansions: options.expansions,
+    shortStringPolicy: options.shortStringPolicy ?? options.short_string_policy ?? 'none',
+    shortStringLength:
+      options.shortStringLength ?? options.short_string_length ?? DEFAULT_SHORT_STRING_LENGTH,
+    segmentInput: options.segmentInput ?? options.segment_input ?? false,
+    lengthFilter: resolveLengthFilter(options),
+    rankFusion: rankFusion && {
+      metrics: rankFusion.metrics.map(normalizeSuggestMetric),
+      method: rankFusion.method,
+      k: fusionK(rankFusion),
+    },
+    recencyBoost: recencyBoost && resolveRecencyBoost(recencyBoost),
   };
 };
 
+/**
+ * Score breakdown attached by `explain: true`, for answering "why did X beat Y".
+ * Skipped candidates are never returned (`prefilterCounts` tallies them), so `prefilter` is
+ * always `'scored'`.
+ */
+export interface SuggestionExplanation {
+  /** Metric score after the short-string policy, before the prefix bonus */
+  raw_score: number;
+  metric: string;
+  prefix_bonus_applied: boolean;
+  /** What the prefix bonus and any recency boost added to `raw_score` (0 without either) */
+  bonus_amount: number;
+  normalized_input: string;
+  normalized_candidate: string;
+  prefilter: 'scored' | 'skipped_length' | 'skipped_ngram';
+}
+
+/**
+ * A candidate with optional metadata. `locale` overrides the `locale` option for this
+ * candidate's normalization; `id` is echoed back on its suggestion; `lastSelectedMs` (ms since
+ * the epoch) feeds the `recencyBoost` option.
+ */
+export interface SuggestionCandidate {
+  value: string;
+  id?: string;
+  locale?: NormalizationLocale;
+  lastSelectedMs?: number;
+}
+
 export interface Suggestion {
   value: string;
+  /** The candidate's `id`, when it was given as a `SuggestionCandidate` */
+  id?: string;
   score: number;
   matchedRange?: { start: number; end: number };
   normalizedValue?: string;
   reason?: string;
+  explanation?: SuggestionExplanation;
 }
 
+// Explanations report metrics by their snake_case (fixture) names
+const snakeCaseMetric = (metric: SuggestMetricCamel): SuggestMetricSnake =>
+  metric.replace(/[A-Z]/g, (c) => `_${c.toLowerCase()}`) as SuggestMetricSnake;
+
 const computeSimilarity = (
   metric: SuggestMetricCamel,
   query: string,
@@ -768,58 +3134,611 @@ const computeSimilarity = (
   }
 };
 
+// Token mode suggestion score; per-token ranges don't make one candidate range
+const scoreTokens = (
+  settings: NormalizedSuggestionOptions,
+  query: string,
+  candidate: string,
+  jaroOptions: { prefixScale: number; maxPrefix: number },
+): { score: number; explanation: string } => {
+  const scoreToken = (token: string, other: string): number =>
+    computeSimilarity(settings.metric, token, other, jaroOptions).score;
+  const score = tokenQueryScore(query, candidate, settings.tokenAggregation, 1, scoreToken);
+  return {
+    score,
+    explanation: `tokens(${settings.tokenAggregation}, ${snakeCaseMetric(settings.metric)})=${score.toFixed(4)}`,
+  };
+};
+
+type SuggestionSettings = NormalizedSuggestionOptions & { preset: NormalizationPreset };
+
+const resolveSuggestionSettings = (options: SuggestionOptions): SuggestionSettings => {
+  const settings = normalizeSuggestionOptions(options);
+  return {
+    ...settings,
+    preset: settings.preset ?? settings.normalizePreset ?? moduleDefaults.preset ?? 'default',
+    locale: settings.locale ?? moduleDefaults.locale,
+  };
+};
+
+const toCandidate = (entry: string | SuggestionCandidate): SuggestionCandidate =>
+  typeof entry === 'string' ? { value: entry } : entry;
+
+// Candidate normalized with its own locale, falling back to the locale option
+const normalizeCandidate = (candidate: SuggestionCandidate, settings: SuggestionSettings): string =>
+  normalize(candidate.value, settings.preset, candidate.locale ?? settings.locale, {
+    expansions: settings.expansions,
+  });
+
+const normalizeQuery = (rawQuery: string, settings: SuggestionSettings): string =>
+  normalize(rawQuery, settings.preset, settings.locale, { expansions: settings.expansions });
+
+type ScoredCandidate = {
+  suggestion: Suggestion;
+  /** The normalized query the score was computed against */
+  normQuery: string;
+  /**
+   * The metric's own score against the unsegmented query; the short-string policy only
+   * lowers it, so sessions cache it
+   */
+  metricScore: number;
+  /** After the short-string policy, before the prefix bonus */
+  rawScore: number;
+  prefixBonusApplied: boolean;
+  /**
+   * The score `minScore` is compared with: the final score, or the one before the recency
+   * boost unless `allowBoostPastCutoff` is set
+   */
+  cutoffScore: number;
+  /** False when the short-string policy rules the candidate out */
+  eligible: boolean;
+};
+
+const scoreCandidate = (
+  candidate: SuggestionCandidate,
+  normCandidate: string,
+  normQuery: string,
+  settings: SuggestionSettings,
+): ScoredCandidate => {
+  const jaroOptions = {
+    prefixScale: settings.jaroPrefixScale,
+    maxPrefix: settings.jaroMaxPrefix,
+  };
+  const { score, matchedRange, explanation } =
+    settings.queryMode === 'tokens'
+      ? scoreTokens(settings, normQuery, normCandidate, jaroOptions)
+      : computeSimilarity(settings.metric, normQuery, normCandidate, jaroOptions);
+  const policyScore = applyShortStringPolicy(
+    settings.shortStringPolicy,
+    settings.shortStringLength,
+    normQuery,
+    normCandidate,
+    score,
+  );
+  const rawScore = policyScore ?? 0;
+
+  let finalScore = rawScore;
+  const reasons = [explanation];
+  if (rawScore < score) {
+    reasons.push('short_string_penalty');
+  }
+  // An empty query is a prefix of everything but shares nothing with it
+  const prefixBonusApplied =
+    settings.preferPrefix && normQuery.length > 0 && normCandidate.startsWith(normQuery);
+
+  if (prefixBonusApplied) {
+    finalScore = Math.min(1, finalScore + (1 - finalScore) * settings.prefixBonusWeight);
+    reasons.push('prefix_bonus');
+  }
+
+  const suggestion: Suggestion = {
+    value: candidate.value,
+    score: finalScore,
+    normalizedValue: normCandidate,
+    matchedRange,
+    reason: reasons.join(', '),
+  };
+  if (candidate.id !== undefined) {
+    suggestion.id = candidate.id;
+  }
+  return {
+    suggestion,
+    normQuery,
+    metricScore: score,
+    rawScore,
+    prefixBonusApplied,
+    cutoffScore: finalScore,
+    eligible: policyScore !== undefined,
+  };
+};
+
+// Add the candidate's recency boost once the query is chosen, so segmentation compares
+// unboosted scores (mirrors suggest_candidates in string-metrics-core)
+const withRecencyBoost = (
+  scored: ScoredCandidate,
+  candidate: SuggestionCandidate,
+  settings: SuggestionSettings,
+): ScoredCandidate => {
+  const boost = settings.recencyBoost;
+  const amount = boost ? recencyBoostAmount(boost, candidate.lastSelectedMs) : 0;
+  if (boost === undefined || amount === 0) {
+    return scored;
+  }
+  const { suggestion } = scored;
+  const boosted = Math.min(1, suggestion.score + amount);
+  return {
+    ...scored,
+    suggestion: { ...suggestion, score: boosted, reason: `${suggestion.reason}, recency_boost` },
+    cutoffScore: boost.allowBoostPastCutoff ? boosted : suggestion.score,
+  };
+};
+
+// The normalized query split into candidate words, when `segmentInput` is set and
+// segmentation changes it
+const segmentQuery = (
+  normQuery: string,
+  normCandidates: string[],
+  settings: SuggestionSettings,
+): string | undefined =>
+  settings.segmentInput ? wasm.segmented_query(normQuery, normCandidates) : undefined;
+
+// The query's score, or the segmented query's when that one scores strictly higher
+// (mirrors suggest_candidates in string-metrics-core)
+const scoreWithSegmentation = (
+  candidate: SuggestionCandidate,
+  normCandidate: string,
+  normQuery: string,
+  segmentedQuery: string | undefined,
+  settings: SuggestionSettings,
+): ScoredCandidate => {
+  const whole = scoreCandidate(candidate, normCandidate, normQuery, settings);
+  if (segmentedQuery === undefined) {
+    return withRecencyBoost(whole, candidate, settings);
+  }
+  const segmented = scoreCandidate(candidate, normCandidate, segmentedQuery, settings);
+  if (
+    segmented.eligible &&
+    (!whole.eligible || segmented.suggestion.score > whole.suggestion.score)
+  ) {
+    segmented.suggestion.reason = `${segmented.suggestion.reason}, segmented_input`;
+    return withRecencyBoost(
+      { ...segmented, metricScore: whole.metricScore },
+      candidate,
+      settings,
+    );
+  }
+  return withRecencyBoost(whole, candidate, settings);
+};
+
+// Explain (when asked) and round one returned suggestion; `settings.metric` scored it
+const finishSuggestion = (
+  { suggestion, normQuery, rawScore, prefixBonusApplied }: ScoredCandidate,
+  settings: SuggestionSettings,
+): Suggestion => {
+  if (settings.explain) {
+    suggestion.explanation = {
+      raw_score: rawScore,
+      metric: snakeCaseMetric(settings.metric),
+      prefix_bonus_applied: prefixBonusApplied,
+      bonus_amount: suggestion.score - rawScore,
+      normalized_input: normQuery,
+      normalized_candidate: suggestion.normalizedValue ?? '',
+      prefilter: 'scored',
+    };
+  }
+  suggestion.score = roundScore(suggestion.score);
+  return suggestion;
+};
+
+const passesMinScore = (
+  { cutoffScore, eligible }: ScoredCandidate,
+  settings: SuggestionSettings,
+): boolean => eligible && cutoffScore >= settings.minScore;
+
+// Filter, rank (ties keep candidate order; Array.prototype.sort is stable on every engine since
+// ES2019) and truncate; explanations are built only for the suggestions returned
+const rankSuggestions = (scored: ScoredCandidate[], settings: SuggestionSettings): Suggestion[] =>
+  scored
+    .filter((candidate) => passesMinScore(candidate, settings))
+    .sort((a, b) => b.suggestion.score - a.suggestion.score)
+    .slice(0, settings.maxSuggestions)
+    .map((candidate) => finishSuggestion(candidate, settings));
+
+type MetricScores = {
+  settings: SuggestionSettings;
+  /** Each candidate's score in input order, undefined when it was length-filtered */
+  scored: Array<ScoredCandidate | undefined>;
+};
+
+// Rank each metric's scores and merge the rankings; every suggestion keeps its best score
+// across the metrics, the first metric's on ties (mirrors suggest_candidates in
+// string-metrics-core)
+const fuseSuggestions = (
+  byMetric: MetricScores[],
+  fusion: { method: FusionMethod; k: number },
+  settings: SuggestionSettings,
+): Suggestion[] => {
+  const best = new Map<number, { candidate: ScoredCandidate; settings: SuggestionSettings }>();
+  const rankings = byMetric.map(({ settings: metricSettings, scored }) => {
+    const ranked: Array<{ index: number; candidate: ScoredCandidate }> = [];
+    scored.forEach((candidate, index) => {
+      if (candidate !== undefined && passesMinScore(candidate, metricSettings)) {
+        ranked.push({ index, candidate });
+        const current = best.get(index)?.candidate.suggestion.score;
+        if (current === undefined || candidate.suggestion.score > current) {
+          best.set(index, { candidate, settings: metricSettings });
+        }
+      }
+    });
+    ranked.sort((a, b) => b.candidate.suggestion.score - a.candidate.suggestion.score);
+    return ranked.map(({ index }) => index);
+  });
+  return fuseRankings(rankings, fusion.method, fusion.k)
+    .slice(0, settings.maxSuggestions)
+    .flatMap((index) => {
+      const entry = best.get(index);
+      return entry === undefined ? [] : [finishSuggestion(entry.candidate, entry.settings)];
+    });
+};
+
+// Scores every candidate against the normalized query, in input order and unrounded, under
+// `settings` or (for rank fusion) one fused metric's copy of them; undefined marks a candidate
+// the length filter skipped
+const candidateScorer = (
+  rawQuery: string,
+  candidates: Array<string | SuggestionCandidate>,
+  settings: SuggestionSettings,
+): ((metricSettings: SuggestionSettings) => Array<ScoredCandidate | undefined>) => {
+  checkInputLengths([rawQuery]);
+  const normQuery = normalizeQuery(rawQuery, settings);
+
+  const entries = candidates.map(toCandidate);
+  checkInputLengths(entries.map((candidate) => candidate.value));
+  const normCandidates = entries.map((candidate) => normalizeCandidate(candidate, settings));
+  const segmentedQuery = segmentQuery(normQuery, normCandidates, settings);
+  const queryLength = Array.from(normQuery).length;
+  const admitted = normCandidates.map((normCandidate) =>
+    admitsLength(settings.lengthFilter, queryLength, normCandidate),
+  );
+  return (metricSettings) =>
+    entries.map((candidate, index) =>
+      admitted[index]
+        ? scoreWithSegmentation(
+            candidate,
+            normCandidates[index],
+            normQuery,
+            segmentedQuery,
+            metricSettings,
+          )
+        : undefined,
+    );
+};
+
 export function suggest(
   rawQuery: string,
-  candidates: string[],
+  candidates: Array<string | SuggestionCandidate>,
   options: SuggestionOptions = {},
 ): Suggestion[] {
-  const {
-    metric,
-    preset: presetOption,
-    normalizePreset: normalizePresetOption,
-    minScore,
-    maxSuggestions,
-    preferPrefix,
-    jaroPrefixScale,
-    jaroMaxPrefix,
-  } = normalizeSuggestionOptions(options);
-
-  const preset = presetOption ?? normalizePresetOption ?? 'default';
-
-  const normQuery = normalize(rawQuery, preset);
-
-  const scored = candidates.map((candidate) => {
-    const normCandidate = normalize(candidate, preset);
-    const { score, matchedRange, explanation } = computeSimilarity(
-      metric,
-      normQuery,
-      normCandidate,
-      {
-        prefixScale: jaroPrefixScale,
-        maxPrefix: jaroMaxPrefix,
-      },
-    );
+  const settings = resolveSuggestionSettings(options);
+  const scoreAll = candidateScorer(rawQuery, candidates, settings);
+  const fusion = settings.rankFusion;
+  if (fusion) {
+    const byMetric = fusion.metrics.map((metric) => {
+      const metricSettings = { ...settings, metric };
+      return { settings: metricSettings, scored: scoreAll(metricSettings) };
+    });
+    return fuseSuggestions(byMetric, fusion, settings);
+  }
+  const scored = scoreAll(settings).filter(
+    (candidate): candidate is ScoredCandidate => candidate !== undefined,
+  );
+  return rankSuggestions(scored, settings);
+}
+
+/** Lowest score `did_you_mean` offers as a correction */
+export const DID_YOU_MEAN_MIN_SCORE = 0.8;
+/** How far the top suggestion must lead the runner-up for `did_you_mean` to pick it */
+export const DID_YOU_MEAN_MARGIN = 0.05;
+
+export type DidYouMean =
+  | { kind: 'exact'; value: string }
+  | { kind: 'corrected'; value: string; score: number }
+  | { kind: 'none' };
+
+/**
+ * The candidate `input` most likely meant, under a fixed policy shared with the Rust core:
+ * a candidate equal to the input after 'default' normalization wins (the first one in
+ * candidate order); otherwise the top jaro_winkler suggestion is a correction when it scores
+ * at least `DID_YOU_MEAN_MIN_SCORE` and leads the runner-up by at least `DID_YOU_MEAN_MARGIN`.
+ * Candidates normalizing to the top's value are not runners-up. Anything else is `none`, so
+ * an ambiguous input is never silently corrected.
+ *
+ * Both steps normalize under the module default locale, and the thresholds compare unrounded
+ * scores; `scorePrecision` only rounds the returned score.
+ */
+export function did_you_mean(input: string, candidates: readonly string[]): DidYouMean {
+  const locale = moduleDefaults.locale;
+  const normalizedInput = normalize(input, 'default', locale);
+  const exact = candidates.find(
+    (candidate) => normalize(candidate, 'default', locale) === normalizedInput,
+  );
+  if (exact !== undefined) {
+    return { kind: 'exact', value: exact };
+  }
+
+  const settings = resolveSuggestionSettings({
+    metric: 'jaro_winkler',
+    normalizePreset: 'default',
+    locale,
+    minScore: 0,
+  });
+  // Stable, so ties keep candidate order as in `suggest`
+  const ranked = candidateScorer(input, [...candidates], settings)(settings)
+    .filter(
+      (candidate): candidate is ScoredCandidate =>
+        candidate !== undefined && passesMinScore(candidate, settings),
+    )
+    .map(({ suggestion }) => suggestion)
+    .sort((a, b) => b.score - a.score);
+  const [top] = ranked;
+  if (top === undefined) {
+    return { kind: 'none' };
+  }
+  const runnerUp =
+    ranked.find((suggestion) => suggestion.normalizedValue !== top.normalizedValue)?.score ?? 0;
+  if (top.score < DID_YOU_MEAN_MIN_SCORE || top.score - runnerUp < DID_YOU_MEAN_MARGIN) {
+    return { kind: 'none' };
+  }
+  return { kind: 'corrected', value: top.value, score: roundScore(top.score) };
+}
+
+// ============================================================================
+// Suggest Sessions - Refining the previous keystroke's results
+// ============================================================================
+
+/**
+ * How `SuggestSession.query` reuses the previous query's scores when the new query only
+ * adds characters before or after it:
+ * - `'off'`: score every candidate
+ * - `'safe'`: skip candidates that provably cannot reach `minScore`; results are identical
+ *   to `suggest`. Only the `levenshtein` and `substring` metrics have a proof, other
+ *   metrics fall back to scoring every candidate
+ * - `'aggressive'`: only rescore candidates whose last score was within
+ *   `AGGRESSIVE_REFINE_MARGIN` of `minScore`. Faster for any metric, but may miss
+ *   candidates that a fresh `suggest` would return
+ */
+export type RefineMode = 'off' | 'safe' | 'aggressive';
+
+export interface SuggestSessionOptions extends SuggestionOptions {
+  refine?: RefineMode;
+}
+
+/** How far below `minScore` a last score may be and still be rescored by `'aggressive'` */
+export const AGGRESSIVE_REFINE_MARGIN = 0.2;
+
+// Absorbs float error in the bounds so a candidate exactly at minScore is never skipped
+const REFINE_BOUND_EPSILON = 1e-9;
+
+type CachedScore = {
+  normCandidate: string;
+  rawScore: number;
+  /** Code point length of the normalized query the score was computed against */
+  queryLength: number;
+};
+
+// Upper bound on a candidate's raw score once `added` characters are placed around the
+// query it was scored against (`queryLength` characters); undefined when no bound is proven
+const refinedScoreBound = (
+  metric: SuggestMetricCamel,
+  cached: CachedScore,
+  added: number,
+): number | undefined => {
+  const m = cached.queryLength;
+  const n = Array.from(cached.normCandidate).length;
+  switch (metric) {
+    case 'levenshtein': {
+      // Each added character lowers the distance by at most one
+      const distance = Math.round((1 - cached.rawScore) * Math.max(m, n));
+      const longest = Math.max(m + added, n);
+      return longest === 0 ? 1 : 1 - Math.max(0, distance - added) / longest;
+    }
+    case 'substring': {
+      // A common substring of the new query overlaps the old one in at most `common`
+      // characters, so it grows by at most the added characters
+      const common = Math.round((cached.rawScore * (m + n)) / 2);
+      return Math.min(1, (2 * (common + added)) / (m + added + n));
+    }
+    default:
+      return undefined;
+  }
+};
+
+// The prefix bonus only raises a score, so a bound that assumes it applies stays a bound;
+// the recency boost only counts toward the cutoff with `allowBoostPastCutoff`
+const withPrefixBonus = (bound: number, settings: SuggestionSettings): number => {
+  const bonused = settings.preferPrefix
+    ? Math.min(1, bound + (1 - bound) * settings.prefixBonusWeight)
+    : bound;
+  const boost = settings.recencyBoost;
+  return boost?.allowBoostPastCutoff ? Math.min(1, bonused + boost.maxBoost) : bonused;
+};
+
+// Raw scores depend only on these settings; changing any of them invalidates the cache
+const scoreCacheKey = (settings: SuggestionSettings): string =>
+  [
+    settings.metric,
+    settings.preset,
+    settings.locale ?? '',
+    settings.jaroPrefixScale,
+    settings.jaroMaxPrefix,
+    settings.queryMode,
+    settings.tokenAggregation,
+    JSON.stringify(settings.expansions ?? {}),
+  ].join('|');
+
+/** Most selections a `SuggestSession` remembers; recording more drops the oldest */
+export const SELECTION_HISTORY_LIMIT = 100;
+
+/** One `SuggestSession.recordSelection`: the candidate's index and when it was picked */
+export interface SuggestionSelection {
+  index: number;
+  timestampMs: number;
+}
 
-    let finalScore = score;
-    const reasons = [explanation];
+/** A `SuggestSession`'s candidates and selection history, as `toJSON` writes them */
+export interface SuggestSessionState {
+  candidates: SuggestionCandidate[];
+  selections: SuggestionSelection[];
+}
+
+/**
+ * Suggestions for a query that is typed one keystroke at a time against a fixed candidate
+ * list. Each `query` caches every candidate's normalized value and raw score, so the next
+ * query can skip work when it extends the previous one (see `RefineMode`).
+ *
+ * `recordSelection` remembers which candidates the user picked; with the `recencyBoost` option
+ * each candidate is boosted from its latest selection (or its own `lastSelectedMs`, whichever
+ * is later). The history serializes with the candidates through `toJSON` and `fromJSON`.
+ */
+export class SuggestSession {
+  private readonly candidates: SuggestionCandidate[];
+  private selections: SuggestionSelection[] = [];
+  private cacheKey: string | undefined;
+  private previousQuery = '';
+  private cache: Array<CachedScore | undefined> = [];
+
+  constructor(candidates: Array<string | SuggestionCandidate>) {
+    this.candidates = candidates.map(toCandidate);
+  }
 
-    if (preferPrefix && normCandidate.startsWith(normQuery)) {
-      const bonusWeight = 0.1;
-      finalScore = Math.min(1, finalScore + (1 - finalScore) * bonusWeight);
-      reasons.push('prefix_bonus');
+  /** A session with the candidates and selection history `toJSON` returned */
+  static fromJSON(state: SuggestSessionState): SuggestSession {
+    const session = new SuggestSession(state.candidates);
+    for (const { index, timestampMs } of state.selections) {
+      session.recordSelection(index, timestampMs);
     }
+    return session;
+  }
 
+  toJSON(): SuggestSessionState {
     return {
-      value: candidate,
-      score: finalScore,
-      normalizedValue: normCandidate,
-      matchedRange,
-      reason: reasons.join(', '),
+      candidates: this.candidates.map((candidate) => ({ ...candidate })),
+      selections: this.selections.map((selection) => ({ ...selection })),
     };
-  });
+  }
+
+  /** Remember that the candidate at `index` was picked at `timestampMs` (ms since the epoch) */
+  recordSelection(index: number, timestampMs: number = Date.now()): void {
+    if (!Number.isInteger(index) || index < 0 || index >= this.candidates.length) {
+      throw new Error(
+        `Selection index ${index} is out of range for ${this.candidates.length} candidates`,
+      );
+    }
+    if (!Number.isFinite(timestampMs)) {
+      throw new Error(`Selection timestamp must be finite, got ${timestampMs}`);
+    }
+    this.selections.push({ index, timestampMs });
+    if (this.selections.length > SELECTION_HISTORY_LIMIT) {
+      this.selections.splice(0, this.selections.length - SELECTION_HISTORY_LIMIT);
+    }
+  }
+
+  query(rawQuery: string, options: SuggestSessionOptions = {}): Suggestion[] {
+    checkInputLengths([rawQuery]);
+    checkInputLengths(this.candidates.map((candidate) => candidate.value));
+    const settings = resolveSuggestionSettings(options);
+    const candidates = settings.recencyBoost ? this.withSelections() : this.candidates;
+    if (settings.rankFusion) {
+      // Scores are cached for one metric; a fused query rescores everything
+      this.cacheKey = undefined;
+      return suggest(rawQuery, candidates, options);
+    }
+    const refine = options.refine ?? 'off';
+    const normQuery = normalizeQuery(rawQuery, settings);
+    const queryLength = Array.from(normQuery).length;
+
+    const key = scoreCacheKey(settings);
+    const extendsPrevious =
+      key === this.cacheKey &&
+      (normQuery.startsWith(this.previousQuery) || normQuery.endsWith(this.previousQuery));
+    if (!extendsPrevious) {
+      this.cache = [];
+    }
+
+    // Segmentation needs every candidate's words, and its query doesn't extend the last one,
+    // so no candidate is skipped
+    const normCandidates = settings.segmentInput
+      ? candidates.map(
+          (candidate, index) =>
+            this.cache[index]?.normCandidate ?? normalizeCandidate(candidate, settings),
+        )
+      : [];
+    const segmentedQuery = segmentQuery(normQuery, normCandidates, settings);
+
+    const scored: ScoredCandidate[] = [];
+    candidates.forEach((candidate, index) => {
+      const cached = this.cache[index];
+      if (!settings.segmentInput && cached && this.canSkip(refine, cached, queryLength, settings)) {
+        return;
+      }
+      const normCandidate =
+        normCandidates[index] ?? cached?.normCandidate ?? normalizeCandidate(candidate, settings);
+      if (!admitsLength(settings.lengthFilter, queryLength, normCandidate)) {
+        return;
+      }
+      const result = scoreWithSegmentation(
+        candidate,
+        normCandidate,
+        normQuery,
+        segmentedQuery,
+        settings,
+      );
+      this.cache[index] = { normCandidate, rawScore: result.metricScore, queryLength };
+      scored.push(result);
+    });
+
+    this.cacheKey = key;
+    this.previousQuery = normQuery;
+    return rankSuggestions(scored, settings);
+  }
+
+  // The candidates with `lastSelectedMs` moved up to their latest recorded selection
+  private withSelections(): SuggestionCandidate[] {
+    const latest = new Map<number, number>();
+    for (const { index, timestampMs } of this.selections) {
+      latest.set(index, Math.max(timestampMs, latest.get(index) ?? -Infinity));
+    }
+    return this.candidates.map((candidate, index) => {
+      const selected = latest.get(index);
+      if (selected === undefined || (candidate.lastSelectedMs ?? -Infinity) >= selected) {
+        return candidate;
+      }
+      return { ...candidate, lastSelectedMs: selected };
+    });
+  }
 
-  return scored
-    .filter((entry) => entry.score >= minScore)
-    .sort((a, b) => b.score - a.score)
-    .slice(0, maxSuggestions);
+  // A skipped candidate keeps the score it was last computed with, so later bounds are
+  // taken from that older (still contained) query
+  private canSkip(
+    refine: RefineMode,
+    cached: CachedScore,
+    queryLength: number,
+    settings: SuggestionSettings,
+  ): boolean {
+    if (refine === 'aggressive') {
+      return cached.rawScore < settings.minScore - AGGRESSIVE_REFINE_MARGIN;
+    }
+    if (refine === 'safe') {
+      // The bounds are proven for whole-query scores only
+      const bound =
+        settings.queryMode === 'whole'
+          ? refinedScoreBound(settings.metric, cached, queryLength - cached.queryLength)
+          : undefined;
+      return (
+        bound !== undefined &&
+        withPrefixBonus(bound, settings) + REFINE_BOUND_EPSILON < settings.minScore
+      );
+    }
+    return false;
+  }
 }
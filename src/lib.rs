@@ -1,7 +1,66 @@
-use unicode_categories::UnicodeCategories;
-use unicode_normalization::UnicodeNormalization;
+use std::sync::{PoisonError, RwLock};
+
+use js_sys::JsString;
+use string_metrics_core::{Expansions, NormalizeOptions, ScoreScale};
 use wasm_bindgen::prelude::*;
 
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn warn(message: &str);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn warn(message: &str) {
+    eprintln!("{}", message);
+}
+
+// Every similarity export returns through here. A score outside its scale or
+// not finite is a bug in the metric: debug builds and the `strict-invariants`
+// feature panic, other release builds clamp it (NaN to 0) and warn instead of
+// handing callers a 1.0000000000000002 that breaks their own clamping.
+fn checked_score(metric: &str, score: f64, scale: ScoreScale) -> f64 {
+    let max = scale.max();
+    if (0.0..=max).contains(&score) {
+        return score;
+    }
+    let message = format!("{} returned {}, outside [0, {}]", metric, score, max);
+    if cfg!(any(debug_assertions, feature = "strict-invariants")) {
+        panic!("{}", message);
+    }
+    warn(&message);
+    if score.is_nan() {
+        0.0
+    } else {
+        score.clamp(0.0, max)
+    }
+}
+
+// JS strings are UTF-16 and may hold unpaired surrogates. wasm-bindgen converts
+// every `&str` argument lossily, so each one arrives as U+FFFD and "\uD800"
+// compares equal to "\uFFFD". That stays the default; callers that set
+// `strictUnicode` run their inputs through here first to get an error instead.
+// Taking the JsString keeps the check ahead of the conversion.
+#[wasm_bindgen]
+pub fn check_well_formed(s: &JsString) -> Result<(), JsError> {
+    let mut index = 0;
+    for decoded in char::decode_utf16(s.iter()) {
+        match decoded {
+            Ok(c) => index += c.len_utf16(),
+            Err(error) => {
+                return Err(JsError::new(&format!(
+                    "Input contains an unpaired surrogate (0x{:04X} at UTF-16 index {}); \
+                     strictUnicode rejects it instead of comparing it as U+FFFD",
+                    error.unpaired_surrogate(),
+                    index
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
 // Levenshtein distance
 #[wasm_bindgen]
 pub fn levenshtein(a: &str, b: &str) -> usize {
@@ -11,7 +70,73 @@ pub fn levenshtein(a: &str, b: &str) -> usize {
 // Normalized Levenshtein similarity
 #[wasm_bindgen]
 pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::levenshtein::normalized_similarity(a.chars(), b.chars())
+    checked_score(
+        "normalized_levenshtein",
+        rapidfuzz::distance::levenshtein::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
+
+// Byte-wise Levenshtein distance. A Uint8Array argument is copied into WASM memory once,
+// without the UTF-8 decoding string arguments go through
+#[wasm_bindgen]
+pub fn levenshtein_bytes(a: &[u8], b: &[u8]) -> usize {
+    string_metrics_core::levenshtein_bytes(a, b)
+}
+
+// Byte-wise normalized Levenshtein similarity
+#[wasm_bindgen]
+pub fn normalized_levenshtein_bytes(a: &[u8], b: &[u8]) -> f64 {
+    checked_score(
+        "normalized_levenshtein_bytes",
+        string_metrics_core::normalized_levenshtein_bytes(a, b),
+        ScoreScale::Unit,
+    )
+}
+
+// Hamming distance over chars; unequal lengths throw
+#[wasm_bindgen]
+pub fn hamming(a: &str, b: &str) -> Result<usize, JsError> {
+    hamming_with_padding(a, b, false)
+}
+
+// Hamming distance over chars; with pad each extra char of the longer string counts once,
+// without it unequal lengths throw
+#[wasm_bindgen]
+pub fn hamming_with_padding(a: &str, b: &str, pad: bool) -> Result<usize, JsError> {
+    string_metrics_core::hamming_distance(a, b, pad).map_err(|message| JsError::new(&message))
+}
+
+// Normalized Hamming similarity over chars; unequal lengths throw
+#[wasm_bindgen]
+pub fn normalized_hamming_similarity(a: &str, b: &str) -> Result<f64, JsError> {
+    normalized_hamming_similarity_with_padding(a, b, false)
+}
+
+// Normalized Hamming similarity over chars, padding the shorter string when pad is set
+#[wasm_bindgen]
+pub fn normalized_hamming_similarity_with_padding(
+    a: &str,
+    b: &str,
+    pad: bool,
+) -> Result<f64, JsError> {
+    string_metrics_core::hamming_normalized_similarity(a, b, pad)
+        .map(|score| checked_score("normalized_hamming_similarity", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Byte-wise Hamming distance; unequal lengths throw unless pad is set
+#[wasm_bindgen]
+pub fn hamming_bytes(a: &[u8], b: &[u8], pad: bool) -> Result<usize, JsError> {
+    string_metrics_core::hamming_bytes(a, b, pad).map_err(|message| JsError::new(&message))
+}
+
+// Byte-wise normalized Hamming similarity
+#[wasm_bindgen]
+pub fn normalized_hamming_bytes(a: &[u8], b: &[u8], pad: bool) -> Result<f64, JsError> {
+    string_metrics_core::normalized_hamming_bytes(a, b, pad)
+        .map(|score| checked_score("normalized_hamming_bytes", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
 }
 
 // Optimal String Alignment (OSA) distance (restricted Damerau-Levenshtein)
@@ -23,7 +148,11 @@ pub fn osa_distance(a: &str, b: &str) -> usize {
 // Normalized OSA similarity
 #[wasm_bindgen]
 pub fn normalized_osa_similarity(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::osa::normalized_similarity(a.chars(), b.chars())
+    checked_score(
+        "normalized_osa_similarity",
+        rapidfuzz::distance::osa::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
 }
 
 // Damerau-Levenshtein distance (unrestricted)
@@ -35,81 +164,199 @@ pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
 // Normalized Damerau-Levenshtein similarity
 #[wasm_bindgen]
 pub fn normalized_damerau_levenshtein(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::damerau_levenshtein::normalized_similarity(a.chars(), b.chars())
+    checked_score(
+        "normalized_damerau_levenshtein",
+        rapidfuzz::distance::damerau_levenshtein::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
+
+// Damerau-Levenshtein distance over extended grapheme clusters (a ZWJ emoji sequence is one unit)
+#[wasm_bindgen]
+pub fn damerau_levenshtein_graphemes(a: &str, b: &str) -> usize {
+    string_metrics_core::damerau_levenshtein_graphemes(a, b)
+}
+
+// Normalized grapheme Damerau-Levenshtein similarity (0.0-1.0)
+#[wasm_bindgen]
+pub fn normalized_damerau_levenshtein_graphemes(a: &str, b: &str) -> f64 {
+    checked_score(
+        "normalized_damerau_levenshtein_graphemes",
+        string_metrics_core::damerau_levenshtein_graphemes_normalized_similarity(a, b),
+        ScoreScale::Unit,
+    )
+}
+
+// Damerau-Levenshtein total cost with a custom transposition cost (clamped to (0, 2])
+#[wasm_bindgen]
+pub fn damerau_levenshtein_weighted(a: &str, b: &str, transposition_cost: f64) -> f64 {
+    string_metrics_core::damerau_levenshtein_weighted(a, b, transposition_cost)
+}
+
+// Normalized weighted Damerau-Levenshtein similarity (0.0-1.0)
+#[wasm_bindgen]
+pub fn normalized_damerau_levenshtein_weighted(a: &str, b: &str, transposition_cost: f64) -> f64 {
+    checked_score(
+        "normalized_damerau_levenshtein_weighted",
+        string_metrics_core::damerau_levenshtein_weighted_normalized_similarity(
+            a,
+            b,
+            transposition_cost,
+        ),
+        ScoreScale::Unit,
+    )
+}
+
+// Most-frequent-k-characters similarity (0.0-1.0); k = 0 compares every char
+#[wasm_bindgen]
+pub fn mfc_similarity(a: &str, b: &str, k: usize) -> f64 {
+    checked_score(
+        "mfc_similarity",
+        string_metrics_core::mfc_similarity(a, b, k),
+        ScoreScale::Unit,
+    )
+}
+
+// Storable most-frequent-k-characters signature, e.g. "r2:e2"
+#[wasm_bindgen]
+pub fn mfc_signature(s: &str, k: usize) -> String {
+    string_metrics_core::mfc_signature(s, k)
+}
+
+// mfc_similarity from two stored signatures; throws on a malformed signature
+#[wasm_bindgen]
+pub fn mfc_signature_similarity(a: &str, b: &str) -> Result<f64, JsError> {
+    string_metrics_core::mfc_signature_similarity(a, b)
+        .map(|score| checked_score("mfc_signature_similarity", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Affine-gap (Gotoh) alignment cost; throws on invalid penalties
+#[wasm_bindgen]
+pub fn affine_gap_distance(
+    a: &str,
+    b: &str,
+    mismatch: f64,
+    gap_open: f64,
+    gap_extend: f64,
+) -> Result<f64, JsError> {
+    string_metrics_core::affine_gap_distance(a, b, mismatch, gap_open, gap_extend)
+        .map_err(|message| JsError::new(&message))
+}
+
+// Normalized affine-gap similarity (0.0-1.0); throws on invalid penalties
+#[wasm_bindgen]
+pub fn normalized_affine_gap_similarity(
+    a: &str,
+    b: &str,
+    mismatch: f64,
+    gap_open: f64,
+    gap_extend: f64,
+) -> Result<f64, JsError> {
+    string_metrics_core::affine_gap_normalized_similarity(a, b, mismatch, gap_open, gap_extend)
+        .map(|score| checked_score("normalized_affine_gap_similarity", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
 }
 
 // Jaro similarity
 #[wasm_bindgen]
 pub fn jaro(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::jaro::similarity(a.chars(), b.chars())
+    checked_score(
+        "jaro",
+        rapidfuzz::distance::jaro::similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
 }
 
 #[wasm_bindgen]
 pub fn jaro_winkler_with_params(a: &str, b: &str, prefix_scale: f64, max_prefix: usize) -> f64 {
-    let clamped_scale = prefix_scale.clamp(0.0, 0.25);
-    let clamped_max = max_prefix.clamp(1, 8);
-    let jaro_score = rapidfuzz::distance::jaro::similarity(a.chars(), b.chars());
-    if jaro_score == 1.0 {
-        return 1.0;
-    }
-
-    let mut prefix_len = 0;
-    for (ca, cb) in a.chars().zip(b.chars()) {
-        if ca == cb {
-            prefix_len += 1;
-            if prefix_len >= clamped_max {
-                break;
-            }
-        } else {
-            break;
-        }
-    }
-
-    let adjustment = (prefix_len as f64) * clamped_scale * (1.0 - jaro_score);
-    (jaro_score + adjustment).min(1.0)
+    checked_score(
+        "jaro_winkler_with_params",
+        string_metrics_core::jaro_winkler_with_params(a, b, prefix_scale, max_prefix),
+        ScoreScale::Unit,
+    )
 }
 
 // Jaro-Winkler similarity
 #[wasm_bindgen]
 pub fn jaro_winkler(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::jaro_winkler::similarity(a.chars(), b.chars())
-}
-
-// Normalization function with optional locale support
-fn case_fold_with_locale(s: &str, locale: Option<&str>) -> String {
-    match locale {
-        // Turkish and Azerbaijani: special handling for dotted/dotless I
-        Some("tr") | Some("az") => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i'],      // İ (with dot) → i (lowercase with dot)
-                'I' => vec!['ı'],      // I (no dot) → ı (lowercase dotless)
-                'ß' => vec!['s', 's'], // German sharp S
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+    checked_score(
+        "jaro_winkler",
+        rapidfuzz::distance::jaro_winkler::similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
 
-        // Lithuanian: add combining dot above for i/j/į when followed by accents
-        // For now, we implement basic support - full Lithuanian requires accent detection
-        Some("lt") => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i', '\u{0307}'], // Preserve combining dot behavior
-                'ß' => vec!['s', 's'],
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+// Jaro over tokens (tokenizer "whitespace" or "words"); two tokens match when their
+// Jaro-Winkler reaches match_threshold
+#[wasm_bindgen]
+pub fn jaro_tokens(
+    a: &str,
+    b: &str,
+    match_threshold: f64,
+    tokenizer: &str,
+) -> Result<f64, JsError> {
+    let tokenizer = string_metrics_core::Tokenizer::parse(tokenizer)
+        .map_err(|message| JsError::new(&message))?;
+    Ok(checked_score(
+        "jaro_tokens",
+        string_metrics_core::jaro_tokens(a, b, match_threshold, tokenizer),
+        ScoreScale::Unit,
+    ))
+}
 
-        // Default (no locale or unknown locale): Unicode casefold
-        _ => s
-            .chars()
-            .flat_map(|c| match c {
-                'İ' => vec!['i', '\u{0307}'], // İ → i + combining dot (Unicode default)
-                'ß' => vec!['s', 's'],
-                _ => c.to_lowercase().collect(),
-            })
-            .collect(),
+// Token Jaro with the Winkler boost for leading matching tokens
+#[wasm_bindgen]
+pub fn jaro_winkler_tokens(
+    a: &str,
+    b: &str,
+    prefix_scale: f64,
+    match_threshold: f64,
+    tokenizer: &str,
+) -> Result<f64, JsError> {
+    let tokenizer = string_metrics_core::Tokenizer::parse(tokenizer)
+        .map_err(|message| JsError::new(&message))?;
+    Ok(checked_score(
+        "jaro_winkler_tokens",
+        string_metrics_core::jaro_winkler_tokens(a, b, prefix_scale, match_threshold, tokenizer),
+        ScoreScale::Unit,
+    ))
+}
+
+// Weighted Jaccard of two token -> weight maps, each passed from JS as parallel token and
+// weight lists; tokens are normalized with preset ("none" compares them exactly)
+#[wasm_bindgen]
+pub fn weighted_jaccard(
+    a_tokens: Vec<String>,
+    a_weights: Vec<f64>,
+    b_tokens: Vec<String>,
+    b_weights: Vec<f64>,
+    preset: &str,
+    locale: Option<String>,
+) -> Result<f64, JsError> {
+    let a = token_weight_pairs(&a_tokens, &a_weights)?;
+    let b = token_weight_pairs(&b_tokens, &b_weights)?;
+    string_metrics_core::weighted_jaccard(&a, &b, preset, locale.as_deref())
+        .map(|score| checked_score("weighted_jaccard", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+fn token_weight_pairs<'a>(
+    tokens: &'a [String],
+    weights: &[f64],
+) -> Result<Vec<(&'a str, f64)>, JsError> {
+    if tokens.len() != weights.len() {
+        return Err(JsError::new(&format!(
+            "Tokens and weights must have equal lengths (got {} and {})",
+            tokens.len(),
+            weights.len()
+        )));
     }
+    Ok(tokens
+        .iter()
+        .map(String::as_str)
+        .zip(weights.iter().copied())
+        .collect())
 }
 
 // Normalization function
@@ -121,30 +368,898 @@ pub fn normalize(s: &str, preset: &str) -> String {
 // Normalization function with locale support
 #[wasm_bindgen]
 pub fn normalize_with_locale(s: &str, preset: &str, locale: Option<String>) -> String {
-    let locale_ref = locale.as_deref();
-
-    match preset {
-        "none" => s.to_string(),
-        "minimal" => s.trim().chars().nfc().collect::<String>(),
-        "default" => case_fold_with_locale(s, locale_ref)
-            .trim()
-            .chars()
-            .nfc()
-            .collect::<String>(),
-        "aggressive" => {
-            let folded = case_fold_with_locale(s, locale_ref);
-            let nfkd = folded.chars().nfkd().collect::<String>();
-            let without_diac = nfkd
-                .chars()
-                .filter(|c| !c.is_mark_nonspacing())
-                .collect::<String>();
-            let alphanum = without_diac
-                .chars()
-                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-                .collect::<String>();
-            alphanum.trim().to_string()
+    string_metrics_core::normalize_with_locale(s, preset, locale.as_deref())
+}
+
+// Whether case folding would change the string; false for uncased scripts like CJK
+#[wasm_bindgen]
+pub fn needs_case_fold(s: &str) -> bool {
+    string_metrics_core::needs_case_fold(s)
+}
+
+// `expansions` comes from JS flattened as [key, replacement, key, replacement, ...]
+fn parse_expansions(expansions: &[String]) -> Result<Expansions, JsError> {
+    if !expansions.len().is_multiple_of(2) {
+        return Err(JsError::new("Expansions must be key and replacement pairs"));
+    }
+    let pairs: Vec<(&str, &str)> = expansions
+        .chunks_exact(2)
+        .map(|pair| (pair[0].as_str(), pair[1].as_str()))
+        .collect();
+    Expansions::new(&pairs).map_err(|message| JsError::new(&message))
+}
+
+fn normalize_options<'a>(
+    locale: &'a Option<String>,
+    strip_ignorables: Option<bool>,
+    keep_joiners: bool,
+    fold_numbers: bool,
+    expansions: &'a Expansions,
+) -> NormalizeOptions<'a> {
+    NormalizeOptions {
+        locale: locale.as_deref(),
+        strip_ignorables,
+        keep_joiners,
+        fold_numbers,
+        expansions: Some(expansions),
+    }
+}
+
+// Normalization with every option; strip_ignorables undefined follows the preset.
+// Throws on malformed expansions
+#[wasm_bindgen]
+pub fn normalize_with_options(
+    s: &str,
+    preset: &str,
+    locale: Option<String>,
+    strip_ignorables: Option<bool>,
+    keep_joiners: bool,
+    fold_numbers: bool,
+    expansions: Vec<String>,
+) -> Result<String, JsError> {
+    let expansions = parse_expansions(&expansions)?;
+    Ok(string_metrics_core::normalize_with_options(
+        s,
+        preset,
+        &normalize_options(
+            &locale,
+            strip_ignorables,
+            keep_joiners,
+            fold_numbers,
+            &expansions,
+        ),
+    ))
+}
+
+// Whether two strings are equal after normalization, without building either normalized string
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn equals_normalized(
+    a: &str,
+    b: &str,
+    preset: &str,
+    locale: Option<String>,
+    strip_ignorables: Option<bool>,
+    keep_joiners: bool,
+    fold_numbers: bool,
+    expansions: Vec<String>,
+) -> Result<bool, JsError> {
+    let expansions = parse_expansions(&expansions)?;
+    Ok(string_metrics_core::equals_normalized(
+        a,
+        b,
+        preset,
+        &normalize_options(
+            &locale,
+            strip_ignorables,
+            keep_joiners,
+            fold_numbers,
+            &expansions,
+        ),
+    ))
+}
+
+// Stable bucketing key (the normalized string)
+#[wasm_bindgen]
+pub fn normalized_key(
+    s: &str,
+    preset: &str,
+    locale: Option<String>,
+    strip_ignorables: Option<bool>,
+    keep_joiners: bool,
+    fold_numbers: bool,
+    expansions: Vec<String>,
+) -> Result<String, JsError> {
+    let expansions = parse_expansions(&expansions)?;
+    Ok(string_metrics_core::normalized_key(
+        s,
+        preset,
+        &normalize_options(
+            &locale,
+            strip_ignorables,
+            keep_joiners,
+            fold_numbers,
+            &expansions,
+        ),
+    ))
+}
+
+// 64-bit FNV-1a of normalized_key, returned to JS as a BigInt
+#[wasm_bindgen]
+pub fn normalized_hash(
+    s: &str,
+    preset: &str,
+    locale: Option<String>,
+    strip_ignorables: Option<bool>,
+    keep_joiners: bool,
+    fold_numbers: bool,
+    expansions: Vec<String>,
+) -> Result<u64, JsError> {
+    let expansions = parse_expansions(&expansions)?;
+    Ok(string_metrics_core::normalized_hash(
+        s,
+        preset,
+        &normalize_options(
+            &locale,
+            strip_ignorables,
+            keep_joiners,
+            fold_numbers,
+            &expansions,
+        ),
+    ))
+}
+
+// Numbers rewritten without grouping separators and with a "." decimal point
+#[wasm_bindgen]
+pub fn fold_numeric_tokens(s: &str) -> String {
+    string_metrics_core::fold_numeric_tokens(s)
+}
+
+// The target in the pattern's casing (lower, upper, title, or char by char when mixed)
+#[wasm_bindgen]
+pub fn transfer_case(pattern: &str, target: &str, locale: Option<String>) -> String {
+    string_metrics_core::transfer_case(pattern, target, locale.as_deref())
+}
+
+// Whether the string contains bidi control characters (e.g. a right-to-left override)
+#[wasm_bindgen]
+pub fn contains_bidi_controls(s: &str) -> bool {
+    string_metrics_core::contains_bidi_controls(s)
+}
+
+// Version of normalized_key/normalized_hash output, to store alongside cached hashes
+#[wasm_bindgen]
+pub fn normalized_hash_version() -> u32 {
+    string_metrics_core::NORMALIZED_HASH_VERSION
+}
+
+// Hex fingerprint of normalize output for a preset and locale, to store alongside caches
+#[wasm_bindgen]
+pub fn normalization_fingerprint(preset: &str, locale: Option<String>) -> String {
+    string_metrics_core::normalization_fingerprint(preset, locale.as_deref())
+}
+
+/// Candidates with their normalized keys, cached as bytes that embed the
+/// normalization fingerprint; `from_bytes` fails with a "FINGERPRINT_MISMATCH: ..."
+/// error when this build normalizes differently
+#[wasm_bindgen]
+pub struct CandidateSet(string_metrics_core::CandidateSet);
+
+#[wasm_bindgen]
+impl CandidateSet {
+    #[wasm_bindgen(constructor)]
+    pub fn new(candidates: Vec<String>, preset: &str, locale: Option<String>) -> CandidateSet {
+        CandidateSet(string_metrics_core::CandidateSet::new(
+            &candidates,
+            preset,
+            locale.as_deref(),
+        ))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<CandidateSet, JsError> {
+        string_metrics_core::CandidateSet::from_bytes(bytes)
+            .map(CandidateSet)
+            .map_err(|message| JsError::new(&message))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    pub fn preset(&self) -> String {
+        self.0.preset().to_string()
+    }
+
+    pub fn locale(&self) -> Option<String> {
+        self.0.locale().map(str::to_string)
+    }
+
+    pub fn candidates(&self) -> Vec<String> {
+        self.0.candidates().to_vec()
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.0.keys().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> String {
+        self.0.fingerprint()
+    }
+
+    pub fn lookup(&self, query: &str) -> Vec<u32> {
+        self.0.lookup(query).into_iter().map(|i| i as u32).collect()
+    }
+}
+
+// ISO 15924 code of the most frequent script, ignoring Common and Inherited
+#[wasm_bindgen]
+pub fn dominant_script(s: &str) -> String {
+    string_metrics_core::dominant_script(s).to_string()
+}
+
+// Whether more than one script appears, ignoring Common and Inherited
+#[wasm_bindgen]
+pub fn is_mixed_script(s: &str) -> bool {
+    string_metrics_core::is_mixed_script(s)
+}
+
+// Script codes that script_counts() entries are aligned with
+#[wasm_bindgen]
+pub fn script_codes() -> Vec<String> {
+    string_metrics_core::SCRIPTS
+        .iter()
+        .map(|script| script.to_string())
+        .collect()
+}
+
+// Characters per script, aligned with script_codes()
+#[wasm_bindgen]
+pub fn script_counts(s: &str) -> Vec<u32> {
+    string_metrics_core::script_counts(s)
+        .into_iter()
+        .map(|count| count as u32)
+        .collect()
+}
+
+// Unified distance with each of `digraphs` segmented as one symbol
+#[wasm_bindgen]
+pub fn distance_with_digraphs(
+    a: &str,
+    b: &str,
+    metric: &str,
+    digraphs: Vec<String>,
+) -> Result<u32, JsError> {
+    string_metrics_core::Digraphs::new(&digraphs)
+        .and_then(|digraphs| {
+            string_metrics_core::unified_distance_with_digraphs(a, b, metric, &digraphs)
+        })
+        .map(|d| d as u32)
+        .map_err(|message| JsError::new(&message))
+}
+
+// Unified score with each of `digraphs` segmented as one symbol
+#[wasm_bindgen]
+pub fn score_with_digraphs(
+    a: &str,
+    b: &str,
+    metric: &str,
+    digraphs: Vec<String>,
+) -> Result<f64, JsError> {
+    string_metrics_core::Digraphs::new(&digraphs)
+        .and_then(|digraphs| {
+            string_metrics_core::unified_score_with_digraphs(a, b, metric, &digraphs)
+        })
+        .map(|score| checked_score(metric, score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Unified score counting the first or last `length` chars as matched, or 0.0 when they differ
+#[wasm_bindgen]
+pub fn anchored_similarity(
+    a: &str,
+    b: &str,
+    side: &str,
+    length: u32,
+    metric: &str,
+    preset: &str,
+    locale: Option<String>,
+) -> Result<f64, JsError> {
+    string_metrics_core::AnchorSide::parse(side)
+        .and_then(|side| {
+            let anchor = string_metrics_core::Anchor {
+                side,
+                length: length as usize,
+            };
+            string_metrics_core::anchored_similarity(
+                a,
+                b,
+                anchor,
+                metric,
+                preset,
+                locale.as_deref(),
+            )
+        })
+        .map(|score| checked_score(metric, score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+// ============================================================================
+// Module Defaults - Options a call falls back to when it leaves them out
+// ============================================================================
+// One set per module instance, replaced whole by set_default_options. The pair
+// and batch exports and Comparator read the preset and locale when a call
+// passes none, round the scores they return to score_precision and reject
+// inputs over max_input_length. The TypeScript wrapper reads the same set
+// through the default_* getters for the APIs it implements (unified score and
+// distance, suggest, extract, ranges), so options passed to a call always win.
+
+const PRESETS: [&str; 4] = ["none", "minimal", "default", "aggressive"];
+const LOCALES: [&str; 3] = ["tr", "az", "lt"];
+const RANGE_UNITS: [&str; 2] = ["char", "utf16"];
+
+// Accepted keys of set_default_options, each camelCase key with its snake_case spelling
+const DEFAULT_OPTION_KEYS: [(&str, &str); 5] = [
+    ("preset", "preset"),
+    ("locale", "locale"),
+    ("rangeUnit", "range_unit"),
+    ("scorePrecision", "score_precision"),
+    ("maxInputLength", "max_input_length"),
+];
+
+// Number.MAX_SAFE_INTEGER, the largest maxInputLength a JS caller can name exactly
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+#[derive(Debug, Clone)]
+struct ModuleDefaults {
+    preset: Option<String>,
+    locale: Option<String>,
+    range_unit: Option<String>,
+    score_precision: Option<u32>,
+    max_input_length: Option<f64>,
+}
+
+impl ModuleDefaults {
+    const UNSET: ModuleDefaults = ModuleDefaults {
+        preset: None,
+        locale: None,
+        range_unit: None,
+        score_precision: None,
+        max_input_length: None,
+    };
+}
+
+static MODULE_DEFAULTS: RwLock<ModuleDefaults> = RwLock::new(ModuleDefaults::UNSET);
+
+fn module_defaults() -> ModuleDefaults {
+    MODULE_DEFAULTS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+fn replace_module_defaults(defaults: ModuleDefaults) {
+    *MODULE_DEFAULTS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = defaults;
+}
+
+// How an invalid value reads in an error: strings bare, numbers as JS prints them
+fn describe_value(value: &JsValue) -> String {
+    value
+        .as_string()
+        .or_else(|| value.as_f64().map(|number| number.to_string()))
+        .unwrap_or_else(|| format!("{:?}", value))
+}
+
+fn invalid_default(name: &str, value: &JsValue, expected: &str) -> JsError {
+    JsError::new(&format!(
+        "Invalid default {}: {} (expected {})",
+        name,
+        describe_value(value),
+        expected
+    ))
+}
+
+fn default_choice(
+    name: &str,
+    value: Option<JsValue>,
+    choices: &[&str],
+) -> Result<Option<String>, JsError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.as_string() {
+        Some(choice) if choices.contains(&choice.as_str()) => Ok(Some(choice)),
+        _ => Err(invalid_default(name, &value, &choices.join(", "))),
+    }
+}
+
+fn default_integer(
+    name: &str,
+    value: Option<JsValue>,
+    min: f64,
+    max: f64,
+) -> Result<Option<f64>, JsError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.as_f64() {
+        Some(number) if number.fract() == 0.0 && (min..=max).contains(&number) => Ok(Some(number)),
+        _ => Err(invalid_default(
+            name,
+            &value,
+            &format!("an integer {}-{}", min, max),
+        )),
+    }
+}
+
+/// Set the options calls fall back to when they leave them out: `preset`, `locale`,
+/// `rangeUnit`, `scorePrecision` (integer 0-15) and `maxInputLength` (UTF-16 code units),
+/// each also accepted in snake_case. Replaces all previous defaults; throws on an unknown
+/// key or an invalid value, leaving the previous defaults in place.
+#[wasm_bindgen]
+pub fn set_default_options(options: JsValue) -> Result<(), JsError> {
+    if !options.is_object() {
+        return Err(JsError::new("Default options must be an object"));
+    }
+    let unknown: Vec<String> = js_sys::Object::keys(options.unchecked_ref::<js_sys::Object>())
+        .iter()
+        .filter_map(|key| key.as_string())
+        .filter(|key| {
+            !DEFAULT_OPTION_KEYS
+                .iter()
+                .any(|(camel, snake)| key == camel || key == snake)
+        })
+        .collect();
+    if !unknown.is_empty() {
+        return Err(JsError::new(&format!(
+            "Unknown default option: {}",
+            unknown.join(", ")
+        )));
+    }
+    // The camelCase spelling wins; null counts as left out
+    let option = |camel: &str, snake: &str| {
+        [camel, snake]
+            .into_iter()
+            .filter_map(|key| js_sys::Reflect::get(&options, &JsValue::from_str(key)).ok())
+            .find(|value| !value.is_null() && !value.is_undefined())
+    };
+    let defaults = ModuleDefaults {
+        preset: default_choice("preset", option("preset", "preset"), &PRESETS)?,
+        locale: default_choice("locale", option("locale", "locale"), &LOCALES)?,
+        range_unit: default_choice("rangeUnit", option("rangeUnit", "range_unit"), &RANGE_UNITS)?,
+        score_precision: default_integer(
+            "scorePrecision",
+            option("scorePrecision", "score_precision"),
+            0.0,
+            15.0,
+        )?
+        .map(|precision| precision as u32),
+        max_input_length: default_integer(
+            "maxInputLength",
+            option("maxInputLength", "max_input_length"),
+            1.0,
+            MAX_SAFE_INTEGER,
+        )?,
+    };
+    replace_module_defaults(defaults);
+    Ok(())
+}
+
+/// A fresh object holding the current defaults, with camelCase keys and unset ones omitted
+#[wasm_bindgen]
+pub fn get_default_options() -> JsValue {
+    let defaults = module_defaults();
+    let object = js_sys::Object::new();
+    let entries = [
+        ("preset", defaults.preset.map(JsValue::from)),
+        ("locale", defaults.locale.map(JsValue::from)),
+        ("rangeUnit", defaults.range_unit.map(JsValue::from)),
+        (
+            "scorePrecision",
+            defaults.score_precision.map(JsValue::from),
+        ),
+        (
+            "maxInputLength",
+            defaults.max_input_length.map(JsValue::from),
+        ),
+    ];
+    for (key, value) in entries {
+        if let Some(value) = value {
+            js_sys::Reflect::set(&object, &JsValue::from_str(key), &value)
+                .expect("setting a property of a plain object");
+        }
+    }
+    object.into()
+}
+
+/// Clear every default, restoring each API's own
+#[wasm_bindgen]
+pub fn reset_default_options() {
+    replace_module_defaults(ModuleDefaults::UNSET);
+}
+
+// One getter per default, for the TypeScript wrapper's per-call reads without
+// building an object each time
+
+#[wasm_bindgen]
+pub fn default_preset() -> Option<String> {
+    module_defaults().preset
+}
+
+#[wasm_bindgen]
+pub fn default_locale() -> Option<String> {
+    module_defaults().locale
+}
+
+#[wasm_bindgen]
+pub fn default_range_unit() -> Option<String> {
+    module_defaults().range_unit
+}
+
+#[wasm_bindgen]
+pub fn default_score_precision() -> Option<u32> {
+    module_defaults().score_precision
+}
+
+#[wasm_bindgen]
+pub fn default_max_input_length() -> Option<f64> {
+    module_defaults().max_input_length
+}
+
+// Scores rounded to the scorePrecision default, if one is set
+fn rounded_scores(mut scores: Vec<f64>) -> Vec<f64> {
+    if let Some(precision) = module_defaults().score_precision {
+        let factor = 10f64.powi(precision as i32);
+        for score in &mut scores {
+            *score = (*score * factor).round() / factor;
+        }
+    }
+    scores
+}
+
+fn rounded_score(score: f64) -> f64 {
+    rounded_scores(vec![score])[0]
+}
+
+// Scores checked as checked_score does, then rounded to the scorePrecision default
+fn returned_scores(metric: &str, scores: Vec<f64>) -> Vec<f64> {
+    rounded_scores(
+        scores
+            .into_iter()
+            .map(|score| checked_score(metric, score, ScoreScale::Unit))
+            .collect(),
+    )
+}
+
+// Errors when an input is longer, in UTF-16 code units, than the maxInputLength default
+fn check_input_lengths<S: AsRef<str>>(inputs: &[S]) -> Result<(), JsError> {
+    let Some(max) = module_defaults().max_input_length else {
+        return Ok(());
+    };
+    for input in inputs {
+        let length = input.as_ref().encode_utf16().count();
+        if length as f64 > max {
+            return Err(JsError::new(&format!(
+                "Input exceeds maxInputLength ({} > {})",
+                length, max
+            )));
+        }
+    }
+    Ok(())
+}
+
+// Unified score of two optional values as `[score, status]`: score is NaN when the
+// missing-value policy ("ignore", "penalize", "treat_as_empty") leaves it out, status
+// 0 = both present, 1 = one missing, 2 = both missing. A negative score_cutoff disables it.
+#[wasm_bindgen]
+pub fn score_nullable(
+    a: Option<String>,
+    b: Option<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    missing: &str,
+    score_cutoff: f64,
+) -> Result<Vec<f64>, JsError> {
+    let missing = string_metrics_core::MissingPolicy::parse(missing)
+        .map_err(|message| JsError::new(&message))?;
+    check_input_lengths(&[a.as_deref().unwrap_or(""), b.as_deref().unwrap_or("")])?;
+    let result = string_metrics_core::score_nullable(
+        a.as_deref(),
+        b.as_deref(),
+        metric,
+        &pair_options(preset, locale, score_cutoff),
+        missing,
+    )
+    .map_err(|message| JsError::new(&message))?;
+    let status = match result.status {
+        string_metrics_core::PresenceStatus::BothPresent => 0.0,
+        string_metrics_core::PresenceStatus::OneMissing => 1.0,
+        string_metrics_core::PresenceStatus::BothMissing => 2.0,
+    };
+    Ok(vec![
+        result.score.map_or(f64::NAN, |score| {
+            rounded_score(checked_score(metric, score, ScoreScale::Unit))
+        }),
+        status,
+    ])
+}
+
+// Unified score for each row of two parallel lists; a negative score_cutoff disables it
+#[wasm_bindgen]
+pub fn score_pairs(
+    a: Vec<String>,
+    b: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+) -> Result<Vec<f64>, JsError> {
+    check_input_lengths(&a)?;
+    check_input_lengths(&b)?;
+    string_metrics_core::score_pairs(&a, &b, metric, &pair_options(preset, locale, score_cutoff))
+        .map(|scores| returned_scores(metric, scores))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Unified distance for each row of two parallel lists; a negative score_cutoff disables it
+#[wasm_bindgen]
+pub fn distance_pairs(
+    a: Vec<String>,
+    b: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+) -> Result<Vec<u32>, JsError> {
+    check_input_lengths(&a)?;
+    check_input_lengths(&b)?;
+    string_metrics_core::distance_pairs(&a, &b, metric, &pair_options(preset, locale, score_cutoff))
+        .map(|distances| distances.into_iter().map(|d| d as u32).collect())
+        .map_err(|message| JsError::new(&message))
+}
+
+/// `score_pairs` that fails with a "CANCELLED: ..." error once `cancel` is cancelled
+#[wasm_bindgen]
+pub fn score_pairs_cancellable(
+    a: Vec<String>,
+    b: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<f64>, JsError> {
+    check_input_lengths(&a)?;
+    check_input_lengths(&b)?;
+    let options = pair_options(preset, locale, score_cutoff);
+    string_metrics_core::score_pairs_cancellable(&a, &b, metric, &options, &cancel.0)
+        .map(|scores| returned_scores(metric, scores))
+        .map_err(|message| JsError::new(&message))
+}
+
+/// `distance_pairs` that fails with a "CANCELLED: ..." error once `cancel` is cancelled
+#[wasm_bindgen]
+pub fn distance_pairs_cancellable(
+    a: Vec<String>,
+    b: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<u32>, JsError> {
+    check_input_lengths(&a)?;
+    check_input_lengths(&b)?;
+    let options = pair_options(preset, locale, score_cutoff);
+    string_metrics_core::distance_pairs_cancellable(&a, &b, metric, &options, &cancel.0)
+        .map(|distances| distances.into_iter().map(|d| d as u32).collect())
+        .map_err(|message| JsError::new(&message))
+}
+
+// Unified score of every query against every choice, row-major (`queries.len()` rows of
+// `choices.len()` scores); a negative score_cutoff disables it
+#[wasm_bindgen]
+pub fn cdist(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+) -> Result<Vec<f64>, JsError> {
+    cdist_cancellable(
+        queries,
+        choices,
+        metric,
+        preset,
+        locale,
+        score_cutoff,
+        &CancellationToken::new(),
+    )
+}
+
+/// `cdist` that fails with a "CANCELLED: ..." error once `cancel` is cancelled
+#[wasm_bindgen]
+pub fn cdist_cancellable(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<f64>, JsError> {
+    check_input_lengths(&queries)?;
+    check_input_lengths(&choices)?;
+    let options = pair_options(preset, locale, score_cutoff);
+    string_metrics_core::cdist_cancellable(&queries, &choices, metric, &options, &cancel.0)
+        .map(|scores| returned_scores(metric, scores))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Every pair i < j of items scoring at least min_score, flattened as [i, j, score] triples
+#[wasm_bindgen]
+pub fn similar_pairs(
+    items: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    min_score: f64,
+) -> Result<Vec<f64>, JsError> {
+    similar_pairs_cancellable(
+        items,
+        metric,
+        preset,
+        locale,
+        min_score,
+        &CancellationToken::new(),
+    )
+}
+
+/// `similar_pairs` that fails with a "CANCELLED: ..." error once `cancel` is cancelled
+#[wasm_bindgen]
+pub fn similar_pairs_cancellable(
+    items: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    min_score: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<f64>, JsError> {
+    check_input_lengths(&items)?;
+    let options = pair_options(preset, locale, -1.0);
+    let pairs = string_metrics_core::similar_pairs_cancellable(
+        &items, metric, &options, min_score, &cancel.0,
+    )
+    .map_err(|message| JsError::new(&message))?;
+    Ok(pairs
+        .into_iter()
+        .flat_map(|pair| {
+            [
+                pair.left as f64,
+                pair.right as f64,
+                rounded_score(checked_score(metric, pair.score, ScoreScale::Unit)),
+            ]
+        })
+        .collect())
+}
+
+// For each item, the index of the first item of its group of similar_pairs
+#[wasm_bindgen]
+pub fn dedupe(
+    items: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    min_score: f64,
+) -> Result<Vec<u32>, JsError> {
+    dedupe_cancellable(
+        items,
+        metric,
+        preset,
+        locale,
+        min_score,
+        &CancellationToken::new(),
+    )
+}
+
+/// `dedupe` that fails with a "CANCELLED: ..." error once `cancel` is cancelled
+#[wasm_bindgen]
+pub fn dedupe_cancellable(
+    items: Vec<String>,
+    metric: &str,
+    preset: Option<String>,
+    locale: Option<String>,
+    min_score: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<u32>, JsError> {
+    check_input_lengths(&items)?;
+    let options = pair_options(preset, locale, -1.0);
+    string_metrics_core::dedupe_cancellable(&items, metric, &options, min_score, &cancel.0)
+        .map(|groups| groups.into_iter().map(|group| group as u32).collect())
+        .map_err(|message| JsError::new(&message))
+}
+
+/// A cancel flag for the `*_cancellable` batch calls, checked every 256 rows.
+/// Cancelling is permanent, and cancelling twice does nothing more. A call blocks its
+/// thread, so JS on the same thread can only cancel between calls; the TypeScript
+/// `score_pairs_async` and `distance_pairs_async` make one call per chunk for that.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct CancellationToken(string_metrics_core::CancellationToken);
+
+#[wasm_bindgen]
+impl CancellationToken {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// Unified score and distance for one metric and preset, reusing its buffers
+/// across calls; a negative score_cutoff disables it. Not shareable across workers.
+#[wasm_bindgen]
+pub struct Comparator(string_metrics_core::Comparator);
+
+#[wasm_bindgen]
+impl Comparator {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        metric: &str,
+        preset: Option<String>,
+        locale: Option<String>,
+        score_cutoff: f64,
+    ) -> Result<Comparator, JsError> {
+        string_metrics_core::Comparator::new(metric, pair_options(preset, locale, score_cutoff))
+            .map(Comparator)
+            .map_err(|message| JsError::new(&message))
+    }
+
+    pub fn score(&mut self, a: &str, b: &str) -> Result<f64, JsError> {
+        check_input_lengths(&[a, b])?;
+        match self.0.score(a, b) {
+            Ok(score) => Ok(rounded_score(checked_score(
+                self.0.metric(),
+                score,
+                ScoreScale::Unit,
+            ))),
+            Err(message) => Err(JsError::new(&message)),
         }
-        _ => s.to_string(),
+    }
+
+    pub fn distance(&mut self, a: &str, b: &str) -> Result<u32, JsError> {
+        check_input_lengths(&[a, b])?;
+        self.0
+            .distance(a, b)
+            .map(|distance| distance as u32)
+            .map_err(|message| JsError::new(&message))
+    }
+}
+
+// A call's preset and locale, falling back to the module defaults and then to no normalization
+fn pair_options(
+    preset: Option<String>,
+    locale: Option<String>,
+    score_cutoff: f64,
+) -> string_metrics_core::PairOptions {
+    let defaults = module_defaults();
+    string_metrics_core::PairOptions {
+        preset: preset
+            .or(defaults.preset)
+            .unwrap_or_else(|| "none".to_string()),
+        locale: locale.or(defaults.locale),
+        score_cutoff: (score_cutoff >= 0.0).then_some(score_cutoff),
     }
 }
 
@@ -159,7 +1274,11 @@ pub fn normalize_with_locale(s: &str, preset: &str, locale: Option<String>) -> S
 #[wasm_bindgen]
 pub fn ratio(a: &str, b: &str) -> f64 {
     // rapidfuzz::fuzz::ratio returns 0-1, scale to 0-100 for Python compatibility
-    rapidfuzz::fuzz::ratio(a.chars(), b.chars()) * 100.0
+    checked_score(
+        "ratio",
+        rapidfuzz::fuzz::ratio(a.chars(), b.chars()) * 100.0,
+        ScoreScale::Percent,
+    )
 }
 
 // ============================================================================
@@ -176,7 +1295,11 @@ pub fn indel_distance(a: &str, b: &str) -> usize {
 /// Normalized Indel similarity (0.0-1.0)
 #[wasm_bindgen]
 pub fn indel_normalized_similarity(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::indel::normalized_similarity(a.chars(), b.chars())
+    checked_score(
+        "indel_normalized_similarity",
+        rapidfuzz::distance::indel::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
 }
 
 /// Longest Common Subsequence (LCS) distance
@@ -195,5 +1318,461 @@ pub fn lcs_seq_similarity(a: &str, b: &str) -> usize {
 /// Normalized LCS similarity (0.0-1.0)
 #[wasm_bindgen]
 pub fn lcs_seq_normalized_similarity(a: &str, b: &str) -> f64 {
-    rapidfuzz::distance::lcs_seq::normalized_similarity(a.chars(), b.chars())
+    checked_score(
+        "lcs_seq_normalized_similarity",
+        rapidfuzz::distance::lcs_seq::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
+
+/// Chars outside the common prefix of the longer string
+#[wasm_bindgen]
+pub fn prefix_distance(a: &str, b: &str) -> usize {
+    rapidfuzz::distance::prefix::distance(a.chars(), b.chars())
+}
+
+/// Common prefix length over the longer length (0.0-1.0)
+#[wasm_bindgen]
+pub fn prefix_normalized_similarity(a: &str, b: &str) -> f64 {
+    checked_score(
+        "prefix_normalized_similarity",
+        rapidfuzz::distance::prefix::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
+
+/// Chars outside the common suffix of the longer string
+#[wasm_bindgen]
+pub fn postfix_distance(a: &str, b: &str) -> usize {
+    rapidfuzz::distance::postfix::distance(a.chars(), b.chars())
+}
+
+/// Common suffix length over the longer length (0.0-1.0)
+#[wasm_bindgen]
+pub fn postfix_normalized_similarity(a: &str, b: &str) -> f64 {
+    checked_score(
+        "postfix_normalized_similarity",
+        rapidfuzz::distance::postfix::normalized_similarity(a.chars(), b.chars()),
+        ScoreScale::Unit,
+    )
+}
+
+/// Sørensen-Dice similarity over char bigrams
+#[wasm_bindgen]
+pub fn dice(a: &str, b: &str) -> f64 {
+    checked_score("dice", string_metrics_core::dice(a, b), ScoreScale::Unit)
+}
+
+/// Jaccard similarity over char n-grams; n must be at least 1
+#[wasm_bindgen]
+pub fn jaccard_ngram(a: &str, b: &str, n: usize) -> Result<f64, JsError> {
+    string_metrics_core::jaccard_ngram(a, b, n)
+        .map(|score| checked_score("jaccard_ngram", score, ScoreScale::Unit))
+        .map_err(|message| JsError::new(&message))
+}
+
+// Tag codes as in word_diff (0 = equal, 1 = replace, 2 = insert, 3 = delete), plus 4 = transpose
+fn edit_tag_code(tag: string_metrics_core::EditTag) -> u32 {
+    match tag {
+        string_metrics_core::EditTag::Equal => 0,
+        string_metrics_core::EditTag::Replace => 1,
+        string_metrics_core::EditTag::Insert => 2,
+        string_metrics_core::EditTag::Delete => 3,
+        string_metrics_core::EditTag::Transpose => 4,
+    }
+}
+
+/// Levenshtein edit operations as flattened triples `[tag, src_pos, dest_pos, ...]`
+/// over chars (tag 1 = replace, 2 = insert, 3 = delete)
+#[wasm_bindgen]
+pub fn levenshtein_editops(a: &str, b: &str) -> Vec<u32> {
+    string_metrics_core::levenshtein_editops(a, b)
+        .into_iter()
+        .flat_map(|step| {
+            [
+                edit_tag_code(step.tag),
+                step.src_pos as u32,
+                step.dest_pos as u32,
+            ]
+        })
+        .collect()
+}
+
+/// Levenshtein opcodes as flattened `[tag, src_start, src_end, dest_start, dest_end, ...]`
+/// over chars (tag 0 = equal, 1 = replace, 2 = insert, 3 = delete)
+#[wasm_bindgen]
+pub fn levenshtein_opcodes(a: &str, b: &str) -> Vec<u32> {
+    string_metrics_core::levenshtein_opcodes(a, b)
+        .into_iter()
+        .flat_map(|op| {
+            [
+                edit_tag_code(op.tag),
+                op.src_start as u32,
+                op.src_end as u32,
+                op.dest_start as u32,
+                op.dest_end as u32,
+            ]
+        })
+        .collect()
+}
+
+fn range_unit(utf16: bool) -> string_metrics_core::RangeUnit {
+    if utf16 {
+        string_metrics_core::RangeUnit::Utf16
+    } else {
+        string_metrics_core::RangeUnit::Char
+    }
+}
+
+/// One longest common subsequence as flattened index pairs `[i0, j0, i1, j1, ...]`
+/// (leftmost-greedy in b); indices count UTF-16 code units when `utf16` is set, else chars
+#[wasm_bindgen]
+pub fn lcs_seq_matching_indices(a: &str, b: &str, utf16: bool) -> Vec<u32> {
+    string_metrics_core::lcs_seq_matching_indices(a, b, range_unit(utf16))
+        .into_iter()
+        .flat_map(|(i, j)| [i as u32, j as u32])
+        .collect()
+}
+
+/// Indel edit operations as flattened triples `[tag, src_pos, dest_pos, ...]`
+/// (tag 0 = delete, 1 = insert), derived from `lcs_seq_matching_indices`
+#[wasm_bindgen]
+pub fn indel_editops(a: &str, b: &str, utf16: bool) -> Vec<u32> {
+    string_metrics_core::indel_editops(a, b, range_unit(utf16))
+        .into_iter()
+        .flat_map(|step| {
+            let tag = u32::from(step.tag == string_metrics_core::EditTag::Insert);
+            [tag, step.src_pos as u32, step.dest_pos as u32]
+        })
+        .collect()
+}
+
+/// Char positions where `a` and `b` differ, ascending; their count is the Hamming distance.
+/// With `pad`, the shorter input is padded at its end and every padded position differs.
+#[wasm_bindgen]
+pub fn positional_diff(a: &str, b: &str, pad: bool) -> Result<Vec<u32>, JsError> {
+    let diff = string_metrics_core::positional_diff(a, b, pad)
+        .map_err(|message| JsError::new(&message))?;
+    Ok(diff
+        .differing_positions
+        .into_iter()
+        .map(|position| position as u32)
+        .collect())
+}
+
+/// One class per candidate char (0 = match, 1 = fold_match, 2 = substituted, 3 = inserted)
+/// from the Levenshtein alignment of both inputs normalized with `preset`
+#[wasm_bindgen]
+pub fn match_profile(
+    query: &str,
+    candidate: &str,
+    preset: &str,
+    locale: Option<String>,
+) -> Result<Vec<u8>, JsError> {
+    let classes = string_metrics_core::match_profile(query, candidate, preset, locale.as_deref())
+        .map_err(|message| JsError::new(&message))?;
+    Ok(classes
+        .into_iter()
+        .map(|class| match class {
+            string_metrics_core::MatchClass::Match => 0,
+            string_metrics_core::MatchClass::FoldMatch => 1,
+            string_metrics_core::MatchClass::Substituted => 2,
+            string_metrics_core::MatchClass::Inserted => 3,
+        })
+        .collect())
+}
+
+/// Every haystack range the longest common substring occupies, sorted by start and
+/// flattened as `[start0, end0, start1, end1, ...]`
+#[wasm_bindgen]
+pub fn substring_all_ranges(needle: &str, haystack: &str, utf16: bool) -> Vec<u32> {
+    string_metrics_core::substring_all_ranges(needle, haystack, range_unit(utf16))
+        .1
+        .into_iter()
+        .flat_map(|range| [range.start as u32, range.end as u32])
+        .collect()
+}
+
+/// Share of the needle found as one contiguous run in the haystack (0.0-1.0);
+/// the haystack's length doesn't count against it
+#[wasm_bindgen]
+pub fn coverage_score(needle: &str, haystack: &str) -> f64 {
+    let options = string_metrics_core::SubstringOptions::default();
+    checked_score(
+        "coverage_score",
+        string_metrics_core::coverage_score(needle, haystack, options).0,
+        ScoreScale::Unit,
+    )
+}
+
+/// Share of the needle found in order in the haystack, gaps allowed (0.0-1.0)
+#[wasm_bindgen]
+pub fn subsequence_coverage_score(needle: &str, haystack: &str) -> f64 {
+    checked_score(
+        "subsequence_coverage_score",
+        string_metrics_core::subsequence_coverage_score(
+            needle,
+            haystack,
+            string_metrics_core::RangeUnit::Char,
+        )
+        .0,
+        ScoreScale::Unit,
+    )
+}
+
+/// Haystack ranges behind `coverage_score` (at most one) or, with `gaps`,
+/// `subsequence_coverage_score`, flattened as `[start0, end0, start1, end1, ...]`;
+/// tie_policy ("first" or "last") picks among equally long contiguous matches
+#[wasm_bindgen]
+pub fn coverage_ranges(
+    needle: &str,
+    haystack: &str,
+    gaps: bool,
+    utf16: bool,
+    tie_policy: Option<String>,
+) -> Result<Vec<u32>, JsError> {
+    let tie_policy = match tie_policy.as_deref() {
+        None => None,
+        Some("first") => Some(string_metrics_core::TiePolicy::First),
+        Some("last") => Some(string_metrics_core::TiePolicy::Last),
+        Some(other) => return Err(JsError::new(&format!("Unknown tie policy: {}", other))),
+    };
+    let ranges = if gaps {
+        string_metrics_core::subsequence_coverage_score(needle, haystack, range_unit(utf16)).1
+    } else {
+        let options = string_metrics_core::SubstringOptions {
+            range_unit: range_unit(utf16),
+            tie_policy,
+        };
+        string_metrics_core::coverage_score(needle, haystack, options)
+            .1
+            .into_iter()
+            .collect()
+    };
+    Ok(ranges
+        .into_iter()
+        .flat_map(|range| [range.start as u32, range.end as u32])
+        .collect())
+}
+
+/// Excerpt of `s` around `start..end` cut at grapheme cluster boundaries, flattened as
+/// `[text, start, end]` with the match's range in the excerpt; offsets count UTF-16 code
+/// units when `utf16` is set, else chars
+#[wasm_bindgen]
+pub fn excerpt_around_range(
+    s: &str,
+    start: u32,
+    end: u32,
+    context_chars: u32,
+    ellipsis: &str,
+    utf16: bool,
+) -> Result<Vec<String>, JsError> {
+    let range = string_metrics_core::MatchRange {
+        start: start as usize,
+        end: end as usize,
+    };
+    let excerpt = string_metrics_core::excerpt_around_range(
+        s,
+        range,
+        context_chars as usize,
+        ellipsis,
+        range_unit(utf16),
+    )
+    .map_err(|message| JsError::new(&message))?;
+    Ok(vec![
+        excerpt.text,
+        excerpt.range.start.to_string(),
+        excerpt.range.end.to_string(),
+    ])
+}
+
+/// Unified score of the needle against each `window`-char haystack slice, one every `step`
+/// chars; window defaults to the needle's length and step to a quarter window
+#[wasm_bindgen]
+pub fn window_scores(
+    needle: &str,
+    haystack: &str,
+    window: Option<u32>,
+    step: Option<u32>,
+    metric: &str,
+) -> Result<Vec<f64>, JsError> {
+    string_metrics_core::window_scores(
+        needle,
+        haystack,
+        window.map(|window| window as usize),
+        step.map(|step| step as usize),
+        metric,
+    )
+    .map(|scores| {
+        scores
+            .into_iter()
+            .map(|score| checked_score(metric, score, ScoreScale::Unit))
+            .collect()
+    })
+    .map_err(|message| JsError::new(&message))
+}
+
+/// Tokens of `s` flattened as `[text0, whitespace0, text1, whitespace1, ...]`
+/// (tokenizer "whitespace" or "words")
+#[wasm_bindgen]
+pub fn word_tokens(s: &str, tokenizer: &str) -> Result<Vec<String>, JsError> {
+    let tokenizer = string_metrics_core::Tokenizer::parse(tokenizer)
+        .map_err(|message| JsError::new(&message))?;
+    Ok(string_metrics_core::word_tokens(s, tokenizer)
+        .into_iter()
+        .flat_map(|token| [token.text.to_string(), token.whitespace.to_string()])
+        .collect())
+}
+
+/// Segmentation of `input` over `words` (weighted by `frequencies`, or 1 each when empty)
+/// flattened as `[score, word0, word1, ...]`, the score in shortest round-trip form
+#[wasm_bindgen]
+pub fn segment_words(
+    input: &str,
+    words: Vec<String>,
+    frequencies: Vec<f64>,
+    unknown_char_penalty: f64,
+) -> Result<Vec<String>, JsError> {
+    let vocabulary = if frequencies.is_empty() {
+        string_metrics_core::Vocabulary::new(&words)
+    } else if frequencies.len() == words.len() {
+        let entries: Vec<(String, f64)> = words.into_iter().zip(frequencies).collect();
+        string_metrics_core::Vocabulary::with_frequencies(&entries)
+            .map_err(|message| JsError::new(&message))?
+    } else {
+        return Err(JsError::new("Expected one frequency per word"));
+    };
+    if !unknown_char_penalty.is_finite() || unknown_char_penalty < 0.0 {
+        return Err(JsError::new(&format!(
+            "unknownCharPenalty must be finite and non-negative, got {}",
+            unknown_char_penalty
+        )));
+    }
+    let options = string_metrics_core::SegmentOptions {
+        unknown_char_penalty,
+    };
+    let segmentation = string_metrics_core::segment_words(input, &vocabulary, &options);
+    Ok(std::iter::once(segmentation.score.to_string())
+        .chain(segmentation.words)
+        .collect())
+}
+
+/// The normalized query split into words of the normalized candidates, as `suggest` does
+/// with `segmentInput`; undefined when segmentation leaves it unchanged
+#[wasm_bindgen]
+pub fn segmented_query(
+    normalized_query: &str,
+    normalized_candidates: Vec<String>,
+) -> Option<String> {
+    string_metrics_core::segmented_query(normalized_query, &normalized_candidates)
+}
+
+/// Word diff ops flattened as `[tag, a_start, a_end, b_start, b_end, score, ...]` over
+/// `word_tokens` indices (tag 0 = equal, 1 = replace, 2 = insert, 3 = delete; score NaN
+/// except on replacements)
+#[wasm_bindgen]
+pub fn word_diff(
+    a: &str,
+    b: &str,
+    tokenizer: &str,
+    metric: &str,
+    threshold: f64,
+) -> Result<Vec<f64>, JsError> {
+    let options = string_metrics_core::WordDiffOptions {
+        tokenizer: string_metrics_core::Tokenizer::parse(tokenizer)
+            .map_err(|message| JsError::new(&message))?,
+        metric: metric.to_string(),
+        threshold,
+    };
+    let ops =
+        string_metrics_core::word_diff(a, b, &options).map_err(|message| JsError::new(&message))?;
+    Ok(ops
+        .into_iter()
+        .flat_map(|op| {
+            let tag = match op.tag {
+                string_metrics_core::EditTag::Equal => 0.0,
+                string_metrics_core::EditTag::Replace => 1.0,
+                string_metrics_core::EditTag::Insert => 2.0,
+                _ => 3.0,
+            };
+            [
+                tag,
+                op.a_start as f64,
+                op.a_end as f64,
+                op.b_start as f64,
+                op.b_end as f64,
+                op.score.map_or(f64::NAN, |score| {
+                    checked_score(metric, score, ScoreScale::Unit)
+                }),
+            ]
+        })
+        .collect())
+}
+
+fn flatten_matches(matches: Vec<string_metrics_core::FuzzyMatch>) -> Vec<u32> {
+    matches
+        .into_iter()
+        .flat_map(|found| [found.start as u32, found.end as u32, found.edits as u32])
+        .collect()
+}
+
+/// Fuzzy occurrences of `needle` within `max_edits` as flattened triples
+/// `[start, end, edits, ...]` (char offsets); throws unless max_edits < needle length
+#[wasm_bindgen]
+pub fn fuzzy_find_all(haystack: &str, needle: &str, max_edits: usize) -> Result<Vec<u32>, JsError> {
+    string_metrics_core::fuzzy_find_all(haystack, needle, max_edits)
+        .map(flatten_matches)
+        .map_err(|message| JsError::new(&message))
+}
+
+/// `fuzzy_find_all` over a haystack fed in chunks; `feed` and `finish` return
+/// the matches they complete as flattened triples with stream-wide offsets
+#[wasm_bindgen]
+pub struct StreamMatcher(string_metrics_core::StreamMatcher);
+
+#[wasm_bindgen]
+impl StreamMatcher {
+    #[wasm_bindgen(constructor)]
+    pub fn new(needle: &str, max_edits: usize) -> Result<StreamMatcher, JsError> {
+        string_metrics_core::StreamMatcher::new(needle, max_edits)
+            .map(StreamMatcher)
+            .map_err(|message| JsError::new(&message))
+    }
+
+    pub fn feed(&mut self, chunk: &str) -> Vec<u32> {
+        flatten_matches(self.0.feed(chunk))
+    }
+
+    pub fn finish(&mut self) -> Vec<u32> {
+        flatten_matches(self.0.finish())
+    }
+}
+
+/// Up to `count` distinct strings exactly `distance` Levenshtein edits from `s`, drawn from a
+/// SplitMix64 stream seeded with `seed`; `edits` names the allowed edits (empty means all)
+#[wasm_bindgen]
+pub fn generate_variants(
+    s: &str,
+    distance: usize,
+    count: usize,
+    seed: u64,
+    edits: Vec<String>,
+    extra_chars: &str,
+) -> Result<Vec<String>, JsError> {
+    let edits = if edits.is_empty() {
+        string_metrics_core::VariantEdit::ALL.to_vec()
+    } else {
+        edits
+            .iter()
+            .map(|name| string_metrics_core::VariantEdit::parse(name))
+            .collect::<Result<_, _>>()
+            .map_err(|message| JsError::new(&message))?
+    };
+    let options = string_metrics_core::VariantOptions {
+        seed,
+        edits,
+        extra_chars: extra_chars.to_string(),
+    };
+    string_metrics_core::generate_variants(s, distance, count, &options)
+        .map_err(|message| JsError::new(&message))
 }
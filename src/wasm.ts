@@ -1,15 +1,225 @@
 type WasmBindings = {
+  // Throws when the JS string holds an unpaired surrogate
+  check_well_formed(input: string): void;
   levenshtein(a: string, b: string): number;
   normalized_levenshtein(a: string, b: string): number;
+  levenshtein_bytes(a: Uint8Array, b: Uint8Array): number;
+  normalized_levenshtein_bytes(a: Uint8Array, b: Uint8Array): number;
+  hamming(a: string, b: string): number;
+  hamming_with_padding(a: string, b: string, pad: boolean): number;
+  normalized_hamming_similarity(a: string, b: string): number;
+  normalized_hamming_similarity_with_padding(a: string, b: string, pad: boolean): number;
+  hamming_bytes(a: Uint8Array, b: Uint8Array, pad: boolean): number;
+  normalized_hamming_bytes(a: Uint8Array, b: Uint8Array, pad: boolean): number;
   osa_distance(a: string, b: string): number;
   normalized_osa_similarity(a: string, b: string): number;
   damerau_levenshtein(a: string, b: string): number;
   normalized_damerau_levenshtein(a: string, b: string): number;
+  damerau_levenshtein_graphemes(a: string, b: string): number;
+  normalized_damerau_levenshtein_graphemes(a: string, b: string): number;
+  damerau_levenshtein_weighted(a: string, b: string, transposition_cost: number): number;
+  normalized_damerau_levenshtein_weighted(a: string, b: string, transposition_cost: number): number;
+  mfc_similarity(a: string, b: string, k: number): number;
+  mfc_signature(input: string, k: number): string;
+  mfc_signature_similarity(a: string, b: string): number;
+  affine_gap_distance(
+    a: string,
+    b: string,
+    mismatch: number,
+    gap_open: number,
+    gap_extend: number,
+  ): number;
+  normalized_affine_gap_similarity(
+    a: string,
+    b: string,
+    mismatch: number,
+    gap_open: number,
+    gap_extend: number,
+  ): number;
   jaro(a: string, b: string): number;
   jaro_winkler(a: string, b: string): number;
   jaro_winkler_with_params(a: string, b: string, prefix_scale: number, max_prefix: number): number;
+  jaro_tokens(a: string, b: string, match_threshold: number, tokenizer: string): number;
+  jaro_winkler_tokens(
+    a: string,
+    b: string,
+    prefix_scale: number,
+    match_threshold: number,
+    tokenizer: string,
+  ): number;
+  weighted_jaccard(
+    a_tokens: string[],
+    a_weights: Float64Array,
+    b_tokens: string[],
+    b_weights: Float64Array,
+    preset: string,
+    locale: string | undefined,
+  ): number;
   normalize(input: string, preset: string): string;
   normalize_with_locale(input: string, preset: string, locale: string | undefined): string;
+  needs_case_fold(input: string): boolean;
+  normalize_with_options(
+    input: string,
+    preset: string,
+    locale: string | undefined,
+    strip_ignorables: boolean | undefined,
+    keep_joiners: boolean,
+    fold_numbers: boolean,
+    expansions: string[],
+  ): string;
+  equals_normalized(
+    a: string,
+    b: string,
+    preset: string,
+    locale: string | undefined,
+    strip_ignorables: boolean | undefined,
+    keep_joiners: boolean,
+    fold_numbers: boolean,
+    expansions: string[],
+  ): boolean;
+  normalized_key(
+    input: string,
+    preset: string,
+    locale: string | undefined,
+    strip_ignorables: boolean | undefined,
+    keep_joiners: boolean,
+    fold_numbers: boolean,
+    expansions: string[],
+  ): string;
+  normalized_hash(
+    input: string,
+    preset: string,
+    locale: string | undefined,
+    strip_ignorables: boolean | undefined,
+    keep_joiners: boolean,
+    fold_numbers: boolean,
+    expansions: string[],
+  ): bigint;
+  // Module defaults: throw on an unknown key or invalid value
+  set_default_options(options: unknown): void;
+  get_default_options(): unknown;
+  reset_default_options(): void;
+  default_preset(): string | undefined;
+  default_locale(): string | undefined;
+  default_range_unit(): string | undefined;
+  default_score_precision(): number | undefined;
+  default_max_input_length(): number | undefined;
+  normalized_hash_version(): number;
+  normalization_fingerprint(preset: string, locale: string | undefined): string;
+  dominant_script(input: string): string;
+  is_mixed_script(input: string): boolean;
+  contains_bidi_controls(input: string): boolean;
+  fold_numeric_tokens(input: string): string;
+  transfer_case(pattern: string, target: string, locale: string | undefined): string;
+  script_codes(): string[];
+  script_counts(input: string): Uint32Array;
+  score_pairs(
+    a: string[],
+    b: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+  ): Float64Array;
+  distance_with_digraphs(a: string, b: string, metric: string, digraphs: string[]): number;
+  // Score with the first or last `length` chars required to match exactly
+  anchored_similarity(
+    a: string,
+    b: string,
+    side: string,
+    length: number,
+    metric: string,
+    preset: string,
+    locale: string | undefined,
+  ): number;
+  score_with_digraphs(a: string, b: string, metric: string, digraphs: string[]): number;
+  // [score (NaN when left out), status (0 both present, 1 one missing, 2 both missing)]
+  score_nullable(
+    a: string | undefined,
+    b: string | undefined,
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    missing: string,
+    score_cutoff: number,
+  ): Float64Array;
+  distance_pairs(
+    a: string[],
+    b: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+  ): Uint32Array;
+  // Throw "CANCELLED: cancelled after <completed> of <total> rows" once cancel is cancelled
+  score_pairs_cancellable(
+    a: string[],
+    b: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+    cancel: WasmCancellationToken,
+  ): Float64Array;
+  distance_pairs_cancellable(
+    a: string[],
+    b: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+    cancel: WasmCancellationToken,
+  ): Uint32Array;
+  // Row-major: queries.length rows of choices.length scores
+  cdist(
+    queries: string[],
+    choices: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+  ): Float64Array;
+  cdist_cancellable(
+    queries: string[],
+    choices: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+    cancel: WasmCancellationToken,
+  ): Float64Array;
+  // Flattened [left, right, score] triples
+  similar_pairs(
+    items: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    min_score: number,
+  ): Float64Array;
+  similar_pairs_cancellable(
+    items: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    min_score: number,
+    cancel: WasmCancellationToken,
+  ): Float64Array;
+  dedupe(
+    items: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    min_score: number,
+  ): Uint32Array;
+  dedupe_cancellable(
+    items: string[],
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    min_score: number,
+    cancel: WasmCancellationToken,
+  ): Uint32Array;
+  CancellationToken: new () => WasmCancellationToken;
   // RapidFuzz fuzz module
   ratio(a: string, b: string): number;
   // RapidFuzz distance - Indel
@@ -19,6 +229,124 @@ type WasmBindings = {
   lcs_seq_distance(a: string, b: string): number;
   lcs_seq_similarity(a: string, b: string): number;
   lcs_seq_normalized_similarity(a: string, b: string): number;
+  // RapidFuzz distance - Prefix and Postfix
+  prefix_distance(a: string, b: string): number;
+  prefix_normalized_similarity(a: string, b: string): number;
+  postfix_distance(a: string, b: string): number;
+  postfix_normalized_similarity(a: string, b: string): number;
+  // N-gram set metrics
+  dice(a: string, b: string): number;
+  jaccard_ngram(a: string, b: string, n: number): number;
+  // Flattened [i, j] pairs and [tag, src_pos, dest_pos] triples
+  lcs_seq_matching_indices(a: string, b: string, utf16: boolean): Uint32Array;
+  indel_editops(a: string, b: string, utf16: boolean): Uint32Array;
+  levenshtein_editops(a: string, b: string): Uint32Array;
+  // Flattened [tag, src_start, src_end, dest_start, dest_end] opcodes
+  levenshtein_opcodes(a: string, b: string): Uint32Array;
+  // Flattened [text, whitespace] pairs and [tag, a_start, a_end, b_start, b_end, score] ops
+  word_tokens(input: string, tokenizer: string): string[];
+  segment_words(
+    input: string,
+    words: string[],
+    frequencies: Float64Array,
+    unknown_char_penalty: number,
+  ): string[];
+  segmented_query(normalized_query: string, normalized_candidates: string[]): string | undefined;
+  word_diff(
+    a: string,
+    b: string,
+    tokenizer: string,
+    metric: string,
+    threshold: number,
+  ): Float64Array;
+  // Char positions where equal-length strings differ (Hamming)
+  positional_diff(a: string, b: string, pad: boolean): Uint32Array;
+  // Class code per candidate char: 0 match, 1 fold_match, 2 substituted, 3 inserted
+  match_profile(
+    query: string,
+    candidate: string,
+    preset: string,
+    locale: string | undefined,
+  ): Uint8Array;
+  // Flattened [start, end] ranges of every longest common substring
+  substring_all_ranges(needle: string, haystack: string, utf16: boolean): Uint32Array;
+  // Coverage of a needle by a haystack, and its flattened [start, end] ranges
+  coverage_score(needle: string, haystack: string): number;
+  subsequence_coverage_score(needle: string, haystack: string): number;
+  coverage_ranges(
+    needle: string,
+    haystack: string,
+    gaps: boolean,
+    utf16: boolean,
+    tie_policy?: string,
+  ): Uint32Array;
+  // Flattened [text, start, end] excerpt around a range
+  excerpt_around_range(
+    s: string,
+    start: number,
+    end: number,
+    context_chars: number,
+    ellipsis: string,
+    utf16: boolean,
+  ): string[];
+  // Score of the needle against each haystack window
+  window_scores(
+    needle: string,
+    haystack: string,
+    window: number | undefined,
+    step: number | undefined,
+    metric: string,
+  ): Float64Array;
+  // Flattened [start, end, edits] triples
+  fuzzy_find_all(haystack: string, needle: string, max_edits: number): Uint32Array;
+  StreamMatcher: new (needle: string, max_edits: number) => WasmStreamMatcher;
+  CandidateSet: {
+    new (candidates: string[], preset: string, locale: string | undefined): WasmCandidateSet;
+    from_bytes(bytes: Uint8Array): WasmCandidateSet;
+  };
+  generate_variants(
+    s: string,
+    distance: number,
+    count: number,
+    seed: bigint,
+    edits: string[],
+    extra_chars: string,
+  ): string[];
+  Comparator: new (
+    metric: string,
+    preset: string | undefined,
+    locale: string | undefined,
+    score_cutoff: number,
+  ) => WasmComparator;
+};
+
+type WasmStreamMatcher = {
+  feed(chunk: string): Uint32Array;
+  finish(): Uint32Array;
+  free(): void;
+};
+
+type WasmCandidateSet = {
+  to_bytes(): Uint8Array;
+  preset(): string;
+  locale(): string | undefined;
+  candidates(): string[];
+  keys(): string[];
+  fingerprint(): string;
+  lookup(query: string): Uint32Array;
+  free(): void;
+};
+
+type WasmCancellationToken = {
+  cancel(): void;
+  readonly cancelled: boolean;
+  free(): void;
+};
+
+type WasmComparator = {
+  score(a: string, b: string): number;
+  distance(a: string, b: string): number;
+  free(): void;
 };
 
 const loadWasm = async (): Promise<WasmBindings> => {
@@ -0,0 +1,5635 @@
+//! Logic shared by the WASM bindings (`string-metrics-wasm`) and `similarity-validator`.
+//!
+//! Anything with behavior of its own (normalization, script detection, custom
+//! Jaro-Winkler prefix handling, Jaro over tokens, Hamming and n-gram metrics,
+//! most-frequent-k-characters similarity, Damerau-Levenshtein variants,
+//! affine-gap distance, digraph segmentation, edit operations, match profiles, LCS alignment,
+//! substring similarity, streaming fuzzy search, suggestion scoring, the unified
+//! metric dispatch and registry, extraction) lives here once, so the fixture oracle and the shipped
+//! library can't drift apart. Plain rapidfuzz calls stay at the call sites.
+//!
+//! Empty inputs follow one policy everywhere: two empty strings are identical
+//! (similarity 1.0, or 100 on the percent scale; distance 0) and an empty string
+//! shares nothing with a non-empty one (similarity 0.0; distance the other's
+//! length). Emptiness is judged after the active normalization, so a
+//! whitespace-only input is empty under a trimming preset and ordinary text
+//! under `none`; metrics over whitespace tokens see no tokens in it.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+use unicode_categories::UnicodeCategories;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+// ============================================================================
+// NORMALIZATION
+// ============================================================================
+
+/// Case folding with optional locale support
+pub fn case_fold_with_locale(s: &str, locale: Option<&str>) -> String {
+    case_folded(s, locale).into_owned()
+}
+
+/// Whether case folding changes `s` under some locale. False for text with no cased
+/// chars (CJK, Thai, digits, punctuation) and for text that is already folded, so
+/// callers can skip folding it. The Turkic pairs only remap chars that fold anyway,
+/// so the answer does not depend on the locale.
+pub fn needs_case_fold(s: &str) -> bool {
+    if s.is_ascii() {
+        return s.bytes().any(|b| b.is_ascii_uppercase());
+    }
+    s.chars().any(|c| {
+        let mut lower = c.to_lowercase();
+        c == 'ß' || lower.next() != Some(c) || lower.next().is_some()
+    })
+}
+
+// Case folding that borrows `s` when nothing in it folds
+fn case_folded<'s>(s: &'s str, locale: Option<&str>) -> Cow<'s, str> {
+    if needs_case_fold(s) {
+        Cow::Owned(fold_chars(s, locale).collect())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+// Turkish and Azerbaijani case pairs (upper, lower): dotted İ ↔ i and dotless I ↔ ı
+const TURKIC_CASE_PAIRS: [(char, char); 2] = [('İ', 'i'), ('I', 'ı')];
+
+fn is_turkic(locale: Option<&str>) -> bool {
+    matches!(locale, Some("tr") | Some("az"))
+}
+
+fn turkic_lowercase(c: char) -> Option<char> {
+    TURKIC_CASE_PAIRS
+        .iter()
+        .find(|&&(upper, _)| upper == c)
+        .map(|&(_, lower)| lower)
+}
+
+fn turkic_uppercase(c: char) -> Option<char> {
+    TURKIC_CASE_PAIRS
+        .iter()
+        .find(|&&(_, lower)| lower == c)
+        .map(|&(upper, _)| upper)
+}
+
+// Case folding one char at a time, so comparisons can stream instead of allocating.
+// Turkish and Azerbaijani use TURKIC_CASE_PAIRS; every other locale uses the
+// Unicode default İ → i + combining dot. ß folds to "ss" everywhere.
+fn fold_chars<'a>(s: &'a str, locale: Option<&str>) -> impl Iterator<Item = char> + 'a {
+    let turkic = is_turkic(locale);
+    s.chars().flat_map(move |c| {
+        // to_lowercase yields at most three chars
+        let mut folded = ['\0'; 3];
+        let turkic_folded = if turkic { turkic_lowercase(c) } else { None };
+        let len = match (c, turkic_folded) {
+            (_, Some(lower)) => {
+                folded[0] = lower;
+                1
+            }
+            ('İ', None) => {
+                folded[..2].copy_from_slice(&['i', '\u{0307}']);
+                2
+            }
+            ('ß', None) => {
+                folded[..2].copy_from_slice(&['s', 's']);
+                2
+            }
+            _ => {
+                let mut len = 0;
+                for lower in c.to_lowercase() {
+                    folded[len] = lower;
+                    len += 1;
+                }
+                len
+            }
+        };
+        folded.into_iter().take(len)
+    })
+}
+
+/// Normalization settings beyond the preset
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeOptions<'a> {
+    /// `tr` and `az` fold case the Turkic way; anything else uses the Unicode default
+    pub locale: Option<&'a str>,
+    /// Remove bidi controls and the other default-ignorable code points. `None`
+    /// follows the preset: on for "default" and "aggressive", off for "minimal";
+    /// "none" never changes the input
+    pub strip_ignorables: Option<bool>,
+    /// Keep ZWNJ (U+200C) and ZWJ (U+200D) while stripping: they change the
+    /// spelling of Persian and Indic words and hold emoji sequences together
+    pub keep_joiners: bool,
+    /// Apply `fold_numeric_tokens` before the preset, so "1,000.50" and
+    /// "1 000,50" compare equal; "none" still never changes the input
+    pub fold_numbers: bool,
+    /// Abbreviations rewritten after the preset (see `Expansions`); "none"
+    /// still never changes the input
+    pub expansions: Option<&'a Expansions>,
+}
+
+impl<'a> NormalizeOptions<'a> {
+    /// Preset defaults with a locale
+    pub fn with_locale(locale: Option<&'a str>) -> Self {
+        NormalizeOptions {
+            locale,
+            ..NormalizeOptions::default()
+        }
+    }
+
+    // Whether `c` survives the ignorable stripping for `preset`
+    fn keeps(&self, preset: &str) -> impl Fn(&char) -> bool + Copy {
+        let strip = self
+            .strip_ignorables
+            .unwrap_or(matches!(preset, "default" | "aggressive"));
+        let keep_joiners = self.keep_joiners;
+        move |&c| {
+            !strip
+                || !is_default_ignorable(c)
+                || (keep_joiners && matches!(c, '\u{200C}' | '\u{200D}'))
+        }
+    }
+}
+
+// Bidi_Control (UAX #9): ALM, LRM, RLM, the embeddings and overrides LRE-RLO,
+// and the isolates LRI-PDI. Invisible, yet they make equal-looking strings
+// differ and reorder what is displayed: "invoice\u{202E}fdp.exe" shows as
+// "invoiceexe.pdf".
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+// Default_Ignorable_Code_Point from Unicode 15.1 DerivedCoreProperties.txt: soft
+// hyphen, zero-width characters, bidi controls, variation selectors, Hangul
+// fillers, tags and the like, which render as nothing
+fn is_default_ignorable(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'..='\u{1160}'
+            | '\u{17B4}'..='\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{FFF0}'..='\u{FFF8}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
+
+/// Whether `s` contains any bidi control character, e.g. a right-to-left
+/// override hiding a file extension
+pub fn contains_bidi_controls(s: &str) -> bool {
+    s.chars().any(is_bidi_control)
+}
+
+// Grouping spaces: space, no-break space, thin space and narrow no-break space
+fn is_group_space(c: char) -> bool {
+    matches!(c, ' ' | '\u{00A0}' | '\u{2009}' | '\u{202F}')
+}
+
+fn is_number_separator(c: char) -> bool {
+    matches!(c, ',' | '.') || is_group_space(c)
+}
+
+/// Rewrite numbers written with grouping separators or a decimal comma into
+/// plain digits with a "." decimal point, leaving all other text as it is:
+/// "1,000.50", "1.000,50" and "1 000,50" all become "1000.50".
+///
+/// A numeric token is a run of ASCII digit groups joined by single ",", "." or
+/// grouping spaces. A separator is only removed as grouping when the digit
+/// groups fit thousands grouping: a first group of one to three digits that
+/// doesn't start with 0, then three digits in each later group, all joined by
+/// one separator kind. The last separator is the decimal one when it is "," or
+/// "." and that kind appears exactly once, unless the whole token fits the
+/// grouping pattern: "1,000" and "1.000" both become "1000", while "3,14" and
+/// "0,500" become "3.14" and "0.500". A plain space only groups a number with a
+/// decimal part ("1 000,50"), so space-separated numbers such as "555 123" stay
+/// apart; no-break and thin spaces always group. Tokens that fit no reading,
+/// such as dates ("12.03.2024"), versions ("1.2.3") or lakh grouping
+/// ("1,00,000"), are left unchanged.
+pub fn fold_numeric_tokens(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut folded = String::with_capacity(s.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            folded.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let start = i;
+        loop {
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i + 1 < chars.len() && is_number_separator(chars[i]) && chars[i + 1].is_ascii_digit()
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        let token = &chars[start..i];
+        match fold_number(token) {
+            Some(number) => folded.push_str(&number),
+            None => folded.extend(token),
+        }
+    }
+    folded
+}
+
+// Whether digit groups joined by `separators` read as thousands grouping
+fn fits_grouping(groups: &[&[char]], separators: &[char]) -> bool {
+    let Some(&first) = separators.first() else {
+        return true;
+    };
+    separators.iter().all(|&c| c == first)
+        && (1..=3).contains(&groups[0].len())
+        && groups[0][0] != '0'
+        && groups[1..].iter().all(|group| group.len() == 3)
+}
+
+// One numeric token (digit groups joined by single separators) as plain digits
+// and an optional "." fraction; None when the token has no valid reading
+fn fold_number(token: &[char]) -> Option<String> {
+    // Every grouping space is the same kind of separator
+    let kind = |c: char| if is_group_space(c) { ' ' } else { c };
+    let separators: Vec<char> = token
+        .iter()
+        .filter(|c| !c.is_ascii_digit())
+        .map(|&c| kind(c))
+        .collect();
+    let groups: Vec<&[char]> = token.split(|c| !c.is_ascii_digit()).collect();
+    let last = *separators.last()?;
+    let decimal = last != ' '
+        && separators.iter().filter(|&&c| c == last).count() == 1
+        && !(separators.len() == 1 && fits_grouping(&groups, &separators));
+    let (integer, fraction) = if decimal {
+        (&groups[..groups.len() - 1], groups.last().copied())
+    } else {
+        (&groups[..], None)
+    };
+    let grouping = &separators[..integer.len() - 1];
+    // A plain space between bare digit groups more often separates two numbers
+    if !fits_grouping(integer, grouping) || (fraction.is_none() && token.contains(&' ')) {
+        return None;
+    }
+    let mut number: String = integer.iter().flat_map(|group| group.iter()).collect();
+    if let Some(fraction) = fraction {
+        number.push('.');
+        number.extend(fraction);
+    }
+    Some(number)
+}
+
+// The input with numeric tokens folded when the options ask for it
+fn numbers_folded<'s>(s: &'s str, options: &NormalizeOptions) -> Cow<'s, str> {
+    if options.fold_numbers {
+        Cow::Owned(fold_numeric_tokens(s))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+// The aggressive preset before trimming: folded, NFKD, without nonspacing marks,
+// keeping only alphanumerics, whitespace and any ignorables the options keep
+fn aggressive_chars<'a>(s: &'a str, options: &NormalizeOptions) -> impl Iterator<Item = char> + 'a {
+    let keeps = options.keeps("aggressive");
+    fold_chars(s, options.locale)
+        .nfkd()
+        .filter(|c| !c.is_mark_nonspacing())
+        .filter(move |&c| {
+            c.is_alphanumeric() || c.is_whitespace() || (is_default_ignorable(c) && keeps(&c))
+        })
+}
+
+/// Apply a normalization preset; unknown presets pass the input through unchanged
+pub fn normalize_with_locale(input: &str, preset: &str, locale: Option<&str>) -> String {
+    normalize_with_options(input, preset, &NormalizeOptions::with_locale(locale))
+}
+
+/// Apply a normalization preset, rejecting unknown preset names
+pub fn try_normalize_with_locale(
+    input: &str,
+    preset: &str,
+    locale: Option<&str>,
+) -> Result<String, String> {
+    try_normalize_with_options(input, preset, &NormalizeOptions::with_locale(locale))
+}
+
+/// `normalize_with_locale` with every option; unknown presets pass the input through
+pub fn normalize_with_options(input: &str, preset: &str, options: &NormalizeOptions) -> String {
+    try_normalize_with_options(input, preset, options).unwrap_or_else(|_| input.to_string())
+}
+
+/// `try_normalize_with_locale` with every option
+pub fn try_normalize_with_options(
+    input: &str,
+    preset: &str,
+    options: &NormalizeOptions,
+) -> Result<String, String> {
+    let keeps = options.keeps(preset);
+    let folded = numbers_folded(input, options);
+    let normalized = match preset {
+        "none" => input.to_string(),
+        "minimal" => folded
+            .chars()
+            .filter(keeps)
+            .collect::<String>()
+            .trim()
+            .nfc()
+            .collect::<String>(),
+        "default" => {
+            // Uncased text (CJK candidate sets) skips the per-char fold
+            let cased: String = if needs_case_fold(&folded) {
+                fold_chars(&folded, options.locale).filter(keeps).collect()
+            } else {
+                folded.chars().filter(keeps).collect()
+            };
+            cased.trim().nfc().collect::<String>()
+        }
+        "aggressive" => aggressive_chars(&folded, options)
+            .collect::<String>()
+            .trim()
+            .to_string(),
+        _ => return Err(format!("Unknown normalization preset: {}", preset)),
+    };
+    match options.expansions {
+        Some(expansions) if preset != "none" && !expansions.is_empty() => {
+            Ok(expansions.apply(&normalized, preset, options))
+        }
+        _ => Ok(normalized),
+    }
+}
+
+/// Whether `a` and `b` normalize to the same string, without building either
+/// normalized string (the presets are compared as lazy char streams)
+pub fn equals_normalized(a: &str, b: &str, preset: &str, options: &NormalizeOptions) -> bool {
+    if a == b {
+        return true;
+    }
+    // Expansions rewrite whole tokens, which a char stream can't do
+    if options
+        .expansions
+        .is_some_and(|expansions| !expansions.is_empty())
+    {
+        return match (
+            try_normalize_with_options(a, preset, options),
+            try_normalize_with_options(b, preset, options),
+        ) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        };
+    }
+    let (a, b) = (numbers_folded(a, options), numbers_folded(b, options));
+    let keeps = options.keeps(preset);
+    // Stripping can expose whitespace, so trimming comes last; NFC never turns
+    // whitespace into anything else, so it can come before
+    match preset {
+        "minimal" => trimmed_eq(a.chars().filter(keeps).nfc(), b.chars().filter(keeps).nfc()),
+        "default" => trimmed_eq(
+            fold_chars(&a, options.locale).filter(keeps).nfc(),
+            fold_chars(&b, options.locale).filter(keeps).nfc(),
+        ),
+        "aggressive" => trimmed_eq(aggressive_chars(&a, options), aggressive_chars(&b, options)),
+        // "none" and unknown presets leave the input unchanged
+        _ => false,
+    }
+}
+
+// Equality of two char streams after trimming whitespace from both ends
+fn trimmed_eq(a: impl Iterator<Item = char>, b: impl Iterator<Item = char>) -> bool {
+    let mut a = a.skip_while(|c| c.is_whitespace());
+    let mut b = b.skip_while(|c| c.is_whitespace());
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return true,
+            (Some(x), Some(y)) if x == y => continue,
+            // From the first difference on, both remainders must be trailing whitespace
+            (x, y) => {
+                return x.into_iter().chain(a).all(char::is_whitespace)
+                    && y.into_iter().chain(b).all(char::is_whitespace)
+            }
+        }
+    }
+}
+
+/// Version of `normalized_key`, `normalized_hash` and `normalization_fingerprint`
+/// output. Store it next to cached keys or hashes and rebuild them when it changes.
+///
+/// It is bumped whenever any input's key or hash changes: a preset or case
+/// folding fix, a Unicode data update in the normalization tables, or a change
+/// of hash function. Releases that keep it equal produce identical keys and
+/// hashes on every platform.
+pub const NORMALIZED_HASH_VERSION: u32 = 2;
+
+/// Stable bucketing key: strings with equal keys are `equals_normalized`.
+/// This is `normalize_with_options`, so unknown presets key the input as is.
+pub fn normalized_key(s: &str, preset: &str, options: &NormalizeOptions) -> String {
+    normalize_with_options(s, preset, options)
+}
+
+/// 64-bit FNV-1a of the UTF-8 bytes of `normalized_key`; stable across
+/// platforms and releases while `NORMALIZED_HASH_VERSION` is unchanged
+pub fn normalized_hash(s: &str, preset: &str, options: &NormalizeOptions) -> u64 {
+    fnv1a_64(normalized_key(s, preset, options).bytes())
+}
+
+/// Fingerprint of `normalize_with_locale` output for one preset and locale:
+/// 16 hex digits of FNV-1a over `NORMALIZED_HASH_VERSION`, the preset and the
+/// locale as given. Store it with cached normalized strings or indexes built
+/// from them and rebuild when the current build reports a different one.
+pub fn normalization_fingerprint(preset: &str, locale: Option<&str>) -> String {
+    // Unit separators keep ("ab", "c") and ("a", "bc") apart
+    let parameters = format!(
+        "{NORMALIZED_HASH_VERSION}\u{1f}{preset}\u{1f}{}",
+        locale.unwrap_or("")
+    );
+    format!("{:016x}", fnv1a_64(parameters.bytes()))
+}
+
+fn fnv1a_64(bytes: impl Iterator<Item = u8>) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+    bytes.fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+// ============================================================================
+// CANDIDATE SETS
+// ============================================================================
+// Candidates with their normalized keys, built once and cached by callers as
+// bytes. The bytes start with the normalization fingerprint of the preset and
+// locale they were built with, and loading them in a build whose fingerprint
+// differs fails with FINGERPRINT_MISMATCH_CODE instead of matching against
+// stale keys. Layout, integers little-endian:
+//
+//     "SMCS" | format u8 | fingerprint, 16 hex digits | preset | locale flag u8
+//     | locale | count u32 | count × (candidate | key)
+//
+// with every string a u32 byte length followed by its UTF-8.
+
+/// Stable prefix of the error for bytes built with another normalization fingerprint
+pub const FINGERPRINT_MISMATCH_CODE: &str = "FINGERPRINT_MISMATCH";
+
+const CANDIDATE_SET_MAGIC: &[u8; 4] = b"SMCS";
+const CANDIDATE_SET_FORMAT: u8 = 1;
+
+/// Candidates and their `normalize_with_locale` keys for one preset and locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidateSet {
+    preset: String,
+    locale: Option<String>,
+    candidates: Vec<String>,
+    keys: Vec<String>,
+}
+
+impl CandidateSet {
+    pub fn new<S: AsRef<str>>(candidates: &[S], preset: &str, locale: Option<&str>) -> Self {
+        let candidates: Vec<String> = candidates
+            .iter()
+            .map(|candidate| candidate.as_ref().to_string())
+            .collect();
+        let keys = candidates
+            .iter()
+            .map(|candidate| normalize_with_locale(candidate, preset, locale))
+            .collect();
+        CandidateSet {
+            preset: preset.to_string(),
+            locale: locale.map(str::to_string),
+            candidates,
+            keys,
+        }
+    }
+
+    pub fn preset(&self) -> &str {
+        &self.preset
+    }
+
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// `normalization_fingerprint` of the preset and locale the keys were built with
+    pub fn fingerprint(&self) -> String {
+        normalization_fingerprint(&self.preset, self.locale.as_deref())
+    }
+
+    /// Indices of the candidates whose key equals the normalized query, in order
+    pub fn lookup(&self, query: &str) -> Vec<usize> {
+        let key = normalize_with_locale(query, &self.preset, self.locale.as_deref());
+        self.keys
+            .iter()
+            .enumerate()
+            .filter(|(_, candidate_key)| **candidate_key == key)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(CANDIDATE_SET_MAGIC);
+        bytes.push(CANDIDATE_SET_FORMAT);
+        bytes.extend_from_slice(self.fingerprint().as_bytes());
+        push_str(&mut bytes, &self.preset);
+        bytes.push(u8::from(self.locale.is_some()));
+        push_str(&mut bytes, self.locale.as_deref().unwrap_or(""));
+        bytes.extend_from_slice(&(self.candidates.len() as u32).to_le_bytes());
+        for (candidate, key) in self.candidates.iter().zip(&self.keys) {
+            push_str(&mut bytes, candidate);
+            push_str(&mut bytes, key);
+        }
+        bytes
+    }
+
+    /// Err with a `fingerprint_mismatch_message` when the bytes were built with
+    /// another normalization fingerprint than this build gives their preset and
+    /// locale, and with "Invalid candidate set: ..." when they are malformed
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = ByteReader(bytes);
+        if reader.take(CANDIDATE_SET_MAGIC.len())? != CANDIDATE_SET_MAGIC {
+            return Err("Invalid candidate set: missing SMCS header".to_string());
+        }
+        let format = reader.take(1)?[0];
+        if format != CANDIDATE_SET_FORMAT {
+            return Err(format!("Invalid candidate set: unknown format {}", format));
+        }
+        let fingerprint = std::str::from_utf8(reader.take(16)?)
+            .map_err(|_| "Invalid candidate set: fingerprint is not hex".to_string())?
+            .to_string();
+        let preset = reader.string()?;
+        let has_locale = reader.take(1)?[0] != 0;
+        let locale = reader.string()?;
+        let locale = has_locale.then_some(locale);
+        // Checked before the entries: their keys are only as good as the fingerprint
+        let current = normalization_fingerprint(&preset, locale.as_deref());
+        if fingerprint != current {
+            return Err(fingerprint_mismatch_message(&fingerprint, &current));
+        }
+        let count = reader.u32()? as usize;
+        let mut candidates = Vec::new();
+        let mut keys = Vec::new();
+        for _ in 0..count {
+            candidates.push(reader.string()?);
+            keys.push(reader.string()?);
+        }
+        if !reader.0.is_empty() {
+            return Err("Invalid candidate set: trailing bytes".to_string());
+        }
+        Ok(CandidateSet {
+            preset,
+            locale,
+            candidates,
+            keys,
+        })
+    }
+}
+
+/// "FINGERPRINT_MISMATCH: built with normalization fingerprint 0123456789abcdef,
+/// this build has fedcba9876543210"
+pub fn fingerprint_mismatch_message(stored: &str, current: &str) -> String {
+    format!(
+        "{}: built with normalization fingerprint {}, this build has {}",
+        FINGERPRINT_MISMATCH_CODE, stored, current
+    )
+}
+
+fn push_str(bytes: &mut Vec<u8>, s: &str) {
+    bytes.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+// Reads a CandidateSet's fields off the front of its bytes
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < len {
+            return Err("Invalid candidate set: truncated".to_string());
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| "Invalid candidate set: string is not UTF-8".to_string())
+    }
+}
+
+// ============================================================================
+// EXPANSIONS
+// ============================================================================
+// Abbreviation dictionaries for address and company matching ("St" → "Street",
+// "Intl" → "International"). They run on the normalized string, so keys match
+// case-insensitively whatever the preset, and the replacement goes through the
+// same preset so it compares like the rest of the text. Tokens are UAX #29
+// words, so "St." matches "St" and leaves the period alone. One left-to-right
+// pass: at each token the key covering the most tokens wins, and replacements
+// are never expanded again, so "St" → "Saint St" stays put.
+
+/// Token → replacement map applied by normalization
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Expansions {
+    // Key tokens as given, the most tokens first (then in input order)
+    entries: Vec<(Vec<String>, String)>,
+}
+
+impl Expansions {
+    /// Every key needs a word token; keys equal after case folding are rejected
+    pub fn new<K: AsRef<str>, V: AsRef<str>>(pairs: &[(K, V)]) -> Result<Self, String> {
+        let mut entries: Vec<(Vec<String>, String)> = Vec::with_capacity(pairs.len());
+        for (key, replacement) in pairs {
+            let key = key.as_ref();
+            let tokens: Vec<String> = word_tokens(key, Tokenizer::Words)
+                .into_iter()
+                .filter(|token| !token.text.is_empty())
+                .map(|token| token.text.to_string())
+                .collect();
+            if tokens.is_empty() {
+                return Err(format!("Expansion key has no tokens: {:?}", key));
+            }
+            let duplicate = entries.iter().any(|(other, _)| {
+                other.len() == tokens.len()
+                    && other
+                        .iter()
+                        .zip(&tokens)
+                        .all(|(x, y)| fold_chars(x, None).eq(fold_chars(y, None)))
+            });
+            if duplicate {
+                return Err(format!("Duplicate expansion key: {:?}", key));
+            }
+            entries.push((tokens, replacement.as_ref().to_string()));
+        }
+        // Stable, so keys of one length keep their input order
+        entries.sort_by_key(|(tokens, _)| std::cmp::Reverse(tokens.len()));
+        Ok(Expansions { entries })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // `normalized` with every matched key replaced, retrimmed since a
+    // replacement may normalize to nothing
+    fn apply(&self, normalized: &str, preset: &str, options: &NormalizeOptions) -> String {
+        let tokens = word_tokens(normalized, Tokenizer::Words);
+        let replacement_options = NormalizeOptions {
+            expansions: None,
+            ..*options
+        };
+        let mut expanded = String::with_capacity(normalized.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            let matched = self.entries.iter().find(|(key, _)| {
+                tokens[i..].len() >= key.len()
+                    && key.iter().zip(&tokens[i..]).all(|(k, token)| {
+                        fold_chars(k, options.locale).eq(fold_chars(token.text, options.locale))
+                    })
+            });
+            match matched {
+                Some((key, replacement)) => {
+                    expanded.push_str(&normalize_with_options(
+                        replacement,
+                        preset,
+                        &replacement_options,
+                    ));
+                    i += key.len();
+                    expanded.push_str(tokens[i - 1].whitespace);
+                }
+                None => {
+                    expanded.push_str(tokens[i].text);
+                    expanded.push_str(tokens[i].whitespace);
+                    i += 1;
+                }
+            }
+        }
+        expanded.trim().to_string()
+    }
+}
+
+// ============================================================================
+// SCRIPT DETECTION
+// ============================================================================
+// Compact range tables rather than the full Unicode Scripts.txt: the scripts
+// a front end routes normalization on (including a few supplementary-plane
+// ones) plus the Common and Inherited ranges. Anything else, unassigned code
+// points included, counts as Unknown (Zzzz).
+
+/// ISO 15924 codes, indexed by `SCRIPT_RANGES`
+pub const SCRIPTS: &[&str] = &[
+    "Zyyy", "Zinh", "Zzzz", "Latn", "Grek", "Cyrl", "Armn", "Hebr", "Arab", "Syrc", "Thaa", "Deva",
+    "Beng", "Guru", "Gujr", "Orya", "Taml", "Telu", "Knda", "Mlym", "Sinh", "Thai", "Laoo", "Tibt",
+    "Mymr", "Geor", "Hang", "Ethi", "Cher", "Khmr", "Mong", "Hira", "Kana", "Bopo", "Hani", "Goth",
+    "Dsrt", "Xsux", "Egyp", "Adlm",
+];
+
+const COMMON: u8 = 0;
+const INHERITED: u8 = 1;
+const UNKNOWN: u8 = 2;
+const LATN: u8 = 3;
+const GREK: u8 = 4;
+const CYRL: u8 = 5;
+const ARMN: u8 = 6;
+const HEBR: u8 = 7;
+const ARAB: u8 = 8;
+const SYRC: u8 = 9;
+const THAA: u8 = 10;
+const DEVA: u8 = 11;
+const BENG: u8 = 12;
+const GURU: u8 = 13;
+const GUJR: u8 = 14;
+const ORYA: u8 = 15;
+const TAML: u8 = 16;
+const TELU: u8 = 17;
+const KNDA: u8 = 18;
+const MLYM: u8 = 19;
+const SINH: u8 = 20;
+const THAI: u8 = 21;
+const LAOO: u8 = 22;
+const TIBT: u8 = 23;
+const MYMR: u8 = 24;
+const GEOR: u8 = 25;
+const HANG: u8 = 26;
+const ETHI: u8 = 27;
+const CHER: u8 = 28;
+const KHMR: u8 = 29;
+const MONG: u8 = 30;
+const HIRA: u8 = 31;
+const KANA: u8 = 32;
+const BOPO: u8 = 33;
+const HANI: u8 = 34;
+const GOTH: u8 = 35;
+const DSRT: u8 = 36;
+const XSUX: u8 = 37;
+const EGYP: u8 = 38;
+const ADLM: u8 = 39;
+
+/// Inclusive code point ranges and their `SCRIPTS` index, sorted and disjoint
+#[rustfmt::skip]
+const SCRIPT_RANGES: &[(u32, u32, u8)] = &[
+    (0x0000, 0x0040, COMMON), (0x0041, 0x005A, LATN), (0x005B, 0x0060, COMMON),
+    (0x0061, 0x007A, LATN), (0x007B, 0x00A9, COMMON), (0x00AA, 0x00AA, LATN),
+    (0x00AB, 0x00B9, COMMON), (0x00BA, 0x00BA, LATN), (0x00BB, 0x00BF, COMMON),
+    (0x00C0, 0x00D6, LATN), (0x00D7, 0x00D7, COMMON), (0x00D8, 0x00F6, LATN),
+    (0x00F7, 0x00F7, COMMON), (0x00F8, 0x02B8, LATN), (0x02B9, 0x02DF, COMMON),
+    (0x02E0, 0x02E4, LATN), (0x02E5, 0x02FF, COMMON), (0x0300, 0x036F, INHERITED),
+    (0x0370, 0x0373, GREK), (0x0374, 0x0374, COMMON), (0x0375, 0x037D, GREK),
+    (0x037E, 0x037E, COMMON), (0x037F, 0x0384, GREK), (0x0385, 0x0385, COMMON),
+    (0x0386, 0x0386, GREK), (0x0387, 0x0387, COMMON), (0x0388, 0x03FF, GREK),
+    (0x0400, 0x0484, CYRL), (0x0485, 0x0486, INHERITED), (0x0487, 0x052F, CYRL),
+    (0x0531, 0x058F, ARMN), (0x0591, 0x05F4, HEBR), (0x0600, 0x060B, ARAB),
+    (0x060C, 0x060C, COMMON), (0x060D, 0x061A, ARAB), (0x061B, 0x061B, COMMON),
+    (0x061C, 0x061E, ARAB), (0x061F, 0x061F, COMMON), (0x0620, 0x063F, ARAB),
+    (0x0640, 0x0640, COMMON), (0x0641, 0x064A, ARAB), (0x064B, 0x0655, INHERITED),
+    (0x0656, 0x066F, ARAB), (0x0670, 0x0670, INHERITED), (0x0671, 0x06FF, ARAB),
+    (0x0700, 0x074F, SYRC), (0x0750, 0x077F, ARAB), (0x0780, 0x07BF, THAA), (0x08A0, 0x08FF, ARAB),
+    (0x0900, 0x0950, DEVA), (0x0951, 0x0954, INHERITED), (0x0955, 0x0963, DEVA),
+    (0x0964, 0x0965, COMMON), (0x0966, 0x097F, DEVA), (0x0980, 0x09FF, BENG),
+    (0x0A00, 0x0A7F, GURU), (0x0A80, 0x0AFF, GUJR), (0x0B00, 0x0B7F, ORYA), (0x0B80, 0x0BFF, TAML),
+    (0x0C00, 0x0C7F, TELU), (0x0C80, 0x0CFF, KNDA), (0x0D00, 0x0D7F, MLYM), (0x0D80, 0x0DFF, SINH),
+    (0x0E01, 0x0E3A, THAI), (0x0E3F, 0x0E3F, COMMON), (0x0E40, 0x0E5B, THAI),
+    (0x0E80, 0x0EFF, LAOO), (0x0F00, 0x0FD4, TIBT), (0x0FD5, 0x0FD8, COMMON),
+    (0x0FD9, 0x0FFF, TIBT), (0x1000, 0x109F, MYMR), (0x10A0, 0x10FA, GEOR),
+    (0x10FB, 0x10FB, COMMON), (0x10FC, 0x10FF, GEOR), (0x1100, 0x11FF, HANG),
+    (0x1200, 0x139F, ETHI), (0x13A0, 0x13FF, CHER), (0x1780, 0x17FF, KHMR), (0x1800, 0x1801, MONG),
+    (0x1802, 0x1803, COMMON), (0x1804, 0x1804, MONG), (0x1805, 0x1805, COMMON),
+    (0x1806, 0x18AF, MONG), (0x19E0, 0x19FF, KHMR), (0x1AB0, 0x1AFF, INHERITED),
+    (0x1C80, 0x1C8F, CYRL), (0x1C90, 0x1CBF, GEOR), (0x1D00, 0x1D25, LATN),
+    (0x1DC0, 0x1DFF, INHERITED), (0x1E00, 0x1EFF, LATN), (0x1F00, 0x1FFF, GREK),
+    (0x2000, 0x200B, COMMON), (0x200C, 0x200D, INHERITED), (0x200E, 0x2070, COMMON),
+    (0x2071, 0x2071, LATN), (0x2072, 0x207E, COMMON), (0x207F, 0x207F, LATN),
+    (0x2080, 0x208F, COMMON), (0x2090, 0x209C, LATN), (0x209D, 0x20CF, COMMON),
+    (0x20D0, 0x20FF, INHERITED), (0x2100, 0x2125, COMMON), (0x2126, 0x2126, GREK),
+    (0x2127, 0x2129, COMMON), (0x212A, 0x212B, LATN), (0x212C, 0x2131, COMMON),
+    (0x2132, 0x2132, LATN), (0x2133, 0x214D, COMMON), (0x214E, 0x214E, LATN),
+    (0x214F, 0x215F, COMMON), (0x2160, 0x2188, LATN), (0x2189, 0x2BFF, COMMON),
+    (0x2C60, 0x2C7F, LATN), (0x2D00, 0x2D2F, GEOR), (0x2D80, 0x2DDF, ETHI), (0x2DE0, 0x2DFF, CYRL),
+    (0x2E00, 0x2E7F, COMMON), (0x2E80, 0x2FDF, HANI), (0x2FF0, 0x3004, COMMON),
+    (0x3005, 0x3005, HANI), (0x3006, 0x3006, COMMON), (0x3007, 0x3007, HANI),
+    (0x3008, 0x3020, COMMON), (0x3021, 0x3029, HANI), (0x302A, 0x302D, INHERITED),
+    (0x302E, 0x3037, COMMON), (0x3038, 0x303B, HANI), (0x303C, 0x303F, COMMON),
+    (0x3041, 0x3096, HIRA), (0x3099, 0x309A, INHERITED), (0x309B, 0x309C, COMMON),
+    (0x309D, 0x309F, HIRA), (0x30A0, 0x30A0, COMMON), (0x30A1, 0x30FA, KANA),
+    (0x30FB, 0x30FC, COMMON), (0x30FD, 0x30FF, KANA), (0x3105, 0x312F, BOPO),
+    (0x3131, 0x318E, HANG), (0x3190, 0x319F, COMMON), (0x31A0, 0x31BF, BOPO),
+    (0x31C0, 0x31EF, COMMON), (0x31F0, 0x31FF, KANA), (0x3200, 0x32CF, COMMON),
+    (0x32D0, 0x32FE, KANA), (0x32FF, 0x32FF, COMMON), (0x3300, 0x3357, KANA),
+    (0x3358, 0x33FF, COMMON), (0x3400, 0x4DBF, HANI), (0x4DC0, 0x4DFF, COMMON),
+    (0x4E00, 0x9FFF, HANI), (0xA640, 0xA69F, CYRL), (0xA700, 0xA721, COMMON),
+    (0xA722, 0xA787, LATN), (0xA788, 0xA78A, COMMON), (0xA78B, 0xA7FF, LATN),
+    (0xA8E0, 0xA8FF, DEVA), (0xA960, 0xA97F, HANG), (0xAB30, 0xAB5A, LATN), (0xAB70, 0xABBF, CHER),
+    (0xAC00, 0xD7A3, HANG), (0xD7B0, 0xD7FF, HANG), (0xF900, 0xFAFF, HANI), (0xFB00, 0xFB06, LATN),
+    (0xFB1D, 0xFB4F, HEBR), (0xFB50, 0xFD3D, ARAB), (0xFD3E, 0xFD3F, COMMON),
+    (0xFD40, 0xFDFF, ARAB), (0xFE00, 0xFE0F, INHERITED), (0xFE10, 0xFE1F, COMMON),
+    (0xFE20, 0xFE2D, INHERITED), (0xFE30, 0xFE6F, COMMON), (0xFE70, 0xFEFC, ARAB),
+    (0xFEFF, 0xFF20, COMMON), (0xFF21, 0xFF3A, LATN), (0xFF3B, 0xFF40, COMMON),
+    (0xFF41, 0xFF5A, LATN), (0xFF5B, 0xFF65, COMMON), (0xFF66, 0xFF6F, KANA),
+    (0xFF70, 0xFF70, COMMON), (0xFF71, 0xFF9D, KANA), (0xFF9E, 0xFF9F, COMMON),
+    (0xFFA0, 0xFFDC, HANG), (0xFFE0, 0xFFFD, COMMON), (0x10330, 0x1034A, GOTH),
+    (0x10400, 0x1044F, DSRT), (0x12000, 0x1254F, XSUX), (0x13000, 0x1345F, EGYP),
+    (0x1B000, 0x1B000, KANA), (0x1B001, 0x1B11F, HIRA), (0x1D000, 0x1D7FF, COMMON),
+    (0x1E900, 0x1E95F, ADLM), (0x1F000, 0x1FBFF, COMMON), (0x20000, 0x2A6DF, HANI),
+    (0x2A700, 0x2EBEF, HANI), (0x2F800, 0x2FA1F, HANI), (0x30000, 0x3134F, HANI),
+    (0xE0001, 0xE007F, COMMON), (0xE0100, 0xE01EF, INHERITED),
+];
+
+/// `SCRIPTS` index of one character
+fn script_index(c: char) -> u8 {
+    let cp = c as u32;
+    let i = SCRIPT_RANGES.partition_point(|&(_, end, _)| end < cp);
+    match SCRIPT_RANGES.get(i) {
+        Some(&(start, _, script)) if start <= cp => script,
+        _ => UNKNOWN,
+    }
+}
+
+/// ISO 15924 code of one character's script (Zyyy Common, Zinh Inherited, Zzzz Unknown)
+pub fn char_script(c: char) -> &'static str {
+    SCRIPTS[script_index(c) as usize]
+}
+
+/// Characters per script, aligned with `SCRIPTS`
+pub fn script_counts(s: &str) -> Vec<usize> {
+    let mut counts = vec![0; SCRIPTS.len()];
+    for c in s.chars() {
+        counts[script_index(c) as usize] += 1;
+    }
+    counts
+}
+
+/// Scripts present in `s` with their character counts, most frequent first
+/// (ties in `SCRIPTS` order). Common and Inherited are included.
+pub fn script_breakdown(s: &str) -> Vec<(&'static str, usize)> {
+    let mut breakdown: Vec<(&'static str, usize)> = SCRIPTS
+        .iter()
+        .zip(script_counts(s))
+        .filter(|&(_, count)| count > 0)
+        .map(|(&script, count)| (script, count))
+        .collect();
+    breakdown.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    breakdown
+}
+
+// Common and Inherited characters belong to no script of their own
+fn is_script_specific(index: usize) -> bool {
+    index != COMMON as usize && index != INHERITED as usize
+}
+
+/// Most frequent script ignoring Common and Inherited (ties in `SCRIPTS` order),
+/// or Zyyy when there is none (e.g. digits and punctuation only)
+pub fn dominant_script(s: &str) -> &'static str {
+    let counts = script_counts(s);
+    let mut best: Option<(usize, usize)> = None;
+    for (index, &count) in counts.iter().enumerate() {
+        if count > 0 && is_script_specific(index) && best.is_none_or(|(_, top)| count > top) {
+            best = Some((index, count));
+        }
+    }
+    best.map_or(SCRIPTS[COMMON as usize], |(index, _)| SCRIPTS[index])
+}
+
+/// Whether more than one script appears, ignoring Common and Inherited. Japanese
+/// text mixing Han and kana counts as mixed.
+pub fn is_mixed_script(s: &str) -> bool {
+    script_counts(s)
+        .iter()
+        .enumerate()
+        .filter(|&(index, &count)| count > 0 && is_script_specific(index))
+        .count()
+        > 1
+}
+
+// ============================================================================
+// ARITHMETIC MODE
+// ============================================================================
+// Rust never contracts or reorders float operations, so every formula here
+// rounds the same way on every host. What differs between implementations is
+// the operation order a formula is written in: Python's rapidfuzz computes the
+// Winkler adjustment as `sim + prefix * weight * (1 - sim)`, other ports as
+// `sim + (1 - sim) * (prefix * weight)`, and the results can be 1 ulp apart.
+//
+// The `canonical-arithmetic` feature (enabled by similarity-validator, so
+// generated fixtures use it) pins each hand-written formula to one written
+// order with one rounding per step: the boost toward 1.0 behind the Winkler
+// prefix adjustment and the suggestion prefix bonus is a single fused
+// multiply-add. The substring score `2 * len / (m + n)` is one division of
+// exact integers either way, and the token Jaro sum follows rapidfuzz's order
+// in both modes. Scores that come straight from rapidfuzz (every unified metric
+// except hamming, dice and jaccard_ngram, plus the Jaro inside
+// `jaro_winkler_with_params`) are used verbatim in both modes.
+
+/// `"canonical"` when built with the `canonical-arithmetic` feature, else `"native"`
+pub const ARITHMETIC_MODE: &str = if cfg!(feature = "canonical-arithmetic") {
+    "canonical"
+} else {
+    "native"
+};
+
+/// `score + (1 - score) * weight`, capped at 1.0: the Winkler prefix adjustment
+/// (weight = prefix length × scale) and the suggestion prefix bonus
+fn boost_toward_one(score: f64, weight: f64) -> f64 {
+    let boosted = if cfg!(feature = "canonical-arithmetic") {
+        (1.0 - score).mul_add(weight, score)
+    } else {
+        score + (1.0 - score) * weight
+    };
+    boosted.min(1.0)
+}
+
+// ============================================================================
+// JARO-WINKLER WITH PARAMETERS
+// ============================================================================
+
+/// Jaro-Winkler with a custom prefix scale (clamped to 0-0.25) and max prefix (clamped to 1-8).
+/// The prefix logic is ours; only the Jaro score comes from rapidfuzz.
+pub fn jaro_winkler_with_params(a: &str, b: &str, prefix_scale: f64, max_prefix: usize) -> f64 {
+    let clamped_scale = prefix_scale.clamp(0.0, 0.25);
+    let clamped_max = max_prefix.clamp(1, 8);
+    let jaro_score = rapidfuzz::distance::jaro::similarity(a.chars(), b.chars());
+    if jaro_score == 1.0 {
+        return 1.0;
+    }
+
+    let mut prefix_len = 0;
+    for (ca, cb) in a.chars().zip(b.chars()) {
+        if ca == cb {
+            prefix_len += 1;
+            if prefix_len >= clamped_max {
+                break;
+            }
+        } else {
+            break;
+        }
+    }
+
+    boost_toward_one(jaro_score, prefix_len as f64 * clamped_scale)
+}
+
+// ============================================================================
+// JARO OVER TOKENS
+// ============================================================================
+// Two levels: the outer Jaro runs over the tokens a `Tokenizer` splits out
+// (whitespace runs by default, like the TypeScript token metrics) as symbols,
+// and two tokens count as equal when they are identical or their char-level
+// Jaro-Winkler exceeds the inner `match_threshold` (a score exactly at it does
+// not match). The same test decides matches within the window, transpositions
+// and the Winkler prefix.
+//
+// The window is Jaro's max(len) / 2 - 1 but at least 1: names have few
+// tokens, and a zero window would never let "Garcia Maria" match "Maria Garcia".
+
+/// Inner Jaro-Winkler a token pair must exceed to count as a match
+pub const DEFAULT_TOKEN_MATCH_THRESHOLD: f64 = 0.9;
+
+/// Longest run of leading matching tokens the Winkler boost counts
+const MAX_TOKEN_PREFIX: usize = 4;
+
+fn tokens_match(a: &str, b: &str, threshold: f64) -> bool {
+    a == b || rapidfuzz::distance::jaro_winkler::similarity(a.chars(), b.chars()) > threshold
+}
+
+// The non-empty token texts of `s`
+fn token_texts(s: &str, tokenizer: Tokenizer) -> Vec<&str> {
+    word_tokens(s, tokenizer)
+        .into_iter()
+        .map(|token| token.text)
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+/// Jaro similarity over the tokens `tokenizer` splits out; tokens match when their
+/// Jaro-Winkler exceeds `match_threshold` (clamped to 0-1, so 1.0 means exact equality)
+pub fn jaro_tokens(a: &str, b: &str, match_threshold: f64, tokenizer: Tokenizer) -> f64 {
+    let a = token_texts(a, tokenizer);
+    let b = token_texts(b, tokenizer);
+    token_jaro(&a, &b, match_threshold.clamp(0.0, 1.0))
+}
+
+/// `jaro_tokens` plus the Winkler boost for up to 4 leading matching tokens,
+/// with `prefix_scale` clamped to 0-0.25 as in `jaro_winkler_with_params`
+pub fn jaro_winkler_tokens(
+    a: &str,
+    b: &str,
+    prefix_scale: f64,
+    match_threshold: f64,
+    tokenizer: Tokenizer,
+) -> f64 {
+    let a = token_texts(a, tokenizer);
+    let b = token_texts(b, tokenizer);
+    let threshold = match_threshold.clamp(0.0, 1.0);
+    let jaro_score = token_jaro(&a, &b, threshold);
+    if jaro_score == 1.0 {
+        return 1.0;
+    }
+
+    let prefix_len = a
+        .iter()
+        .zip(&b)
+        .take(MAX_TOKEN_PREFIX)
+        .take_while(|(x, y)| tokens_match(x, y, threshold))
+        .count();
+    boost_toward_one(
+        jaro_score,
+        prefix_len as f64 * prefix_scale.clamp(0.0, 0.25),
+    )
+}
+
+fn token_jaro(a: &[&str], b: &[&str], threshold: f64) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1).max(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, token) in a.iter().enumerate() {
+        let end = (i + window + 1).min(b.len());
+        for j in i.saturating_sub(window)..end {
+            if !b_matched[j] && tokens_match(token, b[j], threshold) {
+                b_matched[j] = true;
+                a_matches.push(*token);
+                break;
+            }
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    // Matched tokens in each string's order; a pair that no longer matches is half a transposition
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, matched)| **matched)
+        .map(|(token, _)| *token);
+    let half_transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(x, y)| !tokens_match(x, y, threshold))
+        .count();
+
+    let matches = a_matches.len() as f64;
+    let transpositions = (half_transpositions / 2) as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions) / matches)
+        / 3.0
+}
+
+// ============================================================================
+// HAMMING AND N-GRAM METRICS
+// ============================================================================
+// Hamming counts with rapidfuzz, always padded; the length check lives here
+// rather than at the call sites so the unequal-length error reads the same
+// everywhere (fixtures match it with `expect_error`). Dice and Jaccard compare
+// sets of character n-grams; rapidfuzz has no equivalent.
+
+/// N-gram size for Dice and for Jaccard when no size is given
+pub const DEFAULT_NGRAM_SIZE: usize = 2;
+
+/// Positions that differ; with `pad`, each extra character of the longer string counts once
+pub fn hamming_distance(a: &str, b: &str, pad: bool) -> Result<usize, String> {
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    check_hamming_lengths(len_a, len_b, pad)?;
+    Ok(rapidfuzz::distance::hamming::distance_with_args(
+        a.chars(),
+        b.chars(),
+        &rapidfuzz::distance::hamming::Args::default().pad(true),
+    ))
+}
+
+fn check_hamming_lengths(len_a: usize, len_b: usize, pad: bool) -> Result<(), String> {
+    check_hamming_units(len_a, len_b, pad, "characters")
+}
+
+fn check_hamming_units(len_a: usize, len_b: usize, pad: bool, unit: &str) -> Result<(), String> {
+    if len_a != len_b && !pad {
+        return Err(format!(
+            "Hamming distance requires equal lengths ({} vs {} {}) unless pad is set",
+            len_a, len_b, unit
+        ));
+    }
+    Ok(())
+}
+
+/// Hamming distance together with the char positions behind it, for highlighting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionalDiff {
+    pub distance: usize,
+    /// Ascending char positions where the strings differ
+    pub differing_positions: Vec<usize>,
+}
+
+/// Compare two strings position by position. With `pad`, the shorter string is padded at
+/// its end with a sentinel that equals no character, so every position past its end is
+/// reported as differing; `distance` always equals `differing_positions.len()`.
+pub fn positional_diff(a: &str, b: &str, pad: bool) -> Result<PositionalDiff, String> {
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    check_hamming_lengths(len_a, len_b, pad)?;
+    let mut differing_positions: Vec<usize> = a
+        .chars()
+        .zip(b.chars())
+        .enumerate()
+        .filter(|(_, (x, y))| x != y)
+        .map(|(i, _)| i)
+        .collect();
+    differing_positions.extend(len_a.min(len_b)..len_a.max(len_b));
+    Ok(PositionalDiff {
+        distance: differing_positions.len(),
+        differing_positions,
+    })
+}
+
+/// `1 - distance / max(len)`; two empty strings score 1.0
+pub fn hamming_normalized_similarity(a: &str, b: &str, pad: bool) -> Result<f64, String> {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    check_hamming_lengths(a.len(), b.len(), pad)?;
+    Ok(padded_hamming_similarity(&a, &b))
+}
+
+// Hamming distance with the shorter side padded, as unified_distance measures it
+fn padded_hamming_distance(a: &[char], b: &[char]) -> usize {
+    rapidfuzz::distance::hamming::distance_with_args(
+        a.iter(),
+        b.iter(),
+        &rapidfuzz::distance::hamming::Args::default().pad(true),
+    )
+}
+
+fn padded_hamming_similarity(a: &[char], b: &[char]) -> f64 {
+    rapidfuzz::distance::hamming::normalized_similarity_with_args(
+        a.iter(),
+        b.iter(),
+        &rapidfuzz::distance::hamming::Args::default().pad(true),
+    )
+}
+
+fn ngrams(chars: &[char], n: usize) -> std::collections::HashSet<&[char]> {
+    chars.windows(n).collect()
+}
+
+/// Score n-gram overlap with `formula(common, |A|, |B|)`
+fn ngram_similarity(
+    a: &[char],
+    b: &[char],
+    n: usize,
+    formula: fn(usize, usize, usize) -> f64,
+) -> f64 {
+    let (grams_a, grams_b) = (ngrams(a, n), ngrams(b, n));
+    // Strings too short for a single n-gram only match themselves
+    if grams_a.is_empty() && grams_b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    let common = grams_a.intersection(&grams_b).count();
+    formula(common, grams_a.len(), grams_b.len())
+}
+
+/// Sørensen-Dice coefficient over character bigrams: `2|A ∩ B| / (|A| + |B|)`
+pub fn dice(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    dice_chars(&a, &b)
+}
+
+fn dice_chars(a: &[char], b: &[char]) -> f64 {
+    ngram_similarity(a, b, DEFAULT_NGRAM_SIZE, |common, len_a, len_b| {
+        2.0 * common as f64 / (len_a + len_b) as f64
+    })
+}
+
+/// Jaccard index over character n-grams: `|A ∩ B| / |A ∪ B|`
+pub fn jaccard_ngram(a: &str, b: &str, n: usize) -> Result<f64, String> {
+    if n == 0 {
+        return Err("Jaccard n-gram size must be at least 1".to_string());
+    }
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    Ok(jaccard_chars(&a, &b, n))
+}
+
+fn jaccard_chars(a: &[char], b: &[char], n: usize) -> f64 {
+    ngram_similarity(a, b, n, |common, len_a, len_b| {
+        common as f64 / (len_a + len_b - common) as f64
+    })
+}
+
+/// Weighted Jaccard of two token → weight lists: `Σ min(wa, wb) / Σ max(wa, wb)` over the
+/// union of tokens, a token missing from one side weighing 0 there. Tokens are compared
+/// exactly after `preset` ("none" leaves them as given); a token listed twice, or two that
+/// normalize alike, keeps its larger weight. Weights must be finite and non-negative. When
+/// every weight is 0, both sides empty included, the score is 1.0.
+pub fn weighted_jaccard(
+    a: &[(&str, f64)],
+    b: &[(&str, f64)],
+    preset: &str,
+    locale: Option<&str>,
+) -> Result<f64, String> {
+    let (a, b) = (
+        token_weights(a, preset, locale)?,
+        token_weights(b, preset, locale)?,
+    );
+    // Sorted keys keep the float sums in a fixed order
+    let union: BTreeMap<&str, (f64, f64)> = a
+        .keys()
+        .chain(b.keys())
+        .map(|token| {
+            let weight = |side: &BTreeMap<String, f64>| side.get(token).copied().unwrap_or(0.0);
+            (token.as_str(), (weight(&a), weight(&b)))
+        })
+        .collect();
+    let (min_sum, max_sum) = union
+        .values()
+        .fold((0.0, 0.0), |(min_sum, max_sum), &(wa, wb)| {
+            (min_sum + f64::min(wa, wb), max_sum + f64::max(wa, wb))
+        });
+    if max_sum == 0.0 {
+        return Ok(1.0);
+    }
+    Ok(min_sum / max_sum)
+}
+
+fn token_weights(
+    tokens: &[(&str, f64)],
+    preset: &str,
+    locale: Option<&str>,
+) -> Result<BTreeMap<String, f64>, String> {
+    let mut weights = BTreeMap::new();
+    for &(token, weight) in tokens {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(format!(
+                "Token weights must be finite and non-negative (got {} for {:?})",
+                weight, token
+            ));
+        }
+        let entry = weights
+            .entry(try_normalize_with_locale(token, preset, locale)?)
+            .or_insert(0.0);
+        *entry = f64::max(*entry, weight);
+    }
+    Ok(weights)
+}
+
+// ============================================================================
+// BYTE METRICS
+// ============================================================================
+// Levenshtein and Hamming over raw bytes, for base64 tokens, hex digests and
+// Uint8Array input where decoding UTF-8 is wasted work. Every byte is one
+// symbol, so for non-ASCII text these differ from the char-based functions: a
+// two-byte "é" against "e" is two edits here and one there. They are not
+// unified metrics, since they take bytes rather than strings.
+
+/// Byte-wise Levenshtein distance
+pub fn levenshtein_bytes(a: &[u8], b: &[u8]) -> usize {
+    rapidfuzz::distance::levenshtein::distance(a.iter().copied(), b.iter().copied())
+}
+
+/// `1 - distance / max(len)` over bytes; two empty inputs score 1.0
+pub fn normalized_levenshtein_bytes(a: &[u8], b: &[u8]) -> f64 {
+    rapidfuzz::distance::levenshtein::normalized_similarity(a.iter().copied(), b.iter().copied())
+}
+
+/// Byte positions that differ; with `pad`, each extra byte of the longer input counts once
+pub fn hamming_bytes(a: &[u8], b: &[u8], pad: bool) -> Result<usize, String> {
+    check_hamming_units(a.len(), b.len(), pad, "bytes")?;
+    Ok(rapidfuzz::distance::hamming::distance_with_args(
+        a.iter().copied(),
+        b.iter().copied(),
+        &rapidfuzz::distance::hamming::Args::default().pad(true),
+    ))
+}
+
+/// `1 - distance / max(len)` over bytes; two empty inputs score 1.0
+pub fn normalized_hamming_bytes(a: &[u8], b: &[u8], pad: bool) -> Result<f64, String> {
+    check_hamming_units(a.len(), b.len(), pad, "bytes")?;
+    Ok(
+        rapidfuzz::distance::hamming::normalized_similarity_with_args(
+            a.iter().copied(),
+            b.iter().copied(),
+            &rapidfuzz::distance::hamming::Args::default().pad(true),
+        ),
+    )
+}
+
+// ============================================================================
+// MOST FREQUENT K CHARACTERS
+// ============================================================================
+// Seker et al.'s most-frequent-k-characters hashing: a string is reduced to its
+// k most frequent chars with their counts (ties by first appearance), and two
+// signatures score the summed counts of the chars they share, divided by the
+// total of both signatures' counts. Cheap enough for a first pass over a large
+// corpus.
+//
+// With k = 0 (every distinct char) the score is an upper bound on the indel
+// similarity (rapidfuzz `ratio` / 100): the LCS uses each char at most
+// min(count_a, count_b) times. Dropping pairs below a cutoff therefore never
+// loses a pair whose indel similarity reaches it; the validator's
+// `mfc_bounds_indel` property checks that. A smaller k gives no such guarantee.
+
+/// The k most frequent chars with counts, most frequent first; k = 0 keeps all
+fn mfc_counts(s: &str, k: usize) -> Vec<(char, usize)> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut order = Vec::new();
+    for c in s.chars() {
+        let count = counts.entry(c).or_insert(0);
+        if *count == 0 {
+            order.push(c);
+        }
+        *count += 1;
+    }
+    let mut entries: Vec<(char, usize)> = order.into_iter().map(|c| (c, counts[&c])).collect();
+    // Stable, so equal counts keep their first-appearance order
+    entries.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    if k > 0 {
+        entries.truncate(k);
+    }
+    entries
+}
+
+/// Storable signature: each kept char followed by its count, entries joined by
+/// `:` so digit chars parse unambiguously (`"research"`, k = 2 → `"r2:e2"`)
+pub fn mfc_signature(s: &str, k: usize) -> String {
+    mfc_counts(s, k)
+        .iter()
+        .map(|(c, count)| format!("{}{}", c, count))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn parse_mfc_signature(signature: &str) -> Result<Vec<(char, usize)>, String> {
+    let invalid = || format!("Invalid MFC signature: {:?}", signature);
+    let mut entries = Vec::new();
+    let mut chars = signature.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut digits = String::new();
+        while let Some(d) = chars.next_if(char::is_ascii_digit) {
+            digits.push(d);
+        }
+        let count: usize = digits.parse().map_err(|_| invalid())?;
+        if count == 0 || chars.next().is_some_and(|sep| sep != ':') {
+            return Err(invalid());
+        }
+        entries.push((c, count));
+    }
+    Ok(entries)
+}
+
+fn mfc_score(a: &[(char, usize)], b: &[(char, usize)]) -> f64 {
+    let total: usize = a.iter().chain(b).map(|&(_, count)| count).sum();
+    if total == 0 {
+        return 1.0;
+    }
+    let b_counts: HashMap<char, usize> = b.iter().copied().collect();
+    let shared: usize = a
+        .iter()
+        .filter_map(|&(c, count)| b_counts.get(&c).map(|other| count + other))
+        .sum();
+    shared as f64 / total as f64
+}
+
+/// Most-frequent-k-characters similarity (0.0-1.0); k = 0 compares every char
+pub fn mfc_similarity(a: &str, b: &str, k: usize) -> f64 {
+    mfc_score(&mfc_counts(a, k), &mfc_counts(b, k))
+}
+
+/// `mfc_similarity` from two stored `mfc_signature` strings
+pub fn mfc_signature_similarity(a: &str, b: &str) -> Result<f64, String> {
+    Ok(mfc_score(
+        &parse_mfc_signature(a)?,
+        &parse_mfc_signature(b)?,
+    ))
+}
+
+// ============================================================================
+// DAMERAU-LEVENSHTEIN VARIANTS
+// ============================================================================
+// Unrestricted Damerau-Levenshtein (Lowrance-Wagner) over any sequence, so the
+// same code serves grapheme clusters and a weighted transposition. With a cost
+// of 1.0 both reproduce rapidfuzz's integer distance exactly; the validator's
+// `damerau_unit_cost` property checks that.
+
+/// Transposition costs are clamped to (0, 2]; see `damerau_levenshtein_weighted`
+pub const MAX_TRANSPOSITION_COST: f64 = 2.0;
+
+/// Lowest-cost alignment with unit inserts, deletes and substitutions, and
+/// transpositions (of elements with anything in between) at `transposition_cost`
+fn damerau_levenshtein_cost<T: Eq + Hash>(a: &[T], b: &[T], transposition_cost: f64) -> f64 {
+    let (n, m) = (a.len(), b.len());
+    // d[i + 1][j + 1] is the cost for a[..i] vs b[..j]; row and column 0 are sentinels
+    let sentinel = (n + m) as f64;
+    let mut d = vec![vec![0.0; m + 2]; n + 2];
+    d[0][0] = sentinel;
+    for i in 0..=n {
+        d[i + 1][0] = sentinel;
+        d[i + 1][1] = i as f64;
+    }
+    for j in 0..=m {
+        d[0][j + 1] = sentinel;
+        d[1][j + 1] = j as f64;
+    }
+
+    // Last row of a in which each element was seen
+    let mut last_row: HashMap<&T, usize> = HashMap::new();
+    for i in 1..=n {
+        let mut last_match_col = 0;
+        for j in 1..=m {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_col;
+            let substitution = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0.0
+            } else {
+                1.0
+            };
+            let transposition =
+                d[k][l] + (i - k - 1) as f64 + transposition_cost + (j - l - 1) as f64;
+            d[i + 1][j + 1] = (d[i][j] + substitution)
+                .min(d[i + 1][j] + 1.0)
+                .min(d[i][j + 1] + 1.0)
+                .min(transposition);
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    d[n + 1][m + 1]
+}
+
+/// `1 - cost / max(len)`; two empty sequences score 1.0
+fn normalized_cost_similarity(cost: f64, len_a: usize, len_b: usize) -> f64 {
+    let max_len = len_a.max(len_b);
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - cost / max_len as f64
+}
+
+fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+/// Damerau-Levenshtein distance over extended grapheme clusters, so a ZWJ emoji
+/// sequence or a letter with combining marks is one element
+pub fn damerau_levenshtein_graphemes(a: &str, b: &str) -> usize {
+    damerau_levenshtein_cost(&graphemes(a), &graphemes(b), 1.0) as usize
+}
+
+/// `1 - distance / max(grapheme count)`
+pub fn damerau_levenshtein_graphemes_normalized_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (graphemes(a), graphemes(b));
+    normalized_cost_similarity(damerau_levenshtein_cost(&a, &b, 1.0), a.len(), b.len())
+}
+
+/// Clamp to (0, 2]: larger costs become 2, and non-positive or NaN costs the smallest
+/// positive f64 (a free transposition would let "ab" equal "ba")
+fn clamp_transposition_cost(cost: f64) -> f64 {
+    if cost > 0.0 {
+        cost.min(MAX_TRANSPOSITION_COST)
+    } else {
+        f64::MIN_POSITIVE
+    }
+}
+
+/// Damerau-Levenshtein total cost over chars with transpositions at `transposition_cost`
+/// (clamped to (0, 2]); a cost of 1.0 equals the integer distance exactly. As in the
+/// integer metric, transposed elements are not edited again, so a cheap swap is never
+/// chained with another one over the same chars.
+pub fn damerau_levenshtein_weighted(a: &str, b: &str, transposition_cost: f64) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    damerau_levenshtein_cost(&a, &b, clamp_transposition_cost(transposition_cost))
+}
+
+/// `1 - cost / max(len)` for `damerau_levenshtein_weighted`
+pub fn damerau_levenshtein_weighted_normalized_similarity(
+    a: &str,
+    b: &str,
+    transposition_cost: f64,
+) -> f64 {
+    let cost = damerau_levenshtein_weighted(a, b, transposition_cost);
+    normalized_cost_similarity(cost, a.chars().count(), b.chars().count())
+}
+
+// ============================================================================
+// AFFINE-GAP DISTANCE
+// ============================================================================
+// Gotoh's algorithm: a gap of k inserted or deleted chars costs
+// gap_open + (k - 1) * gap_extend, so one long gap is cheaper than several
+// short ones. Three rolling rows track the best alignment ending in a
+// match/mismatch, a deletion (gap in b) or an insertion (gap in a).
+
+/// Validate affine-gap penalties: all positive and finite, `gap_extend <= gap_open`
+fn check_affine_penalties(mismatch: f64, gap_open: f64, gap_extend: f64) -> Result<(), String> {
+    for (name, value) in [
+        ("mismatch", mismatch),
+        ("gap_open", gap_open),
+        ("gap_extend", gap_extend),
+    ] {
+        if !(value.is_finite() && value > 0.0) {
+            return Err(format!(
+                "Affine-gap {} penalty must be positive and finite (got {})",
+                name, value
+            ));
+        }
+    }
+    if gap_extend > gap_open {
+        return Err(format!(
+            "Affine-gap gap_extend must not exceed gap_open (got gap_extend={}, gap_open={})",
+            gap_extend, gap_open
+        ));
+    }
+    Ok(())
+}
+
+// Cost of one gap of `len` chars
+fn affine_gap_cost(len: usize, gap_open: f64, gap_extend: f64) -> f64 {
+    if len == 0 {
+        0.0
+    } else {
+        gap_open + (len - 1) as f64 * gap_extend
+    }
+}
+
+/// Lowest alignment cost with `mismatch` per substituted char and affine gaps
+/// (`gap_open` for a gap's first char, `gap_extend` for each further one)
+pub fn affine_gap_distance(
+    a: &str,
+    b: &str,
+    mismatch: f64,
+    gap_open: f64,
+    gap_extend: f64,
+) -> Result<f64, String> {
+    check_affine_penalties(mismatch, gap_open, gap_extend)?;
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // Row i: best cost of aligning a[..i] with b[..j] ending in a match/mismatch (m),
+    // a deletion of a[i - 1] (x) or an insertion of b[j - 1] (y)
+    let mut m = vec![f64::INFINITY; b.len() + 1];
+    let mut x = vec![f64::INFINITY; b.len() + 1];
+    let mut y: Vec<f64> = (0..=b.len())
+        .map(|j| match j {
+            0 => f64::INFINITY,
+            _ => affine_gap_cost(j, gap_open, gap_extend),
+        })
+        .collect();
+    m[0] = 0.0;
+    let (mut prev_m, mut prev_x, mut prev_y) = (m.clone(), x.clone(), y.clone());
+
+    for (i, &ca) in a.iter().enumerate() {
+        std::mem::swap(&mut m, &mut prev_m);
+        std::mem::swap(&mut x, &mut prev_x);
+        std::mem::swap(&mut y, &mut prev_y);
+        m[0] = f64::INFINITY;
+        x[0] = affine_gap_cost(i + 1, gap_open, gap_extend);
+        y[0] = f64::INFINITY;
+        for (j, &cb) in b.iter().enumerate() {
+            let diagonal = prev_m[j].min(prev_x[j]).min(prev_y[j]);
+            m[j + 1] = diagonal + if ca == cb { 0.0 } else { mismatch };
+            x[j + 1] = (prev_m[j + 1] + gap_open)
+                .min(prev_x[j + 1] + gap_extend)
+                .min(prev_y[j + 1] + gap_open);
+            y[j + 1] = (m[j] + gap_open)
+                .min(y[j] + gap_extend)
+                .min(x[j] + gap_open);
+        }
+    }
+
+    let last = b.len();
+    Ok(m[last].min(x[last]).min(y[last]))
+}
+
+/// `1 - cost / worst case`, where the worst case deletes all of `a` and inserts
+/// all of `b` as two gaps (an alignment that always exists, so the score is in 0-1)
+pub fn affine_gap_normalized_similarity(
+    a: &str,
+    b: &str,
+    mismatch: f64,
+    gap_open: f64,
+    gap_extend: f64,
+) -> Result<f64, String> {
+    let cost = affine_gap_distance(a, b, mismatch, gap_open, gap_extend)?;
+    let worst = affine_gap_cost(a.chars().count(), gap_open, gap_extend)
+        + affine_gap_cost(b.chars().count(), gap_open, gap_extend);
+    if worst == 0.0 {
+        return Ok(1.0);
+    }
+    Ok((1.0 - cost / worst).max(0.0))
+}
+
+// ============================================================================
+// DIGRAPHS
+// ============================================================================
+// Some orthographies treat a letter pair as one letter (Dutch "ij", Hungarian
+// "sz", Czech "ch"), and counting it as two chars inflates distances between
+// names. A configured unit becomes one u32 symbol above the Unicode code space,
+// and every other char its code point, so the rapidfuzz sequence metrics run on
+// the symbols unchanged. Segmentation is greedy: at each position the longest
+// matching unit wins (two distinct units of one length can't both match), and
+// a char no unit starts at stands for itself.
+
+/// First symbol id given to a digraph; every char's code point is below it
+const FIRST_DIGRAPH_SYMBOL: u32 = 0x11_0000;
+
+/// Multi-char units segmented as single symbols, matched exactly (case-sensitively)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Digraphs {
+    // Longest first, then in code point order, so symbol ids don't depend on input order
+    units: Vec<Vec<char>>,
+}
+
+impl Digraphs {
+    /// Every unit needs at least two chars; duplicates are dropped
+    pub fn new<S: AsRef<str>>(units: &[S]) -> Result<Self, String> {
+        let mut parsed = Vec::with_capacity(units.len());
+        for unit in units {
+            let chars: Vec<char> = unit.as_ref().chars().collect();
+            if chars.len() < 2 {
+                return Err(format!(
+                    "Digraph must have at least two characters: {:?}",
+                    unit.as_ref()
+                ));
+            }
+            parsed.push(chars);
+        }
+        parsed.sort_by(|x, y| y.len().cmp(&x.len()).then_with(|| x.cmp(y)));
+        parsed.dedup();
+        Ok(Digraphs { units: parsed })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// `s` as symbols, taking the longest unit that matches at each position
+    pub fn symbols(&self, s: &str) -> Vec<u32> {
+        let chars: Vec<char> = s.chars().collect();
+        let mut symbols = Vec::with_capacity(chars.len());
+        let mut i = 0;
+        while i < chars.len() {
+            match self
+                .units
+                .iter()
+                .position(|unit| chars[i..].starts_with(unit))
+            {
+                Some(k) => {
+                    symbols.push(FIRST_DIGRAPH_SYMBOL + k as u32);
+                    i += self.units[k].len();
+                }
+                None => {
+                    symbols.push(chars[i] as u32);
+                    i += 1;
+                }
+            }
+        }
+        symbols
+    }
+}
+
+/// `unified_distance` with each digraph counted as one symbol. Only the built-in
+/// metrics defined on sequences accept digraphs.
+pub fn unified_distance_with_digraphs(
+    a: &str,
+    b: &str,
+    metric: &str,
+    digraphs: &Digraphs,
+) -> Result<usize, String> {
+    // Report unknown names as unified_distance does, and resolve aliases
+    find_distance(metric)?;
+    let name = find_metric(metric)?.name;
+    let (a, b) = (digraphs.symbols(a), digraphs.symbols(b));
+    let (left, right) = (a.iter().copied(), b.iter().copied());
+    let distance = match name {
+        "levenshtein" => rapidfuzz::distance::levenshtein::distance(left, right),
+        "damerau_levenshtein" => rapidfuzz::distance::damerau_levenshtein::distance(left, right),
+        "osa" => rapidfuzz::distance::osa::distance(left, right),
+        "indel" => rapidfuzz::distance::indel::distance(left, right),
+        "lcs_seq" => rapidfuzz::distance::lcs_seq::distance(left, right),
+        "prefix" => rapidfuzz::distance::prefix::distance(left, right),
+        "postfix" => rapidfuzz::distance::postfix::distance(left, right),
+        _ => return Err(unsupported_digraph_metric(metric)),
+    };
+    Ok(distance)
+}
+
+/// `unified_score` with each digraph counted as one symbol, for the metrics
+/// `unified_distance_with_digraphs` accepts plus `jaro`, `jaro_winkler` and `ratio`
+pub fn unified_score_with_digraphs(
+    a: &str,
+    b: &str,
+    metric: &str,
+    digraphs: &Digraphs,
+) -> Result<f64, String> {
+    let name = find_metric(metric)?.name;
+    let (a, b) = (digraphs.symbols(a), digraphs.symbols(b));
+    let (left, right) = (a.iter().copied(), b.iter().copied());
+    let score = match name {
+        "levenshtein" => rapidfuzz::distance::levenshtein::normalized_similarity(left, right),
+        "damerau_levenshtein" => {
+            rapidfuzz::distance::damerau_levenshtein::normalized_similarity(left, right)
+        }
+        "osa" => rapidfuzz::distance::osa::normalized_similarity(left, right),
+        "jaro" => rapidfuzz::distance::jaro::similarity(left, right),
+        "jaro_winkler" => rapidfuzz::distance::jaro_winkler::similarity(left, right),
+        "indel" => rapidfuzz::distance::indel::normalized_similarity(left, right),
+        "lcs_seq" => rapidfuzz::distance::lcs_seq::normalized_similarity(left, right),
+        "ratio" => rapidfuzz::fuzz::ratio(left, right),
+        "prefix" => rapidfuzz::distance::prefix::normalized_similarity(left, right),
+        "postfix" => rapidfuzz::distance::postfix::normalized_similarity(left, right),
+        _ => return Err(unsupported_digraph_metric(metric)),
+    };
+    Ok(score)
+}
+
+fn unsupported_digraph_metric(metric: &str) -> String {
+    format!("Metric does not support digraphs: {}", metric)
+}
+
+// ============================================================================
+// UNIFIED METRIC REGISTRY
+// ============================================================================
+// Metric names follow the unified API's snake_case spelling. Every metric the
+// unified dispatchers, `Comparator`, extraction scorers and suggestion metrics
+// accept is a `MetricDef` in one table, filled with the built-ins on first use,
+// so adding one is a registration plus fixtures rather than a new match arm in
+// each dispatcher. Forks add their own with `register_metric` behind the
+// `custom-metrics` feature. Token-based metrics are TypeScript-only and
+// reported as unknown here. Hamming pads unequal lengths, and Jaccard uses
+// bigrams.
+
+/// 0-1 similarity of two char sequences
+pub type ScorerFn = fn(&[char], &[char]) -> f64;
+
+/// Edit distance between two char sequences
+pub type DistanceFn = fn(&[char], &[char]) -> usize;
+
+/// Whether a metric has a distance form besides its score
+#[derive(Debug, Clone, Copy)]
+pub enum MetricKind {
+    /// An edit distance; `unified_distance` dispatches it too
+    Distance(DistanceFn),
+    /// Only a score; `unified_distance` reports it as unknown
+    Similarity,
+}
+
+/// A metric the unified dispatchers resolve by name
+#[derive(Debug, Clone, Copy)]
+pub struct MetricDef {
+    pub name: &'static str,
+    /// Further names resolving to this metric, e.g. the suggestion engine's spelling
+    pub aliases: &'static [&'static str],
+    pub kind: MetricKind,
+    pub scorer_fn: ScorerFn,
+    /// Whether a score cutoff may be set with it; a `Comparator` or extraction rejects one otherwise
+    pub supports_cutoff: bool,
+    /// `score(a, b) == score(b, a)`, checked by similarity-validator's `symmetry` property
+    pub symmetric: bool,
+    /// Resolved by name in the unified score and distance APIs, `Comparator`, extraction and
+    /// suggestions; export-only similarities with fixed parameters are only listed
+    pub unified: bool,
+    /// The WASM export reporting this metric, if any
+    pub export: Option<&'static str>,
+    /// Range `export` reports scores in; `score` itself is always 0-1
+    pub scale: ScoreScale,
+}
+
+impl MetricDef {
+    /// The name followed by the aliases
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(self.name).chain(self.aliases.iter().copied())
+    }
+
+    pub fn score(&self, a: &[char], b: &[char]) -> f64 {
+        (self.scorer_fn)(a, b)
+    }
+
+    /// `score` of two strings, decoding both first
+    pub fn score_str(&self, a: &str, b: &str) -> f64 {
+        let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+        self.score(&a, &b)
+    }
+
+    pub fn distance_fn(&self) -> Option<DistanceFn> {
+        match self.kind {
+            MetricKind::Distance(distance) => Some(distance),
+            MetricKind::Similarity => None,
+        }
+    }
+
+    const fn exported_as(self, export: &'static str) -> Self {
+        MetricDef {
+            export: Some(export),
+            ..self
+        }
+    }
+
+    const fn on_scale(self, scale: ScoreScale) -> Self {
+        MetricDef { scale, ..self }
+    }
+}
+
+const fn distance_metric(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    distance: DistanceFn,
+    scorer_fn: ScorerFn,
+) -> MetricDef {
+    MetricDef {
+        name,
+        aliases,
+        kind: MetricKind::Distance(distance),
+        scorer_fn,
+        supports_cutoff: true,
+        symmetric: true,
+        unified: true,
+        export: None,
+        scale: ScoreScale::Unit,
+    }
+}
+
+const fn similarity_metric(name: &'static str, scorer_fn: ScorerFn) -> MetricDef {
+    MetricDef {
+        name,
+        aliases: &[],
+        kind: MetricKind::Similarity,
+        scorer_fn,
+        supports_cutoff: true,
+        symmetric: true,
+        unified: true,
+        export: None,
+        scale: ScoreScale::Unit,
+    }
+}
+
+// A similarity only its WASM export reports, at the parameters the TypeScript wrapper defaults
+// to (or, where it has none, fixed ones)
+const fn export_only(export: &'static str, scorer_fn: ScorerFn, symmetric: bool) -> MetricDef {
+    MetricDef {
+        name: export,
+        aliases: &[],
+        kind: MetricKind::Similarity,
+        scorer_fn,
+        supports_cutoff: false,
+        symmetric,
+        unified: false,
+        export: Some(export),
+        scale: ScoreScale::Unit,
+    }
+}
+
+// Whitespace-separated tokens weighing 1.0 each
+fn unit_weights(s: &str) -> Vec<(&str, f64)> {
+    s.split_whitespace().map(|token| (token, 1.0)).collect()
+}
+
+// Score char slices with a similarity over strings
+fn score_as_strs(a: &[char], b: &[char], score: fn(&str, &str) -> f64) -> f64 {
+    score(&a.iter().collect::<String>(), &b.iter().collect::<String>())
+}
+
+const BUILT_IN_METRICS: &[MetricDef] = &[
+    distance_metric(
+        "levenshtein",
+        &[],
+        |a, b| rapidfuzz::distance::levenshtein::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::levenshtein::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("normalized_levenshtein"),
+    distance_metric(
+        "damerau_levenshtein",
+        &["damerau_unrestricted"],
+        |a, b| rapidfuzz::distance::damerau_levenshtein::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::damerau_levenshtein::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("normalized_damerau_levenshtein"),
+    distance_metric(
+        "osa",
+        &["damerau_osa"],
+        |a, b| rapidfuzz::distance::osa::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::osa::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("normalized_osa_similarity"),
+    similarity_metric("jaro", |a, b| {
+        rapidfuzz::distance::jaro::similarity(a.iter(), b.iter())
+    })
+    .exported_as("jaro"),
+    similarity_metric("jaro_winkler", |a, b| {
+        rapidfuzz::distance::jaro_winkler::similarity(a.iter(), b.iter())
+    })
+    .exported_as("jaro_winkler"),
+    distance_metric(
+        "indel",
+        &[],
+        |a, b| rapidfuzz::distance::indel::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::indel::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("indel_normalized_similarity"),
+    distance_metric(
+        "lcs_seq",
+        &[],
+        |a, b| rapidfuzz::distance::lcs_seq::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::lcs_seq::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("lcs_seq_normalized_similarity"),
+    // Already 0-1 in rapidfuzz-rs; the export reports 0-100
+    similarity_metric("ratio", |a, b| rapidfuzz::fuzz::ratio(a.iter(), b.iter()))
+        .exported_as("ratio")
+        .on_scale(ScoreScale::Percent),
+    // Padded, as `unified_distance` measures it; the unpadded export throws on unequal lengths
+    distance_metric(
+        "hamming",
+        &[],
+        padded_hamming_distance,
+        padded_hamming_similarity,
+    )
+    .exported_as("normalized_hamming_similarity_with_padding"),
+    distance_metric(
+        "prefix",
+        &[],
+        |a, b| rapidfuzz::distance::prefix::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::prefix::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("prefix_normalized_similarity"),
+    distance_metric(
+        "postfix",
+        &[],
+        |a, b| rapidfuzz::distance::postfix::distance(a.iter(), b.iter()),
+        |a, b| rapidfuzz::distance::postfix::normalized_similarity(a.iter(), b.iter()),
+    )
+    .exported_as("postfix_normalized_similarity"),
+    similarity_metric("dice", dice_chars).exported_as("dice"),
+    similarity_metric("jaccard_ngram", |a, b| {
+        jaccard_chars(a, b, DEFAULT_NGRAM_SIZE)
+    })
+    .exported_as("jaccard_ngram"),
+    export_only(
+        "normalized_damerau_levenshtein_graphemes",
+        |a, b| score_as_strs(a, b, damerau_levenshtein_graphemes_normalized_similarity),
+        true,
+    ),
+    export_only(
+        "normalized_damerau_levenshtein_weighted",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                damerau_levenshtein_weighted_normalized_similarity(a, b, 0.5)
+            })
+        },
+        true,
+    ),
+    export_only(
+        "mfc_similarity",
+        |a, b| score_as_strs(a, b, |a, b| mfc_similarity(a, b, 2)),
+        true,
+    ),
+    export_only(
+        "normalized_affine_gap_similarity",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                affine_gap_normalized_similarity(a, b, 1.0, 3.0, 0.5).expect("valid penalties")
+            })
+        },
+        true,
+    ),
+    export_only(
+        "jaro_winkler_with_params",
+        |a, b| score_as_strs(a, b, |a, b| jaro_winkler_with_params(a, b, 0.1, 4)),
+        true,
+    ),
+    export_only(
+        "jaro_tokens",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                jaro_tokens(a, b, DEFAULT_TOKEN_MATCH_THRESHOLD, Tokenizer::Whitespace)
+            })
+        },
+        true,
+    ),
+    export_only(
+        "jaro_winkler_tokens",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                jaro_winkler_tokens(
+                    a,
+                    b,
+                    0.1,
+                    DEFAULT_TOKEN_MATCH_THRESHOLD,
+                    Tokenizer::Whitespace,
+                )
+            })
+        },
+        true,
+    ),
+    export_only(
+        "coverage_score",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                coverage_score(a, b, SubstringOptions::default()).0
+            })
+        },
+        false,
+    ),
+    export_only(
+        "subsequence_coverage_score",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                subsequence_coverage_score(a, b, RangeUnit::Char).0
+            })
+        },
+        false,
+    ),
+    // Over the UTF-8 bytes
+    export_only(
+        "normalized_levenshtein_bytes",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                normalized_levenshtein_bytes(a.as_bytes(), b.as_bytes())
+            })
+        },
+        true,
+    ),
+    export_only(
+        "normalized_hamming_bytes",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                normalized_hamming_bytes(a.as_bytes(), b.as_bytes(), true).expect("padded")
+            })
+        },
+        true,
+    ),
+    // Over whitespace tokens at unit weight
+    export_only(
+        "weighted_jaccard",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                weighted_jaccard(&unit_weights(a), &unit_weights(b), "none", None)
+                    .expect("unit weights")
+            })
+        },
+        true,
+    ),
+    // Two present values under the wrapper's default metric, Jaro-Winkler
+    export_only(
+        "score_nullable",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                let options = PairOptions::default();
+                score_nullable(
+                    Some(a),
+                    Some(b),
+                    "jaro_winkler",
+                    &options,
+                    MissingPolicy::Ignore,
+                )
+                .ok()
+                .and_then(|result| result.score)
+                .expect("a present pair has a score")
+            })
+        },
+        true,
+    ),
+    // Jaro-Winkler, the wrapper's default metric, anchored on the first char
+    export_only(
+        "anchored_similarity",
+        |a, b| {
+            score_as_strs(a, b, |a, b| {
+                let anchor = Anchor {
+                    side: AnchorSide::Start,
+                    length: 1,
+                };
+                anchored_similarity(a, b, anchor, "jaro_winkler", "none", None)
+                    .expect("built-in metric and preset")
+            })
+        },
+        true,
+    ),
+];
+
+// The registered metrics, or why registering the built-ins failed
+type Registry = Result<Vec<MetricDef>, String>;
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut metrics = Vec::with_capacity(BUILT_IN_METRICS.len());
+        let registered = BUILT_IN_METRICS
+            .iter()
+            .try_for_each(|def| add_metric(&mut metrics, *def));
+        RwLock::new(registered.map(|()| metrics))
+    })
+}
+
+// Register `def` unless one of its names is already taken. A clash is a build
+// mistake, so debug builds (and every test run) panic on it; release builds
+// report it from every lookup instead of dispatching to the wrong metric.
+fn add_metric(metrics: &mut Vec<MetricDef>, def: MetricDef) -> Result<(), String> {
+    for (i, name) in def.names().enumerate() {
+        let repeated = def.names().take(i).any(|earlier| earlier == name);
+        if repeated || lookup(metrics, name).is_some() {
+            let message = format!("Duplicate metric name: {}", name);
+            if cfg!(debug_assertions) {
+                panic!("{}", message);
+            }
+            return Err(message);
+        }
+    }
+    metrics.push(def);
+    Ok(())
+}
+
+fn lookup<'m>(metrics: &'m [MetricDef], name: &str) -> Option<&'m MetricDef> {
+    metrics.iter().find(|def| def.names().any(|n| n == name))
+}
+
+// The unified metric under `name`, None when nothing unified is registered under it
+fn lookup_metric(name: &str) -> Result<Option<MetricDef>, String> {
+    let registry = registry().read().unwrap_or_else(PoisonError::into_inner);
+    let metrics = registry.as_ref().map_err(Clone::clone)?;
+    Ok(lookup(metrics, name).filter(|def| def.unified).copied())
+}
+
+/// Add a metric compiled into a fork, after the built-ins. Its name and aliases must
+/// not be registered yet: a clash panics in debug builds and is an error in release.
+#[cfg(feature = "custom-metrics")]
+pub fn register_metric(def: MetricDef) -> Result<(), String> {
+    let mut registry = registry().write().unwrap_or_else(PoisonError::into_inner);
+    let metrics = registry.as_mut().map_err(|error| error.clone())?;
+    add_metric(metrics, def)
+}
+
+/// The unified metric registered under `name` or one of its aliases
+pub fn find_metric(name: &str) -> Result<MetricDef, String> {
+    lookup_metric(name)?.ok_or_else(|| format!("Unknown similarity metric: {}", name))
+}
+
+// The distance form of the metric under `name`; score-only metrics count as unknown
+fn find_distance(name: &str) -> Result<DistanceFn, String> {
+    lookup_metric(name)?
+        .and_then(|def| def.distance_fn())
+        .ok_or_else(|| format!("Unknown distance metric: {}", name))
+}
+
+/// Every registered metric: the built-ins (unified ones in dispatch order, then the
+/// export-only similarities), then any a fork added
+pub fn registered_metrics() -> Result<Vec<MetricDef>, String> {
+    let registry = registry().read().unwrap_or_else(PoisonError::into_inner);
+    registry.clone()
+}
+
+/// Distance for the unified distance API
+pub fn unified_distance(a: &str, b: &str, metric: &str) -> Result<usize, String> {
+    let distance = find_distance(metric)?;
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    Ok(distance(&a, &b))
+}
+
+/// Normalized 0-1 score for the unified score API
+pub fn unified_score(a: &str, b: &str, metric: &str) -> Result<f64, String> {
+    Ok(find_metric(metric)?.score_str(a, b))
+}
+
+/// Options for `score_pairs` and `distance_pairs`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairOptions {
+    /// Normalization preset applied to both sides of every pair
+    pub preset: String,
+    /// Locale for the preset's case folding (see `normalize_with_locale`)
+    pub locale: Option<String>,
+    /// `score_pairs`: scores below this become 0.0. `distance_pairs`: distances above it
+    /// become `cutoff + 1` (rounded down to a whole edit count)
+    pub score_cutoff: Option<f64>,
+}
+
+impl Default for PairOptions {
+    fn default() -> Self {
+        PairOptions {
+            preset: "none".to_string(),
+            locale: None,
+            score_cutoff: None,
+        }
+    }
+}
+
+fn check_pair_lengths<A, B>(a: &[A], b: &[B]) -> Result<(), String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "Pair lists must have equal lengths (got {} and {})",
+            a.len(),
+            b.len()
+        ));
+    }
+    Ok(())
+}
+
+/// `unified_score` and `unified_distance` for one metric under `PairOptions`,
+/// for comparing many pairs. Each side is decoded into a char buffer the
+/// comparator keeps, so calls stop allocating for it once the buffers have
+/// grown to the longest input; preset "none" also skips the normalized copy.
+/// Results are exactly those of the free functions on the normalized inputs.
+#[derive(Debug, Clone)]
+pub struct Comparator {
+    metric: String,
+    def: MetricDef,
+    options: PairOptions,
+    left: Vec<char>,
+    right: Vec<char>,
+}
+
+impl Comparator {
+    /// Rejects metrics the registry doesn't know, and a cutoff for one that doesn't
+    /// support it; score-only metrics are accepted, so `distance` can still fail for them
+    pub fn new(metric: &str, options: PairOptions) -> Result<Self, String> {
+        let def = find_metric(metric)?;
+        if options.score_cutoff.is_some() && !def.supports_cutoff {
+            return Err(format!("Metric does not support score_cutoff: {}", metric));
+        }
+        Ok(Comparator {
+            metric: metric.to_string(),
+            def,
+            options,
+            left: Vec::new(),
+            right: Vec::new(),
+        })
+    }
+
+    pub fn metric(&self) -> &str {
+        &self.metric
+    }
+
+    /// `unified_score` of the normalized pair; scores below the cutoff become 0.0
+    pub fn score(&mut self, a: &str, b: &str) -> Result<f64, String> {
+        self.fill(a, b);
+        let score = self.def.score(&self.left, &self.right);
+        Ok(match self.options.score_cutoff {
+            Some(cutoff) if score < cutoff => 0.0,
+            _ => score,
+        })
+    }
+
+    /// `unified_distance` of the normalized pair; distances above the cutoff
+    /// become `cutoff + 1` (rounded down to a whole edit count)
+    pub fn distance(&mut self, a: &str, b: &str) -> Result<usize, String> {
+        let Some(distance_fn) = self.def.distance_fn() else {
+            return Err(format!("Unknown distance metric: {}", self.metric));
+        };
+        self.fill(a, b);
+        let distance = distance_fn(&self.left, &self.right);
+        let max_distance = self
+            .options
+            .score_cutoff
+            .map(|cutoff| cutoff.max(0.0).floor() as usize);
+        Ok(match max_distance {
+            Some(max) if distance > max => max + 1,
+            _ => distance,
+        })
+    }
+
+    // Decode the normalized pair into the char buffers
+    fn fill(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.normalized(a), self.normalized(b));
+        refill(&mut self.left, &a);
+        refill(&mut self.right, &b);
+    }
+
+    fn normalized<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        match self.options.preset.as_str() {
+            "none" => Cow::Borrowed(s),
+            preset => Cow::Owned(normalize_with_locale(
+                s,
+                preset,
+                self.options.locale.as_deref(),
+            )),
+        }
+    }
+}
+
+// Replace a char buffer's contents, keeping its capacity
+fn refill(buffer: &mut Vec<char>, s: &str) {
+    buffer.clear();
+    buffer.extend(s.chars());
+}
+
+/// `unified_score(a[i], b[i])` for every row of two parallel lists (zip, not cross product)
+pub fn score_pairs<S: AsRef<str>>(
+    a: &[S],
+    b: &[S],
+    metric: &str,
+    options: &PairOptions,
+) -> Result<Vec<f64>, String> {
+    score_pairs_cancellable(a, b, metric, options, &CancellationToken::new())
+}
+
+/// `score_pairs` that stops with a `cancelled_message` once `cancel` is cancelled
+pub fn score_pairs_cancellable<S: AsRef<str>>(
+    a: &[S],
+    b: &[S],
+    metric: &str,
+    options: &PairOptions,
+    cancel: &CancellationToken,
+) -> Result<Vec<f64>, String> {
+    check_pair_lengths(a, b)?;
+    let mut comparator = Comparator::new(metric, options.clone())?;
+    let rows = a.iter().zip(b).enumerate();
+    rows.map(|(i, (left, right))| {
+        cancel.check(i, a.len())?;
+        comparator.score(left.as_ref(), right.as_ref())
+    })
+    .collect()
+}
+
+/// `unified_distance(a[i], b[i])` for every row of two parallel lists (zip, not cross product)
+pub fn distance_pairs<S: AsRef<str>>(
+    a: &[S],
+    b: &[S],
+    metric: &str,
+    options: &PairOptions,
+) -> Result<Vec<usize>, String> {
+    distance_pairs_cancellable(a, b, metric, options, &CancellationToken::new())
+}
+
+/// `distance_pairs` that stops with a `cancelled_message` once `cancel` is cancelled
+pub fn distance_pairs_cancellable<S: AsRef<str>>(
+    a: &[S],
+    b: &[S],
+    metric: &str,
+    options: &PairOptions,
+    cancel: &CancellationToken,
+) -> Result<Vec<usize>, String> {
+    check_pair_lengths(a, b)?;
+    let mut comparator = Comparator::new(metric, options.clone())?;
+    let rows = a.iter().zip(b).enumerate();
+    rows.map(|(i, (left, right))| {
+        cancel.check(i, a.len())?;
+        comparator.distance(left.as_ref(), right.as_ref())
+    })
+    .collect()
+}
+
+/// `unified_score` of every query against every choice, row-major: the score of
+/// `queries[i]` against `choices[j]` is at `i * choices.len() + j`
+pub fn cdist<S: AsRef<str>>(
+    queries: &[S],
+    choices: &[S],
+    metric: &str,
+    options: &PairOptions,
+) -> Result<Vec<f64>, String> {
+    cdist_cancellable(queries, choices, metric, options, &CancellationToken::new())
+}
+
+/// `cdist` that stops with a `cancelled_message` once `cancel` is cancelled; each
+/// score counts as one row
+pub fn cdist_cancellable<S: AsRef<str>>(
+    queries: &[S],
+    choices: &[S],
+    metric: &str,
+    options: &PairOptions,
+    cancel: &CancellationToken,
+) -> Result<Vec<f64>, String> {
+    let mut comparator = Comparator::new(metric, options.clone())?;
+    let total = queries.len() * choices.len();
+    let cells = queries
+        .iter()
+        .flat_map(|query| choices.iter().map(move |choice| (query, choice)));
+    cells
+        .enumerate()
+        .map(|(i, (query, choice))| {
+            cancel.check(i, total)?;
+            comparator.score(query.as_ref(), choice.as_ref())
+        })
+        .collect()
+}
+
+/// Two items of one list that `similar_pairs` found alike
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarPair {
+    /// Index of the earlier item
+    pub left: usize,
+    pub right: usize,
+    pub score: f64,
+}
+
+/// Every pair `left < right` of `items` whose `unified_score` is at least `min_score`,
+/// ordered by `left`, then `right`
+pub fn similar_pairs<S: AsRef<str>>(
+    items: &[S],
+    metric: &str,
+    options: &PairOptions,
+    min_score: f64,
+) -> Result<Vec<SimilarPair>, String> {
+    similar_pairs_cancellable(items, metric, options, min_score, &CancellationToken::new())
+}
+
+/// `similar_pairs` that stops with a `cancelled_message` once `cancel` is cancelled;
+/// each compared pair counts as one row
+pub fn similar_pairs_cancellable<S: AsRef<str>>(
+    items: &[S],
+    metric: &str,
+    options: &PairOptions,
+    min_score: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<SimilarPair>, String> {
+    let mut comparator = Comparator::new(metric, options.clone())?;
+    let total = items.len() * items.len().saturating_sub(1) / 2;
+    let mut pairs = Vec::new();
+    let mut compared = 0;
+    for (left, a) in items.iter().enumerate() {
+        for (right, b) in items.iter().enumerate().skip(left + 1) {
+            cancel.check(compared, total)?;
+            compared += 1;
+            let score = comparator.score(a.as_ref(), b.as_ref())?;
+            if score >= min_score {
+                pairs.push(SimilarPair { left, right, score });
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+/// For each item, the index of the first item of its group, where a group is every
+/// item linked to another by a chain of `similar_pairs`; an item like no other is a
+/// group of its own
+pub fn dedupe<S: AsRef<str>>(
+    items: &[S],
+    metric: &str,
+    options: &PairOptions,
+    min_score: f64,
+) -> Result<Vec<usize>, String> {
+    dedupe_cancellable(items, metric, options, min_score, &CancellationToken::new())
+}
+
+/// `dedupe` that stops with a `cancelled_message` once `cancel` is cancelled, counting
+/// rows as `similar_pairs_cancellable` does
+pub fn dedupe_cancellable<S: AsRef<str>>(
+    items: &[S],
+    metric: &str,
+    options: &PairOptions,
+    min_score: f64,
+    cancel: &CancellationToken,
+) -> Result<Vec<usize>, String> {
+    let pairs = similar_pairs_cancellable(items, metric, options, min_score, cancel)?;
+    // Union-find where every group's root is its lowest index
+    let mut parents: Vec<usize> = (0..items.len()).collect();
+    for pair in pairs {
+        let (a, b) = (
+            group_root(&mut parents, pair.left),
+            group_root(&mut parents, pair.right),
+        );
+        parents[a.max(b)] = a.min(b);
+    }
+    Ok((0..items.len())
+        .map(|i| group_root(&mut parents, i))
+        .collect())
+}
+
+// Follow parent links up to the group's root, halving the path on the way
+fn group_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+// ============================================================================
+// CANCELLATION
+// ============================================================================
+// Batch calls poll a shared flag every CANCEL_CHECK_INTERVAL rows, so a cancel
+// from another thread (or from JS between calls) stops them promptly without
+// an atomic load per row. A cancelled call fails with a message starting with
+// CANCELLED_CODE, then how many rows were done, which bindings can parse back
+// into a structured error.
+
+/// Rows processed between checks of a `CancellationToken`
+pub const CANCEL_CHECK_INTERVAL: usize = 256;
+
+/// Stable prefix of every cancellation error message
+pub const CANCELLED_CODE: &str = "CANCELLED";
+
+/// A cancel flag shared by its clones; cancelling is permanent and idempotent
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(AtomicOrdering::Relaxed)
+    }
+
+    // Err with a cancelled_message when cancelled, polled at row 0 and every
+    // CANCEL_CHECK_INTERVAL rows after
+    fn check(&self, completed: usize, total: usize) -> Result<(), String> {
+        if completed.is_multiple_of(CANCEL_CHECK_INTERVAL) && self.is_cancelled() {
+            return Err(cancelled_message(completed, total));
+        }
+        Ok(())
+    }
+}
+
+/// "CANCELLED: cancelled after 512 of 100000 rows"
+pub fn cancelled_message(completed: usize, total: usize) -> String {
+    format!(
+        "{}: cancelled after {} of {} rows",
+        CANCELLED_CODE, completed, total
+    )
+}
+
+// ============================================================================
+// ANCHORED SIMILARITY
+// ============================================================================
+// Hard anchoring for identifiers such as SKUs: two strings only count as
+// similar when their first (or last) `length` chars agree exactly, where the
+// Winkler prefix bonus merely raises the score. An anchor longer than either
+// string covers all of the shorter one, which must then be a prefix (or
+// suffix) of the other. The anchored chars count as matched and the metric
+// scores the rest, weighted by the share of chars outside the anchor:
+//
+//     score = (2k + (m + n - 2k) * metric(rest_a, rest_b)) / (m + n)
+//
+// with k the anchored length and m, n the char lengths, so identical strings
+// score 1.0 whatever the anchor.
+
+/// Which end of the strings an anchor covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnchorSide {
+    Start,
+    End,
+}
+
+impl AnchorSide {
+    /// "start" or "end"
+    pub fn parse(side: &str) -> Result<Self, String> {
+        match side {
+            "start" => Ok(AnchorSide::Start),
+            "end" => Ok(AnchorSide::End),
+            _ => Err(format!("Unknown anchor side: {}", side)),
+        }
+    }
+}
+
+/// The `length` chars at one end of both strings, which must match exactly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Anchor {
+    pub side: AnchorSide,
+    pub length: usize,
+}
+
+// `chars` split into the anchored `k` chars and the rest
+fn split_anchor(chars: &[char], k: usize, side: AnchorSide) -> (&[char], &[char]) {
+    match side {
+        AnchorSide::Start => chars.split_at(k),
+        AnchorSide::End => {
+            let (rest, anchored) = chars.split_at(chars.len() - k);
+            (anchored, rest)
+        }
+    }
+}
+
+/// `unified_score` of `a` and `b` after `preset`, counting the anchored chars as matched;
+/// 0.0 when the anchored chars differ. Unknown metrics and presets are errors even then.
+pub fn anchored_similarity(
+    a: &str,
+    b: &str,
+    anchor: Anchor,
+    metric: &str,
+    preset: &str,
+    locale: Option<&str>,
+) -> Result<f64, String> {
+    // Resolving the metric first rejects unknown names before any early return
+    let def = find_metric(metric)?;
+    let a: Vec<char> = try_normalize_with_locale(a, preset, locale)?
+        .chars()
+        .collect();
+    let b: Vec<char> = try_normalize_with_locale(b, preset, locale)?
+        .chars()
+        .collect();
+    let k = anchor.length.min(a.len()).min(b.len());
+    let (anchored_a, rest_a) = split_anchor(&a, k, anchor.side);
+    let (anchored_b, rest_b) = split_anchor(&b, k, anchor.side);
+    if anchored_a != anchored_b {
+        return Ok(0.0);
+    }
+
+    let rest = def.score(rest_a, rest_b);
+    let total = a.len() + b.len();
+    if total == 0 {
+        return Ok(rest);
+    }
+    Ok((2 * k) as f64 / total as f64 + rest * (total - 2 * k) as f64 / total as f64)
+}
+
+// ============================================================================
+// NULLABLE FIELDS
+// ============================================================================
+// Record matching compares fields that may be missing, and a missing value is
+// not evidence of a mismatch the way a 0.0 similarity is. The status says
+// which sides had a value; the missing-value policy decides what a comparison
+// with a missing side scores. `Ignore` leaves the score out so a caller
+// weighting several fields can hand that field's weight to the others,
+// `Penalize` scores it 0.0, and `TreatAsEmpty` scores the missing side as ""
+// under the metric's empty-input behavior.
+
+/// Which sides of a nullable comparison had a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresenceStatus {
+    BothPresent,
+    OneMissing,
+    BothMissing,
+}
+
+impl PresenceStatus {
+    /// "both_present", "one_missing" or "both_missing"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PresenceStatus::BothPresent => "both_present",
+            PresenceStatus::OneMissing => "one_missing",
+            PresenceStatus::BothMissing => "both_missing",
+        }
+    }
+}
+
+/// What a comparison with at least one missing side scores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingPolicy {
+    /// No score; the field drops out of a weighted combination
+    #[default]
+    Ignore,
+    /// 0.0, as if the values disagreed completely
+    Penalize,
+    /// The metric's score with each missing side as ""
+    TreatAsEmpty,
+}
+
+impl MissingPolicy {
+    /// "ignore", "penalize" or "treat_as_empty"
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "ignore" => Ok(MissingPolicy::Ignore),
+            "penalize" => Ok(MissingPolicy::Penalize),
+            "treat_as_empty" => Ok(MissingPolicy::TreatAsEmpty),
+            _ => Err(format!("Unknown missing-value policy: {}", name)),
+        }
+    }
+}
+
+/// A nullable comparison's score (None when the policy ignores a missing side) and status
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NullableScore {
+    pub score: Option<f64>,
+    pub status: PresenceStatus,
+}
+
+/// `unified_score` of two optional values after the options' preset, with the score of a
+/// pair missing a side decided by `missing`. Unknown metrics and presets are errors even
+/// when neither side is present; a score cutoff applies to present pairs only.
+pub fn score_nullable(
+    a: Option<&str>,
+    b: Option<&str>,
+    metric: &str,
+    options: &PairOptions,
+    missing: MissingPolicy,
+) -> Result<NullableScore, String> {
+    let mut comparator = Comparator::new(metric, options.clone())?;
+    // The comparator normalizes leniently; check the preset before any early return
+    try_normalize_with_locale("", &options.preset, options.locale.as_deref())?;
+    let status = match (a, b) {
+        (Some(_), Some(_)) => PresenceStatus::BothPresent,
+        (None, None) => PresenceStatus::BothMissing,
+        _ => PresenceStatus::OneMissing,
+    };
+    let score = match (status, missing) {
+        (PresenceStatus::BothPresent, _) | (_, MissingPolicy::TreatAsEmpty) => {
+            Some(comparator.score(a.unwrap_or(""), b.unwrap_or(""))?)
+        }
+        (_, MissingPolicy::Penalize) => Some(0.0),
+        (_, MissingPolicy::Ignore) => None,
+    };
+    Ok(NullableScore { score, status })
+}
+
+// ============================================================================
+// LISTED METRICS
+// ============================================================================
+// The metric registry as similarity-validator sees it: every registered metric,
+// scored as its WASM export reports it when it has one. The validator's symmetry
+// and unit_interval properties and its coverage gate read this list, so a metric
+// registered in BUILT_IN_METRICS (or by a fork) is checked without further wiring.
+
+/// Range a similarity is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreScale {
+    /// 0.0-1.0
+    Unit,
+    /// 0-100 (`ratio`)
+    Percent,
+}
+
+impl ScoreScale {
+    pub fn max(self) -> f64 {
+        match self {
+            ScoreScale::Unit => 1.0,
+            ScoreScale::Percent => 100.0,
+        }
+    }
+}
+
+/// A registered metric and the invariants it promises
+#[derive(Debug, Clone, Copy)]
+pub struct MetricInfo {
+    /// The registry name
+    pub name: &'static str,
+    /// The WASM export reporting it, if any
+    pub export: Option<&'static str>,
+    pub scale: ScoreScale,
+    /// `score(a, b) == score(b, a)`
+    pub symmetric: bool,
+    def: MetricDef,
+}
+
+impl MetricInfo {
+    /// The score on `scale`, as the export reports it
+    pub fn score(&self, a: &str, b: &str) -> f64 {
+        self.def.score_str(a, b) * self.scale.max()
+    }
+}
+
+impl From<MetricDef> for MetricInfo {
+    fn from(def: MetricDef) -> Self {
+        MetricInfo {
+            name: def.name,
+            export: def.export,
+            scale: def.scale,
+            symmetric: def.symmetric,
+            def,
+        }
+    }
+}
+
+/// Every registered metric in registration order; a registry that failed to build lists none
+pub fn list_metrics() -> Vec<MetricInfo> {
+    registered_metrics()
+        .unwrap_or_default()
+        .into_iter()
+        .map(MetricInfo::from)
+        .collect()
+}
+
+// ============================================================================
+// EDIT OPERATIONS
+// ============================================================================
+// Alignments come from a Wagner-Fischer matrix walked back from the end. Ties
+// between optimal alignments break toward equal, then (transpose,) replace,
+// delete and insert; fixtures pin that order, so changing it is a breaking change.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditTag {
+    Equal,
+    Replace,
+    Insert,
+    Delete,
+    /// Adjacent transposition (OSA only), spanning two characters on each side
+    Transpose,
+}
+
+impl EditTag {
+    /// Lowercase name used in fixtures and explain output
+    pub fn name(self) -> &'static str {
+        match self {
+            EditTag::Equal => "equal",
+            EditTag::Replace => "replace",
+            EditTag::Insert => "insert",
+            EditTag::Delete => "delete",
+            EditTag::Transpose => "transpose",
+        }
+    }
+}
+
+/// One alignment step; `src_pos`/`dest_pos` index chars of a and b
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditStep {
+    pub tag: EditTag,
+    pub src_pos: usize,
+    pub dest_pos: usize,
+}
+
+/// A run of steps with the same tag, as half-open char ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opcode {
+    pub tag: EditTag,
+    pub src_start: usize,
+    pub src_end: usize,
+    pub dest_start: usize,
+    pub dest_end: usize,
+}
+
+/// Full alignment of a onto b (including equal steps) with minimal edit cost
+pub fn alignment_steps(a: &[char], b: &[char], transpositions: bool) -> Vec<EditStep> {
+    let (n, m) = (a.len(), b.len());
+    let mut d = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    let is_transposition =
+        |i: usize, j: usize| i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1];
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if transpositions && cost == 1 && is_transposition(i, j) {
+                best = best.min(d[i - 2][j - 2] + 1);
+            }
+            d[i][j] = best;
+        }
+    }
+
+    let mut steps = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let step = if i > 0 && j > 0 && a[i - 1] == b[j - 1] && d[i][j] == d[i - 1][j - 1] {
+            (EditTag::Equal, 1, 1)
+        } else if transpositions
+            && is_transposition(i, j)
+            && a[i - 1] != b[j - 1]
+            && d[i][j] == d[i - 2][j - 2] + 1
+        {
+            (EditTag::Transpose, 2, 2)
+        } else if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + 1 {
+            (EditTag::Replace, 1, 1)
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            (EditTag::Delete, 1, 0)
+        } else {
+            (EditTag::Insert, 0, 1)
+        };
+        let (tag, di, dj) = step;
+        i -= di;
+        j -= dj;
+        steps.push(EditStep {
+            tag,
+            src_pos: i,
+            dest_pos: j,
+        });
+    }
+    steps.reverse();
+    steps
+}
+
+/// Merge consecutive steps with the same tag (transpositions stay separate)
+pub fn opcodes(steps: &[EditStep]) -> Vec<Opcode> {
+    let mut opcodes: Vec<Opcode> = Vec::new();
+    for step in steps {
+        let (src_len, dest_len) = match step.tag {
+            EditTag::Equal | EditTag::Replace => (1, 1),
+            EditTag::Insert => (0, 1),
+            EditTag::Delete => (1, 0),
+            EditTag::Transpose => (2, 2),
+        };
+        match opcodes.last_mut() {
+            Some(last) if last.tag == step.tag && step.tag != EditTag::Transpose => {
+                last.src_end += src_len;
+                last.dest_end += dest_len;
+            }
+            _ => opcodes.push(Opcode {
+                tag: step.tag,
+                src_start: step.src_pos,
+                src_end: step.src_pos + src_len,
+                dest_start: step.dest_pos,
+                dest_end: step.dest_pos + dest_len,
+            }),
+        }
+    }
+    opcodes
+}
+
+/// Levenshtein edit operations (no equal steps), positions in chars
+pub fn levenshtein_editops(a: &str, b: &str) -> Vec<EditStep> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    alignment_steps(&a, &b, false)
+        .into_iter()
+        .filter(|step| step.tag != EditTag::Equal)
+        .collect()
+}
+
+/// Levenshtein alignment as opcodes covering both strings, positions in chars
+pub fn levenshtein_opcodes(a: &str, b: &str) -> Vec<Opcode> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    opcodes(&alignment_steps(&a, &b, false))
+}
+
+// ============================================================================
+// MATCH PROFILE
+// ============================================================================
+// Per-char shading for review UIs. Both strings are cut into units, a char
+// plus the combining marks after it, and each unit is normalized on its own
+// (the preset without trimming), so every normalized char knows the original
+// unit it came from. Trimming then drops leading and trailing whitespace of
+// the normalized candidate. The normalized query and candidate are aligned as
+// in `levenshtein_opcodes`, and every candidate unit takes one class from the
+// steps of its normalized chars, shared by all of its chars:
+// - all equal: `Match` when the unit is identical to the single query unit
+//   whose normalized chars it matched, `FoldMatch` otherwise
+// - all inserted: `Inserted`
+// - anything else (a replacement, or equal and inserted chars mixed): `Substituted`
+// - none left after normalization (ignorables, trimmed whitespace, punctuation
+//   under "aggressive"): `FoldMatch`, since only normalization tells them apart
+
+/// How one candidate char matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchClass {
+    /// Equal to the query char it aligned with, before normalization too
+    Match,
+    /// Equal only after normalization, or removed by it
+    FoldMatch,
+    Substituted,
+    /// No query char opposite it
+    Inserted,
+}
+
+impl MatchClass {
+    /// Snake_case name used in fixtures and the TypeScript API
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchClass::Match => "match",
+            MatchClass::FoldMatch => "fold_match",
+            MatchClass::Substituted => "substituted",
+            MatchClass::Inserted => "inserted",
+        }
+    }
+}
+
+// A char plus the combining marks after it
+fn combining_units(s: &str) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if i > start && unicode_normalization::char::canonical_combining_class(c) == 0 {
+            units.push(&s[start..i]);
+            start = i;
+        }
+    }
+    if start < s.len() {
+        units.push(&s[start..]);
+    }
+    units
+}
+
+// One unit under `preset`, without the trimming that only applies to the whole string
+fn normalize_unit(unit: &str, preset: &str, options: &NormalizeOptions) -> Vec<char> {
+    let keeps = options.keeps(preset);
+    match preset {
+        "minimal" => unit.chars().filter(keeps).nfc().collect(),
+        "default" => fold_chars(unit, options.locale)
+            .filter(keeps)
+            .nfc()
+            .collect(),
+        "aggressive" => aggressive_chars(unit, options).collect(),
+        _ => unit.chars().collect(),
+    }
+}
+
+// Normalized chars, each with the index of the unit it came from, trimmed
+fn normalized_units(
+    units: &[&str],
+    preset: &str,
+    options: &NormalizeOptions,
+) -> (Vec<char>, Vec<usize>) {
+    let mut chars = Vec::new();
+    let mut owners = Vec::new();
+    for (k, unit) in units.iter().enumerate() {
+        for c in normalize_unit(unit, preset, options) {
+            chars.push(c);
+            owners.push(k);
+        }
+    }
+    if preset != "none" {
+        let end = chars.len() - chars.iter().rev().take_while(|c| c.is_whitespace()).count();
+        let start = chars[..end]
+            .iter()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        chars = chars[start..end].to_vec();
+        owners = owners[start..end].to_vec();
+    }
+    (chars, owners)
+}
+
+/// One `MatchClass` per char of `candidate`, from the Levenshtein alignment of
+/// the query and candidate normalized with `preset`; unknown presets are an error
+pub fn match_profile(
+    query: &str,
+    candidate: &str,
+    preset: &str,
+    locale: Option<&str>,
+) -> Result<Vec<MatchClass>, String> {
+    if !matches!(preset, "none" | "minimal" | "default" | "aggressive") {
+        return Err(format!("Unknown normalization preset: {}", preset));
+    }
+    let options = NormalizeOptions::with_locale(locale);
+    let (query_units, candidate_units) = (combining_units(query), combining_units(candidate));
+    let (query_chars, query_owners) = normalized_units(&query_units, preset, &options);
+    let (candidate_chars, candidate_owners) = normalized_units(&candidate_units, preset, &options);
+
+    // Per candidate unit: the steps of its normalized chars, and the query units they matched
+    let mut steps: Vec<Vec<EditTag>> = vec![Vec::new(); candidate_units.len()];
+    let mut matched: Vec<Vec<usize>> = vec![Vec::new(); candidate_units.len()];
+    for step in alignment_steps(&query_chars, &candidate_chars, false) {
+        if step.tag == EditTag::Delete {
+            continue;
+        }
+        let unit = candidate_owners[step.dest_pos];
+        steps[unit].push(step.tag);
+        if step.tag == EditTag::Equal {
+            matched[unit].push(query_owners[step.src_pos]);
+        }
+    }
+
+    let classes = candidate_units.iter().enumerate().map(|(k, unit)| {
+        let tags = &steps[k];
+        let class = if tags.is_empty() {
+            MatchClass::FoldMatch
+        } else if tags.iter().all(|&tag| tag == EditTag::Equal) {
+            let query_unit = matched[k][0];
+            // The whole query unit must have gone to this one candidate unit
+            let whole = matched[k].iter().all(|&q| q == query_unit)
+                && query_owners.iter().filter(|&&q| q == query_unit).count() == matched[k].len();
+            if whole && query_units[query_unit] == *unit {
+                MatchClass::Match
+            } else {
+                MatchClass::FoldMatch
+            }
+        } else if tags.iter().all(|&tag| tag == EditTag::Insert) {
+            MatchClass::Inserted
+        } else {
+            MatchClass::Substituted
+        };
+        (unit.chars().count(), class)
+    });
+    Ok(classes
+        .flat_map(|(count, class)| std::iter::repeat_n(class, count))
+        .collect())
+}
+
+// ============================================================================
+// CASE TRANSFER
+// ============================================================================
+// Presenting a correction in the casing the user typed: "LEVENSHTIEN" gives
+// "LEVENSHTEIN" and "Levenshtien" gives "Levenshtein". A pattern that is all
+// lower, all upper or title case has that style applied to the whole target,
+// whatever the two lengths. Any other pattern is copied char by char along the
+// Levenshtein alignment of the lowercased strings; a target char with no
+// pattern char opposite it takes the case of the last cased pattern char
+// before it (or of the first, at the start). Uncased chars never change.
+//
+// A word starts after whitespace, '-' or '_', and title case means only the
+// first cased char of each word is uppercase. Uppercasing uses the full Unicode
+// mapping, so ß becomes "SS"; Turkish and Azerbaijani use TURKIC_CASE_PAIRS.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaseStyle {
+    Lower,
+    Upper,
+    Title,
+    Mixed,
+}
+
+fn is_cased(c: char) -> bool {
+    c.is_uppercase() || c.is_lowercase()
+}
+
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() || c == '-' || c == '_'
+}
+
+// The style of a string's cased chars; None when it has none
+fn case_style(s: &str) -> Option<CaseStyle> {
+    let mut chars = s.chars().filter(|&c| is_cased(c)).peekable();
+    chars.peek()?;
+    let (mut lower, mut upper) = (true, true);
+    for c in chars {
+        lower &= c.is_lowercase();
+        upper &= c.is_uppercase();
+    }
+    Some(if lower {
+        CaseStyle::Lower
+    } else if is_title_case(s) {
+        CaseStyle::Title
+    } else if upper {
+        CaseStyle::Upper
+    } else {
+        CaseStyle::Mixed
+    })
+}
+
+fn is_title_case(s: &str) -> bool {
+    let mut word_start = true;
+    for c in s.chars() {
+        if is_word_separator(c) {
+            word_start = true;
+        } else if is_cased(c) {
+            if c.is_uppercase() != word_start {
+                return false;
+            }
+            word_start = false;
+        }
+    }
+    true
+}
+
+fn push_with_case(out: &mut String, c: char, upper: bool, turkic: bool) {
+    let turkic_mapped = match (turkic, upper) {
+        (true, true) => turkic_uppercase(c),
+        (true, false) => turkic_lowercase(c),
+        (false, _) => None,
+    };
+    match turkic_mapped {
+        Some(mapped) => out.push(mapped),
+        None if upper => out.extend(c.to_uppercase()),
+        None => out.extend(c.to_lowercase()),
+    }
+}
+
+// One char per char, for aligning strings that differ only in case
+fn lowercase_char(c: char, turkic: bool) -> char {
+    let turkic_mapped = if turkic { turkic_lowercase(c) } else { None };
+    turkic_mapped.unwrap_or_else(|| {
+        let mut lower = c.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(single), None) => single,
+            _ => c,
+        }
+    })
+}
+
+/// `target` written in the casing of `pattern` (see CASE TRANSFER above);
+/// `locale` "tr" or "az" maps i ↔ İ and ı ↔ I
+pub fn transfer_case(pattern: &str, target: &str, locale: Option<&str>) -> String {
+    let turkic = is_turkic(locale);
+    let mut out = String::with_capacity(target.len());
+    match case_style(pattern) {
+        None => out.push_str(target),
+        Some(style @ (CaseStyle::Lower | CaseStyle::Upper)) => {
+            for c in target.chars() {
+                push_with_case(&mut out, c, style == CaseStyle::Upper, turkic);
+            }
+        }
+        Some(CaseStyle::Title) => {
+            let mut word_start = true;
+            for c in target.chars() {
+                if is_word_separator(c) {
+                    word_start = true;
+                    out.push(c);
+                } else if is_cased(c) {
+                    push_with_case(&mut out, c, word_start, turkic);
+                    word_start = false;
+                } else {
+                    out.push(c);
+                }
+            }
+        }
+        Some(CaseStyle::Mixed) => transfer_case_aligned(&mut out, pattern, target, turkic),
+    }
+    out
+}
+
+fn transfer_case_aligned(out: &mut String, pattern: &str, target: &str, turkic: bool) {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let target: Vec<char> = target.chars().collect();
+    let lower = |chars: &[char]| -> Vec<char> {
+        chars.iter().map(|&c| lowercase_char(c, turkic)).collect()
+    };
+    let mut upper = pattern
+        .iter()
+        .find(|&&c| is_cased(c))
+        .is_some_and(|c| c.is_uppercase());
+    for step in alignment_steps(&lower(&pattern), &lower(&target), false) {
+        if step.tag != EditTag::Insert {
+            let p = pattern[step.src_pos];
+            if is_cased(p) {
+                upper = p.is_uppercase();
+            }
+        }
+        if step.tag != EditTag::Delete {
+            push_with_case(out, target[step.dest_pos], upper, turkic);
+        }
+    }
+}
+
+// ============================================================================
+// WORD DIFF
+// ============================================================================
+// Word-level change tracking between two revisions of a sentence. Each token
+// carries the whitespace after it, and whitespace before the first token
+// becomes a token with empty text, so the tokens of a string always
+// concatenate back to it. Tokens align by the heaviest common subsequence:
+// equal text weighs 1, and unequal text scoring at least the threshold under
+// the chosen unified metric weighs its score, reported as a replacement with
+// that score instead of a delete and an insert. Ties prefer matching over
+// skipping, then skipping a token of a over skipping one of b.
+
+/// How `word_tokens` splits text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tokenizer {
+    /// Maximal runs of non-whitespace
+    #[default]
+    Whitespace,
+    /// Unicode word boundaries (UAX #29): words, numbers and each punctuation mark
+    Words,
+}
+
+impl Tokenizer {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "whitespace" => Ok(Tokenizer::Whitespace),
+            "words" => Ok(Tokenizer::Words),
+            _ => Err(format!("Unknown tokenizer: {}", name)),
+        }
+    }
+}
+
+/// A token and the whitespace that follows it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordToken<'a> {
+    pub text: &'a str,
+    pub whitespace: &'a str,
+}
+
+/// Tokens of `s`; joining every `text` and `whitespace` in order gives `s` back
+pub fn word_tokens(s: &str, tokenizer: Tokenizer) -> Vec<WordToken<'_>> {
+    let is_space = |segment: &str| segment.chars().all(char::is_whitespace);
+    // Byte ranges of the non-whitespace tokens
+    let spans: Vec<(usize, usize)> = match tokenizer {
+        Tokenizer::Whitespace => {
+            let mut spans = Vec::new();
+            let mut start = None;
+            for (i, c) in s.char_indices() {
+                match (c.is_whitespace(), start) {
+                    (true, Some(begin)) => {
+                        spans.push((begin, i));
+                        start = None;
+                    }
+                    (false, None) => start = Some(i),
+                    _ => {}
+                }
+            }
+            spans.extend(start.map(|begin| (begin, s.len())));
+            spans
+        }
+        Tokenizer::Words => s
+            .split_word_bound_indices()
+            .filter(|(_, segment)| !is_space(segment))
+            .map(|(i, segment)| (i, i + segment.len()))
+            .collect(),
+    };
+
+    let mut tokens = Vec::with_capacity(spans.len() + 1);
+    let first_start = spans.first().map_or(s.len(), |&(start, _)| start);
+    if first_start > 0 {
+        tokens.push(WordToken {
+            text: "",
+            whitespace: &s[..first_start],
+        });
+    }
+    for (k, &(start, end)) in spans.iter().enumerate() {
+        let next = spans.get(k + 1).map_or(s.len(), |&(next, _)| next);
+        tokens.push(WordToken {
+            text: &s[start..end],
+            whitespace: &s[end..next],
+        });
+    }
+    tokens
+}
+
+/// Options for `word_diff`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordDiffOptions {
+    pub tokenizer: Tokenizer,
+    /// Unified metric scoring two unequal tokens
+    pub metric: String,
+    /// Lowest score at which unequal tokens align as a replacement; above 1 disables it
+    pub threshold: f64,
+}
+
+impl Default for WordDiffOptions {
+    fn default() -> Self {
+        WordDiffOptions {
+            tokenizer: Tokenizer::Whitespace,
+            metric: "levenshtein".to_string(),
+            threshold: 0.6,
+        }
+    }
+}
+
+/// One run of a word diff, as half-open token index ranges into `word_tokens` of
+/// each side. Equal runs may span several tokens; a replacement is one fuzzily
+/// matched pair with its `score`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WordDiffOp {
+    pub tag: EditTag,
+    pub a_start: usize,
+    pub a_end: usize,
+    pub b_start: usize,
+    pub b_end: usize,
+    pub score: Option<f64>,
+}
+
+/// Word-level diff of a into b (see WORD DIFF above). Within a gap between
+/// matches, the deleted run comes before the inserted one.
+pub fn word_diff(a: &str, b: &str, options: &WordDiffOptions) -> Result<Vec<WordDiffOp>, String> {
+    // Resolving the metric first rejects unknown names before any work
+    let def = find_metric(&options.metric)?;
+    let a_tokens = word_tokens(a, options.tokenizer);
+    let b_tokens = word_tokens(b, options.tokenizer);
+    let (n, m) = (a_tokens.len(), b_tokens.len());
+    let decode = |tokens: &[WordToken]| -> Vec<Vec<char>> {
+        tokens
+            .iter()
+            .map(|token| token.text.chars().collect())
+            .collect()
+    };
+    let (a_chars, b_chars) = (decode(&a_tokens), decode(&b_tokens));
+
+    // Weight of aligning a[i] with b[j]: 1 when equal, the score when close enough
+    let mut weights = vec![vec![None; m]; n];
+    for (i, left) in a_chars.iter().enumerate() {
+        for (j, right) in b_chars.iter().enumerate() {
+            weights[i][j] = if left == right {
+                Some(1.0)
+            } else {
+                let score = def.score(left, right);
+                (score >= options.threshold).then_some(score)
+            };
+        }
+    }
+
+    // best[i][j]: heaviest alignment of the suffixes a[i..] and b[j..]
+    let mut best = vec![vec![0.0f64; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            let matched = weights[i][j].map_or(f64::NEG_INFINITY, |w| w + best[i + 1][j + 1]);
+            best[i][j] = matched.max(best[i + 1][j]).max(best[i][j + 1]);
+        }
+    }
+
+    let mut ops: Vec<WordDiffOp> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut gap = (0, 0);
+    while i < n || j < m {
+        let weight = if i < n && j < m { weights[i][j] } else { None };
+        match weight {
+            Some(w) if w + best[i + 1][j + 1] >= best[i][j] => {
+                push_gap(&mut ops, gap, (i, j));
+                let equal = a_tokens[i].text == b_tokens[j].text;
+                match ops.last_mut() {
+                    Some(last)
+                        if equal
+                            && last.tag == EditTag::Equal
+                            && last.a_end == i
+                            && last.b_end == j =>
+                    {
+                        last.a_end += 1;
+                        last.b_end += 1;
+                    }
+                    _ => ops.push(WordDiffOp {
+                        tag: if equal {
+                            EditTag::Equal
+                        } else {
+                            EditTag::Replace
+                        },
+                        a_start: i,
+                        a_end: i + 1,
+                        b_start: j,
+                        b_end: j + 1,
+                        score: (!equal).then_some(w),
+                    }),
+                }
+                i += 1;
+                j += 1;
+                gap = (i, j);
+            }
+            _ if i < n && (j == m || best[i + 1][j] >= best[i][j + 1]) => i += 1,
+            _ => j += 1,
+        }
+    }
+    push_gap(&mut ops, gap, (n, m));
+    Ok(ops)
+}
+
+// The unmatched tokens between two matches: a delete of a's, then an insert of b's
+fn push_gap(ops: &mut Vec<WordDiffOp>, start: (usize, usize), end: (usize, usize)) {
+    if start.0 < end.0 {
+        ops.push(WordDiffOp {
+            tag: EditTag::Delete,
+            a_start: start.0,
+            a_end: end.0,
+            b_start: start.1,
+            b_end: start.1,
+            score: None,
+        });
+    }
+    if start.1 < end.1 {
+        ops.push(WordDiffOp {
+            tag: EditTag::Insert,
+            a_start: end.0,
+            a_end: end.0,
+            b_start: start.1,
+            b_end: end.1,
+            score: None,
+        });
+    }
+}
+
+// ============================================================================
+// LCS ALIGNMENT
+// ============================================================================
+// One longest common subsequence, for highlighting which characters matched.
+// The walk runs forward over a suffix-LCS table, taking a match whenever the
+// chars agree and otherwise skipping a char of a before one of b, so matches
+// land as early in b as possible (leftmost-greedy). Fixtures and callers may
+// rely on that choice.
+
+/// Char offsets of each position in `chars` (plus the end) in the given unit
+fn unit_offsets(chars: &[char], unit: RangeUnit) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(chars.len() + 1);
+    let mut offset = 0;
+    offsets.push(offset);
+    for c in chars {
+        offset += match unit {
+            RangeUnit::Char => 1,
+            RangeUnit::Utf16 => c.len_utf16(),
+        };
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// `(i, j)` char index pairs of one longest common subsequence, increasing in both
+fn lcs_pairs(a: &[char], b: &[char]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // suffix[i][j] = LCS length of a[i..] and b[j..]
+    let mut suffix = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            suffix[i][j] = if a[i] == b[j] {
+                suffix[i + 1][j + 1] + 1
+            } else {
+                suffix[i + 1][j].max(suffix[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(suffix[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if suffix[i + 1][j] >= suffix[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Index pairs `(i, j)` with `a[i] == b[j]` forming one longest common subsequence;
+/// its length equals the LCS similarity. Indices count chars or UTF-16 code units.
+pub fn lcs_seq_matching_indices(a: &str, b: &str, unit: RangeUnit) -> Vec<(usize, usize)> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (a_offsets, b_offsets) = (unit_offsets(&a, unit), unit_offsets(&b, unit));
+    lcs_pairs(&a, &b)
+        .into_iter()
+        .map(|(i, j)| (a_offsets[i], b_offsets[j]))
+        .collect()
+}
+
+/// Indel edit operations (deletes and inserts only) around the LCS of
+/// `lcs_seq_matching_indices`; between two matches, deletes come first.
+/// Positions count chars or UTF-16 code units.
+pub fn indel_editops(a: &str, b: &str, unit: RangeUnit) -> Vec<EditStep> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (a_offsets, b_offsets) = (unit_offsets(&a, unit), unit_offsets(&b, unit));
+
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let matches = lcs_pairs(&a, &b).into_iter().chain([(a.len(), b.len())]);
+    for (next_i, next_j) in matches {
+        for &src_pos in &a_offsets[i..next_i] {
+            steps.push(EditStep {
+                tag: EditTag::Delete,
+                src_pos,
+                dest_pos: b_offsets[j],
+            });
+        }
+        for &dest_pos in &b_offsets[j..next_j] {
+            steps.push(EditStep {
+                tag: EditTag::Insert,
+                src_pos: a_offsets[next_i],
+                dest_pos,
+            });
+        }
+        (i, j) = (next_i + 1, next_j + 1);
+    }
+    steps
+}
+
+// ============================================================================
+// SUBSTRING SIMILARITY (Longest Common Substring)
+// ============================================================================
+
+/// Unit in which substring ranges are counted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum RangeUnit {
+    /// Unicode scalar values (Rust `char`s)
+    #[default]
+    Char,
+    /// UTF-16 code units (JavaScript string indices)
+    Utf16,
+}
+
+/// Which of several equally long common substrings supplies the range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TiePolicy {
+    /// Earliest occurrence in the haystack
+    First,
+    /// Latest occurrence in the haystack
+    Last,
+}
+
+/// Substring options; the defaults keep the historical behavior
+/// (char ranges, first maximum found scanning the needle left to right)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubstringOptions {
+    pub range_unit: RangeUnit,
+    pub tie_policy: Option<TiePolicy>,
+}
+
+/// Half-open range of the match in the haystack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Score of a pair sharing no substring: 1.0 for two empty strings, else 0.0
+fn unmatched_score(needle_len: usize, haystack_len: usize) -> f64 {
+    if needle_len == 0 && haystack_len == 0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Length of the longest common substring and the haystack end (in chars) of each of its
+/// occurrences, in the order a scan over the needle first reaches them; no ends when nothing
+/// matches. Every tie policy picks from this one list.
+fn longest_common_substrings(needle: &[char], haystack: &[char]) -> (usize, Vec<usize>) {
+    let n = haystack.len();
+
+    // Rolling DP rows for longest common substring
+    let mut previous = vec![0usize; n + 1];
+    let mut current = vec![0usize; n + 1];
+    let mut max_len = 0;
+    let mut ends = Vec::new();
+    let mut listed = vec![false; n + 1];
+
+    for &c in needle {
+        for j in 1..=n {
+            current[j] = if c == haystack[j - 1] {
+                previous[j - 1] + 1
+            } else {
+                0
+            };
+            if current[j] > max_len {
+                max_len = current[j];
+                for end in ends.drain(..) {
+                    listed[end] = false;
+                }
+            }
+            // Equal lengths share a start iff they share an end, so ends identify occurrences
+            if max_len > 0 && current[j] == max_len && !listed[j] {
+                listed[j] = true;
+                ends.push(j);
+            }
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    (max_len, ends)
+}
+
+/// Which occurrence's haystack end a tie policy reports
+fn tied_end(ends: &[usize], tie_policy: Option<TiePolicy>) -> usize {
+    match tie_policy {
+        // The first found scanning the needle left to right
+        None => ends[0],
+        Some(TiePolicy::First) => *ends.iter().min().expect("a match has an end"),
+        Some(TiePolicy::Last) => *ends.iter().max().expect("a match has an end"),
+    }
+}
+
+/// The haystack chars `start..end` as a range in the given unit
+fn haystack_range(haystack: &[char], start: usize, end: usize, unit: RangeUnit) -> MatchRange {
+    match unit {
+        RangeUnit::Char => MatchRange { start, end },
+        RangeUnit::Utf16 => {
+            let offset = |chars: &[char]| chars.iter().map(|c| c.len_utf16()).sum();
+            MatchRange {
+                start: offset(&haystack[..start]),
+                end: offset(&haystack[..end]),
+            }
+        }
+    }
+}
+
+/// Longest-common-substring similarity: `(2 * lcs_length) / (needle_length + haystack_length)`.
+/// Returns the score and the match's range in the haystack (None when nothing matches, so
+/// also for two empty strings, which score 1.0).
+pub fn substring_similarity(
+    needle: &str,
+    haystack: &str,
+    options: SubstringOptions,
+) -> (f64, Option<MatchRange>) {
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    let m = needle_chars.len();
+    let n = haystack_chars.len();
+
+    if m == 0 || n == 0 {
+        return (unmatched_score(m, n), None);
+    }
+
+    let (max_len, ends) = longest_common_substrings(&needle_chars, &haystack_chars);
+    if max_len == 0 {
+        return (0.0, None);
+    }
+
+    let score = (2.0 * max_len as f64) / (m + n) as f64;
+    let end_in_haystack = tied_end(&ends, options.tie_policy);
+    let range = haystack_range(
+        &haystack_chars,
+        end_in_haystack - max_len,
+        end_in_haystack,
+        options.range_unit,
+    );
+    (score, Some(range))
+}
+
+/// `substring_similarity`'s score with every haystack range the longest common substring
+/// occupies, sorted by start; `tie_policy` First and Last pick the first and last of these
+pub fn substring_all_ranges(
+    needle: &str,
+    haystack: &str,
+    unit: RangeUnit,
+) -> (f64, Vec<MatchRange>) {
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    let (max_len, mut ends) = longest_common_substrings(&needle_chars, &haystack_chars);
+    if max_len == 0 {
+        return (
+            unmatched_score(needle_chars.len(), haystack_chars.len()),
+            Vec::new(),
+        );
+    }
+
+    ends.sort_unstable();
+    let score = (2.0 * max_len as f64) / (needle_chars.len() + haystack_chars.len()) as f64;
+    let ranges = ends
+        .into_iter()
+        .map(|end| haystack_range(&haystack_chars, end - max_len, end, unit))
+        .collect();
+    (score, ranges)
+}
+
+// ============================================================================
+// COVERAGE
+// ============================================================================
+// How much of a short needle a long haystack contains: the matched length over
+// the needle length only, so a query found verbatim in a long title scores 1.0
+// where substring_similarity's m + n denominator would punish the title. Not
+// symmetric by design. An empty needle scores 0.0 against a non-empty haystack
+// and 1.0 against an empty one, like substring_similarity.
+
+/// Longest common substring length over the needle length, with the substring's range in
+/// the haystack (None when nothing matches)
+pub fn coverage_score(
+    needle: &str,
+    haystack: &str,
+    options: SubstringOptions,
+) -> (f64, Option<MatchRange>) {
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    let (max_len, ends) = longest_common_substrings(&needle_chars, &haystack_chars);
+    if max_len == 0 {
+        return (
+            unmatched_score(needle_chars.len(), haystack_chars.len()),
+            None,
+        );
+    }
+
+    let score = max_len as f64 / needle_chars.len() as f64;
+    let end_in_haystack = tied_end(&ends, options.tie_policy);
+    let range = haystack_range(
+        &haystack_chars,
+        end_in_haystack - max_len,
+        end_in_haystack,
+        options.range_unit,
+    );
+    (score, Some(range))
+}
+
+/// `(i, j)` pairs of the longest common subsequence that splits into the fewest runs (stretches
+/// consecutive in both strings), matching as early in b as ties allow. Scores compare as
+/// `(length, -runs)`, so one DP over prefixes tracks both; `ending` holds the best alignment
+/// whose last pair is `(i - 1, j - 1)`, which a match can extend without opening a run.
+fn lcs_pairs_fewest_runs(a: &[char], b: &[char]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut best = vec![vec![(0usize, 0isize); m + 1]; n + 1];
+    let mut ending: Vec<Vec<Option<(usize, isize)>>> = vec![vec![None; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            if a[i - 1] == b[j - 1] {
+                let (len, runs) = best[i - 1][j - 1];
+                let opened = (len + 1, runs - 1);
+                ending[i][j] = Some(match ending[i - 1][j - 1] {
+                    Some((len, runs)) if (len + 1, runs) >= opened => (len + 1, runs),
+                    _ => opened,
+                });
+            }
+            let skipped = best[i - 1][j].max(best[i][j - 1]);
+            best[i][j] = ending[i][j].map_or(skipped, |ended| ended.max(skipped));
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(best[n][m].0);
+    let (mut i, mut j) = (n, m);
+    let mut in_run = false;
+    while i > 0 && j > 0 {
+        if in_run {
+            pairs.push((i - 1, j - 1));
+            let extended = ending[i - 1][j - 1].map(|(len, runs)| (len + 1, runs));
+            in_run = extended == ending[i][j];
+            i -= 1;
+            j -= 1;
+        } else if best[i][j] == best[i][j - 1] {
+            // Skipping trailing haystack chars first keeps the matches early
+            j -= 1;
+        } else if best[i][j] == best[i - 1][j] {
+            i -= 1;
+        } else {
+            in_run = true;
+        }
+    }
+    pairs.reverse();
+    pairs
+}
+
+/// Gap-tolerant coverage: longest common subsequence length over the needle length, with the
+/// haystack runs the subsequence matched. Of the longest subsequences, the one in the fewest
+/// runs supplies the ranges, so a verbatim occurrence comes back as one range.
+pub fn subsequence_coverage_score(
+    needle: &str,
+    haystack: &str,
+    unit: RangeUnit,
+) -> (f64, Vec<MatchRange>) {
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let haystack_chars: Vec<char> = haystack.chars().collect();
+    let pairs = lcs_pairs_fewest_runs(&needle_chars, &haystack_chars);
+    if pairs.is_empty() {
+        return (
+            unmatched_score(needle_chars.len(), haystack_chars.len()),
+            Vec::new(),
+        );
+    }
+
+    // Runs of consecutive haystack positions
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (_, j) in &pairs {
+        match runs.last_mut() {
+            Some((_, end)) if *end == *j => *end += 1,
+            _ => runs.push((*j, j + 1)),
+        }
+    }
+    let score = pairs.len() as f64 / needle_chars.len() as f64;
+    let ranges = runs
+        .into_iter()
+        .map(|(start, end)| haystack_range(&haystack_chars, start, end, unit))
+        .collect();
+    (score, ranges)
+}
+
+// ============================================================================
+// EXCERPTS
+// ============================================================================
+// Long candidates shown around their matched range ("…text [match] more
+// text…"). Cuts fall only on extended grapheme cluster boundaries, so neither
+// a surrogate pair nor an emoji ZWJ sequence or base-plus-mark cluster is ever
+// split, and the context on each side counts clusters rather than chars. A
+// cluster the range only partly covers is kept whole.
+
+/// An excerpt and the range of the match within it, in the unit of the input range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Excerpt {
+    pub text: String,
+    pub range: MatchRange,
+}
+
+/// The text around `range` of `s` with up to `context_chars` grapheme clusters on each side,
+/// `ellipsis` marking each end where text was removed. Errors when the range is reversed,
+/// runs past the end or, in UTF-16 units, starts or ends inside a surrogate pair.
+pub fn excerpt_around_range(
+    s: &str,
+    range: MatchRange,
+    context_chars: usize,
+    ellipsis: &str,
+    unit: RangeUnit,
+) -> Result<Excerpt, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let offsets = unit_offsets(&chars, unit);
+    let length = offsets[chars.len()];
+    if range.start > range.end || range.end > length {
+        let unit_name = match unit {
+            RangeUnit::Char => "chars",
+            RangeUnit::Utf16 => "UTF-16 code units",
+        };
+        return Err(format!(
+            "Range {}..{} is out of bounds for a string of {} {}",
+            range.start, range.end, length, unit_name
+        ));
+    }
+    let char_index = |offset: usize| {
+        offsets.binary_search(&offset).map_err(|_| {
+            format!(
+                "Range {}..{} splits a surrogate pair",
+                range.start, range.end
+            )
+        })
+    };
+    let (start, end) = (char_index(range.start)?, char_index(range.end)?);
+
+    // Char offsets of every cluster boundary, 0 and the length included
+    let mut bounds = vec![0];
+    for grapheme in s.graphemes(true) {
+        bounds.push(bounds[bounds.len() - 1] + grapheme.chars().count());
+    }
+    // The boundaries at or before the start and at or after the end, widened by the context
+    let first = bounds.partition_point(|&bound| bound <= start) - 1;
+    let last = bounds.partition_point(|&bound| bound < end);
+    let cut_start = bounds[first.saturating_sub(context_chars)];
+    let cut_end = bounds[(last + context_chars).min(bounds.len() - 1)];
+
+    let mut text = String::new();
+    let mut lead = 0;
+    if cut_start > 0 {
+        text.push_str(ellipsis);
+        lead = match unit {
+            RangeUnit::Char => ellipsis.chars().count(),
+            RangeUnit::Utf16 => ellipsis.encode_utf16().count(),
+        };
+    }
+    text.extend(&chars[cut_start..cut_end]);
+    if cut_end < chars.len() {
+        text.push_str(ellipsis);
+    }
+    let excerpt_start = lead + range.start - offsets[cut_start];
+    Ok(Excerpt {
+        text,
+        range: MatchRange {
+            start: excerpt_start,
+            end: excerpt_start + range.end - range.start,
+        },
+    })
+}
+
+// ============================================================================
+// WINDOW SCORES
+// ============================================================================
+// The similarity profile of a short needle along a long haystack: the needle
+// scored against haystack slices of `window` chars, one every `step` chars,
+// for finding peaks or several matching regions rather than one best range.
+// A window as long as the needle (the default) suits the edit metrics, which
+// charge for every char of length mismatch; a wider window lets a paraphrase
+// drift but dilutes those scores by the extra chars. The default step of a
+// quarter window keeps any occurrence within window / 4 chars of some window
+// start. Needle and haystack are decoded once and each window borrows the
+// haystack's chars, so the cost is the window count times one metric call.
+
+/// `unified_score` of the needle against the haystack chars `i * step..i * step + window`
+/// for every window start that fits, in order; a haystack no longer than the window is one
+/// window. `window` defaults to the needle's char length and `step` to `max(1, window / 4)`.
+/// When `step` doesn't divide the overhang, the last `step - 1` chars at most fall in no
+/// window. A zero window (an empty needle without an explicit window) or step is an error.
+pub fn window_scores(
+    needle: &str,
+    haystack: &str,
+    window: Option<usize>,
+    step: Option<usize>,
+    metric: &str,
+) -> Result<Vec<f64>, String> {
+    let needle_chars: Vec<char> = needle.chars().collect();
+    let window = window.unwrap_or(needle_chars.len());
+    if window == 0 {
+        return Err("Window must be at least 1 char".to_string());
+    }
+    let step = step.unwrap_or((window / 4).max(1));
+    if step == 0 {
+        return Err("Step must be at least 1 char".to_string());
+    }
+
+    let def = find_metric(metric)?;
+
+    let haystack: Vec<char> = haystack.chars().collect();
+    let length = haystack.len();
+    Ok((0..=length.saturating_sub(window))
+        .step_by(step)
+        .map(|start| {
+            def.score(
+                &needle_chars,
+                &haystack[start..(start + window).min(length)],
+            )
+        })
+        .collect())
+}
+
+// ============================================================================
+// FUZZY SEARCH
+// ============================================================================
+// Sellers' algorithm: one DP column over the needle per haystack char, with a
+// free start, gives the fewest edits of any match ending at each position
+// (ties prefer the latest start, i.e. the shortest match). Positions within
+// max_edits become candidates, and overlapping candidates collapse to the one
+// with the fewest edits. On equal edits a candidate starting no later than the
+// pending one extends the same occurrence and replaces it ("caf" grows into
+// "café"), while one starting later is a shifted copy and loses. A candidate
+// overlapping a match already reported is dropped.
+//
+// The column and the one pending candidate are the whole state, so feeding
+// the haystack in chunks gives exactly the matches of feeding it at once. A
+// match is at most needle_len + max_edits chars long, so the pending candidate
+// is final once the stream is that far past its end.
+
+/// One fuzzy occurrence of the needle: half-open char range in the haystack
+/// and the edits needed to turn it into the needle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub start: usize,
+    pub end: usize,
+    pub edits: usize,
+}
+
+/// Incremental fuzzy search over a haystack that arrives in chunks
+#[derive(Debug, Clone)]
+pub struct StreamMatcher {
+    needle: Vec<char>,
+    max_edits: usize,
+    // Edits and start of the best match of needle[..i] ending at `position`
+    costs: Vec<usize>,
+    starts: Vec<usize>,
+    next_costs: Vec<usize>,
+    next_starts: Vec<usize>,
+    position: usize,
+    pending: Option<FuzzyMatch>,
+    reported_end: usize,
+}
+
+impl StreamMatcher {
+    /// Errors unless `max_edits` is below the needle's length (otherwise every
+    /// position would match)
+    pub fn new(needle: &str, max_edits: usize) -> Result<Self, String> {
+        let needle: Vec<char> = needle.chars().collect();
+        if max_edits >= needle.len() {
+            return Err(format!(
+                "max_edits must be less than the needle length (got {} for {} chars)",
+                max_edits,
+                needle.len()
+            ));
+        }
+        let m = needle.len();
+        Ok(StreamMatcher {
+            needle,
+            max_edits,
+            costs: (0..=m).collect(),
+            starts: vec![0; m + 1],
+            next_costs: vec![0; m + 1],
+            next_starts: vec![0; m + 1],
+            position: 0,
+            pending: None,
+            reported_end: 0,
+        })
+    }
+
+    fn reset(&mut self) {
+        for (i, cost) in self.costs.iter_mut().enumerate() {
+            *cost = i;
+        }
+        self.starts.fill(0);
+        self.position = 0;
+        self.pending = None;
+        self.reported_end = 0;
+    }
+
+    /// Consume the next chunk, returning the matches it completed (offsets
+    /// count chars from the start of the stream)
+    pub fn feed(&mut self, chunk: &str) -> Vec<FuzzyMatch> {
+        let mut completed = Vec::new();
+        let longest = self.needle.len() + self.max_edits;
+        for c in chunk.chars() {
+            self.step(c, &mut completed);
+            if let Some(pending) = self.pending {
+                if self.position >= pending.end + longest {
+                    self.report(pending, &mut completed);
+                }
+            }
+        }
+        completed
+    }
+
+    /// Report the match still pending at the end of the stream and reset, so
+    /// the matcher can scan another stream
+    pub fn finish(&mut self) -> Vec<FuzzyMatch> {
+        let mut completed = Vec::new();
+        if let Some(pending) = self.pending {
+            self.report(pending, &mut completed);
+        }
+        self.reset();
+        completed
+    }
+
+    fn step(&mut self, c: char, completed: &mut Vec<FuzzyMatch>) {
+        self.position += 1;
+        self.next_costs[0] = 0;
+        self.next_starts[0] = self.position;
+        for i in 1..=self.needle.len() {
+            let substitution = (
+                self.costs[i - 1] + usize::from(self.needle[i - 1] != c),
+                self.starts[i - 1],
+            );
+            let skip_needle = (self.next_costs[i - 1] + 1, self.next_starts[i - 1]);
+            let skip_haystack = (self.costs[i] + 1, self.starts[i]);
+            let (cost, start) = [substitution, skip_needle, skip_haystack]
+                .into_iter()
+                .min_by_key(|&(cost, start)| (cost, std::cmp::Reverse(start)))
+                .unwrap();
+            self.next_costs[i] = cost;
+            self.next_starts[i] = start;
+        }
+        std::mem::swap(&mut self.costs, &mut self.next_costs);
+        std::mem::swap(&mut self.starts, &mut self.next_starts);
+
+        let m = self.needle.len();
+        if self.costs[m] > self.max_edits || self.starts[m] < self.reported_end {
+            return;
+        }
+        let candidate = FuzzyMatch {
+            start: self.starts[m],
+            end: self.position,
+            edits: self.costs[m],
+        };
+        match self.pending {
+            Some(pending) if candidate.start < pending.end => {
+                if (candidate.edits, candidate.start) <= (pending.edits, pending.start) {
+                    self.pending = Some(candidate);
+                }
+            }
+            Some(pending) => {
+                self.report(pending, completed);
+                self.pending = Some(candidate);
+            }
+            None => self.pending = Some(candidate),
+        }
+    }
+
+    fn report(&mut self, found: FuzzyMatch, completed: &mut Vec<FuzzyMatch>) {
+        completed.push(found);
+        self.reported_end = found.end;
+        self.pending = None;
+    }
+}
+
+/// Every non-overlapping fuzzy occurrence of `needle` in `haystack` within
+/// `max_edits`, left to right
+pub fn fuzzy_find_all(
+    haystack: &str,
+    needle: &str,
+    max_edits: usize,
+) -> Result<Vec<FuzzyMatch>, String> {
+    let mut matcher = StreamMatcher::new(needle, max_edits)?;
+    // Without feed's early reporting a match only settles when the next disjoint
+    // candidate arrives or the haystack ends, so streaming is checked against this
+    let mut found = Vec::new();
+    for c in haystack.chars() {
+        matcher.step(c, &mut found);
+    }
+    found.extend(matcher.finish());
+    Ok(found)
+}
+
+// ============================================================================
+// WORD SEGMENTATION
+// ============================================================================
+// Splits run-together queries ("newyorkcity") into vocabulary words with a
+// Viterbi pass over char positions. A word costs its natural log probability
+// under the vocabulary's weights; a char no word covers costs as much as the
+// rarest possible word plus a penalty, and runs of such chars are kept as one
+// unknown word. Whitespace in the input is a fixed boundary.
+
+/// Extra cost of each char no vocabulary word covers, on top of the log
+/// probability of a word seen once
+pub const DEFAULT_UNKNOWN_CHAR_PENALTY: f64 = 10.0;
+
+/// Words with their log probabilities, built once and reused across queries
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    log_probabilities: HashMap<String, f64>,
+    // Log probability of a word seen once, the base cost of an unknown char
+    unseen_log_probability: f64,
+    longest_word_chars: usize,
+}
+
+impl Vocabulary {
+    /// Every word weighs 1; repeated words add up
+    pub fn new<S: AsRef<str>>(words: &[S]) -> Self {
+        let mut weights: BTreeMap<String, f64> = BTreeMap::new();
+        for word in words {
+            *weights.entry(word.as_ref().to_string()).or_default() += 1.0;
+        }
+        Vocabulary::from_weights(weights)
+    }
+
+    /// Words with frequency weights; weights must be finite and non-negative, and
+    /// zero-weight words are left out
+    pub fn with_frequencies<S: AsRef<str>>(entries: &[(S, f64)]) -> Result<Self, String> {
+        let mut weights: BTreeMap<String, f64> = BTreeMap::new();
+        for (word, weight) in entries {
+            if !weight.is_finite() || *weight < 0.0 {
+                return Err(format!(
+                    "Word frequency must be finite and non-negative: {} = {}",
+                    word.as_ref(),
+                    weight
+                ));
+            }
+            if *weight > 0.0 {
+                *weights.entry(word.as_ref().to_string()).or_default() += weight;
+            }
+        }
+        Ok(Vocabulary::from_weights(weights))
+    }
+
+    fn from_weights(mut weights: BTreeMap<String, f64>) -> Self {
+        // Whitespace always splits the input, so words containing it never match
+        weights.retain(|word, _| !word.is_empty() && !word.contains(char::is_whitespace));
+        // Summed in key order: a HashMap's per-process order could change the last bits
+        let total: f64 = weights.values().sum();
+        let longest_word_chars = weights.keys().map(|word| word.chars().count()).max();
+        Vocabulary {
+            log_probabilities: weights
+                .into_iter()
+                .map(|(word, weight)| (word, (weight / total).ln()))
+                .collect(),
+            unseen_log_probability: (1.0 / total.max(1.0)).ln(),
+            longest_word_chars: longest_word_chars.unwrap_or(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.log_probabilities.is_empty()
+    }
+}
+
+/// Word segmentation settings
+#[derive(Debug, Clone, Copy)]
+pub struct SegmentOptions {
+    /// Cost of each char no vocabulary word covers beyond that of a word seen once
+    pub unknown_char_penalty: f64,
+}
+
+impl Default for SegmentOptions {
+    fn default() -> Self {
+        SegmentOptions {
+            unknown_char_penalty: DEFAULT_UNKNOWN_CHAR_PENALTY,
+        }
+    }
+}
+
+/// The most probable split of an input into words
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segmentation {
+    /// Vocabulary words and unknown runs, in input order
+    pub words: Vec<String>,
+    /// Natural log probability of the split (0 for an input without words)
+    pub score: f64,
+}
+
+impl Segmentation {
+    /// The words joined by single spaces
+    pub fn text(&self) -> String {
+        self.words.join(" ")
+    }
+}
+
+/// Viterbi word segmentation of `input` over `vocabulary`. Matching is exact, so
+/// normalize the input and the vocabulary alike first. Substrings no word covers
+/// are kept as they are, each char costing `unknown_char_penalty` more than the
+/// rarest word; ties go to the split whose last word is longest.
+pub fn segment_words(
+    input: &str,
+    vocabulary: &Vocabulary,
+    options: &SegmentOptions,
+) -> Segmentation {
+    let unknown_char_cost = vocabulary.unseen_log_probability - options.unknown_char_penalty;
+    let mut segmentation = Segmentation {
+        words: Vec::new(),
+        score: 0.0,
+    };
+    for chunk in input.split_whitespace() {
+        let (words, score) = segment_chunk(chunk, vocabulary, unknown_char_cost);
+        segmentation.words.extend(words);
+        segmentation.score += score;
+    }
+    segmentation
+}
+
+// One whitespace-free chunk: best[i] is the best score of the first i chars,
+// reached by a vocabulary word or a single unknown char ending at i
+fn segment_chunk(
+    chunk: &str,
+    vocabulary: &Vocabulary,
+    unknown_char_cost: f64,
+) -> (Vec<String>, f64) {
+    let offsets: Vec<usize> = chunk
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(chunk.len()))
+        .collect();
+    let chars = offsets.len() - 1;
+    // (score, start of the last word, whether that word is known)
+    let mut best: Vec<(f64, usize, bool)> = vec![(0.0, 0, true); chars + 1];
+    for end in 1..=chars {
+        let mut step = (best[end - 1].0 + unknown_char_cost, end - 1, false);
+        for start in end.saturating_sub(vocabulary.longest_word_chars)..end {
+            let word = &chunk[offsets[start]..offsets[end]];
+            if let Some(log_probability) = vocabulary.log_probabilities.get(word) {
+                let score = best[start].0 + log_probability;
+                if score > step.0 || (score == step.0 && !step.2) {
+                    step = (score, start, true);
+                }
+            }
+        }
+        best[end] = step;
+    }
+
+    // Walk back, merging adjacent unknown chars into one word
+    let mut words: Vec<(usize, usize, bool)> = Vec::new();
+    let mut end = chars;
+    while end > 0 {
+        let (_, start, known) = best[end];
+        match words.last_mut() {
+            Some((run_start, _, false)) if !known => *run_start = start,
+            _ => words.push((start, end, known)),
+        }
+        end = start;
+    }
+    let words = words
+        .into_iter()
+        .rev()
+        .map(|(start, end, _)| chunk[offsets[start]..offsets[end]].to_string())
+        .collect();
+    (words, best[chars].0)
+}
+
+// ============================================================================
+// RANK FUSION
+// ============================================================================
+// Merges the best-first rankings of several metrics into one list without
+// blending their scores, whose scales and spreads differ too much for fixed
+// weights. Rankings hold item ids (candidate or choice indices) and every tie
+// rule falls back to the lower id, so the fused order is fully determined.
+
+/// Reciprocal rank fusion constant: larger values flatten the gap between top ranks
+pub const DEFAULT_RRF_K: f64 = 60.0;
+
+/// How `RankFusion` merges the per-metric rankings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FusionMethod {
+    /// Each ranking's next unseen item in turn, in metric order
+    #[default]
+    Interleave,
+    /// Reciprocal rank fusion: items ordered by the sum of `1 / (k + rank)`
+    Rrf,
+}
+
+impl FusionMethod {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "interleave" => Ok(FusionMethod::Interleave),
+            "rrf" => Ok(FusionMethod::Rrf),
+            _ => Err(format!("Unknown rank fusion method: {}", name)),
+        }
+    }
+}
+
+/// Rank with every metric in `metrics` and merge the rankings with `method`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankFusion {
+    pub metrics: Vec<String>,
+    pub method: FusionMethod,
+    /// RRF constant; ignored by `Interleave`
+    pub k: f64,
+}
+
+impl RankFusion {
+    /// Rejects an empty metric list and a negative or non-finite `k`
+    pub fn new(metrics: Vec<String>, method: FusionMethod, k: Option<f64>) -> Result<Self, String> {
+        if metrics.is_empty() {
+            return Err("rank_fusion needs at least one metric".to_string());
+        }
+        let k = k.unwrap_or(DEFAULT_RRF_K);
+        if !k.is_finite() || k < 0.0 {
+            return Err(format!(
+                "rank_fusion k must be a non-negative number, got {}",
+                k
+            ));
+        }
+        Ok(RankFusion { metrics, method, k })
+    }
+}
+
+/// Merge best-first rankings of item ids into one list, each id once.
+///
+/// `Interleave` takes rank 1 of every ranking in order, then rank 2, and so on, skipping ids
+/// already taken, so an id found in several rankings lands at its best position. `Rrf` sums
+/// `1 / (k + rank)` (ranks from 1) over the rankings holding each id, in ranking order; equal
+/// sums go to the id with the better best rank, then to the lower id.
+pub fn fuse_rankings(rankings: &[Vec<usize>], method: FusionMethod, k: f64) -> Vec<usize> {
+    match method {
+        FusionMethod::Interleave => {
+            let depth = rankings.iter().map(Vec::len).max().unwrap_or(0);
+            let mut seen = HashSet::new();
+            (0..depth)
+                .flat_map(|rank| rankings.iter().filter_map(move |ranking| ranking.get(rank)))
+                .filter(|&&id| seen.insert(id))
+                .copied()
+                .collect()
+        }
+        FusionMethod::Rrf => {
+            // Fused score and best (0-based) rank per id
+            let mut fused: HashMap<usize, (f64, usize)> = HashMap::new();
+            for ranking in rankings {
+                for (rank, &id) in ranking.iter().enumerate() {
+                    let entry = fused.entry(id).or_insert((0.0, rank));
+                    entry.0 += 1.0 / (k + (rank + 1) as f64);
+                    entry.1 = entry.1.min(rank);
+                }
+            }
+            let mut ids: Vec<(usize, (f64, usize))> = fused.into_iter().collect();
+            ids.sort_by(|(id_a, (score_a, rank_a)), (id_b, (score_b, rank_b))| {
+                score_b
+                    .total_cmp(score_a)
+                    .then_with(|| rank_a.cmp(rank_b))
+                    .then_with(|| id_a.cmp(id_b))
+            });
+            ids.into_iter().map(|(id, _)| id).collect()
+        }
+    }
+}
+
+// ============================================================================
+// SUGGESTIONS
+// ============================================================================
+
+/// Prefix bonus weight when `prefer_prefix` is set without a weight
+pub const DEFAULT_PREFIX_BONUS_WEIGHT: f64 = 0.1;
+
+/// How a query is scored against a candidate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueryMode {
+    /// The whole query against the whole candidate
+    #[default]
+    Whole,
+    /// Each whitespace token of the query against its best-matching candidate token,
+    /// combined with a `TokenAggregation`
+    Tokens,
+}
+
+impl QueryMode {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "whole" => Ok(QueryMode::Whole),
+            "tokens" => Ok(QueryMode::Tokens),
+            _ => Err(format!("Unknown query mode: {}", name)),
+        }
+    }
+}
+
+/// How `QueryMode::Tokens` combines the per-token scores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenAggregation {
+    #[default]
+    Mean,
+    /// The weakest token decides
+    Min,
+    /// Mean weighted by each query token's length in chars
+    Weighted,
+}
+
+impl TokenAggregation {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "mean" => Ok(TokenAggregation::Mean),
+            "min" => Ok(TokenAggregation::Min),
+            "weighted" => Ok(TokenAggregation::Weighted),
+            _ => Err(format!("Unknown token aggregation: {}", name)),
+        }
+    }
+}
+
+/// Fraction of the score scale below which a query token counts as missing
+pub const MISSING_TOKEN_THRESHOLD: f64 = 0.5;
+
+// Token mode: every query token takes its best score against the candidate's
+// tokens. A token below MISSING_TOKEN_THRESHOLD is missing: it scores 0 and
+// the aggregate is scaled by the fraction of tokens found, so a candidate
+// lacking a word ranks below one that only matches every word weakly. A query
+// without tokens falls back to the whole-string score. `scale` is the score
+// range (1, or 100 for extraction), and the arithmetic runs in the order the
+// TypeScript `suggest` and `extract` use so both give identical floats.
+fn token_query_score<F>(
+    query: &str,
+    candidate: &str,
+    aggregation: TokenAggregation,
+    scale: f64,
+    mut score: F,
+) -> Result<f64, String>
+where
+    F: FnMut(&str, &str) -> Result<f64, String>,
+{
+    let query_tokens: Vec<&str> = query.split_whitespace().collect();
+    if query_tokens.is_empty() {
+        return score(query, candidate);
+    }
+    let candidate_tokens: Vec<&str> = candidate.split_whitespace().collect();
+    let threshold = MISSING_TOKEN_THRESHOLD * scale;
+
+    let mut found = 0;
+    let mut token_scores = Vec::with_capacity(query_tokens.len());
+    for token in &query_tokens {
+        let mut best = 0.0;
+        for candidate_token in &candidate_tokens {
+            best = f64::max(best, score(token, candidate_token)?);
+        }
+        if best >= threshold {
+            found += 1;
+            token_scores.push(best);
+        } else {
+            token_scores.push(0.0);
+        }
+    }
+
+    let count = query_tokens.len() as f64;
+    let aggregate = match aggregation {
+        TokenAggregation::Mean => token_scores.iter().fold(0.0, |sum, score| sum + score) / count,
+        TokenAggregation::Min => token_scores.iter().copied().fold(f64::INFINITY, f64::min),
+        TokenAggregation::Weighted => {
+            let mut weighted = 0.0;
+            let mut total = 0.0;
+            for (token, token_score) in query_tokens.iter().zip(&token_scores) {
+                let weight = token.chars().count() as f64;
+                weighted += token_score * weight;
+                total += weight;
+            }
+            weighted / total
+        }
+    };
+    Ok(aggregate * found as f64 / count)
+}
+
+/// Pairs whose shorter side has fewer chars than this count as short by default
+pub const DEFAULT_SHORT_STRING_LENGTH: usize = 3;
+
+/// How `suggest` and `extract` treat short pairs, where Jaro-Winkler in particular
+/// scores one-char noise highly ("a" against "ab" is 0.85). A pair is short when its
+/// shorter normalized side has fewer than `short_string_length` chars; equal pairs are
+/// never affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShortStringPolicy {
+    /// Scores stand as the metric gives them
+    #[default]
+    None,
+    /// Scores scale by the shorter side's length over `short_string_length`
+    Penalize,
+    /// Only candidates that start with the query are eligible
+    RequirePrefix,
+}
+
+impl ShortStringPolicy {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "none" => Ok(ShortStringPolicy::None),
+            "penalize" => Ok(ShortStringPolicy::Penalize),
+            "require_prefix" => Ok(ShortStringPolicy::RequirePrefix),
+            _ => Err(format!("Unknown short string policy: {}", name)),
+        }
+    }
+
+    /// The score of a normalized pair under this policy, or None when a short pair is
+    /// ineligible; applied after the metric and before any bonus or cutoff
+    pub fn apply(self, length: usize, query: &str, candidate: &str, score: f64) -> Option<f64> {
+        let shorter = query.chars().count().min(candidate.chars().count());
+        if self == ShortStringPolicy::None || shorter >= length || query == candidate {
+            return Some(score);
+        }
+        match self {
+            ShortStringPolicy::Penalize => Some(score * shorter as f64 / length as f64),
+            _ => candidate.starts_with(query).then_some(score),
+        }
+    }
+}
+
+/// Candidate length limits checked before scoring, on normalized char lengths, so
+/// single-char entries and long descriptions cost nothing. Every limit is off by
+/// default.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LengthFilter {
+    pub min_candidate_len: Option<usize>,
+    pub max_candidate_len: Option<usize>,
+    /// Largest longer/shorter ratio of the query and candidate lengths; an empty
+    /// side against a non-empty one exceeds every ratio
+    pub max_length_ratio: Option<f64>,
+}
+
+impl LengthFilter {
+    /// Whether a candidate of `candidate_len` chars is scored against a query of `query_len`
+    pub fn admits(&self, query_len: usize, candidate_len: usize) -> bool {
+        let ratio_ok = self.max_length_ratio.is_none_or(|max_ratio| {
+            let (shorter, longer) = (query_len.min(candidate_len), query_len.max(candidate_len));
+            shorter == longer || (shorter > 0 && longer as f64 / shorter as f64 <= max_ratio)
+        });
+        self.min_candidate_len
+            .is_none_or(|min| candidate_len >= min)
+            && self
+                .max_candidate_len
+                .is_none_or(|max| candidate_len <= max)
+            && ratio_ok
+    }
+}
+
+/// How many candidates each prefilter outcome covered; calls add to the counts,
+/// so one value can total several calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefilterCounts {
+    pub scored: usize,
+    pub skipped_length: usize,
+}
+
+/// Ranks recently selected candidates higher: a candidate last selected at `t` gains
+/// `max_boost * 2^(-(now_ms - t) / half_life_ms)`, and the boosted score is capped at 1.0.
+/// `now_ms` is explicit so the same history always ranks the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecencyBoost {
+    pub half_life_ms: f64,
+    pub max_boost: f64,
+    pub now_ms: f64,
+    /// Let the boost lift a candidate scoring below `min_score` past the cutoff
+    pub allow_boost_past_cutoff: bool,
+}
+
+impl RecencyBoost {
+    /// Rejects a non-positive half-life, a `max_boost` outside [0, 1] and a non-finite `now_ms`
+    pub fn new(
+        half_life_ms: f64,
+        max_boost: f64,
+        now_ms: f64,
+        allow_boost_past_cutoff: bool,
+    ) -> Result<Self, String> {
+        if !half_life_ms.is_finite() || half_life_ms <= 0.0 {
+            return Err(format!(
+                "recency_boost half_life_ms must be a positive number, got {}",
+                half_life_ms
+            ));
+        }
+        if !(0.0..=1.0).contains(&max_boost) {
+            return Err(format!(
+                "recency_boost max_boost must be between 0 and 1, got {}",
+                max_boost
+            ));
+        }
+        if !now_ms.is_finite() {
+            return Err(format!(
+                "recency_boost now_ms must be finite, got {}",
+                now_ms
+            ));
+        }
+        Ok(RecencyBoost {
+            half_life_ms,
+            max_boost,
+            now_ms,
+            allow_boost_past_cutoff,
+        })
+    }
+
+    /// What a candidate last selected at `last_selected_ms` gains; never selected gains 0,
+    /// and a selection after `now_ms` counts as selected now
+    pub fn boost(&self, last_selected_ms: Option<f64>) -> f64 {
+        match last_selected_ms {
+            Some(t) if t.is_finite() => {
+                let age = (self.now_ms - t).max(0.0);
+                self.max_boost * (-age / self.half_life_ms).exp2()
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Suggestion engine settings (defaults match the library's `suggest`)
+#[derive(Debug, Clone)]
+pub struct SuggestOptions {
+    pub min_score: f64,
+    pub max_suggestions: usize,
+    pub metric: String,
+    pub normalize_preset: String,
+    pub prefer_prefix: bool,
+    pub prefix_bonus_weight: f64,
+    /// Applied to the query, and to every candidate without a locale of its own
+    pub locale: Option<String>,
+    /// Attach a `SuggestionExplanation` to each returned suggestion
+    pub explain: bool,
+    pub query_mode: QueryMode,
+    pub token_aggregation: TokenAggregation,
+    /// Applied to the query and every candidate after the preset
+    pub expansions: Expansions,
+    pub short_string_policy: ShortStringPolicy,
+    pub short_string_length: usize,
+    /// Also score the query split into candidate words (see `segmented_query`) and
+    /// keep whichever query scores each candidate higher
+    pub segment_input: bool,
+    pub length_filter: LengthFilter,
+    /// Rank with each of these metrics instead of `metric` and merge the rankings; each
+    /// suggestion keeps its best score across them
+    pub rank_fusion: Option<RankFusion>,
+    /// Added after the prefix bonus, from each candidate's `last_selected_ms`
+    pub recency_boost: Option<RecencyBoost>,
+}
+
+impl Default for SuggestOptions {
+    fn default() -> Self {
+        SuggestOptions {
+            min_score: 0.6,
+            max_suggestions: 3,
+            metric: "levenshtein".to_string(),
+            normalize_preset: "default".to_string(),
+            prefer_prefix: false,
+            prefix_bonus_weight: DEFAULT_PREFIX_BONUS_WEIGHT,
+            locale: None,
+            explain: false,
+            query_mode: QueryMode::Whole,
+            token_aggregation: TokenAggregation::Mean,
+            expansions: Expansions::default(),
+            short_string_policy: ShortStringPolicy::None,
+            short_string_length: DEFAULT_SHORT_STRING_LENGTH,
+            segment_input: false,
+            length_filter: LengthFilter::default(),
+            rank_fusion: None,
+            recency_boost: None,
+        }
+    }
+}
+
+/// A candidate with optional metadata; plain strings convert with `From`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Candidate {
+    pub value: String,
+    /// Echoed back on the candidate's suggestion
+    pub id: Option<String>,
+    /// Case-folding locale for this candidate, overriding `SuggestOptions::locale`
+    pub locale: Option<String>,
+    /// When the candidate was last selected (ms since the epoch), for `RecencyBoost`
+    pub last_selected_ms: Option<f64>,
+}
+
+impl From<&str> for Candidate {
+    fn from(value: &str) -> Self {
+        Candidate {
+            value: value.to_string(),
+            ..Candidate::default()
+        }
+    }
+}
+
+impl From<String> for Candidate {
+    fn from(value: String) -> Self {
+        Candidate {
+            value,
+            ..Candidate::default()
+        }
+    }
+}
+
+/// One ranked candidate
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub value: String,
+    /// The candidate's `id`, if it had one
+    pub id: Option<String>,
+    pub score: f64,
+    /// Only populated for the substring metric
+    pub matched_range: Option<MatchRange>,
+    pub normalized_value: String,
+    /// Only populated when `explain` is set
+    pub explanation: Option<SuggestionExplanation>,
+}
+
+/// How a candidate got past the prefilters. Skipped candidates are never returned
+/// (`PrefilterCounts` tallies them), so every returned suggestion reports `Scored`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefilter {
+    Scored,
+    SkippedLength,
+    SkippedNgram,
+}
+
+impl Prefilter {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Prefilter::Scored => "scored",
+            Prefilter::SkippedLength => "skipped_length",
+            Prefilter::SkippedNgram => "skipped_ngram",
+        }
+    }
+}
+
+/// Breakdown of one suggestion's score, for answering "why did X beat Y"
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestionExplanation {
+    /// Metric score after the short-string policy, before the prefix bonus
+    pub raw_score: f64,
+    pub metric: String,
+    pub prefix_bonus_applied: bool,
+    /// What the prefix bonus and any recency boost added to `raw_score` (0 without either)
+    pub bonus_amount: f64,
+    pub normalized_input: String,
+    pub normalized_candidate: String,
+    pub prefilter: Prefilter,
+}
+
+/// Clamp to [0, 1]; non-finite or missing weights fall back to the default
+pub fn clamp_prefix_bonus_weight(weight: Option<f64>) -> f64 {
+    match weight {
+        Some(weight) if weight.is_finite() => weight.clamp(0.0, 1.0),
+        _ => DEFAULT_PREFIX_BONUS_WEIGHT,
+    }
+}
+
+/// Score one suggestion candidate; the range is only populated for the substring metric.
+/// Besides `substring`, any registered metric or alias works; unknown metrics score 0.
+pub fn score_for_metric(input: &str, candidate: &str, metric: &str) -> (f64, Option<MatchRange>) {
+    SuggestScorer::resolve(metric).score(input, candidate)
+}
+
+// A suggestion metric, resolved once per query rather than once per candidate
+#[derive(Clone, Copy)]
+enum SuggestScorer {
+    Substring,
+    Registered(MetricDef),
+    Unknown,
+}
+
+impl SuggestScorer {
+    fn resolve(metric: &str) -> Self {
+        match metric {
+            "substring" => SuggestScorer::Substring,
+            _ => find_metric(metric).map_or(SuggestScorer::Unknown, SuggestScorer::Registered),
+        }
+    }
+
+    fn score(self, input: &str, candidate: &str) -> (f64, Option<MatchRange>) {
+        match self {
+            SuggestScorer::Substring => {
+                substring_similarity(input, candidate, SubstringOptions::default())
+            }
+            SuggestScorer::Registered(def) => (def.score_str(input, candidate), None),
+            SuggestScorer::Unknown => (0.0, None),
+        }
+    }
+}
+
+// An empty query is a prefix of everything but shares nothing with it, so it earns no bonus
+fn prefix_bonus_applies(options: &SuggestOptions, input: &str, candidate: &str) -> bool {
+    options.prefer_prefix && !input.is_empty() && candidate.starts_with(input)
+}
+
+/// Score, filter and rank candidates the way the library's `suggest` does: best score first,
+/// ties in candidate order, so the same input gives the same order on every platform.
+/// Explanations are built only for the suggestions returned.
+pub fn suggest<S: AsRef<str>>(
+    input: &str,
+    candidates: &[S],
+    options: &SuggestOptions,
+) -> Vec<Suggestion> {
+    let candidates: Vec<Candidate> = candidates.iter().map(|c| c.as_ref().into()).collect();
+    suggest_candidates(input, &candidates, options)
+}
+
+/// `suggest` over candidates carrying metadata: each is normalized with its own
+/// locale (falling back to the options' locale) and its `id` is echoed back
+pub fn suggest_candidates(
+    input: &str,
+    candidates: &[Candidate],
+    options: &SuggestOptions,
+) -> Vec<Suggestion> {
+    suggest_candidates_counted(input, candidates, options, &mut PrefilterCounts::default())
+}
+
+/// `suggest_candidates` adding how many candidates were scored or skipped to `counts`
+pub fn suggest_candidates_counted(
+    input: &str,
+    candidates: &[Candidate],
+    options: &SuggestOptions,
+    counts: &mut PrefilterCounts,
+) -> Vec<Suggestion> {
+    let normalize = |s: &str, locale: Option<&str>| {
+        let normalize_options = NormalizeOptions {
+            locale,
+            expansions: Some(&options.expansions),
+            ..NormalizeOptions::default()
+        };
+        normalize_with_options(s, &options.normalize_preset, &normalize_options)
+    };
+    let locale = options.locale.as_deref();
+    let normalized_input = normalize(input, locale);
+    let input_len = normalized_input.chars().count();
+    // The metrics ranked: the fused ones, or just `metric`
+    let metrics: Vec<&str> = match &options.rank_fusion {
+        Some(fusion) => fusion.metrics.iter().map(String::as_str).collect(),
+        None => vec![options.metric.as_str()],
+    };
+    let normalized_candidates: Vec<String> = candidates
+        .iter()
+        .map(|candidate| normalize(&candidate.value, candidate.locale.as_deref().or(locale)))
+        .collect();
+    let segmented_input = options
+        .segment_input
+        .then(|| segmented_query(&normalized_input, &normalized_candidates))
+        .flatten();
+
+    // Metric score after the short-string policy, final score and range of one query,
+    // or None when the policy rules the candidate out
+    let score_query = |scorer: SuggestScorer, query: &str, normalized_candidate: &str| {
+        let (metric_score, matched_range) = match options.query_mode {
+            QueryMode::Whole => scorer.score(query, normalized_candidate),
+            // Per-token ranges don't make one candidate range
+            QueryMode::Tokens => {
+                let score = token_query_score(
+                    query,
+                    normalized_candidate,
+                    options.token_aggregation,
+                    1.0,
+                    |token, candidate_token| Ok(scorer.score(token, candidate_token).0),
+                );
+                (score.unwrap_or_default(), None)
+            }
+        };
+        // Before the bonus, so explanations report the policy's score as raw_score
+        let raw_score = options.short_string_policy.apply(
+            options.short_string_length,
+            query,
+            normalized_candidate,
+            metric_score,
+        )?;
+
+        // Apply prefix bonus if enabled
+        // Formula: finalScore = min(1.0, score + (1 - score) * weight)
+        let mut score = raw_score;
+        if prefix_bonus_applies(options, query, normalized_candidate) {
+            score = boost_toward_one(score, options.prefix_bonus_weight);
+        }
+        Some((raw_score, score, matched_range))
+    };
+
+    let admitted: Vec<bool> = normalized_candidates
+        .iter()
+        .map(|normalized_candidate| {
+            let admits = options
+                .length_filter
+                .admits(input_len, normalized_candidate.chars().count());
+            if admits {
+                counts.scored += 1;
+            } else {
+                counts.skipped_length += 1;
+            }
+            admits
+        })
+        .collect();
+
+    // Per metric and candidate: the pre-bonus score, final score, range and query used,
+    // or None when the candidate was filtered out or scored below min_score
+    let scored: Vec<Vec<Option<ScoredSuggestion>>> = metrics
+        .iter()
+        .map(|metric| {
+            let scorer = SuggestScorer::resolve(metric);
+            normalized_candidates
+                .iter()
+                .zip(candidates)
+                .zip(&admitted)
+                .map(|((normalized_candidate, candidate), &admits)| {
+                    if !admits {
+                        return None;
+                    }
+                    // The segmented query only wins when it scores strictly higher
+                    let (query, (raw_score, score, matched_range)) =
+                        std::iter::once(&*normalized_input)
+                            .chain(segmented_input.as_deref())
+                            .filter_map(|query| {
+                                Some((query, score_query(scorer, query, normalized_candidate)?))
+                            })
+                            .reduce(|best, next| if next.1 .1 > best.1 .1 { next } else { best })?;
+                    // The cutoff sees the unboosted score unless the boost may lift past it
+                    let (boosted, passes) = match &options.recency_boost {
+                        Some(recency) => {
+                            let boosted =
+                                (score + recency.boost(candidate.last_selected_ms)).min(1.0);
+                            let cutoff_score = if recency.allow_boost_past_cutoff {
+                                boosted
+                            } else {
+                                score
+                            };
+                            (boosted, cutoff_score >= options.min_score)
+                        }
+                        None => (score, score >= options.min_score),
+                    };
+                    passes.then_some(ScoredSuggestion {
+                        raw_score,
+                        score: boosted,
+                        matched_range,
+                        query,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    // Each metric's ranking: by score (descending), ties in candidate order
+    let mut rankings: Vec<Vec<usize>> = scored
+        .iter()
+        .map(|per_candidate| {
+            let score = |idx: usize| per_candidate[idx].as_ref().map_or(0.0, |s| s.score);
+            let mut ranking: Vec<usize> = (0..candidates.len())
+                .filter(|&idx| per_candidate[idx].is_some())
+                .collect();
+            ranking.sort_by(|&idx_a, &idx_b| {
+                score(idx_b)
+                    .total_cmp(&score(idx_a))
+                    .then_with(|| idx_a.cmp(&idx_b))
+            });
+            ranking
+        })
+        .collect();
+    let order = match &options.rank_fusion {
+        Some(fusion) => fuse_rankings(&rankings, fusion.method, fusion.k),
+        None => rankings.swap_remove(0),
+    };
+
+    order
+        .into_iter()
+        .take(options.max_suggestions)
+        .filter_map(|idx| {
+            // The metric that scored the candidate highest, the first one on ties
+            let (metric, best) = metrics
+                .iter()
+                .zip(&scored)
+                .filter_map(|(metric, per_candidate)| Some((*metric, per_candidate[idx].as_ref()?)))
+                .reduce(|best, next| {
+                    if next.1.score > best.1.score {
+                        next
+                    } else {
+                        best
+                    }
+                })?;
+            let candidate = &candidates[idx];
+            let normalized_value = normalized_candidates[idx].clone();
+            let explanation = options.explain.then(|| SuggestionExplanation {
+                raw_score: best.raw_score,
+                metric: metric.to_string(),
+                prefix_bonus_applied: prefix_bonus_applies(options, best.query, &normalized_value),
+                bonus_amount: best.score - best.raw_score,
+                normalized_input: best.query.to_string(),
+                normalized_candidate: normalized_value.clone(),
+                prefilter: Prefilter::Scored,
+            });
+            Some(Suggestion {
+                value: candidate.value.clone(),
+                id: candidate.id.clone(),
+                score: best.score,
+                matched_range: best.matched_range,
+                normalized_value,
+                explanation,
+            })
+        })
+        .collect()
+}
+
+// One candidate's score under one suggestion metric
+struct ScoredSuggestion<'q> {
+    raw_score: f64,
+    score: f64,
+    matched_range: Option<MatchRange>,
+    /// The normalized query, or its segmentation when that scored higher
+    query: &'q str,
+}
+
+/// The normalized query split into words of the normalized candidates, or None when
+/// segmentation leaves it as it is (`suggest` with `segment_input`)
+pub fn segmented_query<S: AsRef<str>>(
+    normalized_query: &str,
+    normalized_candidates: &[S],
+) -> Option<String> {
+    let words: Vec<&str> = normalized_candidates
+        .iter()
+        .flat_map(|candidate| candidate.as_ref().split_whitespace())
+        .collect();
+    let segmented = segment_words(
+        normalized_query,
+        &Vocabulary::new(&words),
+        &SegmentOptions::default(),
+    )
+    .text();
+    (segmented != normalized_query).then_some(segmented)
+}
+
+// ============================================================================
+// DID YOU MEAN
+// ============================================================================
+// One-call correction with a fixed policy, so every implementation accepts or
+// refuses the same guesses: an exact match after "default" normalization wins;
+// otherwise the top jaro_winkler suggestion is offered when it scores at least
+// DID_YOU_MEAN_MIN_SCORE and beats the runner-up by DID_YOU_MEAN_MARGIN.
+// Candidates normalizing to the top's value are not runners-up.
+
+/// Lowest score `did_you_mean` offers as a correction
+pub const DID_YOU_MEAN_MIN_SCORE: f64 = 0.8;
+
+/// How far the top suggestion must lead the runner-up for `did_you_mean` to pick it
+pub const DID_YOU_MEAN_MARGIN: f64 = 0.05;
+
+/// The outcome of `did_you_mean`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DidYouMean {
+    /// A candidate equal to the input after normalization
+    Exact { value: String },
+    /// A confident correction and its jaro_winkler score
+    Corrected { value: String, score: f64 },
+    /// No candidate close enough, or two too close to call
+    None,
+}
+
+impl DidYouMean {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DidYouMean::Exact { .. } => "exact",
+            DidYouMean::Corrected { .. } => "corrected",
+            DidYouMean::None => "none",
+        }
+    }
+}
+
+/// The candidate `input` most likely meant under the fixed policy above; the first
+/// exact match in candidate order wins
+pub fn did_you_mean<S: AsRef<str>>(input: &str, candidates: &[S]) -> DidYouMean {
+    let normalized_input = normalize_with_locale(input, "default", None);
+    if let Some(exact) = candidates
+        .iter()
+        .find(|c| normalize_with_locale(c.as_ref(), "default", None) == normalized_input)
+    {
+        return DidYouMean::Exact {
+            value: exact.as_ref().to_string(),
+        };
+    }
+
+    let options = SuggestOptions {
+        min_score: 0.0,
+        max_suggestions: candidates.len(),
+        metric: "jaro_winkler".to_string(),
+        ..SuggestOptions::default()
+    };
+    let ranked = suggest(input, candidates, &options);
+    let Some(top) = ranked.first() else {
+        return DidYouMean::None;
+    };
+    let runner_up = ranked
+        .iter()
+        .find(|s| s.normalized_value != top.normalized_value)
+        .map_or(0.0, |s| s.score);
+    if top.score < DID_YOU_MEAN_MIN_SCORE || top.score - runner_up < DID_YOU_MEAN_MARGIN {
+        return DidYouMean::None;
+    }
+    DidYouMean::Corrected {
+        value: top.value.clone(),
+        score: top.score,
+    }
+}
+
+// ============================================================================
+// EXTRACTION (process.extract / extractOne)
+// ============================================================================
+// Scores use rapidfuzz's 0-100 process scale. The scorer is a unified score
+// metric name; the processor is a normalization preset applied to the query
+// and every choice before scoring.
+
+/// Extraction settings (defaults match the library's `extract`)
+#[derive(Debug, Clone)]
+pub struct ExtractOptions {
+    pub scorer: String,
+    pub processor: String,
+    /// Minimum 0-100 score for a choice to be returned
+    pub score_cutoff: f64,
+    pub limit: Option<usize>,
+    /// Attach a `SuggestionExplanation` to each returned result
+    pub explain: bool,
+    pub query_mode: QueryMode,
+    pub token_aggregation: TokenAggregation,
+    /// Applied to the query and every choice after the processor
+    pub expansions: Expansions,
+    pub short_string_policy: ShortStringPolicy,
+    pub short_string_length: usize,
+    /// Checked against processed choices
+    pub length_filter: LengthFilter,
+    /// Rank with each of these scorers instead of `scorer` and merge the rankings; each
+    /// result keeps its best score across them
+    pub rank_fusion: Option<RankFusion>,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        ExtractOptions {
+            scorer: "ratio".to_string(),
+            processor: "none".to_string(),
+            score_cutoff: 0.0,
+            limit: None,
+            explain: false,
+            query_mode: QueryMode::Whole,
+            token_aggregation: TokenAggregation::Mean,
+            expansions: Expansions::default(),
+            short_string_policy: ShortStringPolicy::None,
+            short_string_length: DEFAULT_SHORT_STRING_LENGTH,
+            length_filter: LengthFilter::default(),
+            rank_fusion: None,
+        }
+    }
+}
+
+impl ExtractOptions {
+    // The processor preset with the expansions, rejecting unknown presets
+    fn process(&self, s: &str) -> Result<String, String> {
+        let normalize_options = NormalizeOptions {
+            expansions: Some(&self.expansions),
+            ..NormalizeOptions::default()
+        };
+        try_normalize_with_options(s, &self.processor, &normalize_options)
+    }
+}
+
+/// One matched choice and its position in the input list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractResult {
+    pub choice: String,
+    pub score: f64,
+    pub index: usize,
+    /// Only populated when `explain` is set
+    pub explanation: Option<SuggestionExplanation>,
+}
+
+/// Score every choice that reaches the cutoff with `scorer`, in input order
+fn score_choices<S: AsRef<str>>(
+    query: &str,
+    choices: &[S],
+    options: &ExtractOptions,
+    scorer_name: &str,
+    counts: &mut PrefilterCounts,
+) -> Result<Vec<ExtractResult>, String> {
+    let processed_query = options.process(query)?;
+    let query_len = processed_query.chars().count();
+    let scorer = find_metric(scorer_name)?;
+    if options.score_cutoff > 0.0 && !scorer.supports_cutoff {
+        return Err(format!(
+            "Metric does not support score_cutoff: {}",
+            scorer_name
+        ));
+    }
+    let mut results = Vec::new();
+    for (index, choice) in choices.iter().enumerate() {
+        let choice = choice.as_ref();
+        let processed_choice = options.process(choice)?;
+        if !options
+            .length_filter
+            .admits(query_len, processed_choice.chars().count())
+        {
+            counts.skipped_length += 1;
+            continue;
+        }
+        counts.scored += 1;
+        let score = match options.query_mode {
+            QueryMode::Whole => scorer.score_str(&processed_query, &processed_choice) * 100.0,
+            QueryMode::Tokens => token_query_score(
+                &processed_query,
+                &processed_choice,
+                options.token_aggregation,
+                100.0,
+                |token, choice_token| Ok(scorer.score_str(token, choice_token) * 100.0),
+            )?,
+        };
+        let Some(score) = options.short_string_policy.apply(
+            options.short_string_length,
+            &processed_query,
+            &processed_choice,
+            score,
+        ) else {
+            continue;
+        };
+        if score >= options.score_cutoff {
+            results.push(ExtractResult {
+                choice: choice.to_string(),
+                score,
+                index,
+                explanation: None,
+            });
+        }
+    }
+    Ok(results)
+}
+
+// The total order of extract results: best score first, ties in input order
+fn by_score_then_index(a: &ExtractResult, b: &ExtractResult) -> std::cmp::Ordering {
+    b.score
+        .total_cmp(&a.score)
+        .then_with(|| a.index.cmp(&b.index))
+}
+
+/// Rank choices the way the library's `extract` does: best score first, ties in input order
+pub fn extract<S: AsRef<str>>(
+    query: &str,
+    choices: &[S],
+    options: &ExtractOptions,
+) -> Result<Vec<ExtractResult>, String> {
+    extract_counted(query, choices, options, &mut PrefilterCounts::default())
+}
+
+/// `extract` adding how many choices were scored or skipped to `counts`
+pub fn extract_counted<S: AsRef<str>>(
+    query: &str,
+    choices: &[S],
+    options: &ExtractOptions,
+    counts: &mut PrefilterCounts,
+) -> Result<Vec<ExtractResult>, String> {
+    let Some(fusion) = &options.rank_fusion else {
+        let mut results = score_choices(query, choices, options, &options.scorer, counts)?;
+        results.sort_by(by_score_then_index);
+        if let Some(limit) = options.limit {
+            results.truncate(limit);
+        }
+        return results
+            .into_iter()
+            .map(|result| explain_extraction(query, result, options, &options.scorer))
+            .collect();
+    };
+
+    // One ranking per scorer; the length filter doesn't depend on the scorer, so only the
+    // first pass is counted
+    let mut per_scorer = Vec::with_capacity(fusion.metrics.len());
+    for (i, scorer) in fusion.metrics.iter().enumerate() {
+        let mut uncounted = PrefilterCounts::default();
+        let counts = if i == 0 { &mut *counts } else { &mut uncounted };
+        let mut results = score_choices(query, choices, options, scorer, counts)?;
+        results.sort_by(by_score_then_index);
+        per_scorer.push(results);
+    }
+    let rankings: Vec<Vec<usize>> = per_scorer
+        .iter()
+        .map(|results| results.iter().map(|result| result.index).collect())
+        .collect();
+    let order = fuse_rankings(&rankings, fusion.method, fusion.k);
+    // Each scorer's result by choice index
+    let by_index: Vec<Vec<Option<&ExtractResult>>> = per_scorer
+        .iter()
+        .map(|results| {
+            let mut by_index = vec![None; choices.len()];
+            for result in results {
+                by_index[result.index] = Some(result);
+            }
+            by_index
+        })
+        .collect();
+
+    order
+        .into_iter()
+        .take(options.limit.unwrap_or(usize::MAX))
+        .filter_map(|index| {
+            // The scorer that scored the choice highest, the first one on ties
+            fusion
+                .metrics
+                .iter()
+                .zip(&by_index)
+                .filter_map(|(scorer, by_index)| Some((scorer, by_index[index]?)))
+                .reduce(|best, next| {
+                    if next.1.score > best.1.score {
+                        next
+                    } else {
+                        best
+                    }
+                })
+                .map(|(scorer, result)| explain_extraction(query, result.clone(), options, scorer))
+        })
+        .collect()
+}
+
+/// Best choice reaching the cutoff, the earliest one on ties (the library's `extractOne`).
+/// With `rank_fusion`, the first choice of the fused ranking.
+pub fn extract_one<S: AsRef<str>>(
+    query: &str,
+    choices: &[S],
+    options: &ExtractOptions,
+) -> Result<Option<ExtractResult>, String> {
+    if options.rank_fusion.is_some() {
+        let options = ExtractOptions {
+            limit: Some(1),
+            ..options.clone()
+        };
+        return Ok(extract(query, choices, &options)?.pop());
+    }
+    let results = score_choices(
+        query,
+        choices,
+        options,
+        &options.scorer,
+        &mut PrefilterCounts::default(),
+    )?;
+    results
+        .into_iter()
+        .fold(None, |best: Option<ExtractResult>, result| match best {
+            Some(best) if best.score >= result.score => Some(best),
+            _ => Some(result),
+        })
+        .map(|best| explain_extraction(query, best, options, &options.scorer))
+        .transpose()
+}
+
+/// Attach the explanation for a returned result when `explain` is set.
+/// Extraction has no prefix bonus, so `raw_score` is the 0-100 score itself.
+fn explain_extraction(
+    query: &str,
+    mut result: ExtractResult,
+    options: &ExtractOptions,
+    scorer: &str,
+) -> Result<ExtractResult, String> {
+    if options.explain {
+        result.explanation = Some(SuggestionExplanation {
+            raw_score: result.score,
+            metric: scorer.to_string(),
+            prefix_bonus_applied: false,
+            bonus_amount: 0.0,
+            normalized_input: options.process(query)?,
+            normalized_candidate: options.process(&result.choice)?,
+            prefilter: Prefilter::Scored,
+        });
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// VARIANT GENERATION
+// ============================================================================
+// Strings exactly k Levenshtein edits from a seed string, for probing score
+// thresholds. Randomness comes from a self-contained SplitMix64 stream, so a
+// seed reproduces the same variants on every platform and with any dependency
+// versions; similarity-validator's random corpus draws from the same stream.
+// Random edits can cancel out or merge (a transposition is two Levenshtein
+// edits), so each candidate is measured with the real metric and kept only at
+// exactly the requested distance.
+
+/// SplitMix64 pseudo-random stream; the same seed always gives the same numbers
+#[derive(Debug, Clone)]
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `range` (half-open, non-empty)
+    pub fn below(&mut self, range: std::ops::Range<usize>) -> usize {
+        range.start + (self.next_u64() % (range.end - range.start) as u64) as usize
+    }
+}
+
+/// An edit `generate_variants` may apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantEdit {
+    Insert,
+    Delete,
+    Substitute,
+    /// Swap two adjacent chars (two Levenshtein edits when they differ)
+    Transpose,
+}
+
+impl VariantEdit {
+    pub const ALL: [VariantEdit; 4] = [
+        VariantEdit::Insert,
+        VariantEdit::Delete,
+        VariantEdit::Substitute,
+        VariantEdit::Transpose,
+    ];
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "insert" => Ok(VariantEdit::Insert),
+            "delete" => Ok(VariantEdit::Delete),
+            "substitute" => Ok(VariantEdit::Substitute),
+            "transpose" => Ok(VariantEdit::Transpose),
+            _ => Err(format!(
+                "Unknown edit type: {} (expected insert, delete, substitute or transpose)",
+                name
+            )),
+        }
+    }
+}
+
+/// Settings for `generate_variants`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantOptions {
+    /// Seed of the SplitMix64 stream
+    pub seed: u64,
+    /// Edits to draw from (default: all four)
+    pub edits: Vec<VariantEdit>,
+    /// Chars to insert and substitute besides those of the input
+    pub extra_chars: String,
+}
+
+impl Default for VariantOptions {
+    fn default() -> Self {
+        VariantOptions {
+            seed: 0,
+            edits: VariantEdit::ALL.to_vec(),
+            extra_chars: String::new(),
+        }
+    }
+}
+
+/// Random attempts per requested variant before settling for fewer
+const VARIANT_ATTEMPTS: usize = 64;
+
+/// Up to `count` distinct strings exactly `distance` Levenshtein edits (over chars) from `s`,
+/// in generation order. Inserted and substituted chars come from `s` plus
+/// `options.extra_chars`. Fewer come back when few strings lie at that distance or the
+/// edits can't reach it, e.g. only transpositions at an odd distance. Distance 0 gives `s`.
+pub fn generate_variants(
+    s: &str,
+    distance: usize,
+    count: usize,
+    options: &VariantOptions,
+) -> Result<Vec<String>, String> {
+    if options.edits.is_empty() {
+        return Err("Variant generation needs at least one edit type".to_string());
+    }
+    if distance == 0 {
+        return Ok(if count == 0 {
+            Vec::new()
+        } else {
+            vec![s.to_string()]
+        });
+    }
+    let base: Vec<char> = s.chars().collect();
+    let mut in_alphabet = HashSet::new();
+    let alphabet: Vec<char> = s
+        .chars()
+        .chain(options.extra_chars.chars())
+        .filter(|&c| in_alphabet.insert(c))
+        .collect();
+
+    let mut rng = SplitMix64(options.seed);
+    let mut variants = Vec::new();
+    let mut found = HashSet::new();
+    for _ in 0..count.saturating_mul(VARIANT_ATTEMPTS) {
+        if variants.len() == count {
+            break;
+        }
+        if let Some(variant) = random_variant(&base, distance, &alphabet, &options.edits, &mut rng)
+        {
+            if found.insert(variant.clone()) {
+                variants.push(variant);
+            }
+        }
+    }
+    Ok(variants)
+}
+
+// Edit a copy of `base` at random until it is exactly `distance` away, giving up once it
+// overshoots or runs out of steps
+fn random_variant(
+    base: &[char],
+    distance: usize,
+    alphabet: &[char],
+    edits: &[VariantEdit],
+    rng: &mut SplitMix64,
+) -> Option<String> {
+    let mut chars = base.to_vec();
+    for _ in 0..2 * distance + 2 {
+        apply_random_edit(&mut chars, alphabet, edits, rng);
+        let reached =
+            rapidfuzz::distance::levenshtein::distance(base.iter().copied(), chars.iter().copied());
+        match reached.cmp(&distance) {
+            std::cmp::Ordering::Equal => return Some(chars.into_iter().collect()),
+            std::cmp::Ordering::Greater => return None,
+            std::cmp::Ordering::Less => {}
+        }
+    }
+    None
+}
+
+fn apply_random_edit(
+    chars: &mut Vec<char>,
+    alphabet: &[char],
+    edits: &[VariantEdit],
+    rng: &mut SplitMix64,
+) {
+    match edits[rng.below(0..edits.len())] {
+        VariantEdit::Insert if !alphabet.is_empty() => {
+            let at = rng.below(0..chars.len() + 1);
+            chars.insert(at, alphabet[rng.below(0..alphabet.len())]);
+        }
+        VariantEdit::Delete if !chars.is_empty() => {
+            chars.remove(rng.below(0..chars.len()));
+        }
+        VariantEdit::Substitute if !chars.is_empty() => {
+            let at = rng.below(0..chars.len());
+            let others: Vec<char> = alphabet
+                .iter()
+                .copied()
+                .filter(|&c| c != chars[at])
+                .collect();
+            if !others.is_empty() {
+                chars[at] = others[rng.below(0..others.len())];
+            }
+        }
+        VariantEdit::Transpose if chars.len() > 1 => {
+            let at = rng.below(0..chars.len() - 1);
+            chars.swap(at, at + 1);
+        }
+        // Not applicable to the current string; the step is spent anyway
+        _ => {}
+    }
+}
@@ -0,0 +1,216 @@
+//! Cancellation of the batch calls, run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use js_sys::{Date, Error, Function, Promise};
+use string_metrics_wasm::{
+    cdist, cdist_cancellable, dedupe, dedupe_cancellable, distance_pairs_cancellable,
+    score_pairs_cancellable, similar_pairs, similar_pairs_cancellable, CancellationToken,
+};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{console_log, wasm_bindgen_test};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, ms: i32) -> JsValue;
+}
+
+// Rows per call in a chunked run, as the TypeScript `_async` batch calls split them
+const CHUNK_ROWS: usize = 4096;
+
+fn rows(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("candidate-{}", i)).collect()
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn error_message<T: std::fmt::Debug>(result: Result<T, wasm_bindgen::JsError>) -> String {
+    let value = JsValue::from(result.expect_err("expected a cancellation error"));
+    Error::from(value).message().into()
+}
+
+// Resolves on a later turn of the event loop, after any timer already due
+async fn next_task() {
+    let promise = Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, 0);
+    });
+    JsFuture::from(promise).await.expect("setTimeout resolves");
+}
+
+// Score `a[i]` against `b[i]` in CHUNK_ROWS-row calls, yielding between them as
+// `score_pairs_async` does. A cancelled run returns the error and the rows completed
+// across the whole run.
+async fn score_in_chunks(
+    a: &[String],
+    b: &[String],
+    token: &CancellationToken,
+) -> Result<usize, (String, usize)> {
+    for start in (0..a.len()).step_by(CHUNK_ROWS) {
+        if start > 0 {
+            next_task().await;
+        }
+        let end = (start + CHUNK_ROWS).min(a.len());
+        let result = score_pairs_cancellable(
+            a[start..end].to_vec(),
+            b[start..end].to_vec(),
+            "jaro_winkler",
+            None,
+            None,
+            -1.0,
+            token,
+        );
+        if result.is_err() {
+            let message = error_message(result);
+            // "CANCELLED: cancelled after <completed> of <chunk rows> rows"
+            let completed: usize = message.split(' ').nth(3).unwrap().parse().unwrap();
+            return Err((message, start + completed));
+        }
+    }
+    Ok(a.len())
+}
+
+#[wasm_bindgen_test]
+fn live_token_scores_every_row() {
+    let token = CancellationToken::new();
+    let scores = score_pairs_cancellable(
+        rows(300),
+        rows(300),
+        "levenshtein",
+        None,
+        None,
+        -1.0,
+        &token,
+    );
+    assert_eq!(scores.unwrap(), vec![1.0; 300]);
+    assert!(!token.cancelled());
+}
+
+#[wasm_bindgen_test]
+fn cdist_scores_every_query_against_every_choice() {
+    let scores = cdist(
+        strings(&["kitten", "flaw"]),
+        strings(&["kitten", "lawn", "flaw"]),
+        "levenshtein",
+        None,
+        None,
+        -1.0,
+    )
+    .unwrap();
+    assert_eq!(scores.len(), 6);
+    assert_eq!((scores[0], scores[5]), (1.0, 1.0));
+    assert_eq!(scores[4], 0.5);
+}
+
+#[wasm_bindgen_test]
+fn similar_pairs_and_dedupe_group_chains_of_near_duplicates() {
+    // abcd/abce and abce/abfe score 0.75, abcd/abfe only 0.5
+    let items = strings(&["abcd", "xyz", "abce", "abfe", "xyz"]);
+    let pairs = similar_pairs(items.clone(), "levenshtein", None, None, 0.75).unwrap();
+    assert_eq!(pairs, vec![0.0, 2.0, 0.75, 1.0, 4.0, 1.0, 2.0, 3.0, 0.75]);
+    let groups = dedupe(items, "levenshtein", None, None, 0.75).unwrap();
+    assert_eq!(groups, vec![0, 1, 0, 0, 1]);
+}
+
+#[wasm_bindgen_test]
+fn cancelled_token_stops_a_large_run_before_any_work() {
+    let token = CancellationToken::new();
+    token.cancel();
+    token.cancel();
+    assert!(token.cancelled());
+
+    let (a, b) = (rows(100_000), rows(100_000));
+    let message = error_message(score_pairs_cancellable(
+        a.clone(),
+        b.clone(),
+        "jaro_winkler",
+        None,
+        None,
+        -1.0,
+        &token,
+    ));
+    assert_eq!(message, "CANCELLED: cancelled after 0 of 100000 rows");
+
+    // The token stays cancelled and can be passed again
+    let message = error_message(distance_pairs_cancellable(
+        a,
+        b,
+        "levenshtein",
+        None,
+        None,
+        -1.0,
+        &token,
+    ));
+    assert!(message.starts_with("CANCELLED: "), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn cancelled_token_stops_cdist_similar_pairs_and_dedupe() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let items = rows(1000);
+
+    let message = error_message(cdist_cancellable(
+        strings(&["candidate"]),
+        items.clone(),
+        "jaro_winkler",
+        None,
+        None,
+        -1.0,
+        &token,
+    ));
+    assert_eq!(message, "CANCELLED: cancelled after 0 of 1000 rows");
+    let message = error_message(similar_pairs_cancellable(
+        items.clone(),
+        "levenshtein",
+        None,
+        None,
+        0.9,
+        &token,
+    ));
+    assert_eq!(message, "CANCELLED: cancelled after 0 of 499500 rows");
+    let message = error_message(dedupe_cancellable(
+        items,
+        "levenshtein",
+        None,
+        None,
+        0.9,
+        &token,
+    ));
+    assert_eq!(message, "CANCELLED: cancelled after 0 of 499500 rows");
+}
+
+#[wasm_bindgen_test]
+async fn timer_cancel_stops_a_chunked_100k_run_partway() {
+    let candidates = rows(100_000);
+    let queries: Vec<String> = candidates.iter().map(|c| c.to_uppercase()).collect();
+    let token = Rc::new(CancellationToken::new());
+    let cancelled_at = Rc::new(Cell::new(f64::NAN));
+    let cancel = {
+        let (token, cancelled_at) = (Rc::clone(&token), Rc::clone(&cancelled_at));
+        Closure::once_into_js(move || {
+            cancelled_at.set(Date::now());
+            token.cancel();
+        })
+    };
+    set_timeout(cancel.unchecked_ref(), 0);
+
+    let (message, completed) = score_in_chunks(&queries, &candidates, &token)
+        .await
+        .expect_err("the timer cancels the run");
+    let latency = Date::now() - cancelled_at.get();
+
+    assert!(message.starts_with("CANCELLED: "), "{}", message);
+    assert!(completed > 0, "the first chunk runs before the timer");
+    assert!(completed < candidates.len(), "{} rows", completed);
+    assert_eq!(completed % CHUNK_ROWS, 0);
+    // A soft bound: reported, not asserted, since it depends on the machine
+    if latency >= 50.0 {
+        console_log!("cancellation took {} ms, over the 50 ms target", latency);
+    }
+}
@@ -0,0 +1,74 @@
+//! Candidate set bytes and their fingerprint check, run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::Error;
+use string_metrics_wasm::{normalization_fingerprint, CandidateSet};
+use wasm_bindgen::{JsError, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// Bytes 5..21 hold the fingerprint, after the "SMCS" magic and the format byte
+const FINGERPRINT: std::ops::Range<usize> = 5..21;
+
+fn error_message(result: Result<CandidateSet, JsError>) -> String {
+    let value = JsValue::from(result.err().expect("expected an error"));
+    Error::from(value).message().into()
+}
+
+fn turkish_cities() -> CandidateSet {
+    let candidates = ["İstanbul", "Ankara", "ISPARTA"]
+        .map(str::to_string)
+        .to_vec();
+    CandidateSet::new(candidates, "default", Some("tr".to_string()))
+}
+
+#[wasm_bindgen_test]
+fn round_trips_candidates_keys_and_fingerprint() {
+    let set = turkish_cities();
+    let bytes = set.to_bytes();
+    let fingerprint = normalization_fingerprint("default", Some("tr".to_string()));
+    assert_eq!(&bytes[FINGERPRINT], fingerprint.as_bytes());
+
+    let loaded = CandidateSet::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.candidates(), set.candidates());
+    assert_eq!(loaded.keys(), ["istanbul", "ankara", "ısparta"]);
+    assert_eq!(loaded.locale().as_deref(), Some("tr"));
+    assert_eq!(loaded.fingerprint(), fingerprint);
+    assert_eq!(loaded.lookup("ıSPARTA"), [2]);
+    assert_eq!(loaded.to_bytes(), bytes);
+}
+
+#[wasm_bindgen_test]
+fn refuses_bytes_with_a_forged_fingerprint() {
+    let mut bytes = turkish_cities().to_bytes();
+    bytes[FINGERPRINT].copy_from_slice(b"0123456789abcdef");
+    let current = normalization_fingerprint("default", Some("tr".to_string()));
+    assert_eq!(
+        error_message(CandidateSet::from_bytes(&bytes)),
+        format!(
+            "FINGERPRINT_MISMATCH: built with normalization fingerprint 0123456789abcdef, \
+             this build has {current}"
+        )
+    );
+}
+
+#[wasm_bindgen_test]
+fn refuses_bytes_whose_fingerprint_belongs_to_another_locale() {
+    // A genuine fingerprint, but of another locale than the one the keys were folded with
+    let mut bytes = turkish_cities().to_bytes();
+    let stale = normalization_fingerprint("default", None);
+    bytes[FINGERPRINT].copy_from_slice(stale.as_bytes());
+    assert!(error_message(CandidateSet::from_bytes(&bytes)).starts_with("FINGERPRINT_MISMATCH: "));
+}
+
+#[wasm_bindgen_test]
+fn rejects_malformed_bytes() {
+    let bytes = turkish_cities().to_bytes();
+    assert_eq!(
+        error_message(CandidateSet::from_bytes(&bytes[..bytes.len() - 1])),
+        "Invalid candidate set: truncated"
+    );
+    assert_eq!(
+        error_message(CandidateSet::from_bytes(b"SMIX")),
+        "Invalid candidate set: missing SMCS header"
+    );
+}
@@ -0,0 +1,228 @@
+//! Module defaults and their precedence, run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Error, Object, Reflect};
+use string_metrics_wasm::{
+    cdist, default_locale, default_preset, distance_pairs, get_default_options,
+    reset_default_options, score_pairs, set_default_options, similar_pairs, Comparator,
+};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// Defaults set for one test and cleared when it ends, pass or fail: every test runs
+// against the same module instance
+struct Defaults;
+
+impl Defaults {
+    fn set(entries: &[(&str, JsValue)]) -> Defaults {
+        set_default_options(object(entries)).expect("valid defaults");
+        Defaults
+    }
+}
+
+impl Drop for Defaults {
+    fn drop(&mut self) {
+        reset_default_options();
+    }
+}
+
+fn object(entries: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+    for (key, value) in entries {
+        Reflect::set(&object, &JsValue::from_str(key), value).unwrap();
+    }
+    object.into()
+}
+
+fn keys(value: &JsValue) -> Vec<String> {
+    Object::keys(value.unchecked_ref::<Object>())
+        .iter()
+        .filter_map(|key| key.as_string())
+        .collect()
+}
+
+fn field(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+fn error_message<T: std::fmt::Debug>(result: Result<T, JsError>) -> String {
+    let value = JsValue::from(result.expect_err("expected an error"));
+    Error::from(value).message().into()
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn ratio_pairs(preset: Option<&str>) -> f64 {
+    score_pairs(
+        strings(&["Café"]),
+        strings(&["cafe"]),
+        "ratio",
+        preset.map(str::to_string),
+        None,
+        -1.0,
+    )
+    .unwrap()[0]
+}
+
+#[wasm_bindgen_test]
+fn stores_validated_defaults_and_hands_out_copies() {
+    let _defaults = Defaults::set(&[
+        ("preset", "default".into()),
+        ("locale", "tr".into()),
+        ("range_unit", "utf16".into()),
+    ]);
+    let defaults = get_default_options();
+    assert_eq!(keys(&defaults), ["preset", "locale", "rangeUnit"]);
+    assert_eq!(field(&defaults, "rangeUnit"), "utf16");
+    Reflect::set(&defaults, &"preset".into(), &"none".into()).unwrap();
+    assert_eq!(field(&get_default_options(), "preset"), "default");
+    assert_eq!(default_preset().as_deref(), Some("default"));
+
+    reset_default_options();
+    assert!(keys(&get_default_options()).is_empty());
+    assert_eq!(default_locale(), None);
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_keys_and_invalid_values_keeping_the_previous_defaults() {
+    let _defaults = Defaults::set(&[("preset", "minimal".into())]);
+    let rejected = [
+        (
+            object(&[("preset", "loud".into())]),
+            "Invalid default preset: loud (expected none, minimal, default, aggressive)",
+        ),
+        (
+            object(&[("scorePrecision", 2.5.into())]),
+            "Invalid default scorePrecision: 2.5 (expected an integer 0-15)",
+        ),
+        (
+            object(&[("max_input_length", 0.into())]),
+            "Invalid default maxInputLength: 0 (expected an integer 1-9007199254740991)",
+        ),
+        (
+            object(&[("rangeUnits", "utf16".into())]),
+            "Unknown default option: rangeUnits",
+        ),
+        ("tr".into(), "Default options must be an object"),
+    ];
+    for (options, expected) in rejected {
+        assert_eq!(error_message(set_default_options(options)), expected);
+    }
+    assert_eq!(keys(&get_default_options()), ["preset"]);
+    assert_eq!(default_preset().as_deref(), Some("minimal"));
+}
+
+#[wasm_bindgen_test]
+fn prefers_the_call_preset_then_the_default_then_none() {
+    let built_in = ratio_pairs(None);
+    assert!(built_in < 1.0);
+    {
+        let _defaults = Defaults::set(&[("preset", "aggressive".into())]);
+        assert_eq!(ratio_pairs(None), 1.0);
+        assert_eq!(ratio_pairs(Some("none")), built_in);
+
+        let mut comparator = Comparator::new("ratio", None, None, -1.0).unwrap();
+        assert_eq!(comparator.score("Café", "cafe").unwrap(), 1.0);
+        let mut explicit = Comparator::new("ratio", Some("none".into()), None, -1.0).unwrap();
+        assert_eq!(explicit.score("Café", "cafe").unwrap(), built_in);
+
+        let scores = cdist(
+            strings(&["Café"]),
+            strings(&["cafe"]),
+            "ratio",
+            None,
+            None,
+            -1.0,
+        );
+        assert_eq!(scores.unwrap(), [1.0]);
+    }
+    assert_eq!(ratio_pairs(None), built_in);
+    assert_eq!(ratio_pairs(Some("aggressive")), 1.0);
+}
+
+#[wasm_bindgen_test]
+fn folds_case_with_the_default_locale_unless_a_call_passes_its_own() {
+    let _defaults = Defaults::set(&[("preset", "default".into()), ("locale", "tr".into())]);
+    let distance = |locale: Option<&str>| {
+        distance_pairs(
+            strings(&["I"]),
+            strings(&["ı"]),
+            "levenshtein",
+            None,
+            locale.map(str::to_string),
+            -1.0,
+        )
+        .unwrap()[0]
+    };
+    assert_eq!(distance(None), 0);
+    assert_eq!(distance(Some("lt")), 1);
+}
+
+#[wasm_bindgen_test]
+fn rounds_returned_scores_to_the_default_precision() {
+    let jaro_winkler = |a: &str, b: &str| {
+        score_pairs(
+            strings(&[a]),
+            strings(&[b]),
+            "jaro_winkler",
+            None,
+            None,
+            -1.0,
+        )
+        .unwrap()[0]
+    };
+    let exact = jaro_winkler("kitten", "sitting");
+    let rounded = (exact * 100.0).round() / 100.0;
+    assert_ne!(exact, rounded);
+
+    let _defaults = Defaults::set(&[("scorePrecision", 2.into())]);
+    assert_eq!(jaro_winkler("kitten", "sitting"), rounded);
+    let mut comparator = Comparator::new("jaro_winkler", None, None, -1.0).unwrap();
+    assert_eq!(comparator.score("kitten", "sitting").unwrap(), rounded);
+    // similar_pairs compares the unrounded score with min_score
+    let pairs = similar_pairs(
+        strings(&["kitten", "sitting"]),
+        "jaro_winkler",
+        None,
+        None,
+        exact,
+    );
+    assert_eq!(pairs.unwrap(), [0.0, 1.0, rounded]);
+}
+
+#[wasm_bindgen_test]
+fn rejects_inputs_longer_than_the_default_maximum() {
+    let _defaults = Defaults::set(&[("maxInputLength", 4.into())]);
+    let pairs = |a: &str, b: &str| {
+        score_pairs(
+            strings(&[a]),
+            strings(&[b]),
+            "levenshtein",
+            None,
+            None,
+            -1.0,
+        )
+    };
+    assert_eq!(pairs("abcd", "abcd").unwrap(), [1.0]);
+    // Counted in UTF-16 code units, as JS string lengths are
+    assert!(pairs("😀😀", "a").is_ok());
+    assert_eq!(
+        error_message(pairs("a", "abcde")),
+        "Input exceeds maxInputLength (5 > 4)"
+    );
+    assert!(error_message(pairs("😀😀a", "a")).contains("(5 > 4)"));
+
+    let mut comparator = Comparator::new("levenshtein", None, None, -1.0).unwrap();
+    assert!(error_message(comparator.distance("abcde", "a")).contains("maxInputLength"));
+    let scores = cdist(
+        strings(&["a"]),
+        strings(&["abcde"]),
+        "ratio",
+        None,
+        None,
+        -1.0,
+    );
+    assert!(error_message(scores).contains("maxInputLength"));
+}
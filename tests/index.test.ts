@@ -1,37 +1,136 @@
 import fs from 'node:fs';
 import path from 'node:path';
 import yaml from 'js-yaml';
-import { describe, expect, it } from 'vitest';
+import { afterEach, describe, expect, it } from 'vitest';
 import {
+  affine_gap_distance,
+  anchored_similarity,
+  CancellationToken,
+  CancelledError,
+  CandidateSet,
+  cdist,
+  Comparator,
   damerau_levenshtein,
+  damerau_levenshtein_graphemes,
+  damerau_levenshtein_weighted,
+  dedupe,
   distance,
+  distance_pairs,
+  distance_pairs_async,
+  did_you_mean,
+  dominant_script,
+  equals_normalized,
+  excerpt_around_range,
   extract,
   extractOne,
+  extractWithBudget,
+  FingerprintMismatchError,
+  fuzzy_find_all,
+  generate_variants,
+  get_default_options,
+  hamming,
+  hamming_bytes,
   indel_distance,
+  indel_editops,
   indel_normalized_similarity,
+  is_mixed_script,
+  contains_bidi_controls,
+  coverage,
+  coverage_score,
+  dice,
+  fold_numeric_tokens,
+  jaccard_ngram,
+  jaro,
+  jaro_tokens,
   jaro_winkler,
+  jaro_winkler_custom,
+  jaro_winkler_tokens,
   lcs_seq_distance,
+  lcs_seq_matching_indices,
   lcs_seq_normalized_similarity,
   lcs_seq_similarity,
   levenshtein,
+  levenshtein_bytes,
+  levenshtein_editops,
+  levenshtein_opcodes,
+  match_profile,
+  mfc_signature,
+  mfc_signature_similarity,
+  mfc_similarity,
+  needs_case_fold,
+  normalization_fingerprint,
+  normalized_affine_gap_similarity,
+  NORMALIZED_HASH_VERSION,
   normalize,
   normalized_damerau_levenshtein,
+  normalized_damerau_levenshtein_graphemes,
+  normalized_damerau_levenshtein_weighted,
+  normalized_hamming_bytes,
+  normalized_hamming_similarity,
+  normalized_hash,
+  normalized_key,
   normalized_levenshtein,
+  normalized_levenshtein_bytes,
   normalized_osa_similarity,
   osa_distance,
   partialRatio,
+  positional_diff,
+  postfix_distance,
+  postfix_normalized_similarity,
+  prefix_distance,
+  prefix_normalized_similarity,
   ratio,
+  reset_default_options,
   score,
+  score_nullable,
+  segment_words,
+  score_pairs,
+  score_pairs_async,
+  script_breakdown,
+  set_default_options,
+  similar_pairs,
+  StreamMatcher,
+  subsequence_coverage_score,
   substringSimilarity,
+  substring_all_ranges,
+  PAIR_CHUNK_ROWS,
+  SELECTION_HISTORY_LIMIT,
+  SuggestSession,
   suggest,
   tokenSetRatio,
   tokenSortRatio,
+  transfer_case,
+  weighted_jaccard,
+  window_scores,
+  word_diff,
+  type Anchor,
+  type MissingPolicy,
+  type NullableScore,
   type NormalizationPreset,
   type NormalizationLocale,
+  type NormalizeOptions,
+  type QueryMode,
+  type ScorerFunction,
+  type ShortStringPolicy,
+  type DidYouMean,
   type DistanceMetric,
+  type Expansions,
+  type ExtractOptions,
+  type ExtractResult,
+  type FusionMethod,
+  type FuzzyMatch,
+  type MatchClass,
+  type MatchProfileOptions,
+  type RankFusion,
   type SimilarityMetric,
   type SuggestMetric,
+  type Suggestion,
+  type SuggestionCandidate,
+  type SuggestionExplanation,
+  type TokenAggregation,
+  type WordDiffOptions,
 } from '../src/index';
+import wasm from '../src/wasm.js';
 
 // Version consistency test
 describe('Version consistency', () => {
@@ -51,36 +150,257 @@ describe('Version consistency', () => {
 // Base test case type
 interface BaseTestCase {
   description: string;
+  expect_error?: string;
+  skip?: boolean;
+  skip_reason?: string;
+  only?: boolean;
+}
+
+// Fixture `skip` / `only` markers map onto vitest's own
+function fixtureTest(testCase: BaseTestCase, skip = false) {
+  if (testCase.only) {
+    return it.only;
+  }
+  return it.skipIf(skip || testCase.skip === true);
+}
+
+// Inclusive bounds for scores whose exact value is implementation-defined
+interface ScoreRange {
+  min: number;
+  max: number;
+}
+
+// Score expectation: an exact value, a range, or both
+interface ScoreExpectation {
+  expected_score?: number;
+  expected_score_range?: ScoreRange;
+}
+
+function expectScore(actual: number, tc: ScoreExpectation): void {
+  if (tc.expected_score !== undefined) {
+    expect(actual).toBeCloseTo(tc.expected_score, 10);
+  }
+  if (tc.expected_score_range) {
+    expect(actual).toBeGreaterThanOrEqual(tc.expected_score_range.min);
+    expect(actual).toBeLessThanOrEqual(tc.expected_score_range.max);
+  }
+}
+
+// Fixtures may assert any subset of the explanation fields
+function expectExplanation(
+  actual: SuggestionExplanation | undefined,
+  expected: Partial<SuggestionExplanation>,
+): void {
+  expect(actual).toBeDefined();
+  for (const [field, value] of Object.entries(expected)) {
+    const actualValue = actual?.[field as keyof SuggestionExplanation];
+    if (typeof value === 'number') {
+      expect(actualValue).toBeCloseTo(value, 10);
+    } else {
+      expect(actualValue).toBe(value);
+    }
+  }
 }
 
 // Distance metric test cases (levenshtein, damerau_osa, damerau_unrestricted, indel, lcs_seq)
-interface DistanceTestCase extends BaseTestCase {
+interface DistanceTestCase extends BaseTestCase, ScoreExpectation {
   input_a: string;
   input_b: string;
   expected_distance: number;
-  expected_score: number;
   expected_similarity?: number;
 }
 
+// Hamming test cases; unequal lengths need pad
+interface HammingTestCase extends DistanceTestCase {
+  pad?: boolean;
+}
+
+// Dice and Jaccard n-gram test cases; n only applies to jaccard_ngram
+interface NgramTestCase extends BaseTestCase, ScoreExpectation {
+  input_a: string;
+  input_b: string;
+  n?: number;
+}
+
+// Levenshtein editops and opcodes test cases, with snake_case position keys
+interface EditOperationsTestCase extends BaseTestCase {
+  input_a: string;
+  input_b: string;
+  expected: Array<Record<string, string | number>>;
+}
+
 // Jaro-Winkler test cases
-interface JaroWinklerTestCase extends BaseTestCase {
+interface JaroWinklerTestCase extends BaseTestCase, ScoreExpectation {
+  input_a: string;
+  input_b: string;
+}
+
+// Weighted Damerau-Levenshtein: `expected` is the total cost
+interface DamerauWeightedTestCase extends BaseTestCase, ScoreExpectation {
+  input_a: string;
+  input_b: string;
+  transposition_cost: number;
+  expected: number;
+}
+
+interface AffineGapTestCase extends BaseTestCase, ScoreExpectation {
+  input_a: string;
+  input_b: string;
+  mismatch: number;
+  gap_open: number;
+  gap_extend: number;
+  expected: number;
+}
+
+// Most-frequent-k-characters test cases (expected holds both signatures)
+interface MfcTestCase extends BaseTestCase, ScoreExpectation {
   input_a: string;
   input_b: string;
-  expected_score: number;
+  k: number;
+  expected?: [string, string];
+}
+
+interface JaroWinklerParamsTestCase extends JaroWinklerTestCase {
+  prefix_scale: number;
+  max_prefix: number;
+}
+
+interface JaroTokensTestCase extends JaroWinklerTestCase {
+  prefix_scale?: number;
+  match_threshold?: number;
+  tokenizer?: 'whitespace' | 'words';
+}
+
+// Fuzzy search test cases
+interface FuzzyFindTestCase extends BaseTestCase {
+  haystack: string;
+  needle: string;
+  max_edits: number;
+  expected: FuzzyMatch[];
 }
 
 // Substring similarity test cases
-interface SubstringTestCase extends BaseTestCase {
+interface SubstringTestCase extends BaseTestCase, ScoreExpectation {
   needle: string;
   haystack: string;
-  expected_score: number;
+  range_unit?: 'char' | 'utf16';
+  tie_policy?: 'first' | 'last';
   expected_range?: { start: number; end: number };
+  expected_ranges?: Array<{ start: number; end: number }>;
+}
+
+// Coverage test cases (contiguous and gap-tolerant, with substring_similarity for contrast)
+interface CoverageTestCase extends BaseTestCase {
+  needle: string;
+  haystack: string;
+  range_unit?: 'char' | 'utf16';
+  tie_policy?: 'first' | 'last';
+  expected: {
+    score?: number;
+    range?: { start: number; end: number } | null;
+    subsequence_score?: number;
+    subsequence_ranges?: Array<{ start: number; end: number }>;
+    substring_score?: number;
+  };
+}
+
+// Window scores test cases; window and step count code points
+interface WindowScoresTestCase extends BaseTestCase {
+  needle: string;
+  haystack: string;
+  metric: SimilarityMetric;
+  window?: number;
+  step?: number;
+  expected: number[];
+}
+
+// Empty-input test cases; every metric scores the inputs after the preset
+interface EmptyInputsTestCase extends BaseTestCase {
+  input_a: string;
+  input_b: string;
+  preset?: NormalizationPreset;
+  expected: {
+    scores: Record<string, number>;
+    unified_scores: Record<string, number>;
+    unified_distances: Record<string, number>;
+    substring_score: number;
+  };
+}
+
+// Whitespace-separated tokens weighing 1 each, as list_metrics() scores weighted_jaccard
+const unitWeights = (s: string): Array<[string, number]> =>
+  s
+    .split(/\s+/)
+    .filter((token) => token !== '')
+    .map((token) => [token, 1]);
+
+// Registered similarities by export name, with the parameters the core registry scores them at
+const REGISTERED_SCORES: Record<string, (a: string, b: string) => number> = {
+  normalized_levenshtein,
+  normalized_osa_similarity,
+  normalized_damerau_levenshtein,
+  normalized_damerau_levenshtein_graphemes,
+  normalized_damerau_levenshtein_weighted: (a, b) =>
+    normalized_damerau_levenshtein_weighted(a, b, 0.5),
+  mfc_similarity: (a, b) => mfc_similarity(a, b, 2),
+  normalized_affine_gap_similarity: (a, b) => normalized_affine_gap_similarity(a, b, 1, 3, 0.5),
+  jaro,
+  jaro_winkler,
+  jaro_winkler_with_params: (a, b) =>
+    jaro_winkler_custom(a, b, { prefix_scale: 0.1, max_prefix: 4 }),
+  jaro_tokens: (a, b) => jaro_tokens(a, b),
+  jaro_winkler_tokens: (a, b) => jaro_winkler_tokens(a, b),
+  ratio,
+  normalized_hamming_similarity_with_padding: (a, b) => normalized_hamming_similarity(a, b, true),
+  prefix_normalized_similarity,
+  postfix_normalized_similarity,
+  dice,
+  jaccard_ngram: (a, b) => jaccard_ngram(a, b),
+  indel_normalized_similarity,
+  lcs_seq_normalized_similarity,
+  coverage_score,
+  subsequence_coverage_score,
+  normalized_levenshtein_bytes,
+  normalized_hamming_bytes: (a, b) => normalized_hamming_bytes(a, b, true),
+  weighted_jaccard: (a, b) => weighted_jaccard(unitWeights(a), unitWeights(b)),
+  anchored_similarity: (a, b) => anchored_similarity(a, b, { side: 'start', length: 1 }),
+  score_nullable: (a, b) => score_nullable(a, b).score ?? NaN,
+};
+
+// Anchored similarity test cases; length counts code points
+interface AnchoredSimilarityTestCase extends BaseTestCase, ScoreExpectation {
+  input_a: string;
+  input_b: string;
+  metric: SimilarityMetric;
+  side: Anchor['side'];
+  length: number;
+  preset?: NormalizationPreset;
+  locale?: string;
+}
+
+// Nullable-field test cases; an omitted or null input is a missing value
+interface ScoreNullableTestCase extends BaseTestCase {
+  input_a?: string | null;
+  input_b?: string | null;
+  metric: SimilarityMetric;
+  missing?: MissingPolicy;
+  preset?: NormalizationPreset;
+  locale?: string;
+  expected: NullableScore;
+}
+
+// Optional normalization settings on normalization_presets and normalized_key cases
+interface NormalizationOptionFields {
+  strip_ignorables?: boolean;
+  keep_joiners?: boolean;
+  fold_numbers?: boolean;
 }
 
 // Normalization preset test cases
-interface NormalizationTestCase extends BaseTestCase {
+interface NormalizationTestCase extends BaseTestCase, NormalizationOptionFields {
   input: string;
   preset: string;
+  locale?: string;
   expected: string;
 }
 
@@ -92,6 +412,106 @@ interface NormalizationLocaleTestCase extends BaseTestCase {
   expected: string;
 }
 
+// Normalized key test cases (key, hash as 16 hex digits, equals_normalized with `other`)
+interface NormalizedKeyTestCase extends BaseTestCase, NormalizationOptionFields {
+  input: string;
+  preset: string;
+  locale?: string;
+  other?: string;
+  expected: {
+    key?: string;
+    hash?: string;
+    equals?: boolean;
+  };
+}
+
+// Numeric token test cases (fold_numeric_tokens)
+interface NumericTokensTestCase extends BaseTestCase {
+  input: string;
+  expected: string;
+}
+
+// Case transfer test cases
+interface CaseTransferTestCase extends BaseTestCase {
+  pattern: string;
+  target: string;
+  locale?: NormalizationLocale;
+  expected: string;
+}
+
+// Word diff test cases; expected tokens carry their trailing whitespace
+interface WordDiffTestCase extends BaseTestCase {
+  input_a: string;
+  input_b: string;
+  tokenizer?: WordDiffOptions['tokenizer'];
+  metric?: SimilarityMetric;
+  threshold?: number;
+  expected: Array<{ op: string; a_tokens: string[]; b_tokens: string[]; score?: number }>;
+}
+
+// Match profile test cases; one class per candidate code point
+interface MatchProfileTestCase extends BaseTestCase {
+  query: string;
+  candidate: string;
+  preset?: string;
+  locale?: string;
+  expected: MatchClass[];
+}
+
+// Positional diff test cases; positions count code points
+interface PositionalDiffTestCase extends BaseTestCase {
+  input_a: string;
+  input_b: string;
+  pad?: boolean;
+  expected: { distance: number; differing_positions: number[] };
+}
+
+// Excerpt test cases; ranges count range_unit
+interface ExcerptTestCase extends BaseTestCase {
+  input: string;
+  range: { start: number; end: number };
+  context_chars: number;
+  ellipsis?: string;
+  range_unit?: 'char' | 'utf16';
+  expected: { text: string; range: { start: number; end: number } };
+}
+
+const runExcerptCase = (tc: ExcerptTestCase) =>
+  excerpt_around_range(tc.input, [tc.range.start, tc.range.end], tc.context_chars, tc.ellipsis, {
+    rangeUnit: tc.range_unit,
+  });
+
+// Byte metric test cases; inputs are standard base64
+interface ByteMetricsTestCase extends BaseTestCase, ScoreExpectation {
+  bytes_a: string;
+  bytes_b: string;
+  metric: 'levenshtein' | 'hamming';
+  pad?: boolean;
+  expected_distance?: number;
+}
+
+// Weighted Jaccard test cases
+interface WeightedJaccardTestCase extends BaseTestCase, ScoreExpectation {
+  tokens_a: Record<string, number>;
+  tokens_b: Record<string, number>;
+  preset?: NormalizationPreset;
+  locale?: string;
+}
+
+const runWeightedJaccardCase = (tc: WeightedJaccardTestCase) =>
+  weighted_jaccard(tc.tokens_a, tc.tokens_b, { preset: tc.preset, locale: tc.locale });
+
+// Script detection test cases
+interface ScriptDetectionTestCase extends BaseTestCase {
+  input: string;
+  expected: {
+    dominant?: string;
+    mixed?: boolean;
+    breakdown?: Record<string, number>;
+    bidi_controls?: boolean;
+  };
+}
+
 // Ratio test cases (ratio, partial_ratio, token_sort_ratio, token_set_ratio)
 interface RatioTestCase extends BaseTestCase {
   input_a: string;
@@ -99,22 +519,59 @@ interface RatioTestCase extends BaseTestCase {
   expected_ratio: number;
 }
 
-// Extract one test cases
-interface ExtractOneTestCase extends BaseTestCase {
-  query: string;
-  choices: string[];
-  score_cutoff?: number;
-  expected_choice: string | null;
-  expected_index?: number;
+// rank_fusion in suggestions and extract options; metrics are fixture (snake_case) names
+interface FixtureRankFusion {
+  metrics: string[];
+  method: FusionMethod;
+  k?: number;
+}
+
+// recency_boost in suggestions options
+interface FixtureRecencyBoost {
+  half_life_ms: number;
+  max_boost: number;
+  now_ms: number;
+  allow_boost_past_cutoff?: boolean;
 }
 
-// Extract test cases
+// A suggestions candidate mapping, with snake_case keys
+type FixtureCandidate = Omit<SuggestionCandidate, 'lastSelectedMs'> & {
+  last_selected_ms?: number;
+};
+
+// Extract one test cases
 interface ExtractTestCase extends BaseTestCase {
   query: string;
   choices: string[];
-  score_cutoff?: number;
-  limit?: number;
-  expected_results: Array<{ choice: string; index: number }>;
+  options: {
+    scorer?: string;
+    processor?: string;
+    score_cutoff?: number;
+    limit?: number;
+    explain?: boolean;
+    query_mode?: QueryMode;
+    token_aggregation?: TokenAggregation;
+    expansions?: Expansions;
+    short_string_policy?: ShortStringPolicy;
+    short_string_length?: number;
+    min_candidate_len?: number;
+    max_candidate_len?: number;
+    max_length_ratio?: number;
+    rank_fusion?: FixtureRankFusion;
+  };
+  expected?: Array<{
+    choice: string;
+    score?: number;
+    index: number;
+    explanation?: Partial<SuggestionExplanation>;
+  }>;
+}
+
+// Did-you-mean test cases; a none case lists neither value nor score
+interface DidYouMeanTestCase extends BaseTestCase {
+  input: string;
+  candidates: string[];
+  expected: DidYouMean;
 }
 
 // Unified distance test cases
@@ -122,21 +579,33 @@ interface UnifiedDistanceTestCase extends BaseTestCase {
   input_a: string;
   input_b: string;
   metric: string;
+  digraphs?: string[];
+  options?: UnifiedCaseOptions;
   expected: number;
 }
 
+// Per-case options on unified cases, run through a Comparator as the validator does
+interface UnifiedCaseOptions {
+  preset?: NormalizationPreset;
+  locale?: NormalizationLocale;
+  score_cutoff?: number;
+}
+
 // Unified score test cases
 interface UnifiedScoreTestCase extends BaseTestCase {
   input_a: string;
   input_b: string;
   metric: string;
+  digraphs?: string[];
+  options?: UnifiedCaseOptions;
   expected: number;
 }
 
 // Suggestion test cases
 interface SuggestionTestCase extends BaseTestCase {
   input: string;
-  candidates: string[];
+  candidates: Array<string | FixtureCandidate>;
+  locale?: string;
   options: {
     metric: string;
     normalize_preset?: string;
@@ -144,14 +613,29 @@ interface SuggestionTestCase extends BaseTestCase {
     min_score?: number;
     max_suggestions?: number;
     prefer_prefix?: boolean;
+    prefix_bonus_weight?: number;
     jaro_prefix_scale?: number;
     jaro_max_prefix?: number;
+    explain?: boolean;
+    query_mode?: QueryMode;
+    token_aggregation?: TokenAggregation;
+    expansions?: Expansions;
+    short_string_policy?: ShortStringPolicy;
+    short_string_length?: number;
+    min_candidate_len?: number;
+    max_candidate_len?: number;
+    max_length_ratio?: number;
+    segment_input?: boolean;
+    rank_fusion?: FixtureRankFusion;
+    recency_boost?: FixtureRecencyBoost;
   };
   expected: Array<{
     value: string;
+    id?: string;
     score: number;
     matched_range?: { start: number; end: number };
     normalized_value?: string;
+    explanation?: Partial<SuggestionExplanation>;
   }>;
 }
 
@@ -159,11 +643,11 @@ interface SuggestionTestCase extends BaseTestCase {
 type TestCase =
   | DistanceTestCase
   | JaroWinklerTestCase
+  | JaroWinklerParamsTestCase
   | SubstringTestCase
   | NormalizationTestCase
   | NormalizationLocaleTestCase
   | RatioTestCase
-  | ExtractOneTestCase
   | ExtractTestCase
   | UnifiedDistanceTestCase
   | UnifiedScoreTestCase
@@ -179,6 +663,28 @@ interface FixtureDocument {
 
 // Helper to convert snake_case metric names from fixtures to camelCase for API
 // Returns string to be compatible with both DistanceMetric and SimilarityMetric
+const UNIFIED_OPTIONS = ['preset', 'locale', 'score_cutoff'];
+
+// A Comparator for a unified case's options; undefined when the case has none
+function toUnifiedComparator(
+  metric: string,
+  options: UnifiedCaseOptions | undefined,
+): Comparator | undefined {
+  if (options === undefined) {
+    return undefined;
+  }
+  // The validator rejects unknown keys as typos; so does the suite
+  const unknown = Object.keys(options).find((key) => !UNIFIED_OPTIONS.includes(key));
+  if (unknown !== undefined) {
+    throw new Error(`Unknown unified option: ${unknown}`);
+  }
+  return new Comparator(toCamelCaseMetric(metric) as SimilarityMetric, {
+    preset: options.preset,
+    locale: options.locale,
+    scoreCutoff: options.score_cutoff,
+  });
+}
+
 function toCamelCaseMetric(snakeCase: string): string {
   const mapping: Record<string, string> = {
     damerau_levenshtein: 'damerauLevenshtein',
@@ -193,6 +699,176 @@ function toCamelCaseMetric(snakeCase: string): string {
   return mapping[snakeCase] || snakeCase;
 }
 
+function toNormalizeOptions(tc: NormalizationOptionFields): NormalizeOptions {
+  return {
+    stripIgnorables: tc.strip_ignorables,
+    keepJoiners: tc.keep_joiners,
+    foldNumbers: tc.fold_numbers,
+  };
+}
+
+// Fixture extract options name the scorer and processor; the API takes functions.
+// Scorers other than ratio are unified score metrics on the 0-100 scale.
+function toExtractScorer(scorer: string): ScorerFunction {
+  return scorer === 'ratio' ? ratio : (a, b) => score(a, b, scorer as SimilarityMetric) * 100;
+}
+
+function toExtractOptions(tc: ExtractTestCase): ExtractOptions {
+  const preset = (tc.options.processor ?? 'none') as NormalizationPreset;
+  const fusion = tc.options.rank_fusion;
+  return {
+    scorer: toExtractScorer(tc.options.scorer ?? 'ratio'),
+    processor: (value) =>
+      normalize(value, preset, undefined, { expansions: tc.options.expansions }),
+    scoreCutoff: tc.options.score_cutoff ?? 0,
+    limit: tc.options.limit,
+    explain: tc.options.explain,
+    queryMode: tc.options.query_mode,
+    tokenAggregation: tc.options.token_aggregation,
+    shortStringPolicy: tc.options.short_string_policy,
+    shortStringLength: tc.options.short_string_length,
+    minCandidateLen: tc.options.min_candidate_len,
+    maxCandidateLen: tc.options.max_candidate_len,
+    maxLengthRatio: tc.options.max_length_ratio,
+    rankFusion: fusion && { ...fusion, metrics: fusion.metrics.map(toExtractScorer) },
+  };
+}
+
+function toMatchProfileOptions(tc: MatchProfileTestCase): MatchProfileOptions {
+  return {
+    preset: tc.preset as NormalizationPreset | undefined,
+    locale: tc.locale as NormalizationLocale | undefined,
+  };
+}
+
+function toWordDiffOptions(tc: WordDiffTestCase): WordDiffOptions {
+  return { tokenizer: tc.tokenizer, metric: tc.metric, threshold: tc.threshold };
+}
+
+function runAnchoredCase(tc: AnchoredSimilarityTestCase): number {
+  return anchored_similarity(tc.input_a, tc.input_b, { side: tc.side, length: tc.length }, tc.metric, {
+    preset: tc.preset,
+    locale: tc.locale as NormalizationLocale | undefined,
+  });
+}
+
+function runNullableCase(tc: ScoreNullableTestCase): NullableScore {
+  return score_nullable(tc.input_a, tc.input_b, tc.metric, {
+    missing: tc.missing,
+    preset: tc.preset,
+    locale: tc.locale as NormalizationLocale | undefined,
+  });
+}
+
+// Buffer.from() silently skips bad characters, so check the alphabet first
+function decodeBase64(field: string, value: string): Uint8Array {
+  if (value.length % 4 !== 0 || !/^[A-Za-z0-9+/]*={0,2}$/.test(value)) {
+    throw new Error(`${field} is not valid base64`);
+  }
+  return new Uint8Array(Buffer.from(value, 'base64'));
+}
+
+function runByteMetricsCase(tc: ByteMetricsTestCase): { distance: number; score: number } {
+  const a = decodeBase64('bytes_a', tc.bytes_a);
+  const b = decodeBase64('bytes_b', tc.bytes_b);
+  if (tc.metric === 'hamming') {
+    const pad = tc.pad ?? false;
+    return { distance: hamming_bytes(a, b, pad), score: normalized_hamming_bytes(a, b, pad) };
+  }
+  return { distance: levenshtein_bytes(a, b), score: normalized_levenshtein_bytes(a, b) };
+}
+
+// Invoke the API for a negative (expect_error) fixture case
+function runErrorCase(category: string, testCase: TestCase): unknown {
+  if (category === 'unified_score') {
+    const tc = testCase as UnifiedScoreTestCase;
+    const comparator = toUnifiedComparator(tc.metric, tc.options);
+    if (comparator !== undefined) {
+      return comparator.score(tc.input_a, tc.input_b);
+    }
+    return score(tc.input_a, tc.input_b, tc.metric as SimilarityMetric, {
+      digraphs: tc.digraphs,
+    });
+  }
+  if (category === 'unified_distance') {
+    const tc = testCase as UnifiedDistanceTestCase;
+    const comparator = toUnifiedComparator(tc.metric, tc.options);
+    if (comparator !== undefined) {
+      return comparator.distance(tc.input_a, tc.input_b);
+    }
+    return distance(tc.input_a, tc.input_b, tc.metric as DistanceMetric, {
+      digraphs: tc.digraphs,
+    });
+  }
+  if (category === 'normalization_presets') {
+    const tc = testCase as NormalizationTestCase;
+    return normalize(tc.input, tc.preset as NormalizationPreset);
+  }
+  if (category === 'affine_gap') {
+    const tc = testCase as AffineGapTestCase;
+    return affine_gap_distance(tc.input_a, tc.input_b, tc.mismatch, tc.gap_open, tc.gap_extend);
+  }
+  if (category === 'fuzzy_find') {
+    const tc = testCase as FuzzyFindTestCase;
+    return fuzzy_find_all(tc.haystack, tc.needle, tc.max_edits);
+  }
+  if (category === 'extract_one' || category === 'extract') {
+    const tc = testCase as ExtractTestCase;
+    return extract(tc.query, tc.choices, toExtractOptions(tc));
+  }
+  if (category === 'word_diff') {
+    const tc = testCase as WordDiffTestCase;
+    return word_diff(tc.input_a, tc.input_b, toWordDiffOptions(tc));
+  }
+  if (category === 'match_profile') {
+    const tc = testCase as MatchProfileTestCase;
+    return match_profile(tc.query, tc.candidate, toMatchProfileOptions(tc));
+  }
+  if (category === 'positional_diff') {
+    const tc = testCase as PositionalDiffTestCase;
+    return positional_diff(tc.input_a, tc.input_b, { pad: tc.pad });
+  }
+  if (category === 'byte_metrics') {
+    return runByteMetricsCase(testCase as ByteMetricsTestCase);
+  }
+  if (category === 'hamming') {
+    const tc = testCase as HammingTestCase;
+    return hamming(tc.input_a, tc.input_b, tc.pad);
+  }
+  if (category === 'jaccard_ngram') {
+    const tc = testCase as NgramTestCase;
+    return jaccard_ngram(tc.input_a, tc.input_b, { n: tc.n });
+  }
+  if (category === 'weighted_jaccard') {
+    return runWeightedJaccardCase(testCase as WeightedJaccardTestCase);
+  }
+  if (category === 'excerpt') {
+    return runExcerptCase(testCase as ExcerptTestCase);
+  }
+  if (category === 'window_scores') {
+    const tc = testCase as WindowScoresTestCase;
+    return window_scores(tc.needle, tc.haystack, tc.metric, { window: tc.window, step: tc.step });
+  }
+  if (category === 'anchored_similarity') {
+    return runAnchoredCase(testCase as AnchoredSimilarityTestCase);
+  }
+  if (category === 'score_nullable') {
+    return runNullableCase(testCase as ScoreNullableTestCase);
+  }
+  throw new Error(`expect_error is not supported for category: ${category}`);
+}
+
+// The WASM normalize() still passes unknown presets through instead of throwing
+const ERRORS_NOT_YET_RAISED = new Set(['normalization_presets', 'empty_inputs']);
+
+// Unified metrics the wrapper's score() and distance() don't resolve by name
+const VALIDATOR_ONLY_METRICS = new Set(['hamming', 'prefix', 'postfix', 'dice', 'jaccard_ngram']);
+
+// A unified_* case selecting one of those metrics
+const selectsValidatorOnlyMetric = (category: string, testCase: BaseTestCase): boolean =>
+  (category === 'unified_score' || category === 'unified_distance') &&
+  VALIDATOR_ONLY_METRICS.has((testCase as UnifiedScoreTestCase).metric);
+
 const fixturesRoot = path.join(__dirname, 'fixtures');
 
 if (!fs.existsSync(fixturesRoot)) {
@@ -244,35 +920,103 @@ for (const document of fixtureDocuments) {
 
     describe(suiteName, () => {
       for (const testCase of categoryGroup.cases) {
-        it(testCase.description, () => {
+        if (testCase.expect_error !== undefined) {
+          const expectedError = testCase.expect_error;
+          const notYetRaised = ERRORS_NOT_YET_RAISED.has(categoryGroup.category);
+          fixtureTest(testCase, notYetRaised)(testCase.description, () => {
+            expect(() => runErrorCase(categoryGroup.category, testCase)).toThrow(expectedError);
+          });
+          continue;
+        }
+
+        const validatorOnly = selectsValidatorOnlyMetric(categoryGroup.category, testCase);
+        fixtureTest(testCase, validatorOnly)(testCase.description, () => {
           if (categoryGroup.category === 'levenshtein') {
             const tc = testCase as DistanceTestCase;
             expect(levenshtein(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
-            expect(normalized_levenshtein(tc.input_a, tc.input_b)).toBeCloseTo(
-              tc.expected_score,
-              10,
-            );
+            expectScore(normalized_levenshtein(tc.input_a, tc.input_b), tc);
           } else if (categoryGroup.category === 'damerau_osa') {
             const tc = testCase as DistanceTestCase;
             expect(osa_distance(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
-            expect(normalized_osa_similarity(tc.input_a, tc.input_b)).toBeCloseTo(
-              tc.expected_score,
-              10,
-            );
+            expectScore(normalized_osa_similarity(tc.input_a, tc.input_b), tc);
           } else if (categoryGroup.category === 'damerau_unrestricted') {
             const tc = testCase as DistanceTestCase;
             expect(damerau_levenshtein(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
-            expect(normalized_damerau_levenshtein(tc.input_a, tc.input_b)).toBeCloseTo(
-              tc.expected_score,
+            expectScore(normalized_damerau_levenshtein(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'damerau_graphemes') {
+            const tc = testCase as DistanceTestCase;
+            expect(damerau_levenshtein_graphemes(tc.input_a, tc.input_b)).toBe(
+              tc.expected_distance,
+            );
+            expectScore(normalized_damerau_levenshtein_graphemes(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'damerau_weighted') {
+            const tc = testCase as DamerauWeightedTestCase;
+            expect(
+              damerau_levenshtein_weighted(tc.input_a, tc.input_b, tc.transposition_cost),
+            ).toBeCloseTo(tc.expected, 10);
+            expectScore(
+              normalized_damerau_levenshtein_weighted(
+                tc.input_a,
+                tc.input_b,
+                tc.transposition_cost,
+              ),
+              tc,
+            );
+          } else if (categoryGroup.category === 'affine_gap') {
+            const tc = testCase as AffineGapTestCase;
+            const penalties = [tc.mismatch, tc.gap_open, tc.gap_extend] as const;
+            expect(affine_gap_distance(tc.input_a, tc.input_b, ...penalties)).toBeCloseTo(
+              tc.expected,
               10,
             );
+            expectScore(normalized_affine_gap_similarity(tc.input_a, tc.input_b, ...penalties), tc);
+          } else if (categoryGroup.category === 'mfc') {
+            const tc = testCase as MfcTestCase;
+            const sigA = mfc_signature(tc.input_a, tc.k);
+            const sigB = mfc_signature(tc.input_b, tc.k);
+            if (tc.expected) {
+              expect([sigA, sigB]).toEqual(tc.expected);
+            }
+            expectScore(mfc_similarity(tc.input_a, tc.input_b, tc.k), tc);
+            expectScore(mfc_signature_similarity(sigA, sigB), tc);
           } else if (categoryGroup.category === 'jaro_winkler') {
             const tc = testCase as JaroWinklerTestCase;
-            expect(jaro_winkler(tc.input_a, tc.input_b)).toBeCloseTo(tc.expected_score, 10);
+            expectScore(jaro_winkler(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'jaro_winkler_params') {
+            const tc = testCase as JaroWinklerParamsTestCase;
+            expectScore(
+              jaro_winkler_custom(tc.input_a, tc.input_b, {
+                prefix_scale: tc.prefix_scale,
+                max_prefix: tc.max_prefix,
+              }),
+              tc,
+            );
+          } else if (categoryGroup.category === 'jaro_tokens') {
+            const tc = testCase as JaroTokensTestCase;
+            expectScore(
+              jaro_tokens(tc.input_a, tc.input_b, {
+                match_threshold: tc.match_threshold,
+                tokenizer: tc.tokenizer,
+              }),
+              tc,
+            );
+          } else if (categoryGroup.category === 'jaro_winkler_tokens') {
+            const tc = testCase as JaroTokensTestCase;
+            expectScore(
+              jaro_winkler_tokens(tc.input_a, tc.input_b, {
+                prefix_scale: tc.prefix_scale,
+                match_threshold: tc.match_threshold,
+                tokenizer: tc.tokenizer,
+              }),
+              tc,
+            );
           } else if (categoryGroup.category === 'substring') {
             const tc = testCase as SubstringTestCase;
-            const result = substringSimilarity(tc.needle, tc.haystack);
-            expect(result.score).toBeCloseTo(tc.expected_score, 10);
+            const result = substringSimilarity(tc.needle, tc.haystack, {
+              rangeUnit: tc.range_unit,
+              tiePolicy: tc.tie_policy,
+            });
+            expectScore(result.score, tc);
             if (tc.expected_range) {
               expect(result.candidateRange).toEqual([
                 tc.expected_range.start,
@@ -281,27 +1025,227 @@ for (const document of fixtureDocuments) {
             } else {
               expect(result.candidateRange).toEqual([0, 0]);
             }
+            if (tc.expected_ranges) {
+              const all = substring_all_ranges(tc.needle, tc.haystack, {
+                rangeUnit: tc.range_unit,
+              });
+              const expected = tc.expected_ranges.map((range) => [range.start, range.end]);
+              expect(all.ranges).toEqual(expected);
+              expect(all.score).toBeCloseTo(result.score, 12);
+            }
+          } else if (categoryGroup.category === 'coverage') {
+            const tc = testCase as CoverageTestCase;
+            const options = { rangeUnit: tc.range_unit, tiePolicy: tc.tie_policy };
+            const toPairs = (ranges: Array<{ start: number; end: number }>) =>
+              ranges.map((range): [number, number] => [range.start, range.end]);
+            const contiguous = coverage(tc.needle, tc.haystack, options);
+            if (tc.expected.score !== undefined) {
+              expect(contiguous.score).toBe(tc.expected.score);
+            }
+            if (tc.expected.range !== undefined) {
+              const expected = tc.expected.range === null ? [] : [tc.expected.range];
+              expect(contiguous.ranges).toEqual(toPairs(expected));
+            }
+            const gapped = coverage(tc.needle, tc.haystack, { ...options, gaps: true });
+            if (tc.expected.subsequence_score !== undefined) {
+              expect(gapped.score).toBe(tc.expected.subsequence_score);
+            }
+            if (tc.expected.subsequence_ranges !== undefined) {
+              expect(gapped.ranges).toEqual(toPairs(tc.expected.subsequence_ranges));
+            }
+            if (tc.expected.substring_score !== undefined) {
+              expect(substringSimilarity(tc.needle, tc.haystack).score).toBeCloseTo(
+                tc.expected.substring_score,
+                12,
+              );
+            }
+          } else if (categoryGroup.category === 'window_scores') {
+            const tc = testCase as WindowScoresTestCase;
+            const scores = window_scores(tc.needle, tc.haystack, tc.metric, {
+              window: tc.window,
+              step: tc.step,
+            });
+            expect(scores).toHaveLength(tc.expected.length);
+            tc.expected.forEach((expected, i) => expect(scores[i]).toBeCloseTo(expected, 12));
+          } else if (categoryGroup.category === 'empty_inputs') {
+            const tc = testCase as EmptyInputsTestCase;
+            const preset = tc.preset ?? 'none';
+            const [a, b] = [normalize(tc.input_a, preset), normalize(tc.input_b, preset)];
+            expect(Object.keys(tc.expected.scores).sort()).toEqual(
+              Object.keys(REGISTERED_SCORES).sort(),
+            );
+            for (const [name, expected] of Object.entries(tc.expected.scores)) {
+              expect(REGISTERED_SCORES[name](a, b), name).toBeCloseTo(expected, 10);
+            }
+            // The unified metrics the library exports; the rest are validator-only
+            for (const [metric, expected] of Object.entries(tc.expected.unified_scores)) {
+              if (!VALIDATOR_ONLY_METRICS.has(metric)) {
+                expect(score(a, b, metric as SimilarityMetric), metric).toBeCloseTo(expected, 10);
+              }
+            }
+            for (const [metric, expected] of Object.entries(tc.expected.unified_distances)) {
+              if (!VALIDATOR_ONLY_METRICS.has(metric)) {
+                expect(distance(a, b, metric as DistanceMetric), metric).toBe(expected);
+              }
+            }
+            expect(substringSimilarity(a, b).score).toBeCloseTo(tc.expected.substring_score, 10);
+            expect(substring_all_ranges(a, b).score).toBeCloseTo(tc.expected.substring_score, 10);
+          } else if (categoryGroup.category === 'anchored_similarity') {
+            const tc = testCase as AnchoredSimilarityTestCase;
+            expectScore(runAnchoredCase(tc), tc);
+          } else if (categoryGroup.category === 'score_nullable') {
+            const tc = testCase as ScoreNullableTestCase;
+            const result = runNullableCase(tc);
+            expect(result.status).toBe(tc.expected.status);
+            if (tc.expected.score === null) {
+              expect(result.score).toBeNull();
+            } else {
+              expect(result.score).toBeCloseTo(tc.expected.score, 12);
+            }
+          } else if (categoryGroup.category === 'fuzzy_find') {
+            const tc = testCase as FuzzyFindTestCase;
+            expect(fuzzy_find_all(tc.haystack, tc.needle, tc.max_edits)).toEqual(tc.expected);
+            // One char per chunk: every match straddles a boundary
+            const matcher = new StreamMatcher(tc.needle, tc.max_edits);
+            const streamed = Array.from(tc.haystack).flatMap((char) => matcher.feed(char));
+            expect([...streamed, ...matcher.finish()]).toEqual(tc.expected);
+            matcher.free();
           } else if (categoryGroup.category === 'normalization_presets') {
             const tc = testCase as NormalizationTestCase;
-            expect(normalize(tc.input, tc.preset as NormalizationPreset)).toBe(tc.expected);
+            expect(
+              normalize(
+                tc.input,
+                tc.preset as NormalizationPreset,
+                tc.locale as NormalizationLocale | undefined,
+                toNormalizeOptions(tc),
+              ),
+            ).toBe(tc.expected);
           } else if (categoryGroup.category === 'normalization_locale') {
             const tc = testCase as NormalizationLocaleTestCase;
             const locale = tc.locale === null ? undefined : (tc.locale as NormalizationLocale);
             expect(normalize(tc.input, tc.preset as NormalizationPreset, locale)).toBe(tc.expected);
+          } else if (categoryGroup.category === 'numeric_tokens') {
+            const tc = testCase as NumericTokensTestCase;
+            expect(fold_numeric_tokens(tc.input)).toBe(tc.expected);
+          } else if (categoryGroup.category === 'case_transfer') {
+            const tc = testCase as CaseTransferTestCase;
+            expect(transfer_case(tc.pattern, tc.target, tc.locale)).toBe(tc.expected);
+          } else if (categoryGroup.category === 'word_diff') {
+            const tc = testCase as WordDiffTestCase;
+            const joined = (tokens: Array<{ text: string; whitespace: string }>) =>
+              tokens.map((token) => token.text + token.whitespace);
+            const ops = word_diff(tc.input_a, tc.input_b, toWordDiffOptions(tc));
+            expect(
+              ops.map((op) => ({
+                op: op.op,
+                a_tokens: joined(op.a_tokens),
+                b_tokens: joined(op.b_tokens),
+                ...(op.score === undefined ? {} : { score: op.score }),
+              })),
+            ).toEqual(tc.expected);
+          } else if (categoryGroup.category === 'match_profile') {
+            const tc = testCase as MatchProfileTestCase;
+            expect(match_profile(tc.query, tc.candidate, toMatchProfileOptions(tc))).toEqual(
+              tc.expected,
+            );
+          } else if (categoryGroup.category === 'positional_diff') {
+            const tc = testCase as PositionalDiffTestCase;
+            const diff = positional_diff(tc.input_a, tc.input_b, { pad: tc.pad });
+            expect({
+              distance: diff.distance,
+              differing_positions: Array.from(diff.differing_positions),
+            }).toEqual(tc.expected);
+          } else if (categoryGroup.category === 'excerpt') {
+            const tc = testCase as ExcerptTestCase;
+            const { text, range } = runExcerptCase(tc);
+            expect({ text, range: { start: range[0], end: range[1] } }).toEqual(tc.expected);
+          } else if (categoryGroup.category === 'byte_metrics') {
+            const tc = testCase as ByteMetricsTestCase;
+            const result = runByteMetricsCase(tc);
+            expect(result.distance).toBe(tc.expected_distance);
+            expectScore(result.score, tc);
+          } else if (categoryGroup.category === 'weighted_jaccard') {
+            const tc = testCase as WeightedJaccardTestCase;
+            expectScore(runWeightedJaccardCase(tc), tc);
+          } else if (categoryGroup.category === 'normalized_key') {
+            const tc = testCase as NormalizedKeyTestCase;
+            const preset = tc.preset as NormalizationPreset;
+            const locale = tc.locale as NormalizationLocale | undefined;
+            const options = toNormalizeOptions(tc);
+            if (tc.expected.key !== undefined) {
+              expect(normalized_key(tc.input, preset, locale, options)).toBe(tc.expected.key);
+            }
+            if (tc.expected.hash !== undefined) {
+              const expectedHash = BigInt(`0x${tc.expected.hash}`);
+              expect(normalized_hash(tc.input, preset, locale, options)).toBe(expectedHash);
+            }
+            if (tc.expected.equals !== undefined && tc.other !== undefined) {
+              const equals = equals_normalized(tc.input, tc.other, preset, locale, options);
+              expect(equals).toBe(tc.expected.equals);
+            }
+          } else if (categoryGroup.category === 'script_detection') {
+            const tc = testCase as ScriptDetectionTestCase;
+            if (tc.expected.dominant !== undefined) {
+              expect(dominant_script(tc.input)).toBe(tc.expected.dominant);
+            }
+            if (tc.expected.mixed !== undefined) {
+              expect(is_mixed_script(tc.input)).toBe(tc.expected.mixed);
+            }
+            if (tc.expected.breakdown !== undefined) {
+              expect(script_breakdown(tc.input)).toEqual(tc.expected.breakdown);
+            }
+            if (tc.expected.bidi_controls !== undefined) {
+              expect(contains_bidi_controls(tc.input)).toBe(tc.expected.bidi_controls);
+            }
           } else if (categoryGroup.category === 'suggestions') {
             const tc = testCase as SuggestionTestCase;
-            const result = suggest(tc.input, tc.candidates, {
+            const candidates = tc.candidates.map((candidate) => {
+              if (typeof candidate === 'string') {
+                return candidate;
+              }
+              const { last_selected_ms, ...rest } = candidate;
+              return { ...rest, lastSelectedMs: last_selected_ms };
+            });
+            const boost = tc.options.recency_boost;
+            const result = suggest(tc.input, candidates, {
               metric: toCamelCaseMetric(tc.options.metric) as SuggestMetric,
               preset: (tc.options.normalize_preset ?? tc.options.preset) as NormalizationPreset,
               minScore: tc.options.min_score,
               maxSuggestions: tc.options.max_suggestions,
               preferPrefix: tc.options.prefer_prefix,
+              prefixBonusWeight: tc.options.prefix_bonus_weight,
               jaroPrefixScale: tc.options.jaro_prefix_scale,
               jaroMaxPrefix: tc.options.jaro_max_prefix,
+              locale: tc.locale as NormalizationLocale | undefined,
+              explain: tc.options.explain,
+              queryMode: tc.options.query_mode,
+              tokenAggregation: tc.options.token_aggregation,
+              expansions: tc.options.expansions,
+              shortStringPolicy: tc.options.short_string_policy,
+              shortStringLength: tc.options.short_string_length,
+              segmentInput: tc.options.segment_input,
+              minCandidateLen: tc.options.min_candidate_len,
+              maxCandidateLen: tc.options.max_candidate_len,
+              maxLengthRatio: tc.options.max_length_ratio,
+              rankFusion: tc.options.rank_fusion && {
+                ...tc.options.rank_fusion,
+                metrics: tc.options.rank_fusion.metrics.map(
+                  (metric) => toCamelCaseMetric(metric) as SuggestMetric,
+                ),
+              },
+              recencyBoost: boost && {
+                halfLifeMs: boost.half_life_ms,
+                maxBoost: boost.max_boost,
+                nowMs: boost.now_ms,
+                allowBoostPastCutoff: boost.allow_boost_past_cutoff,
+              },
             });
             expect(result).toHaveLength(tc.expected.length);
             tc.expected.forEach((exp, i) => {
               expect(result[i].value).toBe(exp.value);
+              if (exp.id !== undefined) {
+                expect(result[i].id).toBe(exp.id);
+              }
               expect(result[i].score).toBeCloseTo(exp.score, 10);
               if (exp.matched_range) {
                 expect(result[i].matchedRange).toEqual(exp.matched_range);
@@ -309,7 +1253,19 @@ for (const document of fixtureDocuments) {
               if (exp.normalized_value) {
                 expect(result[i].normalizedValue).toBe(exp.normalized_value);
               }
+              if (exp.explanation) {
+                expectExplanation(result[i].explanation, exp.explanation);
+              }
             });
+          } else if (categoryGroup.category === 'did_you_mean') {
+            const tc = testCase as DidYouMeanTestCase;
+            const result = did_you_mean(tc.input, tc.candidates);
+            if (result.kind === 'corrected' && tc.expected.kind === 'corrected') {
+              expect(result.score).toBeCloseTo(tc.expected.score, 10);
+              expect(result.value).toBe(tc.expected.value);
+            } else {
+              expect(result).toEqual(tc.expected);
+            }
           } else if (categoryGroup.category === 'ratio') {
             const tc = testCase as RatioTestCase;
             expect(ratio(tc.input_a, tc.input_b)).toBeCloseTo(tc.expected_ratio, 10);
@@ -322,54 +1278,95 @@ for (const document of fixtureDocuments) {
           } else if (categoryGroup.category === 'token_set_ratio') {
             const tc = testCase as RatioTestCase;
             expect(tokenSetRatio(tc.input_a, tc.input_b)).toBeCloseTo(tc.expected_ratio, 10);
+          } else if (categoryGroup.category === 'hamming') {
+            const tc = testCase as HammingTestCase;
+            expect(hamming(tc.input_a, tc.input_b, tc.pad)).toBe(tc.expected_distance);
+            expectScore(normalized_hamming_similarity(tc.input_a, tc.input_b, tc.pad), tc);
+          } else if (categoryGroup.category === 'prefix') {
+            const tc = testCase as DistanceTestCase;
+            expect(prefix_distance(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
+            expectScore(prefix_normalized_similarity(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'postfix') {
+            const tc = testCase as DistanceTestCase;
+            expect(postfix_distance(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
+            expectScore(postfix_normalized_similarity(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'dice') {
+            const tc = testCase as NgramTestCase;
+            expectScore(dice(tc.input_a, tc.input_b), tc);
+          } else if (categoryGroup.category === 'jaccard_ngram') {
+            const tc = testCase as NgramTestCase;
+            expectScore(jaccard_ngram(tc.input_a, tc.input_b, { n: tc.n }), tc);
+          } else if (categoryGroup.category === 'editops') {
+            const tc = testCase as EditOperationsTestCase;
+            expect(levenshtein_editops(tc.input_a, tc.input_b)).toEqual(
+              tc.expected.map((op) => ({ tag: op.tag, srcPos: op.src_pos, destPos: op.dest_pos })),
+            );
+          } else if (categoryGroup.category === 'opcodes') {
+            const tc = testCase as EditOperationsTestCase;
+            expect(levenshtein_opcodes(tc.input_a, tc.input_b)).toEqual(
+              tc.expected.map((op) => ({
+                tag: op.tag,
+                srcStart: op.src_start,
+                srcEnd: op.src_end,
+                destStart: op.dest_start,
+                destEnd: op.dest_end,
+              })),
+            );
           } else if (categoryGroup.category === 'indel') {
             const tc = testCase as DistanceTestCase;
             expect(indel_distance(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
-            expect(indel_normalized_similarity(tc.input_a, tc.input_b)).toBeCloseTo(
-              tc.expected_score,
-              10,
-            );
+            expectScore(indel_normalized_similarity(tc.input_a, tc.input_b), tc);
           } else if (categoryGroup.category === 'lcs_seq') {
             const tc = testCase as DistanceTestCase;
             expect(lcs_seq_distance(tc.input_a, tc.input_b)).toBe(tc.expected_distance);
             expect(lcs_seq_similarity(tc.input_a, tc.input_b)).toBe(tc.expected_similarity);
-            expect(lcs_seq_normalized_similarity(tc.input_a, tc.input_b)).toBeCloseTo(
-              tc.expected_score,
-              10,
-            );
-          } else if (categoryGroup.category === 'extract_one') {
-            const tc = testCase as ExtractOneTestCase;
-            const result = extractOne(tc.query, tc.choices, {
-              scoreCutoff: tc.score_cutoff ?? 0,
-            });
-            if (tc.expected_choice === null) {
-              expect(result).toBeNull();
+            expectScore(lcs_seq_normalized_similarity(tc.input_a, tc.input_b), tc);
+          } else if (
+            categoryGroup.category === 'extract_one' ||
+            categoryGroup.category === 'extract'
+          ) {
+            const tc = testCase as ExtractTestCase;
+            const options = toExtractOptions(tc);
+            let result: ExtractResult[];
+            if (categoryGroup.category === 'extract_one') {
+              // A single best match compares as a one-element list
+              const best = extractOne(tc.query, tc.choices, options);
+              result = best === null ? [] : [best];
             } else {
-              expect(result).not.toBeNull();
-              expect(result?.choice).toBe(tc.expected_choice);
-              expect(result?.index).toBe(tc.expected_index);
+              result = extract(tc.query, tc.choices, options);
             }
-          } else if (categoryGroup.category === 'extract') {
-            const tc = testCase as ExtractTestCase;
-            const result = extract(tc.query, tc.choices, {
-              scoreCutoff: tc.score_cutoff ?? 0,
-              limit: tc.limit,
-            });
-            expect(result).toHaveLength(tc.expected_results.length);
-            tc.expected_results.forEach((exp, i) => {
+            const expected = tc.expected ?? [];
+            expect(result).toHaveLength(expected.length);
+            expected.forEach((exp, i) => {
               expect(result[i].choice).toBe(exp.choice);
               expect(result[i].index).toBe(exp.index);
+              if (exp.score !== undefined) {
+                expect(result[i].score).toBeCloseTo(exp.score, 10);
+              }
+              if (exp.explanation) {
+                expectExplanation(result[i].explanation, exp.explanation);
+              }
             });
           } else if (categoryGroup.category === 'unified_distance') {
             const tc = testCase as UnifiedDistanceTestCase;
-            expect(
-              distance(tc.input_a, tc.input_b, toCamelCaseMetric(tc.metric) as DistanceMetric),
-            ).toBe(tc.expected);
+            const metric = toCamelCaseMetric(tc.metric) as DistanceMetric;
+            const comparator = toUnifiedComparator(tc.metric, tc.options);
+            const actual =
+              comparator === undefined
+                ? distance(tc.input_a, tc.input_b, metric, { digraphs: tc.digraphs })
+                : comparator.distance(tc.input_a, tc.input_b);
+            comparator?.free();
+            expect(actual).toBe(tc.expected);
           } else if (categoryGroup.category === 'unified_score') {
             const tc = testCase as UnifiedScoreTestCase;
-            expect(
-              score(tc.input_a, tc.input_b, toCamelCaseMetric(tc.metric) as SimilarityMetric),
-            ).toBeCloseTo(tc.expected, 10);
+            const metric = toCamelCaseMetric(tc.metric) as SimilarityMetric;
+            const comparator = toUnifiedComparator(tc.metric, tc.options);
+            const actual =
+              comparator === undefined
+                ? score(tc.input_a, tc.input_b, metric, { digraphs: tc.digraphs })
+                : comparator.score(tc.input_a, tc.input_b);
+            comparator?.free();
+            expect(actual).toBeCloseTo(tc.expected, 10);
           }
         });
       }
@@ -418,3 +1415,1204 @@ describe('API compatibility aliases', () => {
     expect(suggestions[0].value).toBe('world hello');
   });
 });
+
+describe('Pair scoring', () => {
+  const before = ['kitten', 'Café', 'hello world', ''];
+  const after = ['sitting', 'cafe', 'world hello', ''];
+
+  it('scores each row against its partner only', () => {
+    const scores = score_pairs(before, after, 'levenshtein');
+    expect(scores).toBeInstanceOf(Float64Array);
+    expect(Array.from(scores)).toEqual(before.map((a, i) => score(a, after[i], 'levenshtein')));
+  });
+
+  it('returns distances as a Uint32Array', () => {
+    const distances = distance_pairs(before, after, 'lcs_seq');
+    expect(distances).toBeInstanceOf(Uint32Array);
+    expect(Array.from(distances)).toEqual(before.map((a, i) => distance(a, after[i], 'lcsSeq')));
+  });
+
+  it('applies the normalization preset and score cutoff', () => {
+    expect(score_pairs(['Café'], ['cafe'], 'ratio', { preset: 'aggressive' })[0]).toBe(1);
+    const cut = score_pairs(before, after, 'jaroWinkler', { scoreCutoff: 0.8 });
+    cut.forEach((value) => expect(value === 0 || value >= 0.8).toBe(true));
+    const capped = distance_pairs(['abc'], ['xyz'], 'levenshtein', { score_cutoff: 1 });
+    expect(Array.from(capped)).toEqual([2]);
+  });
+
+  it('falls back to TypeScript for token metrics', () => {
+    expect(score_pairs(['hello world'], ['world hello'], 'tokenSortRatio')[0]).toBe(1);
+  });
+
+  it('rejects lists of different lengths', () => {
+    expect(() => score_pairs(['a', 'b'], ['a'], 'levenshtein')).toThrow('got 2 and 1');
+    expect(() => distance_pairs(['a'], [], 'levenshtein')).toThrow('got 1 and 0');
+    expect(() => score_pairs(['a'], [], 'tokenSetRatio')).toThrow('got 1 and 0');
+  });
+});
+
+describe('Unpaired surrogates', () => {
+  const lone = `a${String.fromCharCode(0xd800)}b`;
+
+  it('compare as U+FFFD by default', () => {
+    expect(distance(lone, 'a\uFFFDb')).toBe(0);
+    expect(Array.from(distance_pairs([lone], ['a\uFFFDb']))).toEqual([0]);
+  });
+
+  it('throw with strictUnicode on the unified and pair APIs', () => {
+    const message = 'unpaired surrogate (0xD800 at UTF-16 index 1)';
+    expect(() => distance(lone, 'a\uFFFDb', 'levenshtein', { strictUnicode: true })).toThrow(
+      message,
+    );
+    expect(() => score('ab', lone, 'tokenSortRatio', { strictUnicode: true })).toThrow(message);
+    expect(() => score(lone, 'ab', 'jaroWinkler', { strict_unicode: true })).toThrow(message);
+    expect(() => distance('ab', lone, 'osa', { strict_unicode: true })).toThrow(message);
+    expect(() =>
+      anchored_similarity(lone, 'ab', { side: 'start', length: 1 }, 'jaro', {
+        strict_unicode: true,
+      }),
+    ).toThrow(message);
+    expect(() => score_pairs(['ab'], [lone], 'jaro', { strict_unicode: true })).toThrow(message);
+    expect(() => distance_pairs([lone], ['ab'], 'indel', { strictUnicode: true })).toThrow(message);
+    const comparator = new Comparator('levenshtein', { strictUnicode: true });
+    expect(() => comparator.distance(lone, 'ab')).toThrow(message);
+    comparator.free();
+  });
+
+  it('accept surrogate pairs with strictUnicode', () => {
+    expect(distance('🙂a', '🙂b', 'levenshtein', { strictUnicode: true })).toBe(1);
+    expect(() => distance('\uDC00🙂', 'x', 'levenshtein', { strictUnicode: true })).toThrow(
+      '0xDC00 at UTF-16 index 0',
+    );
+  });
+});
+
+describe('Comparator', () => {
+  // Every fixture pair, run through one comparator per metric so buffers carry over
+  const pairs = fixtureDocuments.flatMap((document) =>
+    document.test_cases.flatMap((group) =>
+      group.cases.flatMap((testCase) => {
+        const { input_a: a, input_b: b } = testCase as Partial<DistanceTestCase>;
+        return typeof a === 'string' && typeof b === 'string' ? [[a, b] as const] : [];
+      }),
+    ),
+  );
+  const scoreMetrics: SimilarityMetric[] = ['jaro', 'jaroWinkler', 'ratio'];
+  const distanceMetrics: DistanceMetric[] = [
+    'levenshtein',
+    'damerau_levenshtein',
+    'osa',
+    'indel',
+    'lcs_seq',
+  ];
+
+  it('matches score and distance across the fixture corpus', () => {
+    expect(pairs.length).toBeGreaterThan(100);
+    for (const metric of [...scoreMetrics, ...distanceMetrics]) {
+      const comparator = new Comparator(metric);
+      for (const [a, b] of pairs) {
+        // ratio goes through the 0-100 scale and back, so allow rounding
+        expect(comparator.score(a, b)).toBeCloseTo(score(a, b, metric), 12);
+      }
+      comparator.free();
+    }
+    for (const metric of distanceMetrics) {
+      const comparator = new Comparator(metric);
+      for (const [a, b] of pairs) {
+        expect(comparator.distance(a, b)).toBe(distance(a, b, metric));
+      }
+      comparator.free();
+    }
+  });
+
+  it('applies the preset and score cutoff like score_pairs', () => {
+    const options = { preset: 'default', scoreCutoff: 0.8 } as const;
+    const comparator = new Comparator('jaroWinkler', options);
+    const before = ['Café', 'kitten', 'abc'];
+    const after = ['cafe', 'sitting', 'xyz'];
+    const expected = score_pairs(before, after, 'jaroWinkler', options);
+    expect(before.map((a, i) => comparator.score(a, after[i]))).toEqual(Array.from(expected));
+    comparator.free();
+  });
+
+  it('scores token metrics in TypeScript and rejects distance for them', () => {
+    const comparator = new Comparator('tokenSortRatio');
+    expect(comparator.score('hello world', 'world hello')).toBe(1);
+    expect(() => comparator.distance('a', 'b')).toThrow('Unknown distance metric');
+    comparator.free();
+
+    const jaro = new Comparator('jaro');
+    expect(() => jaro.distance('a', 'b')).toThrow('Unknown distance metric: jaro');
+    jaro.free();
+  });
+});
+
+describe('Module defaults', () => {
+  afterEach(() => reset_default_options());
+
+  // Storage, validation and the batch exports' precedence are tested in tests/default_options.rs
+  it('shares its defaults with the WASM module', () => {
+    set_default_options({ preset: 'default', range_unit: 'utf16' });
+    expect(wasm.get_default_options()).toEqual({ preset: 'default', rangeUnit: 'utf16' });
+    wasm.set_default_options({ locale: 'tr' });
+    expect(get_default_options()).toEqual({ locale: 'tr' });
+    expect(() => set_default_options({ preset: 'loud' as NormalizationPreset })).toThrow(
+      'Invalid default preset',
+    );
+  });
+
+  it('normalizes with the default preset unless a call passes its own', () => {
+    set_default_options({ preset: 'aggressive' });
+    expect(score('Café', 'cafe', 'ratio')).toBe(1);
+    expect(score('Café', 'cafe', 'ratio', { preset: 'none' })).toBeLessThan(1);
+    expect(distance('Café', 'cafe', 'levenshtein')).toBe(0);
+    expect(extractOne('CAFÉ', ['cafe'])?.score).toBe(100);
+    expect(extractOne('CAFÉ', ['cafe'], { processor: (value) => value })?.score).toBe(0);
+
+    // suggest normalizes with 'default' on its own, so a 'none' default turns that off
+    set_default_options({ preset: 'none' });
+    expect(suggest('Cafe', ['cafe'], { minScore: 0.99 })).toEqual([]);
+    expect(suggest('Cafe', ['cafe'], { minScore: 0.99, preset: 'default' })).toHaveLength(1);
+  });
+
+  it('folds case with the default locale', () => {
+    set_default_options({ preset: 'default', locale: 'tr' });
+    expect(score('I', 'ı', 'levenshtein')).toBe(1);
+    expect(suggest('I', ['ı'])[0]?.score).toBe(1);
+  });
+
+  it('reports ranges in the default unit unless a call passes its own', () => {
+    set_default_options({ rangeUnit: 'utf16' });
+    expect(substringSimilarity('😀ab', 'ab').queryRange).toEqual([2, 4]);
+    expect(substringSimilarity('😀ab', 'ab', { rangeUnit: 'char' }).queryRange).toEqual([1, 3]);
+    expect(lcs_seq_matching_indices('😀a', 'a')).toEqual([[2, 0]]);
+    expect(coverage('a', '😀a').ranges).toEqual([[2, 3]]);
+  });
+
+  it('rounds returned scores to the default precision', () => {
+    const exact = score('kitten', 'sitting', 'jaroWinkler');
+    const rounded = Math.round(exact * 100) / 100;
+    set_default_options({ scorePrecision: 2 });
+    expect(score('kitten', 'sitting', 'jaroWinkler')).toBe(rounded);
+    expect(suggest('kitten', ['sitting'], { minScore: 0 })[0].score).toBe(rounded);
+    const ratioScore = ratio('kitten', 'sitting');
+    expect(extract('kitten', ['sitting'])[0].score).toBe(Math.round(ratioScore * 100) / 100);
+  });
+
+  it('rejects inputs longer than the default maximum', () => {
+    set_default_options({ maxInputLength: 4 });
+    expect(score('abcd', 'abcd')).toBe(1);
+    expect(() => score('abcde', 'a')).toThrow('Input exceeds maxInputLength (5 > 4)');
+    expect(() => suggest('a', ['abcde'])).toThrow('maxInputLength');
+    expect(() => extract('abcde', ['a'])).toThrow('maxInputLength');
+  });
+});
+
+describe('LCS alignment', () => {
+  // Small deterministic LCG so failures reproduce
+  const randomStrings = (seed: number, count: number): string[] => {
+    const alphabet = ['a', 'b', 'c', 'é', '日', '👋'];
+    let state = seed;
+    const next = (limit: number) => {
+      state = (state * 1103515245 + 12345) % 2147483648;
+      return state % limit;
+    };
+    return Array.from({ length: count }, () =>
+      Array.from({ length: next(9) }, () => alphabet[next(alphabet.length)]).join(''),
+    );
+  };
+
+  it('returns as many matching indices as lcs_seq_similarity over random strings', () => {
+    const strings = randomStrings(2198, 40);
+    for (let k = 0; k + 1 < strings.length; k++) {
+      const [a, b] = [strings[k], strings[k + 1]];
+      const pairs = lcs_seq_matching_indices(a, b);
+      expect(pairs).toHaveLength(lcs_seq_similarity(a, b));
+      const [aChars, bChars] = [Array.from(a), Array.from(b)];
+      for (const [i, j] of pairs) {
+        expect(aChars[i]).toBe(bChars[j]);
+      }
+      expect(indel_editops(a, b)).toHaveLength(indel_distance(a, b));
+    }
+  });
+
+  it('chooses the leftmost-greedy alignment', () => {
+    expect(lcs_seq_matching_indices('ab', 'ba')).toEqual([[1, 0]]);
+    expect(lcs_seq_matching_indices('abc', 'acb')).toEqual([
+      [0, 0],
+      [2, 1],
+    ]);
+    expect(indel_editops('ab', 'ba')).toEqual([
+      { tag: 'delete', srcPos: 0, destPos: 0 },
+      { tag: 'insert', srcPos: 2, destPos: 1 },
+    ]);
+  });
+
+  it('counts char or UTF-16 positions', () => {
+    expect(lcs_seq_matching_indices('👋a', 'a')).toEqual([[1, 0]]);
+    expect(lcs_seq_matching_indices('👋a', 'a', { rangeUnit: 'utf16' })).toEqual([[2, 0]]);
+    expect(indel_editops('👋a', 'a', { rangeUnit: 'utf16' })).toEqual([
+      { tag: 'delete', srcPos: 0, destPos: 0 },
+    ]);
+  });
+});
+
+describe('Word diff', () => {
+  const rebuild = (ops: ReturnType<typeof word_diff>, side: 'a_tokens' | 'b_tokens') =>
+    ops
+      .filter((op) => op.op !== (side === 'a_tokens' ? 'insert' : 'delete'))
+      .flatMap((op) => op[side])
+      .map((token) => token.text + token.whitespace)
+      .join('');
+
+  it('rebuilds both inputs from their sides of the diff', () => {
+    const inputs = ['', '  ', 'the colour of money', ' The color of  money! ', 'tabs\tand\nlines'];
+    for (const a of inputs) {
+      for (const b of inputs) {
+        for (const tokenizer of ['whitespace', 'words'] as const) {
+          const ops = word_diff(a, b, { tokenizer });
+          expect(rebuild(ops, 'a_tokens')).toBe(a);
+          expect(rebuild(ops, 'b_tokens')).toBe(b);
+        }
+      }
+    }
+  });
+
+  it('reports close words as scored replacements', () => {
+    const ops = word_diff('the colour', 'the color');
+    expect(ops.map((op) => op.op)).toEqual(['equal', 'replace']);
+    expect(ops[1].score).toBeCloseTo(5 / 6, 12);
+    expect(word_diff('the colour', 'the color', { threshold: 0.9 }).map((op) => op.op)).toEqual([
+      'equal',
+      'delete',
+      'insert',
+    ]);
+  });
+
+  it('rejects metrics without a core implementation', () => {
+    expect(() => word_diff('a', 'b', { metric: 'partial_ratio' })).toThrow(
+      'Unsupported word diff metric',
+    );
+  });
+});
+
+describe('Damerau-Levenshtein variants', () => {
+  const pairs: Array<[string, string]> = [
+    ['', ''],
+    ['ca', 'abc'],
+    ['abcdef', 'badcfe'],
+    ['kitten', 'sitting'],
+    ['café', 'cafe'],
+    ['👋 hello', 'hello 👋'],
+  ];
+
+  it('reproduces the integer metric exactly at transposition cost 1.0', () => {
+    for (const [a, b] of pairs) {
+      expect(damerau_levenshtein_weighted(a, b, 1.0)).toBe(damerau_levenshtein(a, b));
+      expect(normalized_damerau_levenshtein_weighted(a, b, 1.0)).toBeCloseTo(
+        normalized_damerau_levenshtein(a, b),
+        10,
+      );
+    }
+  });
+
+  it('clamps the transposition cost to (0, 2]', () => {
+    expect(damerau_levenshtein_weighted('ca', 'ac', 5)).toBe(2);
+    expect(damerau_levenshtein_weighted('ca', 'ac', 0)).toBeGreaterThan(0);
+  });
+
+  it('treats a ZWJ sequence as one grapheme', () => {
+    expect(damerau_levenshtein_graphemes('👨‍👩‍👧🐶', '🐶👨‍👩‍👧')).toBe(1);
+    expect(damerau_levenshtein('👨‍👩‍👧🐶', '🐶👨‍👩‍👧')).toBeGreaterThan(1);
+  });
+
+  it('segments digraphs after normalization', () => {
+    const digraphs = ['ij'];
+    expect(distance('IJsselmeer', 'Ysselmeer', 'osa', { digraphs })).toBe(2);
+    expect(distance('IJsselmeer', 'Ysselmeer', 'osa', { preset: 'default', digraphs })).toBe(1);
+    expect(score('ijs', 'ys', 'damerau_levenshtein', { digraphs })).toBe(0.5);
+    expect(() => score('ijs', 'ys', 'token_sort_ratio', { digraphs })).toThrow(
+      'Metric does not support digraphs',
+    );
+  });
+});
+
+describe('Affine-gap distance', () => {
+  it('charges one long gap less than scattered ones', () => {
+    const query = 'ACGTACGT';
+    const longGap = 'ACGTTTTTACGT';
+    const scattered = 'ACGGTACCGTA';
+    expect(levenshtein(query, scattered)).toBeLessThan(levenshtein(query, longGap));
+    expect(affine_gap_distance(query, longGap, 1, 3, 0.5)).toBeLessThan(
+      affine_gap_distance(query, scattered, 1, 3, 0.5),
+    );
+  });
+
+  it('equals Levenshtein with unit penalties', () => {
+    const pairs = [
+      ['kitten', 'sitting'],
+      ['', 'abc'],
+      ['naïve', 'naive'],
+    ];
+    for (const [a, b] of pairs) {
+      expect(affine_gap_distance(a, b, 1, 1, 1)).toBe(levenshtein(a, b));
+    }
+  });
+
+  it('rejects invalid penalties', () => {
+    expect(() => affine_gap_distance('a', 'b', 1, 1, 2)).toThrow('must not exceed gap_open');
+    expect(() => affine_gap_distance('a', 'b', 0, 1, 1)).toThrow('must be positive');
+    expect(() => normalized_affine_gap_similarity('a', 'b', 1, Number.NaN, 1)).toThrow(
+      'must be positive',
+    );
+  });
+});
+
+describe('Coverage', () => {
+  const title = 'Quarterly invoice reconciliation for the northern region';
+
+  it('ignores the haystack length that substring similarity penalizes', () => {
+    expect(coverage_score('invoice', title)).toBe(1);
+    expect(substringSimilarity('invoice', title).score).toBeLessThan(0.25);
+    expect(coverage('invoice', title)).toEqual({ score: 1, ranges: [[10, 17]] });
+  });
+
+  it('tolerates gaps in the subsequence variant', () => {
+    expect(coverage_score('color', 'colour')).toBe(0.8);
+    expect(subsequence_coverage_score('color', 'colour')).toBe(1);
+    expect(coverage('color', 'colour', { gaps: true }).ranges).toEqual([
+      [0, 4],
+      [5, 6],
+    ]);
+  });
+});
+
+describe('Streaming fuzzy search', () => {
+  it('finds a match split across chunks once', () => {
+    const matcher = new StreamMatcher('needle', 1);
+    const found = [
+      ...matcher.feed('hay nee'),
+      ...matcher.feed('dle hay neadle hay hay hay'),
+      ...matcher.finish(),
+    ];
+    expect(found).toEqual(fuzzy_find_all('hay needle hay neadle hay hay hay', 'needle', 1));
+    expect(found).toEqual([
+      { start: 4, end: 10, edits: 0 },
+      { start: 15, end: 21, edits: 1 },
+    ]);
+    matcher.free();
+  });
+
+  it('reports a match before the stream ends', () => {
+    const matcher = new StreamMatcher('abc', 1);
+    expect(matcher.feed('abc')).toEqual([]);
+    expect(matcher.feed('.....')).toEqual([{ start: 0, end: 3, edits: 0 }]);
+    expect(matcher.finish()).toEqual([]);
+    matcher.free();
+  });
+
+  it('restarts offsets at zero after finish resets it', () => {
+    const matcher = new StreamMatcher('abc', 0);
+    expect([...matcher.feed('xxabc'), ...matcher.finish()]).toEqual([
+      { start: 2, end: 5, edits: 0 },
+    ]);
+    expect([...matcher.feed('abc'), ...matcher.finish()]).toEqual([
+      { start: 0, end: 3, edits: 0 },
+    ]);
+    matcher.free();
+  });
+
+  it('rejects max_edits that would match everywhere', () => {
+    expect(() => new StreamMatcher('abc', 3)).toThrow('less than the needle length');
+    expect(() => fuzzy_find_all('abc', '', 0)).toThrow('less than the needle length');
+  });
+});
+
+describe('Most-frequent-k-characters similarity', () => {
+  it('matches the published research/seeking example', () => {
+    expect(mfc_signature('research', 2)).toBe('r2:e2');
+    expect(mfc_signature('seeking', 2)).toBe('e2:s1');
+    expect(mfc_similarity('research', 'seeking', 2)).toBeCloseTo(4 / 7, 10);
+  });
+
+  it('never falls below the indel score with k = 0', () => {
+    const words = ['research', 'seeking', 'listen', 'silent', 'aab', 'aba', 'café', ''];
+    for (const a of words) {
+      for (const b of words) {
+        expect(mfc_similarity(a, b, 0)).toBeGreaterThanOrEqual(
+          indel_normalized_similarity(a, b) - 1e-10,
+        );
+      }
+    }
+  });
+
+  it('rejects malformed signatures', () => {
+    expect(() => mfc_signature_similarity('r2:e', 'e2')).toThrow('Invalid MFC signature');
+    expect(() => mfc_signature_similarity('r2', 'e2;s1')).toThrow('Invalid MFC signature');
+  });
+});
+
+describe('Normalization-aware equality and hashing', () => {
+  it('agrees with comparing normalize() results', () => {
+    const inputs = ['Café', 'Cafe\u0301', ' CAFÉ ', 'cafe', 'ﬁle', 'FILE', 'İstanbul', 'istanbul'];
+    const presets: NormalizationPreset[] = ['none', 'minimal', 'default', 'aggressive'];
+    for (const preset of presets) {
+      for (const a of inputs) {
+        for (const b of inputs) {
+          const expected = normalize(a, preset) === normalize(b, preset);
+          expect(equals_normalized(a, b, preset)).toBe(expected);
+        }
+      }
+    }
+  });
+
+  it('returns keys and hashes that bucket equal strings together', () => {
+    expect(normalized_key('  Naïve Café!', 'aggressive')).toBe('naive cafe');
+    const hash = normalized_hash('Hello World', 'default');
+    expect(hash).toBe(normalized_hash('hello world', 'default'));
+    expect(typeof normalized_hash('x', 'none')).toBe('bigint');
+  });
+
+  it('strips bidi controls by default and honors the options', () => {
+    const spoofed = 'invoice\u202Efdp.exe';
+    expect(equals_normalized(spoofed, 'invoicefdp.exe', 'default')).toBe(true);
+    expect(
+      equals_normalized(spoofed, 'invoicefdp.exe', 'default', undefined, {
+        stripIgnorables: false,
+      }),
+    ).toBe(false);
+    expect(normalize(spoofed, 'minimal')).toBe(spoofed);
+    expect(normalize('\u200Bab', 'minimal', undefined, { stripIgnorables: true })).toBe('ab');
+    expect(normalize('a\u200Cb', 'default', undefined, { keepJoiners: true })).toBe('a\u200Cb');
+  });
+
+  it('folds numeric tokens only when asked', () => {
+    expect(fold_numeric_tokens('1.000,50 and 1,000.50')).toBe('1000.50 and 1000.50');
+    expect(fold_numeric_tokens('due 12.03.2024')).toBe('due 12.03.2024');
+    expect(equals_normalized('EUR 1.000,50', 'eur 1,000.50', 'default')).toBe(false);
+    expect(
+      equals_normalized('EUR 1.000,50', 'eur 1,000.50', 'default', undefined, {
+        foldNumbers: true,
+      }),
+    ).toBe(true);
+  });
+
+  it('expands abbreviations after the preset', () => {
+    const expansions: Expansions = { St: 'Street', 'St Louis': 'Saint Louis' };
+    expect(normalize('12 St Louis St.', 'default', undefined, { expansions })).toBe(
+      '12 saint louis street.',
+    );
+    expect(normalize('St Mary', 'none', undefined, { expansions })).toBe('St Mary');
+    expect(equals_normalized('Main St', 'main street', 'default', undefined, { expansions })).toBe(
+      true,
+    );
+    expect(() =>
+      normalize('x', 'default', undefined, { expansions: { st: 'a', ST: 'b' } }),
+    ).toThrow('Duplicate expansion key');
+  });
+
+  it('pins the hash function to the reported version', () => {
+    expect(NORMALIZED_HASH_VERSION).toBe(2);
+    expect(normalized_hash('', 'none')).toBe(0xcbf29ce484222325n);
+  });
+
+  it('fingerprints normalization by version, preset and locale', () => {
+    expect(normalization_fingerprint('default')).toBe('deb734156b831334');
+    expect(normalization_fingerprint('default', 'tr')).toBe('fa1c9ec9988fbd76');
+    expect(normalization_fingerprint('aggressive')).not.toBe(normalization_fingerprint('default'));
+  });
+
+  it('refuses candidate set bytes with a forged fingerprint', () => {
+    const set = new CandidateSet(['İstanbul', 'ISPARTA'], 'default', 'tr');
+    expect(set.keys).toEqual(['istanbul', 'ısparta']);
+    const bytes = set.toBytes();
+    set.free();
+    const loaded = CandidateSet.fromBytes(bytes);
+    expect(loaded.fingerprint).toBe('fa1c9ec9988fbd76');
+    expect(loaded.lookup('ıSPARTA')).toEqual([1]);
+    loaded.free();
+
+    // Bytes 5-20 hold the fingerprint, after the 'SMCS' magic and the format byte
+    bytes.set(new TextEncoder().encode('0123456789abcdef'), 5);
+    expect(() => CandidateSet.fromBytes(bytes)).toThrow(
+      new FingerprintMismatchError('0123456789abcdef', 'fa1c9ec9988fbd76'),
+    );
+    expect(() => CandidateSet.fromBytes(bytes.subarray(0, 4))).toThrow(
+      'Invalid candidate set: truncated',
+    );
+  });
+
+  it('reports whether case folding would change the input', () => {
+    for (const input of ['東京タワー', 'กรุงเทพ 2024', 'already folded', '']) {
+      expect(needs_case_fold(input)).toBe(false);
+      expect(normalize(input, 'default')).toBe(input.trim());
+    }
+    expect(needs_case_fold('東京 Tower')).toBe(true);
+    expect(needs_case_fold('straße')).toBe(true);
+  });
+});
+
+describe('Script detection', () => {
+  it('classifies supplementary-plane scripts', () => {
+    expect(dominant_script('𐌲𐌿𐍄𐌰')).toBe('Goth');
+    expect(dominant_script('𞤀𞤣𞤤𞤢𞤥')).toBe('Adlm');
+    expect(script_breakdown('𠀀中')).toEqual({ Hani: 2 });
+  });
+
+  it('ignores Common and Inherited characters when checking for mixing', () => {
+    expect(is_mixed_script('Cafe\u0301 2024!')).toBe(false);
+    expect(is_mixed_script('p\u0430ypal')).toBe(true);
+    expect(dominant_script('123 👋')).toBe('Zyyy');
+  });
+
+  it('detects bidi controls but not other invisible characters', () => {
+    expect(contains_bidi_controls('invoice\u202Efdp.exe')).toBe(true);
+    expect(contains_bidi_controls('\u2067שלום\u2069')).toBe(true);
+    expect(contains_bidi_controls('zero\u200Bwidth')).toBe(false);
+  });
+
+  it('counts every character exactly once', () => {
+    const input = 'Tokyo 東京 とうきょう';
+    const counts = Object.values(script_breakdown(input));
+    expect(counts.reduce((sum, n) => sum + n, 0)).toBe([...input].length);
+  });
+});
+
+describe('Suggestion explanations', () => {
+  it('omits explanations unless requested', () => {
+    expect(suggest('test', ['testing'])[0].explanation).toBeUndefined();
+    expect(extractOne('test', ['testing'])?.explanation).toBeUndefined();
+  });
+
+  it('breaks down the prefix bonus', () => {
+    const [first] = suggest('test', ['testing', 'best'], {
+      metric: 'levenshtein',
+      minScore: 0.5,
+      preferPrefix: true,
+      prefixBonusWeight: 0.5,
+      explain: true,
+    });
+    expect(first.value).toBe('testing');
+    expect(first.explanation).toMatchObject({
+      metric: 'levenshtein',
+      prefix_bonus_applied: true,
+      normalized_input: 'test',
+      normalized_candidate: 'testing',
+      prefilter: 'scored',
+    });
+    const { raw_score, bonus_amount } = first.explanation as SuggestionExplanation;
+    expect(raw_score + bonus_amount).toBeCloseTo(first.score, 10);
+  });
+
+  it('reports the snake_case metric name', () => {
+    const [first] = suggest('tset', ['test'], { metric: 'damerauOsa', explain: true });
+    expect(first.explanation?.metric).toBe('damerau_osa');
+  });
+
+  it('explains only the results returned by extract', () => {
+    const results = extract('jets', ['New York Jets', 'Dallas Cowboys'], {
+      processor: (value) => value.toLowerCase(),
+      limit: 1,
+      explain: true,
+    });
+    expect(results).toHaveLength(1);
+    expect(results[0].explanation).toMatchObject({
+      metric: 'ratio',
+      raw_score: results[0].score,
+      bonus_amount: 0,
+      normalized_candidate: 'new york jets',
+    });
+  });
+});
+
+describe('Suggestion candidates with metadata', () => {
+  it('ranks plain strings and candidate objects alike', () => {
+    const plain = suggest('pythn', ['python', 'java']);
+    const objects = suggest('pythn', [{ value: 'python' }, { value: 'java' }]);
+    expect(objects).toEqual(plain);
+    expect(plain[0]).not.toHaveProperty('id');
+  });
+
+  it('folds each candidate with its own locale and echoes ids', () => {
+    const results = suggest(
+      'izmir',
+      [{ value: 'İZMİR', id: 'city-35', locale: 'tr' }, { value: 'İZMİR', id: 'untagged' }],
+      { metric: 'levenshtein', minScore: 0.5 },
+    );
+    expect(results.map((r) => r.id)).toEqual(['city-35', 'untagged']);
+    expect(results[0].score).toBe(1);
+    expect(results[1].score).toBeLessThan(1);
+  });
+});
+
+describe('Token query mode', () => {
+  const commands = ['Open Recent', 'Opn Rcnt Fil', 'Close Window'];
+
+  it('accepts snake_case option names', () => {
+    expect(suggest('open recnt file', commands, { query_mode: 'tokens', min_score: 0 })).toEqual(
+      suggest('open recnt file', commands, { queryMode: 'tokens', minScore: 0 }),
+    );
+  });
+
+  it('ranks a candidate missing a query word below weak matches of every word', () => {
+    const results = suggest('open recnt file', commands, {
+      metric: 'levenshtein',
+      queryMode: 'tokens',
+      minScore: 0,
+    });
+    expect(results.map((r) => r.value)).toEqual(['Opn Rcnt Fil', 'Open Recent', 'Close Window']);
+  });
+
+  it('scores tokens with a custom extract scorer on its 0-100 scale', () => {
+    const exact = (a: string, b: string): number => (a === b ? 100 : 0);
+    const results = extract('recent open', ['open recent file', 'open file'], {
+      scorer: exact,
+      queryMode: 'tokens',
+    });
+    expect(results.map((r) => [r.choice, r.score])).toEqual([
+      ['open recent file', 100],
+      ['open file', 25],
+    ]);
+  });
+});
+
+describe('SuggestSession', () => {
+  const candidates = [
+    'chemistry',
+    'chemical',
+    'chess',
+    'cheese',
+    'alchemy',
+    'biochemistry',
+    'mechanic',
+    'echelon',
+  ];
+  const keystrokes = ['c', 'ch', 'che', 'chem', 'chemi', 'chemis', 'xchemis'];
+
+  it.each([
+    { metric: 'levenshtein' as const, minScore: 0.5 },
+    { metric: 'substring' as const, minScore: 0.6, preferPrefix: true, prefixBonusWeight: 0.3 },
+    { metric: 'jaroWinkler' as const, minScore: 0.7 },
+    { metric: 'levenshtein' as const, minScore: 0.3, queryMode: 'tokens' as const },
+  ])('safe refinement matches a fresh suggest ($metric)', (options) => {
+    const session = new SuggestSession(candidates);
+    for (const query of keystrokes) {
+      expect(session.query(query, { ...options, refine: 'safe' })).toEqual(
+        suggest(query, candidates, options),
+      );
+    }
+  });
+
+  it('rescores everything when the query does not extend the previous one', () => {
+    const session = new SuggestSession(candidates);
+    session.query('chem', { metric: 'levenshtein', refine: 'aggressive' });
+    expect(session.query('alc', { metric: 'levenshtein', refine: 'aggressive' })).toEqual(
+      suggest('alc', candidates, { metric: 'levenshtein' }),
+    );
+  });
+
+  it('keeps aggressive results to candidates near the previous threshold', () => {
+    const session = new SuggestSession(['ab', 'abcdefgh']);
+    const options = {
+      metric: 'levenshtein' as const,
+      minScore: 0.9,
+      refine: 'aggressive' as const,
+    };
+    session.query('ab', options);
+    // 'abcdefgh' scored 0.25 for 'ab', far below the margin, so it is not rescored
+    expect(session.query('abcdefgh', options).map((s) => s.value)).toEqual([]);
+    expect(suggest('abcdefgh', ['ab', 'abcdefgh'], options)[0].value).toBe('abcdefgh');
+  });
+});
+
+describe('Recency boost', () => {
+  const candidates = ['colour', 'colors', 'collar'];
+  const recencyBoost = { halfLifeMs: 1000, maxBoost: 0.2, nowMs: 10_000 };
+  const options = { metric: 'levenshtein' as const, minScore: 0.6, recencyBoost };
+  const values = (suggestions: Suggestion[]): string[] => suggestions.map((s) => s.value);
+
+  it('halves the boost every half-life', () => {
+    const results = suggest(
+      'color',
+      [
+        'colour',
+        { value: 'colors', lastSelectedMs: 9000 },
+        { value: 'collar', lastSelectedMs: 8000 },
+      ],
+      options,
+    );
+    expect(values(results)).toEqual(['colors', 'colour', 'collar']);
+    expect(results[0].score).toBeCloseTo(5 / 6 + 0.1, 10);
+    expect(results[2].score).toBeCloseTo(2 / 3 + 0.05, 10);
+    expect(results[0].reason).toContain('recency_boost');
+  });
+
+  it('keeps candidates below minScore out unless allowBoostPastCutoff is set', () => {
+    const selected = [{ value: 'collar', lastSelectedMs: 10_000 }];
+    expect(suggest('color', selected, { ...options, minScore: 0.75 })).toEqual([]);
+    const lifted = suggest('color', selected, {
+      ...options,
+      minScore: 0.75,
+      recencyBoost: { ...recencyBoost, allowBoostPastCutoff: true },
+    });
+    expect(values(lifted)).toEqual(['collar']);
+  });
+
+  it('rejects a non-positive half-life', () => {
+    expect(() =>
+      suggest('color', candidates, { recencyBoost: { halfLifeMs: 0, maxBoost: 0.2 } }),
+    ).toThrow('half_life_ms');
+  });
+
+  it('boosts session candidates from their recorded selections', () => {
+    const session = new SuggestSession(candidates);
+    expect(values(session.query('color', options))).toEqual(['colour', 'colors', 'collar']);
+    session.recordSelection(2, 10_000);
+    expect(values(session.query('color', options))).toEqual(['collar', 'colour', 'colors']);
+    expect(values(session.query('color', { ...options, recencyBoost: undefined }))).toEqual([
+      'colour',
+      'colors',
+      'collar',
+    ]);
+  });
+
+  it('serializes the selection history with the session', () => {
+    const session = new SuggestSession(candidates);
+    session.recordSelection(1, 9000);
+    const restored = SuggestSession.fromJSON(JSON.parse(JSON.stringify(session)));
+    expect(restored.toJSON()).toEqual(session.toJSON());
+    expect(restored.query('color', options)).toEqual(session.query('color', options));
+  });
+
+  it('keeps only the most recent selections', () => {
+    const session = new SuggestSession(candidates);
+    session.recordSelection(0, 0);
+    for (let i = 0; i < SELECTION_HISTORY_LIMIT; i += 1) {
+      session.recordSelection(1, i + 1);
+    }
+    const { selections } = session.toJSON();
+    expect(selections).toHaveLength(SELECTION_HISTORY_LIMIT);
+    expect(selections.every(({ index }) => index === 1)).toBe(true);
+    expect(() => session.recordSelection(3, 0)).toThrow('out of range');
+  });
+});
+
+describe('Word segmentation', () => {
+  const vocabulary = ['new', 'york', 'city', 'newyork'];
+
+  it('prefers fewer words unless frequencies say otherwise', () => {
+    const segmentation = segment_words('newyorkcity', vocabulary);
+    expect(segmentation.words).toEqual(['newyork', 'city']);
+    expect(segmentation.score).toBeCloseTo(2 * Math.log(1 / 4), 12);
+    const weighted = segment_words('newyorkcity', vocabulary, { frequencies: [5, 5, 5, 1] });
+    expect(weighted.words).toEqual(['new', 'york', 'city']);
+  });
+
+  it('keeps uncovered runs as unknown words and whitespace as a boundary', () => {
+    expect(segment_words('newxyzcity york', vocabulary).words).toEqual([
+      'new',
+      'xyz',
+      'city',
+      'york',
+    ]);
+    expect(segment_words('', vocabulary)).toEqual({ words: [], score: 0 });
+  });
+
+  it('rejects mismatched frequencies', () => {
+    expect(() => segment_words('new', vocabulary, { frequencies: [1] })).toThrow();
+  });
+
+  it('lets suggest rank the spaced candidate first', () => {
+    const candidates = ['NewYorkCityLights', 'New York City Hall'];
+    expect(suggest('newyorkcity', candidates)[0].value).toBe('NewYorkCityLights');
+    expect(suggest('newyorkcity', candidates, { segmentInput: true })[0].value).toBe(
+      'New York City Hall',
+    );
+  });
+});
+
+describe('Length filters', () => {
+  const candidates = ['a', 'abc', 'abacus', 'absolutely every abbreviation in the glossary'];
+
+  it('leave results unchanged when no limit is set', () => {
+    const counts = { scored: 0, skipped_length: 0 };
+    expect(suggest('ab', candidates, { minScore: 0, prefilterCounts: counts })).toEqual(
+      suggest('ab', candidates, { minScore: 0 }),
+    );
+    expect(counts).toEqual({ scored: 4, skipped_length: 0 });
+  });
+
+  it('count the candidates they skip across calls', () => {
+    const counts = { scored: 0, skipped_length: 0 };
+    const options = { minCandidateLen: 2, maxLengthRatio: 4, prefilter_counts: counts };
+    expect(suggest('ab', candidates, { minScore: 0, ...options }).map((s) => s.value)).toEqual([
+      'abc',
+      'abacus',
+    ]);
+    extract('ab', candidates, options);
+    expect(counts).toEqual({ scored: 4, skipped_length: 4 });
+  });
+});
+
+describe('Time-boxed extract', () => {
+  const choices = Array.from({ length: 1000 }, (_, i) => `item-${i}`);
+
+  it('matches extract when the budget is not exhausted', () => {
+    const options = { limit: 5, scoreCutoff: 40 };
+    const budgeted = extractWithBudget('item-42', choices, { ...options, timeBudgetMs: 60_000 });
+    expect(budgeted).toEqual({
+      results: extract('item-42', choices, options),
+      complete: true,
+      processed: 1000,
+      total: 1000,
+    });
+  });
+
+  it('stops at a check interval and ranks only the scored prefix', () => {
+    const partial = extractWithBudget('item-999', choices, { time_budget_ms: 0 });
+    expect(partial.complete).toBe(false);
+    expect(partial.processed).toBe(256);
+    expect(partial.total).toBe(1000);
+    expect(partial.results).toEqual(extract('item-999', choices.slice(0, 256)));
+  });
+
+  it('rejects a negative budget', () => {
+    expect(() => extractWithBudget('a', ['a'], { timeBudgetMs: -1 })).toThrow();
+  });
+});
+
+describe('Byte metrics', () => {
+  it('treat each UTF-8 byte of a string as one symbol', () => {
+    expect(levenshtein('café', 'cafe')).toBe(1);
+    expect(levenshtein_bytes('café', 'cafe')).toBe(2);
+    expect(levenshtein_bytes('café', new TextEncoder().encode('café'))).toBe(0);
+  });
+
+  it('compare binary identifiers position by position', () => {
+    const a = Uint8Array.of(0xde, 0xad, 0xbe, 0xef);
+    const b = Uint8Array.of(0xde, 0xad, 0xbe, 0xee);
+    expect(hamming_bytes(a, b)).toBe(1);
+    expect(normalized_hamming_bytes(a, b)).toBeCloseTo(0.75, 12);
+    expect(() => hamming_bytes(a, b.subarray(1))).toThrow('(4 vs 3 bytes)');
+    expect(hamming_bytes(a, b.subarray(1), true)).toBe(4);
+  });
+});
+
+describe('Rank fusion', () => {
+  const candidates = ['New York', 'Yorkshire', 'yrok'];
+  const values = (results: Suggestion[]) => results.map((s) => s.value);
+
+  it('interleaves each metric ranking in turn', () => {
+    const rankFusion: RankFusion<SuggestMetric> = {
+      metrics: ['jaro_winkler', 'substring'],
+      method: 'interleave',
+    };
+    expect(values(suggest('york', candidates, { minScore: 0.6, rankFusion }))).toEqual([
+      'yrok',
+      'New York',
+      'Yorkshire',
+    ]);
+  });
+
+  it('orders by reciprocal rank with the k constant', () => {
+    const fusion: RankFusion<SuggestMetric> = {
+      metrics: ['jaroWinkler', 'substring'],
+      method: 'rrf',
+    };
+    expect(values(suggest('york', candidates, { minScore: 0.6, rank_fusion: fusion }))).toEqual([
+      'Yorkshire',
+      'New York',
+      'yrok',
+    ]);
+    const flat = { ...fusion, k: 0 };
+    expect(values(suggest('york', candidates, { minScore: 0.6, rank_fusion: flat }))).toEqual([
+      'New York',
+      'yrok',
+      'Yorkshire',
+    ]);
+  });
+
+  it('keeps the best score and names its scorer when explaining', () => {
+    const results = extract('york', candidates, {
+      scoreCutoff: 60,
+      explain: true,
+      rankFusion: { metrics: [ratio, partialRatio], method: 'interleave' },
+    });
+    expect(results.map((r) => [r.choice, r.explanation?.metric])).toEqual([
+      ['yrok', 'ratio'],
+      ['New York', 'partialRatio'],
+      ['Yorkshire', 'partialRatio'],
+    ]);
+    const single = { rankFusion: { metrics: [ratio], method: 'rrf' as const } };
+    const [top] = extract('york', candidates, { limit: 1 });
+    expect(extractOne('york', candidates, single)).toEqual(top);
+  });
+
+  it('rejects an empty metric list or a negative k', () => {
+    expect(() => suggest('a', ['a'], { rankFusion: { metrics: [], method: 'rrf' } })).toThrow(
+      'at least one metric',
+    );
+    const negative: RankFusion<ScorerFunction> = { metrics: [ratio], method: 'rrf', k: -1 };
+    expect(() => extract('a', ['a'], { rankFusion: negative })).toThrow('non-negative');
+  });
+});
+
+describe('Excerpts', () => {
+  it('cut around a coverage range without splitting emoji', () => {
+    const title = '🎉🎉🎉 Release notes for 👩‍💻 string metrics 👩‍💻 🎉🎉🎉';
+    const options = { rangeUnit: 'utf16' as const };
+    const [range] = coverage('string metrics', title, options).ranges;
+    const excerpt = excerpt_around_range(title, range, 3, undefined, options);
+    expect(excerpt.text).toBe('… 👩‍💻 string metrics 👩‍💻 …');
+    expect(excerpt.text.slice(...excerpt.range)).toBe('string metrics');
+  });
+
+  it('reject a range inside a surrogate pair', () => {
+    expect(() => excerpt_around_range('a😀b', [2, 3], 1, '…', { rangeUnit: 'utf16' })).toThrow(
+      'splits a surrogate pair',
+    );
+  });
+});
+
+describe('Weighted Jaccard', () => {
+  it('accepts objects, Maps and pairs alike', () => {
+    const tags = { rust: 3, wasm: 2, cli: 1 };
+    const other = new Map([
+      ['rust', 1],
+      ['wasm', 2],
+      ['python', 2],
+    ]);
+    expect(weighted_jaccard(tags, other)).toBeCloseTo(3 / 8, 12);
+    expect(weighted_jaccard(Object.entries(tags) as [string, number][], other)).toBeCloseTo(
+      3 / 8,
+      12,
+    );
+  });
+
+  it('compares tokens exactly unless a preset is given', () => {
+    expect(weighted_jaccard({ Rust: 1 }, { rust: 1 })).toBe(0);
+    expect(weighted_jaccard({ Rust: 1 }, { rust: 1 }, { preset: 'default' })).toBe(1);
+    expect(weighted_jaccard({}, {})).toBe(1);
+    expect(() => weighted_jaccard({ rust: -1 }, { rust: 1 })).toThrow('non-negative');
+  });
+});
+
+describe('Deterministic ordering', () => {
+  const candidates = ['grape', 'maple', 'apply', 'apple', 'ample', 'applet', 'cape', 'tape'];
+  // A fixed permutation rather than Math.random, so a failure reproduces
+  const shuffled = [...candidates.slice(3), ...candidates.slice(0, 3)].reverse();
+  // Score descending, then value: an order that doesn't depend on the input order
+  const canonical = (results: Array<{ value: string; score: number }>) =>
+    results
+      .map(({ value, score }) => ({ value, score }))
+      .sort((a, b) => b.score - a.score || (a.value < b.value ? -1 : 1));
+
+  it('ranks suggestions the same way across runs and input orders', () => {
+    const options = { metric: 'levenshtein' as const, minScore: 0 };
+    const first = suggest('appel', candidates, options);
+    expect(suggest('appel', candidates, options)).toEqual(first);
+
+    // Ties keep input order, so only the order among equal scores may follow a shuffle
+    first.slice(1).forEach((result, i) => {
+      const previous = first[i];
+      expect(result.score).toBeLessThanOrEqual(previous.score);
+      if (result.score === previous.score) {
+        expect(candidates.indexOf(result.value)).toBeGreaterThan(
+          candidates.indexOf(previous.value),
+        );
+      }
+    });
+    expect(canonical(suggest('appel', shuffled, options))).toEqual(canonical(first));
+  });
+
+  it('ranks extract results the same way across runs and input orders', () => {
+    const first = extract('appel', candidates);
+    expect(extract('appel', candidates)).toEqual(first);
+    const indexes = first.map((result) => result.index);
+    expect(indexes).toEqual(
+      [...first].sort((a, b) => b.score - a.score || a.index - b.index).map((r) => r.index),
+    );
+
+    const values = (results: ExtractResult[]) =>
+      canonical(results.map(({ choice, score }) => ({ value: choice, score })));
+    expect(values(extract('appel', shuffled))).toEqual(values(first));
+  });
+});
+
+describe('Variant generation', () => {
+  it('returns distinct strings at exactly the requested distance', () => {
+    const variants = generate_variants('kitten', 2, 20, { seed: 7 });
+    expect(variants).toHaveLength(20);
+    expect(new Set(variants).size).toBe(20);
+    for (const variant of variants) {
+      expect(levenshtein('kitten', variant)).toBe(2);
+    }
+    expect(generate_variants('kitten', 0, 5)).toEqual(['kitten']);
+  });
+
+  it('reproduces the same variants for the same seed', () => {
+    const first = generate_variants('similarity', 3, 10, { seed: 42 });
+    expect(generate_variants('similarity', 3, 10, { seed: 42 })).toEqual(first);
+    expect(generate_variants('similarity', 3, 10, { seed: 43 })).not.toEqual(first);
+  });
+
+  it('restricts edits and draws new chars from extraChars', () => {
+    for (const variant of generate_variants('abc', 1, 10, { edits: ['delete'] })) {
+      expect(variant).toHaveLength(2);
+    }
+    const substituted = generate_variants('aaaa', 1, 4, { edits: ['substitute'], extraChars: 'z' });
+    expect(substituted.sort()).toEqual(['aaaz', 'aaza', 'azaa', 'zaaa']);
+    expect(generate_variants('abc', 1, 3, { edits: ['transpose'] })).toEqual([]);
+    expect(() => generate_variants('abc', 1, 3, { seed: -1 })).toThrow('non-negative');
+  });
+});
+
+describe('Did you mean', () => {
+  const commands = ['status', 'commit', 'checkout', 'cherry-pick'];
+
+  it('prefers an exact normalized match, then a confident correction', () => {
+    expect(did_you_mean('STATUS', commands)).toEqual({ kind: 'exact', value: 'status' });
+    const corrected = did_you_mean('comit', commands);
+    expect(corrected.kind).toBe('corrected');
+    expect(corrected.kind === 'corrected' && corrected.value).toBe('commit');
+  });
+
+  it('refuses to guess between close runners-up', () => {
+    expect(did_you_mean('colr', ['color', 'collar', 'cooler'])).toEqual({ kind: 'none' });
+    expect(did_you_mean('xyz', commands)).toEqual({ kind: 'none' });
+    expect(did_you_mean('anything', [])).toEqual({ kind: 'none' });
+  });
+
+  describe('under module defaults', () => {
+    afterEach(() => reset_default_options());
+
+    it('matches exactly under the default locale, as the suggestions do', () => {
+      set_default_options({ locale: 'tr' });
+      expect(did_you_mean('istanbul', ['İstanbul'])).toEqual({ kind: 'exact', value: 'İstanbul' });
+    });
+
+    it('measures the margin before scorePrecision rounds', () => {
+      // color 0.953 leads collar 0.922 by less than the margin; rounded, 1.0 leads 0.9
+      set_default_options({ scorePrecision: 1 });
+      expect(did_you_mean('colr', ['color', 'collar', 'cooler'])).toEqual({ kind: 'none' });
+      expect(did_you_mean('comit', commands)).toMatchObject({ kind: 'corrected', value: 'commit' });
+    });
+  });
+});
+
+describe('Cancellation', () => {
+  const rows = Array.from({ length: 1000 }, (_, i) => `row-${i}`);
+
+  it('leaves a live token alone', () => {
+    const token = new CancellationToken();
+    expect(score_pairs(rows, rows, 'levenshtein', { cancel: token })).toEqual(
+      new Float64Array(rows.length).fill(1),
+    );
+    expect(token.cancelled).toBe(false);
+    token.free();
+  });
+
+  it('throws a structured error once cancelled, however often cancel is called', () => {
+    const token = new CancellationToken();
+    token.cancel();
+    token.cancel();
+    for (const run of [
+      () => score_pairs(rows, rows, 'jaroWinkler', { cancel: token }),
+      () => score_pairs(rows, rows, 'tokenSortRatio', { cancel: token }),
+      () => distance_pairs(rows, rows, 'levenshtein', { cancel: token }),
+    ]) {
+      const error = (() => {
+        try {
+          run();
+        } catch (thrown) {
+          return thrown;
+        }
+        return undefined;
+      })();
+      expect(error).toBeInstanceOf(CancelledError);
+      expect(error).toMatchObject({ code: 'CANCELLED', completed: 0, total: rows.length });
+    }
+    token.free();
+  });
+
+  it('splits async runs into chunks and counts completed rows across them', async () => {
+    const many = Array.from({ length: PAIR_CHUNK_ROWS * 2 + 10 }, (_, i) => `row-${i}`);
+    expect(await score_pairs_async(many, many, 'levenshtein')).toEqual(
+      new Float64Array(many.length).fill(1),
+    );
+    expect(await distance_pairs_async(many, many)).toEqual(new Uint32Array(many.length));
+    expect(await score_pairs_async([], [])).toEqual(new Float64Array());
+    await expect(score_pairs_async(['a'], [])).rejects.toThrow('equal lengths');
+    await expect(distance_pairs_async([], [], 'jaro' as DistanceMetric)).rejects.toThrow(
+      'Unknown distance metric',
+    );
+  });
+
+  it('stops a 100k-row async run at the chunk after a cancel from a timer', async () => {
+    const candidates = Array.from({ length: 100_000 }, (_, i) => `candidate-${i}`);
+    const queries = candidates.map((candidate) => candidate.toUpperCase());
+    const token = new CancellationToken();
+    setTimeout(() => token.cancel(), 0);
+    const error = await score_pairs_async(queries, candidates, 'jaroWinkler', {
+      cancel: token,
+    }).catch((thrown: unknown) => thrown);
+
+    expect(error).toBeInstanceOf(CancelledError);
+    const { completed, total } = error as CancelledError;
+    expect(total).toBe(candidates.length);
+    expect(completed % PAIR_CHUNK_ROWS).toBe(0);
+    expect(completed).toBeLessThan(total);
+    token.free();
+  });
+
+  it('stops cdist, similar_pairs and dedupe with a structured error', () => {
+    const token = new CancellationToken();
+    token.cancel();
+    const pairs = (rows.length * (rows.length - 1)) / 2;
+    for (const [run, total] of [
+      [() => cdist(['row'], rows, 'jaroWinkler', { cancel: token }), rows.length],
+      [() => similar_pairs(rows, 'levenshtein', { cancel: token }), pairs],
+      [() => dedupe(rows, 'levenshtein', { cancel: token }), pairs],
+    ] as const) {
+      expect(run).toThrow(CancelledError);
+      expect(run).toThrow(`CANCELLED: cancelled after 0 of ${total} rows`);
+    }
+    token.free();
+  });
+});
+
+describe('cdist, similar_pairs and dedupe', () => {
+  // abcd/abce and abce/abfe score 0.75 under Levenshtein, abcd/abfe only 0.5
+  const items = ['abcd', 'xyz', 'abce', 'abfe', 'xyz'];
+
+  it('scores every query against every choice, one row per query', () => {
+    const rows = cdist(['kitten', 'flaw'], ['kitten', 'lawn', 'flaw'], 'levenshtein');
+    expect(rows).toHaveLength(2);
+    expect([rows[0][0], rows[0][2]]).toEqual([1, 0]);
+    expect(rows[0][1]).toBeCloseTo(1 / 6);
+    expect(Array.from(rows[1])).toEqual([0, 0.5, 1]);
+    expect(cdist([], ['a'])).toEqual([]);
+    expect(cdist(['Café'], ['cafe'], 'levenshtein', { preset: 'aggressive' })[0][0]).toBe(1);
+  });
+
+  it('lists each pair at or above minScore once', () => {
+    expect(similar_pairs(items, 'levenshtein', { minScore: 0.75 })).toEqual([
+      { left: 0, right: 2, score: 0.75 },
+      { left: 1, right: 4, score: 1 },
+      { left: 2, right: 3, score: 0.75 },
+    ]);
+    expect(similar_pairs(items, 'levenshtein')).toEqual([{ left: 1, right: 4, score: 1 }]);
+  });
+
+  it('groups chains of near-duplicates under their first item', () => {
+    expect(Array.from(dedupe(items, 'levenshtein', { min_score: 0.75 }))).toEqual([0, 1, 0, 0, 1]);
+    expect(Array.from(dedupe(items, 'levenshtein'))).toEqual([0, 1, 2, 3, 1]);
+  });
+
+  it('rejects the TypeScript-only token metrics', () => {
+    expect(() => cdist(['a'], ['b'], 'tokenSortRatio')).toThrow(
+      'cdist does not support metric: tokenSortRatio',
+    );
+    expect(() => dedupe(['a'], 'partialRatio')).toThrow('dedupe does not support metric');
+  });
+});
@@ -0,0 +1,42 @@
+//! Lone surrogates built on the JS side, run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Error, JsString};
+use string_metrics_wasm::{check_well_formed, levenshtein};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// "a\uD800b" via String.fromCharCode, so the surrogate never passes through Rust
+fn lone_high_surrogate() -> JsString {
+    JsString::from_char_code3(0x61, 0xD800, 0x62)
+}
+
+fn error_message(result: Result<(), wasm_bindgen::JsError>) -> String {
+    let value = JsValue::from(result.expect_err("expected an unpaired surrogate error"));
+    Error::from(value).message().into()
+}
+
+#[wasm_bindgen_test]
+fn default_conversion_folds_lone_surrogates_to_replacement_char() {
+    let lossy = String::from(&lone_high_surrogate());
+    assert_eq!(lossy, "a\u{FFFD}b");
+    assert_eq!(levenshtein(&lossy, "a\u{FFFD}b"), 0);
+}
+
+#[wasm_bindgen_test]
+fn check_well_formed_reports_the_surrogate_and_its_index() {
+    let message = error_message(check_well_formed(&lone_high_surrogate()));
+    assert!(message.contains("0xD800 at UTF-16 index 1"), "{}", message);
+
+    let trailing_low = JsString::from_char_code2(0x1F60, 0xDC00);
+    let message = error_message(check_well_formed(&trailing_low));
+    assert!(message.contains("0xDC00 at UTF-16 index 1"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+fn check_well_formed_accepts_surrogate_pairs() {
+    // U+1F642 twice, each as the pair 0xD83D 0xDE42
+    let paired = JsString::from_char_code4(0xD83D, 0xDE42, 0xD83D, 0xDE42);
+    assert!(check_well_formed(&paired).is_ok());
+    assert!(check_well_formed(&JsString::from("")).is_ok());
+}